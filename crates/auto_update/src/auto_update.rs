@@ -15,7 +15,10 @@ use markdown_preview::markdown_preview_view::{MarkdownPreviewMode, MarkdownPrevi
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde_derive::Serialize;
-use smol::{fs, io::AsyncReadExt};
+use smol::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
 
 use settings::{Settings, SettingsSources, SettingsStore};
 use smol::{fs::File, process::Command};
@@ -86,14 +89,14 @@ struct JsonRelease {
     url: String,
 }
 
-struct AutoUpdateSetting(bool);
+pub struct AutoUpdateSetting(pub bool);
 
 /// Whether or not to automatically check for updates.
 ///
 /// Default: true
 #[derive(Clone, Copy, Default, JsonSchema, Deserialize, Serialize)]
 #[serde(transparent)]
-struct AutoUpdateSettingContent(bool);
+pub struct AutoUpdateSettingContent(pub bool);
 
 impl Settings for AutoUpdateSetting {
     const KEY: Option<&'static str> = Some("auto_update");
@@ -110,6 +113,42 @@ impl Settings for AutoUpdateSetting {
     }
 }
 
+/// The release channel to download updates from, instead of the one Zed was built for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, JsonSchema, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    Stable,
+    Preview,
+    Nightly,
+}
+
+impl From<UpdateChannel> for ReleaseChannel {
+    fn from(channel: UpdateChannel) -> Self {
+        match channel {
+            UpdateChannel::Stable => ReleaseChannel::Stable,
+            UpdateChannel::Preview => ReleaseChannel::Preview,
+            UpdateChannel::Nightly => ReleaseChannel::Nightly,
+        }
+    }
+}
+
+pub struct AutoUpdateChannelSetting(pub Option<UpdateChannel>);
+
+impl Settings for AutoUpdateChannelSetting {
+    const KEY: Option<&'static str> = Some("auto_update_channel");
+
+    type FileContent = Option<UpdateChannel>;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        let channel = [sources.release_channel, sources.user]
+            .into_iter()
+            .find_map(|value| value.copied().flatten())
+            .or(*sources.default);
+
+        Ok(Self(channel))
+    }
+}
+
 #[derive(Default)]
 struct GlobalAutoUpdate(Option<Model<AutoUpdater>>);
 
@@ -123,6 +162,7 @@ struct ReleaseNotesBody {
 
 pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut AppContext) {
     AutoUpdateSetting::register(cx);
+    AutoUpdateChannelSetting::register(cx);
 
     cx.observe_new_views(|workspace: &mut Workspace, _cx| {
         workspace.register_action(|_, action: &Check, cx| check(action, cx));
@@ -384,10 +424,14 @@ impl AutoUpdater {
         cx.notify();
     }
 
+    /// Downloads the latest remote server binary for the given platform, unless it is
+    /// already present locally. `on_progress` is invoked with the fraction of the
+    /// binary that has been downloaded so far.
     pub async fn get_latest_remote_server_release(
         os: &str,
         arch: &str,
         mut release_channel: ReleaseChannel,
+        on_progress: &dyn Fn(f32, &mut AsyncAppContext),
         cx: &mut AsyncAppContext,
     ) -> Result<PathBuf> {
         let this = cx.update(|cx| {
@@ -420,7 +464,11 @@ impl AutoUpdater {
         let client = this.read_with(cx, |this, _| this.http_client.clone())?;
         if smol::fs::metadata(&version_path).await.is_err() {
             log::info!("downloading zed-remote-server {os} {arch}");
-            download_remote_server_binary(&version_path, release, client, cx).await?;
+            // Download into a separate file, so that a cancelled download isn't mistaken
+            // for a complete binary on the next connection attempt.
+            let partial_path = version_path.with_extension("gz.partial");
+            download_remote_server_binary(&partial_path, release, client, on_progress, cx).await?;
+            smol::fs::rename(&partial_path, &version_path).await?;
         }
 
         Ok(version_path)
@@ -472,17 +520,23 @@ impl AutoUpdater {
         let (client, current_version, release_channel) = this.update(&mut cx, |this, cx| {
             this.status = AutoUpdateStatus::Checking;
             cx.notify();
+            let release_channel = AutoUpdateChannelSetting::get_global(cx)
+                .0
+                .map(ReleaseChannel::from)
+                .or_else(|| ReleaseChannel::try_global(cx));
             (
                 this.http_client.clone(),
                 this.current_version,
-                ReleaseChannel::try_global(cx),
+                release_channel,
             )
         })?;
 
         let release =
             Self::get_latest_release(&this, "zed", OS, ARCH, release_channel, &mut cx).await?;
 
-        let should_download = match *RELEASE_CHANNEL {
+        let should_download = match release_channel.unwrap_or(*RELEASE_CHANNEL) {
+            // Any release of another channel replaces the running one.
+            channel if channel != *RELEASE_CHANNEL => true,
             ReleaseChannel::Nightly => cx
                 .update(|cx| AppCommitSha::try_global(cx).map(|sha| release.version != sha.0))
                 .ok()
@@ -572,7 +626,8 @@ async fn download_remote_server_binary(
     target_path: &PathBuf,
     release: JsonRelease,
     client: Arc<HttpClientWithUrl>,
-    cx: &AsyncAppContext,
+    on_progress: &dyn Fn(f32, &mut AsyncAppContext),
+    cx: &mut AsyncAppContext,
 ) -> Result<()> {
     let mut target_file = File::create(&target_path).await?;
     let (installation_id, release_channel, telemetry) = cx.update(|cx| {
@@ -590,7 +645,26 @@ async fn download_remote_server_binary(
     })?);
 
     let mut response = client.get(&release.url, request_body, true).await?;
-    smol::io::copy(response.body_mut(), &mut target_file).await?;
+    let total_len = response
+        .headers()
+        .get(isahc::http::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse::<u64>().ok());
+
+    let body = response.body_mut();
+    let mut buffer = vec![0; 64 * 1024];
+    let mut downloaded_len = 0;
+    loop {
+        let len = body.read(&mut buffer).await?;
+        if len == 0 {
+            break;
+        }
+        target_file.write_all(&buffer[..len]).await?;
+        downloaded_len += len as u64;
+        if let Some(total_len) = total_len.filter(|len| *len > 0) {
+            on_progress(downloaded_len as f32 / total_len as f32, cx);
+        }
+    }
+    target_file.flush().await?;
     Ok(())
 }
 
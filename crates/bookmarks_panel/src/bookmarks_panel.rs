@@ -0,0 +1,534 @@
+mod bookmarks_panel_settings;
+mod persistence;
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Context as _;
+use bookmarks_panel_settings::{BookmarksPanelDockPosition, BookmarksPanelSettings};
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{scroll::Autoscroll, Editor};
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, KeyContext, Model, Pixels, Render, Subscription, Task, UniformListScrollHandle,
+    View, ViewContext, WeakView,
+};
+use language::{Buffer, BufferId, Point, ToPoint as _};
+use menu::{Confirm, SelectNext, SelectPrev};
+use persistence::{SerializedBookmark, BOOKMARKS_DB};
+use project::{bookmark_store::BookmarkStore, File, Project, ProjectPath};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{prelude::*, ListItem, ListItemSpacing, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace, WorkspaceId,
+};
+
+actions!(bookmarks_panel, [ToggleFocus, RemoveSelectedBookmark]);
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+/// A panel listing the bookmarks of all buffers in the project.
+pub struct BookmarksPanel {
+    workspace: WeakView<Workspace>,
+    workspace_id: Option<WorkspaceId>,
+    project: Model<Project>,
+    bookmark_store: Model<BookmarkStore>,
+    fs: Arc<dyn project::Fs>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    entries: Vec<BookmarkEntry>,
+    selected_ix: Option<usize>,
+    pending_serialization: Task<Option<()>>,
+    /// Serializes the bookmarks again when their buffers are saved, as their rows may
+    /// have changed.
+    buffer_subscriptions: HashMap<BufferId, Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Clone)]
+struct BookmarkEntry {
+    buffer: Model<Buffer>,
+    position: language::Anchor,
+    path: SharedString,
+    row: u32,
+    label: Option<SharedString>,
+    /// The text of the bookmarked row, shown when the bookmark has no label.
+    text: SharedString,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+}
+
+pub fn init(cx: &mut AppContext) {
+    BookmarksPanelSettings::register(cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+impl BookmarksPanel {
+    pub async fn load(
+        workspace: WeakView<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<View<Self>> {
+        let serialized_panel = cx
+            .background_executor()
+            .spawn(async move { KEY_VALUE_STORE.read_kvp(BOOKMARKS_PANEL_KEY) })
+            .await
+            .context("loading bookmarks panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedBookmarksPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        let workspace_id = workspace.update(&mut cx, |workspace, _| workspace.database_id())?;
+        let bookmarks = match workspace_id {
+            Some(workspace_id) => cx
+                .background_executor()
+                .spawn(async move { BOOKMARKS_DB.get_bookmarks(workspace_id) })
+                .await
+                .context("loading bookmarks")
+                .log_err()
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| Self::new(workspace, cx));
+            panel.update(cx, |panel, cx| {
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                }
+                panel.restore_bookmarks(bookmarks, cx);
+            });
+            panel
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let bookmark_store = project.read(cx).bookmark_store().clone();
+        let focus_handle = cx.focus_handle();
+
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            workspace_id: workspace.database_id(),
+            project,
+            bookmark_store: bookmark_store.clone(),
+            fs: workspace.app_state().fs.clone(),
+            width: None,
+            focus_handle,
+            scroll_handle: UniformListScrollHandle::new(),
+            entries: Vec::new(),
+            selected_ix: None,
+            pending_serialization: Task::ready(None),
+            buffer_subscriptions: HashMap::default(),
+            _subscriptions: vec![cx.observe(&bookmark_store, |this, _, cx| {
+                this.bookmarks_changed(cx);
+            })],
+        };
+        this.update_entries(cx);
+        this
+    }
+
+    /// Opens the buffers of the bookmarks loaded from the database and bookmarks them
+    /// again.
+    fn restore_bookmarks(
+        &mut self,
+        bookmarks: Vec<SerializedBookmark>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let mut bookmarks_by_path = HashMap::<PathBuf, Vec<(u32, Option<String>)>>::default();
+        for (path, row, label) in bookmarks {
+            bookmarks_by_path
+                .entry(path)
+                .or_default()
+                .push((row, label));
+        }
+
+        let open_buffers = bookmarks_by_path
+            .into_iter()
+            .filter_map(|(path, bookmarks)| {
+                let (worktree, relative_path) = self.project.read(cx).find_worktree(&path, cx)?;
+                let project_path = ProjectPath {
+                    worktree_id: worktree.read(cx).id(),
+                    path: relative_path.into(),
+                };
+                let open_buffer = self
+                    .project
+                    .update(cx, |project, cx| project.open_buffer(project_path, cx));
+                Some(async move { (open_buffer.await, bookmarks) })
+            })
+            .collect::<Vec<_>>();
+        if open_buffers.is_empty() {
+            return;
+        }
+
+        let bookmark_store = self.bookmark_store.clone();
+        cx.spawn(|_, mut cx| async move {
+            let buffers = futures::future::join_all(open_buffers).await;
+            bookmark_store.update(&mut cx, |bookmark_store, cx| {
+                for (buffer, bookmarks) in buffers {
+                    // Files may have been removed since the bookmarks were saved.
+                    let Some(buffer) = buffer.log_err() else {
+                        continue;
+                    };
+                    for (row, label) in bookmarks {
+                        bookmark_store.insert(buffer.clone(), row, label.map(Into::into), cx);
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn bookmarks_changed(&mut self, cx: &mut ViewContext<Self>) {
+        let bookmarked_buffers = self
+            .bookmark_store
+            .read(cx)
+            .buffers()
+            .map(|buffer_bookmarks| buffer_bookmarks.buffer.clone())
+            .collect::<Vec<_>>();
+        self.buffer_subscriptions.retain(|buffer_id, _| {
+            bookmarked_buffers
+                .iter()
+                .any(|buffer| buffer.read(cx).remote_id() == *buffer_id)
+        });
+        for buffer in bookmarked_buffers {
+            let buffer_id = buffer.read(cx).remote_id();
+            self.buffer_subscriptions
+                .entry(buffer_id)
+                .or_insert_with(|| {
+                    cx.subscribe(&buffer, |this, _, event, cx| match event {
+                        language::Event::Saved => this.serialize_bookmarks(cx),
+                        language::Event::Edited => this.update_entries(cx),
+                        _ => {}
+                    })
+                });
+        }
+
+        self.update_entries(cx);
+        self.serialize_bookmarks(cx);
+    }
+
+    fn update_entries(&mut self, cx: &mut ViewContext<Self>) {
+        let bookmark_store = self.bookmark_store.read(cx);
+        let mut entries = Vec::new();
+        for buffer_bookmarks in bookmark_store.buffers() {
+            let buffer = buffer_bookmarks.buffer.read(cx);
+            let path: SharedString = match buffer.file() {
+                Some(file) => file.full_path(cx).to_string_lossy().to_string().into(),
+                None => "untitled".into(),
+            };
+            for bookmark in &buffer_bookmarks.bookmarks {
+                let row = bookmark.position.to_point(buffer).row;
+                let text = buffer
+                    .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+                    .collect::<String>();
+                entries.push(BookmarkEntry {
+                    buffer: buffer_bookmarks.buffer.clone(),
+                    position: bookmark.position,
+                    path: path.clone(),
+                    row,
+                    label: bookmark.label.clone(),
+                    text: text.trim().to_string().into(),
+                });
+            }
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path).then(a.row.cmp(&b.row)));
+
+        self.selected_ix = self
+            .selected_ix
+            .filter(|_| !entries.is_empty())
+            .map(|ix| ix.min(entries.len() - 1));
+        self.entries = entries;
+        cx.notify();
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        BOOKMARKS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedBookmarksPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn serialize_bookmarks(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+
+        let mut bookmarks = Vec::new();
+        for buffer_bookmarks in self.bookmark_store.read(cx).buffers() {
+            let buffer = buffer_bookmarks.buffer.read(cx);
+            // Bookmarks of buffers without a file can't be restored.
+            let Some(abs_path) = File::from_dyn(buffer.file())
+                .and_then(|file| file.worktree.read(cx).absolutize(&file.path).ok())
+            else {
+                continue;
+            };
+            for bookmark in &buffer_bookmarks.bookmarks {
+                bookmarks.push((
+                    abs_path.clone(),
+                    bookmark.position.to_point(buffer).row,
+                    bookmark.label.as_ref().map(ToString::to_string),
+                ));
+            }
+        }
+
+        cx.background_executor()
+            .spawn(
+                BOOKMARKS_DB
+                    .save_bookmarks(workspace_id, bookmarks)
+                    .log_err(),
+            )
+            .detach();
+    }
+
+    fn dispatch_context(&self, _: &ViewContext<Self>) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("BookmarksPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_ix
+            .map_or(0, |ix| (ix + 1) % self.entries.len());
+        self.select(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_ix.map_or(self.entries.len() - 1, |ix| {
+            ix.checked_sub(1).unwrap_or(self.entries.len() - 1)
+        });
+        self.select(ix, cx);
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        self.selected_ix = Some(ix);
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.open_bookmark(ix, cx);
+        }
+    }
+
+    fn remove_selected_bookmark(&mut self, _: &RemoveSelectedBookmark, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.remove_bookmark(ix, cx);
+        }
+    }
+
+    fn open_bookmark(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix).cloned() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                let editor = workspace.open_project_item::<Editor>(
+                    pane,
+                    entry.buffer.clone(),
+                    true,
+                    true,
+                    cx,
+                );
+                editor.update(cx, |editor, cx| {
+                    let position = entry.position.to_point(entry.buffer.read(cx));
+                    editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                        s.select_ranges([position..position])
+                    });
+                });
+            })
+            .ok();
+    }
+
+    fn remove_bookmark(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let buffer_id = entry.buffer.read(cx).remote_id();
+        let position = entry.position;
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.remove(buffer_id, position, cx);
+        });
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let title = entry.label.clone().unwrap_or_else(|| entry.text.clone());
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .selected(self.selected_ix == Some(ix))
+            .start_slot(
+                Icon::new(IconName::Bookmark)
+                    .size(IconSize::Small)
+                    .color(Color::Accent),
+            )
+            .child(
+                v_flex().child(Label::new(title).single_line()).child(
+                    Label::new(format!("{}:{}", entry.path, entry.row + 1))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .single_line(),
+                ),
+            )
+            .end_hover_slot(
+                IconButton::new(("remove-bookmark", ix), IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Remove Bookmark", cx))
+                    .on_click(cx.listener(move |this, _, cx| this.remove_bookmark(ix, cx))),
+            )
+            .on_click(cx.listener(move |this, _, cx| {
+                this.selected_ix = Some(ix);
+                this.open_bookmark(ix, cx);
+            }))
+    }
+}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "Bookmarks Panel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        match BookmarksPanelSettings::get_global(cx).dock {
+            BookmarksPanelDockPosition::Left => DockPosition::Left,
+            BookmarksPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<BookmarksPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => BookmarksPanelDockPosition::Left,
+                    DockPosition::Right => BookmarksPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| BookmarksPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+impl FocusableView for BookmarksPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let bookmarks_panel = v_flex()
+            .id("bookmarks-panel")
+            .size_full()
+            .key_context(self.dispatch_context(cx))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::remove_selected_bookmark))
+            .track_focus(&self.focus_handle);
+
+        if self.entries.is_empty() {
+            bookmarks_panel.child(
+                v_flex()
+                    .justify_center()
+                    .size_full()
+                    .child(h_flex().justify_center().child(Label::new("No bookmarks")))
+                    .child(
+                        h_flex()
+                            .pt(Spacing::Small.rems(cx))
+                            .justify_center()
+                            .child({
+                                let keystroke =
+                                    cx.keystroke_text_for(
+                                        &editor::actions::ToggleBookmark::default(),
+                                    );
+                                Label::new(format!("Bookmark a line with {keystroke}"))
+                                    .color(Color::Muted)
+                            }),
+                    ),
+            )
+        } else {
+            bookmarks_panel.child(
+                uniform_list(
+                    cx.view().clone(),
+                    "bookmarks",
+                    self.entries.len(),
+                    |bookmarks_panel, range, cx| {
+                        range
+                            .map(|ix| bookmarks_panel.render_entry(ix, cx))
+                            .collect::<Vec<_>>()
+                    },
+                )
+                .size_full()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+        }
+    }
+}
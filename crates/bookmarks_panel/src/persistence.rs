@@ -0,0 +1,108 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+/// A bookmark as stored in the database: the absolute path of the file, its row and
+/// its label.
+pub(crate) type SerializedBookmark = (PathBuf, u32, Option<String>);
+
+define_connection! {
+    // Current schema shape using pseudo-rust syntax:
+    // bookmarks(
+    //   workspace_id: usize,
+    //   path: PathBuf,
+    //   buffer_row: u32,
+    //   label: Option<String>,
+    // )
+    pub static ref BOOKMARKS_DB: BookmarksDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path BLOB NOT NULL,
+                buffer_row INTEGER NOT NULL,
+                label TEXT,
+                PRIMARY KEY(workspace_id, path, buffer_row),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl BookmarksDb {
+    query! {
+        pub fn get_bookmarks(workspace_id: WorkspaceId) -> Result<Vec<SerializedBookmark>> {
+            SELECT path, buffer_row, label
+            FROM bookmarks
+            WHERE workspace_id = ?
+            ORDER BY path, buffer_row
+        }
+    }
+
+    /// Replaces the bookmarks of the given workspace.
+    pub async fn save_bookmarks(
+        &self,
+        workspace_id: WorkspaceId,
+        bookmarks: Vec<SerializedBookmark>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("save_bookmarks", || {
+                conn.exec_bound(sql!(DELETE FROM bookmarks WHERE workspace_id = ?))?(workspace_id)?;
+                for (path, row, label) in bookmarks {
+                    conn.exec_bound(sql!(
+                        INSERT OR REPLACE INTO bookmarks(workspace_id, path, buffer_row, label)
+                        VALUES (?, ?, ?, ?)
+                    ))?((workspace_id, path, row, label))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_get_bookmarks() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        assert_eq!(BOOKMARKS_DB.get_bookmarks(workspace_id).unwrap(), vec![]);
+
+        let bookmarks = vec![
+            (PathBuf::from("/root/b.rs"), 3, None),
+            (PathBuf::from("/root/a.rs"), 10, Some("label".to_string())),
+            (PathBuf::from("/root/a.rs"), 2, None),
+        ];
+        BOOKMARKS_DB
+            .save_bookmarks(workspace_id, bookmarks)
+            .await
+            .unwrap();
+        assert_eq!(
+            BOOKMARKS_DB.get_bookmarks(workspace_id).unwrap(),
+            vec![
+                (PathBuf::from("/root/a.rs"), 2, None),
+                (PathBuf::from("/root/a.rs"), 10, Some("label".to_string())),
+                (PathBuf::from("/root/b.rs"), 3, None),
+            ]
+        );
+
+        // Saving replaces the previous bookmarks.
+        BOOKMARKS_DB
+            .save_bookmarks(workspace_id, vec![(PathBuf::from("/root/c.rs"), 0, None)])
+            .await
+            .unwrap();
+        assert_eq!(
+            BOOKMARKS_DB.get_bookmarks(workspace_id).unwrap(),
+            vec![(PathBuf::from("/root/c.rs"), 0, None)]
+        );
+
+        BOOKMARKS_DB
+            .save_bookmarks(workspace_id, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(BOOKMARKS_DB.get_bookmarks(workspace_id).unwrap(), vec![]);
+    }
+}
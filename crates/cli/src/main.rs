@@ -51,6 +51,11 @@ struct Args {
     /// Run zed in dev-server mode
     #[arg(long)]
     dev_server_token: Option<String>,
+    /// Open the given paths on a remote host over SSH.
+    ///
+    /// The paths are interpreted on the remote host and must be absolute.
+    #[arg(long, value_name = "USER@HOST[:PORT]")]
+    ssh: Option<String>,
 }
 
 fn parse_path_with_position(argument_str: &str) -> Result<String, std::io::Error> {
@@ -79,6 +84,17 @@ fn parse_path_with_position(argument_str: &str) -> Result<String, std::io::Error
     Ok(canonicalized.to_string(|path| path.display().to_string()))
 }
 
+fn ssh_url(target: &str, path: &str) -> Result<String> {
+    if !path.starts_with('/') {
+        anyhow::bail!("remote paths must be absolute: {path}");
+    }
+    let path = path
+        .replace('%', "%25")
+        .replace('#', "%23")
+        .replace('?', "%3F");
+    Ok(format!("ssh://{target}{path}"))
+}
+
 fn main() -> Result<()> {
     // Exit flatpak sandbox if needed
     #[cfg(target_os = "linux")]
@@ -124,16 +140,25 @@ fn main() -> Result<()> {
     let exit_status = Arc::new(Mutex::new(None));
     let mut paths = vec![];
     let mut urls = vec![];
-    for path in args.paths_with_position.iter() {
-        if path.starts_with("zed://")
-            || path.starts_with("http://")
-            || path.starts_with("https://")
-            || path.starts_with("file://")
-            || path.starts_with("ssh://")
-        {
-            urls.push(path.to_string());
-        } else {
-            paths.push(parse_path_with_position(path)?)
+    if let Some(target) = args.ssh.as_deref() {
+        if args.paths_with_position.is_empty() {
+            anyhow::bail!("--ssh requires at least one path to open");
+        }
+        for path in args.paths_with_position.iter() {
+            urls.push(ssh_url(target, path)?);
+        }
+    } else {
+        for path in args.paths_with_position.iter() {
+            if path.starts_with("zed://")
+                || path.starts_with("http://")
+                || path.starts_with("https://")
+                || path.starts_with("file://")
+                || path.starts_with("ssh://")
+            {
+                urls.push(path.to_string());
+            } else {
+                paths.push(parse_path_with_position(path)?)
+            }
         }
     }
 
@@ -505,6 +505,12 @@ impl Server {
             .add_request_handler(user_handler(
                 forward_mutating_project_request::<proto::GetCodeActions>,
             ))
+            .add_request_handler(user_handler(
+                forward_read_only_project_request::<proto::GetCodeLens>,
+            ))
+            .add_request_handler(user_handler(
+                forward_read_only_project_request::<proto::GetSemanticTokens>,
+            ))
             .add_request_handler(user_handler(
                 forward_mutating_project_request::<proto::ApplyCodeAction>,
             ))
@@ -544,6 +550,9 @@ impl Server {
             .add_request_handler(user_handler(
                 forward_mutating_project_request::<proto::BlameBuffer>,
             ))
+            .add_request_handler(user_handler(
+                forward_read_only_project_request::<proto::GetBufferGitInfo>,
+            ))
             .add_request_handler(user_handler(
                 forward_mutating_project_request::<proto::MultiLspQuery>,
             ))
@@ -1,5 +1,4 @@
 pub mod items;
-mod project_diagnostics_settings;
 mod toolbar_controls;
 
 #[cfg(test)]
@@ -30,14 +29,14 @@ use language::{
     Bias, Buffer, Diagnostic, DiagnosticEntry, DiagnosticSeverity, Point, Selection, SelectionGoal,
 };
 use lsp::LanguageServerId;
-use project::{DiagnosticSummary, Project, ProjectPath};
-use project_diagnostics_settings::ProjectDiagnosticsSettings;
+use project::{project_settings::ProjectSettings, DiagnosticSummary, Project, ProjectPath};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
     cmp::Ordering,
     mem,
     ops::Range,
+    time::Duration,
 };
 use theme::ActiveTheme;
 pub use toolbar_controls::ToolbarControls;
@@ -51,7 +50,6 @@ use workspace::{
 actions!(diagnostics, [Deploy, ToggleWarnings]);
 
 pub fn init(cx: &mut AppContext) {
-    ProjectDiagnosticsSettings::register(cx);
     cx.observe_new_views(ProjectDiagnosticsEditor::register)
         .detach();
     if !cx.has_flag::<feature_flags::GroupedDiagnostics>() {
@@ -72,6 +70,7 @@ struct ProjectDiagnosticsEditor {
     context: u32,
     update_paths_tx: UnboundedSender<(ProjectPath, Option<LanguageServerId>)>,
     _update_excerpts_task: Task<Result<()>>,
+    debounced_update_task: Option<Task<()>>,
     _subscription: Subscription,
 }
 
@@ -149,7 +148,7 @@ impl ProjectDiagnosticsEditor {
                         log::debug!("diagnostics updated for server {language_server_id}, path {path:?}. recording change");
                     } else {
                         log::debug!("diagnostics updated for server {language_server_id}, path {path:?}. updating excerpts");
-                        this.enqueue_update_stale_excerpts(Some(*language_server_id));
+                        this.enqueue_debounced_update(*language_server_id, cx);
                     }
                 }
                 _ => {}
@@ -200,7 +199,7 @@ impl ProjectDiagnosticsEditor {
             editor,
             path_states: Default::default(),
             paths_to_update: Default::default(),
-            include_warnings: ProjectDiagnosticsSettings::get_global(cx).include_warnings,
+            include_warnings: ProjectSettings::get_global(cx).diagnostics.include_warnings,
             update_paths_tx: update_excerpts_tx,
             _update_excerpts_task: cx.spawn(move |this, mut cx| async move {
                 while let Some((path, language_server_id)) = update_excerpts_rx.next().await {
@@ -216,6 +215,7 @@ impl ProjectDiagnosticsEditor {
                 }
                 anyhow::Ok(())
             }),
+            debounced_update_task: None,
             _subscription: project_event_subscription,
         };
         this.enqueue_update_all_excerpts(cx);
@@ -284,6 +284,30 @@ impl ProjectDiagnosticsEditor {
     /// to have changed. If a language server id is passed, then only the excerpts for
     /// that language server's diagnostics will be updated. Otherwise, all stale excerpts
     /// will be refreshed.
+    /// Updates the excerpts of the stale paths once diagnostics have stopped changing for
+    /// the configured debounce, updating those of all language servers at once.
+    fn enqueue_debounced_update(
+        &mut self,
+        language_server_id: LanguageServerId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let debounce_ms = ProjectSettings::get_global(cx)
+            .diagnostics
+            .update_debounce_ms;
+        if debounce_ms == 0 {
+            self.enqueue_update_stale_excerpts(Some(language_server_id));
+            return;
+        }
+
+        self.debounced_update_task = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor()
+                .timer(Duration::from_millis(debounce_ms))
+                .await;
+            this.update(&mut cx, |this, _| this.enqueue_update_stale_excerpts(None))
+                .log_err();
+        }));
+    }
+
     fn enqueue_update_stale_excerpts(&mut self, language_server_id: Option<LanguageServerId>) {
         for (path, server_id) in &self.paths_to_update {
             if language_server_id.map_or(true, |id| id == *server_id) {
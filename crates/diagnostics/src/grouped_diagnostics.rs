@@ -24,7 +24,7 @@ use language::{
 };
 use lsp::LanguageServerId;
 use multi_buffer::{build_excerpt_ranges, ExpandExcerptDirection, MultiBufferRow};
-use project::{DiagnosticSummary, Project, ProjectPath};
+use project::{project_settings::ProjectSettings, DiagnosticSummary, Project, ProjectPath};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
@@ -43,7 +43,6 @@ use workspace::{
     ItemNavHistory, ToolbarItemLocation, Workspace,
 };
 
-use crate::project_diagnostics_settings::ProjectDiagnosticsSettings;
 actions!(grouped_diagnostics, [Deploy, ToggleWarnings]);
 
 pub fn init(cx: &mut AppContext) {
@@ -213,7 +212,7 @@ impl GroupedDiagnosticsEditor {
             editor,
             path_states: Vec::new(),
             paths_to_update: BTreeSet::new(),
-            include_warnings: ProjectDiagnosticsSettings::get_global(cx).include_warnings,
+            include_warnings: ProjectSettings::get_global(cx).diagnostics.include_warnings,
             update_paths_tx: update_excerpts_tx,
             _update_excerpts_task: cx.spawn(move |this, mut cx| async move {
                 while let Some((path, language_server_id)) = update_excerpts_rx.next().await {
@@ -81,6 +81,11 @@ pub struct FoldAt {
     pub buffer_row: MultiBufferRow,
 }
 
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct FoldAtLevel {
+    pub level: u32,
+}
+
 #[derive(PartialEq, Clone, Deserialize, Default)]
 pub struct UnfoldAt {
     pub buffer_row: MultiBufferRow,
@@ -132,6 +137,68 @@ pub struct ShowCompletions {
     pub(super) trigger: Option<String>,
 }
 
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct ReplayMacro {
+    /// How many times to replay the macro.
+    #[serde(default)]
+    pub times: usize,
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct ToggleBookmark {
+    /// The label of the bookmark, shown in the bookmarks panel.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// The diagnostics that [`GoToDiagnostic`] and [`GoToPrevDiagnostic`] go to.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverityFilter {
+    /// Only errors.
+    Error,
+    /// Errors and warnings.
+    #[default]
+    Warning,
+}
+
+impl DiagnosticSeverityFilter {
+    pub(crate) fn includes(self, severity: DiagnosticSeverity) -> bool {
+        match self {
+            Self::Error => severity == DiagnosticSeverity::ERROR,
+            Self::Warning => severity <= DiagnosticSeverity::WARNING,
+        }
+    }
+
+    /// Returns how many of the summarized diagnostics this filter includes.
+    pub(crate) fn count(self, summary: &project::DiagnosticSummary) -> usize {
+        match self {
+            Self::Error => summary.error_count,
+            Self::Warning => summary.error_count + summary.warning_count,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct GoToDiagnostic {
+    #[serde(default)]
+    pub severity: DiagnosticSeverityFilter,
+    /// Whether to go on to the diagnostics of the next file with some, in path order, after
+    /// the last diagnostic of the current one.
+    #[serde(default)]
+    pub project_wide: bool,
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct GoToPrevDiagnostic {
+    #[serde(default)]
+    pub severity: DiagnosticSeverityFilter,
+    /// Whether to go on to the diagnostics of the previous file with some, in path order, before
+    /// the first diagnostic of the current one.
+    #[serde(default)]
+    pub project_wide: bool,
+}
+
 impl_actions!(
     editor,
     [
@@ -141,12 +208,16 @@ impl_actions!(
         ExpandExcerptsUp,
         ExpandExcerptsDown,
         FoldAt,
+        FoldAtLevel,
+        GoToDiagnostic,
+        GoToPrevDiagnostic,
         MoveDownByLines,
         MovePageDown,
         MovePageUp,
         MoveToBeginningOfLine,
         MoveToEndOfLine,
         MoveUpByLines,
+        ReplayMacro,
         SelectDownByLines,
         SelectNext,
         SelectPrevious,
@@ -154,6 +225,7 @@ impl_actions!(
         SelectToEndOfLine,
         SelectUpByLines,
         ShowCompletions,
+        ToggleBookmark,
         ToggleCodeActions,
         ToggleComments,
         UnfoldAt,
@@ -168,6 +240,7 @@ gpui::actions!(
         AcceptPartialInlineCompletion,
         AddSelectionAbove,
         AddSelectionBelow,
+        AlignSelections,
         Backspace,
         Cancel,
         CancelLanguageServerWork,
@@ -200,21 +273,27 @@ gpui::actions!(
         DeleteToPreviousSubwordStart,
         DeleteToPreviousWordStart,
         DisplayCursorNames,
+        DuplicateLineAndComment,
         DuplicateLineDown,
         DuplicateLineUp,
+        DuplicateSelection,
         ExpandAllHunkDiffs,
         ExpandMacroRecursively,
+        ExportAsHtml,
+        ExportAsRtf,
         FindAllReferences,
         Fold,
+        FoldAllFunctions,
+        FoldRecursive,
         FoldSelectedRanges,
         Format,
         GoToDefinition,
         GoToDefinitionSplit,
-        GoToDiagnostic,
         GoToHunk,
         GoToImplementation,
         GoToImplementationSplit,
-        GoToPrevDiagnostic,
+        GoToNextBookmark,
+        GoToPrevBookmark,
         GoToPrevHunk,
         GoToTypeDefinition,
         GoToTypeDefinitionSplit,
@@ -223,6 +302,7 @@ gpui::actions!(
         Hover,
         Indent,
         JoinLines,
+        JumpToChar,
         LineDown,
         LineUp,
         MoveDown,
@@ -253,10 +333,12 @@ gpui::actions!(
         PageDown,
         PageUp,
         Paste,
+        PasteWithoutFormatting,
         PreviousInlineCompletion,
         Redo,
         RedoSelection,
         Rename,
+        ReplaceInSelection,
         RestartLanguageServer,
         RevealInFileManager,
         ReverseLines,
@@ -268,6 +350,9 @@ gpui::actions!(
         SelectAllMatches,
         SelectDown,
         SelectLargerSyntaxNode,
+        SelectEnclosingArgument,
+        SelectEnclosingComment,
+        SelectEnclosingString,
         SelectEnclosingSymbol,
         SelectLeft,
         SelectLine,
@@ -291,6 +376,9 @@ gpui::actions!(
         SortLinesCaseInsensitive,
         SortLinesCaseSensitive,
         SplitSelectionIntoLines,
+        StageSelectedHunks,
+        StartRecordingMacro,
+        StopRecording,
         Tab,
         TabPrev,
         ToggleAutoSignatureHelp,
@@ -309,6 +397,7 @@ gpui::actions!(
         UnfoldLines,
         UniqueLinesCaseInsensitive,
         UniqueLinesCaseSensitive,
+        UnstageSelectedHunks,
     ]
 );
 
@@ -0,0 +1,210 @@
+use std::{collections::BTreeMap, mem, time::Duration};
+
+use gpui::{div, Model, Task, ViewContext, WeakView};
+use language::{language_settings::language_settings, Buffer, Point};
+use multi_buffer::ToPoint;
+use project::CodeAction;
+use ui::{h_flex, prelude::*};
+
+use crate::{
+    display_map::{BlockContext, BlockDisposition, BlockProperties, BlockStyle, RenderBlock},
+    CustomBlockId, Editor, EditorMode,
+};
+
+pub(crate) const CODE_LENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+pub(crate) struct CodeLenses {
+    /// The blocks showing the lenses, one per line that has lenses.
+    blocks: Vec<CustomBlockId>,
+    update_task: Option<Task<()>>,
+}
+
+impl Editor {
+    /// Fetches the code lenses of the buffers that show them, after a short delay, and
+    /// replaces the shown lenses with them.
+    pub(crate) fn refresh_code_lenses(&mut self, cx: &mut ViewContext<Self>) {
+        if self.mode != EditorMode::Full {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let enabled_buffers = self
+            .buffer
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                language_settings(buffer.language(), buffer.file(), cx).code_lens
+            })
+            .collect::<Vec<_>>();
+        if enabled_buffers.is_empty() {
+            self.code_lenses.update_task = None;
+            self.show_code_lenses(Vec::new(), cx);
+            return;
+        }
+
+        self.code_lenses.update_task = Some(cx.spawn(|editor, mut cx| async move {
+            cx.background_executor()
+                .timer(CODE_LENS_DEBOUNCE_TIMEOUT)
+                .await;
+            let Ok(tasks) = project.update(&mut cx, |project, cx| {
+                enabled_buffers
+                    .into_iter()
+                    .map(|buffer| {
+                        let task = project.code_lens(&buffer, cx);
+                        (buffer, task)
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let mut lenses = Vec::new();
+            for (buffer, task) in tasks {
+                match task.await {
+                    Ok(actions) => {
+                        lenses.extend(actions.into_iter().map(|action| (buffer.clone(), action)))
+                    }
+                    Err(error) => log::error!("failed to fetch code lenses: {error:#}"),
+                }
+            }
+            editor
+                .update(&mut cx, |editor, cx| editor.show_code_lenses(lenses, cx))
+                .ok();
+        }));
+    }
+
+    /// Replaces the shown lenses with the given ones, showing the lenses of each line in a
+    /// block above it.
+    fn show_code_lenses(
+        &mut self,
+        lenses: Vec<(Model<Buffer>, CodeAction)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if lenses.is_empty() && self.code_lenses.blocks.is_empty() {
+            return;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut lenses_by_row = BTreeMap::new();
+        for (buffer, action) in lenses {
+            let buffer_id = buffer.read(cx).remote_id();
+            for (excerpt_id, excerpt_buffer, excerpt_range) in snapshot.excerpts() {
+                if excerpt_buffer.remote_id() != buffer_id {
+                    continue;
+                }
+                let row = action.range.start.to_point(excerpt_buffer).row;
+                let excerpt_start = excerpt_range.context.start.to_point(excerpt_buffer);
+                let excerpt_end = excerpt_range.context.end.to_point(excerpt_buffer);
+                if row < excerpt_start.row || row > excerpt_end.row {
+                    continue;
+                }
+
+                // Line the lenses up with the line's indentation.
+                let indent = excerpt_buffer.indent_size_for_line(row).len;
+                let position = excerpt_buffer.anchor_before(Point::new(row, indent));
+                let Some(position) = snapshot.anchor_in_excerpt(excerpt_id, position) else {
+                    continue;
+                };
+                lenses_by_row
+                    .entry(position.to_point(&snapshot).row)
+                    .or_insert_with(|| (position, Vec::new()))
+                    .1
+                    .push((buffer.clone(), action.clone()));
+            }
+        }
+
+        let old_blocks = mem::take(&mut self.code_lenses.blocks);
+        if !old_blocks.is_empty() {
+            self.remove_blocks(old_blocks.into_iter().collect(), None, cx);
+        }
+        let editor = cx.view().downgrade();
+        self.code_lenses.blocks = self.insert_blocks(
+            lenses_by_row
+                .into_values()
+                .map(|(position, lenses)| BlockProperties {
+                    position,
+                    height: 1,
+                    style: BlockStyle::Flex,
+                    render: render_code_lenses(editor.clone(), lenses),
+                    disposition: BlockDisposition::Above,
+                }),
+            None,
+            cx,
+        );
+    }
+
+    /// Runs the command of a lens, opening the edits it makes.
+    fn run_code_lens(
+        &mut self,
+        buffer: Model<Buffer>,
+        action: CodeAction,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let title = action.lsp_action.title.clone();
+        let apply_code_action = workspace
+            .read(cx)
+            .project()
+            .clone()
+            .update(cx, |project, cx| {
+                project.apply_code_action(buffer, action, true, cx)
+            });
+        let workspace = workspace.downgrade();
+        cx.spawn(|editor, cx| async move {
+            let project_transaction = apply_code_action.await?;
+            Self::open_project_transaction(&editor, workspace, project_transaction, title, cx).await
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+fn render_code_lenses(
+    editor: WeakView<Editor>,
+    lenses: Vec<(Model<Buffer>, CodeAction)>,
+) -> RenderBlock {
+    Box::new(move |cx: &mut BlockContext| {
+        let hover_color = cx.theme().colors().text;
+        let mut children = Vec::new();
+        for (ix, (buffer, action)) in lenses.iter().enumerate() {
+            if ix > 0 {
+                children.push(div().child("|").into_any_element());
+            }
+            let editor = editor.clone();
+            let buffer = buffer.clone();
+            let action = action.clone();
+            children.push(
+                div()
+                    .id(ix)
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(hover_color))
+                    .child(action.lsp_action.title.clone())
+                    .on_click(move |_, cx| {
+                        editor
+                            .update(cx, |editor, cx| {
+                                editor.run_code_lens(buffer.clone(), action.clone(), cx)
+                            })
+                            .ok();
+                    })
+                    .into_any_element(),
+            );
+        }
+
+        h_flex()
+            .id(cx.block_id)
+            .h(cx.line_height)
+            .pl(cx.anchor_x)
+            .gap_1()
+            .font_family(cx.editor_style.text.font_family.clone())
+            .text_size(cx.editor_style.text.font_size)
+            .text_color(cx.theme().status().hint)
+            .children(children)
+            .into_any_element()
+    })
+}
@@ -0,0 +1,561 @@
+use std::{ops::Range, time::Duration};
+
+use collections::{HashMap, HashSet};
+use gpui::{hsla, Hsla, Rgba, Task, ViewContext};
+use language::{language_settings::language_settings, BufferSnapshot};
+use multi_buffer::{Anchor, AnchorRangeExt, MultiBufferSnapshot};
+use text::{BufferId, ToOffset};
+use util::post_inc;
+
+use crate::{
+    display_map::{DisplaySnapshot, Inlay, InlayOffset},
+    Editor, EditorMode, InlayId,
+};
+
+const COLOR_SWATCHES_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+
+/// How far a color function's closing parenthesis is looked for.
+const MAX_COLOR_FUNCTION_LEN: usize = 64;
+
+/// How a color literal is written, to write the picked colors the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorFormat {
+    /// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`.
+    Hex { uppercase: bool },
+    /// `rgb(…)`, `rgba(…)`, `hsl(…)` or `hsla(…)`.
+    Function {
+        kind: ColorFunction,
+        /// Whether the function name ends with an `a`.
+        alpha_suffix: bool,
+        /// Whether the arguments are separated by commas rather than spaces.
+        commas: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorFunction {
+    Rgb,
+    Hsl,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ColorLiteral {
+    pub range: Range<usize>,
+    pub color: Hsla,
+    pub format: ColorFormat,
+}
+
+/// A swatch shown before a color literal.
+pub(crate) struct ColorSwatch {
+    pub inlay_id: InlayId,
+    /// The range of the color literal, which keeps covering the literal when it's rewritten.
+    pub range: Range<Anchor>,
+    pub color: Hsla,
+    pub format: ColorFormat,
+}
+
+/// The color picker opened by clicking a swatch.
+pub(crate) struct ColorPickerState {
+    pub range: Range<Anchor>,
+    pub color: Hsla,
+    pub format: ColorFormat,
+}
+
+#[derive(Default)]
+pub(crate) struct ColorSwatches {
+    swatches: Vec<ColorSwatch>,
+    pub picker: Option<ColorPickerState>,
+    update_task: Option<Task<()>>,
+}
+
+/// Finds the color literals in the given text.
+pub(crate) fn find_color_literals(text: &str) -> Vec<ColorLiteral> {
+    let mut literals = Vec::new();
+    let mut offset = 0;
+    while offset < text.len() {
+        let preceded_by_word_char = text[..offset]
+            .chars()
+            .next_back()
+            .map_or(false, is_word_char);
+        let literal = if preceded_by_word_char {
+            None
+        } else if text[offset..].starts_with('#') {
+            parse_hex_literal(text, offset)
+        } else {
+            parse_function_literal(text, offset)
+        };
+
+        match literal {
+            Some(literal) => {
+                offset = literal.range.end;
+                literals.push(literal);
+            }
+            None => {
+                offset += text[offset..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    literals
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn parse_hex_literal(text: &str, start: usize) -> Option<ColorLiteral> {
+    let digits_len = text[start + 1..]
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(text.len() - start - 1);
+    let end = start + 1 + digits_len;
+    if ![3, 4, 6, 8].contains(&digits_len) || text[end..].starts_with(is_word_char) {
+        return None;
+    }
+
+    let literal = &text[start..end];
+    let color = Rgba::try_from(literal).ok()?;
+    let uppercase = literal.chars().any(|c| c.is_ascii_uppercase())
+        && !literal.chars().any(|c| c.is_ascii_lowercase());
+    Some(ColorLiteral {
+        range: start..end,
+        color: color.into(),
+        format: ColorFormat::Hex { uppercase },
+    })
+}
+
+fn parse_function_literal(text: &str, start: usize) -> Option<ColorLiteral> {
+    let rest = &text[start..];
+    let (kind, alpha_suffix, name_len) = [
+        ("rgba(", ColorFunction::Rgb, true),
+        ("rgb(", ColorFunction::Rgb, false),
+        ("hsla(", ColorFunction::Hsl, true),
+        ("hsl(", ColorFunction::Hsl, false),
+    ]
+    .into_iter()
+    .find_map(|(name, kind, alpha_suffix)| {
+        rest.get(..name.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(name))
+            .map(|_| (kind, alpha_suffix, name.len()))
+    })?;
+
+    let arguments_len = rest[name_len..]
+        .char_indices()
+        .take_while(|(ix, _)| *ix < MAX_COLOR_FUNCTION_LEN)
+        .find(|(_, c)| matches!(c, ')' | '(' | '\n'))
+        .filter(|(_, c)| *c == ')')?
+        .0;
+    let arguments = &rest[name_len..name_len + arguments_len];
+    let end = start + name_len + arguments_len + 1;
+
+    let commas = arguments.contains(',');
+    let (channels, alpha) = if commas {
+        let mut arguments = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+        let alpha = if arguments.len() == 4 {
+            arguments.pop()
+        } else {
+            None
+        };
+        (arguments, alpha)
+    } else {
+        let (channels, alpha) = match arguments.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (arguments, None),
+        };
+        (channels.split_whitespace().collect::<Vec<_>>(), alpha)
+    };
+    let [first, second, third] = channels[..] else {
+        return None;
+    };
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.,
+    };
+
+    let color = match kind {
+        ColorFunction::Rgb => Rgba {
+            r: parse_rgb_channel(first)?,
+            g: parse_rgb_channel(second)?,
+            b: parse_rgb_channel(third)?,
+            a: alpha,
+        }
+        .into(),
+        ColorFunction::Hsl => hsla(
+            parse_hue(first)?,
+            parse_percentage(second)?,
+            parse_percentage(third)?,
+            alpha,
+        ),
+    };
+    Some(ColorLiteral {
+        range: start..end,
+        color,
+        format: ColorFormat::Function {
+            kind,
+            alpha_suffix,
+            commas,
+        },
+    })
+}
+
+fn parse_number(text: &str) -> Option<f32> {
+    text.parse::<f32>().ok().filter(|number| number.is_finite())
+}
+
+fn parse_rgb_channel(text: &str) -> Option<f32> {
+    let value = match text.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? / 100.,
+        None => parse_number(text)? / 255.,
+    };
+    Some(value.clamp(0., 1.))
+}
+
+/// Parses a hue in degrees, returning it as a fraction of a turn.
+fn parse_hue(text: &str) -> Option<f32> {
+    let degrees = parse_number(text.strip_suffix("deg").unwrap_or(text))?;
+    Some((degrees / 360.).rem_euclid(1.))
+}
+
+fn parse_percentage(text: &str) -> Option<f32> {
+    let value = parse_number(text.strip_suffix('%').unwrap_or(text))?;
+    Some((value / 100.).clamp(0., 1.))
+}
+
+fn parse_alpha(text: &str) -> Option<f32> {
+    let value = match text.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? / 100.,
+        None => parse_number(text)?,
+    };
+    Some(value.clamp(0., 1.))
+}
+
+/// Writes the color in the given format.
+pub(crate) fn format_color(color: Hsla, format: ColorFormat) -> String {
+    let rgba = Rgba::from(color);
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    let has_alpha = channel(rgba.a) < u8::MAX;
+
+    match format {
+        ColorFormat::Hex { uppercase } => {
+            let mut hex = format!(
+                "#{:02x}{:02x}{:02x}",
+                channel(rgba.r),
+                channel(rgba.g),
+                channel(rgba.b)
+            );
+            if has_alpha {
+                hex.push_str(&format!("{:02x}", channel(rgba.a)));
+            }
+            if uppercase {
+                hex.make_ascii_uppercase();
+            }
+            hex
+        }
+        ColorFormat::Function {
+            kind,
+            alpha_suffix,
+            commas,
+        } => {
+            let (name, channels) = match kind {
+                ColorFunction::Rgb => (
+                    "rgb",
+                    [
+                        channel(rgba.r).to_string(),
+                        channel(rgba.g).to_string(),
+                        channel(rgba.b).to_string(),
+                    ],
+                ),
+                ColorFunction::Hsl => (
+                    "hsl",
+                    [
+                        format!("{}", (color.h * 360.).round() as u32 % 360),
+                        format!("{}%", (color.s * 100.).round() as u32),
+                        format!("{}%", (color.l * 100.).round() as u32),
+                    ],
+                ),
+            };
+            let alpha = format!("{}", (rgba.a.clamp(0., 1.) * 100.).round() / 100.);
+
+            if commas {
+                let suffix = if alpha_suffix || has_alpha { "a" } else { "" };
+                let mut arguments = channels.join(", ");
+                if alpha_suffix || has_alpha {
+                    arguments.push_str(", ");
+                    arguments.push_str(&alpha);
+                }
+                format!("{name}{suffix}({arguments})")
+            } else {
+                let suffix = if alpha_suffix { "a" } else { "" };
+                let mut arguments = channels.join(" ");
+                if has_alpha {
+                    arguments.push_str(" / ");
+                    arguments.push_str(&alpha);
+                }
+                format!("{name}{suffix}({arguments})")
+            }
+        }
+    }
+}
+
+/// Finds the color literals of the excerpts whose buffers show color swatches, skipping the
+/// ones in comments.
+fn scan_color_literals(
+    snapshot: &MultiBufferSnapshot,
+    enabled_buffers: &HashSet<BufferId>,
+) -> Vec<(Range<Anchor>, Hsla, ColorFormat)> {
+    let mut literals = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        if !enabled_buffers.contains(&buffer.remote_id()) {
+            continue;
+        }
+
+        let excerpt_start = excerpt_range.context.start.to_offset(buffer);
+        let excerpt_end = excerpt_range.context.end.to_offset(buffer);
+        let text = buffer
+            .text_for_range(excerpt_start..excerpt_end)
+            .collect::<String>();
+        for literal in find_color_literals(&text) {
+            let start = excerpt_start + literal.range.start;
+            let end = excerpt_start + literal.range.end;
+            if is_in_comment(buffer, start) {
+                continue;
+            }
+            let (Some(start), Some(end)) = (
+                snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_before(start)),
+                snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_after(end)),
+            ) else {
+                continue;
+            };
+            literals.push((start..end, literal.color, literal.format));
+        }
+    }
+    literals
+}
+
+fn is_in_comment(buffer: &BufferSnapshot, offset: usize) -> bool {
+    buffer
+        .language_scope_at(offset)
+        .map_or(false, |scope| scope.override_name() == Some("comment"))
+}
+
+impl Editor {
+    /// Rescans the buffers for color literals, after a short delay.
+    pub(crate) fn refresh_color_swatches(&mut self, cx: &mut ViewContext<Self>) {
+        if self.mode != EditorMode::Full {
+            return;
+        }
+
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let enabled_buffers = multi_buffer
+            .all_buffers()
+            .into_iter()
+            .filter_map(|buffer| {
+                let buffer = buffer.read(cx);
+                language_settings(buffer.language(), buffer.file(), cx)
+                    .color_swatches
+                    .then(|| buffer.remote_id())
+            })
+            .collect::<HashSet<_>>();
+
+        self.color_swatches.update_task = Some(cx.spawn(|editor, mut cx| async move {
+            cx.background_executor()
+                .timer(COLOR_SWATCHES_DEBOUNCE_TIMEOUT)
+                .await;
+            let literals = cx
+                .background_executor()
+                .spawn(async move { scan_color_literals(&snapshot, &enabled_buffers) })
+                .await;
+            editor
+                .update(&mut cx, |editor, cx| {
+                    editor.set_color_swatches(literals, cx)
+                })
+                .ok();
+        }));
+    }
+
+    /// Replaces the swatches with the ones of the given literals, keeping the swatches that
+    /// didn't change.
+    fn set_color_swatches(
+        &mut self,
+        literals: Vec<(Range<Anchor>, Hsla, ColorFormat)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut old_swatches = self
+            .color_swatches
+            .swatches
+            .drain(..)
+            .map(|swatch| (swatch.range.to_offset(&snapshot), swatch))
+            .collect::<HashMap<_, _>>();
+
+        let mut to_remove = Vec::new();
+        let mut to_insert = Vec::new();
+        for (range, color, format) in literals {
+            let offset_range = range.to_offset(&snapshot);
+            if let Some(swatch) = old_swatches.remove(&offset_range) {
+                if swatch.color == color {
+                    self.color_swatches
+                        .swatches
+                        .push(ColorSwatch { format, ..swatch });
+                    continue;
+                }
+                to_remove.push(swatch.inlay_id);
+            }
+
+            let inlay = Inlay::color_swatch(
+                post_inc(&mut self.next_inlay_id),
+                snapshot.anchor_after(offset_range.start),
+                color,
+            );
+            self.color_swatches.swatches.push(ColorSwatch {
+                inlay_id: inlay.id,
+                range,
+                color,
+                format,
+            });
+            to_insert.push(inlay);
+        }
+
+        to_remove.extend(old_swatches.into_values().map(|swatch| swatch.inlay_id));
+
+        // Close the color picker when its literal is gone.
+        if let Some(picker) = self.color_swatches.picker.as_ref() {
+            let picker_range = picker.range.to_offset(&snapshot);
+            if !self
+                .color_swatches
+                .swatches
+                .iter()
+                .any(|swatch| swatch.range.to_offset(&snapshot) == picker_range)
+            {
+                self.color_swatches.picker = None;
+                cx.notify();
+            }
+        }
+        if !to_remove.is_empty() || !to_insert.is_empty() {
+            self.splice_inlays(to_remove, to_insert, cx);
+        }
+    }
+
+    /// Returns the range of the literal and the color of the swatch at the given offset.
+    pub(crate) fn color_swatch_at(
+        &self,
+        offset: InlayOffset,
+        snapshot: &DisplaySnapshot,
+        cx: &ViewContext<Self>,
+    ) -> Option<(Range<Anchor>, Hsla, ColorFormat)> {
+        let display_map = self.display_map.read(cx);
+        self.color_swatches.swatches.iter().find_map(|swatch| {
+            let inlay = display_map
+                .current_inlays()
+                .find(|inlay| inlay.id == swatch.inlay_id)?;
+            let start = snapshot.anchor_to_inlay_offset(inlay.position);
+            let end = InlayOffset(start.0 + inlay.text.len());
+            (start <= offset && offset < end)
+                .then(|| (swatch.range.clone(), swatch.color, swatch.format))
+        })
+    }
+
+    pub(crate) fn show_color_picker(
+        &mut self,
+        range: Range<Anchor>,
+        color: Hsla,
+        format: ColorFormat,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.color_swatches.picker = Some(ColorPickerState {
+            range,
+            color,
+            format,
+        });
+        cx.notify();
+    }
+
+    pub(crate) fn hide_color_picker(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        if self.color_swatches.picker.take().is_some() {
+            cx.notify();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Rewrites the literal of the open color picker with the given color.
+    pub(crate) fn pick_color(&mut self, color: Hsla, cx: &mut ViewContext<Self>) {
+        let Some(picker) = self.color_swatches.picker.as_mut() else {
+            return;
+        };
+        picker.color = color;
+        let range = picker.range.clone();
+        let text = format_color(color, picker.format);
+        self.edit([(range, text)], cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal_texts(text: &str) -> Vec<&str> {
+        find_color_literals(text)
+            .into_iter()
+            .map(|literal| &text[literal.range])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_color_literals() {
+        assert_eq!(
+            literal_texts("color: #fff; background: #FF000080; border: #1234567;"),
+            ["#fff", "#FF000080"]
+        );
+        assert_eq!(
+            literal_texts("a { color: rgb(255, 0, 0); fill: rgba(0 128 255 / 50%) }"),
+            ["rgb(255, 0, 0)", "rgba(0 128 255 / 50%)"]
+        );
+        assert_eq!(
+            literal_texts("hsl(120deg, 100%, 50%) HSLA(0, 0%, 0%, 0.5)"),
+            ["hsl(120deg, 100%, 50%)", "HSLA(0, 0%, 0%, 0.5)"]
+        );
+        assert_eq!(
+            literal_texts("issue#123 myrgb(1, 2, 3) rgb(1, 2) rgb(1, 2, 3"),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_format_color() {
+        let literal = |text: &str| find_color_literals(text).pop().unwrap();
+
+        let hex = literal("#FF0000");
+        assert_eq!(format_color(hex.color, hex.format), "#FF0000");
+        assert_eq!(
+            format_color(hsla(0., 1., 0.5, 0.5), hex.format),
+            "#FF000080"
+        );
+
+        let rgb = literal("rgb(0, 128, 255)");
+        assert_eq!(format_color(rgb.color, rgb.format), "rgb(0, 128, 255)");
+        assert_eq!(
+            format_color(
+                Hsla {
+                    a: 0.5,
+                    ..rgb.color
+                },
+                rgb.format
+            ),
+            "rgba(0, 128, 255, 0.5)"
+        );
+
+        let hsl = literal("hsl(120 100% 50%)");
+        assert_eq!(format_color(hsl.color, hsl.format), "hsl(120 100% 50%)");
+        assert_eq!(
+            format_color(
+                Hsla {
+                    a: 0.25,
+                    ..hsl.color
+                },
+                hsl.format
+            ),
+            "hsl(120 100% 50% / 0.25)"
+        );
+    }
+}
@@ -43,7 +43,7 @@ pub(crate) use inlay_map::Inlay;
 use inlay_map::{InlayMap, InlaySnapshot};
 pub use inlay_map::{InlayOffset, InlayPoint};
 use language::{
-    language_settings::language_settings, ChunkRenderer, OffsetUtf16, Point,
+    language_settings::language_settings, ChunkRenderer, HighlightId, OffsetUtf16, Point,
     Subscription as BufferSubscription,
 };
 use lsp::DiagnosticSeverity;
@@ -82,6 +82,7 @@ pub trait ToDisplayPoint {
 
 type TextHighlights = TreeMap<Option<TypeId>, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
+type SemanticTokenHighlights = Arc<[(Range<Anchor>, HighlightId)]>;
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
@@ -105,10 +106,13 @@ pub struct DisplayMap {
     text_highlights: TextHighlights,
     /// Regions of inlays that should be highlighted.
     inlay_highlights: InlayHighlights,
+    /// Tokens classified by language servers, highlighted over the syntax highlighting.
+    semantic_tokens: SemanticTokenHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
     fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
+    pub underline_warnings: bool,
 }
 
 impl DisplayMap {
@@ -155,7 +159,9 @@ impl DisplayMap {
             fold_placeholder,
             text_highlights: Default::default(),
             inlay_highlights: Default::default(),
+            semantic_tokens: Arc::new([]),
             clip_at_line_ends: false,
+            underline_warnings: true,
         }
     }
 
@@ -181,7 +187,9 @@ impl DisplayMap {
             crease_snapshot: self.crease_map.snapshot(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
+            semantic_tokens: self.semantic_tokens.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
+            underline_warnings: self.underline_warnings,
             fold_placeholder: self.fold_placeholder.clone(),
         }
     }
@@ -396,6 +404,11 @@ impl DisplayMap {
         }
     }
 
+    /// Replaces the highlighted semantic tokens, which must be sorted and must not overlap.
+    pub fn set_semantic_tokens(&mut self, tokens: Vec<(Range<Anchor>, HighlightId)>) {
+        self.semantic_tokens = tokens.into();
+    }
+
     pub fn text_highlights(&self, type_id: TypeId) -> Option<(HighlightStyle, &[Range<Anchor>])> {
         let highlights = self.text_highlights.get(&Some(type_id))?;
         Some((highlights.0, &highlights.1))
@@ -450,11 +463,10 @@ impl DisplayMap {
     }
 
     fn tab_size(buffer: &Model<MultiBuffer>, cx: &mut ModelContext<Self>) -> NonZeroU32 {
-        let language = buffer
-            .read(cx)
-            .as_singleton()
-            .and_then(|buffer| buffer.read(cx).language());
-        language_settings(language, None, cx).tab_size
+        let buffer = buffer.read(cx).as_singleton().map(|buffer| buffer.read(cx));
+        let language = buffer.and_then(|buffer| buffer.language());
+        let file = buffer.and_then(|buffer| buffer.file());
+        language_settings(language, file, cx).tab_size
     }
 
     #[cfg(test)]
@@ -471,6 +483,7 @@ impl DisplayMap {
 pub(crate) struct Highlights<'a> {
     pub text_highlights: Option<&'a TextHighlights>,
     pub inlay_highlights: Option<&'a InlayHighlights>,
+    pub semantic_tokens: Option<&'a [(Range<Anchor>, HighlightId)]>,
     pub styles: HighlightStyles,
 }
 
@@ -498,7 +511,9 @@ pub struct DisplaySnapshot {
     block_snapshot: BlockSnapshot,
     text_highlights: TextHighlights,
     inlay_highlights: InlayHighlights,
+    semantic_tokens: SemanticTokenHighlights,
     clip_at_line_ends: bool,
+    underline_warnings: bool,
     pub(crate) fold_placeholder: FoldPlaceholder,
 }
 
@@ -679,6 +694,7 @@ impl DisplaySnapshot {
             Highlights {
                 text_highlights: Some(&self.text_highlights),
                 inlay_highlights: Some(&self.inlay_highlights),
+                semantic_tokens: Some(&self.semantic_tokens),
                 styles: highlight_styles,
             },
         )
@@ -718,8 +734,14 @@ impl DisplaySnapshot {
             }
 
             if let Some(severity) = chunk.diagnostic_severity {
-                // Omit underlines for HINT/INFO diagnostics on 'unnecessary' code.
-                if severity <= DiagnosticSeverity::WARNING || !chunk.is_unnecessary {
+                // Omit underlines for HINT/INFO diagnostics on 'unnecessary' code, and for
+                // warnings if they aren't underlined.
+                let show_underline = if severity == DiagnosticSeverity::WARNING {
+                    self.underline_warnings
+                } else {
+                    severity < DiagnosticSeverity::WARNING || !chunk.is_unnecessary
+                };
+                if show_underline {
                     let diagnostic_color = super::diagnostic_style(severity, &editor_style.status);
                     diagnostic_highlight.underline = Some(UnderlineStyle {
                         color: Some(diagnostic_color),
@@ -974,6 +996,81 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Returns whether the given row has a region marker with a matching end marker.
+    pub fn starts_region(&self, buffer_row: MultiBufferRow) -> bool {
+        self.region_range(buffer_row).is_some()
+    }
+
+    /// Returns the range spanning from a `region` marker on the given row to the end of its
+    /// matching `endregion` marker.
+    fn region_range(&self, buffer_row: MultiBufferRow) -> Option<Range<Point>> {
+        if self.region_marker(buffer_row)? != RegionMarker::Start {
+            return None;
+        }
+
+        let mut depth = 0;
+        for row in buffer_row.0 + 1..=self.buffer_snapshot.max_buffer_row().0 {
+            match self.region_marker(MultiBufferRow(row)) {
+                Some(RegionMarker::Start) => depth += 1,
+                Some(RegionMarker::End) if depth == 0 => {
+                    let start = Point::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row));
+                    let end = Point::new(row, self.buffer_snapshot.line_len(MultiBufferRow(row)));
+                    return Some(start..end);
+                }
+                Some(RegionMarker::End) => depth -= 1,
+                None => {}
+            }
+        }
+        None
+    }
+
+    /// Returns the region marker on the given row, written either in a line comment, like
+    /// `// region Name`, or as a directive, like `#region Name`.
+    fn region_marker(&self, buffer_row: MultiBufferRow) -> Option<RegionMarker> {
+        let line_indent = self.line_indent_for_buffer_row(buffer_row);
+        if line_indent.is_line_blank() {
+            return None;
+        }
+
+        let start = Point::new(buffer_row.0, line_indent.raw_len());
+        let line = self
+            .buffer_snapshot
+            .chars_at(start)
+            .take_while(|c| *c != '\n')
+            .take(MAX_REGION_MARKER_LEN)
+            .collect::<String>();
+
+        let comment_prefix = self
+            .buffer_snapshot
+            .language_scope_at(start)
+            .and_then(|scope| {
+                scope
+                    .line_comment_prefixes()
+                    .iter()
+                    .map(|prefix| prefix.trim_end())
+                    .find(|prefix| !prefix.is_empty() && line.starts_with(prefix))
+                    .map(|prefix| prefix.len())
+            });
+        let mut marker = match comment_prefix {
+            Some(prefix_len) => line[prefix_len..].trim_start(),
+            None => line.strip_prefix('#')?,
+        };
+        marker = marker.strip_prefix('#').unwrap_or(marker);
+
+        let starts_with_word = |word: &str| {
+            marker.strip_prefix(word).map_or(false, |rest| {
+                rest.chars().next().map_or(true, char::is_whitespace)
+            })
+        };
+        if starts_with_word("region") {
+            Some(RegionMarker::Start)
+        } else if starts_with_word("endregion") {
+            Some(RegionMarker::End)
+        } else {
+            None
+        }
+    }
+
     pub fn foldable_range(
         &self,
         buffer_row: MultiBufferRow,
@@ -987,6 +1084,9 @@ impl DisplaySnapshot {
                 crease.range.to_point(&self.buffer_snapshot),
                 crease.placeholder.clone(),
             ))
+        } else if let Some(region_range) = self.region_range(buffer_row) {
+            (!self.is_line_folded(buffer_row))
+                .then(|| (region_range, self.fold_placeholder.clone()))
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -1047,6 +1147,15 @@ impl DisplaySnapshot {
     }
 }
 
+/// The longest prefix of a line that is read when looking for a region marker.
+const MAX_REGION_MARKER_LEN: usize = 32;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RegionMarker {
+    Start,
+    End,
+}
+
 #[derive(Copy, Clone, Default, Eq, Ord, PartialOrd, PartialEq)]
 pub struct DisplayPoint(BlockPoint);
 
@@ -1915,6 +2024,90 @@ pub mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_chunks_with_semantic_tokens(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| init_test(cx, |_| {}));
+
+        let theme = SyntaxTheme::new_test(vec![
+            ("operator", Hsla::red()),
+            ("string", Hsla::green()),
+            ("variable", Hsla::blue()),
+        ]);
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Test".into(),
+                    matcher: LanguageMatcher {
+                        path_suffixes: vec![".test".to_string()],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::language()),
+            )
+            .with_highlights_query(
+                r#"
+                ":" @operator
+                (string_literal) @string
+                "#,
+            )
+            .unwrap(),
+        );
+        language.set_theme(&theme);
+
+        let (text, token_ranges) = marked_text_ranges(r#"const «a»: B = "c «d»""#, false);
+
+        let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.condition(&buffer, |buf, _| !buf.is_parsing()).await;
+
+        let buffer = cx.new_model(|cx| MultiBuffer::singleton(buffer, cx));
+        let buffer_snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+
+        let map = cx.new_model(|cx| {
+            DisplayMap::new(
+                buffer,
+                font("Courier"),
+                px(16.0),
+                None,
+                true,
+                1,
+                1,
+                1,
+                FoldPlaceholder::test(),
+                cx,
+            )
+        });
+
+        // Semantic tokens replace the syntax highlighting of their text.
+        let highlight_id = HighlightId::for_capture_name("variable.parameter", &theme);
+        map.update(cx, |map, _| {
+            map.set_semantic_tokens(
+                token_ranges
+                    .into_iter()
+                    .map(|range| {
+                        (
+                            buffer_snapshot.anchor_after(range.start)
+                                ..buffer_snapshot.anchor_before(range.end),
+                            highlight_id,
+                        )
+                    })
+                    .collect(),
+            );
+        });
+        assert_eq!(
+            cx.update(|cx| syntax_chunks(DisplayRow(0)..DisplayRow(10), &map, &theme, cx)),
+            [
+                ("const ".to_string(), None),
+                ("a".to_string(), Some(Hsla::blue())),
+                (":".to_string(), Some(Hsla::red())),
+                (" B = ".to_string(), None),
+                ("\"c ".to_string(), Some(Hsla::green())),
+                ("d".to_string(), Some(Hsla::blue())),
+                ("\"".to_string(), Some(Hsla::green())),
+            ]
+        );
+    }
+
     #[gpui::test]
     fn test_clip_point(cx: &mut gpui::AppContext) {
         init_test(cx, |_| {});
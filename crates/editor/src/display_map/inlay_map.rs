@@ -1,7 +1,7 @@
 use crate::{HighlightStyles, InlayId};
 use collections::{BTreeMap, BTreeSet};
-use gpui::HighlightStyle;
-use language::{Chunk, Edit, Point, TextSummary};
+use gpui::{HighlightStyle, Hsla};
+use language::{Chunk, Edit, HighlightId, Point, TextSummary};
 use multi_buffer::{
     Anchor, MultiBufferChunks, MultiBufferRow, MultiBufferRows, MultiBufferSnapshot, ToOffset,
 };
@@ -44,6 +44,8 @@ pub(crate) struct Inlay {
     pub(crate) id: InlayId,
     pub position: Anchor,
     pub text: text::Rope,
+    /// The color of the inlay's text, overriding the highlight style of its kind.
+    pub color: Option<Hsla>,
 }
 
 impl Inlay {
@@ -59,6 +61,7 @@ impl Inlay {
             id: InlayId::Hint(id),
             position,
             text: text.into(),
+            color: None,
         }
     }
 
@@ -67,6 +70,16 @@ impl Inlay {
             id: InlayId::Suggestion(id),
             position,
             text: text.into(),
+            color: None,
+        }
+    }
+
+    pub fn color_swatch(id: usize, position: Anchor, color: Hsla) -> Self {
+        Self {
+            id: InlayId::ColorSwatch(id),
+            position,
+            text: "■ ".into(),
+            color: Some(color),
         }
     }
 }
@@ -220,6 +233,7 @@ pub struct InlayChunks<'a> {
     highlight_styles: HighlightStyles,
     highlight_endpoints: Peekable<vec::IntoIter<HighlightEndpoint>>,
     active_highlights: BTreeMap<Option<TypeId>, HighlightStyle>,
+    semantic_tokens: Peekable<vec::IntoIter<(Range<InlayOffset>, HighlightId)>>,
     highlights: Highlights<'a>,
     snapshot: &'a InlaySnapshot,
 }
@@ -265,6 +279,24 @@ impl<'a> Iterator for InlayChunks<'a> {
 
         let chunk = match self.transforms.item()? {
             Transform::Isomorphic(_) => {
+                // Split the chunk where the next semantic token starts or ends, skipping the
+                // tokens that end before it.
+                let mut next_semantic_token_boundary = InlayOffset(usize::MAX);
+                let mut semantic_token = None;
+                while let Some((range, highlight_id)) = self.semantic_tokens.peek() {
+                    if range.end <= self.output_offset {
+                        self.semantic_tokens.next();
+                    } else {
+                        if range.start <= self.output_offset {
+                            next_semantic_token_boundary = range.end;
+                            semantic_token = Some(*highlight_id);
+                        } else {
+                            next_semantic_token_boundary = range.start;
+                        }
+                        break;
+                    }
+                }
+
                 let chunk = self
                     .buffer_chunk
                     .get_or_insert_with(|| self.buffer_chunks.next().unwrap());
@@ -277,7 +309,8 @@ impl<'a> Iterator for InlayChunks<'a> {
                         .text
                         .len()
                         .min(self.transforms.end(&()).0 .0 - self.output_offset.0)
-                        .min(next_highlight_endpoint.0 - self.output_offset.0),
+                        .min(next_highlight_endpoint.0 - self.output_offset.0)
+                        .min(next_semantic_token_boundary.0 - self.output_offset.0),
                 );
 
                 chunk.text = suffix;
@@ -286,6 +319,9 @@ impl<'a> Iterator for InlayChunks<'a> {
                     text: prefix,
                     ..chunk.clone()
                 };
+                if semantic_token.is_some() {
+                    prefix.syntax_highlight_id = semantic_token;
+                }
                 if !self.active_highlights.is_empty() {
                     let mut highlight_style = HighlightStyle::default();
                     for active_highlight in self.active_highlights.values() {
@@ -310,6 +346,10 @@ impl<'a> Iterator for InlayChunks<'a> {
                 let mut highlight_style = match inlay.id {
                     InlayId::Suggestion(_) => self.highlight_styles.suggestion,
                     InlayId::Hint(_) => self.highlight_styles.inlay_hint,
+                    InlayId::ColorSwatch(_) => Some(HighlightStyle {
+                        color: inlay.color,
+                        ..HighlightStyle::default()
+                    }),
                 };
                 let next_inlay_highlight_endpoint;
                 let offset_in_inlay = self.output_offset - self.transforms.start().0;
@@ -670,6 +710,7 @@ impl InlayMap {
                     id: inlay_id,
                     position: snapshot.buffer.anchor_at(position, bias),
                     text: text.into(),
+                    color: None,
                 });
             } else {
                 to_remove.push(
@@ -1039,6 +1080,23 @@ impl InlaySnapshot {
             }
         }
         highlight_endpoints.sort();
+
+        let mut semantic_tokens = Vec::new();
+        if let Some(tokens) = highlights.semantic_tokens.filter(|_| language_aware) {
+            let start = self.buffer.anchor_after(self.to_buffer_offset(range.start));
+            let end = self.buffer.anchor_before(self.to_buffer_offset(range.end));
+            let start_ix =
+                tokens.partition_point(|(token, _)| token.end.cmp(&start, &self.buffer).is_le());
+            for (token, highlight_id) in &tokens[start_ix..] {
+                if token.start.cmp(&end, &self.buffer).is_ge() {
+                    break;
+                }
+                let token_start = self.to_inlay_offset(token.start.to_offset(&self.buffer));
+                let token_end = self.to_inlay_offset(token.end.to_offset(&self.buffer));
+                semantic_tokens.push((token_start..token_end, *highlight_id));
+            }
+        }
+
         let buffer_range = self.to_buffer_offset(range.start)..self.to_buffer_offset(range.end);
         let buffer_chunks = self.buffer.chunks(buffer_range, language_aware);
 
@@ -1053,6 +1111,7 @@ impl InlaySnapshot {
             highlight_styles: highlights.styles,
             highlight_endpoints: highlight_endpoints.into_iter().peekable(),
             active_highlights: Default::default(),
+            semantic_tokens: semantic_tokens.into_iter().peekable(),
             highlights,
             snapshot: self,
         }
@@ -1276,6 +1335,7 @@ mod tests {
                 id: InlayId::Hint(post_inc(&mut next_inlay_id)),
                 position: buffer.read(cx).snapshot(cx).anchor_after(3),
                 text: "|123|".into(),
+                color: None,
             }],
         );
         assert_eq!(inlay_snapshot.text(), "abc|123|defghi");
@@ -1353,11 +1413,13 @@ mod tests {
                     id: InlayId::Hint(post_inc(&mut next_inlay_id)),
                     position: buffer.read(cx).snapshot(cx).anchor_before(3),
                     text: "|123|".into(),
+                    color: None,
                 },
                 Inlay {
                     id: InlayId::Suggestion(post_inc(&mut next_inlay_id)),
                     position: buffer.read(cx).snapshot(cx).anchor_after(3),
                     text: "|456|".into(),
+                    color: None,
                 },
             ],
         );
@@ -1564,16 +1626,19 @@ mod tests {
                     id: InlayId::Hint(post_inc(&mut next_inlay_id)),
                     position: buffer.read(cx).snapshot(cx).anchor_before(0),
                     text: "|123|\n".into(),
+                    color: None,
                 },
                 Inlay {
                     id: InlayId::Hint(post_inc(&mut next_inlay_id)),
                     position: buffer.read(cx).snapshot(cx).anchor_before(4),
                     text: "|456|".into(),
+                    color: None,
                 },
                 Inlay {
                     id: InlayId::Suggestion(post_inc(&mut next_inlay_id)),
                     position: buffer.read(cx).snapshot(cx).anchor_before(7),
                     text: "\n|567|\n".into(),
+                    color: None,
                 },
             ],
         );
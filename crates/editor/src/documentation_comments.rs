@@ -0,0 +1,248 @@
+use std::{ops::Range, sync::Arc};
+
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{UniformListScrollHandle, ViewContext};
+use language::{
+    CodeLabel, Documentation, DocumentationCommentConfig, FunctionSignature, LanguageServerId,
+    Point,
+};
+use multi_buffer::{Anchor, ToOffset as _};
+use parking_lot::{Mutex, RwLock};
+use project::Completion;
+use snippet::Snippet;
+use text::ToOffset;
+use util::{post_inc, ResultExt};
+
+use crate::{debounced_delay::DebouncedDelay, CompletionsMenu, ContextMenu, Editor};
+
+impl Editor {
+    /// Offers to expand the documentation comment trigger typed above a function, such as `///`
+    /// or `/**`, into a skeleton listing the function's parameters and return value, in a
+    /// completion menu. Returns whether there was such a trigger.
+    pub(crate) fn show_documentation_comment_skeleton(
+        &mut self,
+        cx: &mut ViewContext<Self>,
+    ) -> bool {
+        if self.pending_rename.is_some() || self.selections.count() > 1 {
+            return false;
+        }
+
+        let position = self.selections.newest_anchor().head();
+        let Some((buffer, buffer_position)) =
+            self.buffer.read(cx).text_anchor_for_position(position, cx)
+        else {
+            return false;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let offset = buffer_position.to_offset(&snapshot);
+        let Some(language) = snapshot.language_at(offset) else {
+            return false;
+        };
+        let Some(config) = language.documentation_comment() else {
+            return false;
+        };
+
+        // The trigger must be alone on its line, apart from the end of the comment when it was
+        // auto-closed.
+        let cursor = snapshot.offset_to_point(offset);
+        let line = snapshot
+            .text_for_range(
+                Point::new(cursor.row, 0)..Point::new(cursor.row, snapshot.line_len(cursor.row)),
+            )
+            .collect::<String>();
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let Some(tail) = line[indent.len()..].strip_prefix(config.trigger.as_ref()) else {
+            return false;
+        };
+        if cursor.column as usize != indent.len() + config.trigger.len()
+            || !(tail.is_empty() || config.end.as_deref() == Some(tail))
+        {
+            return false;
+        }
+
+        // Attributes and decorators may stand between the comment and the function.
+        let Some(signature_row) = (cursor.row + 1..=snapshot.max_point().row)
+            .map(|row| {
+                let line = snapshot
+                    .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+                    .collect::<String>();
+                (row, line)
+            })
+            .filter(|(_, line)| !line.trim().is_empty())
+            .find(|(_, line)| !line.trim_start().starts_with(['#', '@']))
+            .map(|(row, _)| row)
+        else {
+            return false;
+        };
+        let Some(signature) = snapshot.function_signature_at_row(signature_row) else {
+            return false;
+        };
+
+        let skeleton = documentation_comment_skeleton(config, &signature, indent);
+        let Some(snippet) = Snippet::parse(&skeleton).log_err() else {
+            return false;
+        };
+
+        let multibuffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let line_start = multibuffer_snapshot
+            .anchor_before(position.to_offset(&multibuffer_snapshot) - config.trigger.len());
+        let line_end = multibuffer_snapshot
+            .anchor_after(position.to_offset(&multibuffer_snapshot) + tail.len());
+        let editor = cx.view().downgrade();
+        let preview = snippet.text.clone();
+        let label = "Documentation comment".to_string();
+        let completion = Completion {
+            old_range: snapshot.anchor_before(offset - config.trigger.len())..buffer_position,
+            new_text: config.trigger.to_string(),
+            label: CodeLabel {
+                filter_range: 0..label.len(),
+                text: label,
+                runs: Vec::new(),
+            },
+            server_id: LanguageServerId(usize::MAX),
+            documentation: Some(Documentation::MultiLinePlainText(preview)),
+            lsp_completion: Default::default(),
+            // The skeleton is inserted once the completion is done with the editor, and without
+            // autoindent, which would change the leading whitespace of its lines.
+            confirm: Some(Arc::new(move |cx| {
+                let editor = editor.clone();
+                let snippet = snippet.clone();
+                cx.defer(move |cx| {
+                    editor
+                        .update(cx, |editor, cx| {
+                            editor.insert_documentation_comment_skeleton(
+                                line_start..line_end,
+                                snippet,
+                                cx,
+                            )
+                        })
+                        .ok();
+                });
+            })),
+            show_new_completions_on_confirm: false,
+        };
+        let match_candidates = vec![StringMatchCandidate::new(0, completion.label.text.clone())];
+        let matches = vec![StringMatch {
+            candidate_id: 0,
+            score: Default::default(),
+            positions: Default::default(),
+            string: completion.label.text.clone(),
+        }];
+
+        let menu = CompletionsMenu {
+            id: post_inc(&mut self.next_completion_id),
+            initial_position: position,
+            buffer,
+            completions: Arc::new(RwLock::new(vec![completion].into())),
+            match_candidates: match_candidates.into(),
+            matches: matches.into(),
+            selected_item: 0,
+            scroll_handle: UniformListScrollHandle::new(),
+            selected_completion_documentation_resolve_debounce: Arc::new(Mutex::new(
+                DebouncedDelay::new(),
+            )),
+        };
+        *self.context_menu.write() = Some(ContextMenu::Completions(menu));
+        self.discard_inline_completion(false, cx);
+        cx.notify();
+        true
+    }
+
+    fn insert_documentation_comment_skeleton(
+        &mut self,
+        range: Range<Anchor>,
+        snippet: Snippet,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let range = range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot);
+        self.transact(cx, |editor, cx| {
+            editor
+                .insert_snippet_with_autoindent(&[range], snippet, None, cx)
+                .log_err();
+        });
+    }
+}
+
+/// Builds the snippet of a documentation comment for the function, with a tabstop for its
+/// description, each of its parameters and its return value. The lines after the first one are
+/// indented with `indent`.
+fn documentation_comment_skeleton(
+    config: &DocumentationCommentConfig,
+    signature: &FunctionSignature,
+    indent: &str,
+) -> String {
+    let prefix = escape_snippet_text(&config.prefix);
+    let blank_line = prefix.trim_end().to_string();
+    let mut tabstops = 1..;
+    let mut lines = Vec::new();
+    if let Some(start) = &config.start {
+        lines.push(escape_snippet_text(start));
+    }
+    lines.push(format!("{prefix}${}", tabstops.next().unwrap()));
+    lines.push(blank_line.clone());
+
+    let mut is_first_section = true;
+    let mut push_section =
+        |lines: &mut Vec<String>, heading: Option<&str>, entries: Vec<String>| {
+            if entries.is_empty() {
+                return;
+            }
+            if let Some(heading) = heading {
+                if !is_first_section {
+                    lines.push(blank_line.clone());
+                }
+                lines.push(format!("{prefix}{}", escape_snippet_text(heading)));
+                lines.push(blank_line.clone());
+            }
+            lines.extend(entries);
+            is_first_section = false;
+        };
+
+    let parameters = signature
+        .parameters
+        .iter()
+        .map(|name| {
+            let parameter = escape_snippet_text(&config.parameter)
+                .replace("{name}", &escape_snippet_text(name));
+            format!("{prefix}{parameter}${}", tabstops.next().unwrap())
+        })
+        .collect();
+    push_section(&mut lines, config.parameters_heading.as_deref(), parameters);
+
+    let return_type = signature
+        .return_type
+        .as_deref()
+        .map(|return_type| return_type.trim_start_matches(':').trim())
+        .filter(|return_type| !matches!(*return_type, "" | "()" | "void"));
+    if return_type.is_some() {
+        let returns = format!(
+            "{prefix}{}${}",
+            escape_snippet_text(&config.returns),
+            tabstops.next().unwrap()
+        );
+        push_section(&mut lines, config.returns_heading.as_deref(), vec![returns]);
+    }
+
+    // Without any section, the blank line after the description is only noise.
+    if is_first_section {
+        lines.pop();
+    }
+    if let Some(end) = &config.end {
+        lines.push(escape_snippet_text(end));
+    }
+
+    let indent = escape_snippet_text(indent);
+    lines.join(&format!("\n{indent}"))
+}
+
+fn escape_snippet_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(character, '$' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
@@ -15,11 +15,15 @@
 pub mod actions;
 mod blame_entry_tooltip;
 mod blink_manager;
+mod code_lens;
+mod color_swatches;
 mod debounced_delay;
 pub mod display_map;
+mod documentation_comments;
 mod editor_settings;
 mod editor_settings_controls;
 mod element;
+mod export;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
@@ -29,14 +33,21 @@ mod indent_guides;
 mod inlay_hint_cache;
 mod inline_completion_provider;
 pub mod items;
+mod jump_to_char;
+mod large_file_banner;
 mod linked_editing_ranges;
+mod macro_recorder;
 mod mouse_context_menu;
 pub mod movement;
+mod numeric_setting_editor;
 mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
+mod spelling;
 pub mod tasks;
+mod unicode_input;
 
 #[cfg(test)]
 mod editor_tests;
@@ -53,7 +64,7 @@ use blink_manager::BlinkManager;
 use client::{Collaborator, ParticipantIndex};
 use clock::ReplicaId;
 use collections::{BTreeMap, Bound, HashMap, HashSet, VecDeque};
-use convert_case::{Case, Casing};
+use convert_case::{Boundary, Case, Casing};
 use debounced_delay::DebouncedDelay;
 use display_map::*;
 pub use display_map::{DisplayPoint, FoldPlaceholder};
@@ -86,15 +97,18 @@ use inlay_hint_cache::{InlayHintCache, InlaySplice, InvalidationStrategy};
 pub use inline_completion_provider::*;
 pub use items::MAX_TAB_TITLE_LEN;
 use itertools::Itertools;
+use jump_to_char::JumpToCharState;
 use language::{
     char_kind,
-    language_settings::{self, all_language_settings, InlayHintSettings},
+    language_settings::{self, all_language_settings, InlayHintSettings, WrapGuide},
     markdown, point_from_lsp, AutoindentMode, BracketPair, Buffer, Capability, CharKind, CodeLabel,
     CursorShape, Diagnostic, Documentation, IndentKind, IndentSize, Language, OffsetRangeExt,
-    Point, Selection, SelectionGoal, TransactionId,
+    Point, Selection, SelectionGoal, TextObject, TransactionId,
 };
 use language::{point_to_lsp, BufferRow, Runnable, RunnableRange};
+pub use large_file_banner::LargeFileBanner;
 use linked_editing_ranges::refresh_linked_ranges;
+pub use numeric_setting_editor::*;
 use task::{ResolvedTask, TaskTemplate, TaskVariables};
 
 use hover_links::{HoverLink, HoveredLinkState, InlayHighlight};
@@ -112,10 +126,11 @@ pub use multi_buffer::{
 use multi_buffer::{ExpandExcerptDirection, MultiBufferPoint, MultiBufferRow, ToOffsetUtf16};
 use ordered_float::OrderedFloat;
 use parking_lot::{Mutex, RwLock};
+use persistence::DB;
 use project::project_settings::{GitGutterSetting, ProjectSettings};
 use project::{
-    CodeAction, Completion, FormatTrigger, Item, Location, Project, ProjectPath,
-    ProjectTransaction, TaskSourceKind, WorktreeId,
+    search::SearchQuery, CodeAction, Completion, FormatTrigger, Item, Location, Project,
+    ProjectPath, ProjectTransaction, TaskSourceKind, WorktreeId,
 };
 use rand::prelude::*;
 use rpc::{proto::*, ErrorExt};
@@ -125,6 +140,8 @@ use serde::{Deserialize, Serialize};
 use settings::{update_settings_file, Settings, SettingsStore};
 use smallvec::SmallVec;
 use snippet::Snippet;
+use spell_check::SpellChecker;
+use spelling::SpellingAction;
 use std::{
     any::TypeId,
     borrow::Cow,
@@ -153,7 +170,7 @@ use util::{defer, maybe, post_inc, RangeExt, ResultExt, TryFutureExt};
 use workspace::item::{ItemHandle, PreviewTabsSettings};
 use workspace::notifications::{DetachAndPromptErr, NotificationId};
 use workspace::{
-    searchable::SearchEvent, ItemNavHistory, SplitDirection, ViewId, Workspace, WorkspaceId,
+    searchable::SearchEvent, ItemId, ItemNavHistory, SplitDirection, ViewId, Workspace, WorkspaceId,
 };
 use workspace::{OpenInTerminal, OpenTerminal, TabBarSettings, Toast};
 
@@ -171,8 +188,9 @@ const MAX_SELECTION_HISTORY_LEN: usize = 1024;
 pub(crate) const CURSORS_VISIBLE_FOR: Duration = Duration::from_millis(2000);
 #[doc(hidden)]
 pub const CODE_ACTIONS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
-#[doc(hidden)]
-pub const DOCUMENT_HIGHLIGHTS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+/// How long to wait for the language server to highlight the symbol under the cursor, before
+/// falling back to highlighting the word under it.
+const DOCUMENT_HIGHLIGHTS_TIMEOUT: Duration = Duration::from_millis(500);
 
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(2);
 
@@ -243,6 +261,7 @@ pub fn render_parsed_markdown(
 pub(crate) enum InlayId {
     Suggestion(usize),
     Hint(usize),
+    ColorSwatch(usize),
 }
 
 impl InlayId {
@@ -250,6 +269,7 @@ impl InlayId {
         match self {
             Self::Suggestion(id) => *id,
             Self::Hint(id) => *id,
+            Self::ColorSwatch(id) => *id,
         }
     }
 }
@@ -257,6 +277,7 @@ impl InlayId {
 enum DiffRowHighlight {}
 enum DocumentHighlightRead {}
 enum DocumentHighlightWrite {}
+enum WordOccurrenceHighlight {}
 enum InputComposition {}
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -275,6 +296,7 @@ pub fn init(cx: &mut AppContext) {
     workspace::register_project_item::<Editor>(cx);
     workspace::FollowableViewRegistry::register::<Editor>(cx);
     workspace::register_serializable_item::<Editor>(cx);
+    macro_recorder::init(cx);
 
     cx.observe_new_views(
         |workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>| {
@@ -461,12 +483,14 @@ pub struct Editor {
     pub(crate) show_cursor_when_unfocused: bool,
     columnar_selection_tail: Option<Anchor>,
     add_selections_state: Option<AddSelectionsState>,
+    block_selection: Option<BlockSelection>,
+    jump_to_char_state: Option<JumpToCharState>,
     select_next_state: Option<SelectNextState>,
     select_prev_state: Option<SelectNextState>,
     selection_history: SelectionHistory,
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
-    select_larger_syntax_node_stack: Vec<Box<[Selection<usize>]>>,
+    select_syntax_node_history: SelectSyntaxNodeHistory,
     ime_transaction: Option<TransactionId>,
     active_diagnostics: Option<ActiveDiagnosticGroup>,
     soft_wrap_mode_override: Option<language_settings::SoftWrap>,
@@ -480,12 +504,16 @@ pub struct Editor {
     mode: EditorMode,
     show_breadcrumbs: bool,
     show_gutter: bool,
+    /// Whether the editor's workspace is in zen mode, which hides the gutter.
+    zen_mode: bool,
     redact_all: bool,
     show_line_numbers: Option<bool>,
     show_git_diff_gutter: Option<bool>,
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
     show_wrap_guides: Option<bool>,
+    /// The wrap guides to show instead of the ones of the language settings.
+    wrap_guides_override: Option<Vec<WrapGuide>>,
     show_indent_guides: Option<bool>,
     placeholder_text: Option<Arc<str>>,
     highlight_order: usize,
@@ -508,9 +536,16 @@ pub struct Editor {
     document_highlights_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
+    code_lenses: code_lens::CodeLenses,
+    semantic_tokens: semantic_tokens::SemanticTokensState,
+    color_swatches: color_swatches::ColorSwatches,
+    spell_check: spelling::SpellCheckState,
     pending_rename: Option<RenameState>,
     searchable: bool,
     cursor_shape: CursorShape,
+    /// The cursor shape from the settings, which `cursor_shape` follows unless it
+    /// was changed with `set_cursor_shape`.
+    default_cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
     collapse_matches: bool,
     autoindent_mode: Option<AutoindentMode>,
@@ -733,6 +768,53 @@ struct AddSelectionsState {
     stack: Vec<usize>,
 }
 
+/// A rectangular selection made of one selection per row, as created by a columnar mouse
+/// selection or by adding cursors above and below.
+#[derive(Clone, Copy, Debug)]
+struct BlockSelection {
+    /// The display column the block starts at. Cursors sitting at the end of rows that are
+    /// too short to reach it get padded with spaces when text is inserted.
+    start_column: u32,
+}
+
+/// The selections that expanding the selections went through, for shrinking them back
+/// through the same states.
+#[derive(Default)]
+struct SelectSyntaxNodeHistory {
+    /// The selections before each expansion, the most recent one last.
+    stack: Vec<Arc<[Selection<Anchor>]>>,
+    /// The selections made by the last expansion or shrink.
+    current: Option<Arc<[Selection<Anchor>]>>,
+}
+
+impl SelectSyntaxNodeHistory {
+    fn push(&mut self, previous: Arc<[Selection<Anchor>]>, current: Arc<[Selection<Anchor>]>) {
+        self.stack.push(previous);
+        self.current = Some(current);
+    }
+
+    /// Forgets the history when the selections move away from the ones made by the last
+    /// expansion or shrink. Nudging them, such as by extending them by a character, keeps it.
+    fn selections_changed(
+        &mut self,
+        selections: &[Selection<Anchor>],
+        buffer: &MultiBufferSnapshot,
+    ) {
+        let Some(current) = self.current.as_ref() else {
+            return;
+        };
+        let nudged = current.len() == selections.len()
+            && current.iter().zip(selections).all(|(current, selection)| {
+                let current = current.range().to_offset(buffer);
+                let selection = selection.range().to_offset(buffer);
+                selection.start <= current.end && selection.end >= current.start
+            });
+        if !nudged {
+            *self = Self::default();
+        }
+    }
+}
+
 #[derive(Clone)]
 struct SelectNextState {
     query: AhoCorasick,
@@ -1273,25 +1355,21 @@ impl CompletionsMenu {
 struct CodeActionContents {
     tasks: Option<Arc<ResolvedTasks>>,
     actions: Option<Arc<[CodeAction]>>,
+    spelling_actions: Option<Arc<[SpellingAction]>>,
 }
 
 impl CodeActionContents {
     fn len(&self) -> usize {
-        match (&self.tasks, &self.actions) {
-            (Some(tasks), Some(actions)) => actions.len() + tasks.templates.len(),
-            (Some(tasks), None) => tasks.templates.len(),
-            (None, Some(actions)) => actions.len(),
-            (None, None) => 0,
-        }
+        self.tasks.as_ref().map_or(0, |tasks| tasks.templates.len())
+            + self.actions.as_ref().map_or(0, |actions| actions.len())
+            + self
+                .spelling_actions
+                .as_ref()
+                .map_or(0, |spelling_actions| spelling_actions.len())
     }
 
     fn is_empty(&self) -> bool {
-        match (&self.tasks, &self.actions) {
-            (Some(tasks), Some(actions)) => actions.is_empty() && tasks.templates.is_empty(),
-            (Some(tasks), None) => tasks.templates.is_empty(),
-            (None, Some(actions)) => actions.is_empty(),
-            (None, None) => true,
-        }
+        self.len() == 0
     }
 
     fn iter(&self) -> impl Iterator<Item = CodeActionsItem> + '_ {
@@ -1308,31 +1386,31 @@ impl CodeActionContents {
                     .iter()
                     .map(|action| CodeActionsItem::CodeAction(action.clone()))
             }))
+            .chain(self.spelling_actions.iter().flat_map(|spelling_actions| {
+                spelling_actions
+                    .iter()
+                    .map(|action| CodeActionsItem::Spelling(action.clone()))
+            }))
     }
-    fn get(&self, index: usize) -> Option<CodeActionsItem> {
-        match (&self.tasks, &self.actions) {
-            (Some(tasks), Some(actions)) => {
-                if index < tasks.templates.len() {
-                    tasks
-                        .templates
-                        .get(index)
-                        .cloned()
-                        .map(|(kind, task)| CodeActionsItem::Task(kind, task))
-                } else {
-                    actions
-                        .get(index - tasks.templates.len())
-                        .cloned()
-                        .map(CodeActionsItem::CodeAction)
-                }
+
+    fn get(&self, mut index: usize) -> Option<CodeActionsItem> {
+        if let Some(tasks) = &self.tasks {
+            if let Some((kind, task)) = tasks.templates.get(index) {
+                return Some(CodeActionsItem::Task(kind.clone(), task.clone()));
             }
-            (Some(tasks), None) => tasks
-                .templates
-                .get(index)
-                .cloned()
-                .map(|(kind, task)| CodeActionsItem::Task(kind, task)),
-            (None, Some(actions)) => actions.get(index).cloned().map(CodeActionsItem::CodeAction),
-            (None, None) => None,
+            index -= tasks.templates.len();
         }
+        if let Some(actions) = &self.actions {
+            if let Some(action) = actions.get(index) {
+                return Some(CodeActionsItem::CodeAction(action.clone()));
+            }
+            index -= actions.len();
+        }
+        self.spelling_actions
+            .as_ref()?
+            .get(index)
+            .cloned()
+            .map(CodeActionsItem::Spelling)
     }
 }
 
@@ -1341,6 +1419,7 @@ impl CodeActionContents {
 enum CodeActionsItem {
     Task(TaskSourceKind, ResolvedTask),
     CodeAction(CodeAction),
+    Spelling(SpellingAction),
 }
 
 impl CodeActionsItem {
@@ -1356,10 +1435,17 @@ impl CodeActionsItem {
         };
         Some(action)
     }
+    fn as_spelling_action(&self) -> Option<&SpellingAction> {
+        let Self::Spelling(action) = self else {
+            return None;
+        };
+        Some(action)
+    }
     fn label(&self) -> String {
         match self {
             Self::CodeAction(action) => action.lsp_action.title.clone(),
             Self::Task(_, task) => task.resolved_label.clone(),
+            Self::Spelling(action) => action.label(),
         }
     }
 }
@@ -1481,6 +1567,23 @@ impl CodeActionsMenu {
                                 )
                                 .child(SharedString::from(task.resolved_label.clone()))
                             })
+                            .when_some(action.as_spelling_action(), |this, action| {
+                                this.on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |editor, _, cx| {
+                                        cx.stop_propagation();
+                                        if let Some(task) = editor.confirm_code_action(
+                                            &ConfirmCodeAction {
+                                                item_ix: Some(item_ix),
+                                            },
+                                            cx,
+                                        ) {
+                                            task.detach_and_log_err(cx)
+                                        }
+                                    }),
+                                )
+                                .child(SharedString::from(action.label()))
+                            })
                     })
                     .collect()
             },
@@ -1498,6 +1601,7 @@ impl CodeActionsMenu {
                 .max_by_key(|(_, action)| match action {
                     CodeActionsItem::Task(_, task) => task.resolved_label.chars().count(),
                     CodeActionsItem::CodeAction(action) => action.lsp_action.title.chars().count(),
+                    CodeActionsItem::Spelling(action) => action.label().chars().count(),
                 })
                 .map(|(ix, _)| ix),
         )
@@ -1528,6 +1632,10 @@ pub struct ClipboardSelection {
     pub len: usize,
     pub is_entire_line: bool,
     pub first_line_indent: u32,
+    /// Whether this selection was copied as one row of a block selection, in which case
+    /// pasting it at a single cursor inserts the rows at the same column on successive lines.
+    #[serde(default)]
+    pub is_block: bool,
 }
 
 #[derive(Debug)]
@@ -1697,7 +1805,7 @@ impl Editor {
         };
         let file_header_size = if show_excerpt_controls { 3 } else { 2 };
         let display_map = cx.new_model(|cx| {
-            DisplayMap::new(
+            let mut display_map = DisplayMap::new(
                 buffer.clone(),
                 style.font(),
                 font_size,
@@ -1708,7 +1816,11 @@ impl Editor {
                 MULTI_BUFFER_EXCERPT_FOOTER_HEIGHT,
                 fold_placeholder,
                 cx,
-            )
+            );
+            display_map.underline_warnings = ProjectSettings::get_global(cx)
+                .diagnostics
+                .underline_warnings;
+            display_map
         });
 
         let selections = SelectionsCollection::new(display_map.clone(), buffer.clone());
@@ -1729,6 +1841,8 @@ impl Editor {
                 project_subscriptions.push(cx.subscribe(project, |editor, _, event, cx| {
                     if let project::Event::RefreshInlayHints = event {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
+                        editor.refresh_code_lenses(cx);
+                        editor.refresh_semantic_tokens(cx);
                     } else if let project::Event::SnippetEdit(id, snippet_edits) = event {
                         if let Some(buffer) = editor.buffer.read(cx).buffer(*id) {
                             let focus_handle = editor.focus_handle(cx);
@@ -1749,6 +1863,8 @@ impl Editor {
                 project_subscriptions.push(cx.observe(&task_inventory, |editor, _, cx| {
                     editor.tasks_update_task = Some(editor.refresh_runnables(cx));
                 }));
+                let bookmark_store = project.read(cx).bookmark_store().clone();
+                project_subscriptions.push(cx.observe(&bookmark_store, |_, _, cx| cx.notify()));
             }
         }
 
@@ -1781,12 +1897,14 @@ impl Editor {
             scroll_manager: ScrollManager::new(cx),
             columnar_selection_tail: None,
             add_selections_state: None,
+            block_selection: None,
+            jump_to_char_state: None,
             select_next_state: None,
             select_prev_state: None,
             selection_history: Default::default(),
             autoclose_regions: Default::default(),
             snippet_stack: Default::default(),
-            select_larger_syntax_node_stack: Vec::new(),
+            select_syntax_node_history: SelectSyntaxNodeHistory::default(),
             ime_transaction: Default::default(),
             active_diagnostics: None,
             soft_wrap_mode_override,
@@ -1798,11 +1916,13 @@ impl Editor {
             mode,
             show_breadcrumbs: EditorSettings::get_global(cx).toolbar.breadcrumbs,
             show_gutter: mode == EditorMode::Full,
+            zen_mode: false,
             show_line_numbers: None,
             show_git_diff_gutter: None,
             show_code_actions: None,
             show_runnables: None,
             show_wrap_guides: None,
+            wrap_guides_override: None,
             redact_all: false,
             show_indent_guides,
             placeholder_text: None,
@@ -1828,7 +1948,8 @@ impl Editor {
             linked_editing_range_task: Default::default(),
             pending_rename: Default::default(),
             searchable: true,
-            cursor_shape: Default::default(),
+            cursor_shape: EditorSettings::get_global(cx).cursor_shape,
+            default_cursor_shape: EditorSettings::get_global(cx).cursor_shape,
             current_line_highlight: None,
             autoindent_mode: Some(AutoindentMode::EachLine),
             collapse_matches: false,
@@ -1894,12 +2015,26 @@ impl Editor {
             ],
             tasks_update_task: None,
             linked_edit_ranges: Default::default(),
+            code_lenses: Default::default(),
+            semantic_tokens: Default::default(),
+            color_swatches: Default::default(),
+            spell_check: Default::default(),
             previous_search_ranges: None,
             breadcrumb_header: None,
             focused_block: None,
         };
         this.tasks_update_task = Some(this.refresh_runnables(cx));
+        this.refresh_code_lenses(cx);
+        this.refresh_semantic_tokens(cx);
+        this.refresh_color_swatches(cx);
+        this.refresh_misspellings(cx);
         this._subscriptions.extend(project_subscriptions);
+        if let Some(spell_checker) = SpellChecker::global(cx) {
+            this._subscriptions
+                .push(cx.observe(&spell_checker, |editor, _, cx| {
+                    editor.refresh_misspellings(cx)
+                }));
+        }
 
         this.end_selection(cx);
         this.scroll_manager.show_scrollbar(cx);
@@ -1955,8 +2090,14 @@ impl Editor {
             }
         }
 
-        for layer in self.keymap_context_layers.values() {
-            key_context.extend(layer);
+        // While jumping to a character, the typed characters have to reach the editor
+        // instead of being handled by the bindings of layers such as vim's.
+        if self.is_jumping_to_char() {
+            key_context.add("jumping_to_char");
+        } else {
+            for layer in self.keymap_context_layers.values() {
+                key_context.extend(layer);
+            }
         }
 
         if let Some(extension) = self
@@ -2066,7 +2207,7 @@ impl Editor {
     pub fn snapshot(&mut self, cx: &mut WindowContext) -> EditorSnapshot {
         EditorSnapshot {
             mode: self.mode,
-            show_gutter: self.show_gutter,
+            show_gutter: self.show_gutter && !self.zen_mode,
             show_line_numbers: self.show_line_numbers,
             show_git_diff_gutter: self.show_git_diff_gutter,
             show_code_actions: self.show_code_actions,
@@ -2302,9 +2443,12 @@ impl Editor {
             .update(cx, |display_map, cx| display_map.snapshot(cx));
         let buffer = &display_map.buffer_snapshot;
         self.add_selections_state = None;
+        self.block_selection = None;
+        self.jump_to_char_state = None;
         self.select_next_state = None;
         self.select_prev_state = None;
-        self.select_larger_syntax_node_stack.clear();
+        self.select_syntax_node_history
+            .selections_changed(&self.selections.disjoint_anchors(), buffer);
         self.invalidate_autoclose_regions(&self.selections.disjoint_anchors(), buffer);
         self.snippet_stack
             .invalidate(&self.selections.disjoint_anchors(), buffer);
@@ -2823,20 +2967,30 @@ impl Editor {
         let selection_ranges = (start_row.0..=end_row.0)
             .map(DisplayRow)
             .filter_map(|row| {
-                if start_column <= display_map.line_len(row) && !display_map.is_block_line(row) {
-                    let start = display_map
-                        .clip_point(DisplayPoint::new(row, start_column), Bias::Left)
-                        .to_point(display_map);
-                    let end = display_map
-                        .clip_point(DisplayPoint::new(row, end_column), Bias::Right)
+                if display_map.is_block_line(row) {
+                    return None;
+                }
+
+                let line_len = display_map.line_len(row);
+                if start_column > line_len {
+                    // Rows too short to reach the block get a cursor at their end, which is
+                    // padded up to the block's start column once text is inserted.
+                    let end_of_line = display_map
+                        .clip_point(DisplayPoint::new(row, line_len), Bias::Left)
                         .to_point(display_map);
-                    if reversed {
-                        Some(end..start)
-                    } else {
-                        Some(start..end)
-                    }
+                    return Some(end_of_line..end_of_line);
+                }
+
+                let start = display_map
+                    .clip_point(DisplayPoint::new(row, start_column), Bias::Left)
+                    .to_point(display_map);
+                let end = display_map
+                    .clip_point(DisplayPoint::new(row, end_column), Bias::Right)
+                    .to_point(display_map);
+                if reversed {
+                    Some(end..start)
                 } else {
-                    None
+                    Some(start..end)
                 }
             })
             .collect::<Vec<_>>();
@@ -2844,9 +2998,37 @@ impl Editor {
         self.change_selections(None, cx, |s| {
             s.select_ranges(selection_ranges);
         });
+        if start_row != end_row {
+            self.block_selection = Some(BlockSelection { start_column });
+        }
         cx.notify();
     }
 
+    /// Returns the number of spaces to insert in front of the text inserted at each selection
+    /// of the active block selection, keyed by selection id, so that cursors at the end of rows
+    /// too short to reach the block are padded out to the block's start column.
+    fn block_selection_padding(&self, cx: &mut ViewContext<Self>) -> HashMap<usize, usize> {
+        let mut padding = HashMap::default();
+        let Some(block_selection) = self.block_selection else {
+            return padding;
+        };
+
+        let (display_map, selections) = self.selections.all_display(cx);
+        for selection in selections {
+            let head = selection.head();
+            if selection.is_empty()
+                && head.column() < block_selection.start_column
+                && head.column() == display_map.line_len(head.row())
+            {
+                padding.insert(
+                    selection.id,
+                    (block_selection.start_column - head.column()) as usize,
+                );
+            }
+        }
+        padding
+    }
+
     pub fn has_pending_nonempty_selection(&self) -> bool {
         let pending_nonempty_selection = match self.selections.pending_anchor() {
             Some(Selection { start, end, .. }) => start != end,
@@ -2862,6 +3044,9 @@ impl Editor {
     }
 
     pub fn cancel(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+        if self.cancel_jump_to_char(cx) {
+            return;
+        }
         if self.clear_clicked_diff_hunks(cx) {
             cx.notify();
             return;
@@ -2869,6 +3054,9 @@ impl Editor {
         if self.dismiss_menus_and_popups(true, cx) {
             return;
         }
+        if linked_editing_ranges::cancel_linked_ranges(self, cx) {
+            return;
+        }
 
         if self.mode == EditorMode::Full {
             if self.change_selections(Some(Autoscroll::fit()), cx, |s| s.try_cancel()) {
@@ -2904,6 +3092,10 @@ impl Editor {
             return true;
         }
 
+        if self.hide_color_picker(cx) {
+            return true;
+        }
+
         if self.discard_inline_completion(should_report_inline_completion_event, cx) {
             return true;
         }
@@ -2975,6 +3167,7 @@ impl Editor {
             return;
         }
 
+        let block_selection_padding = self.block_selection_padding(cx);
         let selections = self.selections.all_adjusted(cx);
         let mut bracket_inserted = false;
         let mut edits = Vec::new();
@@ -2987,6 +3180,19 @@ impl Editor {
             self.selections_with_autoclose_regions(selections, &snapshot)
         {
             if let Some(scope) = snapshot.language_scope_at(selection.head()) {
+                let snapshot_settings = snapshot.settings_at(selection.start, cx);
+                let auto_pairs = snapshot_settings
+                    .auto_pairs
+                    .iter()
+                    .map(|pair| BracketPair {
+                        start: pair.start.clone(),
+                        end: pair.end.clone(),
+                        close: pair.close,
+                        surround: pair.surround,
+                        newline: false,
+                    })
+                    .collect::<Vec<_>>();
+
                 // Determine if the inserted text matches the opening or closing
                 // bracket of any of this language's bracket pairs.
                 let mut bracket_pair = None;
@@ -2995,12 +3201,18 @@ impl Editor {
                 if !text.is_empty() {
                     // `text` can be empty when a user is using IME (e.g. Chinese Wubi Simplified)
                     //  and they are removing the character that triggered IME popup.
-                    for (pair, enabled) in scope.brackets() {
+                    let pairs = scope
+                        .brackets()
+                        .chain(auto_pairs.iter().map(|pair| (pair, true)));
+                    for (pair, enabled) in pairs {
                         if !pair.close && !pair.surround {
                             continue;
                         }
 
-                        if enabled && pair.start.ends_with(text.as_ref()) {
+                        // Pairs disabled in the current scope aren't auto-closed, but can
+                        // still surround selected text.
+                        if (enabled || !selection.is_empty()) && pair.start.ends_with(text.as_ref())
+                        {
                             bracket_pair = Some(pair.clone());
                             is_bracket_pair_start = true;
                             break;
@@ -3014,8 +3226,12 @@ impl Editor {
                 }
 
                 if let Some(bracket_pair) = bracket_pair {
-                    let snapshot_settings = snapshot.settings_at(selection.start, cx);
-                    let autoclose = self.use_autoclose && snapshot_settings.use_autoclose;
+                    let in_string_or_comment =
+                        matches!(scope.override_name(), Some("string" | "comment"));
+                    let autoclose = self.use_autoclose
+                        && snapshot_settings.use_autoclose
+                        && (snapshot_settings.autoclose_in_strings_and_comments
+                            || !in_string_or_comment);
                     let auto_surround =
                         self.use_auto_surround && snapshot_settings.use_auto_surround;
                     if selection.is_empty() {
@@ -3188,7 +3404,12 @@ impl Editor {
             }
 
             new_selections.push((selection.map(|_| anchor), 0));
-            edits.push((selection.start..selection.end, text.clone()));
+            if let Some(padding) = block_selection_padding.get(&selection.id) {
+                let padded_text = format!("{}{}", " ".repeat(*padding), text);
+                edits.push((selection.start..selection.end, padded_text.into()));
+            } else {
+                edits.push((selection.start..selection.end, text.clone()));
+            }
         }
 
         drop(snapshot);
@@ -3638,6 +3859,9 @@ impl Editor {
         trigger_in_words: bool,
         cx: &mut ViewContext<Self>,
     ) {
+        if self.show_unicode_completions(cx) || self.show_documentation_comment_skeleton(cx) {
+            return;
+        }
         if self.is_completion_trigger(text, trigger_in_words, cx) {
             self.show_completions(
                 &ShowCompletions {
@@ -3942,6 +4166,9 @@ impl Editor {
             .filter(|(_, excerpt_visible_range, _)| !excerpt_visible_range.is_empty())
             .filter_map(|(buffer_handle, excerpt_visible_range, excerpt_id)| {
                 let buffer = buffer_handle.read(cx);
+                if buffer.is_large_file() {
+                    return None;
+                }
                 let buffer_file = project::File::from_dyn(buffer.file())?;
                 let buffer_worktree = project.worktree_for_id(buffer_file.worktree_id(cx), cx)?;
                 let worktree_entry = buffer_worktree
@@ -4425,7 +4652,13 @@ impl Editor {
                         .tasks
                         .get(&(buffer_id, buffer_row))
                         .map(|t| Arc::new(t.to_owned()));
-                    if tasks.is_none() && code_actions.is_none() {
+                    let spelling_actions = if action.deployed_from_indicator.is_none() {
+                        let position = editor.selections.newest_anchor().head();
+                        editor.spelling_actions_at(position, cx)
+                    } else {
+                        None
+                    };
+                    if tasks.is_none() && code_actions.is_none() && spelling_actions.is_none() {
                         return None;
                     }
 
@@ -4487,7 +4720,8 @@ impl Editor {
                             .map_or(false, |tasks| tasks.templates.len() == 1)
                             && code_actions
                                 .as_ref()
-                                .map_or(true, |actions| actions.is_empty());
+                                .map_or(true, |actions| actions.is_empty())
+                            && spelling_actions.is_none();
                         if let Some(task) = editor
                             .update(&mut cx, |editor, cx| {
                                 *editor.context_menu.write() =
@@ -4496,6 +4730,7 @@ impl Editor {
                                         actions: CodeActionContents {
                                             tasks: resolved_tasks,
                                             actions: code_actions,
+                                            spelling_actions,
                                         },
                                         selected_item: Default::default(),
                                         scroll_handle: UniformListScrollHandle::default(),
@@ -4547,11 +4782,10 @@ impl Editor {
         let action = actions_menu.actions.get(action_ix)?;
         let title = action.label();
         let buffer = actions_menu.buffer;
-        let workspace = self.workspace()?;
 
         match action {
             CodeActionsItem::Task(task_source_kind, resolved_task) => {
-                workspace.update(cx, |workspace, cx| {
+                self.workspace()?.update(cx, |workspace, cx| {
                     workspace::tasks::schedule_resolved_task(
                         workspace,
                         task_source_kind,
@@ -4564,6 +4798,7 @@ impl Editor {
                 })
             }
             CodeActionsItem::CodeAction(action) => {
+                let workspace = self.workspace()?;
                 let apply_code_actions = workspace
                     .read(cx)
                     .project()
@@ -4584,6 +4819,7 @@ impl Editor {
                     .await
                 }))
             }
+            CodeActionsItem::Spelling(action) => Some(self.apply_spelling_action(action, cx)),
         }
     }
 
@@ -4733,11 +4969,10 @@ impl Editor {
     }
 
     fn refresh_document_highlights(&mut self, cx: &mut ViewContext<Self>) -> Option<()> {
-        if self.pending_rename.is_some() {
+        if self.pending_rename.is_some() || self.mode != EditorMode::Full {
             return None;
         }
 
-        let project = self.project.clone()?;
         let buffer = self.buffer.read(cx);
         let newest_selection = self.selections.newest_anchor().clone();
         let cursor_position = newest_selection.head();
@@ -4748,22 +4983,69 @@ impl Editor {
             return None;
         }
 
+        let project = self.project.clone();
+        let settings = EditorSettings::get_global(cx).document_highlights;
         self.document_highlights_task = Some(cx.spawn(|this, mut cx| async move {
             cx.background_executor()
-                .timer(DOCUMENT_HIGHLIGHTS_DEBOUNCE_TIMEOUT)
+                .timer(Duration::from_millis(settings.debounce_ms))
                 .await;
 
-            let highlights = if let Some(highlights) = project
-                .update(&mut cx, |project, cx| {
-                    project.document_highlights(&cursor_buffer, cursor_buffer_position, cx)
-                })
-                .log_err()
-            {
-                highlights.await.log_err()
-            } else {
-                None
+            let highlights = match project.and_then(|project| {
+                project
+                    .update(&mut cx, |project, cx| {
+                        project.document_highlights(&cursor_buffer, cursor_buffer_position, cx)
+                    })
+                    .log_err()
+            }) {
+                Some(highlights) => {
+                    let mut timeout = cx
+                        .background_executor()
+                        .timer(DOCUMENT_HIGHLIGHTS_TIMEOUT)
+                        .fuse();
+                    futures::select_biased! {
+                        highlights = highlights.log_err().fuse() => highlights,
+                        () = timeout => None,
+                    }
+                }
+                None => None,
             };
 
+            // Highlight the word under the cursor textually when the language server doesn't
+            // highlight anything.
+            if highlights
+                .as_ref()
+                .map_or(true, |highlights| highlights.is_empty())
+                && settings.textual_fallback
+            {
+                let word_occurrences = this.update(&mut cx, |this, cx| {
+                    this.find_word_occurrences(&cursor_buffer, cursor_position, cx)
+                });
+                let word_occurrences = match word_occurrences {
+                    Ok(Some(word_occurrences)) => word_occurrences.await,
+                    Ok(None) => Vec::new(),
+                    Err(_) => return,
+                };
+                this.update(&mut cx, |this, cx| {
+                    if this.pending_rename.is_some() {
+                        return;
+                    }
+                    this.clear_background_highlights::<DocumentHighlightRead>(cx);
+                    this.clear_background_highlights::<DocumentHighlightWrite>(cx);
+                    if word_occurrences.is_empty() {
+                        this.clear_background_highlights::<WordOccurrenceHighlight>(cx);
+                    } else {
+                        this.highlight_background::<WordOccurrenceHighlight>(
+                            &word_occurrences,
+                            |theme| theme.editor_document_highlight_read_background,
+                            cx,
+                        );
+                    }
+                    cx.notify();
+                })
+                .log_err();
+                return;
+            }
+
             if let Some(highlights) = highlights {
                 this.update(&mut cx, |this, cx| {
                     if this.pending_rename.is_some() {
@@ -4815,6 +5097,7 @@ impl Editor {
                         }
                     }
 
+                    this.clear_background_highlights::<WordOccurrenceHighlight>(cx);
                     this.highlight_background::<DocumentHighlightRead>(
                         &read_ranges,
                         |theme| theme.editor_document_highlight_read_background,
@@ -4833,6 +5116,56 @@ impl Editor {
         None
     }
 
+    /// Finds the occurrences of the word under the cursor in the excerpts of its buffer, or
+    /// returns `None` when the cursor isn't on a word.
+    fn find_word_occurrences(
+        &self,
+        cursor_buffer: &Model<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<Task<Vec<Range<Anchor>>>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let (word_range, kind) = snapshot.surrounding_word(cursor_position);
+        if kind != Some(CharKind::Word) || word_range.is_empty() {
+            return None;
+        }
+        let word = snapshot.text_for_range(word_range).collect::<String>();
+        let query = SearchQuery::text(
+            word,
+            true,
+            true,
+            false,
+            Default::default(),
+            Default::default(),
+        )
+        .log_err()?;
+        let excerpts = self.buffer.read(cx).excerpts_for_buffer(cursor_buffer, cx);
+        let buffer_snapshot = cursor_buffer.read(cx).snapshot();
+
+        Some(cx.background_executor().spawn(async move {
+            let mut occurrences = Vec::new();
+            for (excerpt_id, excerpt_range) in excerpts {
+                let excerpt_range =
+                    text::ToOffset::to_offset(&excerpt_range.context.start, &buffer_snapshot)
+                        ..text::ToOffset::to_offset(&excerpt_range.context.end, &buffer_snapshot);
+                for range in query
+                    .search(&buffer_snapshot, Some(excerpt_range.clone()))
+                    .await
+                {
+                    let start = buffer_snapshot.anchor_after(excerpt_range.start + range.start);
+                    let end = buffer_snapshot.anchor_before(excerpt_range.start + range.end);
+                    if let Some((start, end)) = snapshot
+                        .anchor_in_excerpt(excerpt_id, start)
+                        .zip(snapshot.anchor_in_excerpt(excerpt_id, end))
+                    {
+                        occurrences.push(start..end);
+                    }
+                }
+            }
+            occurrences
+        }))
+    }
+
     fn refresh_inline_completion(
         &mut self,
         debounce: bool,
@@ -5202,6 +5535,21 @@ impl Editor {
         insertion_ranges: &[Range<usize>],
         snippet: Snippet,
         cx: &mut ViewContext<Self>,
+    ) -> Result<()> {
+        self.insert_snippet_with_autoindent(
+            insertion_ranges,
+            snippet,
+            Some(AutoindentMode::EachLine),
+            cx,
+        )
+    }
+
+    pub fn insert_snippet_with_autoindent(
+        &mut self,
+        insertion_ranges: &[Range<usize>],
+        snippet: Snippet,
+        autoindent_mode: Option<AutoindentMode>,
+        cx: &mut ViewContext<Self>,
     ) -> Result<()> {
         struct Tabstop<T> {
             is_end_tabstop: bool,
@@ -5215,7 +5563,7 @@ impl Editor {
                     .iter()
                     .cloned()
                     .map(|range| (range, snippet_text.clone())),
-                Some(AutoindentMode::EachLine),
+                autoindent_mode,
                 cx,
             );
 
@@ -5921,6 +6269,32 @@ impl Editor {
         }
     }
 
+    pub fn stage_selected_hunks(&mut self, _: &StageSelectedHunks, cx: &mut ViewContext<Self>) {
+        let rows = self.buffer_rows_for_selections(cx);
+        self.update_index_for_rows(rows, true, cx);
+    }
+
+    pub fn unstage_selected_hunks(&mut self, _: &UnstageSelectedHunks, cx: &mut ViewContext<Self>) {
+        let rows = self.buffer_rows_for_selections(cx);
+        self.update_index_for_rows(rows, false, cx);
+    }
+
+    /// Returns the rows of the buffers that the selections span.
+    fn buffer_rows_for_selections(&self, cx: &AppContext) -> Vec<(Model<Buffer>, Range<u32>)> {
+        let multi_buffer = self.buffer.read(cx);
+        self.selections
+            .disjoint_anchor_ranges()
+            .into_iter()
+            .flat_map(|range| multi_buffer.range_to_buffer_ranges(range, cx))
+            .map(|(buffer, range, _)| {
+                let snapshot = buffer.read(cx).snapshot();
+                let rows = snapshot.offset_to_point(range.start).row
+                    ..snapshot.offset_to_point(range.end).row + 1;
+                (buffer, rows)
+            })
+            .collect()
+    }
+
     pub fn open_active_item_in_terminal(&mut self, _: &OpenInTerminal, cx: &mut ViewContext<Self>) {
         if let Some(working_directory) = self.active_excerpt(cx).and_then(|(_, buffer, _)| {
             let project_path = buffer.read(cx).project_path(cx)?;
@@ -5995,7 +6369,21 @@ impl Editor {
 
         let mut edits = Vec::new();
 
-        let selections = self.selections.all::<Point>(cx);
+        let mut selections = self.selections.all::<Point>(cx);
+        // Without any text selected, manipulate all the lines of the buffer. Excerpts of a
+        // multi-buffer aren't manipulated together, so cursors keep manipulating their line there.
+        if self.buffer.read(cx).is_singleton()
+            && selections.iter().all(|selection| selection.is_empty())
+        {
+            let newest_selection = self.selections.newest::<Point>(cx);
+            selections = vec![Selection {
+                id: newest_selection.id,
+                start: Point::zero(),
+                end: buffer.max_point(),
+                goal: SelectionGoal::None,
+                reversed: false,
+            }];
+        }
         let mut selections = selections.iter().peekable();
         let mut contiguous_row_selections = Vec::new();
         let mut new_selections = Vec::new();
@@ -6085,21 +6473,15 @@ impl Editor {
     }
 
     pub fn convert_to_title_case(&mut self, _: &ConvertToTitleCase, cx: &mut ViewContext<Self>) {
-        self.manipulate_text(cx, |text| {
-            // Hack to get around the fact that to_case crate doesn't support '\n' as a word boundary
-            // https://github.com/rutrum/convert-case/issues/16
-            text.split('\n')
-                .map(|line| line.to_case(Case::Title))
-                .join("\n")
-        })
+        self.manipulate_text(cx, |text| convert_identifier_case(text, Case::Title))
     }
 
     pub fn convert_to_snake_case(&mut self, _: &ConvertToSnakeCase, cx: &mut ViewContext<Self>) {
-        self.manipulate_text(cx, |text| text.to_case(Case::Snake))
+        self.manipulate_text(cx, |text| convert_identifier_case(text, Case::Snake))
     }
 
     pub fn convert_to_kebab_case(&mut self, _: &ConvertToKebabCase, cx: &mut ViewContext<Self>) {
-        self.manipulate_text(cx, |text| text.to_case(Case::Kebab))
+        self.manipulate_text(cx, |text| convert_identifier_case(text, Case::Kebab))
     }
 
     pub fn convert_to_upper_camel_case(
@@ -6107,13 +6489,7 @@ impl Editor {
         _: &ConvertToUpperCamelCase,
         cx: &mut ViewContext<Self>,
     ) {
-        self.manipulate_text(cx, |text| {
-            // Hack to get around the fact that to_case crate doesn't support '\n' as a word boundary
-            // https://github.com/rutrum/convert-case/issues/16
-            text.split('\n')
-                .map(|line| line.to_case(Case::UpperCamel))
-                .join("\n")
-        })
+        self.manipulate_text(cx, |text| convert_identifier_case(text, Case::UpperCamel))
     }
 
     pub fn convert_to_lower_camel_case(
@@ -6121,7 +6497,7 @@ impl Editor {
         _: &ConvertToLowerCamelCase,
         cx: &mut ViewContext<Self>,
     ) {
-        self.manipulate_text(cx, |text| text.to_case(Case::Camel))
+        self.manipulate_text(cx, |text| convert_identifier_case(text, Case::Camel))
     }
 
     pub fn convert_to_opposite_case(
@@ -6254,6 +6630,101 @@ impl Editor {
         self.duplicate_line(false, cx);
     }
 
+    /// Inserts a copy of each selection's text right after it, keeping the selections on the
+    /// original text. When nothing is selected, the cursors' lines are duplicated instead.
+    pub fn duplicate_selection(&mut self, _: &DuplicateSelection, cx: &mut ViewContext<Self>) {
+        let selections = self.selections.all::<usize>(cx);
+        if selections.iter().all(|selection| selection.is_empty()) {
+            self.duplicate_line(false, cx);
+            return;
+        }
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut new_selections = Vec::new();
+        let mut inserted_len = 0;
+        for mut selection in selections {
+            let text = buffer.text_for_range(selection.range()).collect::<String>();
+            let insert_location = selection.end;
+            selection.start += inserted_len;
+            selection.end += inserted_len;
+            new_selections.push(selection);
+            if !text.is_empty() {
+                inserted_len += text.len();
+                edits.push((insert_location..insert_location, text));
+            }
+        }
+
+        self.transact(cx, |this, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(Some(Autoscroll::fit()), cx, |s| s.select(new_selections));
+        });
+    }
+
+    /// Copies the selected lines below themselves and comments out the original ones, moving
+    /// the selections to the copy.
+    pub fn duplicate_line_and_comment(
+        &mut self,
+        _: &DuplicateLineAndComment,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        let selections = self.selections.all::<Point>(cx);
+
+        let mut edits = Vec::new();
+        let mut original_ranges = Vec::new();
+        let mut new_selections = Vec::new();
+        let mut inserted_rows = 0;
+        let mut selections_iter = selections.into_iter().peekable();
+        while let Some(selection) = selections_iter.next() {
+            // Avoid duplicating the same lines twice.
+            let mut rows = selection.spanned_rows(false, &display_map);
+            let mut row_selections = vec![selection];
+            while let Some(next_selection) = selections_iter.peek() {
+                let next_rows = next_selection.spanned_rows(false, &display_map);
+                if next_rows.start < rows.end {
+                    rows.end = next_rows.end;
+                    row_selections.push(selections_iter.next().unwrap());
+                } else {
+                    break;
+                }
+            }
+
+            let start = Point::new(rows.start.0, 0);
+            let end = Point::new(
+                rows.end.previous_row().0,
+                buffer.line_len(rows.end.previous_row()),
+            );
+            let mut text = String::from("\n");
+            text.extend(buffer.text_for_range(start..end));
+            edits.push((end..end, text));
+
+            let row_count = rows.end.0 - rows.start.0;
+            original_ranges.push(
+                Point::new(start.row + inserted_rows, 0)
+                    ..Point::new(end.row + inserted_rows, end.column),
+            );
+            for mut selection in row_selections {
+                selection.start.row += inserted_rows + row_count;
+                selection.end.row += inserted_rows + row_count;
+                new_selections.push(selection);
+            }
+            inserted_rows += row_count;
+        }
+
+        self.transact(cx, |this, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(None, cx, |s| s.select_ranges(original_ranges));
+            this.toggle_comments(&ToggleComments::default(), cx);
+            this.change_selections(Some(Autoscroll::fit()), cx, |s| s.select(new_selections));
+        });
+    }
+
     pub fn move_line_up(&mut self, _: &MoveLineUp, cx: &mut ViewContext<Self>) {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
@@ -6508,29 +6979,103 @@ impl Editor {
         });
     }
 
-    pub fn cut(&mut self, _: &Cut, cx: &mut ViewContext<Self>) {
-        let mut text = String::new();
-        let buffer = self.buffer.read(cx).snapshot(cx);
-        let mut selections = self.selections.all::<Point>(cx);
-        let mut clipboard_selections = Vec::with_capacity(selections.len());
-        {
-            let max_point = buffer.max_point();
-            let mut is_first = true;
-            for selection in &mut selections {
-                let is_entire_line = selection.is_empty() || self.selections.line_mode;
-                if is_entire_line {
-                    selection.start = Point::new(selection.start.row, 0);
-                    selection.end = cmp::min(max_point, Point::new(selection.end.row + 1, 0));
-                    selection.goal = SelectionGoal::None;
-                }
-                if is_first {
-                    is_first = false;
-                } else {
-                    text += "\n";
-                }
-                let mut len = 0;
-                for chunk in buffer.text_for_range(selection.start..selection.end) {
-                    text.push_str(chunk);
+    /// Inserts spaces before the selections so that they start at the same column. When
+    /// several selections start on the same line, each one is aligned with the selections
+    /// having the same position on the other lines.
+    pub fn align_selections(&mut self, _: &AlignSelections, cx: &mut ViewContext<Self>) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        let selections = self.selections.all::<Point>(cx);
+        if selections.len() < 2 {
+            return;
+        }
+
+        // The starts and display columns of the selections of each display row, with the
+        // padding inserted before them so far. Columns are counted on the display text, so
+        // that tabs count for the width they are expanded to.
+        let mut rows = BTreeMap::<DisplayRow, (Vec<(Point, usize)>, usize)>::new();
+        for selection in &selections {
+            let start = selection.start.to_display_point(&display_map);
+            let column = display_map
+                .display_chars_at(DisplayPoint::new(start.row(), 0))
+                .take_while(|(_, point)| *point < start)
+                .count();
+            rows.entry(start.row())
+                .or_default()
+                .0
+                .push((start.to_point(&display_map), column));
+        }
+
+        let mut edits = Vec::new();
+        let max_selections_per_row = rows.values().map(|(starts, _)| starts.len()).max();
+        for ix in 0..max_selections_per_row.unwrap_or(0) {
+            let target_column = rows
+                .values()
+                .filter_map(|(starts, padding)| Some(starts.get(ix)?.1 + padding))
+                .max()
+                .unwrap_or(0);
+            for (starts, padding) in rows.values_mut() {
+                let Some((start, column)) = starts.get(ix) else {
+                    continue;
+                };
+                let missing_columns = target_column - (column + *padding);
+                if missing_columns > 0 {
+                    edits.push((*start..*start, " ".repeat(missing_columns)));
+                    *padding += missing_columns;
+                }
+            }
+        }
+        if edits.is_empty() {
+            return;
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+
+        // Anchor the selections after the inserted padding.
+        let new_selections = selections
+            .into_iter()
+            .map(|selection| Selection {
+                id: selection.id,
+                start: buffer.anchor_after(selection.start),
+                end: buffer.anchor_after(selection.end),
+                reversed: selection.reversed,
+                goal: SelectionGoal::None,
+            })
+            .collect::<Vec<_>>();
+        self.transact(cx, |this, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select_anchors(new_selections);
+            });
+        });
+    }
+
+    pub fn cut(&mut self, _: &Cut, cx: &mut ViewContext<Self>) {
+        let mut text = String::new();
+        let block_selection_padding = self.block_selection_padding(cx);
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut selections = self.selections.all::<Point>(cx);
+        let is_block = self.is_block_selection(&selections, &block_selection_padding);
+        let mut clipboard_selections = Vec::with_capacity(selections.len());
+        {
+            let max_point = buffer.max_point();
+            let mut is_first = true;
+            for selection in &mut selections {
+                let is_entire_line =
+                    !is_block && (selection.is_empty() || self.selections.line_mode);
+                if is_entire_line {
+                    selection.start = Point::new(selection.start.row, 0);
+                    selection.end = cmp::min(max_point, Point::new(selection.end.row + 1, 0));
+                    selection.goal = SelectionGoal::None;
+                }
+                if is_first {
+                    is_first = false;
+                } else {
+                    text += "\n";
+                }
+                let mut len = 0;
+                for chunk in buffer.text_for_range(selection.start..selection.end) {
+                    text.push_str(chunk);
                     len += chunk.len();
                 }
                 clipboard_selections.push(ClipboardSelection {
@@ -6539,6 +7084,7 @@ impl Editor {
                     first_line_indent: buffer
                         .indent_size_for_line(MultiBufferRow(selection.start.row))
                         .len,
+                    is_block,
                 });
             }
         }
@@ -6553,7 +7099,9 @@ impl Editor {
     }
 
     pub fn copy(&mut self, _: &Copy, cx: &mut ViewContext<Self>) {
+        let block_selection_padding = self.block_selection_padding(cx);
         let selections = self.selections.all::<Point>(cx);
+        let is_block = self.is_block_selection(&selections, &block_selection_padding);
         let buffer = self.buffer.read(cx).read(cx);
         let mut text = String::new();
 
@@ -6564,7 +7112,8 @@ impl Editor {
             for selection in selections.iter() {
                 let mut start = selection.start;
                 let mut end = selection.end;
-                let is_entire_line = selection.is_empty() || self.selections.line_mode;
+                let is_entire_line =
+                    !is_block && (selection.is_empty() || self.selections.line_mode);
                 if is_entire_line {
                     start = Point::new(start.row, 0);
                     end = cmp::min(max_point, Point::new(end.row + 1, 0));
@@ -6583,6 +7132,7 @@ impl Editor {
                     len,
                     is_entire_line,
                     first_line_indent: buffer.indent_size_for_line(MultiBufferRow(start.row)).len,
+                    is_block,
                 });
             }
         }
@@ -6590,12 +7140,49 @@ impl Editor {
         cx.write_to_clipboard(ClipboardItem::new(text).with_metadata(clipboard_selections));
     }
 
+    /// Whether the given selections are the rows of the active block selection, with each
+    /// row either selecting text or being a cursor padded out to the block.
+    fn is_block_selection(
+        &self,
+        selections: &[Selection<Point>],
+        block_selection_padding: &HashMap<usize, usize>,
+    ) -> bool {
+        self.block_selection.is_some()
+            && !self.selections.line_mode
+            && selections.len() > 1
+            && selections.iter().all(|selection| {
+                selection.start.row == selection.end.row
+                    && (!selection.is_empty()
+                        || block_selection_padding.contains_key(&selection.id))
+            })
+    }
+
     pub fn do_paste(
         &mut self,
         text: &String,
         clipboard_selections: Option<Vec<ClipboardSelection>>,
         handle_entire_lines: bool,
         cx: &mut ViewContext<Self>,
+    ) {
+        let auto_indent = EditorSettings::get_global(cx).auto_indent_on_paste;
+        self.paste_text(
+            text,
+            clipboard_selections,
+            handle_entire_lines,
+            auto_indent,
+            cx,
+        );
+    }
+
+    /// Pastes the text, re-indenting it to match the indentation of the lines it's pasted
+    /// on when `auto_indent` is true.
+    fn paste_text(
+        &mut self,
+        text: &String,
+        clipboard_selections: Option<Vec<ClipboardSelection>>,
+        handle_entire_lines: bool,
+        auto_indent: bool,
+        cx: &mut ViewContext<Self>,
     ) {
         if self.read_only(cx) {
             return;
@@ -6605,7 +7192,26 @@ impl Editor {
 
         self.transact(cx, |this, cx| {
             if let Some(mut clipboard_selections) = clipboard_selections {
+                let block_selection_padding = this.block_selection_padding(cx);
                 let old_selections = this.selections.all::<usize>(cx);
+                if old_selections.len() == 1
+                    && clipboard_selections.len() > 1
+                    && clipboard_selections.iter().all(|s| s.is_block)
+                {
+                    let mut start_offset = 0;
+                    let rows = clipboard_selections
+                        .iter()
+                        .map(|clipboard_selection| {
+                            let end_offset = start_offset + clipboard_selection.len;
+                            let row = &clipboard_text[start_offset..end_offset];
+                            start_offset = end_offset + 1;
+                            row
+                        })
+                        .collect::<Vec<_>>();
+                    this.paste_block(&rows, cx);
+                    return;
+                }
+
                 let all_selections_were_entire_line =
                     clipboard_selections.iter().all(|s| s.is_entire_line);
                 let first_selection_indent_column =
@@ -6647,6 +7253,12 @@ impl Editor {
                             selection.range()
                         };
 
+                        let to_insert = match block_selection_padding.get(&selection.id) {
+                            Some(padding) if !entire_line => {
+                                Cow::Owned(format!("{}{}", " ".repeat(*padding), to_insert))
+                            }
+                            _ => Cow::Borrowed(to_insert),
+                        };
                         edits.push((range, to_insert));
                         original_indent_columns.extend(original_indent_column);
                     }
@@ -6654,7 +7266,7 @@ impl Editor {
 
                     buffer.edit(
                         edits,
-                        Some(AutoindentMode::Block {
+                        auto_indent.then(|| AutoindentMode::Block {
                             original_indent_columns,
                         }),
                         cx,
@@ -6664,11 +7276,81 @@ impl Editor {
                 let selections = this.selections.all::<usize>(cx);
                 this.change_selections(Some(Autoscroll::fit()), cx, |s| s.select(selections));
             } else {
-                this.insert(&clipboard_text, cx);
+                // Text copied outside of Zed has no metadata about its original indentation.
+                let autoindent = auto_indent.then(|| AutoindentMode::Block {
+                    original_indent_columns: vec![
+                        original_indent_column(&clipboard_text);
+                        this.selections.count()
+                    ],
+                });
+                this.insert_with_autoindent_mode(&clipboard_text, autoindent, cx);
             }
         });
     }
 
+    /// Pastes the rows of a block selection at the newest selection, inserting each row at the
+    /// same display column on successive lines. Lines too short to reach that column are padded
+    /// with spaces, and new lines are appended when the block extends past the end of the buffer.
+    fn paste_block(&mut self, rows: &[&str], cx: &mut ViewContext<Self>) {
+        self.insert("", cx);
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let start = self
+            .selections
+            .newest::<Point>(cx)
+            .head()
+            .to_display_point(&display_map);
+        let column = start.column();
+        let max_row = display_map.max_point().row();
+
+        let mut edits = Vec::new();
+        let mut cursors = Vec::new();
+        let mut appended_text = String::new();
+        let mut appended_row = display_map.buffer_snapshot.max_point().row;
+        for (ix, row_text) in rows.iter().enumerate() {
+            let row = DisplayRow(start.row().0 + ix as u32);
+            if row <= max_row {
+                let line_len = display_map.line_len(row);
+                let padding = " ".repeat(column.saturating_sub(line_len) as usize);
+                let position = display_map
+                    .clip_point(DisplayPoint::new(row, column.min(line_len)), Bias::Left)
+                    .to_point(&display_map);
+                let text = format!("{padding}{row_text}");
+                cursors.push(Point::new(
+                    position.row,
+                    position.column + text.len() as u32,
+                ));
+                edits.push((position..position, text));
+            } else {
+                let padding = " ".repeat(column as usize);
+                appended_row += 1;
+                cursors.push(Point::new(
+                    appended_row,
+                    (padding.len() + row_text.len()) as u32,
+                ));
+                appended_text.push('\n');
+                appended_text.push_str(&padding);
+                appended_text.push_str(row_text);
+            }
+        }
+        if !appended_text.is_empty() {
+            let end = display_map.buffer_snapshot.max_point();
+            match edits.last_mut() {
+                Some((range, text)) if range.start == end => text.push_str(&appended_text),
+                _ => edits.push((end..end, appended_text)),
+            }
+        }
+
+        self.buffer
+            .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+        self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+            s.select_ranges(cursors.into_iter().map(|cursor| cursor..cursor));
+        });
+        self.block_selection = Some(BlockSelection {
+            start_column: column,
+        });
+    }
+
     pub fn paste(&mut self, _: &Paste, cx: &mut ViewContext<Self>) {
         if let Some(item) = cx.read_from_clipboard() {
             self.do_paste(
@@ -6680,6 +7362,22 @@ impl Editor {
         };
     }
 
+    pub fn paste_without_formatting(
+        &mut self,
+        _: &PasteWithoutFormatting,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(item) = cx.read_from_clipboard() {
+            self.paste_text(
+                item.text(),
+                item.metadata::<Vec<ClipboardSelection>>(),
+                true,
+                false,
+                cx,
+            )
+        };
+    }
+
     pub fn undo(&mut self, _: &Undo, cx: &mut ViewContext<Self>) {
         if self.read_only(cx) {
             return;
@@ -7680,11 +8378,16 @@ impl Editor {
             state.stack.pop();
         }
 
+        let start_column = new_selections
+            .iter()
+            .map(|selection| selection.start.to_display_point(&display_map).column())
+            .min();
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
             s.select(new_selections);
         });
         if state.stack.len() > 1 {
             self.add_selections_state = Some(state);
+            self.block_selection = start_column.map(|start_column| BlockSelection { start_column });
         }
     }
 
@@ -8413,7 +9116,7 @@ impl Editor {
         let buffer = self.buffer.read(cx).snapshot(cx);
         let old_selections = self.selections.all::<usize>(cx).into_boxed_slice();
 
-        let mut stack = mem::take(&mut self.select_larger_syntax_node_stack);
+        let mut history = mem::take(&mut self.select_syntax_node_history);
         let mut selected_larger_node = false;
         let new_selections = old_selections
             .iter()
@@ -8443,12 +9146,13 @@ impl Editor {
             .collect::<Vec<_>>();
 
         if selected_larger_node {
-            stack.push(old_selections);
+            let previous = self.selections.disjoint_anchors();
             self.change_selections(Some(Autoscroll::fit()), cx, |s| {
                 s.select(new_selections);
             });
+            history.push(previous, self.selections.disjoint_anchors());
         }
-        self.select_larger_syntax_node_stack = stack;
+        self.select_syntax_node_history = history;
     }
 
     pub fn select_smaller_syntax_node(
@@ -8456,13 +9160,79 @@ impl Editor {
         _: &SelectSmallerSyntaxNode,
         cx: &mut ViewContext<Self>,
     ) {
-        let mut stack = mem::take(&mut self.select_larger_syntax_node_stack);
-        if let Some(selections) = stack.pop() {
+        let mut history = mem::take(&mut self.select_syntax_node_history);
+        if let Some(selections) = history.stack.pop() {
             self.change_selections(Some(Autoscroll::fit()), cx, |s| {
-                s.select(selections.to_vec());
+                s.select_anchors(selections.to_vec());
             });
+            history.current = Some(self.selections.disjoint_anchors());
         }
-        self.select_larger_syntax_node_stack = stack;
+        self.select_syntax_node_history = history;
+    }
+
+    pub fn select_enclosing_string(
+        &mut self,
+        _: &SelectEnclosingString,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.select_enclosing_text_object(TextObject::String, cx);
+    }
+
+    pub fn select_enclosing_comment(
+        &mut self,
+        _: &SelectEnclosingComment,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.select_enclosing_text_object(TextObject::Comment, cx);
+    }
+
+    pub fn select_enclosing_argument(
+        &mut self,
+        _: &SelectEnclosingArgument,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.select_enclosing_text_object(TextObject::Argument, cx);
+    }
+
+    /// Expands each selection to the smallest text object of the given kind that contains it,
+    /// so that shrinking the selections returns to them.
+    fn select_enclosing_text_object(
+        &mut self,
+        text_object: TextObject,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let old_selections = self.selections.all::<usize>(cx);
+
+        let mut history = mem::take(&mut self.select_syntax_node_history);
+        let mut selected_text_object = false;
+        let new_selections = old_selections
+            .iter()
+            .map(
+                |selection| match buffer.range_for_text_object(selection.range(), text_object) {
+                    Some(range) => {
+                        selected_text_object = true;
+                        Selection {
+                            id: selection.id,
+                            start: range.start,
+                            end: range.end,
+                            goal: SelectionGoal::None,
+                            reversed: selection.reversed,
+                        }
+                    }
+                    None => selection.clone(),
+                },
+            )
+            .collect::<Vec<_>>();
+
+        if selected_text_object {
+            let previous = self.selections.disjoint_anchors();
+            self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select(new_selections);
+            });
+            history.push(previous, self.selections.disjoint_anchors());
+        }
+        self.select_syntax_node_history = history;
     }
 
     fn refresh_runnables(&mut self, cx: &mut ViewContext<Self>) -> Task<()> {
@@ -8751,15 +9521,196 @@ impl Editor {
         })
     }
 
-    fn go_to_diagnostic(&mut self, _: &GoToDiagnostic, cx: &mut ViewContext<Self>) {
-        self.go_to_diagnostic_impl(Direction::Next, cx)
+    fn go_to_diagnostic(&mut self, action: &GoToDiagnostic, cx: &mut ViewContext<Self>) {
+        self.go_to_diagnostic_in_direction(
+            Direction::Next,
+            action.severity,
+            action.project_wide,
+            cx,
+        )
+    }
+
+    fn go_to_prev_diagnostic(&mut self, action: &GoToPrevDiagnostic, cx: &mut ViewContext<Self>) {
+        self.go_to_diagnostic_in_direction(
+            Direction::Prev,
+            action.severity,
+            action.project_wide,
+            cx,
+        )
+    }
+
+    /// Goes to the next or previous diagnostic of the given severity, and shows its position
+    /// among the others. After the last diagnostic of the buffer, it wraps around to the other
+    /// end of the buffer, or goes on to the next file with diagnostics when `project_wide` is set.
+    fn go_to_diagnostic_in_direction(
+        &mut self,
+        direction: Direction,
+        severity: DiagnosticSeverityFilter,
+        project_wide: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let project_path = self
+            .buffer
+            .read(cx)
+            .as_singleton()
+            .and_then(|buffer| buffer.read(cx).project_path(cx))
+            .filter(|_| project_wide);
+        let Some(project_path) = project_path else {
+            if self.go_to_diagnostic_impl(direction, severity, true, cx) {
+                self.show_diagnostic_position(severity, None, cx);
+            }
+            return;
+        };
+        if self.go_to_diagnostic_impl(direction, severity, false, cx) {
+            self.show_diagnostic_position(severity, Some(&project_path), cx);
+            return;
+        }
+
+        let paths = self.paths_with_diagnostics(severity, cx);
+        let next_path = match direction {
+            Direction::Next => paths
+                .iter()
+                .find(|(path, _)| *path > project_path)
+                .or(paths.first()),
+            Direction::Prev => paths
+                .iter()
+                .rev()
+                .find(|(path, _)| *path < project_path)
+                .or(paths.last()),
+        }
+        .map(|(path, _)| path.clone())
+        .filter(|path| *path != project_path);
+        let (Some(path), Some(workspace)) = (next_path, self.workspace()) else {
+            if self.go_to_diagnostic_impl(direction, severity, true, cx) {
+                self.show_diagnostic_position(severity, Some(&project_path), cx);
+            }
+            return;
+        };
+
+        let open = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(path.clone(), None, true, cx)
+        });
+        cx.spawn(|_, mut cx| async move {
+            let editor = open
+                .await?
+                .downcast::<Editor>()
+                .context("the file with diagnostics was not opened in an editor")?;
+            editor.update(&mut cx, |editor, cx| {
+                let offset = match direction {
+                    Direction::Next => 0,
+                    Direction::Prev => editor.buffer.read(cx).len(cx),
+                };
+                editor.dismiss_diagnostics(cx);
+                editor.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                    s.select_ranges([offset..offset])
+                });
+                if editor.go_to_diagnostic_impl(direction, severity, true, cx) {
+                    editor.show_diagnostic_position(severity, Some(&path), cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
-    fn go_to_prev_diagnostic(&mut self, _: &GoToPrevDiagnostic, cx: &mut ViewContext<Self>) {
-        self.go_to_diagnostic_impl(Direction::Prev, cx)
+    /// Returns the paths of the project's files with diagnostics of the given severity, in path
+    /// order, along with how many of them they have.
+    fn paths_with_diagnostics(
+        &self,
+        severity: DiagnosticSeverityFilter,
+        cx: &AppContext,
+    ) -> Vec<(ProjectPath, usize)> {
+        let Some(project) = self.project.as_ref() else {
+            return Vec::new();
+        };
+        let mut counts = BTreeMap::<ProjectPath, usize>::default();
+        for (path, _, summary) in project.read(cx).diagnostic_summaries(false, cx) {
+            let count = severity.count(&summary);
+            if count > 0 {
+                *counts.entry(path).or_default() += count;
+            }
+        }
+        counts.into_iter().collect()
     }
 
-    pub fn go_to_diagnostic_impl(&mut self, direction: Direction, cx: &mut ViewContext<Self>) {
+    /// Shows the position of the active diagnostic among the ones of the given severity, such as
+    /// "3/17 errors", counting the ones of the other files of the project when there's a path.
+    fn show_diagnostic_position(
+        &mut self,
+        severity: DiagnosticSeverityFilter,
+        project_path: Option<&ProjectPath>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(group_id) = self
+            .active_diagnostics
+            .as_ref()
+            .map(|active| active.group_id)
+        else {
+            return;
+        };
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let group_ids = buffer
+            .diagnostics_in_range::<_, usize>(0..buffer.len(), false)
+            .filter(|entry| {
+                entry.diagnostic.is_primary
+                    && severity.includes(entry.diagnostic.severity)
+                    && !entry.range.is_empty()
+            })
+            .map(|entry| entry.diagnostic.group_id)
+            .collect::<Vec<_>>();
+        let Some(ix) = group_ids.iter().position(|id| *id == group_id) else {
+            return;
+        };
+        let mut position = ix + 1;
+        let mut total = group_ids.len();
+        if let Some(project_path) = project_path {
+            for (path, count) in self.paths_with_diagnostics(severity, cx) {
+                if path < *project_path {
+                    position += count;
+                    total += count;
+                } else if path > *project_path {
+                    total += count;
+                }
+            }
+        }
+
+        let noun = match severity {
+            DiagnosticSeverityFilter::Error => "error",
+            DiagnosticSeverityFilter::Warning => "diagnostic",
+        };
+        let message = format!(
+            "{position}/{total} {noun}{}",
+            if total == 1 { "" } else { "s" }
+        );
+        let workspace = workspace.downgrade();
+        cx.defer(move |_, cx| {
+            struct DiagnosticPosition;
+
+            workspace
+                .update(cx, |workspace, cx| {
+                    workspace.show_toast(
+                        Toast::new(NotificationId::unique::<DiagnosticPosition>(), message)
+                            .autohide(),
+                        cx,
+                    )
+                })
+                .ok();
+        });
+    }
+
+    /// Goes to the next or previous diagnostic of the given severity, returning whether there
+    /// was one. Unless `wrap_around` is set, only the diagnostics after or before the cursor are
+    /// considered.
+    pub fn go_to_diagnostic_impl(
+        &mut self,
+        direction: Direction,
+        severity: DiagnosticSeverityFilter,
+        wrap_around: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> bool {
         let buffer = self.buffer.read(cx).snapshot(cx);
         let selection = self.selections.newest::<usize>(cx);
 
@@ -8774,7 +9725,7 @@ impl Editor {
                         s.select_anchors(vec![new_selection.clone()]);
                     });
                 }
-                return;
+                return true;
             }
         }
 
@@ -8816,7 +9767,7 @@ impl Editor {
                 })
                 .find_map(|entry| {
                     if entry.diagnostic.is_primary
-                        && entry.diagnostic.severity <= DiagnosticSeverity::WARNING
+                        && severity.includes(entry.diagnostic.severity)
                         && !entry.range.is_empty()
                         // if we match with the active diagnostic, skip it
                         && Some(entry.diagnostic.group_id)
@@ -8840,19 +9791,21 @@ impl Editor {
                         }]);
                     });
                 }
-                break;
+                return true;
+            } else if !wrap_around {
+                return false;
             } else {
                 // Cycle around to the start of the buffer, potentially moving back to the start of
                 // the currently active diagnostic.
                 active_primary_range.take();
                 if direction == Direction::Prev {
                     if search_start == buffer.len() {
-                        break;
+                        return false;
                     } else {
                         search_start = buffer.len();
                     }
                 } else if search_start == 0 {
-                    break;
+                    return false;
                 } else {
                     search_start = 0;
                 }
@@ -8916,6 +9869,120 @@ impl Editor {
         }
     }
 
+    pub fn toggle_bookmark(&mut self, action: &ToggleBookmark, cx: &mut ViewContext<Self>) {
+        let Some(project) = self.project.as_ref() else {
+            return;
+        };
+        let bookmark_store = project.read(cx).bookmark_store().clone();
+        let label = action.label.clone().map(SharedString::from);
+
+        let mut rows_to_toggle = Vec::new();
+        let multi_buffer = self.buffer.read(cx);
+        for selection in self.selections.all::<Point>(cx) {
+            let Some((buffer, offset, _)) =
+                multi_buffer.point_to_buffer_offset(selection.head(), cx)
+            else {
+                continue;
+            };
+            let row = buffer.read(cx).offset_to_point(offset).row;
+            if !rows_to_toggle.contains(&(buffer.clone(), row)) {
+                rows_to_toggle.push((buffer, row));
+            }
+        }
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            for (buffer, row) in rows_to_toggle {
+                bookmark_store.toggle(buffer, row, label.clone(), cx);
+            }
+        });
+    }
+
+    fn go_to_next_bookmark(&mut self, _: &GoToNextBookmark, cx: &mut ViewContext<Self>) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let head_row = self.selections.newest::<Point>(cx).head().row;
+        let bookmarks = self.bookmarks(cx);
+        let next_bookmark = bookmarks
+            .iter()
+            .find(|bookmark| bookmark.to_point(&snapshot).row > head_row)
+            .or_else(|| bookmarks.first());
+        if let Some(bookmark) = next_bookmark {
+            self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select_anchor_ranges([*bookmark..*bookmark]);
+            });
+        }
+    }
+
+    fn go_to_prev_bookmark(&mut self, _: &GoToPrevBookmark, cx: &mut ViewContext<Self>) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let head_row = self.selections.newest::<Point>(cx).head().row;
+        let bookmarks = self.bookmarks(cx);
+        let prev_bookmark = bookmarks
+            .iter()
+            .rev()
+            .find(|bookmark| bookmark.to_point(&snapshot).row < head_row)
+            .or_else(|| bookmarks.last());
+        if let Some(bookmark) = prev_bookmark {
+            self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select_anchor_ranges([*bookmark..*bookmark]);
+            });
+        }
+    }
+
+    /// Returns the positions of the bookmarks in the excerpts of this editor, in order.
+    pub fn bookmarks(&self, cx: &AppContext) -> Vec<Anchor> {
+        let Some(project) = self.project.as_ref() else {
+            return Vec::new();
+        };
+        let bookmark_store = project.read(cx).bookmark_store().read(cx);
+        if bookmark_store.is_empty() {
+            return Vec::new();
+        }
+
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut bookmarks = Vec::new();
+        for buffer_bookmarks in bookmark_store.buffers() {
+            let buffer = buffer_bookmarks.buffer.read(cx);
+            for (excerpt_id, range) in
+                multi_buffer.excerpts_for_buffer(&buffer_bookmarks.buffer, cx)
+            {
+                bookmarks.extend(
+                    buffer_bookmarks
+                        .bookmarks
+                        .iter()
+                        .filter(|bookmark| {
+                            range.context.start.cmp(&bookmark.position, buffer).is_le()
+                                && range.context.end.cmp(&bookmark.position, buffer).is_ge()
+                        })
+                        .filter_map(|bookmark| {
+                            snapshot.anchor_in_excerpt(excerpt_id, bookmark.position)
+                        }),
+                );
+            }
+        }
+        bookmarks.sort_by(|a, b| a.cmp(b, &snapshot));
+        bookmarks
+    }
+
+    fn render_bookmark_indicator(&self, row: DisplayRow, cx: &mut ViewContext<Self>) -> IconButton {
+        IconButton::new(
+            ("bookmark_indicator", row.0 as usize),
+            ui::IconName::Bookmark,
+        )
+        .shape(ui::IconButtonShape::Square)
+        .icon_size(IconSize::XSmall)
+        .icon_color(Color::Accent)
+        .tooltip(|cx| Tooltip::for_action("Remove bookmark", &ToggleBookmark::default(), cx))
+        .on_click(cx.listener(move |editor, _e, cx| {
+            editor.focus(cx);
+            editor.change_selections(None, cx, |s| {
+                let point = DisplayPoint::new(row, 0);
+                s.select_display_ranges([point..point]);
+            });
+            editor.toggle_bookmark(&ToggleBookmark::default(), cx);
+        }))
+    }
+
     fn seek_in_direction(
         &mut self,
         snapshot: &DisplaySnapshot,
@@ -9538,6 +10605,7 @@ impl Editor {
                     })
                     .detach();
 
+                    this.clear_background_highlights::<WordOccurrenceHighlight>(cx);
                     let write_highlights =
                         this.clear_background_highlights::<DocumentHighlightWrite>(cx);
                     let read_highlights =
@@ -10010,6 +11078,121 @@ impl Editor {
         }
     }
 
+    pub fn fold_recursive(&mut self, _: &actions::FoldRecursive, cx: &mut ViewContext<Self>) {
+        let mut fold_ranges = Vec::new();
+        let mut folded_rows = HashSet::default();
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
+        let selections = self.selections.all_adjusted(cx);
+        for selection in selections {
+            let range = selection.range().sorted();
+
+            // Fold the innermost range enclosing the selection, along with all the ranges
+            // nested within it.
+            let enclosing_range = (0..=range.start.row).rev().find_map(|row| {
+                display_map
+                    .foldable_range(MultiBufferRow(row))
+                    .filter(|(foldable_range, _)| foldable_range.end.row >= range.end.row)
+            });
+            let Some((enclosing_range, _)) = enclosing_range else {
+                continue;
+            };
+
+            for row in enclosing_range.start.row..=enclosing_range.end.row {
+                if !folded_rows.insert(row) {
+                    continue;
+                }
+                if let Some(foldable_range) = display_map.foldable_range(MultiBufferRow(row)) {
+                    fold_ranges.push(foldable_range);
+                }
+            }
+        }
+
+        self.fold_ranges(fold_ranges, true, cx);
+    }
+
+    pub fn fold_at_level(&mut self, fold_at: &FoldAtLevel, cx: &mut ViewContext<Self>) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
+        let mut fold_ranges = Vec::new();
+        let mut stack = vec![(0, display_map.buffer_snapshot.max_buffer_row().0, 1)];
+        while let Some((mut start_row, end_row, level)) = stack.pop() {
+            while start_row <= end_row {
+                if let Some((foldable_range, fold_text)) =
+                    display_map.foldable_range(MultiBufferRow(start_row))
+                {
+                    let nested_end_row = foldable_range.end.row;
+                    if level < fold_at.level {
+                        stack.push((start_row + 1, nested_end_row, level + 1));
+                    } else if level == fold_at.level {
+                        fold_ranges.push((foldable_range, fold_text));
+                    }
+                    start_row = nested_end_row + 1;
+                } else if display_map.is_line_folded(MultiBufferRow(start_row)) {
+                    // Skip the rows hidden by an existing fold.
+                    let (fold_end, _) = display_map.next_line_boundary(Point::new(start_row, 0));
+                    start_row = fold_end.row + 1;
+                } else {
+                    start_row += 1;
+                }
+            }
+        }
+
+        self.fold_ranges(fold_ranges, true, cx);
+    }
+
+    pub fn fold_all_functions(&mut self, _: &FoldAllFunctions, cx: &mut ViewContext<Self>) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+
+        let mut fold_ranges = Vec::new();
+        for body_range in buffer.function_body_ranges(0..buffer.len()) {
+            let start = body_range.start.to_point(buffer);
+            let end = body_range.end.to_point(buffer);
+            let starts_line = start.column
+                <= display_map
+                    .line_indent_for_buffer_row(MultiBufferRow(start.row))
+                    .raw_len();
+            let ends_line = end.column
+                <= display_map
+                    .line_indent_for_buffer_row(MultiBufferRow(end.row))
+                    .raw_len()
+                    + 1;
+            let opens_with_bracket = buffer
+                .chars_at(body_range.start)
+                .next()
+                .map_or(false, |c| matches!(c, '{' | '(' | '['));
+            let closes_with_bracket = buffer
+                .reversed_chars_at(body_range.end)
+                .next()
+                .map_or(false, |c| matches!(c, '}' | ')' | ']'));
+
+            // Like folds based on indentation, keep the lines of the function's signature and
+            // of its closing bracket visible.
+            let fold_start_row = if starts_line && !opens_with_bracket {
+                start.row.saturating_sub(1)
+            } else {
+                start.row
+            };
+            let fold_end = if ends_line && closes_with_bracket {
+                let row = end.row.saturating_sub(1);
+                Point::new(row, buffer.line_len(MultiBufferRow(row)))
+            } else {
+                end
+            };
+            let fold_start = Point::new(
+                fold_start_row,
+                buffer.line_len(MultiBufferRow(fold_start_row)),
+            );
+            if fold_start < fold_end {
+                fold_ranges.push((fold_start..fold_end, display_map.fold_placeholder.clone()));
+            }
+        }
+
+        self.fold_ranges(fold_ranges, true, cx);
+    }
+
     pub fn unfold_lines(&mut self, _: &UnfoldLines, cx: &mut ViewContext<Self>) {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = &display_map.buffer_snapshot;
@@ -10113,6 +11296,7 @@ impl Editor {
             }
 
             self.scrollbar_marker_state.dirty = true;
+            self.save_folds(cx);
         }
     }
 
@@ -10148,9 +11332,68 @@ impl Editor {
             cx.notify();
             self.scrollbar_marker_state.dirty = true;
             self.active_indent_guides_state.dirty = true;
+            self.save_folds(cx);
         }
     }
 
+    /// Returns the offset ranges of the folds of a singleton buffer, as they are persisted.
+    pub(crate) fn serialized_folds(&self, cx: &mut ViewContext<Self>) -> Vec<(usize, usize)> {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        display_map
+            .folds_in_range(0..buffer.len())
+            .map(|fold| {
+                (
+                    fold.range.start.to_offset(buffer),
+                    fold.range.end.to_offset(buffer),
+                )
+            })
+            .collect()
+    }
+
+    fn save_folds(&self, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.workspace.as_ref().and_then(|workspace| workspace.1) else {
+            return;
+        };
+        if !self.buffer.read(cx).is_singleton() {
+            return;
+        }
+
+        let item_id = cx.view().entity_id().as_u64() as ItemId;
+        let folds = self.serialized_folds(cx);
+        cx.foreground_executor()
+            .spawn(async move {
+                DB.save_editor_folds(item_id, workspace_id, folds)
+                    .await
+                    .log_err()
+            })
+            .detach();
+    }
+
+    pub fn read_folds_from_db(
+        &mut self,
+        item_id: ItemId,
+        workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(folds) = DB.get_editor_folds(item_id, workspace_id).log_err() else {
+            return;
+        };
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        let fold_ranges = folds
+            .into_iter()
+            .filter_map(|(start, end)| {
+                // The file may have changed since the folds were saved.
+                let start = buffer.clip_offset(start.min(buffer.len()), Bias::Left);
+                let end = buffer.clip_offset(end.min(buffer.len()), Bias::Right);
+                (start < end).then(|| (start..end, display_map.fold_placeholder.clone()))
+            })
+            .collect::<Vec<_>>();
+        self.fold_ranges(fold_ranges, false, cx);
+    }
+
     pub fn set_gutter_hovered(&mut self, hovered: bool, cx: &mut ViewContext<Self>) {
         if hovered != self.gutter_hovered {
             self.gutter_hovered = hovered;
@@ -10278,19 +11521,30 @@ impl Editor {
             .text()
     }
 
-    pub fn wrap_guides(&self, cx: &AppContext) -> SmallVec<[(usize, bool); 2]> {
+    /// Returns the wrap guides to show, and whether each of them is the one at the soft wrap
+    /// column.
+    pub fn wrap_guides(&self, cx: &AppContext) -> SmallVec<[(WrapGuide, bool); 2]> {
         let mut wrap_guides = smallvec::smallvec![];
 
         if self.show_wrap_guides == Some(false) {
             return wrap_guides;
         }
+        if let Some(guides) = &self.wrap_guides_override {
+            wrap_guides.extend(guides.iter().map(|guide| (guide.clone(), false)));
+            return wrap_guides;
+        }
 
         let settings = self.buffer.read(cx).settings_at(0, cx);
         if settings.show_wrap_guides {
             if let SoftWrap::Column(soft_wrap) = self.soft_wrap_mode(cx) {
-                wrap_guides.push((soft_wrap as usize, true));
+                wrap_guides.push((WrapGuide::Column(soft_wrap as usize), true));
             }
-            wrap_guides.extend(settings.wrap_guides.iter().map(|guide| (*guide, false)))
+            wrap_guides.extend(
+                settings
+                    .wrap_guides
+                    .iter()
+                    .map(|guide| (guide.clone(), false)),
+            )
         }
 
         wrap_guides
@@ -10430,6 +11684,11 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn set_wrap_guides(&mut self, wrap_guides: Vec<WrapGuide>, cx: &mut ViewContext<Self>) {
+        self.wrap_guides_override = Some(wrap_guides);
+        cx.notify();
+    }
+
     pub fn set_show_indent_guides(&mut self, show_indent_guides: bool, cx: &mut ViewContext<Self>) {
         self.show_indent_guides = Some(show_indent_guides);
         cx.notify();
@@ -10596,8 +11855,10 @@ impl Editor {
         snapshot.line_len(buffer_row) == 0
     }
 
-    fn get_permalink_to_line(&mut self, cx: &mut ViewContext<Self>) -> Result<url::Url> {
-        let (path, selection, repo) = maybe!({
+    fn get_permalink_to_line(&mut self, cx: &mut ViewContext<Self>) -> Task<Result<url::Url>> {
+        const REMOTE_NAME: &str = "origin";
+
+        let Some((path, selection, git_info)) = maybe!({
             let project_handle = self.project.as_ref()?.clone();
             let project = project_handle.read(cx);
 
@@ -10624,90 +11885,108 @@ impl Editor {
                 (buffer.clone(), selection)
             };
 
-            let path = buffer
-                .read(cx)
-                .file()?
-                .as_local()?
-                .path()
-                .to_str()?
-                .to_string();
-            let repo = project.get_repo(&buffer.read(cx).project_path(cx)?, cx)?;
-            Some((path, selection, repo))
-        })
-        .ok_or_else(|| anyhow!("unable to open git repository"))?;
+            let path = buffer.read(cx).file()?.path().to_str()?.to_string();
+            let git_info = project.buffer_git_info(&buffer, REMOTE_NAME, cx);
+            Some((path, selection, git_info))
+        }) else {
+            return Task::ready(Err(anyhow!("unable to open git repository")));
+        };
 
-        const REMOTE_NAME: &str = "origin";
-        let origin_url = repo
-            .remote_url(REMOTE_NAME)
-            .ok_or_else(|| anyhow!("remote \"{REMOTE_NAME}\" not found"))?;
-        let sha = repo
-            .head_sha()
-            .ok_or_else(|| anyhow!("failed to read HEAD SHA"))?;
-
-        let (provider, remote) =
-            parse_git_remote_url(GitHostingProviderRegistry::default_global(cx), &origin_url)
+        let provider_registry = GitHostingProviderRegistry::default_global(cx);
+        cx.background_executor().spawn(async move {
+            let git_info = git_info.await?;
+            let origin_url = git_info
+                .remote_url
+                .ok_or_else(|| anyhow!("remote \"{REMOTE_NAME}\" not found"))?;
+            let sha = git_info
+                .head_sha
+                .ok_or_else(|| anyhow!("failed to read HEAD SHA"))?;
+
+            let (provider, remote) = parse_git_remote_url(provider_registry, &origin_url)
                 .ok_or_else(|| anyhow!("failed to parse Git remote URL"))?;
 
-        Ok(provider.build_permalink(
-            remote,
-            BuildPermalinkParams {
-                sha: &sha,
-                path: &path,
-                selection: Some(selection),
-            },
-        ))
+            Ok(provider.build_permalink(
+                remote,
+                BuildPermalinkParams {
+                    sha: &sha,
+                    path: &path,
+                    selection: Some(selection),
+                },
+            ))
+        })
     }
 
     pub fn copy_permalink_to_line(&mut self, _: &CopyPermalinkToLine, cx: &mut ViewContext<Self>) {
-        let permalink = self.get_permalink_to_line(cx);
+        let permalink_task = self.get_permalink_to_line(cx);
+        let workspace = self.workspace();
 
-        match permalink {
-            Ok(permalink) => {
-                cx.write_to_clipboard(ClipboardItem::new(permalink.to_string()));
-            }
-            Err(err) => {
-                let message = format!("Failed to copy permalink: {err}");
+        cx.spawn(|_, mut cx| async move {
+            match permalink_task.await {
+                Ok(permalink) => {
+                    cx.update(|cx| {
+                        cx.write_to_clipboard(ClipboardItem::new(permalink.to_string()));
+                    })
+                    .ok();
+                }
+                Err(err) => {
+                    let message = format!("Failed to copy permalink: {err}");
 
-                Err::<(), anyhow::Error>(err).log_err();
+                    Err::<(), anyhow::Error>(err).log_err();
 
-                if let Some(workspace) = self.workspace() {
-                    workspace.update(cx, |workspace, cx| {
-                        struct CopyPermalinkToLine;
+                    if let Some(workspace) = workspace {
+                        workspace
+                            .update(&mut cx, |workspace, cx| {
+                                struct CopyPermalinkToLine;
 
-                        workspace.show_toast(
-                            Toast::new(NotificationId::unique::<CopyPermalinkToLine>(), message),
-                            cx,
-                        )
-                    })
+                                workspace.show_toast(
+                                    Toast::new(
+                                        NotificationId::unique::<CopyPermalinkToLine>(),
+                                        message,
+                                    ),
+                                    cx,
+                                )
+                            })
+                            .ok();
+                    }
                 }
             }
-        }
+        })
+        .detach();
     }
 
     pub fn open_permalink_to_line(&mut self, _: &OpenPermalinkToLine, cx: &mut ViewContext<Self>) {
-        let permalink = self.get_permalink_to_line(cx);
+        let permalink_task = self.get_permalink_to_line(cx);
+        let workspace = self.workspace();
 
-        match permalink {
-            Ok(permalink) => {
-                cx.open_url(permalink.as_ref());
-            }
-            Err(err) => {
-                let message = format!("Failed to open permalink: {err}");
+        cx.spawn(|_, mut cx| async move {
+            match permalink_task.await {
+                Ok(permalink) => {
+                    cx.update(|cx| cx.open_url(permalink.as_ref())).ok();
+                }
+                Err(err) => {
+                    let message = format!("Failed to open permalink: {err}");
 
-                Err::<(), anyhow::Error>(err).log_err();
+                    Err::<(), anyhow::Error>(err).log_err();
 
-                if let Some(workspace) = self.workspace() {
-                    workspace.update(cx, |workspace, cx| {
-                        struct OpenPermalinkToLine;
+                    if let Some(workspace) = workspace {
+                        workspace
+                            .update(&mut cx, |workspace, cx| {
+                                struct OpenPermalinkToLine;
 
-                        workspace.show_toast(
-                            Toast::new(NotificationId::unique::<OpenPermalinkToLine>(), message),
-                            cx,
-                        )
-                    })
+                                workspace.show_toast(
+                                    Toast::new(
+                                        NotificationId::unique::<OpenPermalinkToLine>(),
+                                        message,
+                                    ),
+                                    cx,
+                                )
+                            })
+                            .ok();
+                    }
                 }
             }
-        }
+        })
+        .detach();
     }
 
     /// Adds or removes (on `None` color) a highlight for the rows corresponding to the anchor range given.
@@ -11197,6 +12476,10 @@ impl Editor {
                 self.active_indent_guides_state.dirty = true;
                 self.refresh_active_diagnostics(cx);
                 self.refresh_code_actions(cx);
+                self.refresh_code_lenses(cx);
+                self.refresh_semantic_tokens(cx);
+                self.refresh_color_swatches(cx);
+                self.refresh_misspellings(cx);
                 if self.has_active_inline_completion(cx) {
                     self.update_visible_inline_completion(cx);
                 }
@@ -11249,9 +12532,17 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                self.refresh_code_lenses(cx);
+                self.refresh_semantic_tokens(cx);
+                self.refresh_color_swatches(cx);
+                self.refresh_misspellings(cx);
             }
             multi_buffer::Event::ExcerptsRemoved { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
+                self.refresh_code_lenses(cx);
+                self.refresh_semantic_tokens(cx);
+                self.refresh_color_swatches(cx);
+                self.refresh_misspellings(cx);
                 cx.emit(EditorEvent::ExcerptsRemoved { ids: ids.clone() })
             }
             multi_buffer::Event::ExcerptsEdited { ids } => {
@@ -11262,11 +12553,17 @@ impl Editor {
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
                 self.tasks_update_task = Some(self.refresh_runnables(cx));
+                self.refresh_color_swatches(cx);
+                self.refresh_misspellings(cx);
 
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
                 linked_editing_ranges::refresh_linked_ranges(self, cx);
+                self.refresh_code_lenses(cx);
+                self.refresh_semantic_tokens(cx);
+                self.refresh_color_swatches(cx);
+                self.refresh_misspellings(cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
                 cx.notify();
             }
@@ -11300,6 +12597,10 @@ impl Editor {
 
     fn settings_changed(&mut self, cx: &mut ViewContext<Self>) {
         self.tasks_update_task = Some(self.refresh_runnables(cx));
+        self.refresh_code_lenses(cx);
+        self.refresh_semantic_tokens(cx);
+        self.refresh_color_swatches(cx);
+        self.refresh_misspellings(cx);
         self.refresh_inline_completion(true, cx);
         self.refresh_inlay_hints(
             InlayHintRefreshReason::SettingsChange(inlay_hint_settings(
@@ -11312,9 +12613,16 @@ impl Editor {
         let editor_settings = EditorSettings::get_global(cx);
         self.scroll_manager.vertical_scroll_margin = editor_settings.vertical_scroll_margin;
         self.show_breadcrumbs = editor_settings.toolbar.breadcrumbs;
+        if self.default_cursor_shape != editor_settings.cursor_shape {
+            if self.cursor_shape == self.default_cursor_shape {
+                self.cursor_shape = editor_settings.cursor_shape;
+            }
+            self.default_cursor_shape = editor_settings.cursor_shape;
+        }
 
         let project_settings = ProjectSettings::get_global(cx);
         self.serialize_dirty_buffers = project_settings.session.restore_unsaved_buffers;
+        let underline_warnings = project_settings.diagnostics.underline_warnings;
 
         if self.mode == EditorMode::Full {
             let inline_blame_enabled = project_settings.git.inline_blame_enabled();
@@ -11323,6 +12631,11 @@ impl Editor {
             }
         }
 
+        if self.display_map.read(cx).underline_warnings != underline_warnings {
+            self.display_map
+                .update(cx, |map, _| map.underline_warnings = underline_warnings);
+        }
+
         cx.notify();
     }
 
@@ -12146,6 +13459,45 @@ fn inlay_hint_settings(
         .inlay_hints
 }
 
+/// Converts the case of the words of each line of the text, splitting identifiers into words
+/// the way they're usually written in code: acronyms are kept together (`XMLHttpRequest` is
+/// `xml_http_request`), and digits stay with the word they follow (`utf8String` is
+/// `utf8_string`).
+fn convert_identifier_case(text: &str, case: Case) -> String {
+    const BOUNDARIES: [Boundary; 6] = [
+        Boundary::Underscore,
+        Boundary::Hyphen,
+        Boundary::Space,
+        Boundary::LowerUpper,
+        Boundary::DigitUpper,
+        Boundary::Acronym,
+    ];
+
+    // The case conversion doesn't treat '\n' as a word boundary, so convert lines separately.
+    // https://github.com/rutrum/convert-case/issues/16
+    text.split('\n')
+        .map(|line| line.with_boundaries(&BOUNDARIES).to_case(case))
+        .join("\n")
+}
+
+/// Guesses the original indentation of the first line of text copied outside of Zed. When the
+/// first line isn't indented, the text may have been copied from the middle of a line, so the
+/// least indented of the following lines is used instead.
+fn original_indent_column(text: &str) -> u32 {
+    let indent_len =
+        |line: &str| line.chars().take_while(|c| *c == ' ' || *c == '\t').count() as u32;
+    let mut lines = text.lines();
+    let first_line_indent = lines.next().map_or(0, indent_len);
+    if first_line_indent > 0 {
+        return first_line_indent;
+    }
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(indent_len)
+        .min()
+        .unwrap_or(0)
+}
+
 fn consume_contiguous_rows(
     contiguous_row_selections: &mut Vec<Selection<Point>>,
     selection: &Selection<Point>,
@@ -12326,7 +13678,8 @@ impl EditorSnapshot {
                 cx,
             ))
         } else if folded
-            || (self.starts_indent(buffer_row) && (row_contains_cursor || self.gutter_hovered))
+            || ((self.starts_indent(buffer_row) || self.starts_region(buffer_row))
+                && (row_contains_cursor || self.gutter_hovered))
         {
             Some(
                 Disclosure::new(("indent-fold-indicator", buffer_row.0), !folded)
@@ -12521,6 +13874,11 @@ impl ViewInputHandler for Editor {
         text: &str,
         cx: &mut ViewContext<Self>,
     ) {
+        if self.is_jumping_to_char() {
+            self.jump_to_char_input(text, cx);
+            return;
+        }
+
         if !self.input_enabled {
             cx.emit(EditorEvent::InputIgnored { text: text.into() });
             return;
@@ -1,4 +1,6 @@
+use collections::HashMap;
 use gpui::AppContext;
+use language::CursorShape;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -6,12 +8,14 @@ use settings::{Settings, SettingsSources};
 #[derive(Deserialize, Clone)]
 pub struct EditorSettings {
     pub cursor_blink: bool,
+    pub cursor_shape: CursorShape,
     pub current_line_highlight: CurrentLineHighlight,
     pub hover_popover_enabled: bool,
     pub show_completions_on_input: bool,
     pub show_completion_documentation: bool,
     pub completion_documentation_secondary_query_debounce: u64,
     pub use_on_type_format: bool,
+    pub auto_indent_on_paste: bool,
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
     pub gutter: Gutter,
@@ -29,6 +33,10 @@ pub struct EditorSettings {
     pub auto_signature_help: bool,
     pub show_signature_help_after_edits: bool,
     pub jupyter: Jupyter,
+    pub sticky_scroll: StickyScroll,
+    pub minimap: Minimap,
+    pub document_highlights: DocumentHighlights,
+    pub unicode_input: UnicodeInput,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -85,6 +93,94 @@ pub struct JupyterContent {
     pub enabled: Option<bool>,
 }
 
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct StickyScroll {
+    /// Whether to pin the headers of the scopes enclosing the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: bool,
+}
+
+#[derive(Default, Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct StickyScrollContent {
+    /// Whether to pin the headers of the scopes enclosing the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct Minimap {
+    /// Whether to show a zoomed-out view of the buffer next to the scrollbar.
+    ///
+    /// Default: false
+    pub enabled: bool,
+}
+
+#[derive(Default, Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct MinimapContent {
+    /// Whether to show a zoomed-out view of the buffer next to the scrollbar.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct DocumentHighlights {
+    /// The delay in milliseconds before highlighting the occurrences of the symbol under the
+    /// cursor after it moves.
+    ///
+    /// Default: 75
+    pub debounce_ms: u64,
+    /// Whether to highlight the occurrences of the word under the cursor when the language
+    /// server doesn't highlight the symbol under it.
+    ///
+    /// Default: true
+    pub textual_fallback: bool,
+}
+
+#[derive(Default, Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct DocumentHighlightsContent {
+    /// The delay in milliseconds before highlighting the occurrences of the symbol under the
+    /// cursor after it moves.
+    ///
+    /// Default: 75
+    pub debounce_ms: Option<u64>,
+    /// Whether to highlight the occurrences of the word under the cursor when the language
+    /// server doesn't highlight the symbol under it.
+    ///
+    /// Default: true
+    pub textual_fallback: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UnicodeInput {
+    /// Whether typing a backslash followed by an abbreviation, such as `\alpha`, offers to
+    /// replace it with the character it stands for.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// Abbreviations to offer in addition to the built-in ones, without their leading
+    /// backslash, mapped to the text they're replaced with.
+    ///
+    /// Default: {}
+    pub abbreviations: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct UnicodeInputContent {
+    /// Whether typing a backslash followed by an abbreviation, such as `\alpha`, offers to
+    /// replace it with the character it stands for.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Abbreviations to offer in addition to the built-in ones, without their leading
+    /// backslash, mapped to the text they're replaced with.
+    ///
+    /// Default: {}
+    pub abbreviations: Option<HashMap<String, String>>,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Toolbar {
     pub breadcrumbs: bool,
@@ -97,6 +193,7 @@ pub struct Scrollbar {
     pub show: ShowScrollbar,
     pub git_diff: bool,
     pub selected_symbol: bool,
+    pub word_occurrences: bool,
     pub search_results: bool,
     pub diagnostics: bool,
     pub cursors: bool,
@@ -160,6 +257,10 @@ pub struct EditorSettingsContent {
     ///
     /// Default: true
     pub cursor_blink: Option<bool>,
+    /// The shape of the cursor in the editor.
+    ///
+    /// Default: bar
+    pub cursor_shape: Option<CursorShape>,
     /// How to highlight the current line in the editor.
     ///
     /// Default: all
@@ -190,6 +291,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: true
     pub use_on_type_format: Option<bool>,
+    /// Whether to re-indent pasted text to match the indentation of the lines
+    /// it's pasted on, using the indentation rules of the language.
+    ///
+    /// Default: true
+    pub auto_indent_on_paste: Option<bool>,
     /// Toolbar related settings
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar related settings
@@ -255,6 +361,18 @@ pub struct EditorSettingsContent {
 
     /// Jupyter REPL settings.
     pub jupyter: Option<JupyterContent>,
+
+    /// Sticky scroll related settings.
+    pub sticky_scroll: Option<StickyScrollContent>,
+
+    /// Minimap related settings.
+    pub minimap: Option<MinimapContent>,
+
+    /// Settings related to highlighting the occurrences of the symbol under the cursor.
+    pub document_highlights: Option<DocumentHighlightsContent>,
+
+    /// Settings related to inserting unicode characters by typing their abbreviations.
+    pub unicode_input: Option<UnicodeInputContent>,
 }
 
 // Toolbar related settings
@@ -276,7 +394,7 @@ pub struct ToolbarContent {
 }
 
 /// Scrollbar related settings
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct ScrollbarContent {
     /// When to show the scrollbar in the editor.
     ///
@@ -294,6 +412,11 @@ pub struct ScrollbarContent {
     ///
     /// Default: true
     pub selected_symbol: Option<bool>,
+    /// Whether to show the occurrences of the word under the cursor in the scrollbar, when
+    /// they are highlighted in place of the symbol's.
+    ///
+    /// Default: true
+    pub word_occurrences: Option<bool>,
     /// Whether to show diagnostic indicators in the scrollbar.
     ///
     /// Default: true
@@ -305,7 +428,7 @@ pub struct ScrollbarContent {
 }
 
 /// Gutter related settings
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GutterContent {
     /// Whether to show line numbers in the gutter.
     ///
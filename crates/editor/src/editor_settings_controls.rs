@@ -1,39 +1,509 @@
-use std::sync::Arc;
+use crate::{
+    editor_settings::{ScrollBeyondLastLine, ShowScrollbar},
+    Editor, EditorEvent, EditorSettings, NumericSettingControl, NumericSettingEditor,
+};
+
+use std::{mem, num::NonZeroU32, sync::Arc};
 
-use gpui::{AppContext, FontFeatures, FontWeight};
-use project::project_settings::{InlineBlameSettings, ProjectSettings};
-use settings::{EditableSettingControl, Settings};
-use theme::{FontFamilyCache, ThemeSettings};
+use gpui::{AppContext, ClickEvent, FontFeatures, FontWeight, Global, Subscription, View};
+use language::{
+    language_settings::{
+        AllLanguageSettings, AllLanguageSettingsContent, FormatOnSave, Formatter, FormatterList,
+        InlayHintSettings, LanguageSettings, LanguageSettingsContent, SelectedFormatter, SoftWrap,
+        WrapGuide,
+    },
+    CursorShape, LanguageRegistry,
+};
+use project::{
+    project_settings::{
+        DiagnosticSeverityLevel, GitGutterSetting, InlineBlameSettings, ProjectSettings,
+    },
+    Fs,
+};
+use schemars::schema::SingleOrVec;
+use settings::{update_settings_file, EditableSettingControl, Settings};
+use theme::{clamp_font_size, BufferLineHeight, FontFamilyCache, ThemeSettings};
 use ui::{
-    prelude::*, CheckboxWithLabel, ContextMenu, DropdownMenu, NumericStepper, SettingsContainer,
-    SettingsGroup,
+    prelude::*, CheckboxWithLabel, ContextMenu, Disclosure, DropdownMenu, DropdownSettingControl,
+    NumericStepper, SettingsContainer, SettingsControl, SettingsFilter, SettingsGroup,
 };
+use workspace::{AutosaveSetting, WorkspaceSettings};
 
-#[derive(IntoElement)]
-pub struct EditorSettingsControls {}
+pub struct EditorSettingsControls {
+    languages: Arc<LanguageRegistry>,
+    buffer_font_size: View<NumericSettingEditor>,
+    tab_size: View<NumericSettingEditor>,
+    preferred_line_length: View<NumericSettingEditor>,
+    font_features_expanded: bool,
+    new_wrap_guide: View<Editor>,
+    external_formatter_command: View<Editor>,
+    external_formatter_arguments: View<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
 
 impl EditorSettingsControls {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(languages: Arc<LanguageRegistry>, cx: &mut ViewContext<Self>) -> Self {
+        cx.set_global(EditedLanguage::default());
+
+        let (command, arguments) = FormatterControl::external_command(cx)
+            .map(|(command, arguments)| (command.to_string(), arguments.join(" ")))
+            .unwrap_or_default();
+
+        let external_formatter_command = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Command", cx);
+            editor.set_text(command, cx);
+            editor
+        });
+        let external_formatter_arguments = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Arguments", cx);
+            editor.set_text(arguments, cx);
+            editor
+        });
+
+        let new_wrap_guide = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Add", cx);
+            editor
+        });
+
+        let mut _subscriptions = [&external_formatter_command, &external_formatter_arguments]
+            .into_iter()
+            .map(|editor| {
+                cx.subscribe(editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::Blurred = event {
+                        this.save_external_formatter(cx);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        _subscriptions.push(
+            cx.subscribe(&new_wrap_guide, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::Blurred = event {
+                    this.add_wrap_guide(cx);
+                }
+            }),
+        );
+
+        Self {
+            languages,
+            buffer_font_size: cx.new_view(NumericSettingEditor::new::<BufferFontSizeControl>),
+            tab_size: cx.new_view(NumericSettingEditor::new::<TabSizeControl>),
+            preferred_line_length: cx
+                .new_view(NumericSettingEditor::new::<PreferredLineLengthControl>),
+            font_features_expanded: false,
+            new_wrap_guide,
+            external_formatter_command,
+            external_formatter_arguments,
+            _subscriptions,
+        }
+    }
+
+    /// Switches the language settings controls to edit the given language, or the defaults
+    /// for all languages.
+    fn set_edited_language(&mut self, language: Option<Arc<str>>, cx: &mut ViewContext<Self>) {
+        cx.set_global(EditedLanguage(language));
+
+        let (command, arguments) = FormatterControl::external_command(cx)
+            .map(|(command, arguments)| (command.to_string(), arguments.join(" ")))
+            .unwrap_or_default();
+        self.external_formatter_command
+            .update(cx, |editor, cx| editor.set_text(command, cx));
+        self.external_formatter_arguments
+            .update(cx, |editor, cx| editor.set_text(arguments, cx));
+        for value_editor in [&self.tab_size, &self.preferred_line_length] {
+            value_editor.update(cx, |value_editor, cx| value_editor.refresh(cx));
+        }
+
+        cx.notify();
+    }
+
+    /// Removes the settings of the edited language from the settings file.
+    fn remove_language_overrides(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(language) = EditedLanguage::get(cx) else {
+            return;
+        };
+
+        update_settings_file::<AllLanguageSettings>(
+            <dyn Fs>::global(cx),
+            cx,
+            move |settings, _| {
+                settings.languages.remove(&language);
+            },
+        );
+    }
+
+    fn render_language_selector(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let edited_language = EditedLanguage::get(cx);
+        let mut language_names = self.languages.language_names();
+        language_names.sort_unstable();
+        let controls = cx.view().downgrade();
+
+        h_flex()
+            .gap_2()
+            .child(Label::new("Language"))
+            .child(DropdownMenu::new(
+                "edited-language",
+                edited_language
+                    .as_deref()
+                    .unwrap_or("All Languages")
+                    .to_string(),
+                ContextMenu::build(cx, |mut menu, _cx| {
+                    menu = menu
+                        .custom_entry(|_cx| Label::new("All Languages").into_any_element(), {
+                            let controls = controls.clone();
+                            move |cx| {
+                                controls
+                                    .update(cx, |this, cx| this.set_edited_language(None, cx))
+                                    .ok();
+                            }
+                        })
+                        .separator();
+
+                    for language_name in language_names {
+                        let language_name: Arc<str> = language_name.into();
+                        menu = menu.custom_entry(
+                            {
+                                let language_name = language_name.clone();
+                                move |_cx| Label::new(language_name.to_string()).into_any_element()
+                            },
+                            {
+                                let controls = controls.clone();
+                                move |cx| {
+                                    let language_name = language_name.clone();
+                                    controls
+                                        .update(cx, |this, cx| {
+                                            this.set_edited_language(Some(language_name), cx)
+                                        })
+                                        .ok();
+                                }
+                            },
+                        )
+                    }
+
+                    menu
+                }),
+            ))
+            .when(edited_language.is_some(), |this| {
+                this.child(
+                    Button::new("remove-language-overrides", "Remove Overrides")
+                        .on_click(cx.listener(|this, _, cx| this.remove_language_overrides(cx))),
+                )
+            })
+    }
+
+    /// Adds the wrap guide typed in the new wrap guide editor, if it's a valid column.
+    fn add_wrap_guide(&mut self, cx: &mut ViewContext<Self>) {
+        let text = self.new_wrap_guide.read(cx).text(cx);
+        self.new_wrap_guide
+            .update(cx, |editor, cx| editor.set_text("", cx));
+
+        let Ok(column) = text.trim().parse::<usize>() else {
+            return;
+        };
+        let mut wrap_guides = WrapGuidesControl::read(cx);
+        if !wrap_guides.iter().any(|guide| guide.column() == column) {
+            wrap_guides.push(WrapGuide::Column(column));
+            WrapGuidesControl::write(wrap_guides, cx);
+        }
+    }
+
+    /// Writes the external formatter from the command and arguments editors, as long as
+    /// an external command is still the selected formatter.
+    fn save_external_formatter(&mut self, cx: &mut ViewContext<Self>) {
+        if FormatterControl::external_command(cx).is_none() {
+            return;
+        }
+
+        let formatter = FormatterControl::external_formatter(
+            &self.external_formatter_command,
+            &self.external_formatter_arguments,
+            cx,
+        );
+        FormatterControl::write(formatter, cx);
     }
 }
 
-impl RenderOnce for EditorSettingsControls {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+impl Render for EditorSettingsControls {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         SettingsContainer::new()
+            .child(self.render_language_selector(cx))
             .child(
                 SettingsGroup::new("Font")
-                    .child(
+                    .searchable_child(
+                        [
+                            BufferFontFamilyControl.name(),
+                            BufferFontWeightControl.name(),
+                        ],
                         h_flex()
                             .gap_2()
                             .justify_between()
-                            .child(BufferFontFamilyControl)
-                            .child(BufferFontWeightControl),
+                            .child(SettingsControl::new(BufferFontFamilyControl))
+                            .child(SettingsControl::new(BufferFontWeightControl)),
                     )
-                    .child(BufferFontSizeControl)
-                    .child(BufferFontLigaturesControl),
+                    .control(BufferFontSizeControl {
+                        value_editor: self.buffer_font_size.clone(),
+                    })
+                    .control(BufferLineHeightControl)
+                    .control(BufferFontLigaturesControl)
+                    .control(BufferFontFeaturesControl {
+                        expanded: self.font_features_expanded,
+                        on_toggle: Arc::new(cx.listener(|this, _: &ClickEvent, cx| {
+                            this.font_features_expanded = !this.font_features_expanded;
+                            cx.notify();
+                        })),
+                    }),
+            )
+            .child(
+                SettingsGroup::new("Indentation")
+                    .control(TabSizeControl {
+                        value_editor: self.tab_size.clone(),
+                    })
+                    .control(HardTabsControl),
+            )
+            .child(
+                SettingsGroup::new("Soft Wrap")
+                    .control(SoftWrapControl)
+                    .control(PreferredLineLengthControl {
+                        value_editor: self.preferred_line_length.clone(),
+                    }),
+            )
+            .child(
+                SettingsGroup::new("Gutter")
+                    .control(LineNumbersControl)
+                    .control(RelativeLineNumbersControl)
+                    .control(GitGutterControl)
+                    .control(GutterFoldsControl),
+            )
+            .child(
+                SettingsGroup::new("Inline Git Blame")
+                    .control(InlineGitBlameControl)
+                    .control(InlineGitBlameDelayControl)
+                    .control(InlineGitBlameCommitSummaryControl),
             )
-            .child(SettingsGroup::new("Editor").child(InlineGitBlameControl))
+            .child(
+                SettingsGroup::new("Diagnostics")
+                    .control(InlineDiagnosticsControl)
+                    .dropdown(inline_diagnostics_severity_control())
+                    .control(IncludeWarningsControl)
+                    .control(UnderlineWarningsControl)
+                    .control(DiagnosticsUpdateDebounceControl),
+            )
+            .child(
+                SettingsGroup::new("Scrolling")
+                    .dropdown(scrollbar_visibility_control())
+                    .control(ScrollbarDiagnosticsControl)
+                    .control(ScrollbarSearchResultsControl)
+                    .control(VerticalScrollMarginControl)
+                    .dropdown(scroll_beyond_last_line_control())
+                    .control(StickyScrollControl)
+                    .control(MinimapControl),
+            )
+            .child(
+                SettingsGroup::new("Inlay Hints")
+                    .control(InlayHintsControl)
+                    .control(TypeHintsControl)
+                    .control(ParameterHintsControl),
+            )
+            .child(
+                SettingsGroup::new("Editor")
+                    .dropdown(cursor_shape_control())
+                    .control(CursorBlinkControl)
+                    .control(FormatOnSaveControl)
+                    .control(FormatterControl {
+                        external_command: self.external_formatter_command.clone(),
+                        external_arguments: self.external_formatter_arguments.clone(),
+                    })
+                    .control(AutosaveControl)
+                    .control(ShowWrapGuidesControl)
+                    .control(WrapGuidesControl {
+                        new_wrap_guide: self.new_wrap_guide.clone(),
+                    }),
+            )
+    }
+}
+
+/// The language whose settings are edited by the language settings controls, which edit
+/// the defaults for all languages when there's none.
+#[derive(Default)]
+struct EditedLanguage(Option<Arc<str>>);
+
+impl Global for EditedLanguage {}
+
+impl EditedLanguage {
+    fn get(cx: &AppContext) -> Option<Arc<str>> {
+        cx.try_global::<Self>()
+            .and_then(|language| language.0.clone())
+    }
+
+    /// Returns the current settings of the edited language.
+    fn settings(cx: &AppContext) -> &LanguageSettings {
+        AllLanguageSettings::get_global(cx).language(Self::get(cx).as_deref())
+    }
+
+    /// Returns the settings file contents to update for the edited language.
+    fn content<'a>(
+        settings: &'a mut AllLanguageSettingsContent,
+        cx: &AppContext,
+    ) -> &'a mut LanguageSettingsContent {
+        match Self::get(cx) {
+            Some(language) => settings.languages.entry(language).or_default(),
+            None => &mut settings.defaults,
+        }
+    }
+}
+
+/// Returns the inlay hint settings to update for the edited language, starting from the
+/// current ones if they haven't been customized yet.
+fn inlay_hint_settings<'a>(
+    settings: &'a mut <AllLanguageSettings as Settings>::FileContent,
+    cx: &AppContext,
+) -> &'a mut InlayHintSettings {
+    EditedLanguage::content(settings, cx)
+        .inlay_hints
+        .get_or_insert_with(|| EditedLanguage::settings(cx).inlay_hints)
+}
+
+/// Renders a note listing the languages that override a language setting, as changes
+/// to the default value don't apply to them.
+fn render_language_overrides<T: PartialEq>(
+    setting: impl Fn(&LanguageSettings) -> T,
+    cx: &WindowContext,
+) -> Option<impl IntoElement> {
+    if EditedLanguage::get(cx).is_some() {
+        return None;
+    }
+    let languages = AllLanguageSettings::get_global(cx).languages_overriding(setting);
+    if languages.is_empty() {
+        return None;
+    }
+    Some(
+        Label::new(format!("Overridden for {}", languages.join(", ")))
+            .size(LabelSize::Small)
+            .color(Color::Muted),
+    )
+}
+
+#[derive(IntoElement)]
+struct TabSizeControl {
+    value_editor: View<NumericSettingEditor>,
+}
+
+impl TabSizeControl {
+    const MAX_TAB_SIZE: u32 = 16;
+}
+
+impl EditableSettingControl for TabSizeControl {
+    type Value = NonZeroU32;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Tab Size".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).tab_size
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        let value = value.min(NonZeroU32::new(Self::MAX_TAB_SIZE).unwrap());
+        EditedLanguage::content(settings, cx).tab_size = Some(value);
+    }
+}
+
+impl NumericSettingControl for TabSizeControl {
+    fn format(value: &Self::Value) -> String {
+        value.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self::Value> {
+        text.parse().ok()
+    }
+}
+
+impl RenderOnce for TabSizeControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(SettingsFilter::label(self.name(), cx))
+                    .child(
+                        NumericStepper::new(
+                            value.to_string(),
+                            move |event, cx| {
+                                let steps = NumericStepper::step_count(event);
+                                if let Some(value) =
+                                    NonZeroU32::new(value.get().saturating_sub(steps))
+                                {
+                                    Self::write(value, cx);
+                                }
+                            },
+                            move |event, cx| {
+                                let steps = NumericStepper::step_count(event);
+                                Self::write(value.saturating_add(steps), cx);
+                            },
+                        )
+                        .value_editor(self.value_editor),
+                    ),
+            )
+            .children(render_language_overrides(|settings| settings.tab_size, cx))
+    }
+}
+
+#[derive(IntoElement)]
+struct HardTabsControl;
+
+impl EditableSettingControl for HardTabsControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Indent With Tabs".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).hard_tabs
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        EditedLanguage::content(settings, cx).hard_tabs = Some(value);
+    }
+}
+
+impl RenderOnce for HardTabsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(CheckboxWithLabel::new(
+                "hard-tabs",
+                SettingsFilter::label(self.name(), cx),
+                value.into(),
+                |selection, cx| {
+                    Self::write(
+                        match selection {
+                            Selection::Selected => true,
+                            Selection::Unselected | Selection::Indeterminate => false,
+                        },
+                        cx,
+                    );
+                },
+            ))
+            .children(render_language_overrides(|settings| settings.hard_tabs, cx))
     }
 }
 
@@ -97,7 +567,9 @@ impl RenderOnce for BufferFontFamilyControl {
 }
 
 #[derive(IntoElement)]
-struct BufferFontSizeControl;
+struct BufferFontSizeControl {
+    value_editor: View<NumericSettingEditor>,
+}
 
 impl EditableSettingControl for BufferFontSizeControl {
     type Value = Pixels;
@@ -117,7 +589,17 @@ impl EditableSettingControl for BufferFontSizeControl {
         value: Self::Value,
         _cx: &AppContext,
     ) {
-        settings.buffer_font_size = Some(value.into());
+        settings.buffer_font_size = Some(clamp_font_size(value).into());
+    }
+}
+
+impl NumericSettingControl for BufferFontSizeControl {
+    fn format(value: &Self::Value) -> String {
+        value.0.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self::Value> {
+        text.trim_end_matches("px").parse().ok().map(px)
     }
 }
 
@@ -128,15 +610,114 @@ impl RenderOnce for BufferFontSizeControl {
         h_flex()
             .gap_2()
             .child(Icon::new(IconName::FontSize))
-            .child(NumericStepper::new(
-                value.to_string(),
-                move |_, cx| {
-                    Self::write(value - px(1.), cx);
+            .child(
+                NumericStepper::new(
+                    value.to_string(),
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value - px(steps), cx);
+                    },
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value + px(steps), cx);
+                    },
+                )
+                .value_editor(self.value_editor),
+            )
+            .child(Label::new("px").color(Color::Muted))
+    }
+}
+
+#[derive(IntoElement)]
+struct BufferLineHeightControl;
+
+impl BufferLineHeightControl {
+    /// How much the custom line height changes with each step.
+    const STEP: f32 = 0.1;
+    const MIN_LINE_HEIGHT: f32 = 1.;
+    const MAX_LINE_HEIGHT: f32 = 3.;
+
+    fn label(line_height: BufferLineHeight) -> &'static str {
+        match line_height {
+            BufferLineHeight::Comfortable => "Comfortable",
+            BufferLineHeight::Standard => "Standard",
+            BufferLineHeight::Custom(_) => "Custom",
+        }
+    }
+}
+
+impl EditableSettingControl for BufferLineHeightControl {
+    type Value = BufferLineHeight;
+    type Settings = ThemeSettings;
+
+    fn name(&self) -> SharedString {
+        "Buffer Line Height".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ThemeSettings::get_global(cx);
+        settings.buffer_line_height
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.buffer_line_height = Some(match value {
+            BufferLineHeight::Custom(line_height) => BufferLineHeight::Custom(
+                line_height.clamp(Self::MIN_LINE_HEIGHT, Self::MAX_LINE_HEIGHT),
+            ),
+            line_height => line_height,
+        });
+    }
+}
+
+impl RenderOnce for BufferLineHeightControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::LineHeight))
+            .child(DropdownMenu::new(
+                "buffer-line-height",
+                Self::label(value),
+                ContextMenu::build(cx, |mut menu, _cx| {
+                    for line_height in [
+                        BufferLineHeight::Comfortable,
+                        BufferLineHeight::Standard,
+                        // Switching to a custom line height starts from the current one.
+                        BufferLineHeight::Custom(value.value()),
+                    ] {
+                        menu = menu.custom_entry(
+                            move |_cx| Label::new(Self::label(line_height)).into_any_element(),
+                            move |cx| Self::write(line_height, cx),
+                        )
+                    }
+
+                    menu
+                }),
+            ))
+            .when_some(
+                match value {
+                    BufferLineHeight::Custom(line_height) => Some(line_height),
+                    _ => None,
                 },
-                move |_, cx| {
-                    Self::write(value + px(1.), cx);
+                |this, line_height| {
+                    this.child(NumericStepper::new(
+                        format!("{line_height:.1}"),
+                        move |event, cx| {
+                            let step = Self::STEP * NumericStepper::step_count(event) as f32;
+                            Self::write(BufferLineHeight::Custom(line_height - step), cx);
+                        },
+                        move |event, cx| {
+                            let step = Self::STEP * NumericStepper::step_count(event) as f32;
+                            Self::write(BufferLineHeight::Custom(line_height + step), cx);
+                        },
+                    ))
                 },
-            ))
+            )
     }
 }
 
@@ -218,27 +799,8 @@ impl EditableSettingControl for BufferFontLigaturesControl {
         value: Self::Value,
         _cx: &AppContext,
     ) {
-        let value = if value { 1 } else { 0 };
-
-        let mut features = settings
-            .buffer_font_features
-            .as_ref()
-            .map(|features| {
-                features
-                    .tag_value_list()
-                    .into_iter()
-                    .cloned()
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-
-        if let Some(calt_index) = features.iter().position(|(tag, _)| tag == "calt") {
-            features[calt_index].1 = value;
-        } else {
-            features.push(("calt".into(), value));
-        }
-
-        settings.buffer_font_features = Some(FontFeatures(Arc::new(features)));
+        let features = settings.buffer_font_features.clone().unwrap_or_default();
+        settings.buffer_font_features = Some(features.with_feature("calt", value));
     }
 }
 
@@ -248,7 +810,7 @@ impl RenderOnce for BufferFontLigaturesControl {
 
         CheckboxWithLabel::new(
             "buffer-font-ligatures",
-            Label::new(self.name()),
+            SettingsFilter::label(self.name(), cx),
             value.into(),
             |selection, cx| {
                 Self::write(
@@ -264,19 +826,66 @@ impl RenderOnce for BufferFontLigaturesControl {
 }
 
 #[derive(IntoElement)]
-struct InlineGitBlameControl;
+struct BufferFontFeaturesControl {
+    expanded: bool,
+    on_toggle: Arc<dyn Fn(&ClickEvent, &mut WindowContext)>,
+}
 
-impl EditableSettingControl for InlineGitBlameControl {
-    type Value = bool;
-    type Settings = ProjectSettings;
+impl BufferFontFeaturesControl {
+    /// Returns the OpenType features known to be offered by the given font family.
+    ///
+    /// Fonts don't report which features they support, so this covers popular coding
+    /// fonts and falls back to widely supported features for other fonts.
+    fn known_features(font_family: &str) -> &'static [&'static str] {
+        match font_family {
+            "Fira Code" => &[
+                "zero", "onum", "ss01", "ss02", "ss03", "ss04", "ss05", "ss06", "ss07", "ss08",
+                "ss09", "cv01", "cv02", "cv03", "cv04", "cv05", "cv06", "cv07", "cv08", "cv09",
+                "cv10",
+            ],
+            "JetBrains Mono" => &[
+                "zero", "ss01", "ss02", "ss19", "ss20", "cv01", "cv02", "cv03", "cv04", "cv05",
+                "cv06", "cv07", "cv08", "cv09", "cv10",
+            ],
+            "Cascadia Code" | "Cascadia Mono" => {
+                &["zero", "onum", "ss01", "ss02", "ss03", "ss19", "ss20"]
+            }
+            "Zed Plex Mono" | "IBM Plex Mono" => &["zero", "ss01", "ss02", "ss03", "ss04", "ss05"],
+            _ => &[
+                "zero", "onum", "tnum", "ss01", "ss02", "ss03", "ss04", "ss05",
+            ],
+        }
+    }
+
+    fn label(tag: &str) -> String {
+        match tag {
+            "zero" => "Slashed Zero".into(),
+            "onum" => "Oldstyle Figures".into(),
+            "tnum" => "Tabular Figures".into(),
+            _ => {
+                if let Some(set) = tag.strip_prefix("ss") {
+                    format!("Stylistic Set {set}")
+                } else if let Some(variant) = tag.strip_prefix("cv") {
+                    format!("Character Variant {variant}")
+                } else {
+                    tag.to_string()
+                }
+            }
+        }
+    }
+}
+
+impl EditableSettingControl for BufferFontFeaturesControl {
+    type Value = FontFeatures;
+    type Settings = ThemeSettings;
 
     fn name(&self) -> SharedString {
-        "Inline Git Blame".into()
+        "Font Features".into()
     }
 
     fn read(cx: &AppContext) -> Self::Value {
-        let settings = ProjectSettings::get_global(cx);
-        settings.git.inline_blame_enabled()
+        let settings = ThemeSettings::get_global(cx);
+        settings.buffer_font.features.clone()
     }
 
     fn apply(
@@ -284,34 +893,2115 @@ impl EditableSettingControl for InlineGitBlameControl {
         value: Self::Value,
         _cx: &AppContext,
     ) {
-        if let Some(inline_blame) = settings.git.inline_blame.as_mut() {
-            inline_blame.enabled = value;
-        } else {
-            settings.git.inline_blame = Some(InlineBlameSettings {
-                enabled: false,
-                ..Default::default()
-            });
-        }
+        settings.buffer_font_features = Some(value);
     }
 }
 
-impl RenderOnce for InlineGitBlameControl {
+impl RenderOnce for BufferFontFeaturesControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
+        let font_family = ThemeSettings::get_global(cx).buffer_font.family.clone();
 
-        CheckboxWithLabel::new(
-            "inline-git-blame",
-            Label::new(self.name()),
-            value.into(),
-            |selection, cx| {
-                Self::write(
-                    match selection {
-                        Selection::Selected => true,
-                        Selection::Unselected | Selection::Indeterminate => false,
-                    },
-                    cx,
-                );
-            },
-        )
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Disclosure::new("buffer-font-features-disclosure", self.expanded)
+                            .on_toggle(self.on_toggle),
+                    )
+                    .child(SettingsFilter::label(self.name(), cx)),
+            )
+            .when(self.expanded, |this| {
+                this.child(
+                    v_flex().pl_5().gap_1().children(
+                        Self::known_features(&font_family)
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, tag)| {
+                                let features = value.clone();
+                                CheckboxWithLabel::new(
+                                    ("buffer-font-feature", ix),
+                                    Label::new(Self::label(tag)),
+                                    value.is_enabled(tag).unwrap_or(false).into(),
+                                    move |selection, cx| {
+                                        let enabled = match selection {
+                                            Selection::Selected => true,
+                                            Selection::Unselected | Selection::Indeterminate => {
+                                                false
+                                            }
+                                        };
+                                        Self::write(features.with_feature(tag, enabled), cx);
+                                    },
+                                )
+                            }),
+                    ),
+                )
+            })
+    }
+}
+
+#[derive(IntoElement)]
+struct SoftWrapControl;
+
+impl SoftWrapControl {
+    fn label(soft_wrap: SoftWrap) -> &'static str {
+        match soft_wrap {
+            SoftWrap::None => "None",
+            SoftWrap::PreferLine => "Prefer Single Line",
+            SoftWrap::EditorWidth => "Editor Width",
+            SoftWrap::PreferredLineLength => "Preferred Line Length",
+        }
+    }
+}
+
+impl EditableSettingControl for SoftWrapControl {
+    type Value = SoftWrap;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Soft Wrap".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).soft_wrap
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        EditedLanguage::content(settings, cx).soft_wrap = Some(value);
+    }
+}
+
+impl RenderOnce for SoftWrapControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(SettingsFilter::label(self.name(), cx))
+                    .child(DropdownMenu::new(
+                        "soft-wrap",
+                        Self::label(value),
+                        ContextMenu::build(cx, |mut menu, _cx| {
+                            for soft_wrap in [
+                                SoftWrap::None,
+                                SoftWrap::PreferLine,
+                                SoftWrap::EditorWidth,
+                                SoftWrap::PreferredLineLength,
+                            ] {
+                                menu = menu.custom_entry(
+                                    move |_cx| {
+                                        Label::new(Self::label(soft_wrap)).into_any_element()
+                                    },
+                                    move |cx| Self::write(soft_wrap, cx),
+                                )
+                            }
+
+                            menu
+                        }),
+                    )),
+            )
+            .children(render_language_overrides(|settings| settings.soft_wrap, cx))
+    }
+}
+
+#[derive(IntoElement)]
+struct PreferredLineLengthControl {
+    value_editor: View<NumericSettingEditor>,
+}
+
+impl PreferredLineLengthControl {
+    const MAX_LINE_LENGTH: u32 = 1000;
+}
+
+impl EditableSettingControl for PreferredLineLengthControl {
+    type Value = u32;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Preferred Line Length".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).preferred_line_length
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        EditedLanguage::content(settings, cx).preferred_line_length =
+            Some(value.clamp(1, Self::MAX_LINE_LENGTH));
+    }
+}
+
+impl NumericSettingControl for PreferredLineLengthControl {
+    fn format(value: &Self::Value) -> String {
+        value.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self::Value> {
+        text.parse().ok()
+    }
+}
+
+impl RenderOnce for PreferredLineLengthControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(SettingsFilter::label(self.name(), cx))
+                    .child(
+                        NumericStepper::new(
+                            value.to_string(),
+                            move |event, cx| {
+                                let steps = NumericStepper::step_count(event);
+                                Self::write(value.saturating_sub(steps), cx);
+                            },
+                            move |event, cx| {
+                                let steps = NumericStepper::step_count(event);
+                                Self::write(value.saturating_add(steps), cx);
+                            },
+                        )
+                        .value_editor(self.value_editor),
+                    ),
+            )
+            .children(render_language_overrides(
+                |settings| settings.preferred_line_length,
+                cx,
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct ShowWrapGuidesControl;
+
+impl EditableSettingControl for ShowWrapGuidesControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Show Wrap Guides".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).show_wrap_guides
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        EditedLanguage::content(settings, cx).show_wrap_guides = Some(value);
+    }
+}
+
+impl RenderOnce for ShowWrapGuidesControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(CheckboxWithLabel::new(
+                "show-wrap-guides",
+                SettingsFilter::label(self.name(), cx),
+                value.into(),
+                |selection, cx| {
+                    Self::write(
+                        match selection {
+                            Selection::Selected => true,
+                            Selection::Unselected | Selection::Indeterminate => false,
+                        },
+                        cx,
+                    );
+                },
+            ))
+            .children(render_language_overrides(
+                |settings| settings.show_wrap_guides,
+                cx,
+            ))
+    }
+}
+
+/// The columns at which wrap guides are shown, in addition to the preferred line length
+/// when soft wrapping at it.
+#[derive(IntoElement)]
+struct WrapGuidesControl {
+    new_wrap_guide: View<Editor>,
+}
+
+impl EditableSettingControl for WrapGuidesControl {
+    type Value = Vec<WrapGuide>;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Wrap Guides".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).wrap_guides.clone()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        mut value: Self::Value,
+        cx: &AppContext,
+    ) {
+        value.retain(|guide| {
+            (1..=PreferredLineLengthControl::MAX_LINE_LENGTH as usize).contains(&guide.column())
+        });
+        value.sort_by_key(WrapGuide::column);
+        value.dedup_by_key(|guide| guide.column());
+        EditedLanguage::content(settings, cx).wrap_guides = Some(value);
+    }
+}
+
+impl RenderOnce for WrapGuidesControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(SettingsFilter::label(self.name(), cx))
+                    .children(value.iter().map(|guide| {
+                        let column = guide.column();
+                        let wrap_guides = value.clone();
+
+                        h_flex()
+                            .pl_2()
+                            .rounded_md()
+                            .bg(cx.theme().colors().element_background)
+                            .child(Label::new(column.to_string()))
+                            .child(
+                                IconButton::new(("remove-wrap-guide", column), IconName::Close)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(move |_, cx| {
+                                        let mut wrap_guides = wrap_guides.clone();
+                                        wrap_guides.retain(|guide| guide.column() != column);
+                                        Self::write(wrap_guides, cx);
+                                    }),
+                            )
+                    }))
+                    .child(div().w_12().child(self.new_wrap_guide)),
+            )
+            .children(render_language_overrides(
+                |settings| settings.wrap_guides.clone(),
+                cx,
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct InlineGitBlameControl;
+
+impl EditableSettingControl for InlineGitBlameControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Inline Git Blame".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.git.inline_blame_enabled()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        inline_blame_settings(settings).enabled = value;
+    }
+}
+
+impl RenderOnce for InlineGitBlameControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "inline-git-blame",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+/// Returns the inline blame settings to update, creating them if needed. Inline blame
+/// is enabled unless the settings say otherwise.
+fn inline_blame_settings(settings: &mut ProjectSettings) -> &mut InlineBlameSettings {
+    settings
+        .git
+        .inline_blame
+        .get_or_insert_with(|| InlineBlameSettings {
+            enabled: true,
+            ..Default::default()
+        })
+}
+
+#[derive(IntoElement)]
+struct InlineGitBlameDelayControl;
+
+impl InlineGitBlameDelayControl {
+    /// How much the delay changes with each step.
+    const DELAY_STEP_MS: u64 = 100;
+    const MAX_DELAY_MS: u64 = 10_000;
+}
+
+impl EditableSettingControl for InlineGitBlameDelayControl {
+    type Value = u64;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Delay".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings
+            .git
+            .inline_blame
+            .and_then(|inline_blame| inline_blame.delay_ms)
+            .unwrap_or(0)
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        inline_blame_settings(settings).delay_ms = Some(value.min(Self::MAX_DELAY_MS));
+    }
+}
+
+impl RenderOnce for InlineGitBlameDelayControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(self.name(), cx))
+            .child(NumericStepper::new(
+                if value == 0 {
+                    "None".to_string()
+                } else {
+                    format!("{value} ms")
+                },
+                move |event, cx| {
+                    let step = Self::DELAY_STEP_MS * NumericStepper::step_count(event) as u64;
+                    Self::write(value.saturating_sub(step), cx);
+                },
+                move |event, cx| {
+                    let step = Self::DELAY_STEP_MS * NumericStepper::step_count(event) as u64;
+                    Self::write(value.saturating_add(step), cx);
+                },
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct InlineGitBlameCommitSummaryControl;
+
+impl EditableSettingControl for InlineGitBlameCommitSummaryControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Show Commit Summary".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.git.show_inline_commit_summary()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        inline_blame_settings(settings).show_commit_summary = value;
+    }
+}
+
+impl RenderOnce for InlineGitBlameCommitSummaryControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "inline-git-blame-commit-summary",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct InlineDiagnosticsControl;
+
+impl EditableSettingControl for InlineDiagnosticsControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Inline Diagnostics".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.diagnostics.inline.enabled
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.diagnostics.inline.enabled = value;
+    }
+}
+
+impl RenderOnce for InlineDiagnosticsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "inline-diagnostics",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+fn inline_diagnostics_severity_control(
+) -> DropdownSettingControl<ProjectSettings, DiagnosticSeverityLevel> {
+    DropdownSettingControl::<ProjectSettings, _>::new(
+        "Minimum Severity",
+        |cx| {
+            ProjectSettings::get_global(cx)
+                .diagnostics
+                .inline
+                .min_severity
+        },
+        |settings, value, _cx| settings.diagnostics.inline.min_severity = value,
+    )
+    .option(DiagnosticSeverityLevel::Error, "Error")
+    .option(DiagnosticSeverityLevel::Warning, "Warning")
+    .option(DiagnosticSeverityLevel::Info, "Info")
+    .option(DiagnosticSeverityLevel::Hint, "Hint")
+}
+
+#[derive(IntoElement)]
+struct IncludeWarningsControl;
+
+impl EditableSettingControl for IncludeWarningsControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Include Warnings".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.diagnostics.include_warnings
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.diagnostics.include_warnings = value;
+    }
+}
+
+impl RenderOnce for IncludeWarningsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "diagnostics-include-warnings",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct UnderlineWarningsControl;
+
+impl EditableSettingControl for UnderlineWarningsControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Underline Warnings".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.diagnostics.underline_warnings
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.diagnostics.underline_warnings = value;
+    }
+}
+
+impl RenderOnce for UnderlineWarningsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "diagnostics-underline-warnings",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct DiagnosticsUpdateDebounceControl;
+
+impl DiagnosticsUpdateDebounceControl {
+    /// How much the debounce changes with each step.
+    const DEBOUNCE_STEP_MS: u64 = 50;
+    const MAX_DEBOUNCE_MS: u64 = 5_000;
+}
+
+impl EditableSettingControl for DiagnosticsUpdateDebounceControl {
+    type Value = u64;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Update Debounce".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings.diagnostics.update_debounce_ms
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.diagnostics.update_debounce_ms = value.min(Self::MAX_DEBOUNCE_MS);
+    }
+}
+
+impl RenderOnce for DiagnosticsUpdateDebounceControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(self.name(), cx))
+            .child(NumericStepper::new(
+                if value == 0 {
+                    "None".to_string()
+                } else {
+                    format!("{value} ms")
+                },
+                move |event, cx| {
+                    let step = Self::DEBOUNCE_STEP_MS * NumericStepper::step_count(event) as u64;
+                    Self::write(value.saturating_sub(step), cx);
+                },
+                move |event, cx| {
+                    let step = Self::DEBOUNCE_STEP_MS * NumericStepper::step_count(event) as u64;
+                    Self::write(value.saturating_add(step), cx);
+                },
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct LineNumbersControl;
+
+impl EditableSettingControl for LineNumbersControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Line Numbers".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).gutter.line_numbers
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .gutter
+            .get_or_insert_with(Default::default)
+            .line_numbers = Some(value);
+    }
+}
+
+impl RenderOnce for LineNumbersControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "line-numbers",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct RelativeLineNumbersControl;
+
+impl EditableSettingControl for RelativeLineNumbersControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Relative Line Numbers".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).relative_line_numbers
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.relative_line_numbers = Some(value);
+    }
+}
+
+impl RenderOnce for RelativeLineNumbersControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "relative-line-numbers",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct GitGutterControl;
+
+impl EditableSettingControl for GitGutterControl {
+    type Value = bool;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Git Gutter".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        !matches!(settings.git.git_gutter, Some(GitGutterSetting::Hide))
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.git.git_gutter = Some(if value {
+            GitGutterSetting::TrackedFiles
+        } else {
+            GitGutterSetting::Hide
+        });
+    }
+}
+
+impl RenderOnce for GitGutterControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "git-gutter",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct GutterFoldsControl;
+
+impl EditableSettingControl for GutterFoldsControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Fold Indicators".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).gutter.folds
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.gutter.get_or_insert_with(Default::default).folds = Some(value);
+    }
+}
+
+impl RenderOnce for GutterFoldsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "gutter-folds",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+fn scrollbar_visibility_control() -> DropdownSettingControl<EditorSettings, ShowScrollbar> {
+    DropdownSettingControl::<EditorSettings, _>::new(
+        "Show Scrollbar",
+        |cx| EditorSettings::get_global(cx).scrollbar.show,
+        |settings, value, _cx| {
+            settings.scrollbar.get_or_insert_with(Default::default).show = Some(value);
+        },
+    )
+    .option(ShowScrollbar::Auto, "Auto")
+    .option(ShowScrollbar::System, "System")
+    .option(ShowScrollbar::Always, "Always")
+    .option(ShowScrollbar::Never, "Never")
+}
+
+#[derive(IntoElement)]
+struct ScrollbarDiagnosticsControl;
+
+impl EditableSettingControl for ScrollbarDiagnosticsControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Diagnostic Markers".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).scrollbar.diagnostics
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .scrollbar
+            .get_or_insert_with(Default::default)
+            .diagnostics = Some(value);
+    }
+}
+
+impl RenderOnce for ScrollbarDiagnosticsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "scrollbar-diagnostics",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct StickyScrollControl;
+
+impl EditableSettingControl for StickyScrollControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Sticky Scroll".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).sticky_scroll.enabled
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .sticky_scroll
+            .get_or_insert_with(Default::default)
+            .enabled = Some(value);
+    }
+}
+
+impl RenderOnce for StickyScrollControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "sticky-scroll",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct MinimapControl;
+
+impl EditableSettingControl for MinimapControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Minimap".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).minimap.enabled
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .minimap
+            .get_or_insert_with(Default::default)
+            .enabled = Some(value);
+    }
+}
+
+impl RenderOnce for MinimapControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "minimap",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct ScrollbarSearchResultsControl;
+
+impl EditableSettingControl for ScrollbarSearchResultsControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Search Result Markers".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).scrollbar.search_results
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .scrollbar
+            .get_or_insert_with(Default::default)
+            .search_results = Some(value);
+    }
+}
+
+impl RenderOnce for ScrollbarSearchResultsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "scrollbar-search-results",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct VerticalScrollMarginControl;
+
+impl VerticalScrollMarginControl {
+    const MAX_MARGIN: f32 = 20.;
+}
+
+impl EditableSettingControl for VerticalScrollMarginControl {
+    type Value = f32;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Vertical Scroll Margin".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).vertical_scroll_margin
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.vertical_scroll_margin = Some(value.clamp(0., Self::MAX_MARGIN));
+    }
+}
+
+impl RenderOnce for VerticalScrollMarginControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(self.name(), cx))
+            .child(NumericStepper::new(
+                format!("{value} lines"),
+                move |event, cx| Self::write(value - NumericStepper::step_count(event) as f32, cx),
+                move |event, cx| Self::write(value + NumericStepper::step_count(event) as f32, cx),
+            ))
+    }
+}
+
+fn scroll_beyond_last_line_control() -> DropdownSettingControl<EditorSettings, ScrollBeyondLastLine>
+{
+    DropdownSettingControl::<EditorSettings, _>::new(
+        "Scroll Beyond Last Line",
+        |cx| EditorSettings::get_global(cx).scroll_beyond_last_line,
+        |settings, value, _cx| settings.scroll_beyond_last_line = Some(value),
+    )
+    .option(ScrollBeyondLastLine::Off, "Off")
+    .option(ScrollBeyondLastLine::OnePage, "One Page")
+    .option(
+        ScrollBeyondLastLine::VerticalScrollMargin,
+        "Vertical Scroll Margin",
+    )
+}
+
+#[derive(IntoElement)]
+struct FormatOnSaveControl;
+
+impl EditableSettingControl for FormatOnSaveControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Format On Save".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).format_on_save != FormatOnSave::Off
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        let settings = EditedLanguage::content(settings, cx);
+        // Keep a configured list of formatters when formatting on save stays enabled.
+        if value && matches!(settings.format_on_save, Some(FormatOnSave::List(_))) {
+            return;
+        }
+        settings.format_on_save = Some(if value {
+            FormatOnSave::On
+        } else {
+            FormatOnSave::Off
+        });
+    }
+}
+
+impl RenderOnce for FormatOnSaveControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(CheckboxWithLabel::new(
+                "format-on-save",
+                SettingsFilter::label(self.name(), cx),
+                value.into(),
+                |selection, cx| {
+                    Self::write(
+                        match selection {
+                            Selection::Selected => true,
+                            Selection::Unselected | Selection::Indeterminate => false,
+                        },
+                        cx,
+                    );
+                },
+            ))
+            .children(render_language_overrides(
+                |settings| settings.format_on_save == FormatOnSave::Off,
+                cx,
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct FormatterControl {
+    external_command: View<Editor>,
+    external_arguments: View<Editor>,
+}
+
+impl FormatterControl {
+    fn label(formatter: &SelectedFormatter) -> &'static str {
+        match formatter {
+            SelectedFormatter::Auto => "Auto",
+            SelectedFormatter::List(list) => match list.as_ref() {
+                [Formatter::LanguageServer { .. }] => "Language Server",
+                [Formatter::Prettier] => "Prettier",
+                [Formatter::External { .. }] => "External Command",
+                _ => "Custom",
+            },
+        }
+    }
+
+    fn single(formatter: Formatter) -> SelectedFormatter {
+        SelectedFormatter::List(FormatterList(SingleOrVec::Single(Box::new(formatter))))
+    }
+
+    /// Returns the command and arguments of the default formatter, if it's a single
+    /// external command.
+    fn external_command(cx: &AppContext) -> Option<(Arc<str>, Arc<[String]>)> {
+        match &EditedLanguage::settings(cx).formatter {
+            SelectedFormatter::List(list) => match list.as_ref() {
+                [Formatter::External { command, arguments }] => {
+                    Some((command.clone(), arguments.clone()))
+                }
+                _ => None,
+            },
+            SelectedFormatter::Auto => None,
+        }
+    }
+
+    /// Builds an external formatter from the command and the whitespace-separated
+    /// arguments in the given editors.
+    fn external_formatter(
+        command: &View<Editor>,
+        arguments: &View<Editor>,
+        cx: &AppContext,
+    ) -> SelectedFormatter {
+        let command = command.read(cx).text(cx).trim().into();
+        let arguments = arguments
+            .read(cx)
+            .text(cx)
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect();
+        Self::single(Formatter::External { command, arguments })
+    }
+}
+
+impl EditableSettingControl for FormatterControl {
+    type Value = SelectedFormatter;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Formatter".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).formatter.clone()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        EditedLanguage::content(settings, cx).formatter = Some(value);
+    }
+}
+
+impl RenderOnce for FormatterControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+        let is_external = Self::external_command(cx).is_some();
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(SettingsFilter::label(self.name(), cx))
+                    .child(DropdownMenu::new(
+                        "formatter",
+                        Self::label(&value),
+                        ContextMenu::build(cx, {
+                            let external_command = self.external_command.clone();
+                            let external_arguments = self.external_arguments.clone();
+                            move |menu, _cx| {
+                                menu.custom_entry(
+                                    |_cx| Label::new("Auto").into_any_element(),
+                                    |cx| Self::write(SelectedFormatter::Auto, cx),
+                                )
+                                .custom_entry(
+                                    |_cx| Label::new("Language Server").into_any_element(),
+                                    |cx| {
+                                        Self::write(
+                                            Self::single(Formatter::LanguageServer { name: None }),
+                                            cx,
+                                        )
+                                    },
+                                )
+                                .custom_entry(
+                                    |_cx| Label::new("Prettier").into_any_element(),
+                                    |cx| Self::write(Self::single(Formatter::Prettier), cx),
+                                )
+                                .custom_entry(
+                                    |_cx| Label::new("External Command").into_any_element(),
+                                    move |cx| {
+                                        let formatter = Self::external_formatter(
+                                            &external_command,
+                                            &external_arguments,
+                                            cx,
+                                        );
+                                        Self::write(formatter, cx);
+                                    },
+                                )
+                            }
+                        }),
+                    )),
+            )
+            .when(is_external, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .child(div().w_48().child(self.external_command))
+                        .child(div().flex_1().child(self.external_arguments)),
+                )
+            })
+            .children(render_language_overrides(
+                |settings| settings.formatter.clone(),
+                cx,
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct AutosaveControl;
+
+impl AutosaveControl {
+    /// The delay used when switching to autosaving after a delay.
+    const DEFAULT_DELAY_MS: u64 = 1000;
+    /// How much the delay changes with each step.
+    const DELAY_STEP_MS: u64 = 100;
+    const MAX_DELAY_MS: u64 = 60_000;
+
+    fn label(autosave: AutosaveSetting) -> &'static str {
+        match autosave {
+            AutosaveSetting::Off => "Off",
+            AutosaveSetting::AfterDelay { .. } => "After Delay",
+            AutosaveSetting::OnFocusChange => "On Focus Change",
+            AutosaveSetting::OnWindowChange => "On Window Change",
+        }
+    }
+}
+
+impl EditableSettingControl for AutosaveControl {
+    type Value = AutosaveSetting;
+    type Settings = WorkspaceSettings;
+
+    fn name(&self) -> SharedString {
+        "Autosave".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        WorkspaceSettings::get_global(cx).autosave
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.autosave = Some(match value {
+            AutosaveSetting::AfterDelay { milliseconds } => AutosaveSetting::AfterDelay {
+                milliseconds: milliseconds.clamp(Self::DELAY_STEP_MS, Self::MAX_DELAY_MS),
+            },
+            autosave => autosave,
+        });
+    }
+}
+
+impl RenderOnce for AutosaveControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(self.name(), cx))
+            .child(DropdownMenu::new(
+                "autosave",
+                Self::label(value),
+                ContextMenu::build(cx, |mut menu, _cx| {
+                    for autosave in [
+                        AutosaveSetting::Off,
+                        AutosaveSetting::AfterDelay {
+                            milliseconds: Self::DEFAULT_DELAY_MS,
+                        },
+                        AutosaveSetting::OnFocusChange,
+                        AutosaveSetting::OnWindowChange,
+                    ] {
+                        menu = menu.custom_entry(
+                            move |_cx| Label::new(Self::label(autosave)).into_any_element(),
+                            move |cx| {
+                                // Keep the configured delay when it's already in use.
+                                if mem::discriminant(&Self::read(cx))
+                                    != mem::discriminant(&autosave)
+                                {
+                                    Self::write(autosave, cx);
+                                }
+                            },
+                        )
+                    }
+
+                    menu
+                }),
+            ))
+            .when_some(
+                match value {
+                    AutosaveSetting::AfterDelay { milliseconds } => Some(milliseconds),
+                    _ => None,
+                },
+                |this, milliseconds| {
+                    this.child(NumericStepper::new(
+                        format!("{milliseconds} ms"),
+                        move |event, cx| {
+                            let step =
+                                Self::DELAY_STEP_MS * NumericStepper::step_count(event) as u64;
+                            let milliseconds = milliseconds.saturating_sub(step);
+                            Self::write(AutosaveSetting::AfterDelay { milliseconds }, cx);
+                        },
+                        move |event, cx| {
+                            let step =
+                                Self::DELAY_STEP_MS * NumericStepper::step_count(event) as u64;
+                            let milliseconds = milliseconds.saturating_add(step);
+                            Self::write(AutosaveSetting::AfterDelay { milliseconds }, cx);
+                        },
+                    ))
+                },
+            )
+    }
+}
+
+fn cursor_shape_control() -> DropdownSettingControl<EditorSettings, CursorShape> {
+    DropdownSettingControl::<EditorSettings, _>::new(
+        "Cursor Shape",
+        |cx| EditorSettings::get_global(cx).cursor_shape,
+        |settings, value, _cx| settings.cursor_shape = Some(value),
+    )
+    .option(CursorShape::Bar, "Bar")
+    .option(CursorShape::Block, "Block")
+    .option(CursorShape::Underscore, "Underline")
+    .option(CursorShape::Hollow, "Hollow")
+}
+
+#[derive(IntoElement)]
+struct CursorBlinkControl;
+
+impl EditableSettingControl for CursorBlinkControl {
+    type Value = bool;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Cursor Blink".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditorSettings::get_global(cx).cursor_blink
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.cursor_blink = Some(value);
+    }
+}
+
+impl RenderOnce for CursorBlinkControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "cursor-blink",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct InlayHintsControl;
+
+impl EditableSettingControl for InlayHintsControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Show Inlay Hints".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).inlay_hints.enabled
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        inlay_hint_settings(settings, cx).enabled = value;
+    }
+}
+
+impl RenderOnce for InlayHintsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(CheckboxWithLabel::new(
+                "inlay-hints",
+                SettingsFilter::label(self.name(), cx),
+                value.into(),
+                |selection, cx| {
+                    Self::write(
+                        match selection {
+                            Selection::Selected => true,
+                            Selection::Unselected | Selection::Indeterminate => false,
+                        },
+                        cx,
+                    );
+                },
+            ))
+            .children(render_language_overrides(
+                |settings| settings.inlay_hints.enabled,
+                cx,
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct TypeHintsControl;
+
+impl EditableSettingControl for TypeHintsControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Type Hints".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx).inlay_hints.show_type_hints
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        inlay_hint_settings(settings, cx).show_type_hints = value;
+    }
+}
+
+impl RenderOnce for TypeHintsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "inlay-hints-types",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct ParameterHintsControl;
+
+impl EditableSettingControl for ParameterHintsControl {
+    type Value = bool;
+    type Settings = AllLanguageSettings;
+
+    fn name(&self) -> SharedString {
+        "Parameter Hints".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        EditedLanguage::settings(cx)
+            .inlay_hints
+            .show_parameter_hints
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        cx: &AppContext,
+    ) {
+        inlay_hint_settings(settings, cx).show_parameter_hints = value;
+    }
+}
+
+impl RenderOnce for ParameterHintsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "inlay-hints-parameters",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::{apply_test_setting, write_test_setting, SettingsStore};
+
+    fn init_test(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        theme::init(theme::LoadThemes::JustBase, cx);
+        language::init(cx);
+        project::Project::init_settings(cx);
+        workspace::init_settings(cx);
+        EditorSettings::register(cx);
+    }
+
+    /// Picks each option of the dropdown in turn, checking that the setting takes its value.
+    fn assert_dropdown_options<S: Settings, V: 'static + Copy + PartialEq + Send>(
+        control: DropdownSettingControl<S, V>,
+        cx: &mut AppContext,
+    ) {
+        assert!(!control.options().is_empty());
+        for (value, label) in control.options() {
+            apply_test_setting::<S>(|settings, cx| control.apply(settings, *value, cx), cx);
+            assert!(
+                control.read(cx) == *value,
+                "{} isn't {label:?}",
+                control.name()
+            );
+        }
+    }
+
+    #[gpui::test]
+    fn test_indentation_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<TabSizeControl>(NonZeroU32::new(2).unwrap(), cx);
+        assert_eq!(TabSizeControl::read(cx).get(), 2);
+        write_test_setting::<TabSizeControl>(NonZeroU32::new(100).unwrap(), cx);
+        assert_eq!(TabSizeControl::read(cx).get(), TabSizeControl::MAX_TAB_SIZE);
+
+        write_test_setting::<HardTabsControl>(true, cx);
+        assert!(HardTabsControl::read(cx));
+        write_test_setting::<HardTabsControl>(false, cx);
+        assert!(!HardTabsControl::read(cx));
+    }
+
+    #[gpui::test]
+    fn test_soft_wrap_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<SoftWrapControl>(SoftWrap::PreferredLineLength, cx);
+        assert_eq!(SoftWrapControl::read(cx), SoftWrap::PreferredLineLength);
+
+        write_test_setting::<PreferredLineLengthControl>(100, cx);
+        assert_eq!(PreferredLineLengthControl::read(cx), 100);
+        write_test_setting::<PreferredLineLengthControl>(0, cx);
+        assert_eq!(PreferredLineLengthControl::read(cx), 1);
+        write_test_setting::<PreferredLineLengthControl>(5000, cx);
+        assert_eq!(
+            PreferredLineLengthControl::read(cx),
+            PreferredLineLengthControl::MAX_LINE_LENGTH
+        );
+    }
+
+    #[gpui::test]
+    fn test_buffer_line_height_control(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<BufferLineHeightControl>(BufferLineHeight::Standard, cx);
+        assert_eq!(
+            BufferLineHeightControl::read(cx),
+            BufferLineHeight::Standard
+        );
+
+        write_test_setting::<BufferLineHeightControl>(BufferLineHeight::Custom(1.5), cx);
+        assert_eq!(
+            BufferLineHeightControl::read(cx),
+            BufferLineHeight::Custom(1.5)
+        );
+        write_test_setting::<BufferLineHeightControl>(BufferLineHeight::Custom(10.), cx);
+        assert_eq!(
+            BufferLineHeightControl::read(cx),
+            BufferLineHeight::Custom(BufferLineHeightControl::MAX_LINE_HEIGHT)
+        );
+        write_test_setting::<BufferLineHeightControl>(BufferLineHeight::Custom(0.5), cx);
+        assert_eq!(
+            BufferLineHeightControl::read(cx),
+            BufferLineHeight::Custom(BufferLineHeightControl::MIN_LINE_HEIGHT)
+        );
+    }
+
+    #[gpui::test]
+    fn test_gutter_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<LineNumbersControl>(false, cx);
+        write_test_setting::<GutterFoldsControl>(false, cx);
+        assert!(!LineNumbersControl::read(cx));
+        assert!(!GutterFoldsControl::read(cx));
+        write_test_setting::<GutterFoldsControl>(true, cx);
+        assert!(GutterFoldsControl::read(cx));
+        assert!(!LineNumbersControl::read(cx));
+
+        write_test_setting::<RelativeLineNumbersControl>(true, cx);
+        assert!(RelativeLineNumbersControl::read(cx));
+
+        write_test_setting::<GitGutterControl>(false, cx);
+        assert!(!GitGutterControl::read(cx));
+        assert!(matches!(
+            ProjectSettings::get_global(cx).git.git_gutter,
+            Some(GitGutterSetting::Hide)
+        ));
+        write_test_setting::<GitGutterControl>(true, cx);
+        assert!(GitGutterControl::read(cx));
+        assert!(matches!(
+            ProjectSettings::get_global(cx).git.git_gutter,
+            Some(GitGutterSetting::TrackedFiles)
+        ));
+    }
+
+    #[gpui::test]
+    fn test_formatting_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<FormatOnSaveControl>(false, cx);
+        assert!(!FormatOnSaveControl::read(cx));
+        write_test_setting::<FormatOnSaveControl>(true, cx);
+        assert!(FormatOnSaveControl::read(cx));
+        assert_eq!(
+            EditedLanguage::settings(cx).format_on_save,
+            FormatOnSave::On
+        );
+
+        // Enabling formatting on save keeps the formatters it's configured with.
+        let formatters = FormatOnSave::List(FormatterList(SingleOrVec::Single(Box::new(
+            Formatter::Prettier,
+        ))));
+        apply_test_setting::<AllLanguageSettings>(
+            |settings, _| settings.defaults.format_on_save = Some(formatters.clone()),
+            cx,
+        );
+        write_test_setting::<FormatOnSaveControl>(true, cx);
+        assert_eq!(EditedLanguage::settings(cx).format_on_save, formatters);
+
+        let formatter = FormatterControl::single(Formatter::External {
+            command: "rustfmt".into(),
+            arguments: vec!["--edition".to_string(), "2021".to_string()].into(),
+        });
+        write_test_setting::<FormatterControl>(formatter.clone(), cx);
+        assert_eq!(FormatterControl::read(cx), formatter);
+        assert_eq!(FormatterControl::label(&formatter), "External Command");
+        let (command, arguments) = FormatterControl::external_command(cx).unwrap();
+        assert_eq!(command.as_ref(), "rustfmt");
+        assert_eq!(arguments.as_ref(), ["--edition", "2021"]);
+
+        write_test_setting::<FormatterControl>(SelectedFormatter::Auto, cx);
+        assert_eq!(FormatterControl::read(cx), SelectedFormatter::Auto);
+        assert!(FormatterControl::external_command(cx).is_none());
+    }
+
+    #[gpui::test]
+    fn test_autosave_control(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<AutosaveControl>(AutosaveSetting::OnFocusChange, cx);
+        assert_eq!(AutosaveControl::read(cx), AutosaveSetting::OnFocusChange);
+
+        write_test_setting::<AutosaveControl>(
+            AutosaveSetting::AfterDelay { milliseconds: 500 },
+            cx,
+        );
+        assert_eq!(
+            AutosaveControl::read(cx),
+            AutosaveSetting::AfterDelay { milliseconds: 500 }
+        );
+        write_test_setting::<AutosaveControl>(AutosaveSetting::AfterDelay { milliseconds: 0 }, cx);
+        assert_eq!(
+            AutosaveControl::read(cx),
+            AutosaveSetting::AfterDelay {
+                milliseconds: AutosaveControl::DELAY_STEP_MS
+            }
+        );
+        write_test_setting::<AutosaveControl>(
+            AutosaveSetting::AfterDelay {
+                milliseconds: u64::MAX,
+            },
+            cx,
+        );
+        assert_eq!(
+            AutosaveControl::read(cx),
+            AutosaveSetting::AfterDelay {
+                milliseconds: AutosaveControl::MAX_DELAY_MS
+            }
+        );
+
+        write_test_setting::<AutosaveControl>(AutosaveSetting::Off, cx);
+        assert_eq!(AutosaveControl::read(cx), AutosaveSetting::Off);
+    }
+
+    #[gpui::test]
+    fn test_cursor_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<CursorBlinkControl>(false, cx);
+        assert!(!CursorBlinkControl::read(cx));
+        write_test_setting::<CursorBlinkControl>(true, cx);
+        assert!(CursorBlinkControl::read(cx));
+
+        apply_test_setting::<EditorSettings>(
+            |settings, _| settings.cursor_shape = Some(CursorShape::Underscore),
+            cx,
+        );
+        assert_eq!(
+            EditorSettings::get_global(cx).cursor_shape,
+            CursorShape::Underscore
+        );
+    }
+
+    #[gpui::test]
+    fn test_inlay_hint_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<InlayHintsControl>(true, cx);
+        assert!(InlayHintsControl::read(cx));
+        let type_hints = TypeHintsControl::read(cx);
+        let parameter_hints = ParameterHintsControl::read(cx);
+
+        // Each control only changes its own inlay hint setting.
+        write_test_setting::<TypeHintsControl>(!type_hints, cx);
+        assert_eq!(TypeHintsControl::read(cx), !type_hints);
+        assert_eq!(ParameterHintsControl::read(cx), parameter_hints);
+        assert!(InlayHintsControl::read(cx));
+
+        write_test_setting::<ParameterHintsControl>(!parameter_hints, cx);
+        assert_eq!(ParameterHintsControl::read(cx), !parameter_hints);
+        assert_eq!(TypeHintsControl::read(cx), !type_hints);
+
+        write_test_setting::<InlayHintsControl>(false, cx);
+        assert!(!InlayHintsControl::read(cx));
+        assert_eq!(TypeHintsControl::read(cx), !type_hints);
+    }
+
+    #[gpui::test]
+    fn test_language_overrides(cx: &mut AppContext) {
+        init_test(cx);
+        let default_tab_size = TabSizeControl::read(cx);
+
+        cx.set_global(EditedLanguage(Some("Rust".into())));
+        write_test_setting::<TabSizeControl>(NonZeroU32::new(8).unwrap(), cx);
+        assert_eq!(TabSizeControl::read(cx).get(), 8);
+
+        let settings = AllLanguageSettings::get_global(cx);
+        assert_eq!(settings.language(Some("Rust")).tab_size.get(), 8);
+        assert_eq!(settings.language(None).tab_size, default_tab_size);
+        assert_eq!(
+            settings.languages_overriding(|settings| settings.tab_size),
+            vec![Arc::<str>::from("Rust")]
+        );
+
+        // Without an edited language, the controls edit the defaults again.
+        cx.set_global(EditedLanguage::default());
+        assert_eq!(TabSizeControl::read(cx), default_tab_size);
+        write_test_setting::<TabSizeControl>(NonZeroU32::new(2).unwrap(), cx);
+        let settings = AllLanguageSettings::get_global(cx);
+        assert_eq!(settings.language(None).tab_size.get(), 2);
+        assert_eq!(settings.language(Some("Rust")).tab_size.get(), 8);
+    }
+
+    #[gpui::test]
+    fn test_scrolling_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        let diagnostics = ScrollbarDiagnosticsControl::read(cx);
+        let search_results = ScrollbarSearchResultsControl::read(cx);
+        write_test_setting::<ScrollbarDiagnosticsControl>(!diagnostics, cx);
+        assert_eq!(ScrollbarDiagnosticsControl::read(cx), !diagnostics);
+        assert_eq!(ScrollbarSearchResultsControl::read(cx), search_results);
+        write_test_setting::<ScrollbarSearchResultsControl>(!search_results, cx);
+        assert_eq!(ScrollbarSearchResultsControl::read(cx), !search_results);
+        assert_eq!(ScrollbarDiagnosticsControl::read(cx), !diagnostics);
+
+        let sticky_scroll = StickyScrollControl::read(cx);
+        write_test_setting::<StickyScrollControl>(!sticky_scroll, cx);
+        assert_eq!(StickyScrollControl::read(cx), !sticky_scroll);
+
+        let minimap = MinimapControl::read(cx);
+        write_test_setting::<MinimapControl>(!minimap, cx);
+        assert_eq!(MinimapControl::read(cx), !minimap);
+
+        write_test_setting::<VerticalScrollMarginControl>(5., cx);
+        assert_eq!(VerticalScrollMarginControl::read(cx), 5.);
+        write_test_setting::<VerticalScrollMarginControl>(100., cx);
+        assert_eq!(
+            VerticalScrollMarginControl::read(cx),
+            VerticalScrollMarginControl::MAX_MARGIN
+        );
+        write_test_setting::<VerticalScrollMarginControl>(-1., cx);
+        assert_eq!(VerticalScrollMarginControl::read(cx), 0.);
+    }
+
+    #[test]
+    fn test_numeric_controls_parsing() {
+        assert_eq!(TabSizeControl::parse("8"), NonZeroU32::new(8));
+        assert_eq!(TabSizeControl::parse("0"), None);
+        assert_eq!(TabSizeControl::parse("four"), None);
+        assert_eq!(TabSizeControl::format(&NonZeroU32::new(4).unwrap()), "4");
+
+        assert_eq!(BufferFontSizeControl::parse("15"), Some(px(15.)));
+        assert_eq!(BufferFontSizeControl::parse("13.5px"), Some(px(13.5)));
+        assert_eq!(BufferFontSizeControl::parse("px"), None);
+        assert_eq!(BufferFontSizeControl::format(&px(14.)), "14");
+
+        assert_eq!(PreferredLineLengthControl::parse("100"), Some(100));
+        assert_eq!(PreferredLineLengthControl::parse("-1"), None);
+        assert_eq!(PreferredLineLengthControl::format(&80), "80");
+    }
+
+    #[gpui::test]
+    fn test_font_features_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<BufferFontFeaturesControl>(
+            FontFeatures::default().with_feature("ss01", true),
+            cx,
+        );
+        assert_eq!(
+            BufferFontFeaturesControl::read(cx).is_enabled("ss01"),
+            Some(true)
+        );
+        assert!(BufferFontLigaturesControl::read(cx));
+
+        // Toggling the ligatures keeps the other font features.
+        write_test_setting::<BufferFontLigaturesControl>(false, cx);
+        assert!(!BufferFontLigaturesControl::read(cx));
+        let features = BufferFontFeaturesControl::read(cx);
+        assert_eq!(features.is_calt_enabled(), Some(false));
+        assert_eq!(features.is_enabled("ss01"), Some(true));
+
+        write_test_setting::<BufferFontFeaturesControl>(features.with_feature("ss01", false), cx);
+        let features = BufferFontFeaturesControl::read(cx);
+        assert_eq!(features.is_enabled("ss01"), Some(false));
+        assert_eq!(features.is_calt_enabled(), Some(false));
+    }
+
+    #[test]
+    fn test_font_feature_labels() {
+        assert_eq!(BufferFontFeaturesControl::label("zero"), "Slashed Zero");
+        assert_eq!(BufferFontFeaturesControl::label("ss01"), "Stylistic Set 01");
+        assert_eq!(
+            BufferFontFeaturesControl::label("cv10"),
+            "Character Variant 10"
+        );
+        assert_eq!(BufferFontFeaturesControl::label("liga"), "liga");
+        assert!(BufferFontFeaturesControl::known_features("Fira Code").contains(&"cv01"));
+        assert!(!BufferFontFeaturesControl::known_features("Unknown Font").is_empty());
+    }
+
+    #[gpui::test]
+    fn test_inline_git_blame_controls(cx: &mut AppContext) {
+        init_test(cx);
+        assert!(InlineGitBlameControl::read(cx));
+
+        // Each control keeps the other inline blame settings, including whether it's enabled.
+        write_test_setting::<InlineGitBlameDelayControl>(500, cx);
+        assert_eq!(InlineGitBlameDelayControl::read(cx), 500);
+        assert!(InlineGitBlameControl::read(cx));
+
+        write_test_setting::<InlineGitBlameCommitSummaryControl>(true, cx);
+        assert!(InlineGitBlameCommitSummaryControl::read(cx));
+        assert_eq!(InlineGitBlameDelayControl::read(cx), 500);
+
+        write_test_setting::<InlineGitBlameControl>(false, cx);
+        assert!(!InlineGitBlameControl::read(cx));
+        assert_eq!(InlineGitBlameDelayControl::read(cx), 500);
+        assert!(InlineGitBlameCommitSummaryControl::read(cx));
+
+        write_test_setting::<InlineGitBlameDelayControl>(60_000, cx);
+        assert_eq!(
+            InlineGitBlameDelayControl::read(cx),
+            InlineGitBlameDelayControl::MAX_DELAY_MS
+        );
+        assert!(!InlineGitBlameControl::read(cx));
+    }
+
+    #[gpui::test]
+    fn test_dropdown_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        assert_dropdown_options(cursor_shape_control(), cx);
+        assert_dropdown_options(scrollbar_visibility_control(), cx);
+        assert_dropdown_options(scroll_beyond_last_line_control(), cx);
+
+        // Picking an option keeps the other settings of the same object.
+        write_test_setting::<ScrollbarDiagnosticsControl>(false, cx);
+        let control = scrollbar_visibility_control();
+        apply_test_setting::<EditorSettings>(
+            |settings, cx| control.apply(settings, ShowScrollbar::Always, cx),
+            cx,
+        );
+        assert_eq!(control.read(cx), ShowScrollbar::Always);
+        assert!(!ScrollbarDiagnosticsControl::read(cx));
+    }
+
+    #[gpui::test]
+    fn test_wrap_guide_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<ShowWrapGuidesControl>(false, cx);
+        assert!(!ShowWrapGuidesControl::read(cx));
+        write_test_setting::<ShowWrapGuidesControl>(true, cx);
+        assert!(ShowWrapGuidesControl::read(cx));
+
+        // Guides are sorted by column, without duplicates or columns out of range.
+        write_test_setting::<WrapGuidesControl>(
+            vec![
+                WrapGuide::Column(120),
+                WrapGuide::Column(0),
+                WrapGuide::Column(80),
+                WrapGuide::Column(5000),
+                WrapGuide::Column(120),
+            ],
+            cx,
+        );
+        let columns = WrapGuidesControl::read(cx)
+            .iter()
+            .map(WrapGuide::column)
+            .collect::<Vec<_>>();
+        assert_eq!(columns, [80, 120]);
+
+        write_test_setting::<WrapGuidesControl>(Vec::new(), cx);
+        assert!(WrapGuidesControl::read(cx).is_empty());
+    }
+
+    #[gpui::test]
+    fn test_diagnostics_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<InlineDiagnosticsControl>(true, cx);
+        assert!(InlineDiagnosticsControl::read(cx));
+        assert_dropdown_options(inline_diagnostics_severity_control(), cx);
+        assert!(InlineDiagnosticsControl::read(cx));
+
+        let include_warnings = IncludeWarningsControl::read(cx);
+        let underline_warnings = UnderlineWarningsControl::read(cx);
+        write_test_setting::<IncludeWarningsControl>(!include_warnings, cx);
+        assert_eq!(IncludeWarningsControl::read(cx), !include_warnings);
+        assert_eq!(UnderlineWarningsControl::read(cx), underline_warnings);
+        write_test_setting::<UnderlineWarningsControl>(!underline_warnings, cx);
+        assert_eq!(UnderlineWarningsControl::read(cx), !underline_warnings);
+        assert_eq!(IncludeWarningsControl::read(cx), !include_warnings);
+
+        write_test_setting::<DiagnosticsUpdateDebounceControl>(200, cx);
+        assert_eq!(DiagnosticsUpdateDebounceControl::read(cx), 200);
+        write_test_setting::<DiagnosticsUpdateDebounceControl>(60_000, cx);
+        assert_eq!(
+            DiagnosticsUpdateDebounceControl::read(cx),
+            DiagnosticsUpdateDebounceControl::MAX_DEBOUNCE_MS
+        );
+        assert!(InlineDiagnosticsControl::read(cx));
     }
 }
@@ -16,12 +16,13 @@ use gpui::{
 use indoc::indoc;
 use language::{
     language_settings::{
-        AllLanguageSettings, AllLanguageSettingsContent, LanguageSettingsContent, PrettierSettings,
+        AllLanguageSettings, AllLanguageSettingsContent, AutoPair, LanguageSettingsContent,
+        PrettierSettings, SpellCheckSetting,
     },
     BracketPairConfig,
     Capability::ReadWrite,
-    FakeLspAdapter, IndentGuide, LanguageConfig, LanguageConfigOverride, LanguageMatcher, Override,
-    ParsedMarkdown, Point,
+    DocumentationCommentConfig, FakeLspAdapter, IndentGuide, LanguageConfig,
+    LanguageConfigOverride, LanguageMatcher, Override, ParsedMarkdown, Point,
 };
 use language_settings::{Formatter, FormatterList, IndentGuideSettings};
 use multi_buffer::MultiBufferIndentGuide;
@@ -1081,6 +1082,220 @@ fn test_fold_action_multiple_line_breaks(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_fold_at_level_and_recursive(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let view = cx.add_window(|cx| {
+        let buffer = MultiBuffer::build_simple(
+            &"
+                impl Foo {
+                    fn a() {
+                        if true {
+                            1
+                        }
+                    }
+
+                    fn b() {
+                        2
+                    }
+                }
+            "
+            .unindent(),
+            cx,
+        );
+        build_editor(buffer.clone(), cx)
+    });
+
+    _ = view.update(cx, |view, cx| {
+        view.fold_at_level(&FoldAtLevel { level: 2 }, cx);
+        assert_eq!(
+            view.display_text(cx),
+            "
+                impl Foo {
+                    fn a() {⋯
+                    }
+
+                    fn b() {⋯
+                    }
+                }
+            "
+            .unindent(),
+        );
+
+        view.select_all(&SelectAll, cx);
+        view.unfold_lines(&UnfoldLines, cx);
+        view.fold_at_level(&FoldAtLevel { level: 1 }, cx);
+        assert_eq!(
+            view.display_text(cx),
+            "
+                impl Foo {⋯
+                }
+            "
+            .unindent(),
+        );
+
+        view.select_all(&SelectAll, cx);
+        view.unfold_lines(&UnfoldLines, cx);
+        view.change_selections(None, cx, |s| {
+            s.select_display_ranges([
+                DisplayPoint::new(DisplayRow(0), 0)..DisplayPoint::new(DisplayRow(0), 0)
+            ]);
+        });
+        view.fold_recursive(&FoldRecursive, cx);
+        assert_eq!(
+            view.display_text(cx),
+            "
+                impl Foo {⋯
+                }
+            "
+            .unindent(),
+        );
+
+        // Unfolding the outermost range keeps the nested ranges folded.
+        view.unfold_lines(&UnfoldLines, cx);
+        assert_eq!(
+            view.display_text(cx),
+            "
+                impl Foo {
+                    fn a() {⋯
+                    }
+
+                    fn b() {⋯
+                    }
+                }
+            "
+            .unindent(),
+        );
+    });
+}
+
+#[gpui::test]
+fn test_fold_region_markers(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(Language::new(
+        LanguageConfig {
+            line_comments: vec!["// ".into()],
+            ..LanguageConfig::default()
+        },
+        None,
+    ));
+    let text = "
+        fn main() {
+            // region Setup
+            let a = 1;
+            // region Nested
+            let b = 2;
+            // endregion
+            // endregion
+            let c = 3;
+        }
+    "
+    .unindent();
+
+    let view = cx.add_window(|cx| {
+        let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(language, cx));
+        let buffer = cx.new_model(|cx| MultiBuffer::singleton(buffer, cx));
+        build_editor(buffer, cx)
+    });
+
+    _ = view.update(cx, |view, cx| {
+        view.fold_at(
+            &FoldAt {
+                buffer_row: MultiBufferRow(3),
+            },
+            cx,
+        );
+        assert_eq!(
+            view.display_text(cx),
+            "
+                fn main() {
+                    // region Setup
+                    let a = 1;
+                    // region Nested⋯
+                    // endregion
+                    let c = 3;
+                }
+            "
+            .unindent(),
+        );
+
+        view.fold_at(
+            &FoldAt {
+                buffer_row: MultiBufferRow(1),
+            },
+            cx,
+        );
+        assert_eq!(
+            view.display_text(cx),
+            "
+                fn main() {
+                    // region Setup⋯
+                    let c = 3;
+                }
+            "
+            .unindent(),
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_fold_all_functions(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::language()),
+        )
+        .with_text_object_query(
+            r#"
+                (function_item
+                    body: (_) @function.inside)
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {"
+        ˇfn a() {
+            1
+        }
+
+        fn b()
+        where
+            T: Clone,
+        {
+            2
+        }
+
+        fn c() { 3 }
+    "});
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, cx| {
+        editor.fold_all_functions(&FoldAllFunctions, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                fn a() {⋯
+                }
+
+                fn b()
+                where
+                    T: Clone,
+                {⋯
+                }
+
+                fn c() { 3 }
+            "}
+        );
+    });
+}
+
 #[gpui::test]
 fn test_move_cursor(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -3191,7 +3406,7 @@ async fn test_manipulate_lines_with_single_selection(cx: &mut TestAppContext) {
     // From here on out, test more complex cases of manipulate_lines() with a single driver method: sort_lines_case_sensitive()
     // Since all methods calling manipulate_lines() are doing the exact same general thing (reordering lines)
 
-    // Don't manipulate when cursor is on single line, but expand the selection
+    // Manipulate the whole buffer when nothing is selected
     cx.set_state(indoc! {"
         ddˇdd
         ccc
@@ -3200,10 +3415,29 @@ async fn test_manipulate_lines_with_single_selection(cx: &mut TestAppContext) {
     "});
     cx.update_editor(|e, cx| e.sort_lines_case_sensitive(&SortLinesCaseSensitive, cx));
     cx.assert_editor_state(indoc! {"
-        «ddddˇ»
-        ccc
+        «a
         bb
-        a
+        ccc
+        ddddˇ»
+    "});
+
+    // The whole buffer is manipulated once, even with several cursors
+    cx.set_state(indoc! {"
+        ˇ3
+        1
+        ˇ2
+    "});
+    cx.update_editor(|e, cx| e.sort_lines_case_sensitive(&SortLinesCaseSensitive, cx));
+    cx.assert_editor_state(indoc! {"
+        «1
+        2
+        3ˇ»
+    "});
+    cx.update_editor(|e, cx| e.undo(&Undo, cx));
+    cx.assert_editor_state(indoc! {"
+        ˇ3
+        1
+        ˇ2
     "});
 
     // Basic manipulate case
@@ -3557,6 +3791,31 @@ async fn test_manipulate_text(cx: &mut TestAppContext) {
         «aaaBbbˇ» «bbbCccˇ» «cccDddˇ»
     "});
 
+    // Keep acronyms and digits together when splitting identifiers into words
+    cx.set_state(indoc! {"
+        ˇXMLHttpRequest ˇutf8String ˇparseHTTP2Response
+    "});
+    cx.update_editor(|e, cx| e.convert_to_snake_case(&ConvertToSnakeCase, cx));
+    cx.assert_editor_state(indoc! {"
+        «xml_http_requestˇ» «utf8_stringˇ» «parse_http2_responseˇ»
+    "});
+
+    cx.set_state(indoc! {"
+        ˇgetUserID ˇsha256_digest ˇHTTPServer
+    "});
+    cx.update_editor(|e, cx| e.convert_to_kebab_case(&ConvertToKebabCase, cx));
+    cx.assert_editor_state(indoc! {"
+        «get-user-idˇ» «sha256-digestˇ» «http-serverˇ»
+    "});
+
+    cx.set_state(indoc! {"
+        ˇsha256_digest ˇHTTPServer
+    "});
+    cx.update_editor(|e, cx| e.convert_to_upper_camel_case(&ConvertToUpperCamelCase, cx));
+    cx.assert_editor_state(indoc! {"
+        «Sha256Digestˇ» «HttpServerˇ»
+    "});
+
     cx.set_state(indoc! {"
         «hElLo, WoRld!ˇ»
     "});
@@ -3669,6 +3928,86 @@ fn test_duplicate_line(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_duplicate_selection_and_line_and_comment(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = Arc::new(Language::new(
+        LanguageConfig {
+            line_comments: vec!["// ".into()],
+            ..Default::default()
+        },
+        Some(tree_sitter_rust::language()),
+    ));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+
+    // The selected text is copied right after each selection, which stays on the original.
+    cx.set_state(indoc! {"
+        let «aˇ» = «foo(ˇ»1);
+        let b = 2;ˇ
+    "});
+    cx.update_editor(|e, cx| e.duplicate_selection(&DuplicateSelection, cx));
+    cx.assert_editor_state(indoc! {"
+        let «aˇ»a = «foo(ˇ»foo(1);
+        let b = 2;ˇ
+    "});
+
+    // Without any selected text, the lines are duplicated.
+    cx.set_state(indoc! {"
+        let a = ˇ1;
+    "});
+    cx.update_editor(|e, cx| e.duplicate_selection(&DuplicateSelection, cx));
+    cx.assert_editor_state(indoc! {"
+        let a = 1;
+        let a = ˇ1;
+    "});
+
+    // The original lines are commented out and the selections move to the copy.
+    cx.set_state(indoc! {"
+        fn a() {
+            «b();
+            cˇ»();
+            d(ˇ);
+        }
+    "});
+    cx.update_editor(|e, cx| e.duplicate_line_and_comment(&DuplicateLineAndComment, cx));
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            // b();
+            // c();
+            «b();
+            cˇ»();
+            // d();
+            d(ˇ);
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_export_as_html_and_rtf(cx: &mut gpui::TestAppContext) {
+    use crate::export::ExportFormat;
+
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        fn «a<T>()ˇ» {}
+        let s = \"{é}\";ˇ
+    "});
+    let html = cx.update_editor(|editor, cx| editor.exported_text(ExportFormat::Html, cx));
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<code>a&lt;T&gt;()</code>"));
+
+    // Without a selection, the whole buffer is exported.
+    cx.set_state(indoc! {"
+        fn a<T>() {}
+        let s = \"{é}\";ˇ
+    "});
+    let rtf = cx.update_editor(|editor, cx| editor.exported_text(ExportFormat::Rtf, cx));
+    assert!(rtf.starts_with("{\\rtf1\\ansi"));
+    assert!(rtf.contains("{ fn a<T>() \\{\\}\\line\nlet s = \"\\{\\u233?\\}\";\\line\n}"));
+}
+
 #[gpui::test]
 fn test_move_line_up_down(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -3796,6 +4135,42 @@ fn test_move_line_up_down_with_blocks(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_align_selections(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        let aˇ = 1;
+        let bcdˇ = 2;
+        let efˇ = 3;
+    "});
+    cx.update_editor(|editor, cx| editor.align_selections(&AlignSelections, cx));
+    cx.assert_editor_state(indoc! {"
+        let a  ˇ = 1;
+        let bcdˇ = 2;
+        let ef ˇ = 3;
+    "});
+
+    // Selections are aligned with the ones at the same position on the other lines.
+    cx.set_state(indoc! {"
+        «aˇ» = «1ˇ»;
+        «bcdˇ» = «23ˇ»;
+        «efˇ»
+    "});
+    cx.update_editor(|editor, cx| editor.align_selections(&AlignSelections, cx));
+    cx.assert_editor_state(indoc! {"
+        «aˇ» =   «1ˇ»;
+        «bcdˇ» = «23ˇ»;
+        «efˇ»
+    "});
+
+    // Tabs count for the width they are displayed with.
+    cx.set_state("\taˇ = 1;\nbcdefgˇ = 2;\n");
+    cx.update_editor(|editor, cx| editor.align_selections(&AlignSelections, cx));
+    cx.assert_editor_state("\ta ˇ = 1;\nbcdefgˇ = 2;\n");
+}
+
 #[gpui::test]
 fn test_transpose(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -3976,7 +4351,175 @@ async fn test_clipboard(cx: &mut gpui::TestAppContext) {
 }
 
 #[gpui::test]
-async fn test_paste_multiline(cx: &mut gpui::TestAppContext) {
+async fn test_block_selection_editing(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    fn select_block(cx: &mut EditorTestContext, tail: DisplayPoint, head: DisplayPoint) {
+        cx.update_editor(|editor, cx| {
+            editor.select(
+                SelectPhase::BeginColumnar {
+                    position: tail,
+                    goal_column: tail.column(),
+                    reset: true,
+                },
+                cx,
+            );
+            editor.select(
+                SelectPhase::Update {
+                    position: head,
+                    goal_column: head.column(),
+                    scroll_delta: gpui::Point::default(),
+                },
+                cx,
+            );
+            editor.select(SelectPhase::End, cx);
+        });
+    }
+
+    // Rows too short to reach the block get a cursor at their end, which is padded
+    // out to the block's column when typing.
+    cx.set_state(indoc! {"
+        ˇone two
+        three
+        a
+        four five"});
+    select_block(
+        &mut cx,
+        DisplayPoint::new(DisplayRow(0), 4),
+        DisplayPoint::new(DisplayRow(3), 4),
+    );
+    cx.assert_editor_state(indoc! {"
+        one ˇtwo
+        threˇe
+        aˇ
+        fourˇ five"});
+    cx.update_editor(|editor, cx| editor.handle_input("|", cx));
+    cx.assert_editor_state(indoc! {"
+        one |ˇtwo
+        thre|ˇe
+        a   |ˇ
+        four|ˇ five"});
+
+    // Copying a block and pasting it at a single cursor inserts its rows at the same
+    // column on successive lines, padding short lines and appending missing ones.
+    cx.set_state(indoc! {"
+        ˇabcd
+        ef
+        ghij"});
+    select_block(
+        &mut cx,
+        DisplayPoint::new(DisplayRow(0), 1),
+        DisplayPoint::new(DisplayRow(2), 3),
+    );
+    cx.assert_editor_state(indoc! {"
+        a«bcˇ»d
+        e«fˇ»
+        g«hiˇ»j"});
+    cx.update_editor(|editor, cx| editor.copy(&Copy, cx));
+    assert_eq!(
+        cx.read_from_clipboard().map(|item| item.text().to_owned()),
+        Some("bc\nf\nhi".to_owned())
+    );
+
+    cx.set_state(indoc! {"
+        xyˇw
+        z"});
+    cx.update_editor(|editor, cx| editor.paste(&Paste, cx));
+    cx.assert_editor_state(indoc! {"
+        xybcˇw
+        z fˇ
+          hiˇ"});
+
+    // Pasting the block again at a block of the same height pastes one row per cursor,
+    // still padding the cursors on short rows.
+    cx.set_state(indoc! {"
+        ˇ12345
+        1
+        123"});
+    select_block(
+        &mut cx,
+        DisplayPoint::new(DisplayRow(0), 3),
+        DisplayPoint::new(DisplayRow(2), 3),
+    );
+    cx.update_editor(|editor, cx| editor.paste(&Paste, cx));
+    cx.assert_editor_state(indoc! {"
+        123bcˇ45
+        1  fˇ
+        123hiˇ"});
+}
+
+#[gpui::test]
+async fn test_jump_to_char(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        ˇfn one() {}
+        fn two() {}
+        fn three() {}"});
+    cx.update_editor(|editor, cx| editor.set_visible_line_count(10., cx));
+
+    // A character occurring once in the visible rows is jumped to right away.
+    cx.update_editor(|editor, cx| {
+        editor.jump_to_char(&JumpToChar, cx);
+        editor.jump_to_char_input("w", cx);
+        assert!(!editor.is_jumping_to_char());
+    });
+    cx.assert_editor_state(indoc! {"
+        fn one() {}
+        fn tˇwo() {}
+        fn three() {}"});
+
+    // Otherwise, the occurrences are labeled closest to the cursor first, leaving out
+    // the characters following an occurrence.
+    cx.update_editor(|editor, cx| {
+        editor.jump_to_char(&JumpToChar, cx);
+        editor.jump_to_char_input("o", cx);
+        let labels = editor
+            .jump_to_char_state
+            .as_ref()
+            .unwrap()
+            .targets
+            .iter()
+            .map(|target| target.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["f", "j"]);
+        editor.jump_to_char_input("j", cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        fn ˇone() {}
+        fn two() {}
+        fn three() {}"});
+
+    // Typing a character that isn't a label narrows down the occurrences.
+    cx.update_editor(|editor, cx| {
+        editor.jump_to_char(&JumpToChar, cx);
+        editor.jump_to_char_input("fn", cx);
+        assert_eq!(editor.jump_to_char_state.as_ref().unwrap().targets.len(), 3);
+        editor.jump_to_char_input("d", cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        fn one() {}
+        fn two() {}
+        ˇfn three() {}"});
+
+    // Cancelling leaves the cursor in place.
+    cx.update_editor(|editor, cx| {
+        editor.jump_to_char(&JumpToChar, cx);
+        editor.jump_to_char_input("f", cx);
+        editor.cancel(&Cancel, cx);
+        assert!(!editor.is_jumping_to_char());
+    });
+    cx.assert_editor_state(indoc! {"
+        fn one() {}
+        fn two() {}
+        ˇfn three() {}"});
+}
+
+#[gpui::test]
+async fn test_paste_multiline(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
 
     let mut cx = EditorTestContext::new(cx).await;
@@ -4089,6 +4632,84 @@ async fn test_paste_multiline(cx: &mut gpui::TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_paste_external_text(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = Arc::new(Language::new(
+        LanguageConfig::default(),
+        Some(tree_sitter_rust::language()),
+    ));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+
+    // Text copied from the middle of an indented line, outside of Zed.
+    cx.write_to_clipboard(ClipboardItem::new(
+        "d(\n        e,\n        f\n    )".to_string(),
+    ));
+
+    // Paste it at a line with a lower indent level.
+    cx.set_state(indoc! {"
+        ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+    cx.update_editor(|e, cx| e.paste(&Paste, cx));
+    cx.assert_editor_state(indoc! {"
+        d(
+            e,
+            f
+        )ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+
+    // Paste it as is.
+    cx.set_state(indoc! {"
+        ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+    cx.update_editor(|e, cx| e.paste_without_formatting(&PasteWithoutFormatting, cx));
+    cx.assert_editor_state(indoc! {"
+        d(
+                e,
+                f
+            )ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+
+    // Pasted text isn't re-indented when `auto_indent_on_paste` is disabled.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.auto_indent_on_paste = Some(false);
+            });
+        });
+    });
+    cx.set_state(indoc! {"
+        ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+    cx.update_editor(|e, cx| e.paste(&Paste, cx));
+    cx.assert_editor_state(indoc! {"
+        d(
+                e,
+                f
+            )ˇ
+        const a: B = (
+            c(),
+        );
+    "});
+}
+
 #[gpui::test]
 fn test_select_all(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -4883,6 +5504,97 @@ async fn test_select_larger_smaller_syntax_node(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_select_enclosing_text_objects_and_shrink_back(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::language()),
+        )
+        .with_text_object_query(
+            r#"
+            (string_literal) @string.around
+            (block_comment) @comment.around
+            (arguments (_) @argument.around)
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(fiˇrst, "secˇond");
+        }
+    "#});
+
+    cx.update_editor(|e, cx| e.select_enclosing_string(&SelectEnclosingString, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(fiˇrst, «"second"ˇ»);
+        }
+    "#});
+
+    // Selections that are already a whole argument are left as they are.
+    cx.update_editor(|e, cx| e.select_enclosing_argument(&SelectEnclosingArgument, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(«firstˇ», «"second"ˇ»);
+        }
+    "#});
+
+    cx.update_editor(|e, cx| e.select_enclosing_comment(&SelectEnclosingComment, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            «/* a comment */ˇ»
+            call(«firstˇ», «"second"ˇ»);
+        }
+    "#});
+
+    // Shrinking the selections after nudging them returns through the previous states.
+    cx.update_editor(|e, cx| e.select_right(&SelectRight, cx));
+    cx.update_editor(|e, cx| e.select_smaller_syntax_node(&SelectSmallerSyntaxNode, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(«firstˇ», «"second"ˇ»);
+        }
+    "#});
+    cx.update_editor(|e, cx| e.select_smaller_syntax_node(&SelectSmallerSyntaxNode, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(fiˇrst, «"second"ˇ»);
+        }
+    "#});
+    cx.update_editor(|e, cx| e.select_smaller_syntax_node(&SelectSmallerSyntaxNode, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a ˇcomment */
+            call(fiˇrst, "secˇond");
+        }
+    "#});
+
+    // Moving the selections elsewhere forgets the history.
+    cx.update_editor(|e, cx| {
+        e.select_enclosing_string(&SelectEnclosingString, cx);
+        e.move_to_end(&MoveToEnd, cx);
+        e.select_smaller_syntax_node(&SelectSmallerSyntaxNode, cx);
+    });
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            /* a comment */
+            call(first, "second");
+        }
+        ˇ"#});
+}
+
 #[gpui::test]
 async fn test_autoindent_selections(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -5569,6 +6281,68 @@ async fn test_autoclose_with_overrides(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_auto_pairs_and_autoclose_in_strings_and_comments(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.autoclose_in_strings_and_comments = Some(false);
+        settings.defaults.auto_pairs = Some(vec![AutoPair {
+            start: "*".into(),
+            end: "*".into(),
+            close: false,
+            surround: true,
+        }]);
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let rust_language = Arc::new(
+        Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                brackets: serde_json::from_value(json!([
+                    { "start": "(", "end": ")", "close": true, "newline": false },
+                    { "start": "\"", "end": "\"", "close": true, "newline": false, "not_in": ["string"] },
+                ]))
+                .unwrap(),
+                autoclose_before: ";".into(),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::language()),
+        )
+        .with_override_query("(string_literal) @string (line_comment) @comment")
+        .unwrap(),
+    );
+
+    cx.language_registry().add(rust_language.clone());
+    cx.update_buffer(|buffer, cx| {
+        buffer.set_language(Some(rust_language), cx);
+    });
+
+    // Brackets are still auto-closed outside of strings and comments.
+    cx.set_state("let x = ˇ;");
+    cx.update_editor(|editor, cx| editor.handle_input("(", cx));
+    cx.assert_editor_state("let x = (ˇ);");
+
+    // Brackets aren't auto-closed inside strings and comments.
+    cx.set_state(indoc! {r#"
+        let x = "aˇ";
+        // aˇ b"#});
+    cx.update_editor(|editor, cx| editor.handle_input("(", cx));
+    cx.assert_editor_state(indoc! {r#"
+        let x = "a(ˇ";
+        // a(ˇ b"#});
+
+    // Pairs that are disabled in the current scope still surround selected text.
+    cx.set_state(r#"let x = "«aˇ»";"#);
+    cx.update_editor(|editor, cx| editor.handle_input("\"", cx));
+    cx.assert_editor_state(r#"let x = ""«aˇ»"";"#);
+
+    // Pairs from the settings surround selected text, and are only closed if configured to.
+    cx.set_state("// «wordˇ» ˇ");
+    cx.update_editor(|editor, cx| editor.handle_input("*", cx));
+    cx.assert_editor_state("// *«wordˇ»* *ˇ");
+}
+
 #[gpui::test]
 async fn test_surround_with_pair(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -5985,24 +6759,254 @@ async fn test_auto_replace_emoji_shortcode(cx: &mut gpui::TestAppContext) {
         editor.handle_input(" Test:wave", cx);
         assert_eq!(editor.text(cx), "Hello 👋 😄👋:1: Test:wave".unindent());
 
-        editor.handle_input(":", cx);
-        assert_eq!(editor.text(cx), "Hello 👋 😄👋:1: Test:wave:".unindent());
+        editor.handle_input(":", cx);
+        assert_eq!(editor.text(cx), "Hello 👋 😄👋:1: Test:wave:".unindent());
+
+        editor.set_auto_replace_emoji_shortcode(false);
+
+        // Ensure shortcode does not get replaced when auto replace is off
+        editor.handle_input(" :wave", cx);
+        assert_eq!(
+            editor.text(cx),
+            "Hello 👋 😄👋:1: Test:wave: :wave".unindent()
+        );
+
+        editor.handle_input(":", cx);
+        assert_eq!(
+            editor.text(cx),
+            "Hello 👋 😄👋:1: Test:wave: :wave:".unindent()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_unicode_input(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.unicode_input = Some(crate::editor_settings::UnicodeInputContent {
+                    enabled: Some(true),
+                    abbreviations: Some(
+                        [("check".to_string(), "✓".to_string())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                });
+            });
+        });
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("x = ˇ");
+
+    cx.update_editor(|editor, cx| editor.handle_input("\\al", cx));
+    assert!(cx.editor(|editor, _| editor.context_menu_visible()));
+    cx.update_editor(|editor, cx| editor.handle_input("pha", cx));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.assert_editor_state("x = αˇ");
+
+    // The shortest abbreviation is offered first.
+    cx.update_editor(|editor, cx| editor.handle_input(" \\in", cx));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.assert_editor_state("x = α ∈ˇ");
+
+    // Configured abbreviations and emoji shortcodes are offered too.
+    cx.update_editor(|editor, cx| editor.handle_input(" \\check", cx));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.update_editor(|editor, cx| editor.handle_input(" \\:rocket:", cx));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.assert_editor_state("x = α ∈ ✓ 🚀ˇ");
+
+    // Typing past an abbreviation that doesn't exist hides the menu.
+    cx.update_editor(|editor, cx| editor.handle_input(" \\alphaz", cx));
+    assert!(!cx.editor(|editor, _| editor.context_menu_visible()));
+    cx.assert_editor_state("x = α ∈ ✓ 🚀 \\alphazˇ");
+}
+
+#[gpui::test]
+async fn test_documentation_comment_skeleton(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let rust_language = Arc::new(
+        Language::new(
+            LanguageConfig {
+                documentation_comment: Some(DocumentationCommentConfig {
+                    trigger: "///".into(),
+                    start: None,
+                    prefix: "/// ".into(),
+                    end: None,
+                    parameters_heading: Some("# Arguments".into()),
+                    parameter: "* `{name}` - ".into(),
+                    returns_heading: Some("# Returns".into()),
+                    returns: "".into(),
+                }),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::language()),
+        )
+        .with_documentation_query(
+            r#"
+                (function_item
+                    parameters: (parameters) @parameters
+                    return_type: (_)? @return_type) @item
 
-        editor.set_auto_replace_emoji_shortcode(false);
+                (parameters
+                    (parameter pattern: (identifier) @parameter))
+            "#,
+        )
+        .unwrap(),
+    );
 
-        // Ensure shortcode does not get replaced when auto replace is off
-        editor.handle_input(" :wave", cx);
-        assert_eq!(
-            editor.text(cx),
-            "Hello 👋 😄👋:1: Test:wave: :wave".unindent()
-        );
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_language), cx));
+    cx.set_state(indoc! {"
+        mod math {
+            ˇ
+            #[inline]
+            pub fn sum(a: u32, mut b: u32) -> u32 {
+                a + b
+            }
+        }
+    "});
+    cx.update_editor(|editor, cx| editor.handle_input("///", cx));
+    assert!(cx.editor(|editor, _| editor.context_menu_visible()));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    cx.update_editor(|editor, cx| {
+        editor.handle_input("Adds two numbers.", cx);
+        for text in ["the first", "the second", "their sum"] {
+            assert!(editor.move_to_next_snippet_tabstop(cx));
+            editor.handle_input(text, cx);
+        }
+    });
+    cx.assert_editor_state(indoc! {"
+        mod math {
+            /// Adds two numbers.
+            ///
+            /// # Arguments
+            ///
+            /// * `a` - the first
+            /// * `b` - the second
+            ///
+            /// # Returns
+            ///
+            /// their sumˇ
+            #[inline]
+            pub fn sum(a: u32, mut b: u32) -> u32 {
+                a + b
+            }
+        }
+    "});
 
-        editor.handle_input(":", cx);
-        assert_eq!(
-            editor.text(cx),
-            "Hello 👋 😄👋:1: Test:wave: :wave:".unindent()
-        );
+    // The lines keep their leading whitespace, and functions that return nothing get no
+    // returns section.
+    let typescript_language = Arc::new(
+        Language::new(
+            LanguageConfig {
+                documentation_comment: Some(DocumentationCommentConfig {
+                    trigger: "/**".into(),
+                    start: Some("/**".into()),
+                    prefix: " * ".into(),
+                    end: Some(" */".into()),
+                    parameters_heading: None,
+                    parameter: "@param {name} ".into(),
+                    returns_heading: None,
+                    returns: "@returns ".into(),
+                }),
+                ..Default::default()
+            },
+            Some(tree_sitter_typescript::language_typescript()),
+        )
+        .with_documentation_query(
+            r#"
+                (method_definition
+                    parameters: (formal_parameters) @parameters
+                    return_type: (type_annotation)? @return_type) @item
+
+                (formal_parameters
+                    [
+                        (required_parameter pattern: (identifier) @parameter)
+                        (optional_parameter pattern: (identifier) @parameter)
+                    ])
+            "#,
+        )
+        .unwrap(),
+    );
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(typescript_language), cx));
+    cx.set_state(indoc! {"
+        class Greeter {
+          ˇ
+          greet(name: string, times?: number): void {}
+        }
+    "});
+    cx.update_editor(|editor, cx| editor.handle_input("/**", cx));
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    cx.update_editor(|editor, cx| {
+        editor.handle_input("Greets someone.", cx);
+        for text in ["their name", "how many times"] {
+            assert!(editor.move_to_next_snippet_tabstop(cx));
+            editor.handle_input(text, cx);
+        }
     });
+    cx.assert_editor_state(indoc! {"
+        class Greeter {
+          /**
+           * Greets someone.
+           *
+           * @param name their name
+           * @param times how many timesˇ
+           */
+          greet(name: string, times?: number): void {}
+        }
+    "});
+
+    // Typing the trigger away from a function offers nothing.
+    cx.set_state(indoc! {"
+        class Greeter {
+          greet(name: string, times?: number): void {}
+          ˇ
+        }
+    "});
+    cx.update_editor(|editor, cx| editor.handle_input("/**", cx));
+    assert!(!cx.editor(|editor, _| editor.context_menu_visible()));
 }
 
 #[gpui::test]
@@ -6874,6 +7878,208 @@ async fn test_strip_whitespace_and_format_via_lsp(cx: &mut gpui::TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_code_lens(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            code_lens_provider: Some(lsp::CodeLensOptions {
+                resolve_provider: Some(false),
+            }),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    let lens = |row, title: &str| lsp::CodeLens {
+        range: lsp::Range::new(lsp::Position::new(row, 4), lsp::Position::new(row, 5)),
+        command: Some(lsp::Command {
+            title: title.to_string(),
+            command: "run".to_string(),
+            arguments: None,
+        }),
+        data: None,
+    };
+    let mut requests = cx.handle_request::<lsp::request::CodeLensRequest, _, _>(move |_, _, _| {
+        async move {
+            Ok(Some(vec![
+                lens(0, "1 reference"),
+                lens(2, "Run test"),
+                lens(2, "Debug test"),
+                // Lenses without a command aren't shown when they can't be resolved.
+                lsp::CodeLens {
+                    range: lsp::Range::new(lsp::Position::new(2, 4), lsp::Position::new(2, 5)),
+                    command: None,
+                    data: None,
+                },
+            ]))
+        }
+    });
+
+    // Each line's lenses are shown in a block above it.
+    cx.set_state(indoc! {"
+        fn a() {}
+
+            fn bˇ() {}
+    "});
+    cx.executor()
+        .advance_clock(crate::code_lens::CODE_LENS_DEBOUNCE_TIMEOUT);
+    requests.next().await;
+    cx.run_until_parked();
+    assert_eq!(cx.display_text(), "\nfn a() {}\n\n\n    fn b() {}\n");
+    cx.update_editor(|editor, cx| {
+        let snapshot = editor.snapshot(cx);
+        let blocks = snapshot
+            .blocks_in_range(DisplayRow(0)..DisplayRow(4))
+            .map(|(row, _)| row)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, [DisplayRow(0), DisplayRow(3)]);
+    });
+
+    // Lenses are removed when they're disabled.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<AllLanguageSettings>(cx, |settings| {
+                settings.defaults.code_lens = Some(false);
+            });
+        });
+    });
+    cx.run_until_parked();
+    assert_eq!(cx.display_text(), "fn a() {}\n\n    fn b() {}\n");
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            semantic_tokens_provider: Some(
+                lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                    lsp::SemanticTokensOptions {
+                        legend: lsp::SemanticTokensLegend {
+                            token_types: vec![
+                                lsp::SemanticTokenType::FUNCTION,
+                                lsp::SemanticTokenType::PARAMETER,
+                                lsp::SemanticTokenType::KEYWORD,
+                            ],
+                            token_modifiers: vec![lsp::SemanticTokenModifier::DECLARATION],
+                        },
+                        full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                        ..Default::default()
+                    },
+                ),
+            ),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    let token =
+        |delta_line, delta_start, length, token_type, token_modifiers_bitset| lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset,
+        };
+    let mut requests = cx.handle_request::<lsp::request::SemanticTokensFullRequest, _, _>(
+        move |_, _, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: None,
+                    data: vec![
+                        token(0, 0, 2, 2, 0),
+                        token(0, 3, 1, 0, 1),
+                        token(0, 2, 1, 1, 1),
+                        token(1, 4, 1, 1, 0),
+                    ],
+                },
+            )))
+        },
+    );
+
+    cx.set_state(indoc! {"
+        fn a(b: u8) {
+            bˇ
+        }
+    "});
+    cx.executor()
+        .advance_clock(crate::semantic_tokens::SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT);
+    requests.next().await;
+    cx.run_until_parked();
+
+    // Identifiers are highlighted with the theme's style for their type, while the
+    // other tokens keep their syntax highlighting.
+    cx.update_editor(|editor, cx| {
+        let snapshot = editor.snapshot(cx);
+        let syntax = cx.theme().syntax().clone();
+        let highlights = snapshot
+            .chunks(DisplayRow(0)..DisplayRow(2), true, Default::default())
+            .filter_map(|chunk| {
+                let name = chunk.syntax_highlight_id?.name(&syntax)?;
+                Some((chunk.text.trim().to_string(), name.to_string()))
+            })
+            .filter(|(text, _)| !text.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            highlights,
+            [
+                ("a".to_string(), "function".to_string()),
+                ("b".to_string(), "variable".to_string()),
+                ("b".to_string(), "variable".to_string()),
+            ]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_linked_edits_of_tags_without_language_server_support(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Language::new(
+        LanguageConfig {
+            name: "HTML".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["html".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        Some(tree_sitter_html::language()),
+    )
+    .with_linked_edits_query(
+        r#"
+        (element
+          (start_tag (tag_name) @open)
+          (end_tag (tag_name) @close))
+        "#,
+    )
+    .unwrap();
+    let mut cx = EditorLspTestContext::new(language, Default::default(), cx).await;
+
+    // Editing the name of a tag edits the name of its matching tag too.
+    cx.set_state("<dˇiv><p>a</p></div>");
+    cx.run_until_parked();
+    cx.update_editor(|editor, cx| editor.handle_input("x", cx));
+    cx.run_until_parked();
+    cx.assert_editor_state("<dxˇiv><p>a</p></dxiv>");
+
+    // Escape stops editing the tags together.
+    cx.update_editor(|editor, cx| {
+        editor.cancel(&Cancel, cx);
+        editor.handle_input("y", cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state("<dxyˇiv><p>a</p></dxiv>");
+    cx.update_editor(|editor, cx| editor.handle_input("z", cx));
+    cx.run_until_parked();
+    cx.assert_editor_state("<dxyzˇiv><p>a</p></dxiv>");
+}
+
 #[gpui::test]
 async fn test_handle_input_for_show_signature_help_auto_signature_help_true(
     cx: &mut gpui::TestAppContext,
@@ -9052,7 +10258,7 @@ async fn go_to_prev_overlapping_diagnostic(
     executor.run_until_parked();
 
     cx.update_editor(|editor, cx| {
-        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic, cx);
+        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic::default(), cx);
     });
 
     cx.assert_editor_state(indoc! {"
@@ -9061,29 +10267,132 @@ async fn go_to_prev_overlapping_diagnostic(
     "});
 
     cx.update_editor(|editor, cx| {
-        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic, cx);
+        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic::default(), cx);
+    });
+
+    cx.assert_editor_state(indoc! {"
+        fn func(abc ˇdef: i32) -> u32 {
+        }
+    "});
+
+    cx.update_editor(|editor, cx| {
+        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic::default(), cx);
+    });
+
+    cx.assert_editor_state(indoc! {"
+        fn func(abcˇ def: i32) -> u32 {
+        }
+    "});
+
+    cx.update_editor(|editor, cx| {
+        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic::default(), cx);
+    });
+
+    cx.assert_editor_state(indoc! {"
+        fn func(abc def: i32) -> ˇu32 {
+        }
+    "});
+}
+
+#[gpui::test]
+async fn go_to_diagnostic_with_severity(
+    executor: BackgroundExecutor,
+    cx: &mut gpui::TestAppContext,
+) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let project = cx.update_editor(|editor, _| editor.project.clone().unwrap());
+
+    cx.set_state(indoc! {"
+        ˇfn func(abc def: i32) -> u32 {
+        }
+    "});
+
+    _ = cx.update(|cx| {
+        _ = project.update(cx, |project, cx| {
+            project
+                .update_diagnostics(
+                    LanguageServerId(0),
+                    lsp::PublishDiagnosticsParams {
+                        uri: lsp::Url::from_file_path("/root/file").unwrap(),
+                        version: None,
+                        diagnostics: vec![
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 8),
+                                    lsp::Position::new(0, 11),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                ..Default::default()
+                            },
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 12),
+                                    lsp::Position::new(0, 15),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                ..Default::default()
+                            },
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 25),
+                                    lsp::Position::new(0, 28),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                    &[],
+                    cx,
+                )
+                .unwrap()
+        });
     });
 
+    executor.run_until_parked();
+
+    let only_errors = GoToDiagnostic {
+        severity: DiagnosticSeverityFilter::Error,
+        project_wide: false,
+    };
+    cx.update_editor(|editor, cx| editor.go_to_diagnostic(&only_errors, cx));
+    cx.assert_editor_state(indoc! {"
+        fn func(abc ˇdef: i32) -> u32 {
+        }
+    "});
+
+    // The only error is already active, so the cursor stays on it.
+    cx.update_editor(|editor, cx| editor.go_to_diagnostic(&only_errors, cx));
     cx.assert_editor_state(indoc! {"
         fn func(abc ˇdef: i32) -> u32 {
         }
     "});
 
-    cx.update_editor(|editor, cx| {
-        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic, cx);
-    });
-
+    cx.update_editor(|editor, cx| editor.go_to_diagnostic(&GoToDiagnostic::default(), cx));
+    cx.assert_editor_state(indoc! {"
+        fn func(abc def: i32) -> ˇu32 {
+        }
+    "});
+
+    cx.update_editor(|editor, cx| editor.go_to_diagnostic(&GoToDiagnostic::default(), cx));
     cx.assert_editor_state(indoc! {"
-        fn func(abcˇ def: i32) -> u32 {
+        fn func(ˇabc def: i32) -> u32 {
         }
     "});
 
     cx.update_editor(|editor, cx| {
-        editor.go_to_prev_diagnostic(&GoToPrevDiagnostic, cx);
+        editor.go_to_prev_diagnostic(
+            &GoToPrevDiagnostic {
+                severity: DiagnosticSeverityFilter::Error,
+                project_wide: false,
+            },
+            cx,
+        )
     });
-
     cx.assert_editor_state(indoc! {"
-        fn func(abc def: i32) -> ˇu32 {
+        fn func(abc ˇdef: i32) -> u32 {
         }
     "});
 }
@@ -10209,6 +11518,84 @@ struct Row10;"#};
     );
 }
 
+#[gpui::test]
+async fn test_stage_and_unstage_hunks(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let head_text = "one\ntwo\nthree\nfour\nfive\n".to_string();
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/my-repo",
+        json!({
+            ".git": {},
+            "file.txt": "one\nTWO\nthree\nfive\nsix\n",
+        }),
+    )
+    .await;
+    fs.set_head_for_repo(
+        Path::new("/my-repo/.git"),
+        &[(Path::new("file.txt"), head_text.clone())],
+    );
+    fs.set_index_for_repo(
+        Path::new("/my-repo/.git"),
+        &[(Path::new("file.txt"), head_text.clone())],
+    );
+
+    let project = Project::test(fs, ["/my-repo".as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/my-repo/file.txt", cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let (editor, cx) =
+        cx.add_window_view(|cx| Editor::for_buffer(buffer.clone(), Some(project.clone()), cx));
+    let diff_base = |cx: &mut VisualTestContext| {
+        buffer.update(cx, |buffer, _| {
+            buffer.diff_base().map(|diff_base| diff_base.to_string())
+        })
+    };
+
+    // Only the changes on the selected rows are staged.
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(1, 1)..Point::new(1, 1)])
+        });
+        editor.stage_selected_hunks(&StageSelectedHunks, cx);
+    });
+    cx.executor().run_until_parked();
+    assert_eq!(
+        diff_base(cx).as_deref(),
+        Some("one\nTWO\nthree\nfour\nfive\n")
+    );
+
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(3, 0)..Point::new(4, 0)])
+        });
+        editor.stage_selected_hunks(&StageSelectedHunks, cx);
+    });
+    cx.executor().run_until_parked();
+    assert_eq!(
+        diff_base(cx).as_deref(),
+        Some("one\nTWO\nthree\nfive\nsix\n")
+    );
+
+    // Staged changes are unstaged even when they no longer differ from the working copy.
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(1, 0)..Point::new(1, 0)])
+        });
+        editor.unstage_selected_hunks(&UnstageSelectedHunks, cx);
+    });
+    cx.executor().run_until_parked();
+    assert_eq!(
+        diff_base(cx).as_deref(),
+        Some("one\ntwo\nthree\nfive\nsix\n")
+    );
+}
+
 #[gpui::test]
 async fn test_multibuffer_reverts(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -10800,6 +12187,78 @@ async fn test_toggle_hunk_diff(executor: BackgroundExecutor, cx: &mut gpui::Test
     });
 }
 
+#[gpui::test]
+async fn test_revert_and_copy_hovered_hunk(
+    executor: BackgroundExecutor,
+    cx: &mut gpui::TestAppContext,
+) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let diff_base = r#"
+        use some::mod;
+
+        fn main() {
+            println!("hello");
+        }
+        "#
+    .unindent();
+
+    cx.set_state(
+        &r#"
+        use some::modified;
+
+        fn main() {
+            ˇprintln!("hello there");
+        }
+        "#
+        .unindent(),
+    );
+    cx.set_diff_base(Some(&diff_base));
+    executor.run_until_parked();
+
+    let hovered_hunk = |editor: &mut Editor, row: u32, cx: &mut ViewContext<Editor>| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let hunk = snapshot
+            .git_diff_hunks_in_range(MultiBufferRow(row)..MultiBufferRow(row + 1))
+            .next()
+            .unwrap();
+        HoveredHunk {
+            status: hunk_status(&hunk),
+            multi_buffer_range: (Point::new(hunk.associated_range.start.0, 0)
+                ..Point::new(hunk.associated_range.end.0, 0))
+                .to_anchors(&snapshot),
+            diff_base_byte_range: hunk.diff_base_byte_range,
+        }
+    };
+
+    cx.update_editor(|editor, cx| {
+        let hunk = hovered_hunk(editor, 3, cx);
+        editor.copy_hunk_original_text(&hunk, cx);
+    });
+    assert_eq!(
+        cx.read_from_clipboard().map(|item| item.text().to_owned()),
+        Some("    println!(\"hello\");\n".to_owned())
+    );
+
+    cx.update_editor(|editor, cx| {
+        let hunk = hovered_hunk(editor, 0, cx);
+        editor.revert_hovered_hunk(&hunk, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_editor_state(
+        &r#"
+        use some::mod;
+
+        fn main() {
+            ˇprintln!("hello there");
+        }
+        "#
+        .unindent(),
+    );
+}
+
 #[gpui::test]
 async fn test_toggled_diff_base_change(
     executor: BackgroundExecutor,
@@ -13042,6 +14501,61 @@ async fn test_active_indent_guide_non_matching_indent(cx: &mut gpui::TestAppCont
     );
 }
 
+#[gpui::test]
+async fn test_spelling_code_actions(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.spell_check = Some(SpellCheckSetting::All);
+    });
+    cx.update(|cx| {
+        spell_check::init(FakeFs::new(cx.background_executor().clone()), cx);
+        let dictionary = spell_check::Dictionary::new(
+            b"TRY abcdefghijklmnopqrstuvwxyz\n",
+            b"3\nhello\nworld\nthe\n",
+        )
+        .unwrap();
+        SpellChecker::global(cx)
+            .unwrap()
+            .update(cx, |spell_checker, cx| {
+                spell_checker.insert_dictionary("en_US", dictionary, cx)
+            });
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("hello wrˇold the");
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, cx| {
+        editor.toggle_code_actions(
+            &ToggleCodeActions {
+                deployed_from_indicator: None,
+            },
+            cx,
+        )
+    });
+    cx.run_until_parked();
+    cx.update_editor(|editor, _| {
+        let Some(ContextMenu::CodeActions(menu)) = editor.context_menu.read().as_ref() else {
+            panic!("expected the code actions menu to be shown");
+        };
+        assert_eq!(
+            menu.actions
+                .iter()
+                .map(|action| action.label())
+                .collect::<Vec<_>>(),
+            ["Change to \"world\"", "Add \"wrold\" to dictionary"]
+        );
+    });
+
+    cx.update_editor(|editor, cx| {
+        editor
+            .confirm_code_action(&ConfirmCodeAction { item_ix: Some(0) }, cx)
+            .unwrap()
+            .detach()
+    });
+    assert_eq!(cx.buffer_text(), "hello world the");
+}
+
 #[gpui::test]
 fn test_crease_insertion_and_rendering(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -13104,6 +14618,234 @@ fn test_crease_insertion_and_rendering(cx: &mut TestAppContext) {
     assert!(!snapshot.is_line_folded(MultiBufferRow(1)));
 }
 
+#[gpui::test]
+async fn test_document_highlights_textual_fallback(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            document_highlight_provider: Some(lsp::OneOf::Left(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    // When the language server doesn't highlight anything, the occurrences of the word under
+    // the cursor are highlighted.
+    let mut requests =
+        cx.handle_request::<lsp::request::DocumentHighlightRequest, _, _>(|_, _, _| async move {
+            Ok(Some(Vec::new()))
+        });
+    cx.set_state(indoc! {r#"
+        fn main() {
+            let vaˇlue = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.executor().advance_clock(Duration::from_millis(100));
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<WordOccurrenceHighlight>(indoc! {r#"
+        fn main() {
+            let «value» = 1;
+            println!("{}", «value» + other_value);
+        }
+    "#});
+
+    // The highlights of the language server are used when there are any.
+    let mut requests =
+        cx.handle_request::<lsp::request::DocumentHighlightRequest, _, _>(|_, _, _| async move {
+            Ok(Some(vec![
+                lsp::DocumentHighlight {
+                    range: lsp::Range::new(lsp::Position::new(1, 8), lsp::Position::new(1, 13)),
+                    kind: Some(lsp::DocumentHighlightKind::WRITE),
+                },
+                lsp::DocumentHighlight {
+                    range: lsp::Range::new(lsp::Position::new(2, 19), lsp::Position::new(2, 24)),
+                    kind: Some(lsp::DocumentHighlightKind::READ),
+                },
+            ]))
+        });
+    cx.set_state(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", valˇue + other_value);
+        }
+    "#});
+    cx.executor().advance_clock(Duration::from_millis(100));
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<WordOccurrenceHighlight>(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.assert_editor_background_highlights::<DocumentHighlightWrite>(indoc! {r#"
+        fn main() {
+            let «value» = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.assert_editor_background_highlights::<DocumentHighlightRead>(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", «value» + other_value);
+        }
+    "#});
+
+    // Nothing is highlighted when the fallback is disabled and the language server doesn't
+    // highlight anything.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.document_highlights =
+                    Some(crate::editor_settings::DocumentHighlightsContent {
+                        debounce_ms: None,
+                        textual_fallback: Some(false),
+                    });
+            });
+        });
+    });
+    let mut requests =
+        cx.handle_request::<lsp::request::DocumentHighlightRequest, _, _>(|_, _, _| async move {
+            Ok(Some(Vec::new()))
+        });
+    cx.set_state(indoc! {r#"
+        fn main() {
+            let vaˇlue = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.executor().advance_clock(Duration::from_millis(100));
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<WordOccurrenceHighlight>(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.assert_editor_background_highlights::<DocumentHighlightRead>(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+    cx.assert_editor_background_highlights::<DocumentHighlightWrite>(indoc! {r#"
+        fn main() {
+            let value = 1;
+            println!("{}", value + other_value);
+        }
+    "#});
+}
+
+#[gpui::test]
+async fn test_no_word_highlights_in_single_line_editors(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let full_editor = cx.add_window(|cx| {
+        let buffer = MultiBuffer::build_simple("value + other_value + value", cx);
+        build_editor(buffer, cx)
+    });
+    let single_line_editor = cx.add_window(|cx| {
+        let mut editor = Editor::single_line(cx);
+        editor.set_text("value + other_value + value", cx);
+        editor
+    });
+    for editor in [full_editor, single_line_editor] {
+        _ = editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |s| s.select_ranges([2..2]));
+        });
+    }
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.run_until_parked();
+
+    _ = full_editor.update(cx, |editor, _| {
+        assert!(editor.has_background_highlights::<WordOccurrenceHighlight>());
+    });
+    _ = single_line_editor.update(cx, |editor, _| {
+        assert!(!editor.has_background_highlights::<WordOccurrenceHighlight>());
+    });
+}
+
+#[gpui::test]
+async fn test_go_to_bookmarks(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        one
+        ˇtwo
+        three
+        ˇfour
+        five
+    "});
+    cx.update_editor(|editor, cx| {
+        editor.toggle_bookmark(&ToggleBookmark::default(), cx);
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(4, 2)..Point::new(4, 2)])
+        });
+    });
+
+    // Going to the next bookmark wraps around to the first one.
+    cx.update_editor(|editor, cx| editor.go_to_next_bookmark(&GoToNextBookmark, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇtwo
+        three
+        four
+        five
+    "});
+    cx.update_editor(|editor, cx| editor.go_to_next_bookmark(&GoToNextBookmark, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        three
+        ˇfour
+        five
+    "});
+
+    // Going to the previous bookmark wraps around to the last one.
+    cx.update_editor(|editor, cx| editor.go_to_prev_bookmark(&GoToPrevBookmark, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇtwo
+        three
+        four
+        five
+    "});
+    cx.update_editor(|editor, cx| editor.go_to_prev_bookmark(&GoToPrevBookmark, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        three
+        ˇfour
+        five
+    "});
+
+    // Toggling a bookmarked row removes its bookmark.
+    cx.update_editor(|editor, cx| {
+        editor.toggle_bookmark(&ToggleBookmark::default(), cx);
+        editor.go_to_next_bookmark(&GoToNextBookmark, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇtwo
+        three
+        four
+        five
+    "});
+    cx.update_editor(|editor, cx| editor.go_to_prev_bookmark(&GoToPrevBookmark, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇtwo
+        three
+        four
+        five
+    "});
+}
+
 fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     let point = DisplayPoint::new(DisplayRow(row as u32), column as u32);
     point..point
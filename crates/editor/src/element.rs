@@ -24,7 +24,8 @@ use crate::{
     DocumentHighlightWrite, Editor, EditorMode, EditorSettings, EditorSnapshot, EditorStyle,
     ExpandExcerpts, FocusedBlock, GutterDimensions, HalfPageDown, HalfPageUp, HoveredCursor,
     HoveredHunk, LineDown, LineUp, OpenExcerpts, PageDown, PageUp, Point, RangeToAnchorExt, RowExt,
-    RowRangeExt, SelectPhase, Selection, SoftWrap, ToPoint, CURSORS_VISIBLE_FOR, MAX_LINE_LEN,
+    RowRangeExt, SelectPhase, Selection, SoftWrap, ToPoint, WordOccurrenceHighlight,
+    CURSORS_VISIBLE_FOR, MAX_LINE_LEN,
 };
 use client::ParticipantIndex;
 use collections::{BTreeMap, HashMap};
@@ -34,16 +35,18 @@ use gpui::{
     anchored, deferred, div, fill, outline, point, px, quad, relative, size, svg,
     transparent_black, Action, AnchorCorner, AnyElement, AvailableSpace, Bounds, ClipboardItem,
     ContentMask, Corners, CursorStyle, DispatchPhase, Edges, Element, ElementInputHandler, Entity,
-    EntityId, FontId, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Length,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
-    StatefulInteractiveElement, Style, Styled, TextRun, TextStyle, TextStyleRefinement, View,
-    ViewContext, WeakView, WindowContext,
+    EntityId, FontId, FontWeight, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement,
+    Length, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    PaintQuad, ParentElement, Pixels, Rgba, ScrollDelta, ScrollWheelEvent, ShapedLine,
+    SharedString, Size, StatefulInteractiveElement, Style, Styled, StyledText, TextRun, TextStyle,
+    TextStyleRefinement, View, ViewContext, WeakView, WindowContext,
 };
 use itertools::Itertools;
 use language::language_settings::{
     IndentGuideBackgroundColoring, IndentGuideColoring, IndentGuideSettings, ShowWhitespaceSetting,
+    WrapGuideStyle,
 };
+use language::OutlineItem;
 use lsp::DiagnosticSeverity;
 use multi_buffer::{Anchor, MultiBufferPoint, MultiBufferRow};
 use project::{
@@ -65,11 +68,14 @@ use std::{
 use sum_tree::Bias;
 use theme::{ActiveTheme, PlayerColor};
 use ui::prelude::*;
-use ui::{h_flex, ButtonLike, ButtonStyle, ContextMenu, Tooltip};
+use ui::{h_flex, ButtonLike, ButtonStyle, ColorPicker, ContextMenu, Tooltip};
 use util::RangeExt;
 use util::ResultExt;
 use workspace::{item::Item, Workspace};
 
+/// The length of the dashes of dashed wrap guides, and of the gaps between them.
+const WRAP_GUIDE_DASH_LENGTH: Pixels = px(4.);
+
 struct SelectionLayout {
     head: DisplayPoint,
     cursor_shape: CursorShape,
@@ -187,6 +193,7 @@ impl EditorElement {
         register_action(view, cx, Editor::outdent);
         register_action(view, cx, Editor::delete_line);
         register_action(view, cx, Editor::join_lines);
+        register_action(view, cx, Editor::jump_to_char);
         register_action(view, cx, Editor::sort_lines_case_sensitive);
         register_action(view, cx, Editor::sort_lines_case_insensitive);
         register_action(view, cx, Editor::reverse_lines);
@@ -208,12 +215,18 @@ impl EditorElement {
         register_action(view, cx, Editor::cut_to_end_of_line);
         register_action(view, cx, Editor::duplicate_line_up);
         register_action(view, cx, Editor::duplicate_line_down);
+        register_action(view, cx, Editor::duplicate_selection);
+        register_action(view, cx, Editor::duplicate_line_and_comment);
+        register_action(view, cx, Editor::export_as_html);
+        register_action(view, cx, Editor::export_as_rtf);
         register_action(view, cx, Editor::move_line_up);
         register_action(view, cx, Editor::move_line_down);
         register_action(view, cx, Editor::transpose);
+        register_action(view, cx, Editor::align_selections);
         register_action(view, cx, Editor::cut);
         register_action(view, cx, Editor::copy);
         register_action(view, cx, Editor::paste);
+        register_action(view, cx, Editor::paste_without_formatting);
         register_action(view, cx, Editor::undo);
         register_action(view, cx, Editor::redo);
         register_action(view, cx, Editor::move_page_up);
@@ -282,6 +295,9 @@ impl EditorElement {
         register_action(view, cx, Editor::select_larger_syntax_node);
         register_action(view, cx, Editor::select_smaller_syntax_node);
         register_action(view, cx, Editor::select_enclosing_symbol);
+        register_action(view, cx, Editor::select_enclosing_string);
+        register_action(view, cx, Editor::select_enclosing_comment);
+        register_action(view, cx, Editor::select_enclosing_argument);
         register_action(view, cx, Editor::move_to_enclosing_bracket);
         register_action(view, cx, Editor::undo_selection);
         register_action(view, cx, Editor::redo_selection);
@@ -294,6 +310,12 @@ impl EditorElement {
         register_action(view, cx, Editor::go_to_prev_diagnostic);
         register_action(view, cx, Editor::go_to_hunk);
         register_action(view, cx, Editor::go_to_prev_hunk);
+        register_action(view, cx, Editor::toggle_bookmark);
+        register_action(view, cx, Editor::go_to_next_bookmark);
+        register_action(view, cx, Editor::go_to_prev_bookmark);
+        register_action(view, cx, Editor::start_recording_macro);
+        register_action(view, cx, Editor::stop_recording);
+        register_action(view, cx, Editor::replay_macro);
         register_action(view, cx, |editor, a, cx| {
             editor.go_to_definition(a, cx).detach_and_log_err(cx);
         });
@@ -318,7 +340,10 @@ impl EditorElement {
         });
         register_action(view, cx, Editor::open_url);
         register_action(view, cx, Editor::fold);
+        register_action(view, cx, Editor::fold_recursive);
         register_action(view, cx, Editor::fold_at);
+        register_action(view, cx, Editor::fold_at_level);
+        register_action(view, cx, Editor::fold_all_functions);
         register_action(view, cx, Editor::unfold_lines);
         register_action(view, cx, Editor::unfold_at);
         register_action(view, cx, Editor::fold_selected_ranges);
@@ -401,6 +426,8 @@ impl EditorElement {
         register_action(view, cx, Editor::accept_partial_inline_completion);
         register_action(view, cx, Editor::accept_inline_completion);
         register_action(view, cx, Editor::revert_selected_hunks);
+        register_action(view, cx, Editor::stage_selected_hunks);
+        register_action(view, cx, Editor::unstage_selected_hunks);
         register_action(view, cx, Editor::open_active_item_in_terminal)
     }
 
@@ -479,7 +506,13 @@ impl EditorElement {
                     },
                 );
                 if hunk_bounds.contains(&event.position) {
-                    editor.open_hunk_context_menu(hovered_hunk, event.position, cx);
+                    if hovered_hunk.status == DiffHunkStatus::Added {
+                        editor.open_hunk_context_menu(hovered_hunk, event.position, cx);
+                    } else {
+                        // Preview the original text inline, along with buttons to revert
+                        // the hunk or copy the original text.
+                        editor.toggle_hovered_hunk(&hovered_hunk, cx);
+                    }
                 }
             }
             cx.notify();
@@ -511,6 +544,22 @@ impl EditorElement {
 
         let point_for_position =
             position_map.point_for_position(text_hitbox.bounds, event.position);
+        if click_count == 1
+            && !modifiers.modified()
+            && point_for_position.column_overshoot_after_line_end == 0
+        {
+            let offset = position_map
+                .snapshot
+                .display_point_to_inlay_offset(point_for_position.exact_unclipped, Bias::Left);
+            if let Some((range, color, format)) =
+                editor.color_swatch_at(offset, &position_map.snapshot, cx)
+            {
+                editor.show_color_picker(range, color, format, cx);
+                cx.stop_propagation();
+                return;
+            }
+        }
+
         let position = point_for_position.previous_valid;
         if modifiers.shift && modifiers.alt {
             editor.select(
@@ -552,10 +601,17 @@ impl EditorElement {
     fn mouse_right_down(
         editor: &mut Editor,
         event: &MouseDownEvent,
+        hovered_hunk: Option<HoveredHunk>,
         position_map: &PositionMap,
         text_hitbox: &Hitbox,
         cx: &mut ViewContext<Editor>,
     ) {
+        if let Some(hovered_hunk) = hovered_hunk {
+            editor.open_hunk_context_menu(hovered_hunk, event.position, cx);
+            cx.stop_propagation();
+            cx.notify();
+            return;
+        }
         if !text_hitbox.is_hovered(cx) {
             return;
         }
@@ -1116,6 +1172,9 @@ impl EditorElement {
                     // Selected Symbol Occurrences
                     (is_singleton && scrollbar_settings.selected_symbol && (editor.has_background_highlights::<DocumentHighlightRead>() || editor.has_background_highlights::<DocumentHighlightWrite>()))
                     ||
+                    // Word Occurrences
+                    (is_singleton && scrollbar_settings.word_occurrences && editor.has_background_highlights::<WordOccurrenceHighlight>())
+                    ||
                     // Diagnostics
                     (is_singleton && scrollbar_settings.diagnostics && snapshot.buffer_snapshot.has_diagnostics())
                     ||
@@ -1170,6 +1229,146 @@ impl EditorElement {
         })
     }
 
+    /// Lays out a zoomed-out view of the rows around the visible ones, to the left of the
+    /// scrollbar, in which each run of non-whitespace characters is drawn as a block.
+    fn layout_minimap(
+        &self,
+        snapshot: &EditorSnapshot,
+        bounds: Bounds<Pixels>,
+        scroll_position: gpui::Point<f32>,
+        rows_per_page: f32,
+        cx: &mut WindowContext,
+    ) -> Option<MinimapLayout> {
+        if !EditorSettings::get_global(cx).minimap.enabled || snapshot.mode != EditorMode::Full {
+            return None;
+        }
+
+        let scrollbar_left = self.scrollbar_left(&bounds);
+        let minimap_bounds = Bounds::from_corners(
+            point(scrollbar_left - MinimapLayout::WIDTH, bounds.origin.y),
+            point(scrollbar_left, bounds.lower_left().y),
+        );
+
+        // Scroll the minimap proportionally to the editor when it can't show all rows.
+        let total_rows = snapshot.max_point().row().next_row().as_f32();
+        let minimap_rows = bounds.size.height / MinimapLayout::ROW_HEIGHT;
+        let max_scroll_top = (total_rows - rows_per_page).max(0.);
+        let first_row = if max_scroll_top > 0. {
+            ((scroll_position.y / max_scroll_top).min(1.) * (total_rows - minimap_rows).max(0.))
+                .floor()
+        } else {
+            0.
+        };
+        let start_row = DisplayRow(first_row as u32);
+        let end_row = DisplayRow(((first_row + minimap_rows).ceil() as u32).min(total_rows as u32));
+
+        let mut text_quads = Vec::new();
+        let mut row = 0;
+        let mut column = 0;
+        for chunk in snapshot.highlighted_chunks(start_row..end_row, true, &self.style) {
+            let mut color = chunk
+                .style
+                .and_then(|style| style.color)
+                .unwrap_or(self.style.text.color);
+            color.fade_out(0.4);
+
+            for (ix, line) in chunk.text.split('\n').enumerate() {
+                if ix > 0 {
+                    row += 1;
+                    column = 0;
+                }
+
+                let mut run_start = None;
+                for character in line.chars().chain([' ']) {
+                    match (character.is_whitespace(), run_start) {
+                        (false, None) => run_start = Some(column),
+                        (true, Some(start)) => {
+                            run_start = None;
+                            let end = cmp::min(column, MinimapLayout::MAX_COLUMNS);
+                            if start < end {
+                                text_quads.push(fill(
+                                    Bounds {
+                                        origin: minimap_bounds.origin
+                                            + point(
+                                                MinimapLayout::COLUMN_WIDTH * start as f32,
+                                                MinimapLayout::ROW_HEIGHT * row as f32,
+                                            ),
+                                        size: size(
+                                            MinimapLayout::COLUMN_WIDTH * (end - start) as f32,
+                                            MinimapLayout::ROW_HEIGHT * 0.75,
+                                        ),
+                                    },
+                                    color,
+                                ));
+                            }
+                        }
+                        _ => {}
+                    }
+                    column += 1;
+                }
+                // The chained space isn't part of the line.
+                column -= 1;
+            }
+        }
+
+        let start_point = DisplayPoint::new(start_row, 0).to_point(snapshot);
+        let end_point = DisplayPoint::new(end_row, 0).to_point(snapshot);
+        let mut highlights = Vec::new();
+
+        let editor = self.editor.read(cx);
+        let theme = cx.theme();
+        if let Some((_, search_ranges)) = editor
+            .background_highlights
+            .get(&TypeId::of::<BufferSearchHighlights>())
+        {
+            highlights.extend(search_ranges.iter().filter_map(|range| {
+                let start = range.start.to_display_point(snapshot).row();
+                let end = range.end.to_display_point(snapshot).row();
+                (start < end_row && end >= start_row).then_some(ColoredRange {
+                    start,
+                    end,
+                    color: theme.status().info,
+                })
+            }));
+        }
+
+        let start_anchor = snapshot.buffer_snapshot.anchor_before(start_point);
+        let end_anchor = snapshot.buffer_snapshot.anchor_after(end_point);
+        highlights.extend(
+            editor
+                .selections
+                .disjoint_in_range::<Point>(start_anchor..end_anchor, cx)
+                .into_iter()
+                .map(|selection| ColoredRange {
+                    start: selection.start.to_display_point(snapshot).row(),
+                    end: selection.end.to_display_point(snapshot).row(),
+                    color: self.style.local_player.selection,
+                }),
+        );
+
+        // Paint the most severe diagnostics last.
+        highlights.extend(
+            snapshot
+                .buffer_snapshot
+                .diagnostics_in_range::<_, Point>(start_point..end_point, false)
+                .filter(|entry| entry.diagnostic.is_primary)
+                .sorted_by_key(|entry| cmp::Reverse(entry.diagnostic.severity))
+                .map(|entry| ColoredRange {
+                    start: entry.range.start.to_display_point(snapshot).row(),
+                    end: entry.range.end.to_display_point(snapshot).row(),
+                    color: crate::diagnostic_style(entry.diagnostic.severity, &self.style.status),
+                }),
+        );
+
+        Some(MinimapLayout {
+            hitbox: cx.insert_hitbox(minimap_bounds, false),
+            first_row,
+            visible_row_range: scroll_position.y..scroll_position.y + rows_per_page,
+            text_quads,
+            highlights,
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn prepaint_gutter_fold_toggles(
         &self,
@@ -1370,6 +1569,234 @@ impl EditorElement {
         Some(element)
     }
 
+    /// Lays out the headers of the outline items enclosing the top of the editor, pinned
+    /// one per line at the top of the text area.
+    fn layout_sticky_headers(
+        &self,
+        snapshot: &EditorSnapshot,
+        start_row: DisplayRow,
+        content_origin: gpui::Point<Pixels>,
+        text_hitbox: &Hitbox,
+        line_height: Pixels,
+        cx: &mut WindowContext,
+    ) -> Vec<AnyElement> {
+        if !EditorSettings::get_global(cx).sticky_scroll.enabled
+            || snapshot.mode != EditorMode::Full
+        {
+            return Vec::new();
+        }
+
+        let headers = self.sticky_header_items(snapshot, start_row);
+        let header_count = headers.len();
+        headers
+            .into_iter()
+            .enumerate()
+            .map(|(ix, item)| {
+                let position = item.range.start;
+                let mut header = h_flex()
+                    .id(("sticky-header", ix))
+                    .w(text_hitbox.size.width)
+                    .h(line_height)
+                    .pl(content_origin.x - text_hitbox.origin.x)
+                    .bg(self.style.background)
+                    .when(ix + 1 == header_count, |header| {
+                        header
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                    })
+                    .cursor_pointer()
+                    .child(
+                        StyledText::new(item.text)
+                            .with_highlights(&self.style.text, item.highlight_ranges),
+                    )
+                    .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+                    .on_click(cx.listener_for(&self.editor, move |editor, _, cx| {
+                        editor.change_selections(
+                            Some(crate::scroll::Autoscroll::top_relative(ix)),
+                            cx,
+                            |selections| selections.select_ranges([position..position]),
+                        );
+                    }))
+                    .into_any();
+                header.prepaint_as_root(
+                    point(
+                        text_hitbox.origin.x,
+                        content_origin.y + line_height * ix as f32,
+                    ),
+                    AvailableSpace::min_size(),
+                    cx,
+                );
+                header
+            })
+            .collect()
+    }
+
+    /// Returns the outline items whose headers are pinned when the editor is scrolled to
+    /// `start_row`, from the outermost one.
+    fn sticky_header_items(
+        &self,
+        snapshot: &EditorSnapshot,
+        start_row: DisplayRow,
+    ) -> Vec<OutlineItem<Anchor>> {
+        let display_snapshot = &snapshot.display_snapshot;
+        let top_point = DisplayPoint::new(start_row, 0).to_point(display_snapshot);
+        let Some((_, items)) = display_snapshot
+            .buffer_snapshot
+            .symbols_containing(top_point, Some(&self.style.syntax))
+        else {
+            return Vec::new();
+        };
+
+        let mut headers = Vec::new();
+        for item in items {
+            let pinned_row = DisplayRow(start_row.0 + headers.len() as u32);
+            // Stop at the first item whose header is still visible in place, or whose
+            // scope ends under the pinned headers.
+            let item_start_row = item.range.start.to_display_point(display_snapshot).row();
+            let item_end_row = item.range.end.to_display_point(display_snapshot).row();
+            if item_start_row >= pinned_row || item_end_row <= pinned_row {
+                break;
+            }
+            headers.push(item);
+        }
+        headers
+    }
+
+    /// Lays out the message of the most severe diagnostic starting on each visible row,
+    /// to be shown after the end of the row.
+    #[allow(clippy::too_many_arguments)]
+    fn layout_inline_diagnostics(
+        &self,
+        snapshot: &EditorSnapshot,
+        rows: Range<DisplayRow>,
+        buffer_range: Range<Anchor>,
+        line_layouts: &[LineWithInvisibles],
+        crease_trailers: &[Option<CreaseTrailerLayout>],
+        em_width: Pixels,
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        line_height: Pixels,
+        cx: &mut WindowContext,
+    ) -> BTreeMap<DisplayRow, AnyElement> {
+        let settings = ProjectSettings::get_global(cx).diagnostics.inline;
+        if !settings.enabled || snapshot.mode != EditorMode::Full {
+            return BTreeMap::default();
+        }
+
+        let display_snapshot = &snapshot.display_snapshot;
+        let mut diagnostics = BTreeMap::<DisplayRow, language::Diagnostic>::default();
+        for entry in display_snapshot
+            .buffer_snapshot
+            .diagnostics_in_range::<_, Point>(buffer_range, false)
+        {
+            if !entry.diagnostic.is_primary
+                || !settings.min_severity.includes(entry.diagnostic.severity)
+            {
+                continue;
+            }
+
+            let display_row = entry.range.start.to_display_point(display_snapshot).row();
+            if !rows.contains(&display_row) {
+                continue;
+            }
+            let is_most_severe = diagnostics.get(&display_row).map_or(true, |diagnostic| {
+                entry.diagnostic.severity < diagnostic.severity
+            });
+            if is_most_severe {
+                diagnostics.insert(display_row, entry.diagnostic);
+            }
+        }
+
+        diagnostics
+            .into_iter()
+            .map(|(display_row, diagnostic)| {
+                const INLINE_DIAGNOSTIC_PADDING_EM_WIDTHS: f32 = 4.;
+
+                let line_ix = display_row.minus(rows.start) as usize;
+                let line_end = if let Some(crease_trailer) = crease_trailers[line_ix].as_ref() {
+                    crease_trailer.bounds.right()
+                } else {
+                    content_origin.x - scroll_pixel_position.x + line_layouts[line_ix].width
+                };
+                let start_x = line_end + em_width * INLINE_DIAGNOSTIC_PADDING_EM_WIDTHS;
+                let start_y = content_origin.y
+                    + line_height * (display_row.as_f32() - scroll_pixel_position.y / line_height);
+
+                let message = diagnostic.message.lines().next().unwrap_or_default();
+                let mut element = div()
+                    .font_family(self.style.text.font().family)
+                    .line_height(self.style.text.line_height)
+                    .text_color(crate::diagnostic_style(
+                        diagnostic.severity,
+                        &self.style.status,
+                    ))
+                    .child(message.to_string())
+                    .into_any();
+                element.prepaint_as_root(point(start_x, start_y), AvailableSpace::min_size(), cx);
+
+                (display_row, element)
+            })
+            .collect()
+    }
+
+    /// Lays out the labels of the targets of an in-progress jump to a character, over
+    /// the first character of each target.
+    fn layout_jump_labels(
+        &self,
+        snapshot: &EditorSnapshot,
+        rows: Range<DisplayRow>,
+        line_layouts: &[LineWithInvisibles],
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        line_height: Pixels,
+        cx: &mut WindowContext,
+    ) -> Vec<AnyElement> {
+        let editor = self.editor.read(cx);
+        let Some(state) = editor.jump_to_char_state.as_ref() else {
+            return Vec::new();
+        };
+
+        let labels = state
+            .targets
+            .iter()
+            .filter(|target| {
+                !target.label.is_empty() && target.label.starts_with(&state.typed_label)
+            })
+            .filter_map(|target| {
+                let position = target.position.to_display_point(&snapshot.display_snapshot);
+                rows.contains(&position.row()).then(|| {
+                    let label = target.label[state.typed_label.len()..].to_string();
+                    (position, label)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let colors = cx.theme().colors();
+        let (background, text_color) = (colors.text_accent, colors.editor_background);
+        labels
+            .into_iter()
+            .map(|(position, label)| {
+                let line_layout = &line_layouts[position.row().minus(rows.start) as usize];
+                let x = content_origin.x - scroll_pixel_position.x
+                    + line_layout.x_for_index(position.column() as usize);
+                let y = content_origin.y
+                    + line_height
+                        * (position.row().as_f32() - scroll_pixel_position.y / line_height);
+
+                let mut element = div()
+                    .font_family(self.style.text.font().family)
+                    .line_height(self.style.text.line_height)
+                    .font_weight(FontWeight::BOLD)
+                    .bg(background)
+                    .text_color(text_color)
+                    .child(label)
+                    .into_any();
+                element.prepaint_as_root(point(x, y), AvailableSpace::min_size(), cx);
+                element
+            })
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_blame_entries(
         &self,
@@ -1614,6 +2041,42 @@ impl EditorElement {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn layout_bookmark_indicators(
+        &self,
+        line_height: Pixels,
+        rows: Range<DisplayRow>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        rows_with_hunk_bounds: &HashMap<DisplayRow, Bounds<Pixels>>,
+        snapshot: &EditorSnapshot,
+        cx: &mut WindowContext,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            editor
+                .bookmarks(cx)
+                .into_iter()
+                .map(|bookmark| bookmark.to_display_point(snapshot).row())
+                .filter(|row| rows.contains(row))
+                .dedup()
+                .map(|display_row| {
+                    let button = editor.render_bookmark_indicator(display_row, cx);
+                    prepaint_gutter_button(
+                        button,
+                        display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_pixel_position,
+                        gutter_hitbox,
+                        rows_with_hunk_bounds,
+                        cx,
+                    )
+                })
+                .collect_vec()
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_code_actions_indicator(
         &self,
@@ -2688,6 +3151,63 @@ impl EditorElement {
         Some(element)
     }
 
+    /// Lays out the color picker opened by clicking a color swatch, below the color literal.
+    #[allow(clippy::too_many_arguments)]
+    fn layout_color_picker(
+        &self,
+        snapshot: &EditorSnapshot,
+        visible_display_row_range: Range<DisplayRow>,
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        line_layouts: &[LineWithInvisibles],
+        line_height: Pixels,
+        cx: &mut WindowContext,
+    ) -> Option<AnyElement> {
+        let (literal_start, color) = {
+            let picker = self.editor.read(cx).color_swatches.picker.as_ref()?;
+            (picker.range.start.to_display_point(snapshot), picker.color)
+        };
+        if !visible_display_row_range.contains(&literal_start.row()) {
+            return None;
+        }
+
+        let line_layout =
+            &line_layouts[literal_start.row().minus(visible_display_row_range.start) as usize];
+        let x = line_layout.x_for_index(literal_start.column() as usize) - scroll_pixel_position.x;
+        let y = (literal_start.row().as_f32() + 1.) * line_height - scroll_pixel_position.y;
+        let position = content_origin + point(x, y);
+
+        let editor = self.editor.clone();
+        let mut element = deferred(
+            anchored()
+                .position(position)
+                .anchor(AnchorCorner::TopLeft)
+                .snap_to_window()
+                .child(
+                    div()
+                        .id("color-picker-popover")
+                        .occlude()
+                        .elevation_2(cx)
+                        .on_mouse_down_out({
+                            let editor = editor.clone();
+                            move |_, cx| {
+                                editor.update(cx, |editor, cx| {
+                                    editor.hide_color_picker(cx);
+                                });
+                            }
+                        })
+                        .child(ColorPicker::new("color-picker", color, move |color, cx| {
+                            editor.update(cx, |editor, cx| editor.pick_color(color, cx));
+                        })),
+                ),
+        )
+        .with_priority(1)
+        .into_any();
+
+        element.prepaint_as_root(position, AvailableSpace::min_size(), cx);
+        Some(element)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_hover_popovers(
         &self,
@@ -2978,9 +3498,9 @@ impl EditorElement {
                 let scroll_left =
                     layout.position_map.snapshot.scroll_position().x * layout.position_map.em_width;
 
-                for (wrap_position, active) in layout.wrap_guides.iter() {
+                for wrap_guide in layout.wrap_guides.iter() {
                     let x = (layout.text_hitbox.origin.x
-                        + *wrap_position
+                        + wrap_guide.x
                         + layout.position_map.em_width / 2.)
                         - scroll_left;
 
@@ -2994,18 +3514,40 @@ impl EditorElement {
                         continue;
                     }
 
-                    let color = if *active {
-                        cx.theme().colors().editor_active_wrap_guide
-                    } else {
-                        cx.theme().colors().editor_wrap_guide
-                    };
-                    cx.paint_quad(fill(
-                        Bounds {
-                            origin: point(x, layout.text_hitbox.origin.y),
-                            size: size(px(1.), layout.text_hitbox.size.height),
-                        },
-                        color,
-                    ));
+                    let color = wrap_guide.color.unwrap_or_else(|| {
+                        if wrap_guide.active {
+                            cx.theme().colors().editor_active_wrap_guide
+                        } else {
+                            cx.theme().colors().editor_wrap_guide
+                        }
+                    });
+                    let top = layout.text_hitbox.origin.y;
+                    let bottom = top + layout.text_hitbox.size.height;
+                    match wrap_guide.style {
+                        WrapGuideStyle::Solid => {
+                            cx.paint_quad(fill(
+                                Bounds {
+                                    origin: point(x, top),
+                                    size: size(px(1.), bottom - top),
+                                },
+                                color,
+                            ));
+                        }
+                        WrapGuideStyle::Dashed => {
+                            let mut dash_top = top;
+                            while dash_top < bottom {
+                                let dash_bottom = (dash_top + WRAP_GUIDE_DASH_LENGTH).min(bottom);
+                                cx.paint_quad(fill(
+                                    Bounds {
+                                        origin: point(x, dash_top),
+                                        size: size(px(1.), dash_bottom - dash_top),
+                                    },
+                                    color,
+                                ));
+                                dash_top = dash_bottom + WRAP_GUIDE_DASH_LENGTH;
+                            }
+                        }
+                    }
                 }
             }
         })
@@ -3254,6 +3796,9 @@ impl EditorElement {
                 }
             });
 
+            for bookmark_indicator in layout.bookmark_indicators.iter_mut() {
+                bookmark_indicator.paint(cx);
+            }
             for test_indicator in layout.test_indicators.iter_mut() {
                 test_indicator.paint(cx);
             }
@@ -3353,7 +3898,9 @@ impl EditorElement {
                 self.paint_lines(&invisible_display_ranges, layout, cx);
                 self.paint_redactions(layout, cx);
                 self.paint_cursors(layout, cx);
+                self.paint_inline_diagnostics(layout, cx);
                 self.paint_inline_blame(layout, cx);
+                self.paint_jump_labels(layout, cx);
                 cx.with_element_namespace("crease_trailers", |cx| {
                     for trailer in layout.crease_trailers.iter_mut().flatten() {
                         trailer.element.paint(cx);
@@ -3599,6 +4146,136 @@ impl EditorElement {
         }
     }
 
+    fn paint_minimap(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        let Some(minimap_layout) = layout.minimap_layout.as_ref() else {
+            return;
+        };
+
+        let bounds = minimap_layout.hitbox.bounds;
+        let visible_bounds = Bounds::from_corners(
+            point(
+                bounds.left(),
+                minimap_layout.y_for_row(minimap_layout.visible_row_range.start),
+            ),
+            point(
+                bounds.right(),
+                minimap_layout.y_for_row(minimap_layout.visible_row_range.end),
+            ),
+        )
+        .intersect(&bounds);
+        cx.paint_layer(bounds, |cx| {
+            cx.paint_quad(quad(
+                bounds,
+                Corners::default(),
+                self.style.background,
+                Edges {
+                    top: Pixels::ZERO,
+                    right: Pixels::ZERO,
+                    bottom: Pixels::ZERO,
+                    left: ScrollbarLayout::BORDER_WIDTH,
+                },
+                cx.theme().colors().scrollbar_track_border,
+            ));
+
+            for highlight in &minimap_layout.highlights {
+                let mut color = highlight.color;
+                color.fade_out(0.6);
+                cx.paint_quad(fill(
+                    Bounds::from_corners(
+                        point(
+                            bounds.left(),
+                            minimap_layout.y_for_row(highlight.start.as_f32()),
+                        ),
+                        point(
+                            bounds.right(),
+                            minimap_layout.y_for_row(highlight.end.as_f32() + 1.),
+                        ),
+                    )
+                    .intersect(&bounds),
+                    color,
+                ));
+            }
+
+            for text_quad in &minimap_layout.text_quads {
+                cx.paint_quad(text_quad.clone());
+            }
+
+            cx.paint_quad(fill(
+                visible_bounds,
+                cx.theme().colors().scrollbar_thumb_background,
+            ));
+        });
+
+        cx.set_cursor_style(CursorStyle::Arrow, &minimap_layout.hitbox);
+
+        // Clicking or dragging in the minimap centers the editor on the row under the mouse.
+        let scroll_to_y = {
+            let first_row = minimap_layout.first_row;
+            let top = bounds.top();
+            let rows_per_page =
+                minimap_layout.visible_row_range.end - minimap_layout.visible_row_range.start;
+            move |editor: &mut Editor, y: Pixels, cx: &mut ViewContext<Editor>| {
+                let row = first_row + (y - top) / MinimapLayout::ROW_HEIGHT;
+                let mut position = editor.scroll_position(cx);
+                position.y = (row - rows_per_page / 2.).max(0.);
+                editor.set_scroll_position(position, cx);
+            }
+        };
+
+        cx.on_mouse_event({
+            let editor = self.editor.clone();
+            move |event: &MouseMoveEvent, phase, cx| {
+                if phase == DispatchPhase::Capture {
+                    return;
+                }
+
+                editor.update(cx, |editor, cx| {
+                    if !editor.scroll_manager.is_dragging_minimap() {
+                        return;
+                    }
+                    if event.pressed_button == Some(MouseButton::Left) {
+                        scroll_to_y(editor, event.position.y, cx);
+                        cx.stop_propagation();
+                    } else {
+                        editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                    }
+                })
+            }
+        });
+
+        if self.editor.read(cx).scroll_manager.is_dragging_minimap() {
+            cx.on_mouse_event({
+                let editor = self.editor.clone();
+                move |_: &MouseUpEvent, phase, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        } else {
+            cx.on_mouse_event({
+                let editor = self.editor.clone();
+                let hitbox = minimap_layout.hitbox.clone();
+                move |event: &MouseDownEvent, phase, cx| {
+                    if phase == DispatchPhase::Capture || !hitbox.is_hovered(cx) {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(true, cx);
+                        scroll_to_y(editor, event.position.y, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        }
+    }
+
     fn collect_fast_scrollbar_markers(
         &self,
         layout: &EditorLayout,
@@ -3696,11 +4373,14 @@ impl EditorElement {
                                     == TypeId::of::<DocumentHighlightRead>()
                                     || *background_highlight_id
                                         == TypeId::of::<DocumentHighlightWrite>();
+                                let is_word_occurrences = *background_highlight_id
+                                    == TypeId::of::<WordOccurrenceHighlight>();
                                 if (is_search_highlights && scrollbar_settings.search_results)
                                     || (is_symbol_occurrences && scrollbar_settings.selected_symbol)
+                                    || (is_word_occurrences && scrollbar_settings.word_occurrences)
                                 {
                                     let mut color = theme.status().info;
-                                    if is_symbol_occurrences {
+                                    if is_symbol_occurrences || is_word_occurrences {
                                         color.fade_out(0.5);
                                     }
                                     let marker_row_ranges =
@@ -3845,6 +4525,42 @@ impl EditorElement {
         }
     }
 
+    fn paint_sticky_headers(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        if layout.sticky_headers.is_empty() {
+            return;
+        }
+
+        cx.paint_layer(layout.text_hitbox.bounds, |cx| {
+            for sticky_header in &mut layout.sticky_headers {
+                sticky_header.paint(cx);
+            }
+        })
+    }
+
+    fn paint_inline_diagnostics(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        if layout.inline_diagnostics.is_empty() {
+            return;
+        }
+
+        cx.paint_layer(layout.text_hitbox.bounds, |cx| {
+            for inline_diagnostic in layout.inline_diagnostics.values_mut() {
+                inline_diagnostic.paint(cx);
+            }
+        })
+    }
+
+    fn paint_jump_labels(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        if layout.jump_labels.is_empty() {
+            return;
+        }
+
+        cx.paint_layer(layout.text_hitbox.bounds, |cx| {
+            for jump_label in &mut layout.jump_labels {
+                jump_label.paint(cx);
+            }
+        })
+    }
+
     fn paint_blocks(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
         for mut block in layout.blocks.drain(..) {
             block.element.paint(cx);
@@ -3857,6 +4573,12 @@ impl EditorElement {
         }
     }
 
+    fn paint_color_picker(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        if let Some(color_picker) = layout.color_picker.as_mut() {
+            color_picker.paint(cx);
+        }
+    }
+
     fn paint_scroll_wheel_listener(&mut self, layout: &EditorLayout, cx: &mut WindowContext) {
         cx.on_mouse_event({
             let position_map = layout.position_map.clone();
@@ -3949,7 +4671,14 @@ impl EditorElement {
                             );
                         }),
                         MouseButton::Right => editor.update(cx, |editor, cx| {
-                            Self::mouse_right_down(editor, event, &position_map, &text_hitbox, cx);
+                            Self::mouse_right_down(
+                                editor,
+                                event,
+                                hovered_hunk.clone(),
+                                &position_map,
+                                &text_hitbox,
+                                cx,
+                            );
                         }),
                         MouseButton::Middle => editor.update(cx, |editor, cx| {
                             Self::mouse_middle_down(editor, event, &position_map, &text_hitbox, cx);
@@ -4136,7 +4865,16 @@ fn render_inline_blame_entry(
     let relative_timestamp = blame_entry_relative_timestamp(&blame_entry);
 
     let author = blame_entry.author.as_deref().unwrap_or_default();
-    let text = format!("{}, {}", author, relative_timestamp);
+    let text = match blame_entry.summary.as_ref() {
+        Some(summary)
+            if ProjectSettings::get_global(cx)
+                .git
+                .show_inline_commit_summary() =>
+        {
+            format!("{}, {} - {}", author, relative_timestamp, summary)
+        }
+        _ => format!("{}, {}", author, relative_timestamp),
+    };
 
     let details = blame.read(cx).details_for_entry(&blame_entry);
 
@@ -4269,6 +5007,69 @@ fn deploy_blame_entry_context_menu(
     });
 }
 
+/// Returns the symbol displayed in place of characters that are easily missed or can change how
+/// the surrounding text is displayed, such as non-breaking spaces, zero-width spaces and bidi
+/// control characters.
+///
+/// The symbol is encoded with as many bytes as the character, so that it can be shaped in its
+/// place without changing the offsets of the line.
+fn suspicious_char_symbol(char: char) -> Option<char> {
+    match char {
+        // No-break space.
+        '\u{00A0}' => Some('·'),
+        // Soft hyphen.
+        '\u{00AD}' => Some('¬'),
+        // Figure space and narrow no-break space.
+        '\u{2007}' | '\u{202F}' => Some('⍽'),
+        // Zero-width space, word joiner and zero-width no-break space.
+        '\u{200B}' | '\u{2060}' | '\u{FEFF}' => Some('␣'),
+        // Bidi marks, embeddings, overrides and isolates.
+        '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => Some('⇄'),
+        _ => None,
+    }
+}
+
+/// Appends the text to the line, replacing suspicious characters with a symbol tinted with the
+/// given color.
+fn push_text_with_suspicious_chars(
+    text: &str,
+    run: TextRun,
+    suspicious_color: Hsla,
+    line: &mut String,
+    styles: &mut Vec<TextRun>,
+) {
+    let mut segment_start = 0;
+    for (ix, char) in text.char_indices() {
+        let Some(symbol) = suspicious_char_symbol(char) else {
+            continue;
+        };
+        debug_assert_eq!(symbol.len_utf8(), char.len_utf8());
+
+        if segment_start < ix {
+            line.push_str(&text[segment_start..ix]);
+            styles.push(TextRun {
+                len: ix - segment_start,
+                ..run.clone()
+            });
+        }
+        line.push(symbol);
+        styles.push(TextRun {
+            len: symbol.len_utf8(),
+            color: suspicious_color,
+            ..run.clone()
+        });
+        segment_start = ix + char.len_utf8();
+    }
+
+    if segment_start < text.len() {
+        line.push_str(&text[segment_start..]);
+        styles.push(TextRun {
+            len: text.len() - segment_start,
+            ..run
+        });
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LineWithInvisibles {
     fragments: SmallVec<[LineFragment; 1]>,
@@ -4421,14 +5222,14 @@ impl LineWithInvisibles {
                             line_exceeded_max_len = true;
                         }
 
-                        styles.push(TextRun {
+                        let run = TextRun {
                             len: line_chunk.len(),
                             font: text_style.font(),
                             color: text_style.color,
                             background_color: text_style.background_color,
                             underline: text_style.underline,
                             strikethrough: text_style.strikethrough,
-                        });
+                        };
 
                         if editor_mode == EditorMode::Full {
                             // Line wrap pads its contents with fake whitespaces,
@@ -4447,11 +5248,12 @@ impl LineWithInvisibles {
                             } else {
                                 invisibles.extend(
                                     line_chunk
-                                        .bytes()
-                                        .enumerate()
-                                        .filter(|(_, line_byte)| {
-                                            let is_whitespace =
-                                                (*line_byte as char).is_whitespace();
+                                        .char_indices()
+                                        .filter(|(_, line_char)| {
+                                            // Suspicious characters are replaced by a symbol
+                                            // instead.
+                                            let is_whitespace = line_char.is_whitespace()
+                                                && suspicious_char_symbol(*line_char).is_none();
                                             non_whitespace_added |= !is_whitespace;
                                             is_whitespace
                                                 && (non_whitespace_added || !inside_wrapped_string)
@@ -4463,7 +5265,13 @@ impl LineWithInvisibles {
                             }
                         }
 
-                        line.push_str(line_chunk);
+                        push_text_with_suspicious_chars(
+                            line_chunk,
+                            run,
+                            cx.theme().status().warning,
+                            &mut line,
+                            &mut styles,
+                        );
                     }
                 }
             }
@@ -4926,7 +5734,11 @@ impl Element for EditorElement {
                     let text_width = bounds.size.width - gutter_dimensions.width;
 
                     let right_margin = if snapshot.mode == EditorMode::Full {
-                        EditorElement::SCROLLBAR_WIDTH
+                        if EditorSettings::get_global(cx).minimap.enabled {
+                            EditorElement::SCROLLBAR_WIDTH + MinimapLayout::WIDTH
+                        } else {
+                            EditorElement::SCROLLBAR_WIDTH
+                        }
                     } else {
                         px(0.)
                     };
@@ -4960,7 +5772,15 @@ impl Element for EditorElement {
                         .read(cx)
                         .wrap_guides(cx)
                         .iter()
-                        .map(|(guide, active)| (self.column_pixels(*guide, cx), *active))
+                        .map(|(guide, active)| WrapGuideLayout {
+                            x: self.column_pixels(guide.column(), cx),
+                            active: *active,
+                            style: guide.style(),
+                            color: guide
+                                .color()
+                                .and_then(|color| Rgba::try_from(color).ok())
+                                .map(Hsla::from),
+                        })
                         .collect::<SmallVec<[_; 2]>>();
 
                     let hitbox = cx.insert_hitbox(bounds, false);
@@ -5190,10 +6010,47 @@ impl Element for EditorElement {
                         )
                     });
 
+                    let sticky_headers = cx.with_element_namespace("sticky_headers", |cx| {
+                        self.layout_sticky_headers(
+                            &snapshot,
+                            start_row,
+                            content_origin,
+                            &text_hitbox,
+                            line_height,
+                            cx,
+                        )
+                    });
+
+                    let inline_diagnostics = self.layout_inline_diagnostics(
+                        &snapshot,
+                        start_row..end_row,
+                        start_anchor..end_anchor,
+                        &line_layouts,
+                        &crease_trailers,
+                        em_width,
+                        content_origin,
+                        scroll_pixel_position,
+                        line_height,
+                        cx,
+                    );
+
+                    let jump_labels = self.layout_jump_labels(
+                        &snapshot,
+                        start_row..end_row,
+                        &line_layouts,
+                        content_origin,
+                        scroll_pixel_position,
+                        line_height,
+                        cx,
+                    );
+
                     let mut inline_blame = None;
                     if let Some(newest_selection_head) = newest_selection_head {
                         let display_row = newest_selection_head.row();
-                        if (start_row..end_row).contains(&display_row) {
+                        // Diagnostics take precedence over blame at the end of a line.
+                        if (start_row..end_row).contains(&display_row)
+                            && !inline_diagnostics.contains_key(&display_row)
+                        {
                             let line_ix = display_row.minus(start_row) as usize;
                             let line_layout = &line_layouts[line_ix];
                             let crease_trailer_layout = crease_trailers[line_ix].as_ref();
@@ -5297,6 +6154,14 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let minimap_layout = self.layout_minimap(
+                        &snapshot,
+                        bounds,
+                        scroll_position,
+                        height_in_lines,
+                        cx,
+                    );
+
                     let gutter_settings = EditorSettings::get_global(cx).gutter;
 
                     let expanded_add_hunks_by_rows = self.editor.update(cx, |editor, _| {
@@ -5394,6 +6259,17 @@ impl Element for EditorElement {
                         }
                     }
 
+                    let bookmark_indicators = self.layout_bookmark_indicators(
+                        line_height,
+                        start_row..end_row,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &rows_with_hunk_bounds,
+                        &snapshot,
+                        cx,
+                    );
+
                     let test_indicators = if gutter_settings.runnables {
                         self.layout_run_indicators(
                             line_height,
@@ -5447,6 +6323,15 @@ impl Element for EditorElement {
 
                     let mouse_context_menu =
                         self.layout_mouse_context_menu(&snapshot, start_row..end_row, cx);
+                    let color_picker = self.layout_color_picker(
+                        &snapshot,
+                        start_row..end_row,
+                        content_origin,
+                        scroll_pixel_position,
+                        &line_layouts,
+                        line_height,
+                        cx,
+                    );
 
                     cx.with_element_namespace("gutter_fold_toggles", |cx| {
                         self.prepaint_gutter_fold_toggles(
@@ -5514,6 +6399,7 @@ impl Element for EditorElement {
                         display_hunks,
                         content_origin,
                         scrollbar_layout,
+                        minimap_layout,
                         active_rows,
                         highlighted_rows,
                         highlighted_ranges,
@@ -5522,12 +6408,17 @@ impl Element for EditorElement {
                         line_elements,
                         line_numbers,
                         blamed_display_rows,
+                        sticky_headers,
+                        inline_diagnostics,
                         inline_blame,
+                        jump_labels,
                         blocks,
                         cursors,
                         visible_cursors,
                         selections,
                         mouse_context_menu,
+                        color_picker,
+                        bookmark_indicators,
                         test_indicators,
                         close_indicators,
                         code_actions_indicator,
@@ -5617,8 +6508,13 @@ impl Element for EditorElement {
                         });
                     }
 
+                    cx.with_element_namespace("sticky_headers", |cx| {
+                        self.paint_sticky_headers(layout, cx);
+                    });
+                    self.paint_minimap(layout, cx);
                     self.paint_scrollbar(layout, cx);
                     self.paint_mouse_context_menu(layout, cx);
+                    self.paint_color_picker(layout, cx);
                 });
             })
         })
@@ -5651,8 +6547,9 @@ pub struct EditorLayout {
     gutter_dimensions: GutterDimensions,
     content_origin: gpui::Point<Pixels>,
     scrollbar_layout: Option<ScrollbarLayout>,
+    minimap_layout: Option<MinimapLayout>,
     mode: EditorMode,
-    wrap_guides: SmallVec<[(Pixels, bool); 2]>,
+    wrap_guides: SmallVec<[WrapGuideLayout; 2]>,
     indent_guides: Option<Vec<IndentGuideLayout>>,
     visible_display_row_range: Range<DisplayRow>,
     active_rows: BTreeMap<DisplayRow, bool>,
@@ -5661,7 +6558,10 @@ pub struct EditorLayout {
     line_numbers: Vec<Option<ShapedLine>>,
     display_hunks: Vec<(DisplayDiffHunk, Option<Hitbox>)>,
     blamed_display_rows: Option<Vec<AnyElement>>,
+    sticky_headers: Vec<AnyElement>,
+    inline_diagnostics: BTreeMap<DisplayRow, AnyElement>,
     inline_blame: Option<AnyElement>,
+    jump_labels: Vec<AnyElement>,
     blocks: Vec<BlockLayout>,
     highlighted_ranges: Vec<(Range<DisplayPoint>, Hsla)>,
     highlighted_gutter_ranges: Vec<(Range<DisplayPoint>, Hsla)>,
@@ -5670,11 +6570,13 @@ pub struct EditorLayout {
     visible_cursors: Vec<CursorLayout>,
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     code_actions_indicator: Option<AnyElement>,
+    bookmark_indicators: Vec<AnyElement>,
     test_indicators: Vec<AnyElement>,
     close_indicators: Vec<AnyElement>,
     gutter_fold_toggles: Vec<Option<AnyElement>>,
     crease_trailers: Vec<Option<CreaseTrailerLayout>>,
     mouse_context_menu: Option<AnyElement>,
+    color_picker: Option<AnyElement>,
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
 }
@@ -5685,6 +6587,29 @@ impl EditorLayout {
     }
 }
 
+struct MinimapLayout {
+    hitbox: Hitbox,
+    /// The first row shown in the minimap.
+    first_row: f32,
+    /// The rows visible in the editor.
+    visible_row_range: Range<f32>,
+    /// The blocks drawn for the text of the rows shown in the minimap.
+    text_quads: Vec<PaintQuad>,
+    /// The rows of search results, selections and diagnostics.
+    highlights: Vec<ColoredRange<DisplayRow>>,
+}
+
+impl MinimapLayout {
+    const WIDTH: Pixels = px(100.);
+    const ROW_HEIGHT: Pixels = px(2.);
+    const COLUMN_WIDTH: Pixels = px(1.);
+    const MAX_COLUMNS: u32 = 100;
+
+    fn y_for_row(&self, row: f32) -> Pixels {
+        self.hitbox.top() + (row - self.first_row) * Self::ROW_HEIGHT
+    }
+}
+
 struct ColoredRange<T> {
     start: T,
     end: T,
@@ -5891,6 +6816,15 @@ fn layout_line(
         .unwrap()
 }
 
+struct WrapGuideLayout {
+    x: Pixels,
+    /// Whether this is the guide at the soft wrap column.
+    active: bool,
+    style: WrapGuideStyle,
+    /// The color of the guide, when it overrides the theme's wrap guide colors.
+    color: Option<Hsla>,
+}
+
 #[derive(Debug)]
 pub struct IndentGuideLayout {
     origin: gpui::Point<Pixels>,
@@ -6171,12 +7105,16 @@ mod tests {
     use super::*;
     use crate::{
         display_map::{BlockDisposition, BlockProperties},
+        editor_settings::MinimapContent,
         editor_tests::{init_test, update_test_language_settings},
+        test::editor_test_context::EditorTestContext,
         Editor, MultiBuffer,
     };
-    use gpui::{TestAppContext, VisualTestContext};
-    use language::language_settings;
+    use gpui::{Modifiers, TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use language::{language_settings, Language, LanguageConfig};
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use ui::Context;
     use util::test::sample_text;
@@ -6611,6 +7549,166 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    fn test_suspicious_chars_drawing(cx: &mut TestAppContext) {
+        init_test(cx, |s| {
+            s.defaults.show_whitespaces = Some(ShowWhitespaceSetting::All);
+        });
+
+        let window = cx.add_window(|cx| {
+            let buffer = MultiBuffer::build_simple("a\u{00A0}b\u{200B}c \u{202E}d\u{2066}", cx);
+            Editor::new(EditorMode::Full, buffer, None, true, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style)
+        });
+
+        let line_layout = &state.position_map.line_layouts[0];
+        let shaped_text = line_layout
+            .fragments
+            .iter()
+            .filter_map(|fragment| match fragment {
+                LineFragment::Text(shaped_line) => Some(shaped_line.text.to_string()),
+                LineFragment::Element { .. } => None,
+            })
+            .collect::<String>();
+        assert_eq!(shaped_text, "a·b␣c ⇄d⇄");
+        // Only the regular space is drawn as a whitespace.
+        assert_eq!(
+            line_layout.invisibles,
+            vec![Invisible::Whitespace { line_offset: 8 }]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_sticky_header_items(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig::default(),
+                Some(tree_sitter_rust::language()),
+            )
+            .with_outline_query(
+                r#"
+                (mod_item
+                    "mod" @context
+                    name: (_) @name) @item
+                (impl_item
+                    "impl" @context
+                    type: (_) @name) @item
+                (function_item
+                    "fn" @context
+                    name: (_) @name) @item
+                "#,
+            )
+            .unwrap(),
+        );
+
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+        cx.set_state(indoc! {"
+            ˇmod a {
+                impl B {
+                    fn c() {
+                        1
+                    }
+
+                    fn d() {
+                        2
+                        3
+                        4
+                        5
+                        6
+                    }
+                }
+            }
+        "});
+        cx.run_until_parked();
+
+        let mut sticky_headers = |start_row: u32| {
+            cx.update_editor(|editor, cx| {
+                let style = editor.style().unwrap().clone();
+                let element = EditorElement::new(cx.view(), style);
+                element
+                    .sticky_header_items(&editor.snapshot(cx), DisplayRow(start_row))
+                    .into_iter()
+                    .map(|item| item.text)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // The header of the outermost item is still visible in place.
+        assert_eq!(sticky_headers(0), Vec::<String>::new());
+        // The top row is before the start of the `impl`, so only `mod` encloses it.
+        assert_eq!(sticky_headers(1), ["mod a"]);
+        // `fn c` ends on the row that its header would be pinned to.
+        assert_eq!(sticky_headers(3), ["mod a", "impl B"]);
+        assert_eq!(sticky_headers(7), ["mod a", "impl B", "fn d"]);
+        assert_eq!(sticky_headers(9), ["mod a", "impl B", "fn d"]);
+        assert_eq!(sticky_headers(10), ["mod a", "impl B"]);
+        // The `impl` ends under the header of `mod`.
+        assert_eq!(sticky_headers(13), ["mod a"]);
+    }
+
+    #[gpui::test]
+    async fn test_minimap_click_scrolls_to_row(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        enabled: Some(true),
+                    });
+                });
+            });
+        });
+
+        let mut cx = EditorTestContext::new(cx).await;
+        let text = (0..200)
+            .map(|row| format!("line {row}\n"))
+            .collect::<String>();
+        cx.set_state(&format!("ˇ{text}"));
+        cx.run_until_parked();
+
+        let (bounds, scrollbar_width, rows_per_page) = cx.update_editor(|editor, _| {
+            (
+                editor.last_bounds.unwrap(),
+                editor.style().unwrap().scrollbar_width,
+                editor.visible_line_count().unwrap(),
+            )
+        });
+        // All the rows fit in the minimap, so its first row is the first row of the buffer.
+        assert!(bounds.size.height / MinimapLayout::ROW_HEIGHT > 201.);
+        let minimap_x = bounds.right() - scrollbar_width - MinimapLayout::WIDTH / 2.;
+
+        // Clicking centers the editor on the row under the mouse.
+        cx.simulate_click(
+            point(minimap_x, bounds.top() + MinimapLayout::ROW_HEIGHT * 150.5),
+            Modifiers::none(),
+        );
+        cx.update_editor(|editor, cx| {
+            let scroll_top = editor.scroll_position(cx).y;
+            assert!(
+                (scroll_top - (150.5 - rows_per_page / 2.)).abs() < 0.01,
+                "unexpected scroll top {scroll_top}"
+            );
+            assert!(!editor.scroll_manager.is_dragging_minimap());
+        });
+
+        // Rows closer to the top than half a page scroll to the top.
+        cx.simulate_click(
+            point(minimap_x, bounds.top() + MinimapLayout::ROW_HEIGHT * 2.),
+            Modifiers::none(),
+        );
+        cx.update_editor(|editor, cx| {
+            assert_eq!(editor.scroll_position(cx).y, 0.);
+        });
+    }
+
     fn collect_invisibles_from_new_editor(
         cx: &mut TestAppContext,
         editor_mode: EditorMode,
@@ -0,0 +1,268 @@
+use std::fmt::Write as _;
+
+use collections::HashMap;
+use gpui::{FontStyle, HighlightStyle, Hsla, Pixels, Rgba, SharedString, ViewContext};
+use settings::Settings;
+use theme::{ActiveTheme, ThemeSettings};
+
+use crate::{
+    actions::{ExportAsHtml, ExportAsRtf},
+    Editor,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExportFormat {
+    Html,
+    Rtf,
+}
+
+/// The colors and font that exported text is shown with, taken from the theme and the buffer
+/// font settings.
+struct ExportStyle {
+    background: Hsla,
+    foreground: Hsla,
+    font_family: SharedString,
+    font_size: Pixels,
+}
+
+impl Editor {
+    pub fn export_as_html(&mut self, _: &ExportAsHtml, cx: &mut ViewContext<Self>) {
+        self.export(ExportFormat::Html, cx);
+    }
+
+    pub fn export_as_rtf(&mut self, _: &ExportAsRtf, cx: &mut ViewContext<Self>) {
+        self.export(ExportFormat::Rtf, cx);
+    }
+
+    /// Prompts for a path to save the exported text at, and saves it there.
+    fn export(&mut self, format: ExportFormat, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let text = self.exported_text(format, cx);
+        let path = workspace.update(cx, |workspace, cx| workspace.prompt_for_new_path(cx));
+        cx.spawn(|_, mut cx| async move {
+            let Some(path) = path.await.ok().flatten() else {
+                return Ok(());
+            };
+            let buffer = project
+                .update(&mut cx, |project, cx| project.create_buffer(cx))?
+                .await?;
+            buffer.update(&mut cx, |buffer, cx| buffer.set_text(text, cx))?;
+            project
+                .update(&mut cx, |project, cx| {
+                    project.save_buffer_as(buffer, path, cx)
+                })?
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Returns the selected text, or the whole buffer when nothing is selected, in the given
+    /// format, styled with the theme's colors for its syntax highlighting.
+    pub(crate) fn exported_text(
+        &mut self,
+        format: ExportFormat,
+        cx: &mut ViewContext<Self>,
+    ) -> String {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut ranges = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| selection.range())
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            ranges.push(0..snapshot.len());
+        }
+
+        let syntax_theme = cx.theme().syntax().clone();
+        let mut runs = Vec::<(String, HighlightStyle)>::new();
+        let mut push_run = |text: &str, style: HighlightStyle| match runs.last_mut() {
+            Some((last_text, last_style)) if *last_style == style => last_text.push_str(text),
+            _ => runs.push((text.to_string(), style)),
+        };
+        for (ix, range) in ranges.into_iter().enumerate() {
+            if ix > 0 {
+                push_run("\n", HighlightStyle::default());
+            }
+            for chunk in snapshot.chunks(range, true) {
+                let style = chunk
+                    .syntax_highlight_id
+                    .and_then(|highlight_id| highlight_id.style(&syntax_theme))
+                    .unwrap_or_default();
+                push_run(chunk.text, style);
+            }
+        }
+
+        let theme_settings = ThemeSettings::get_global(cx);
+        let style = ExportStyle {
+            background: cx.theme().colors().editor_background,
+            foreground: cx.theme().colors().editor_foreground,
+            font_family: theme_settings.buffer_font.family.clone(),
+            font_size: theme_settings.buffer_font_size(cx),
+        };
+        match format {
+            ExportFormat::Html => to_html(&runs, &style),
+            ExportFormat::Rtf => to_rtf(&runs, &style),
+        }
+    }
+}
+
+fn to_html(runs: &[(String, HighlightStyle)], style: &ExportStyle) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n<body>\n",
+    );
+    write!(
+        html,
+        "<pre style=\"background-color: {}; color: {}; font-family: '{}', monospace; font-size: {}px; padding: 1em;\"><code>",
+        css_color(style.background),
+        css_color(style.foreground),
+        escape_html(&style.font_family),
+        style.font_size.0,
+    )
+    .unwrap();
+    for (text, highlight) in runs {
+        let mut declarations = Vec::new();
+        if let Some(color) = highlight.color {
+            declarations.push(format!("color: {}", css_color(color)));
+        }
+        if let Some(font_weight) = highlight.font_weight {
+            declarations.push(format!("font-weight: {}", font_weight.0));
+        }
+        if let Some(FontStyle::Italic | FontStyle::Oblique) = highlight.font_style {
+            declarations.push("font-style: italic".to_string());
+        }
+
+        if declarations.is_empty() {
+            html.push_str(&escape_html(text));
+        } else {
+            write!(
+                html,
+                "<span style=\"{}\">{}</span>",
+                declarations.join("; "),
+                escape_html(text)
+            )
+            .unwrap();
+        }
+    }
+    html.push_str("</code></pre>\n</body>\n</html>\n");
+    html
+}
+
+fn to_rtf(runs: &[(String, HighlightStyle)], style: &ExportStyle) -> String {
+    // The first colors of the table are the foreground and background ones, followed by the
+    // colors of the highlights in the order they appear in.
+    let mut colors = Vec::new();
+    let mut color_indices = HashMap::default();
+    let mut color_index = |color: Hsla| {
+        let color = Rgba::from(color);
+        let rgb = [color.r, color.g, color.b].map(|component| (component * 255.).round() as u8);
+        *color_indices.entry(rgb).or_insert_with(|| {
+            colors.push(rgb);
+            colors.len()
+        })
+    };
+    let foreground_index = color_index(style.foreground);
+    let background_index = color_index(style.background);
+    let run_color_indices = runs
+        .iter()
+        .map(|(_, highlight)| highlight.color.map(&mut color_index))
+        .collect::<Vec<_>>();
+
+    let mut rtf = String::from("{\\rtf1\\ansi\\deff0");
+    write!(
+        rtf,
+        "{{\\fonttbl{{\\f0\\fmodern {};}}}}",
+        escape_rtf(&style.font_family)
+    )
+    .unwrap();
+    rtf.push_str("{\\colortbl;");
+    for [red, green, blue] in colors {
+        write!(rtf, "\\red{red}\\green{green}\\blue{blue};").unwrap();
+    }
+    rtf.push('}');
+    // Font sizes are in half points, and there are three quarters of a point in a pixel.
+    write!(
+        rtf,
+        "\n\\f0\\fs{}\\cb{background_index}\\cf{foreground_index} ",
+        (style.font_size.0 * 1.5).round() as u32
+    )
+    .unwrap();
+
+    for ((text, highlight), color_index) in runs.iter().zip(run_color_indices) {
+        rtf.push('{');
+        if let Some(color_index) = color_index {
+            write!(rtf, "\\cf{color_index}").unwrap();
+        }
+        if highlight
+            .font_weight
+            .map_or(false, |font_weight| font_weight.0 >= 600.)
+        {
+            rtf.push_str("\\b");
+        }
+        if let Some(FontStyle::Italic | FontStyle::Oblique) = highlight.font_style {
+            rtf.push_str("\\i");
+        }
+        rtf.push(' ');
+        rtf.push_str(&escape_rtf(text));
+        rtf.push('}');
+    }
+    rtf.push_str("}\n");
+    rtf
+}
+
+fn css_color(color: Hsla) -> String {
+    let color = Rgba::from(color);
+    let [red, green, blue] =
+        [color.r, color.g, color.b].map(|component| (component * 255.).round() as u8);
+    if color.a < 1. {
+        format!("rgba({red}, {green}, {blue}, {:.2})", color.a)
+    } else {
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn escape_rtf(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\n' => escaped.push_str("\\line\n"),
+            '\t' => escaped.push_str("\\tab "),
+            _ if character.is_ascii() => escaped.push(character),
+            // Other characters are written as their signed UTF-16 code units, followed by a
+            // `?` for the readers that don't support unicode.
+            _ => {
+                let mut code_units = [0; 2];
+                for code_unit in character.encode_utf16(&mut code_units) {
+                    write!(escaped, "\\u{}?", *code_unit as i16).unwrap();
+                }
+            }
+        }
+    }
+    escaped
+}
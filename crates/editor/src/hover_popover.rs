@@ -660,7 +660,9 @@ impl DiagnosticPopover {
                 this.bg(gpui::transparent_black())
             })
             .cursor(CursorStyle::PointingHand)
-            .tooltip(move |cx| Tooltip::for_action("Go To Diagnostic", &crate::GoToDiagnostic, cx))
+            .tooltip(move |cx| {
+                Tooltip::for_action("Go To Diagnostic", &crate::GoToDiagnostic::default(), cx)
+            })
             // Prevent a mouse move on the popover from being propagated to the editor,
             // because that would dismiss the popover.
             .on_mouse_move(|_, cx| cx.stop_propagation())
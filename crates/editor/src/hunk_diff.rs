@@ -5,17 +5,17 @@ use std::{
 
 use collections::{hash_map, HashMap, HashSet};
 use git::diff::{DiffHunk, DiffHunkStatus};
-use gpui::{Action, AppContext, CursorStyle, Hsla, Model, MouseButton, Subscription, Task, View};
+use gpui::{
+    Action, AppContext, ClipboardItem, CursorStyle, Hsla, Model, MouseButton, Subscription, Task,
+    View,
+};
 use language::Buffer;
 use multi_buffer::{
     Anchor, AnchorRangeExt, ExcerptRange, MultiBuffer, MultiBufferRow, MultiBufferSnapshot, ToPoint,
 };
 use settings::SettingsStore;
-use text::{BufferId, Point};
-use ui::{
-    div, h_flex, rems, v_flex, ActiveTheme, Context as _, ContextMenu, InteractiveElement,
-    IntoElement, ParentElement, Pixels, Styled, ViewContext, VisualContext,
-};
+use text::{BufferId, Point, ToPoint as _};
+use ui::{prelude::*, ContextMenu, IconButtonShape, Tooltip};
 use util::{debug_panic, RangeExt};
 
 use crate::{
@@ -23,9 +23,9 @@ use crate::{
     git::{diff_hunk_to_display, DisplayDiffHunk},
     hunk_status, hunks_for_selections,
     mouse_context_menu::MouseContextMenu,
-    BlockDisposition, BlockProperties, BlockStyle, CustomBlockId, DiffRowHighlight, Editor,
-    EditorElement, EditorSnapshot, ExpandAllHunkDiffs, RangeToAnchorExt, RevertSelectedHunks,
-    ToDisplayPoint, ToggleHunkDiff,
+    BlockDisposition, BlockProperties, BlockStyle, CustomBlockId, DiffRowHighlight, DisplayRow,
+    Editor, EditorElement, EditorSnapshot, ExpandAllHunkDiffs, RangeToAnchorExt,
+    RevertSelectedHunks, StageSelectedHunks, ToDisplayPoint, ToggleHunkDiff, UnstageSelectedHunks,
 };
 
 #[derive(Debug, Clone)]
@@ -98,7 +98,8 @@ impl Editor {
             closest_source,
             clicked_point,
             ContextMenu::build(cx, move |menu, _| {
-                menu.on_blur_subscription(Subscription::new(|| {}))
+                let menu = menu
+                    .on_blur_subscription(Subscription::new(|| {}))
                     .context(focus_handle)
                     .entry(
                         if expanded {
@@ -121,51 +122,65 @@ impl Editor {
                         let editor = editor_handle.clone();
                         let hunk = hovered_hunk.clone();
                         move |cx| {
-                            let multi_buffer = editor.read(cx).buffer().clone();
-                            let multi_buffer_snapshot = multi_buffer.read(cx).snapshot(cx);
-                            let mut revert_changes = HashMap::default();
-                            if let Some(hunk) =
-                                crate::hunk_diff::to_diff_hunk(&hunk, &multi_buffer_snapshot)
-                            {
-                                Editor::prepare_revert_change(
-                                    &mut revert_changes,
-                                    &multi_buffer,
-                                    &hunk,
-                                    cx,
-                                );
-                            }
-                            if !revert_changes.is_empty() {
-                                editor.update(cx, |editor, cx| editor.revert(revert_changes, cx));
-                            }
+                            editor.update(cx, |editor, cx| editor.revert_hovered_hunk(&hunk, cx));
                         }
                     })
-                    .entry("Revert File", None, {
+                    .entry("Stage Hunk", Some(StageSelectedHunks.boxed_clone()), {
                         let editor = editor_handle.clone();
+                        let hunk = hovered_hunk.clone();
                         move |cx| {
-                            let mut revert_changes = HashMap::default();
-                            let multi_buffer = editor.read(cx).buffer().clone();
-                            let multi_buffer_snapshot = multi_buffer.read(cx).snapshot(cx);
-                            for hunk in crate::hunks_for_rows(
-                                Some(MultiBufferRow(0)..multi_buffer_snapshot.max_buffer_row())
-                                    .into_iter(),
-                                &multi_buffer_snapshot,
-                            ) {
-                                Editor::prepare_revert_change(
-                                    &mut revert_changes,
-                                    &multi_buffer,
-                                    &hunk,
-                                    cx,
-                                );
-                            }
-                            if !revert_changes.is_empty() {
-                                editor.update(cx, |editor, cx| {
-                                    editor.transact(cx, |editor, cx| {
-                                        editor.revert(revert_changes, cx);
-                                    });
-                                });
-                            }
+                            editor.update(cx, |editor, cx| {
+                                editor.stage_hovered_hunk(&hunk, true, cx)
+                            });
                         }
                     })
+                    .entry("Unstage Hunk", Some(UnstageSelectedHunks.boxed_clone()), {
+                        let editor = editor_handle.clone();
+                        let hunk = hovered_hunk.clone();
+                        move |cx| {
+                            editor.update(cx, |editor, cx| {
+                                editor.stage_hovered_hunk(&hunk, false, cx)
+                            });
+                        }
+                    });
+                let menu = if hovered_hunk.status == DiffHunkStatus::Added {
+                    menu
+                } else {
+                    menu.entry("Copy Original Text", None, {
+                        let editor = editor_handle.clone();
+                        let hunk = hovered_hunk.clone();
+                        move |cx| {
+                            editor.read(cx).copy_hunk_original_text(&hunk, cx);
+                        }
+                    })
+                };
+                menu.entry("Revert File", None, {
+                    let editor = editor_handle.clone();
+                    move |cx| {
+                        let mut revert_changes = HashMap::default();
+                        let multi_buffer = editor.read(cx).buffer().clone();
+                        let multi_buffer_snapshot = multi_buffer.read(cx).snapshot(cx);
+                        for hunk in crate::hunks_for_rows(
+                            Some(MultiBufferRow(0)..multi_buffer_snapshot.max_buffer_row())
+                                .into_iter(),
+                            &multi_buffer_snapshot,
+                        ) {
+                            Editor::prepare_revert_change(
+                                &mut revert_changes,
+                                &multi_buffer,
+                                &hunk,
+                                cx,
+                            );
+                        }
+                        if !revert_changes.is_empty() {
+                            editor.update(cx, |editor, cx| {
+                                editor.transact(cx, |editor, cx| {
+                                    editor.revert(revert_changes, cx);
+                                });
+                            });
+                        }
+                    }
+                })
             }),
             cx,
         )
@@ -183,6 +198,126 @@ impl Editor {
         }
     }
 
+    pub(super) fn revert_hovered_hunk(
+        &mut self,
+        hovered_hunk: &HoveredHunk,
+        cx: &mut ViewContext<Editor>,
+    ) {
+        let multi_buffer = self.buffer().clone();
+        let multi_buffer_snapshot = multi_buffer.read(cx).snapshot(cx);
+        let mut revert_changes = HashMap::default();
+        if let Some(hunk) = to_diff_hunk(hovered_hunk, &multi_buffer_snapshot) {
+            Editor::prepare_revert_change(&mut revert_changes, &multi_buffer, &hunk, cx);
+        }
+        if !revert_changes.is_empty() {
+            self.revert(revert_changes, cx);
+        }
+    }
+
+    /// Stages the changes of the hovered hunk when `stage` is true, and otherwise unstages the
+    /// staged changes on its lines.
+    pub(super) fn stage_hovered_hunk(
+        &mut self,
+        hovered_hunk: &HoveredHunk,
+        stage: bool,
+        cx: &mut ViewContext<Editor>,
+    ) {
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let Some(hunk) = to_diff_hunk(hovered_hunk, &multi_buffer_snapshot) else {
+            return;
+        };
+        let Some(buffer) = self.buffer().read(cx).buffer(hunk.buffer_id) else {
+            return;
+        };
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let rows = hunk.buffer_range.start.to_point(&buffer_snapshot).row
+            ..hunk.buffer_range.end.to_point(&buffer_snapshot).row;
+        self.update_index_for_rows(vec![(buffer, rows)], stage, cx);
+    }
+
+    /// Stages the changes on the given rows of the buffers when `stage` is true, and otherwise
+    /// resets the staged changes on them to their version in HEAD, by writing the new contents
+    /// of their files to the index.
+    pub(super) fn update_index_for_rows(
+        &mut self,
+        rows: Vec<(Model<Buffer>, Range<u32>)>,
+        stage: bool,
+        cx: &mut ViewContext<Editor>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let mut rows_by_buffer = HashMap::<BufferId, (Model<Buffer>, Vec<Range<u32>>)>::default();
+        for (buffer, rows) in rows {
+            rows_by_buffer
+                .entry(buffer.read(cx).remote_id())
+                .or_insert_with(|| (buffer.clone(), Vec::new()))
+                .1
+                .push(rows);
+        }
+
+        for (buffer, rows) in rows_by_buffer.into_values() {
+            // Buffers without a diff base aren't in the index.
+            let Some(index_text) = buffer.read(cx).diff_base().map(ToString::to_string) else {
+                continue;
+            };
+            let working_text = buffer.read(cx).text();
+            let head_text = (!stage).then(|| project.read(cx).load_head_text(&buffer, cx));
+            let project = project.clone();
+            cx.spawn(|_, mut cx| async move {
+                let head_text = match head_text {
+                    Some(head_text) => Some(head_text.await?.unwrap_or_default()),
+                    None => None,
+                };
+                let new_index_text = cx
+                    .background_executor()
+                    .spawn({
+                        let index_text = index_text.clone();
+                        async move {
+                            match head_text {
+                                Some(head_text) => git::staging::unstage_rows(
+                                    &head_text,
+                                    &index_text,
+                                    &working_text,
+                                    &rows,
+                                ),
+                                None => git::staging::stage_rows(&index_text, &working_text, &rows),
+                            }
+                        }
+                    })
+                    .await;
+                if new_index_text == index_text {
+                    return Ok(());
+                }
+                project
+                    .update(&mut cx, |project, cx| {
+                        project.set_index_text(&buffer, new_index_text, cx)
+                    })?
+                    .await
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Copies the text of the diff base that the hunk changed.
+    pub(super) fn copy_hunk_original_text(&self, hovered_hunk: &HoveredHunk, cx: &AppContext) {
+        let Some((_, buffer, _)) = self
+            .buffer()
+            .read(cx)
+            .excerpt_containing(hovered_hunk.multi_buffer_range.start, cx)
+        else {
+            return;
+        };
+        let Some(original_text) = buffer.read(cx).diff_base().map(|diff_base| {
+            diff_base
+                .slice(hovered_hunk.diff_base_byte_range.clone())
+                .to_string()
+        }) else {
+            return;
+        };
+        cx.write_to_clipboard(ClipboardItem::new(original_text));
+    }
+
     pub fn toggle_hunk_diff(&mut self, _: &ToggleHunkDiff, cx: &mut ViewContext<Self>) {
         let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
         let selections = self.selections.disjoint_anchors();
@@ -441,7 +576,7 @@ impl Editor {
                     let Some(gutter_bounds) = editor.read(cx).gutter_bounds() else {
                         return div().into_any_element();
                     };
-                    let (gutter_dimensions, hunk_bounds, close_button) =
+                    let (gutter_dimensions, hunk_bounds, hunk_buttons) =
                         editor.update(cx.context, |editor, cx| {
                             let editor_snapshot = editor.snapshot(cx);
                             let hunk_display_range = hunk
@@ -462,12 +597,15 @@ impl Editor {
                                 },
                             );
 
-                            let close_button = editor.close_hunk_diff_button(
-                                hunk.clone(),
-                                hunk_display_range.start.row(),
-                                cx,
-                            );
-                            (gutter_dimensions, hunk_bounds, close_button)
+                            let row = hunk_display_range.start.row();
+                            let hunk_buttons = [
+                                editor.close_hunk_diff_button(hunk.clone(), row, cx),
+                                editor.stage_hunk_button(hunk.clone(), row, cx),
+                                editor.unstage_hunk_button(hunk.clone(), row, cx),
+                                editor.revert_hunk_button(hunk.clone(), row, cx),
+                                editor.copy_hunk_original_text_button(hunk.clone(), row, cx),
+                            ];
+                            (gutter_dimensions, hunk_bounds, hunk_buttons)
                         });
                     let click_editor = editor.clone();
                     let clicked_hunk = hunk.clone();
@@ -517,7 +655,7 @@ impl Editor {
                                         .size_full()
                                         .pt(rems(0.25))
                                         .justify_start()
-                                        .child(close_button),
+                                        .children(hunk_buttons),
                                 ),
                         )
                         .child(editor_with_deleted_text.clone())
@@ -537,6 +675,70 @@ impl Editor {
         }
     }
 
+    fn revert_hunk_button(
+        &self,
+        hunk: HoveredHunk,
+        row: DisplayRow,
+        cx: &mut ViewContext<Self>,
+    ) -> IconButton {
+        IconButton::new(
+            ("revert_hunk_indicator", row.0 as usize),
+            IconName::RotateCcw,
+        )
+        .shape(IconButtonShape::Square)
+        .icon_size(IconSize::XSmall)
+        .icon_color(Color::Muted)
+        .tooltip(|cx| Tooltip::for_action("Revert hunk", &RevertSelectedHunks, cx))
+        .on_click(cx.listener(move |editor, _e, cx| editor.revert_hovered_hunk(&hunk, cx)))
+    }
+
+    fn stage_hunk_button(
+        &self,
+        hunk: HoveredHunk,
+        row: DisplayRow,
+        cx: &mut ViewContext<Self>,
+    ) -> IconButton {
+        IconButton::new(("stage_hunk_indicator", row.0 as usize), IconName::Plus)
+            .shape(IconButtonShape::Square)
+            .icon_size(IconSize::XSmall)
+            .icon_color(Color::Muted)
+            .tooltip(|cx| Tooltip::for_action("Stage hunk", &StageSelectedHunks, cx))
+            .on_click(cx.listener(move |editor, _e, cx| editor.stage_hovered_hunk(&hunk, true, cx)))
+    }
+
+    fn unstage_hunk_button(
+        &self,
+        hunk: HoveredHunk,
+        row: DisplayRow,
+        cx: &mut ViewContext<Self>,
+    ) -> IconButton {
+        IconButton::new(("unstage_hunk_indicator", row.0 as usize), IconName::Dash)
+            .shape(IconButtonShape::Square)
+            .icon_size(IconSize::XSmall)
+            .icon_color(Color::Muted)
+            .tooltip(|cx| Tooltip::for_action("Unstage hunk", &UnstageSelectedHunks, cx))
+            .on_click(
+                cx.listener(move |editor, _e, cx| editor.stage_hovered_hunk(&hunk, false, cx)),
+            )
+    }
+
+    fn copy_hunk_original_text_button(
+        &self,
+        hunk: HoveredHunk,
+        row: DisplayRow,
+        cx: &mut ViewContext<Self>,
+    ) -> IconButton {
+        IconButton::new(
+            ("copy_hunk_original_text_indicator", row.0 as usize),
+            IconName::Copy,
+        )
+        .shape(IconButtonShape::Square)
+        .icon_size(IconSize::XSmall)
+        .icon_color(Color::Muted)
+        .tooltip(|cx| Tooltip::text("Copy original text", cx))
+        .on_click(cx.listener(move |editor, _e, cx| editor.copy_hunk_original_text(&hunk, cx)))
+    }
+
     pub(super) fn clear_clicked_diff_hunks(&mut self, cx: &mut ViewContext<'_, Editor>) -> bool {
         self.expanded_hunks.hunk_update_tasks.clear();
         self.clear_row_highlights::<DiffRowHighlight>();
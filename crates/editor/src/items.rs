@@ -859,6 +859,12 @@ impl Item for Editor {
 
     fn added_to_workspace(&mut self, workspace: &mut Workspace, _: &mut ViewContext<Self>) {
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        self.zen_mode = workspace.is_zen_mode();
+    }
+
+    fn set_zen_mode(&mut self, zen_mode: bool, cx: &mut ViewContext<Self>) {
+        self.zen_mode = zen_mode;
+        cx.notify();
     }
 
     fn to_item_events(event: &EditorEvent, mut f: impl FnMut(ItemEvent)) {
@@ -1041,6 +1047,7 @@ impl SerializableItem for Editor {
                 cx.new_view(|cx| {
                     let mut editor = Editor::for_buffer(buffer, Some(project), cx);
 
+                    editor.read_folds_from_db(item_id, workspace_id, cx);
                     editor.read_scroll_position_from_db(item_id, workspace_id, cx);
                     editor
                 })
@@ -1078,6 +1085,7 @@ impl SerializableItem for Editor {
         let mtime = buffer.read(cx).saved_mtime();
 
         let snapshot = buffer.read(cx).snapshot();
+        let folds = self.serialized_folds(cx);
 
         Some(cx.spawn(|_this, cx| async move {
             cx.background_executor()
@@ -1099,7 +1107,10 @@ impl SerializableItem for Editor {
 
                     DB.save_serialized_editor(item_id, workspace_id, editor)
                         .await
-                        .context("failed to save serialized editor")
+                        .context("failed to save serialized editor")?;
+                    DB.save_editor_folds(item_id, workspace_id, folds)
+                        .await
+                        .context("failed to save editor folds")
                 })
                 .await
                 .context("failed to save contents of buffer")?;
@@ -1326,14 +1337,42 @@ impl SearchableItem for Editor {
         query: Arc<project::search::SearchQuery>,
         cx: &mut ViewContext<Self>,
     ) -> Task<Vec<Range<Anchor>>> {
-        let buffer = self.buffer().read(cx).snapshot(cx);
         let search_within_ranges = self
             .background_highlights
             .get(&TypeId::of::<SearchWithinRange>())
             .map_or(vec![], |(_color, ranges)| {
                 ranges.iter().map(|range| range.clone()).collect::<Vec<_>>()
             });
+        self.find_matches_in_ranges(query, search_within_ranges, cx)
+    }
+
+    fn active_match_index(
+        &mut self,
+        matches: &[Range<Anchor>],
+        cx: &mut ViewContext<Self>,
+    ) -> Option<usize> {
+        active_match_index(
+            matches,
+            &self.selections.newest_anchor().head(),
+            &self.buffer().read(cx).snapshot(cx),
+        )
+    }
 
+    fn search_bar_visibility_changed(&mut self, _visible: bool, _cx: &mut ViewContext<Self>) {
+        self.expect_bounds_change = self.last_bounds;
+    }
+}
+
+impl Editor {
+    /// Finds the matches of the query within the given ranges, or in the whole buffer when there
+    /// are no ranges.
+    pub fn find_matches_in_ranges(
+        &mut self,
+        query: Arc<SearchQuery>,
+        search_within_ranges: Vec<Range<Anchor>>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Vec<Range<Anchor>>> {
+        let buffer = self.buffer().read(cx).snapshot(cx);
         cx.background_executor().spawn(async move {
             let mut ranges = Vec::new();
 
@@ -1393,22 +1432,6 @@ impl SearchableItem for Editor {
             ranges
         })
     }
-
-    fn active_match_index(
-        &mut self,
-        matches: &[Range<Anchor>],
-        cx: &mut ViewContext<Self>,
-    ) -> Option<usize> {
-        active_match_index(
-            matches,
-            &self.selections.newest_anchor().head(),
-            &self.buffer().read(cx).snapshot(cx),
-        )
-    }
-
-    fn search_bar_visibility_changed(&mut self, _visible: bool, _cx: &mut ViewContext<Self>) {
-        self.expect_bounds_change = self.last_bounds;
-    }
 }
 
 pub fn active_match_index(
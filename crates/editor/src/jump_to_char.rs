@@ -0,0 +1,213 @@
+use collections::HashSet;
+use gpui::ViewContext;
+use language::Bias;
+use multi_buffer::{Anchor, ToOffset};
+
+use crate::{
+    actions::JumpToChar,
+    display_map::{DisplayRow, ToDisplayPoint},
+    scroll::Autoscroll,
+    DisplayPoint, Editor,
+};
+
+/// The characters that targets are labeled with, starting with the easiest ones to type.
+const LABEL_CHARACTERS: &str = "fjdkslaghrueiwoqpvncmtyxbz";
+
+/// How many characters can be typed to narrow down the targets, before the typed
+/// characters are only matched against labels.
+const MAX_QUERY_LEN: usize = 2;
+
+/// An in-progress [`JumpToChar`], from the moment it's started until a target is picked
+/// or it's cancelled.
+#[derive(Debug, Default)]
+pub(crate) struct JumpToCharState {
+    /// The characters to jump to, typed so far.
+    query: String,
+    /// The label characters typed so far, when there are more targets than label
+    /// characters and labels are two characters long.
+    pub(crate) typed_label: String,
+    /// The occurrences of the query in the visible rows, closest to the cursor first.
+    pub(crate) targets: Vec<JumpTarget>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct JumpTarget {
+    pub(crate) position: Anchor,
+    /// The characters to type to jump to this target, which is empty when there
+    /// are too many targets to label them all.
+    pub(crate) label: String,
+}
+
+impl Editor {
+    pub fn jump_to_char(&mut self, _: &JumpToChar, cx: &mut ViewContext<Self>) {
+        self.jump_to_char_state = Some(JumpToCharState::default());
+        cx.notify();
+    }
+
+    pub fn is_jumping_to_char(&self) -> bool {
+        self.jump_to_char_state.is_some()
+    }
+
+    /// Cancels the in-progress jump to a character, returning whether there was one.
+    pub(crate) fn cancel_jump_to_char(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        if self.jump_to_char_state.take().is_some() {
+            cx.notify();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Handles text typed while jumping to a character. The first characters narrow down the
+    /// targets, and once the targets are labeled, typing a label moves the cursor to its target.
+    /// Typing anything else cancels the jump.
+    pub(crate) fn jump_to_char_input(&mut self, text: &str, cx: &mut ViewContext<Self>) {
+        let Some(mut state) = self.jump_to_char_state.take() else {
+            return;
+        };
+        cx.notify();
+
+        for character in text.chars() {
+            if !state.query.is_empty() {
+                let mut typed_label = state.typed_label.clone();
+                typed_label.push(character);
+                if let Some(target) = state
+                    .targets
+                    .iter()
+                    .find(|target| target.label == typed_label)
+                {
+                    let position = target.position;
+                    self.jump_to(position, cx);
+                    return;
+                }
+                if state
+                    .targets
+                    .iter()
+                    .any(|target| target.label.starts_with(&typed_label))
+                {
+                    state.typed_label = typed_label;
+                    continue;
+                }
+                if !state.typed_label.is_empty() || state.query.chars().count() >= MAX_QUERY_LEN {
+                    return;
+                }
+            }
+
+            state.query.push(character);
+            state.targets = self.jump_targets(&state.query, cx);
+            match state.targets.as_slice() {
+                [] => return,
+                [target] => {
+                    let position = target.position;
+                    self.jump_to(position, cx);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        self.jump_to_char_state = Some(state);
+    }
+
+    fn jump_to(&mut self, position: Anchor, cx: &mut ViewContext<Self>) {
+        self.change_selections(Some(Autoscroll::fit()), cx, |selections| {
+            selections.select_anchor_ranges([position..position])
+        });
+    }
+
+    /// Finds the occurrences of the query in the visible rows and labels them, closest to the
+    /// newest cursor first. The query is matched case-insensitively unless it contains an
+    /// uppercase character.
+    fn jump_targets(&mut self, query: &str, cx: &mut ViewContext<Self>) -> Vec<JumpTarget> {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        let scroll_top = self.scroll_manager.anchor().scroll_position(&display_map).y;
+        let visible_line_count = self.visible_line_count().unwrap_or(0.);
+        let start_row = DisplayRow(scroll_top.floor() as u32);
+        let end_row = DisplayRow((scroll_top + visible_line_count).ceil() as u32)
+            .min(display_map.max_point().row());
+        let start = DisplayPoint::new(start_row, 0).to_point(&display_map);
+        let end = display_map
+            .clip_point(DisplayPoint::new(end_row, u32::MAX), Bias::Left)
+            .to_point(&display_map);
+        let start_offset = start.to_offset(buffer);
+
+        let ignore_case = !query.chars().any(char::is_uppercase);
+        let normalize = |character: char| {
+            if ignore_case {
+                character.to_ascii_lowercase()
+            } else {
+                character
+            }
+        };
+        let query = query.chars().map(normalize).collect::<Vec<_>>();
+        let text = buffer.text_for_range(start..end).collect::<String>();
+        let characters = text.char_indices().collect::<Vec<_>>();
+
+        let cursor = self.selections.newest_anchor().head().to_offset(buffer);
+        let mut occurrences = Vec::new();
+        let mut following_characters = HashSet::default();
+        for (ix, window) in characters.windows(query.len()).enumerate() {
+            let matches = window
+                .iter()
+                .zip(&query)
+                .all(|((_, character), query_character)| normalize(*character) == *query_character);
+            if !matches {
+                continue;
+            }
+
+            // Skip occurrences hidden inside of folds.
+            let offset = start_offset + window[0].0;
+            let point = buffer.offset_to_point(offset);
+            if point.to_display_point(&display_map).to_point(&display_map) != point {
+                continue;
+            }
+
+            if let Some((_, following_character)) = characters.get(ix + query.len()) {
+                following_characters.insert(normalize(*following_character));
+            }
+            occurrences.push((offset, point));
+        }
+        occurrences.sort_by_key(|(offset, _)| offset.abs_diff(cursor));
+
+        // While the query can still grow, leave out the labels that could be confused with
+        // its next character.
+        let label_characters = LABEL_CHARACTERS
+            .chars()
+            .filter(|character| {
+                query.len() >= MAX_QUERY_LEN || !following_characters.contains(character)
+            })
+            .collect::<Vec<_>>();
+        let labels = labels(&label_characters, occurrences.len());
+        occurrences
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (_, point))| JumpTarget {
+                position: buffer.anchor_before(point),
+                label: labels.get(ix).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Returns up to `count` distinct labels made of the given characters, using single
+/// characters when there are enough of them, and pairs of characters otherwise.
+fn labels(label_characters: &[char], count: usize) -> Vec<String> {
+    if count <= label_characters.len() {
+        return label_characters
+            .iter()
+            .take(count)
+            .map(|character| character.to_string())
+            .collect();
+    }
+
+    label_characters
+        .iter()
+        .flat_map(|first| {
+            LABEL_CHARACTERS
+                .chars()
+                .map(move |second| format!("{first}{second}"))
+        })
+        .take(count)
+        .collect()
+}
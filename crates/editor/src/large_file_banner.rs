@@ -0,0 +1,94 @@
+use collections::HashSet;
+use gpui::{EntityId, EventEmitter, View};
+use ui::{prelude::*, IconButtonShape, Tooltip};
+use workspace::{item::ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
+
+use crate::Editor;
+
+/// A banner shown above editors of files opened in large file mode, explaining which
+/// features are disabled for them.
+pub struct LargeFileBanner {
+    active_editor: Option<View<Editor>>,
+    dismissed_on: HashSet<EntityId>,
+}
+
+impl LargeFileBanner {
+    pub fn new() -> Self {
+        Self {
+            active_editor: None,
+            dismissed_on: HashSet::default(),
+        }
+    }
+
+    fn toolbar_location(&self, cx: &mut ViewContext<Self>) -> ToolbarItemLocation {
+        let Some(editor) = self.active_editor.as_ref() else {
+            return ToolbarItemLocation::Hidden;
+        };
+        if self.dismissed_on.contains(&editor.entity_id()) {
+            return ToolbarItemLocation::Hidden;
+        }
+
+        let is_large_file = editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .map_or(false, |buffer| buffer.read(cx).is_large_file());
+        if is_large_file {
+            ToolbarItemLocation::Secondary
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for LargeFileBanner {}
+
+impl ToolbarItemView for LargeFileBanner {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) -> ToolbarItemLocation {
+        cx.notify();
+        self.active_editor = active_pane_item.and_then(|item| item.act_as::<Editor>(cx));
+        self.toolbar_location(cx)
+    }
+}
+
+impl Render for LargeFileBanner {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .justify_between()
+            .bg(cx.theme().status().warning_background)
+            .rounded_md()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(
+                        "This file is opened in large file mode: syntax highlighting, inlay hints and language servers are disabled.",
+                    )),
+            )
+            .child(
+                IconButton::new("dismiss", IconName::Close)
+                    .style(ButtonStyle::Transparent)
+                    .shape(IconButtonShape::Square)
+                    .icon_size(IconSize::Small)
+                    .on_click(cx.listener(|this, _event, cx| {
+                        if let Some(editor) = this.active_editor.as_ref() {
+                            this.dismissed_on.insert(editor.entity_id());
+                        }
+                        cx.emit(ToolbarItemEvent::ChangeLocation(
+                            ToolbarItemLocation::Hidden,
+                        ))
+                    }))
+                    .tooltip(move |cx| Tooltip::text("Dismiss", cx)),
+            )
+    }
+}
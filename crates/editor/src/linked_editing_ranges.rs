@@ -2,12 +2,14 @@ use std::ops::Range;
 
 use collections::HashMap;
 use itertools::Itertools;
-use text::{AnchorRangeExt, BufferId, ToPoint};
+use text::{AnchorRangeExt, BufferId, ToOffset, ToPoint};
 use ui::ViewContext;
 use util::ResultExt;
 
 use crate::Editor;
 
+enum LinkedEditingHighlight {}
+
 #[derive(Clone, Default)]
 pub(super) struct LinkedEditingRanges(
     /// Ranges are non-overlapping and sorted by .0 (thus, [x + 1].start > [x].end must hold)
@@ -119,6 +121,7 @@ pub(super) fn refresh_linked_ranges(this: &mut Editor, cx: &mut ViewContext<Edit
         this.update(&mut cx, |this, cx| {
             this.linked_edit_ranges.0.clear();
             if this.pending_rename.is_some() {
+                highlight_linked_ranges(this, cx);
                 return;
             }
             for (buffer_id, ranges) in highlights.into_iter().flatten() {
@@ -139,6 +142,7 @@ pub(super) fn refresh_linked_ranges(this: &mut Editor, cx: &mut ViewContext<Edit
                 };
                 values.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0, &snapshot));
             }
+            highlight_linked_ranges(this, cx);
 
             cx.notify();
         })
@@ -148,3 +152,53 @@ pub(super) fn refresh_linked_ranges(this: &mut Editor, cx: &mut ViewContext<Edit
     }));
     None
 }
+
+/// Subtly highlights the ranges that are edited together, so that it's clear that editing one
+/// of them edits the others too.
+fn highlight_linked_ranges(this: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let snapshot = this.buffer.read(cx).snapshot(cx);
+    let mut highlights = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        let Some(ranges) = this.linked_edit_ranges.0.get(&buffer.remote_id()) else {
+            continue;
+        };
+        let excerpt_start = excerpt_range.context.start.to_offset(buffer);
+        let excerpt_end = excerpt_range.context.end.to_offset(buffer);
+        for (range, _) in ranges {
+            if range.start.to_offset(buffer) < excerpt_start
+                || range.end.to_offset(buffer) > excerpt_end
+            {
+                continue;
+            }
+            if let Some((start, end)) = snapshot
+                .anchor_in_excerpt(excerpt_id, range.start)
+                .zip(snapshot.anchor_in_excerpt(excerpt_id, range.end))
+            {
+                highlights.push(start..end);
+            }
+        }
+    }
+
+    if highlights.is_empty() {
+        this.clear_background_highlights::<LinkedEditingHighlight>(cx);
+    } else {
+        this.highlight_background::<LinkedEditingHighlight>(
+            &highlights,
+            |colors| colors.editor_document_highlight_write_background,
+            cx,
+        );
+    }
+}
+
+/// Stops editing the linked ranges together, until they're refreshed after the selections
+/// move. Returns whether there were linked ranges.
+pub(super) fn cancel_linked_ranges(this: &mut Editor, cx: &mut ViewContext<Editor>) -> bool {
+    this.linked_editing_range_task = None;
+    if this.linked_edit_ranges.is_empty() {
+        return false;
+    }
+    this.linked_edit_ranges.0.clear();
+    this.clear_background_highlights::<LinkedEditingHighlight>(cx);
+    cx.notify();
+    true
+}
@@ -0,0 +1,311 @@
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+
+use gpui::{
+    Action, AppContext, EntityId, Global, KeystrokeEvent, ReadGlobal, UpdateGlobal, View,
+    ViewContext, WindowContext,
+};
+
+use crate::{
+    actions::{ReplayMacro, StartRecordingMacro, StopRecording},
+    Editor, EditorEvent,
+};
+
+/// A step of a keyboard macro.
+#[derive(Debug)]
+enum MacroStep {
+    /// An action dispatched by a keystroke.
+    Action(Box<dyn Action>),
+    /// Text typed in an editor.
+    Insertion {
+        text: Arc<str>,
+        utf16_range_to_replace: Option<Range<isize>>,
+    },
+}
+
+impl Clone for MacroStep {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Action(action) => Self::Action(action.boxed_clone()),
+            Self::Insertion {
+                text,
+                utf16_range_to_replace,
+            } => Self::Insertion {
+                text: text.clone(),
+                utf16_range_to_replace: utf16_range_to_replace.clone(),
+            },
+        }
+    }
+}
+
+/// Records the actions dispatched and the text typed in editors, to replay them with
+/// [`ReplayMacro`].
+#[derive(Default)]
+struct MacroRecorder {
+    /// The steps recorded so far, while a macro is being recorded.
+    recording: Option<Vec<MacroStep>>,
+    /// The editor in which the recording started. Only the text typed in it is recorded, as
+    /// macros are replayed in a single editor.
+    recording_editor: Option<EntityId>,
+    last_macro: Vec<MacroStep>,
+    replaying: bool,
+}
+
+impl Global for MacroRecorder {}
+
+impl MacroRecorder {
+    fn record(&mut self, step: MacroStep) {
+        if self.replaying {
+            return;
+        }
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(step);
+        }
+    }
+
+    fn record_insertion(&mut self, editor: EntityId, step: MacroStep) {
+        if self.recording_editor == Some(editor) {
+            self.record(step);
+        }
+    }
+}
+
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.set_global(MacroRecorder::default());
+    cx.observe_keystrokes(observe_keystroke).detach();
+    cx.observe_new_views(|_, cx: &mut ViewContext<Editor>| {
+        let editor = cx.view().clone();
+        cx.subscribe(&editor, |_, editor, event: &EditorEvent, cx| {
+            if let EditorEvent::InputHandled {
+                text,
+                utf16_range_to_replace,
+            } = event
+            {
+                MacroRecorder::update_global(cx, |recorder, _| {
+                    recorder.record_insertion(
+                        editor.entity_id(),
+                        MacroStep::Insertion {
+                            text: text.clone(),
+                            utf16_range_to_replace: utf16_range_to_replace.clone(),
+                        },
+                    )
+                });
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+fn observe_keystroke(keystroke_event: &KeystrokeEvent, cx: &mut WindowContext) {
+    // Keystrokes typing text don't resolve to an action, and are recorded as insertions
+    // by the editor handling them instead.
+    let Some(action) = keystroke_event.action.as_ref() else {
+        return;
+    };
+    if StartRecordingMacro.partial_eq(&**action)
+        || StopRecording.partial_eq(&**action)
+        || action.as_any().is::<ReplayMacro>()
+    {
+        return;
+    }
+
+    MacroRecorder::update_global(cx, |recorder, _| {
+        recorder.record(MacroStep::Action(action.boxed_clone()))
+    });
+}
+
+/// Replays the steps one by one, letting each dispatched action be handled before the
+/// next step.
+fn replay_steps(editor: View<Editor>, mut steps: VecDeque<MacroStep>, cx: &mut WindowContext) {
+    let Some(step) = steps.pop_front() else {
+        MacroRecorder::update_global(cx, |recorder, _| recorder.replaying = false);
+        return;
+    };
+
+    match step {
+        MacroStep::Action(action) => cx.dispatch_action(action),
+        MacroStep::Insertion {
+            text,
+            utf16_range_to_replace,
+        } => editor.update(cx, |editor, cx| {
+            editor.replay_insert_event(&text, utf16_range_to_replace, cx)
+        }),
+    }
+    cx.defer(move |cx| replay_steps(editor, steps, cx));
+}
+
+impl Editor {
+    pub fn start_recording_macro(&mut self, _: &StartRecordingMacro, cx: &mut ViewContext<Self>) {
+        let editor = cx.view().entity_id();
+        MacroRecorder::update_global(cx, |recorder, _| {
+            if !recorder.replaying {
+                recorder.recording = Some(Vec::new());
+                recorder.recording_editor = Some(editor);
+            }
+        });
+    }
+
+    pub fn stop_recording(&mut self, _: &StopRecording, cx: &mut ViewContext<Self>) {
+        MacroRecorder::update_global(cx, |recorder, _| {
+            recorder.recording_editor = None;
+            // Keep the previous macro when nothing was recorded.
+            if let Some(recording) = recorder.recording.take().filter(|steps| !steps.is_empty()) {
+                recorder.last_macro = recording;
+            }
+        });
+    }
+
+    pub fn replay_macro(&mut self, action: &ReplayMacro, cx: &mut ViewContext<Self>) {
+        let recorder = MacroRecorder::global(cx);
+        if recorder.replaying || recorder.recording.is_some() || recorder.last_macro.is_empty() {
+            return;
+        }
+
+        let times = action.times.max(1);
+        let steps = (0..times)
+            .flat_map(|_| recorder.last_macro.iter().cloned())
+            .collect::<VecDeque<_>>();
+        MacroRecorder::update_global(cx, |recorder, _| recorder.replaying = true);
+
+        let editor = cx.view().clone();
+        cx.window_context()
+            .defer(move |cx| replay_steps(editor, steps, cx));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        actions::{MoveDown, MoveLeft, MoveRight},
+        editor_tests::init_test,
+        test::editor_test_context::EditorTestContext,
+    };
+    use gpui::{KeyBinding, TestAppContext, ViewInputHandler as _, VisualContext as _};
+    use indoc::indoc;
+
+    fn bind_macro_keys(cx: &mut EditorTestContext) {
+        cx.update(|cx| {
+            cx.bind_keys([
+                KeyBinding::new("ctrl-q", StartRecordingMacro, None),
+                KeyBinding::new("ctrl-w", StopRecording, None),
+                KeyBinding::new("ctrl-r", ReplayMacro::default(), None),
+                KeyBinding::new("ctrl-t", ReplayMacro { times: 2 }, None),
+                KeyBinding::new("left", MoveLeft, None),
+                KeyBinding::new("right", MoveRight, None),
+                KeyBinding::new("down", MoveDown, None),
+            ])
+        });
+    }
+
+    #[gpui::test]
+    async fn test_replay_macro(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        bind_macro_keys(&mut cx);
+
+        cx.set_state(indoc! {"
+            ˇone
+            two
+            three
+            four
+            five
+            six
+        "});
+        cx.simulate_keystrokes("ctrl-q - space left left down ctrl-w");
+        cx.assert_editor_state(indoc! {"
+            - one
+            ˇtwo
+            three
+            four
+            five
+            six
+        "});
+
+        cx.simulate_keystrokes("ctrl-r");
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - one
+            - two
+            ˇthree
+            four
+            five
+            six
+        "});
+
+        // The macro is replayed as many times as requested.
+        cx.simulate_keystrokes("ctrl-t");
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - one
+            - two
+            - three
+            - four
+            ˇfive
+            six
+        "});
+
+        // Replaying doesn't record anything, and the last macro is kept when nothing was
+        // recorded.
+        cx.simulate_keystrokes("ctrl-q ctrl-w ctrl-r");
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - one
+            - two
+            - three
+            - four
+            - five
+            ˇsix
+        "});
+    }
+
+    #[gpui::test]
+    async fn test_replay_macro_with_multiple_cursors(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        bind_macro_keys(&mut cx);
+
+        cx.set_state(indoc! {"
+            ˇabc
+            ˇdef
+        "});
+        cx.simulate_keystrokes("ctrl-q x right ctrl-w");
+        cx.assert_editor_state(indoc! {"
+            xaˇbc
+            xdˇef
+        "});
+
+        cx.simulate_keystrokes("ctrl-r");
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            xaxbˇc
+            xdxeˇf
+        "});
+    }
+
+    #[gpui::test]
+    async fn test_macro_ignores_text_typed_in_other_editors(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        bind_macro_keys(&mut cx);
+        let query_editor = cx.new_view(Editor::single_line);
+
+        cx.set_state(indoc! {"
+            ˇone
+            two
+        "});
+        cx.simulate_keystrokes("ctrl-q x");
+        query_editor.update(&mut cx.cx, |editor, cx| {
+            editor.replace_text_in_range(None, "query", cx)
+        });
+        cx.simulate_keystrokes("down ctrl-w");
+        cx.assert_editor_state(indoc! {"
+            xone
+            tˇwo
+        "});
+
+        cx.simulate_keystrokes("ctrl-r");
+        cx.run_until_parked();
+        cx.assert_editor_state("xone\ntxwo\nˇ");
+    }
+}
@@ -0,0 +1,86 @@
+use gpui::{AppContext, FocusableView, Render, Subscription, View, ViewContext};
+use settings::{EditableSettingControl, SettingsStore};
+use ui::prelude::*;
+
+use crate::{Editor, EditorEvent};
+
+/// A setting control with a numeric value that can be typed in.
+pub trait NumericSettingControl: EditableSettingControl {
+    /// Formats the value for editing, without its unit.
+    fn format(value: &Self::Value) -> String;
+
+    /// Parses a typed value, returning `None` if it isn't a valid value.
+    fn parse(text: &str) -> Option<Self::Value>;
+}
+
+/// A single line editor for typing the exact value of a numeric setting.
+///
+/// The editor shows the current value of the setting while it isn't focused, and writes
+/// the typed value when it loses focus.
+pub struct NumericSettingEditor {
+    editor: View<Editor>,
+    read: fn(&AppContext) -> String,
+    write: fn(&str, &AppContext) -> bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl NumericSettingEditor {
+    pub fn new<C: NumericSettingControl>(cx: &mut ViewContext<Self>) -> Self {
+        let read = |cx: &AppContext| C::format(&C::read(cx));
+        let write = |text: &str, cx: &AppContext| match C::parse(text.trim()) {
+            Some(value) => {
+                C::write(value, cx);
+                true
+            }
+            None => false,
+        };
+
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_text(read(cx), cx);
+            editor
+        });
+        let _subscriptions = vec![
+            cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::Blurred = event {
+                    this.confirm(cx);
+                }
+            }),
+            cx.observe_global::<SettingsStore>(|this, cx| this.refresh(cx)),
+        ];
+
+        Self {
+            editor,
+            read,
+            write,
+            _subscriptions,
+        }
+    }
+
+    /// Shows the current value of the setting, unless it's being typed in.
+    pub fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        if self.editor.focus_handle(cx).is_focused(cx) {
+            return;
+        }
+
+        let text = (self.read)(cx);
+        if self.editor.read(cx).text(cx) != text {
+            self.editor
+                .update(cx, |editor, cx| editor.set_text(text, cx));
+        }
+    }
+
+    /// Writes the typed value, restoring the current one if it isn't valid.
+    fn confirm(&mut self, cx: &mut ViewContext<Self>) {
+        let text = self.editor.read(cx).text(cx);
+        if text.trim() == (self.read)(cx) || !(self.write)(&text, cx) {
+            self.refresh(cx);
+        }
+    }
+}
+
+impl Render for NumericSettingEditor {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div().w_12().child(self.editor.clone())
+    }
+}
@@ -89,6 +89,13 @@ define_connection!(
     //   mtime_seconds: Option<i64>,
     //   mtime_nanos: Option<i32>,
     // )
+    //
+    // editor_folds(
+    //   item_id: usize,
+    //   workspace_id: usize,
+    //   start: usize,
+    //   end: usize,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> =
         &[sql! (
             CREATE TABLE editors(
@@ -136,6 +143,18 @@ define_connection!(
             ALTER TABLE editors ADD COLUMN mtime_seconds INTEGER DEFAULT NULL;
             ALTER TABLE editors ADD COLUMN mtime_nanos INTEGER DEFAULT NULL;
         ),
+        sql! (
+            CREATE TABLE editor_folds (
+                item_id INTEGER NOT NULL,
+                workspace_id INTEGER NOT NULL,
+                start INTEGER NOT NULL,
+                end INTEGER NOT NULL,
+                PRIMARY KEY(item_id, workspace_id, start, end),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
         ];
 );
 
@@ -190,6 +209,39 @@ impl EditorDb {
         }
     }
 
+    // Returns the offset ranges of the folds
+    query! {
+        pub fn get_editor_folds(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Vec<(usize, usize)>> {
+            SELECT start, end
+            FROM editor_folds
+            WHERE item_id = ? AND workspace_id = ?
+            ORDER BY start
+        }
+    }
+
+    pub async fn save_editor_folds(
+        &self,
+        item_id: ItemId,
+        workspace_id: WorkspaceId,
+        folds: Vec<(usize, usize)>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("update_editor_folds", || {
+                conn.exec_bound(sql!(
+                    DELETE FROM editor_folds WHERE item_id = ? AND workspace_id = ?
+                ))?((item_id, workspace_id))?;
+                for (start, end) in folds {
+                    conn.exec_bound(sql!(
+                        INSERT OR IGNORE INTO editor_folds (item_id, workspace_id, start, end)
+                        VALUES (?, ?, ?, ?)
+                    ))?((item_id, workspace_id, start, end))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
     pub async fn delete_unloaded_items(
         &self,
         workspace: WorkspaceId,
@@ -202,7 +254,8 @@ impl EditorDb {
             .join(", ");
 
         let query = format!(
-            "DELETE FROM editors WHERE workspace_id = ? AND item_id NOT IN ({placeholders})"
+            "DELETE FROM editors WHERE workspace_id = ? AND item_id NOT IN ({placeholders});
+            DELETE FROM editor_folds WHERE workspace_id = ? AND item_id NOT IN ({placeholders})"
         );
 
         self.write(move |conn| {
@@ -298,4 +351,31 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_get_editor_folds() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        DB.save_editor_folds(1234, workspace_id, vec![(10, 20), (0, 5)])
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_editor_folds(1234, workspace_id).unwrap(),
+            vec![(0, 5), (10, 20)]
+        );
+
+        // Saving the folds replaces the previous ones
+        DB.save_editor_folds(1234, workspace_id, vec![(3, 8)])
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_editor_folds(1234, workspace_id).unwrap(),
+            vec![(3, 8)]
+        );
+
+        DB.delete_unloaded_items(workspace_id, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_editor_folds(1234, workspace_id).unwrap(), Vec::new());
+    }
 }
@@ -3,7 +3,9 @@ use crate::{
 };
 use gpui::{px, Bounds, Pixels, ViewContext};
 use language::Point;
+use settings::Settings;
 use std::{cmp, f32};
+use workspace::WorkspaceSettings;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Autoscroll {
@@ -65,6 +67,15 @@ impl Editor {
         self.scroll_manager.autoscroll_requested()
     }
 
+    fn typewriter_scrolling(&self, cx: &ViewContext<Editor>) -> bool {
+        self.zen_mode
+            && self.mode == EditorMode::Full
+            && WorkspaceSettings::get_global(cx)
+                .zen_mode
+                .typewriter_scrolling
+                .unwrap_or(false)
+    }
+
     pub fn autoscroll_vertically(
         &mut self,
         bounds: Bounds<Pixels>,
@@ -164,6 +175,16 @@ impl Editor {
             }
         };
 
+        // Typewriter scrolling keeps the cursor vertically centered as it moves.
+        let strategy = match strategy {
+            AutoscrollStrategy::Fit | AutoscrollStrategy::Newest
+                if self.typewriter_scrolling(cx) =>
+            {
+                AutoscrollStrategy::Center
+            }
+            strategy => strategy,
+        };
+
         match strategy {
             AutoscrollStrategy::Fit | AutoscrollStrategy::Newest => {
                 let margin = margin.min(self.scroll_manager.vertical_scroll_margin);
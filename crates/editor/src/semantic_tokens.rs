@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use collections::HashMap;
+use gpui::{Task, ViewContext};
+use language::{language_settings::language_settings, HighlightId};
+use project::SemanticToken;
+use text::{BufferId, ToOffset};
+use theme::ActiveTheme;
+
+use crate::{Editor, EditorMode};
+
+pub(crate) const SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+
+#[derive(Default)]
+pub(crate) struct SemanticTokensState {
+    update_task: Option<Task<()>>,
+}
+
+impl Editor {
+    /// Fetches the semantic tokens of the buffers that highlight them, after a short delay, and
+    /// highlights them.
+    pub(crate) fn refresh_semantic_tokens(&mut self, cx: &mut ViewContext<Self>) {
+        if self.mode != EditorMode::Full {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let enabled_buffers = self
+            .buffer
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                language_settings(buffer.language(), buffer.file(), cx).semantic_tokens
+            })
+            .collect::<Vec<_>>();
+        if enabled_buffers.is_empty() {
+            self.semantic_tokens.update_task = None;
+            self.display_map
+                .update(cx, |map, _| map.set_semantic_tokens(Vec::new()));
+            return;
+        }
+
+        self.semantic_tokens.update_task = Some(cx.spawn(|editor, mut cx| async move {
+            cx.background_executor()
+                .timer(SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT)
+                .await;
+            let Ok(tasks) = project.update(&mut cx, |project, cx| {
+                enabled_buffers
+                    .into_iter()
+                    .map(|buffer| {
+                        let buffer_id = buffer.read(cx).remote_id();
+                        (buffer_id, project.semantic_tokens(buffer, cx))
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let mut tokens = HashMap::default();
+            for (buffer_id, task) in tasks {
+                match task.await {
+                    Ok(buffer_tokens) => {
+                        tokens.insert(buffer_id, buffer_tokens);
+                    }
+                    Err(error) => log::error!("failed to fetch semantic tokens: {error:#}"),
+                }
+            }
+            editor
+                .update(&mut cx, |editor, cx| editor.set_semantic_tokens(tokens, cx))
+                .ok();
+        }));
+    }
+
+    /// Highlights the tokens of each excerpt with the theme's syntax styles of their types.
+    fn set_semantic_tokens(
+        &mut self,
+        tokens: HashMap<BufferId, Vec<SemanticToken>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let syntax = cx.theme().syntax().clone();
+        let mut highlight_ids = HashMap::<String, HighlightId>::default();
+        let mut highlights = Vec::new();
+        for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+            let Some(tokens) = tokens.get(&buffer.remote_id()) else {
+                continue;
+            };
+
+            let excerpt_start = excerpt_range.context.start.to_offset(buffer);
+            let excerpt_end = excerpt_range.context.end.to_offset(buffer);
+            for token in tokens {
+                let start = token.range.start.to_offset(buffer);
+                let end = token.range.end.to_offset(buffer);
+                if start < excerpt_start || end > excerpt_end {
+                    continue;
+                }
+                let Some(name) = highlight_name(token) else {
+                    continue;
+                };
+                let highlight_id = *highlight_ids
+                    .entry(name)
+                    .or_insert_with_key(|name| HighlightId::for_capture_name(name, &syntax));
+                if highlight_id.is_default() {
+                    continue;
+                }
+
+                let (Some(start), Some(end)) = (
+                    snapshot.anchor_in_excerpt(excerpt_id, token.range.start),
+                    snapshot.anchor_in_excerpt(excerpt_id, token.range.end),
+                ) else {
+                    continue;
+                };
+                highlights.push((start..end, highlight_id));
+            }
+        }
+
+        self.display_map
+            .update(cx, |map, _| map.set_semantic_tokens(highlights));
+        cx.notify();
+    }
+}
+
+/// Returns the name of the syntax style a token is highlighted with, made of its type and
+/// modifiers, so that themes can style `variable.mutable` differently from `variable`.
+///
+/// Only identifiers are highlighted, as the syntax highlighting already tells the other
+/// tokens, such as keywords and strings, apart.
+fn highlight_name(token: &SemanticToken) -> Option<String> {
+    let mut name = match token.token_type.as_str() {
+        "namespace" => "namespace",
+        "type" | "class" | "enum" | "interface" | "struct" | "typeParameter" | "typeAlias"
+        | "builtinType" => "type",
+        "parameter" => "variable.parameter",
+        "variable" => "variable",
+        "property" => "property",
+        "enumMember" => "variant",
+        "function" => "function",
+        "method" => "function.method",
+        "macro" => "function.macro",
+        "decorator" => "attribute",
+        _ => return None,
+    }
+    .to_string();
+    for modifier in &token.modifiers {
+        name.push('.');
+        name.push_str(modifier);
+    }
+    Some(name)
+}
@@ -0,0 +1,269 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use gpui::{px, HighlightStyle, Task, UnderlineStyle, ViewContext};
+use language::{
+    language_settings::{language_settings, SpellCheckSetting},
+    BufferSnapshot,
+};
+use multi_buffer::{Anchor, MultiBufferSnapshot};
+use spell_check::{words_to_check, Dictionary, SpellChecker};
+use text::{BufferId, ToOffset};
+use theme::ActiveTheme;
+
+use crate::{Editor, EditorMode};
+
+const SPELL_CHECK_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Highlights the misspelled words.
+pub(crate) enum Misspelling {}
+
+#[derive(Default)]
+pub(crate) struct SpellCheckState {
+    update_task: Option<Task<()>>,
+}
+
+/// An action offered for a misspelled word in the code actions menu.
+#[derive(Clone, Debug)]
+pub(crate) enum SpellingAction {
+    Correct {
+        range: Range<Anchor>,
+        replacement: String,
+    },
+    AddToDictionary {
+        word: String,
+    },
+}
+
+impl SpellingAction {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Correct { replacement, .. } => format!("Change to \"{replacement}\""),
+            Self::AddToDictionary { word } => format!("Add \"{word}\" to dictionary"),
+        }
+    }
+}
+
+/// How the words of a buffer are spell checked.
+struct BufferSpellCheck {
+    dictionary: Arc<Dictionary>,
+    /// The setting of the buffer's language.
+    default_mode: SpellCheckSetting,
+    /// The settings of the languages of the buffer's syntax layers, like the ones injected
+    /// in Markdown.
+    language_modes: HashMap<Arc<str>, SpellCheckSetting>,
+}
+
+/// Finds the misspelled words of the excerpts whose buffers are spell checked.
+fn scan_misspellings(
+    snapshot: &MultiBufferSnapshot,
+    buffers: &HashMap<BufferId, BufferSpellCheck>,
+    user_words: &HashSet<String>,
+) -> Vec<Range<Anchor>> {
+    let mut misspellings = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        let Some(spell_check) = buffers.get(&buffer.remote_id()) else {
+            continue;
+        };
+
+        let excerpt_start = excerpt_range.context.start.to_offset(buffer);
+        let excerpt_end = excerpt_range.context.end.to_offset(buffer);
+        let text = buffer
+            .text_for_range(excerpt_start..excerpt_end)
+            .collect::<String>();
+        for word_range in words_to_check(&text) {
+            let word = &text[word_range.clone()];
+            let start = excerpt_start + word_range.start;
+            let end = excerpt_start + word_range.end;
+            let mode = buffer
+                .language_at(start)
+                .and_then(|language| spell_check.language_modes.get(&language.name()))
+                .copied()
+                .unwrap_or(spell_check.default_mode);
+            let is_checked = match mode {
+                SpellCheckSetting::Off => false,
+                SpellCheckSetting::CommentsAndStrings => is_in_comment_or_string(buffer, start),
+                SpellCheckSetting::All => true,
+            };
+            if !is_checked
+                || user_words.contains(word)
+                || user_words.contains(&word.to_lowercase())
+                || spell_check.dictionary.check(word)
+            {
+                continue;
+            }
+
+            let (Some(start), Some(end)) = (
+                snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_before(start)),
+                snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_after(end)),
+            ) else {
+                continue;
+            };
+            misspellings.push(start..end);
+        }
+    }
+    misspellings
+}
+
+fn is_in_comment_or_string(buffer: &BufferSnapshot, offset: usize) -> bool {
+    buffer.language_scope_at(offset).map_or(false, |scope| {
+        matches!(scope.override_name(), Some("comment" | "string"))
+    })
+}
+
+impl Editor {
+    /// Spell checks the buffers again, after a short delay.
+    pub(crate) fn refresh_misspellings(&mut self, cx: &mut ViewContext<Self>) {
+        if self.mode != EditorMode::Full {
+            return;
+        }
+        let Some(spell_checker) = SpellChecker::global(cx) else {
+            return;
+        };
+
+        let buffers = self
+            .buffer
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter_map(|buffer| {
+                let buffer = buffer.read(cx);
+                if buffer.is_large_file() {
+                    return None;
+                }
+                let settings = language_settings(buffer.language(), buffer.file(), cx);
+                let language_modes = buffer
+                    .snapshot()
+                    .syntax_layers()
+                    .map(|layer| {
+                        let settings = language_settings(Some(layer.language), buffer.file(), cx);
+                        (layer.language.name(), settings.spell_check)
+                    })
+                    .collect::<HashMap<_, _>>();
+                if settings.spell_check == SpellCheckSetting::Off
+                    && language_modes
+                        .values()
+                        .all(|mode| *mode == SpellCheckSetting::Off)
+                {
+                    return None;
+                }
+                Some((
+                    buffer.remote_id(),
+                    settings.spell_check_dictionary.clone(),
+                    settings.spell_check,
+                    language_modes,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut enabled_buffers = HashMap::default();
+        for (buffer_id, dictionary_name, default_mode, language_modes) in buffers {
+            // The editor is refreshed once the dictionary is loaded.
+            let Some(dictionary) = spell_checker.update(cx, |spell_checker, cx| {
+                spell_checker.dictionary(&dictionary_name, cx)
+            }) else {
+                continue;
+            };
+            enabled_buffers.insert(
+                buffer_id,
+                BufferSpellCheck {
+                    dictionary,
+                    default_mode,
+                    language_modes,
+                },
+            );
+        }
+
+        let user_words = spell_checker.read(cx).user_words();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.spell_check.update_task = Some(cx.spawn(|editor, mut cx| async move {
+            cx.background_executor()
+                .timer(SPELL_CHECK_DEBOUNCE_TIMEOUT)
+                .await;
+            let misspellings = cx
+                .background_executor()
+                .spawn(async move { scan_misspellings(&snapshot, &enabled_buffers, &user_words) })
+                .await;
+            editor
+                .update(&mut cx, |editor, cx| {
+                    if misspellings.is_empty() {
+                        editor.clear_highlights::<Misspelling>(cx);
+                    } else {
+                        let style = HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                color: Some(cx.theme().status().info),
+                                thickness: px(1.),
+                                wavy: true,
+                            }),
+                            ..Default::default()
+                        };
+                        editor.highlight_text::<Misspelling>(misspellings, style, cx);
+                    }
+                })
+                .ok();
+        }));
+    }
+
+    /// Returns the corrections of the misspelled word at the given position, followed by
+    /// the action adding it to the user's dictionary.
+    pub(crate) fn spelling_actions_at(
+        &self,
+        position: Anchor,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<Arc<[SpellingAction]>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let range = self
+            .text_highlights::<Misspelling>(cx)?
+            .1
+            .iter()
+            .find(|range| {
+                range.start.cmp(&position, &snapshot).is_le()
+                    && range.end.cmp(&position, &snapshot).is_ge()
+            })?
+            .clone();
+        let word = snapshot.text_for_range(range.clone()).collect::<String>();
+
+        let (buffer, _, _) = self
+            .buffer
+            .read(cx)
+            .point_to_buffer_offset(range.start, cx)?;
+        let buffer = buffer.read(cx);
+        let dictionary_name = language_settings(buffer.language(), buffer.file(), cx)
+            .spell_check_dictionary
+            .clone();
+        let dictionary = SpellChecker::global(cx)?.update(cx, |spell_checker, cx| {
+            spell_checker.dictionary(&dictionary_name, cx)
+        })?;
+
+        let mut actions = dictionary
+            .suggest(&word)
+            .into_iter()
+            .map(|replacement| SpellingAction::Correct {
+                range: range.clone(),
+                replacement,
+            })
+            .collect::<Vec<_>>();
+        actions.push(SpellingAction::AddToDictionary { word });
+        Some(actions.into())
+    }
+
+    pub(crate) fn apply_spelling_action(
+        &mut self,
+        action: SpellingAction,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        match action {
+            SpellingAction::Correct { range, replacement } => {
+                self.transact(cx, |editor, cx| editor.edit([(range, replacement)], cx));
+                Task::ready(Ok(()))
+            }
+            SpellingAction::AddToDictionary { word } => match SpellChecker::global(cx) {
+                Some(spell_checker) => spell_checker.update(cx, |spell_checker, cx| {
+                    spell_checker.add_to_user_dictionary(&word, cx)
+                }),
+                None => Task::ready(Ok(())),
+            },
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{UniformListScrollHandle, ViewContext};
+use language::{CodeLabel, LanguageServerId};
+use parking_lot::{Mutex, RwLock};
+use project::Completion;
+use settings::Settings;
+use text::ToOffset;
+use util::post_inc;
+
+use crate::{
+    debounced_delay::DebouncedDelay, CompletionsMenu, ContextMenu, Editor, EditorSettings,
+};
+
+/// The longest abbreviation that is looked for before the cursor.
+const MAX_ABBREVIATION_LEN: usize = 32;
+
+/// The built-in abbreviations, named after the LaTeX commands of the characters they stand for.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    // Greek letters
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    // Logic and sets
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("nexists", "∄"),
+    ("neg", "¬"),
+    ("wedge", "∧"),
+    ("vee", "∨"),
+    ("top", "⊤"),
+    ("bot", "⊥"),
+    ("vdash", "⊢"),
+    ("models", "⊨"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("ni", "∋"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("setminus", "∖"),
+    ("emptyset", "∅"),
+    ("bbN", "ℕ"),
+    ("bbZ", "ℤ"),
+    ("bbQ", "ℚ"),
+    ("bbR", "ℝ"),
+    ("bbC", "ℂ"),
+    // Operators
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("cdot", "⋅"),
+    ("circ", "∘"),
+    ("oplus", "⊕"),
+    ("otimes", "⊗"),
+    ("sqrt", "√"),
+    ("cbrt", "∛"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+    ("oint", "∮"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("infty", "∞"),
+    // Relations
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("neq", "≠"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("simeq", "≃"),
+    ("cong", "≅"),
+    ("propto", "∝"),
+    ("perp", "⟂"),
+    ("parallel", "∥"),
+    // Arrows
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("leftrightarrow", "↔"),
+    ("uparrow", "↑"),
+    ("downarrow", "↓"),
+    ("mapsto", "↦"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"),
+    // Miscellaneous symbols
+    ("ell", "ℓ"),
+    ("hbar", "ħ"),
+    ("aleph", "ℵ"),
+    ("angle", "∠"),
+    ("degree", "°"),
+    ("dagger", "†"),
+    ("star", "⋆"),
+    ("bullet", "•"),
+    ("ldots", "…"),
+    ("cdots", "⋯"),
+    ("vdots", "⋮"),
+    ("ddots", "⋱"),
+    // Superscripts and subscripts
+    ("^0", "⁰"),
+    ("^1", "¹"),
+    ("^2", "²"),
+    ("^3", "³"),
+    ("^4", "⁴"),
+    ("^5", "⁵"),
+    ("^6", "⁶"),
+    ("^7", "⁷"),
+    ("^8", "⁸"),
+    ("^9", "⁹"),
+    ("^+", "⁺"),
+    ("^-", "⁻"),
+    ("^i", "ⁱ"),
+    ("^n", "ⁿ"),
+    ("_0", "₀"),
+    ("_1", "₁"),
+    ("_2", "₂"),
+    ("_3", "₃"),
+    ("_4", "₄"),
+    ("_5", "₅"),
+    ("_6", "₆"),
+    ("_7", "₇"),
+    ("_8", "₈"),
+    ("_9", "₉"),
+    ("_+", "₊"),
+    ("_-", "₋"),
+];
+
+impl Editor {
+    /// Shows the characters that the abbreviation before the newest cursor may stand for, such
+    /// as `α` for `\alpha`, in a completion menu. Returns whether there was such an abbreviation.
+    pub(crate) fn show_unicode_completions(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let settings = &EditorSettings::get_global(cx).unicode_input;
+        if !settings.enabled || self.pending_rename.is_some() {
+            return false;
+        }
+
+        let position = self.selections.newest_anchor().head();
+        let Some((buffer, buffer_position)) =
+            self.buffer.read(cx).text_anchor_for_position(position, cx)
+        else {
+            return false;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let offset = buffer_position.to_offset(&snapshot);
+        let mut name = snapshot
+            .reversed_chars_at(offset)
+            .take_while(|character| is_abbreviation_character(*character))
+            .take(MAX_ABBREVIATION_LEN + 1)
+            .collect::<String>();
+        if name.is_empty()
+            || name.len() > MAX_ABBREVIATION_LEN
+            || snapshot.reversed_chars_at(offset - name.len()).next() != Some('\\')
+        {
+            return false;
+        }
+        name = name.chars().rev().collect();
+
+        // The configured abbreviations come first, so that they take precedence over the
+        // built-in ones of the same name.
+        let mut abbreviations = settings
+            .abbreviations
+            .clone()
+            .into_iter()
+            .chain(
+                ABBREVIATIONS
+                    .iter()
+                    .map(|(name, text)| (name.to_string(), text.to_string())),
+            )
+            .filter(|(abbreviation, _)| abbreviation.starts_with(&name))
+            .collect::<Vec<_>>();
+        if let Some(shortcode) = name.strip_prefix(':') {
+            abbreviations.extend(emojis::iter().flat_map(|emoji| {
+                emoji
+                    .shortcodes()
+                    .filter(|candidate| candidate.starts_with(shortcode.trim_end_matches(':')))
+                    .map(|candidate| (format!(":{candidate}:"), emoji.as_str().to_string()))
+                    .collect::<Vec<_>>()
+            }));
+        }
+        if abbreviations.is_empty() {
+            return false;
+        }
+        // Offer the shortest abbreviations first, so that confirming one that's fully typed
+        // inserts its character rather than the one of a longer abbreviation.
+        abbreviations.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        abbreviations.dedup_by(|(a, _), (b, _)| a == b);
+
+        let old_range = snapshot.anchor_before(offset - name.len() - 1)..buffer_position;
+        let completions = abbreviations
+            .into_iter()
+            .map(|(name, text)| {
+                let label = format!("{text} \\{name}");
+                Completion {
+                    old_range: old_range.clone(),
+                    new_text: text,
+                    label: CodeLabel {
+                        filter_range: label.len() - name.len()..label.len(),
+                        text: label,
+                        runs: Vec::new(),
+                    },
+                    server_id: LanguageServerId(usize::MAX),
+                    documentation: None,
+                    lsp_completion: Default::default(),
+                    confirm: None,
+                    show_new_completions_on_confirm: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        let match_candidates = completions
+            .iter()
+            .enumerate()
+            .map(|(id, completion)| {
+                StringMatchCandidate::new(
+                    id,
+                    completion.label.text[completion.label.filter_range.clone()].into(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let matches = match_candidates
+            .iter()
+            .map(|candidate| StringMatch {
+                candidate_id: candidate.id,
+                score: Default::default(),
+                positions: Default::default(),
+                string: candidate.string.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let menu = CompletionsMenu {
+            id: post_inc(&mut self.next_completion_id),
+            initial_position: position,
+            buffer,
+            completions: Arc::new(RwLock::new(completions.into())),
+            match_candidates: match_candidates.into(),
+            matches: matches.into(),
+            selected_item: 0,
+            scroll_handle: UniformListScrollHandle::new(),
+            selected_completion_documentation_resolve_debounce: Arc::new(Mutex::new(
+                DebouncedDelay::new(),
+            )),
+        };
+        *self.context_menu.write() = Some(ContextMenu::Completions(menu));
+        self.discard_inline_completion(false, cx);
+        cx.notify();
+        true
+    }
+}
+
+fn is_abbreviation_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || matches!(character, '_' | '^' | ':' | '+' | '-')
+}
@@ -52,6 +52,8 @@ pub trait Fs: Send + Sync {
         path: &Path,
         content: Pin<&mut (dyn AsyncRead + Send)>,
     ) -> Result<()>;
+    /// Makes a file executable, like `chmod 755`. Does nothing on Windows.
+    async fn set_executable(&self, path: &Path) -> Result<()>;
     async fn extract_tar_file(
         &self,
         path: &Path,
@@ -72,6 +74,9 @@ pub trait Fs: Send + Sync {
         Ok(String::from_utf8(self.load_bytes(path).await?)?)
     }
     async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>>;
+    /// Loads a text file into a [`Rope`] with normalized line endings, reading it in chunks
+    /// rather than holding all of its content in a single string.
+    async fn load_rope(&self, path: &Path) -> Result<(Rope, LineEnding)>;
     async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()>;
     async fn save(&self, path: &Path, text: &Rope, line_ending: LineEnding) -> Result<()>;
     async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
@@ -213,6 +218,17 @@ impl Fs for RealFs {
         Ok(())
     }
 
+    async fn set_executable(&self, path: &Path) -> Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            smol::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).await?;
+        }
+        #[cfg(not(unix))]
+        let _ = path;
+        Ok(())
+    }
+
     async fn extract_tar_file(
         &self,
         path: &Path,
@@ -344,6 +360,11 @@ impl Fs for RealFs {
         Ok(bytes)
     }
 
+    async fn load_rope(&self, path: &Path) -> Result<(Rope, LineEnding)> {
+        let path = path.to_path_buf();
+        smol::unblock(|| read_rope(std::fs::File::open(path)?)).await
+    }
+
     async fn atomic_write(&self, path: PathBuf, data: String) -> Result<()> {
         smol::unblock(move || {
             let mut tmp_file = if cfg!(target_os = "linux") {
@@ -994,6 +1015,20 @@ impl FakeFs {
         })
     }
 
+    pub fn set_branches_for_repo(&self, dot_git: &Path, branches: Vec<git::repository::Branch>) {
+        self.with_git_state(dot_git, true, |state| state.branches = branches)
+    }
+
+    pub fn set_staging_statuses_for_repo(
+        &self,
+        dot_git: &Path,
+        staging_statuses: Vec<git::status::StagingStatus>,
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.staging_statuses = staging_statuses
+        })
+    }
+
     pub fn set_index_for_repo(&self, dot_git: &Path, head_state: &[(&Path, String)]) {
         self.with_git_state(dot_git, true, |state| {
             state.index_contents.clear();
@@ -1005,6 +1040,17 @@ impl FakeFs {
         });
     }
 
+    pub fn set_head_for_repo(&self, dot_git: &Path, head_state: &[(&Path, String)]) {
+        self.with_git_state(dot_git, true, |state| {
+            state.head_contents.clear();
+            state.head_contents.extend(
+                head_state
+                    .iter()
+                    .map(|(path, content)| (path.to_path_buf(), content.clone())),
+            );
+        });
+    }
+
     pub fn set_blame_for_repo(&self, dot_git: &Path, blames: Vec<(&Path, git::blame::Blame)>) {
         self.with_git_state(dot_git, true, |state| {
             state.blames.clear();
@@ -1279,6 +1325,16 @@ impl Fs for FakeFs {
         Ok(())
     }
 
+    async fn set_executable(&self, path: &Path) -> Result<()> {
+        self.simulate_random_delay().await;
+        let path = normalize_path(path);
+        let state = self.state.lock();
+        let entry = state.read_path(&path)?;
+        let entry = entry.lock();
+        entry.file_content(&path)?;
+        Ok(())
+    }
+
     async fn extract_tar_file(
         &self,
         path: &Path,
@@ -1460,6 +1516,11 @@ impl Fs for FakeFs {
         self.load_internal(path).await
     }
 
+    async fn load_rope(&self, path: &Path) -> Result<(Rope, LineEnding)> {
+        let content = self.load_internal(path).await?;
+        read_rope(io::Cursor::new(content))
+    }
+
     async fn atomic_write(&self, path: PathBuf, data: String) -> Result<()> {
         self.simulate_random_delay().await;
         let path = normalize_path(path.as_path());
@@ -1645,6 +1706,48 @@ fn chunks(rope: &Rope, line_ending: LineEnding) -> impl Iterator<Item = &str> {
     })
 }
 
+const LOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Reads UTF-8 text into a [`Rope`] one chunk at a time, normalizing its line endings.
+fn read_rope(mut reader: impl io::Read) -> Result<(Rope, LineEnding)> {
+    let mut rope = Rope::new();
+    let mut line_ending = None;
+    let mut chunk = vec![0; LOAD_CHUNK_SIZE];
+    let mut pending = Vec::with_capacity(LOAD_CHUNK_SIZE);
+    loop {
+        let read_len = match reader.read(&mut chunk) {
+            Ok(read_len) => read_len,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+        let is_eof = read_len == 0;
+        pending.extend_from_slice(&chunk[..read_len]);
+
+        // Keep a character split across chunks, as well as a trailing `\r` that may be
+        // followed by a `\n`, for the next chunk.
+        let mut text_len = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            Err(error) if error.error_len().is_none() && !is_eof => error.valid_up_to(),
+            Err(error) => return Err(error.into()),
+        };
+        if !is_eof && pending[..text_len].ends_with(b"\r") {
+            text_len -= 1;
+        }
+
+        if text_len > 0 {
+            let mut text = std::str::from_utf8(&pending[..text_len])?.to_string();
+            line_ending.get_or_insert_with(|| LineEnding::detect(&text));
+            LineEnding::normalize(&mut text);
+            rope.push(&text);
+            pending.drain(..text_len);
+        }
+        if is_eof {
+            break;
+        }
+    }
+    Ok((rope, line_ending.unwrap_or_default()))
+}
+
 pub fn normalize_path(path: &Path) -> PathBuf {
     let mut components = path.components().peekable();
     let mut ret = if let Some(c @ Component::Prefix(..)) = components.peek().cloned() {
@@ -1811,6 +1914,25 @@ mod tests {
             "D",
         );
     }
+
+    #[test]
+    fn test_read_rope() {
+        // Split a line ending and a multi-byte character across chunks.
+        let text = format!(
+            "{}\r\n{}é\r\nc",
+            "a".repeat(LOAD_CHUNK_SIZE - 1),
+            "b".repeat(LOAD_CHUNK_SIZE - 2)
+        );
+        let (rope, line_ending) = read_rope(io::Cursor::new(text.clone())).unwrap();
+        assert_eq!(line_ending, LineEnding::Windows);
+        assert_eq!(rope.to_string(), text.replace("\r\n", "\n"));
+
+        let (rope, line_ending) = read_rope(io::Cursor::new("one\rtwo\n")).unwrap();
+        assert_eq!(line_ending, LineEnding::Unix);
+        assert_eq!(rope.to_string(), "one\ntwo\n");
+
+        assert!(read_rope(io::Cursor::new(b"\xffinvalid".to_vec())).is_err());
+    }
 }
 
 #[cfg(target_os = "linux")]
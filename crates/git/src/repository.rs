@@ -1,6 +1,9 @@
 use crate::GitHostingProviderRegistry;
-use crate::{blame::Blame, status::GitStatus};
-use anyhow::{Context, Result};
+use crate::{
+    blame::Blame,
+    status::{FileChange, GitStatus, StagingStatus},
+};
+use anyhow::{anyhow, Context, Result};
 use collections::HashMap;
 use git2::BranchType;
 use parking_lot::Mutex;
@@ -8,7 +11,10 @@ use rope::Rope;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
+    ffi::OsStr,
+    io::Write as _,
     path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
     sync::Arc,
 };
 use sum_tree::MapSeekTarget;
@@ -18,10 +24,23 @@ use util::ResultExt;
 pub struct Branch {
     pub is_head: bool,
     pub name: Box<str>,
+    /// Whether this is a remote-tracking branch, such as `origin/main`.
+    pub is_remote: bool,
+    /// The branch that a local branch tracks, if any.
+    pub upstream: Option<Upstream>,
     /// Timestamp of most recent commit, normalized to Unix Epoch format.
     pub unix_timestamp: Option<i64>,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct Upstream {
+    pub name: Box<str>,
+    /// How many commits the local branch has that its upstream doesn't.
+    pub ahead: usize,
+    /// How many commits the upstream has that the local branch doesn't.
+    pub behind: usize,
+}
+
 pub trait GitRepository: Send + Sync {
     fn reload_index(&self);
 
@@ -29,6 +48,13 @@ pub trait GitRepository: Send + Sync {
     /// Note that for symlink entries, this will return the contents of the symlink, not the target.
     fn load_index_text(&self, relative_file_path: &Path) -> Option<String>;
 
+    /// Loads a file's contents in the HEAD commit.
+    fn load_head_text(&self, relative_file_path: &Path) -> Option<String>;
+
+    /// Replaces the contents of a file in the index, leaving the file in the working directory
+    /// untouched. The file must already be in the index.
+    fn set_index_text(&self, relative_file_path: &Path, content: &str) -> Result<()>;
+
     /// Returns the URL of the remote with the given name.
     fn remote_url(&self, name: &str) -> Option<String>;
     fn branch_name(&self) -> Option<String>;
@@ -46,6 +72,18 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, _: &str) -> Result<()>;
     fn create_branch(&self, _: &str) -> Result<()>;
 
+    /// Returns the staged and unstaged changes of the files in the working directory.
+    fn staging_statuses(&self) -> Result<Vec<StagingStatus>>;
+    /// Adds the changes of the given files to the index.
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()>;
+    /// Resets the given files in the index to their state in HEAD.
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()>;
+    /// Commits the staged changes, or replaces the HEAD commit with one that includes them when
+    /// `amend` is true.
+    fn commit(&self, message: &str, amend: bool) -> Result<()>;
+    /// Returns the message of the HEAD commit.
+    fn head_commit_message(&self) -> Option<String>;
+
     fn blame(&self, path: &Path, content: Rope) -> Result<crate::blame::Blame>;
 }
 
@@ -75,6 +113,61 @@ impl RealGitRepository {
     }
 }
 
+impl RealGitRepository {
+    fn working_directory(&self) -> Result<PathBuf> {
+        Ok(self
+            .repository
+            .lock()
+            .workdir()
+            .context("failed to read git work directory")?
+            .to_path_buf())
+    }
+
+    /// Runs the git binary in the working directory, writing `input` to its standard input.
+    fn run_git_command(
+        &self,
+        args: impl IntoIterator<Item = impl AsRef<OsStr>>,
+        input: Option<&str>,
+    ) -> Result<()> {
+        let mut command = Command::new(&self.git_binary_path);
+        command
+            .current_dir(self.working_directory()?)
+            .args(args)
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| anyhow!("Failed to start git process: {}", e))?;
+        if let Some(input) = input {
+            child
+                .stdin
+                .take()
+                .context("failed to open git standard input")?
+                .write_all(input.as_bytes())?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| anyhow!("Failed to read git output: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git process failed: {}", stderr.trim()));
+        }
+        Ok(())
+    }
+}
+
 // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
 const GIT_MODE_SYMLINK: u32 = 0o120000;
 
@@ -109,6 +202,40 @@ impl GitRepository for RealGitRepository {
         None
     }
 
+    fn load_head_text(&self, relative_file_path: &Path) -> Option<String> {
+        fn logic(repo: &git2::Repository, relative_file_path: &Path) -> Result<Option<String>> {
+            let tree = repo.head()?.peel_to_tree()?;
+            let entry = match tree.get_path(relative_file_path) {
+                Ok(entry) if entry.filemode() != GIT_MODE_SYMLINK as i32 => entry,
+                _ => return Ok(None),
+            };
+
+            let content = entry.to_object(repo)?.peel_to_blob()?.content().to_owned();
+            Ok(Some(String::from_utf8(content)?))
+        }
+
+        match logic(&self.repository.lock(), relative_file_path) {
+            Ok(value) => return value,
+            Err(err) => log::error!("Error loading HEAD text: {:?}", err),
+        }
+        None
+    }
+
+    fn set_index_text(&self, relative_file_path: &Path, content: &str) -> Result<()> {
+        const STAGE_NORMAL: i32 = 0;
+        let repo = self.repository.lock();
+        let mut index = repo.index()?;
+
+        check_path_to_repo_path_errors(relative_file_path)?;
+
+        let entry = index
+            .get_path(relative_file_path, STAGE_NORMAL)
+            .with_context(|| format!("{relative_file_path:?} is not in the index"))?;
+        index.add_frombuffer(&entry, content.as_bytes())?;
+        index.write()?;
+        Ok(())
+    }
+
     fn remote_url(&self, name: &str) -> Option<String> {
         let repo = self.repository.lock();
         let remote = repo.find_remote(name).ok()?;
@@ -138,13 +265,30 @@ impl GitRepository for RealGitRepository {
 
     fn branches(&self) -> Result<Vec<Branch>> {
         let repo = self.repository.lock();
-        let local_branches = repo.branches(Some(BranchType::Local))?;
-        let valid_branches = local_branches
+        let branches = repo.branches(None)?;
+        let valid_branches = branches
             .filter_map(|branch| {
-                branch.ok().and_then(|(branch, _)| {
+                branch.ok().and_then(|(branch, branch_type)| {
                     let is_head = branch.is_head();
                     let name = branch.name().ok().flatten().map(Box::from)?;
-                    let timestamp = branch.get().peel_to_commit().ok()?.time();
+                    // Skip the symbolic references to the default branch of the remotes.
+                    if branch.get().symbolic_target().is_some() {
+                        return None;
+                    }
+                    let commit = branch.get().peel_to_commit().ok()?;
+                    let upstream = branch.upstream().ok().and_then(|upstream| {
+                        let name = upstream.name().ok().flatten().map(Box::from)?;
+                        let upstream_commit = upstream.get().peel_to_commit().ok()?;
+                        let (ahead, behind) = repo
+                            .graph_ahead_behind(commit.id(), upstream_commit.id())
+                            .ok()?;
+                        Some(Upstream {
+                            name,
+                            ahead,
+                            behind,
+                        })
+                    });
+                    let timestamp = commit.time();
                     let unix_timestamp = timestamp.seconds();
                     let timezone_offset = timestamp.offset_minutes();
                     let utc_offset =
@@ -154,6 +298,8 @@ impl GitRepository for RealGitRepository {
                     Some(Branch {
                         is_head,
                         name,
+                        is_remote: branch_type == BranchType::Remote,
+                        upstream,
                         unix_timestamp: Some(unix_timestamp.to_offset(utc_offset).unix_timestamp()),
                     })
                 })
@@ -164,7 +310,28 @@ impl GitRepository for RealGitRepository {
 
     fn change_branch(&self, name: &str) -> Result<()> {
         let repo = self.repository.lock();
-        let revision = repo.find_branch(name, BranchType::Local)?;
+        let revision = match repo.find_branch(name, BranchType::Local) {
+            Ok(branch) => branch,
+            // Checking out a remote branch checks out a local branch that tracks it, creating it
+            // if needed.
+            Err(error) => {
+                let remote_branch = repo
+                    .find_branch(name, BranchType::Remote)
+                    .map_err(|_| error)?;
+                let (_, local_name) = name
+                    .split_once('/')
+                    .ok_or_else(|| anyhow::anyhow!("invalid remote branch name {name}"))?;
+                match repo.find_branch(local_name, BranchType::Local) {
+                    Ok(branch) => branch,
+                    Err(_) => {
+                        let commit = remote_branch.get().peel_to_commit()?;
+                        let mut branch = repo.branch(local_name, &commit, false)?;
+                        branch.set_upstream(Some(name))?;
+                        branch
+                    }
+                }
+            }
+        };
         let revision = revision.get();
         let as_tree = revision.peel_to_tree()?;
         repo.checkout_tree(as_tree.as_object(), None)?;
@@ -183,6 +350,46 @@ impl GitRepository for RealGitRepository {
         Ok(())
     }
 
+    fn staging_statuses(&self) -> Result<Vec<StagingStatus>> {
+        StagingStatus::list(&self.git_binary_path, &self.working_directory()?)
+    }
+
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let args = ["add", "--all", "--"].into_iter().map(OsStr::new);
+        self.run_git_command(
+            args.chain(paths.iter().map(|path| path.0.as_os_str())),
+            None,
+        )
+    }
+
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let args = ["reset", "--quiet", "--"].into_iter().map(OsStr::new);
+        self.run_git_command(
+            args.chain(paths.iter().map(|path| path.0.as_os_str())),
+            None,
+        )
+    }
+
+    fn commit(&self, message: &str, amend: bool) -> Result<()> {
+        let mut args = vec!["commit", "--quiet", "--file=-"];
+        if amend {
+            args.push("--amend");
+        }
+        self.run_git_command(args, Some(message))
+    }
+
+    fn head_commit_message(&self) -> Option<String> {
+        let repo = self.repository.lock();
+        let commit = repo.head().ok()?.peel_to_commit().ok()?;
+        commit.message().map(ToString::to_string)
+    }
+
     fn blame(&self, path: &Path, content: Rope) -> Result<crate::blame::Blame> {
         let working_directory = self
             .repository
@@ -213,9 +420,14 @@ pub struct FakeGitRepository {
 #[derive(Debug, Clone, Default)]
 pub struct FakeGitRepositoryState {
     pub index_contents: HashMap<PathBuf, String>,
+    pub head_contents: HashMap<PathBuf, String>,
     pub blames: HashMap<PathBuf, Blame>,
     pub worktree_statuses: HashMap<RepoPath, GitFileStatus>,
     pub branch_name: Option<String>,
+    pub branches: Vec<Branch>,
+    pub staging_statuses: Vec<StagingStatus>,
+    /// The messages of the commits made in the repository, the last one being HEAD's.
+    pub commit_messages: Vec<String>,
 }
 
 impl FakeGitRepository {
@@ -232,6 +444,21 @@ impl GitRepository for FakeGitRepository {
         state.index_contents.get(path).cloned()
     }
 
+    fn load_head_text(&self, path: &Path) -> Option<String> {
+        let state = self.state.lock();
+        state.head_contents.get(path).cloned()
+    }
+
+    fn set_index_text(&self, path: &Path, content: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        let index_text = state
+            .index_contents
+            .get_mut(path)
+            .with_context(|| format!("{path:?} is not in the index"))?;
+        *index_text = content.to_string();
+        Ok(())
+    }
+
     fn remote_url(&self, _name: &str) -> Option<String> {
         None
     }
@@ -265,7 +492,15 @@ impl GitRepository for FakeGitRepository {
     }
 
     fn branches(&self) -> Result<Vec<Branch>> {
-        Ok(vec![])
+        let state = self.state.lock();
+        Ok(state
+            .branches
+            .iter()
+            .map(|branch| Branch {
+                is_head: state.branch_name.as_deref() == Some(&*branch.name),
+                ..branch.clone()
+            })
+            .collect())
     }
 
     fn change_branch(&self, name: &str) -> Result<()> {
@@ -276,10 +511,83 @@ impl GitRepository for FakeGitRepository {
 
     fn create_branch(&self, name: &str) -> Result<()> {
         let mut state = self.state.lock();
-        state.branch_name = Some(name.to_owned());
+        if state.branches.iter().any(|branch| &*branch.name == name) {
+            anyhow::bail!("a branch named '{name}' already exists");
+        }
+        state.branches.push(Branch {
+            is_head: false,
+            name: name.into(),
+            is_remote: false,
+            upstream: None,
+            unix_timestamp: None,
+        });
+        Ok(())
+    }
+
+    fn staging_statuses(&self) -> Result<Vec<StagingStatus>> {
+        let state = self.state.lock();
+        Ok(state.staging_statuses.clone())
+    }
+
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        let mut state = self.state.lock();
+        for status in &mut state.staging_statuses {
+            if paths.contains(&status.repo_path) {
+                if let Some(change) = status.unstaged.take() {
+                    status.staged = Some(match change {
+                        FileChange::Untracked => FileChange::Added,
+                        change => change,
+                    });
+                }
+            }
+        }
         Ok(())
     }
 
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        let mut state = self.state.lock();
+        for status in &mut state.staging_statuses {
+            if paths.contains(&status.repo_path) {
+                if let Some(change) = status.staged.take() {
+                    status.unstaged = Some(match change {
+                        FileChange::Added => FileChange::Untracked,
+                        change => change,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn commit(&self, message: &str, amend: bool) -> Result<()> {
+        let mut state = self.state.lock();
+        let has_staged_changes = state
+            .staging_statuses
+            .iter()
+            .any(|status| status.staged.is_some());
+        if amend {
+            state
+                .commit_messages
+                .pop()
+                .context("there is no commit to amend")?;
+        } else if !has_staged_changes {
+            anyhow::bail!("nothing to commit");
+        }
+        for status in &mut state.staging_statuses {
+            status.staged = None;
+        }
+        state
+            .staging_statuses
+            .retain(|status| status.unstaged.is_some());
+        state.commit_messages.push(message.to_string());
+        Ok(())
+    }
+
+    fn head_commit_message(&self) -> Option<String> {
+        let state = self.state.lock();
+        state.commit_messages.last().cloned()
+    }
+
     fn blame(&self, path: &Path, _content: Rope) -> Result<crate::blame::Blame> {
         let state = self.state.lock();
         state
@@ -0,0 +1,232 @@
+//! Stages and unstages the changes on some lines of a file, as `git add -p` and `git reset -p`
+//! do, by computing the new contents of the file in the index.
+
+use std::ops::Range;
+
+use crate::libgit::{DiffOptions as GitOptions, Patch as GitPatch};
+
+/// A change between two versions of a text, as the rows that it replaces in each of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RowChange {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+}
+
+/// Returns the index text with the changes of the working copy that touch the given rows of the
+/// working copy staged.
+pub fn stage_rows(index_text: &str, working_text: &str, rows: &[Range<u32>]) -> String {
+    let changes = row_changes(index_text, working_text)
+        .into_iter()
+        .filter(|change| {
+            rows.iter()
+                .any(|rows| rows_intersect(&change.new_rows, rows))
+        })
+        .collect::<Vec<_>>();
+    apply_row_changes(index_text, working_text, &changes)
+}
+
+/// Returns the index text with the staged changes that touch the given rows of the working copy
+/// reset to their version in HEAD.
+pub fn unstage_rows(
+    head_text: &str,
+    index_text: &str,
+    working_text: &str,
+    rows: &[Range<u32>],
+) -> String {
+    let unstaged_changes = row_changes(index_text, working_text);
+    let index_rows = rows
+        .iter()
+        .map(|rows| old_rows_for_new_rows(&unstaged_changes, rows))
+        .collect::<Vec<_>>();
+    let changes = row_changes(index_text, head_text)
+        .into_iter()
+        .filter(|change| {
+            index_rows
+                .iter()
+                .any(|rows| rows_intersect(&change.old_rows, rows))
+        })
+        .collect::<Vec<_>>();
+    apply_row_changes(index_text, head_text, &changes)
+}
+
+fn row_changes(old_text: &str, new_text: &str) -> Vec<RowChange> {
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let patch = match GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    ) {
+        Ok(patch) => patch,
+        Err(err) => {
+            log::error!("`GitPatch::from_buffers` failed: {}", err);
+            return Vec::new();
+        }
+    };
+
+    // Hunks without lines on one side start after the row they are inserted at, and the others
+    // start at their first row, counting from 1.
+    let rows = |start: u32, lines: u32| {
+        if lines == 0 {
+            start..start
+        } else {
+            start - 1..start - 1 + lines
+        }
+    };
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| {
+            let (hunk, _) = patch.hunk(hunk_index).ok()?;
+            Some(RowChange {
+                old_rows: rows(hunk.old_start(), hunk.old_lines()),
+                new_rows: rows(hunk.new_start(), hunk.new_lines()),
+            })
+        })
+        .collect()
+}
+
+/// Returns `old_text` with the given changes, sorted by position, replaced by their rows of
+/// `new_text`.
+fn apply_row_changes(old_text: &str, new_text: &str, changes: &[RowChange]) -> String {
+    let old_lines = old_text.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new_text.split_inclusive('\n').collect::<Vec<_>>();
+    let lines = |lines: &[&str], rows: Range<u32>| {
+        let end = (rows.end as usize).min(lines.len());
+        let start = (rows.start as usize).min(end);
+        lines[start..end].concat()
+    };
+
+    let mut text = String::with_capacity(old_text.len());
+    let mut row = 0;
+    for change in changes {
+        text.push_str(&lines(&old_lines, row..change.old_rows.start));
+        text.push_str(&lines(&new_lines, change.new_rows.clone()));
+        row = change.old_rows.end;
+    }
+    text.push_str(&lines(&old_lines, row..old_lines.len() as u32));
+    text
+}
+
+/// Converts rows of the new text to the rows of the old text they correspond to, including the
+/// whole changes that they touch.
+fn old_rows_for_new_rows(changes: &[RowChange], rows: &Range<u32>) -> Range<u32> {
+    let shift = |row: u32, change: &RowChange| {
+        (row as i64 + change.old_rows.end as i64 - change.new_rows.end as i64) as u32
+    };
+
+    let mut start = rows.start;
+    for change in changes {
+        if change.new_rows.start > rows.start {
+            break;
+        }
+        if rows.start < change.new_rows.end || change.new_rows.start == rows.start {
+            start = change.old_rows.start;
+            break;
+        }
+        start = shift(rows.start, change);
+    }
+
+    let mut end = rows.end;
+    for change in changes {
+        if change.new_rows.start > rows.end
+            || (change.new_rows.start == rows.end && !change.new_rows.is_empty())
+        {
+            break;
+        }
+        if rows.end < change.new_rows.end {
+            end = change.old_rows.end;
+            break;
+        }
+        end = shift(rows.end, change);
+    }
+
+    start..end
+}
+
+/// Whether a change touches the given rows. Changes that are only an insertion point touch the
+/// rows right above and below them too.
+fn rows_intersect(change_rows: &Range<u32>, rows: &Range<u32>) -> bool {
+    if change_rows.is_empty() || rows.is_empty() {
+        change_rows.start <= rows.end && rows.start <= change_rows.end
+    } else {
+        change_rows.start < rows.end && rows.start < change_rows.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unindent::Unindent as _;
+
+    #[test]
+    fn test_stage_and_unstage_rows() {
+        let head_text = "
+            one
+            two
+            three
+            four
+            five
+        "
+        .unindent();
+        let working_text = "
+            one
+            TWO
+            three
+            five
+            six
+        "
+        .unindent();
+
+        // Stage the modification of the second row, but not the removal and the addition.
+        let index_text = stage_rows(&head_text, &working_text, &[1..2]);
+        assert_eq!(
+            index_text,
+            "
+            one
+            TWO
+            three
+            four
+            five
+            "
+            .unindent()
+        );
+
+        // The removal is staged from the row right after it.
+        let index_text = stage_rows(&index_text, &working_text, &[3..4]);
+        assert_eq!(
+            index_text,
+            "
+            one
+            TWO
+            three
+            five
+            "
+            .unindent()
+        );
+
+        // Unstaging rows without staged changes keeps the index as is.
+        assert_eq!(
+            unstage_rows(&head_text, &index_text, &working_text, &[4..5]),
+            index_text
+        );
+
+        // The rows of the working copy are mapped to the ones of the index, even when there
+        // are unstaged changes above them.
+        let working_text = format!("zero\n{working_text}");
+        assert_eq!(
+            unstage_rows(&head_text, &index_text, &working_text, &[2..3]),
+            "
+            one
+            two
+            three
+            five
+            "
+            .unindent()
+        );
+        assert_eq!(
+            unstage_rows(&head_text, &index_text, &working_text, &[0..6]),
+            head_text
+        );
+    }
+}
@@ -11,52 +11,146 @@ pub struct GitStatus {
     pub entries: Arc<[(RepoPath, GitFileStatus)]>,
 }
 
+/// A change of a file, either in the index relative to HEAD or in the working tree relative to
+/// the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FileChange {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Untracked,
+    Conflict,
+}
+
+impl FileChange {
+    fn from_status_code(code: char) -> Option<Self> {
+        match code {
+            'M' | 'T' => Some(Self::Modified),
+            'A' | 'C' => Some(Self::Added),
+            'D' => Some(Self::Deleted),
+            'R' => Some(Self::Renamed),
+            _ => None,
+        }
+    }
+}
+
+/// The staged and unstaged changes of a file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StagingStatus {
+    pub repo_path: RepoPath,
+    /// The change recorded in the index, relative to HEAD.
+    pub staged: Option<FileChange>,
+    /// The change in the working tree, relative to the index.
+    pub unstaged: Option<FileChange>,
+}
+
+impl StagingStatus {
+    pub(crate) fn list(git_binary: &Path, working_directory: &Path) -> Result<Vec<Self>> {
+        let output = run_status(git_binary, working_directory, Path::new("."))?;
+        Ok(Self::parse(&output))
+    }
+
+    /// Parses the output of `git status --porcelain=v1 -z`.
+    fn parse(output: &str) -> Vec<Self> {
+        let mut entries = Vec::new();
+        let mut fields = output.split('\0');
+        while let Some(entry) = fields.next() {
+            if entry.len() < 4 || !entry.is_char_boundary(3) {
+                continue;
+            }
+            let (status, path) = entry.split_at(3);
+            let mut codes = status.chars();
+            let (Some(index), Some(worktree)) = (codes.next(), codes.next()) else {
+                continue;
+            };
+            // Renamed and copied files are followed by their original path.
+            if matches!(index, 'R' | 'C') || matches!(worktree, 'R' | 'C') {
+                fields.next();
+            }
+
+            let is_conflict = index == 'U'
+                || worktree == 'U'
+                || (index, worktree) == ('A', 'A')
+                || (index, worktree) == ('D', 'D');
+            let (staged, unstaged) = if is_conflict {
+                (None, Some(FileChange::Conflict))
+            } else if (index, worktree) == ('?', '?') {
+                (None, Some(FileChange::Untracked))
+            } else {
+                (
+                    FileChange::from_status_code(index),
+                    FileChange::from_status_code(worktree),
+                )
+            };
+            if staged.is_some() || unstaged.is_some() {
+                entries.push(Self {
+                    repo_path: RepoPath(PathBuf::from(path)),
+                    staged,
+                    unstaged,
+                });
+            }
+        }
+        entries.sort_unstable_by(|a, b| a.repo_path.cmp(&b.repo_path));
+        entries
+    }
+
+    /// Whether all the changes of the file are staged.
+    pub fn is_staged(&self) -> bool {
+        self.staged.is_some() && self.unstaged.is_none()
+    }
+}
+
+fn run_status(git_binary: &Path, working_directory: &Path, path_prefix: &Path) -> Result<String> {
+    let mut child = Command::new(git_binary);
+
+    child
+        .current_dir(working_directory)
+        .args([
+            "--no-optional-locks",
+            "status",
+            "--porcelain=v1",
+            "--untracked-files=all",
+            "-z",
+        ])
+        .arg(path_prefix)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        child.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+    }
+
+    let child = child
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start git status process: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| anyhow!("Failed to read git blame output: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git status process failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 impl GitStatus {
     pub(crate) fn new(
         git_binary: &Path,
         working_directory: &Path,
         mut path_prefix: &Path,
     ) -> Result<Self> {
-        let mut child = Command::new(git_binary);
-
         if path_prefix == Path::new("") {
             path_prefix = Path::new(".");
         }
 
-        child
-            .current_dir(working_directory)
-            .args([
-                "--no-optional-locks",
-                "status",
-                "--porcelain=v1",
-                "--untracked-files=all",
-                "-z",
-            ])
-            .arg(path_prefix)
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        #[cfg(windows)]
-        {
-            use std::os::windows::process::CommandExt;
-            child.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
-        }
-
-        let child = child
-            .spawn()
-            .map_err(|e| anyhow!("Failed to start git status process: {}", e))?;
-
-        let output = child
-            .wait_with_output()
-            .map_err(|e| anyhow!("Failed to read git blame output: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("git status process failed: {}", stderr));
-        }
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = run_status(git_binary, working_directory, path_prefix)?;
         let mut entries = stdout
             .split('\0')
             .filter_map(|entry| {
@@ -97,3 +191,45 @@ impl Default for GitStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_staging_statuses() {
+        let output = [
+            "M  staged.rs",
+            " M unstaged.rs",
+            "MM both.rs",
+            "R  new.rs",
+            "old.rs",
+            "D  removed.rs",
+            "UU conflict.rs",
+            "?? untracked.rs",
+            "",
+        ]
+        .join("\0");
+        let status = |path: &str, staged, unstaged| StagingStatus {
+            repo_path: RepoPath(PathBuf::from(path)),
+            staged,
+            unstaged,
+        };
+        assert_eq!(
+            StagingStatus::parse(&output),
+            [
+                status(
+                    "both.rs",
+                    Some(FileChange::Modified),
+                    Some(FileChange::Modified)
+                ),
+                status("conflict.rs", None, Some(FileChange::Conflict)),
+                status("new.rs", Some(FileChange::Renamed), None),
+                status("removed.rs", Some(FileChange::Deleted), None),
+                status("staged.rs", Some(FileChange::Modified), None),
+                status("unstaged.rs", None, Some(FileChange::Modified)),
+                status("untracked.rs", None, Some(FileChange::Untracked)),
+            ]
+        );
+    }
+}
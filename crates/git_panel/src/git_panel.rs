@@ -0,0 +1,624 @@
+mod git_panel_settings;
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, EditorEvent};
+use git::{
+    repository::RepoPath,
+    status::{FileChange, StagingStatus},
+};
+use git_panel_settings::{GitPanelDockPosition, GitPanelSettings};
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, KeyContext, Model, Pixels, Render, Subscription, Task, UniformListScrollHandle,
+    View, ViewContext, WeakView,
+};
+use language::language_settings::WrapGuide;
+use menu::{Confirm, SelectNext, SelectPrev};
+use project::{Project, ProjectPath};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, Checkbox, ListItem, ListItemSpacing, Selection, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
+    Toast, Workspace,
+};
+
+actions!(
+    git_panel,
+    [
+        ToggleFocus,
+        ToggleStaged,
+        StageAll,
+        UnstageAll,
+        Commit,
+        Amend
+    ]
+);
+
+const GIT_PANEL_KEY: &str = "GitPanel";
+
+/// A panel listing the changes of the repository at the root of the project, to stage and
+/// unstage them and commit the staged ones.
+pub struct GitPanel {
+    workspace: WeakView<Workspace>,
+    project: Model<Project>,
+    fs: Arc<dyn project::Fs>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    commit_editor: View<Editor>,
+    entries: Vec<StagingStatus>,
+    /// Whether the project has a repository at its root, as of the last refresh.
+    has_repository: bool,
+    selected_ix: Option<usize>,
+    pending_serialization: Task<Option<()>>,
+    pending_refresh: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedGitPanel {
+    width: Option<Pixels>,
+}
+
+pub fn init(cx: &mut AppContext) {
+    GitPanelSettings::register(cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<GitPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+impl GitPanel {
+    pub async fn load(
+        workspace: WeakView<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<View<Self>> {
+        let serialized_panel = cx
+            .background_executor()
+            .spawn(async move { KEY_VALUE_STORE.read_kvp(GIT_PANEL_KEY) })
+            .await
+            .context("loading git panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedGitPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| Self::new(workspace, cx));
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let commit_editor = cx.new_view(|cx| {
+            let mut editor = Editor::multi_line(cx);
+            editor.set_placeholder_text("Commit message", cx);
+            editor.set_show_gutter(false, cx);
+            editor.set_wrap_guides(commit_message_rulers(cx), cx);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::WorktreeUpdatedGitRepositories
+                | project::Event::WorktreeUpdatedEntries(..) => this.refresh(cx),
+                _ => {}
+            }),
+            cx.subscribe(&commit_editor, |_, _, event, cx| {
+                if let EditorEvent::Focused | EditorEvent::Blurred = event {
+                    cx.notify();
+                }
+            }),
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                let rulers = commit_message_rulers(cx);
+                this.commit_editor
+                    .update(cx, |editor, cx| editor.set_wrap_guides(rulers, cx));
+            }),
+        ];
+
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            project,
+            fs: workspace.app_state().fs.clone(),
+            width: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            commit_editor,
+            entries: Vec::new(),
+            has_repository: false,
+            selected_ix: None,
+            pending_serialization: Task::ready(None),
+            pending_refresh: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        let statuses = self.project.read(cx).staging_statuses(cx);
+        self.pending_refresh = cx.spawn(|this, mut cx| async move {
+            let statuses = statuses.await;
+            this.update(&mut cx, |this, cx| {
+                this.has_repository = statuses.is_ok();
+                let entries = statuses.unwrap_or_default();
+                this.selected_ix = this
+                    .selected_ix
+                    .filter(|_| !entries.is_empty())
+                    .map(|ix| ix.min(entries.len() - 1));
+                this.entries = entries;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        GIT_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedGitPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn dispatch_context(&self, cx: &ViewContext<Self>) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("GitPanel");
+        dispatch_context.add("menu");
+        let identifier = if self.commit_editor.focus_handle(cx).is_focused(cx) {
+            "editing"
+        } else {
+            "not_editing"
+        };
+        dispatch_context.add(identifier);
+        dispatch_context
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_ix
+            .map_or(0, |ix| (ix + 1) % self.entries.len());
+        self.select(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_ix.map_or(self.entries.len() - 1, |ix| {
+            ix.checked_sub(1).unwrap_or(self.entries.len() - 1)
+        });
+        self.select(ix, cx);
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        self.selected_ix = Some(ix);
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.open_entry(ix, cx);
+        }
+    }
+
+    fn toggle_staged(&mut self, _: &ToggleStaged, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.toggle_entry_staged(ix, cx);
+        }
+    }
+
+    fn stage_all(&mut self, _: &StageAll, cx: &mut ViewContext<Self>) {
+        let paths = self
+            .entries
+            .iter()
+            .filter(|entry| entry.unstaged.is_some())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        self.update_staging(paths, true, cx);
+    }
+
+    fn unstage_all(&mut self, _: &UnstageAll, cx: &mut ViewContext<Self>) {
+        let paths = self
+            .entries
+            .iter()
+            .filter(|entry| entry.staged.is_some())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        self.update_staging(paths, false, cx);
+    }
+
+    fn commit(&mut self, _: &Commit, cx: &mut ViewContext<Self>) {
+        self.commit_changes(false, cx);
+    }
+
+    fn amend(&mut self, _: &Amend, cx: &mut ViewContext<Self>) {
+        self.commit_changes(true, cx);
+    }
+
+    /// Stages the changes of the entry when some of them are unstaged, and unstages them
+    /// otherwise.
+    fn toggle_entry_staged(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let stage = !entry.is_staged();
+        self.update_staging(vec![entry.repo_path.clone()], stage, cx);
+    }
+
+    fn update_staging(&mut self, paths: Vec<RepoPath>, stage: bool, cx: &mut ViewContext<Self>) {
+        if paths.is_empty() {
+            return;
+        }
+        let project = self.project.read(cx);
+        let task = if stage {
+            project.stage_paths(paths, cx)
+        } else {
+            project.unstage_paths(paths, cx)
+        };
+        cx.spawn(|this, mut cx| async move {
+            let result = task.await;
+            this.update(&mut cx, |this, cx| {
+                if let Err(error) = &result {
+                    this.show_error(error.to_string(), cx);
+                }
+                this.refresh(cx);
+            })?;
+            result
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Commits the staged changes with the message of the commit editor. Amending with an
+    /// empty message fills the editor with the message of the commit to amend first, so that
+    /// it can be edited before amending again.
+    fn commit_changes(&mut self, amend: bool, cx: &mut ViewContext<Self>) {
+        let message = self.commit_editor.read(cx).text(cx);
+        let message = message.trim();
+        if message.is_empty() {
+            if amend {
+                self.load_head_commit_message(cx);
+            } else {
+                self.show_error("Write a commit message first".to_string(), cx);
+                self.commit_editor.focus_handle(cx).focus(cx);
+            }
+            return;
+        }
+
+        let commit = self.project.read(cx).commit(message.to_string(), amend, cx);
+        cx.spawn(|this, mut cx| async move {
+            let result = commit.await;
+            this.update(&mut cx, |this, cx| {
+                match &result {
+                    Ok(()) => this.commit_editor.update(cx, |editor, cx| editor.clear(cx)),
+                    Err(error) => this.show_error(error.to_string(), cx),
+                }
+                this.refresh(cx);
+            })?;
+            result
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn load_head_commit_message(&mut self, cx: &mut ViewContext<Self>) {
+        let message = self.project.read(cx).head_commit_message(cx);
+        cx.spawn(|this, mut cx| async move {
+            let Some(message) = message.await? else {
+                this.update(&mut cx, |this, cx| {
+                    this.show_error("There is no commit to amend".to_string(), cx)
+                })?;
+                return Ok(());
+            };
+            this.update(&mut cx, |this, cx| {
+                this.commit_editor.update(cx, |editor, cx| {
+                    editor.set_text(message.trim_end(), cx);
+                    editor.focus(cx);
+                });
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn show_error(&self, message: String, cx: &mut ViewContext<Self>) {
+        struct GitPanelError;
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<GitPanelError>(), message),
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn open_entry(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        // Deleted files have nothing to open.
+        if entry.unstaged.or(entry.staged) == Some(FileChange::Deleted) {
+            return;
+        }
+        let Some(worktree) = self.project.read(cx).visible_worktrees(cx).next() else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: entry.repo_path.0.as_path().into(),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_path(project_path, None, true, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let selection = if entry.is_staged() {
+            Selection::Selected
+        } else if entry.staged.is_some() {
+            Selection::Indeterminate
+        } else {
+            Selection::Unselected
+        };
+        let (change, color) = match entry.unstaged.or(entry.staged) {
+            Some(FileChange::Added) => ("A", Color::Created),
+            Some(FileChange::Untracked) => ("U", Color::Created),
+            Some(FileChange::Deleted) => ("D", Color::Deleted),
+            Some(FileChange::Renamed) => ("R", Color::Modified),
+            Some(FileChange::Conflict) => ("C", Color::Conflict),
+            Some(FileChange::Modified) | None => ("M", Color::Modified),
+        };
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .selected(self.selected_ix == Some(ix))
+            .start_slot(
+                Checkbox::new(("stage", ix), selection).on_click(cx.listener(
+                    move |this, _: &Selection, cx| {
+                        this.selected_ix = Some(ix);
+                        this.toggle_entry_staged(ix, cx);
+                    },
+                )),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_between()
+                    .child(Label::new(entry.repo_path.to_string_lossy().to_string()).single_line())
+                    .child(Label::new(change).size(LabelSize::Small).color(color)),
+            )
+            .on_click(cx.listener(move |this, _, cx| {
+                this.selected_ix = Some(ix);
+                this.open_entry(ix, cx);
+            }))
+    }
+
+    fn render_commit_area(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let has_staged_changes = self.entries.iter().any(|entry| entry.staged.is_some());
+
+        v_flex()
+            .p_2()
+            .gap_2()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                div()
+                    .key_context("CommitMessage")
+                    .h(rems(8.))
+                    .p_1()
+                    .border_1()
+                    .rounded_md()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.commit_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_1()
+                    .child(
+                        Button::new("amend", "Amend")
+                            .tooltip(|cx| Tooltip::for_action("Amend the last commit", &Amend, cx))
+                            .on_click(cx.listener(|this, _, cx| this.amend(&Amend, cx))),
+                    )
+                    .child(
+                        Button::new("commit", "Commit")
+                            .style(ButtonStyle::Filled)
+                            .disabled(!has_staged_changes)
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Commit the staged changes", &Commit, cx)
+                            })
+                            .on_click(cx.listener(|this, _, cx| this.commit(&Commit, cx))),
+                    ),
+            )
+    }
+}
+
+fn commit_message_rulers(cx: &AppContext) -> Vec<WrapGuide> {
+    GitPanelSettings::get_global(cx)
+        .commit_message_rulers
+        .iter()
+        .map(|column| WrapGuide::Column(*column))
+        .collect()
+}
+
+impl Panel for GitPanel {
+    fn persistent_name() -> &'static str {
+        "Git Panel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        match GitPanelSettings::get_global(cx).dock {
+            GitPanelDockPosition::Left => DockPosition::Left,
+            GitPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<GitPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => GitPanelDockPosition::Left,
+                    DockPosition::Right => GitPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| GitPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        GitPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::FileGit)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Git Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+impl FocusableView for GitPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for GitPanel {}
+
+impl Render for GitPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let git_panel = v_flex()
+            .id("git-panel")
+            .size_full()
+            .key_context(self.dispatch_context(cx))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_staged))
+            .on_action(cx.listener(Self::stage_all))
+            .on_action(cx.listener(Self::unstage_all))
+            .on_action(cx.listener(Self::commit))
+            .on_action(cx.listener(Self::amend))
+            .track_focus(&self.focus_handle);
+
+        if !self.has_repository {
+            return git_panel.child(
+                v_flex().justify_center().size_full().child(
+                    h_flex()
+                        .justify_center()
+                        .child(Label::new("No git repository at the root of the project")),
+                ),
+            );
+        }
+
+        let count = self.entries.len();
+        let noun = if count == 1 { "change" } else { "changes" };
+        let header = h_flex()
+            .justify_between()
+            .px_2()
+            .py_1()
+            .child(
+                Label::new(format!("{count} {noun}"))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("unstage-all", "Unstage All")
+                            .label_size(LabelSize::Small)
+                            .disabled(!self.entries.iter().any(|entry| entry.staged.is_some()))
+                            .on_click(cx.listener(|this, _, cx| this.unstage_all(&UnstageAll, cx))),
+                    )
+                    .child(
+                        Button::new("stage-all", "Stage All")
+                            .label_size(LabelSize::Small)
+                            .disabled(!self.entries.iter().any(|entry| entry.unstaged.is_some()))
+                            .on_click(cx.listener(|this, _, cx| this.stage_all(&StageAll, cx))),
+                    ),
+            );
+
+        let entries = if self.entries.is_empty() {
+            v_flex()
+                .justify_center()
+                .flex_1()
+                .child(h_flex().justify_center().child(Label::new("No changes")))
+                .into_any_element()
+        } else {
+            uniform_list(
+                cx.view().clone(),
+                "git-panel-entries",
+                self.entries.len(),
+                |git_panel, range, cx| {
+                    range
+                        .map(|ix| git_panel.render_entry(ix, cx))
+                        .collect::<Vec<_>>()
+                },
+            )
+            .flex_1()
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        };
+
+        git_panel
+            .child(header)
+            .child(entries)
+            .child(self.render_commit_area(cx))
+    }
+}
@@ -178,6 +178,38 @@ pub enum KeyBindingContextPredicate {
     ),
 }
 
+impl fmt::Display for KeyBindingContextPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Identifier(name) => write!(f, "{}", name),
+            Self::Equal(left, right) => write!(f, "{} == {}", left, right),
+            Self::NotEqual(left, right) => write!(f, "{} != {}", left, right),
+            Self::Not(predicate) => match predicate.as_ref() {
+                Self::Identifier(_) | Self::Not(_) => write!(f, "!{}", predicate),
+                _ => write!(f, "!({})", predicate),
+            },
+            Self::Child(parent, child) => write!(f, "{} > {}", Operand(parent), Operand(child)),
+            Self::And(left, right) => write!(f, "{} && {}", Operand(left), Operand(right)),
+            Self::Or(left, right) => write!(f, "{} || {}", Operand(left), Operand(right)),
+        }
+    }
+}
+
+/// Formats an operand of a binary operator, wrapping it in parentheses when it's another
+/// binary operator so that it parses back into the same predicate.
+struct Operand<'a>(&'a KeyBindingContextPredicate);
+
+impl fmt::Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            KeyBindingContextPredicate::Child(_, _)
+            | KeyBindingContextPredicate::And(_, _)
+            | KeyBindingContextPredicate::Or(_, _) => write!(f, "({})", self.0),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
 impl KeyBindingContextPredicate {
     /// Parse a string in the same format as the keymap's context field.
     ///
@@ -531,4 +563,23 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_display_predicates() {
+        for source in [
+            "Editor",
+            "mode == full",
+            "!Editor",
+            "Editor && mode == full",
+            "Workspace > (Editor || Terminal)",
+            "!(vim_mode == normal) && (a > b)",
+        ] {
+            let predicate = KeyBindingContextPredicate::parse(source).unwrap();
+            assert_eq!(predicate.to_string(), source);
+            assert_eq!(
+                KeyBindingContextPredicate::parse(&predicate.to_string()).unwrap(),
+                predicate
+            );
+        }
+    }
 }
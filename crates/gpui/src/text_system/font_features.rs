@@ -17,11 +17,30 @@ impl FontFeatures {
     ///
     /// Returns `None` if the feature is not present.
     pub fn is_calt_enabled(&self) -> Option<bool> {
+        self.is_enabled("calt")
+    }
+
+    /// Returns whether the feature with the given tag is enabled.
+    ///
+    /// Returns `None` if the feature is not present.
+    pub fn is_enabled(&self, tag: &str) -> Option<bool> {
         self.0
             .iter()
-            .find(|(feature, _)| feature == "calt")
+            .find(|(feature, _)| feature == tag)
             .map(|(_, value)| *value == 1)
     }
+
+    /// Returns these features with the feature with the given tag enabled or disabled.
+    pub fn with_feature(&self, tag: &str, enabled: bool) -> Self {
+        let value = if enabled { 1 } else { 0 };
+        let mut features = self.0.as_ref().clone();
+        if let Some(index) = features.iter().position(|(feature, _)| feature == tag) {
+            features[index].1 = value;
+        } else {
+            features.push((tag.to_string(), value));
+        }
+        Self(Arc::new(features))
+    }
 }
 
 impl std::fmt::Debug for FontFeatures {
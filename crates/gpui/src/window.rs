@@ -1024,6 +1024,25 @@ impl<'a> WindowContext<'a> {
         })
     }
 
+    fn dispatch_keystroke_interceptors(&mut self, event: &dyn Any) {
+        let Some(key_down_event) = event.downcast_ref::<KeyDownEvent>() else {
+            return;
+        };
+
+        self.keystroke_interceptors
+            .clone()
+            .retain(&(), move |callback| {
+                (callback)(
+                    &KeystrokeEvent {
+                        keystroke: key_down_event.keystroke.clone(),
+                        action: None,
+                    },
+                    self,
+                );
+                true
+            });
+    }
+
     pub(crate) fn dispatch_keystroke_observers(
         &mut self,
         event: &dyn Any,
@@ -3248,6 +3267,11 @@ impl<'a> WindowContext<'a> {
             return;
         };
 
+        self.dispatch_keystroke_interceptors(event);
+        if !self.propagate_event {
+            return;
+        }
+
         let mut currently_pending = self.window.pending_input.take().unwrap_or_default();
         if currently_pending.focus.is_some() && currently_pending.focus != self.window.focus {
             currently_pending = PendingInput::default();
@@ -14,7 +14,7 @@ use crate::{
         SyntaxSnapshot, ToTreeSitterPoint,
     },
     task_context::RunnableRange,
-    LanguageScope, Outline, RunnableCapture, RunnableTag,
+    LanguageScope, Outline, RunnableCapture, RunnableTag, TextObject,
 };
 use anyhow::{anyhow, Context, Result};
 use async_watch as watch;
@@ -27,6 +27,8 @@ use gpui::{
 use lazy_static::lazy_static;
 use lsp::LanguageServerId;
 use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use similar::{ChangeTag, TextDiff};
 use smallvec::SmallVec;
@@ -115,6 +117,7 @@ pub struct Buffer {
     completion_triggers_timestamp: clock::Lamport,
     deferred_ops: OperationQueue<Operation>,
     capability: Capability,
+    large_file: bool,
     has_conflict: bool,
     diff_base_version: usize,
     /// Memoize calls to has_changes_since(saved_version).
@@ -162,7 +165,8 @@ pub enum IndentKind {
 }
 
 /// The shape of a selection cursor.
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum CursorShape {
     /// A vertical bar
     #[default]
@@ -170,6 +174,7 @@ pub enum CursorShape {
     /// A block that surrounds the following character
     Block,
     /// An underline that runs along the following character
+    #[serde(rename = "underline")]
     Underscore,
     /// A box drawn around the following character
     Hollow,
@@ -532,6 +537,16 @@ pub struct Runnable {
     pub buffer: BufferId,
 }
 
+/// The signature of a function, used to write the skeleton of its documentation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionSignature {
+    /// The range of the whole function.
+    pub range: Range<usize>,
+    /// The names of the parameters.
+    pub parameters: Vec<String>,
+    pub return_type: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct IndentGuide {
     pub buffer_id: BufferId,
@@ -717,6 +732,7 @@ impl Buffer {
             git_diff: git::diff::BufferDiff::new(),
             file,
             capability,
+            large_file: false,
             syntax_map: Mutex::new(SyntaxMap::new()),
             parsing_in_background: false,
             non_text_state_update_count: 0,
@@ -808,6 +824,20 @@ impl Buffer {
         cx.emit(Event::CapabilityChanged)
     }
 
+    /// Whether this buffer was opened in large file mode, where it isn't parsed with
+    /// Tree-sitter nor opened in language servers.
+    pub fn is_large_file(&self) -> bool {
+        self.large_file
+    }
+
+    /// Sets whether this buffer is in large file mode.
+    pub fn set_large_file(&mut self, large_file: bool) {
+        self.large_file = large_file;
+        if large_file {
+            self.syntax_map.lock().clear();
+        }
+    }
+
     /// This method is called to signal that the buffer has been saved.
     pub fn did_save(
         &mut self,
@@ -1043,8 +1073,10 @@ impl Buffer {
     /// initiate an additional reparse recursively. To avoid concurrent parses
     /// for the same buffer, we only initiate a new parse if we are not already
     /// parsing in the background.
+    ///
+    /// Buffers in large file mode are never parsed.
     pub fn reparse(&mut self, cx: &mut ModelContext<Self>) {
-        if self.parsing_in_background {
+        if self.parsing_in_background || self.large_file {
             return;
         }
         let language = if let Some(language) = self.language.clone() {
@@ -1483,6 +1515,50 @@ impl Buffer {
         })
     }
 
+    /// Like [`Buffer::remove_trailing_whitespace`], but only searches the given
+    /// rows for whitespace to remove.
+    pub fn remove_trailing_whitespace_in_rows(
+        &self,
+        rows: Vec<Range<u32>>,
+        cx: &AppContext,
+    ) -> Task<Diff> {
+        let old_text = self.as_rope().clone();
+        let line_ending = self.line_ending();
+        let base_version = self.version();
+        cx.background_executor().spawn(async move {
+            let empty = Arc::<str>::from("");
+            let edits = trailing_whitespace_ranges(&old_text)
+                .into_iter()
+                .filter(|range| {
+                    let row = old_text.offset_to_point(range.start).row;
+                    rows.iter().any(|rows| rows.contains(&row))
+                })
+                .map(|range| (range, empty.clone()))
+                .collect();
+            Diff {
+                base_version,
+                line_ending,
+                edits,
+            }
+        })
+    }
+
+    /// Returns the ranges of rows that were edited since the buffer was last
+    /// saved, including the rows that deletions joined together.
+    pub fn rows_edited_since_save(&self) -> Vec<Range<u32>> {
+        let mut edited_rows: Vec<Range<u32>> = Vec::new();
+        for edit in self.edits_since::<Point>(&self.saved_version) {
+            let rows = edit.new.start.row..edit.new.end.row + 1;
+            match edited_rows.last_mut() {
+                Some(last_rows) if last_rows.end >= rows.start => {
+                    last_rows.end = last_rows.end.max(rows.end);
+                }
+                _ => edited_rows.push(rows),
+            }
+        }
+        edited_rows
+    }
+
     /// Ensures that the buffer ends with a single newline character, and
     /// no other whitespace.
     pub fn ensure_final_newline(&mut self, cx: &mut ModelContext<Self>) {
@@ -2562,11 +2638,7 @@ impl BufferSnapshot {
     }
 
     /// Returns the settings for the language at the given location.
-    pub fn settings_at<'a, D: ToOffset>(
-        &self,
-        position: D,
-        cx: &'a AppContext,
-    ) -> &'a LanguageSettings {
+    pub fn settings_at<D: ToOffset>(&self, position: D, cx: &AppContext) -> Arc<LanguageSettings> {
         language_settings(self.language_at(position), self.file.as_ref(), cx)
     }
 
@@ -3058,6 +3130,342 @@ impl BufferSnapshot {
         })
     }
 
+    /// Returns the ranges of the bodies of the functions intersecting the given range, as
+    /// captured by the text objects query of each language of the buffer.
+    pub fn function_body_ranges<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> impl Iterator<Item = Range<usize>> + '_ {
+        let offset_range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut syntax_matches = self.syntax.matches(offset_range, self, |grammar| {
+            grammar
+                .text_object_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.text_object_config.as_ref())
+            .collect::<Vec<_>>();
+
+        iter::from_fn(move || loop {
+            let mat = syntax_matches.peek()?;
+            let body_range = configs[mat.grammar_index]
+                .and_then(|config| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| Some(capture.index) == config.function_inside_capture_ix)
+                })
+                .map(|capture| capture.node.byte_range());
+            syntax_matches.advance();
+            if body_range.is_some() {
+                return body_range;
+            }
+        })
+    }
+
+    /// Returns the smallest text object of the given kind that strictly contains the given
+    /// range, as captured by the text objects query of the language at the range.
+    pub fn range_for_text_object<T: ToOffset>(
+        &self,
+        range: Range<T>,
+        text_object: TextObject,
+    ) -> Option<Range<usize>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut syntax_matches = self.syntax.matches(
+            range.start.saturating_sub(1)..self.len().min(range.end + 1),
+            self,
+            |grammar| {
+                grammar
+                    .text_object_config
+                    .as_ref()
+                    .map(|config| &config.query)
+            },
+        );
+
+        let capture_indices = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| {
+                grammar
+                    .text_object_config
+                    .as_ref()
+                    .and_then(|config| config.capture_ix(text_object))
+            })
+            .collect::<Vec<_>>();
+
+        let mut result: Option<Range<usize>> = None;
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(capture_ix) = capture_indices[mat.grammar_index] {
+                for capture in mat.captures {
+                    if capture.index != capture_ix {
+                        continue;
+                    }
+                    let node_range = capture.node.byte_range();
+                    if node_range.start <= range.start
+                        && node_range.end >= range.end
+                        && node_range.len() > range.len()
+                        && result
+                            .as_ref()
+                            .map_or(true, |result| node_range.len() < result.len())
+                    {
+                        result = Some(node_range);
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+        result
+    }
+
+    /// Returns the ranges that are edited together with the one containing the given offset,
+    /// such as the names of an opening tag and of its closing tag, as captured by the linked
+    /// edits query of the language at the offset. The ranges are only linked while their
+    /// texts are the same.
+    pub fn linked_edit_ranges(&self, offset: usize) -> Vec<Range<usize>> {
+        let range = offset.saturating_sub(1)..self.len().min(offset + 1);
+        let mut syntax_matches = self.syntax.matches(range, self, |grammar| {
+            grammar
+                .linked_edits_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.linked_edits_config.as_ref())
+            .collect::<Vec<_>>();
+
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                let capture_range = |capture_ix| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| capture.index == capture_ix)
+                        .map(|capture| capture.node.byte_range())
+                };
+                if let Some((open, close)) = capture_range(config.open_capture_ix)
+                    .zip(capture_range(config.close_capture_ix))
+                {
+                    let contains_offset =
+                        |range: &Range<usize>| range.start <= offset && offset <= range.end;
+                    if (contains_offset(&open) || contains_offset(&close))
+                        && self.text_for_range(open.clone()).collect::<String>()
+                            == self.text_for_range(close.clone()).collect::<String>()
+                    {
+                        return vec![open, close];
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+        Vec::new()
+    }
+
+    /// Returns the names of the functions called in the given range, along with the ranges of
+    /// their arguments, as captured by the parameter hints query of their language.
+    pub fn call_arguments(&self, range: Range<usize>) -> Vec<(String, Vec<Range<usize>>)> {
+        let mut syntax_matches = self.syntax.matches(range, self, |grammar| {
+            grammar
+                .parameter_hints_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.parameter_hints_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut calls = Vec::new();
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                let capture_node = |capture_ix| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| capture.index == capture_ix)
+                        .map(|capture| capture.node)
+                };
+                if let Some((name, arguments)) = capture_node(config.call_name_capture_ix)
+                    .zip(capture_node(config.call_arguments_capture_ix))
+                {
+                    let mut cursor = arguments.walk();
+                    let arguments = arguments
+                        .named_children(&mut cursor)
+                        .filter(|argument| !argument.kind().contains("comment"))
+                        .map(|argument| argument.byte_range())
+                        .collect();
+                    calls.push((self.text_for_range(name.byte_range()).collect(), arguments));
+                }
+            }
+            syntax_matches.advance();
+        }
+        calls
+    }
+
+    /// Returns the names of the functions defined in the buffer, along with the names of their
+    /// parameters, as captured by the parameter hints query of their language.
+    pub fn function_parameters(&self) -> Vec<(String, Vec<String>)> {
+        let mut syntax_matches = self.syntax.matches(0..self.len(), self, |grammar| {
+            grammar
+                .parameter_hints_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.parameter_hints_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut definitions = Vec::new();
+        let mut parameters = Vec::new();
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                let capture_range = |capture_ix| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| capture.index == capture_ix)
+                        .map(|capture| capture.node.byte_range())
+                };
+                parameters.extend(
+                    mat.captures
+                        .iter()
+                        .filter(|capture| {
+                            capture.index == config.parameter_capture_ix
+                                && !capture.node.kind().contains("comment")
+                        })
+                        .map(|capture| capture.node.byte_range()),
+                );
+                if let Some(definition) = capture_range(config.definition_name_capture_ix)
+                    .zip(capture_range(config.definition_parameters_capture_ix))
+                {
+                    definitions.push(definition);
+                }
+            }
+            syntax_matches.advance();
+        }
+
+        definitions
+            .into_iter()
+            .map(|(name, parameters_range)| {
+                let mut parameter_names = Vec::new();
+                let mut previous_end = parameters_range.start;
+                for parameter in &parameters {
+                    // Skip the parameters of the functions nested in other parameters,
+                    // such as in their default values.
+                    if parameter.start < previous_end || parameter.end > parameters_range.end {
+                        continue;
+                    }
+                    previous_end = parameter.end;
+                    parameter_names.push(self.text_for_range(parameter.clone()).collect());
+                }
+                (self.text_for_range(name).collect(), parameter_names)
+            })
+            .collect()
+    }
+
+    /// Returns the signature of the function that starts on the given row, as captured by the
+    /// documentation query of its language.
+    pub fn function_signature_at_row(&self, row: u32) -> Option<FunctionSignature> {
+        let row_range = Point::new(row, 0).to_offset(self)..Point::new(row + 1, 0).to_offset(self);
+        let mut syntax_matches = self.syntax.matches(row_range.clone(), self, |grammar| {
+            grammar
+                .documentation_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.documentation_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut item = None;
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                let capture_range = |capture_ix| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| Some(capture.index) == capture_ix)
+                        .map(|capture| capture.node.byte_range())
+                };
+                let parameters = mat
+                    .captures
+                    .iter()
+                    .find(|capture| capture.index == config.parameters_capture_ix)
+                    .map(|capture| capture.node);
+                if let Some((item_range, parameters)) =
+                    capture_range(Some(config.item_capture_ix)).zip(parameters)
+                {
+                    if row_range.contains(&item_range.start) {
+                        let return_type = capture_range(config.return_type_capture_ix);
+                        item = Some((
+                            item_range,
+                            parameters.byte_range(),
+                            parameters.kind_id(),
+                            return_type,
+                        ));
+                        break;
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+        let (item_range, parameters_range, parameters_kind, return_type) = item?;
+
+        let mut syntax_matches = self
+            .syntax
+            .matches(parameters_range.clone(), self, |grammar| {
+                grammar
+                    .documentation_config
+                    .as_ref()
+                    .map(|config| &config.query)
+            });
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.documentation_config.as_ref())
+            .collect::<Vec<_>>();
+        let mut parameters = Vec::<Range<usize>>::new();
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                for capture in mat.captures {
+                    if capture.index != config.parameter_capture_ix {
+                        continue;
+                    }
+                    // Skip the parameters of the functions nested in other parameters, such as
+                    // in their default values.
+                    let mut list = capture.node.parent();
+                    while let Some(node) = list {
+                        if node.kind_id() == parameters_kind {
+                            break;
+                        }
+                        list = node.parent();
+                    }
+                    if list.map(|list| list.byte_range()) == Some(parameters_range.clone()) {
+                        parameters.push(capture.node.byte_range());
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+
+        Some(FunctionSignature {
+            range: item_range,
+            parameters: parameters
+                .into_iter()
+                .map(|range| self.text_for_range(range).collect())
+                .collect(),
+            return_type: return_type.map(|range| self.text_for_range(range).collect()),
+        })
+    }
+
     pub fn injections_intersecting_range<T: ToOffset>(
         &self,
         range: Range<T>,
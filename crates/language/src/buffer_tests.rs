@@ -376,6 +376,41 @@ async fn test_apply_diff(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_remove_trailing_whitespace_in_edited_rows(cx: &mut gpui::TestAppContext) {
+    let buffer = cx.new_model(|cx| Buffer::local("zero  \none  \ntwo  \nthree  ", cx));
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(Point::new(0, 0)..Point::new(0, 0), "0")], None, cx);
+        let version = buffer.version();
+        buffer.did_save(version, None, cx);
+
+        buffer.edit(
+            [
+                (Point::new(1, 0)..Point::new(1, 0), "1"),
+                (Point::new(3, 0)..Point::new(3, 0), "3"),
+            ],
+            None,
+            cx,
+        );
+        assert_eq!(buffer.rows_edited_since_save(), vec![1..2, 3..4]);
+    });
+
+    let rows = buffer.update(cx, |buffer, _| buffer.rows_edited_since_save());
+    let diff = buffer
+        .update(cx, |buffer, cx| {
+            buffer.remove_trailing_whitespace_in_rows(rows, cx)
+        })
+        .await;
+    buffer.update(cx, |buffer, cx| {
+        buffer.apply_diff(diff, cx);
+        assert_eq!(buffer.text(), "0zero  \n1one\ntwo  \n3three");
+
+        // Joining an edited row with the next one merges their ranges.
+        buffer.edit([(Point::new(1, 4)..Point::new(2, 0), "")], None, cx);
+        assert_eq!(buffer.rows_edited_since_save(), vec![1..3]);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_normalize_whitespace(cx: &mut gpui::TestAppContext) {
     let text = [
@@ -2017,6 +2052,37 @@ fn test_language_scope_at_with_combined_injections(cx: &mut AppContext) {
     });
 }
 
+#[gpui::test]
+fn test_linked_edit_ranges(cx: &mut AppContext) {
+    init_settings(cx, |_| {});
+
+    let language = html_lang()
+        .with_linked_edits_query(
+            "
+            (element
+              (start_tag (tag_name) @open)
+              (end_tag (tag_name) @close))
+            ",
+        )
+        .unwrap();
+    let text = "<div><span>a</span></div>";
+    let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(Arc::new(language), cx));
+
+    buffer.update(cx, |buffer, cx| {
+        let snapshot = buffer.snapshot();
+        // The names of the innermost element's tags are linked, from either tag.
+        assert_eq!(snapshot.linked_edit_ranges(7), vec![6..10, 14..18]);
+        assert_eq!(snapshot.linked_edit_ranges(18), vec![6..10, 14..18]);
+        assert_eq!(snapshot.linked_edit_ranges(1), vec![1..4, 21..24]);
+        // Positions outside of tag names aren't linked.
+        assert_eq!(snapshot.linked_edit_ranges(12), vec![]);
+
+        // Tags whose names differ aren't linked.
+        buffer.edit([(21..24, "p")], None, cx);
+        assert_eq!(buffer.snapshot().linked_edit_ranges(1), vec![]);
+    });
+}
+
 #[gpui::test]
 fn test_serialization(cx: &mut gpui::AppContext) {
     let mut now = Instant::now();
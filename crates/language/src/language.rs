@@ -277,6 +277,17 @@ pub trait LspAdapterDelegate: Send + Sync {
     async fn read_text_file(&self, path: PathBuf) -> Result<String>;
 }
 
+/// A language server binary to download onto the remote host of a project.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteServerDownload {
+    /// The name of the installed binary, which should include its version so that each
+    /// version is only downloaded once.
+    pub file_name: String,
+    pub url: String,
+    /// Whether the binary is gzipped.
+    pub gzipped: bool,
+}
+
 #[async_trait(?Send)]
 pub trait LspAdapter: 'static + Send + Sync {
     fn name(&self) -> LanguageServerName;
@@ -395,6 +406,29 @@ pub trait LspAdapter: 'static + Send + Sync {
         delegate: &dyn LspAdapterDelegate,
     ) -> Option<LanguageServerBinary>;
 
+    /// Returns where to download the latest version of the server from to install it on a
+    /// remote host with the given OS and architecture, such as `linux` and `x86_64`.
+    ///
+    /// Servers that can't be installed this way must already be on remote hosts.
+    async fn fetch_remote_server_download(
+        &self,
+        _os: &str,
+        _arch: &str,
+        _delegate: &dyn LspAdapterDelegate,
+    ) -> Result<Option<RemoteServerDownload>> {
+        Ok(None)
+    }
+
+    /// Returns the command that starts the server on a remote host: the name of the binary
+    /// to look up on the remote `PATH`, and the arguments it's run with. When the server is
+    /// downloaded with [`fetch_remote_server_download`](Self::fetch_remote_server_download),
+    /// the downloaded binary is run with the same arguments instead.
+    ///
+    /// Servers that can't be started on remote hosts return `None`.
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        None
+    }
+
     /// Returns `true` if a language server can be reinstalled.
     ///
     /// If language server initialization fails, a reinstallation will be attempted unless the value returned from this method is `false`.
@@ -591,6 +625,10 @@ pub struct LanguageConfig {
     /// Starting and closing characters of a block comment.
     #[serde(default)]
     pub block_comment: Option<(Arc<str>, Arc<str>)>,
+    /// How to write the documentation comments of functions, whose skeleton is offered when
+    /// typing the comment's trigger above a function.
+    #[serde(default)]
+    pub documentation_comment: Option<DocumentationCommentConfig>,
     /// A list of language servers that are allowed to run on subranges of a given language.
     #[serde(default)]
     pub scope_opt_in_language_servers: Vec<String>,
@@ -617,6 +655,34 @@ pub struct LanguageConfig {
     pub prettier_parser_name: Option<String>,
 }
 
+/// The layout of a language's documentation comments, whose skeletons list the parameters and the
+/// return value of the documented function.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct DocumentationCommentConfig {
+    /// The text that offers the skeleton when typed at the start of the line above a function,
+    /// such as `///` or `/**`.
+    pub trigger: Arc<str>,
+    /// The line that opens the comment, above its first prefixed line.
+    #[serde(default)]
+    pub start: Option<Arc<str>>,
+    /// The prefix of each line of the comment.
+    pub prefix: Arc<str>,
+    /// The line that closes the comment, below its last prefixed line.
+    #[serde(default)]
+    pub end: Option<Arc<str>>,
+    /// The heading of the section listing the parameters.
+    #[serde(default)]
+    pub parameters_heading: Option<Arc<str>>,
+    /// The line describing a parameter, where `{name}` stands for its name.
+    pub parameter: Arc<str>,
+    /// The heading of the section describing the return value.
+    #[serde(default)]
+    pub returns_heading: Option<Arc<str>>,
+    /// The start of the line describing the return value.
+    #[serde(default)]
+    pub returns: Arc<str>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
 pub struct LanguageMatcher {
     /// Given a list of `LanguageConfig`'s, the language of a file can be determined based on the path extension matching any of the `path_suffixes`.
@@ -693,6 +759,7 @@ impl Default for LanguageConfig {
             autoclose_before: Default::default(),
             line_comments: Default::default(),
             block_comment: Default::default(),
+            documentation_comment: None,
             scope_opt_in_language_servers: Default::default(),
             overrides: Default::default(),
             word_characters: Default::default(),
@@ -746,6 +813,8 @@ pub struct FakeLspAdapter {
     pub disk_based_diagnostics_sources: Vec<String>,
     pub prettier_plugins: Vec<&'static str>,
     pub language_server_binary: LanguageServerBinary,
+    pub remote_server_download: Option<RemoteServerDownload>,
+    pub remote_server_command: Option<LanguageServerBinary>,
 }
 
 /// Configuration of handling bracket pairs for a given language.
@@ -844,6 +913,10 @@ pub struct Grammar {
     pub(crate) highlights_query: Option<Query>,
     pub(crate) brackets_config: Option<BracketConfig>,
     pub(crate) redactions_config: Option<RedactionConfig>,
+    pub(crate) text_object_config: Option<TextObjectConfig>,
+    pub(crate) linked_edits_config: Option<LinkedEditsConfig>,
+    pub(crate) parameter_hints_config: Option<ParameterHintsConfig>,
+    pub(crate) documentation_config: Option<DocumentationConfig>,
     pub(crate) runnable_config: Option<RunnableConfig>,
     pub(crate) indents_config: Option<IndentConfig>,
     pub outline_config: Option<OutlineConfig>,
@@ -893,6 +966,59 @@ struct RedactionConfig {
     pub redaction_capture_ix: u32,
 }
 
+struct TextObjectConfig {
+    pub query: Query,
+    pub function_inside_capture_ix: Option<u32>,
+    pub string_capture_ix: Option<u32>,
+    pub comment_capture_ix: Option<u32>,
+    pub argument_capture_ix: Option<u32>,
+}
+
+impl TextObjectConfig {
+    fn capture_ix(&self, text_object: TextObject) -> Option<u32> {
+        match text_object {
+            TextObject::String => self.string_capture_ix,
+            TextObject::Comment => self.comment_capture_ix,
+            TextObject::Argument => self.argument_capture_ix,
+        }
+    }
+}
+
+/// A kind of syntactic range that can be selected, as captured by the text objects query
+/// of a language.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextObject {
+    /// A string literal, captured as `@string.around`.
+    String,
+    /// A comment, captured as `@comment.around`.
+    Comment,
+    /// An argument of a call or a parameter of a function, captured as `@argument.around`.
+    Argument,
+}
+
+struct LinkedEditsConfig {
+    pub query: Query,
+    pub open_capture_ix: u32,
+    pub close_capture_ix: u32,
+}
+
+struct ParameterHintsConfig {
+    pub query: Query,
+    pub call_name_capture_ix: u32,
+    pub call_arguments_capture_ix: u32,
+    pub definition_name_capture_ix: u32,
+    pub definition_parameters_capture_ix: u32,
+    pub parameter_capture_ix: u32,
+}
+
+struct DocumentationConfig {
+    pub query: Query,
+    pub item_capture_ix: u32,
+    pub parameters_capture_ix: u32,
+    pub parameter_capture_ix: u32,
+    pub return_type_capture_ix: Option<u32>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum RunnableCapture {
     Named(SharedString),
@@ -946,6 +1072,10 @@ impl Language {
                     injection_config: None,
                     override_config: None,
                     redactions_config: None,
+                    text_object_config: None,
+                    linked_edits_config: None,
+                    parameter_hints_config: None,
+                    documentation_config: None,
                     runnable_config: None,
                     error_query: Query::new(&ts_language, "(ERROR) @error").unwrap(),
                     ts_language,
@@ -1007,6 +1137,26 @@ impl Language {
                 .with_runnable_query(query.as_ref())
                 .context("Error loading tests query")?;
         }
+        if let Some(query) = queries.textobjects {
+            self = self
+                .with_text_object_query(query.as_ref())
+                .context("Error loading text objects query")?;
+        }
+        if let Some(query) = queries.linked_edits {
+            self = self
+                .with_linked_edits_query(query.as_ref())
+                .context("Error loading linked edits query")?;
+        }
+        if let Some(query) = queries.parameter_hints {
+            self = self
+                .with_parameter_hints_query(query.as_ref())
+                .context("Error loading parameter hints query")?;
+        }
+        if let Some(query) = queries.documentation {
+            self = self
+                .with_documentation_query(query.as_ref())
+                .context("Error loading documentation query")?;
+        }
         Ok(self)
     }
 
@@ -1309,6 +1459,150 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_text_object_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let grammar = self
+            .grammar_mut()
+            .ok_or_else(|| anyhow!("cannot mutate grammar"))?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let mut function_inside_capture_ix = None;
+        let mut string_capture_ix = None;
+        let mut comment_capture_ix = None;
+        let mut argument_capture_ix = None;
+        get_capture_indices(
+            &query,
+            &mut [
+                ("function.inside", &mut function_inside_capture_ix),
+                ("string.around", &mut string_capture_ix),
+                ("comment.around", &mut comment_capture_ix),
+                ("argument.around", &mut argument_capture_ix),
+            ],
+        );
+
+        grammar.text_object_config = Some(TextObjectConfig {
+            query,
+            function_inside_capture_ix,
+            string_capture_ix,
+            comment_capture_ix,
+            argument_capture_ix,
+        });
+
+        Ok(self)
+    }
+
+    pub fn with_linked_edits_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let grammar = self
+            .grammar_mut()
+            .ok_or_else(|| anyhow!("cannot mutate grammar"))?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let mut open_capture_ix = None;
+        let mut close_capture_ix = None;
+        get_capture_indices(
+            &query,
+            &mut [
+                ("open", &mut open_capture_ix),
+                ("close", &mut close_capture_ix),
+            ],
+        );
+
+        if let Some((open_capture_ix, close_capture_ix)) = open_capture_ix.zip(close_capture_ix) {
+            grammar.linked_edits_config = Some(LinkedEditsConfig {
+                query,
+                open_capture_ix,
+                close_capture_ix,
+            });
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_parameter_hints_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let grammar = self
+            .grammar_mut()
+            .ok_or_else(|| anyhow!("cannot mutate grammar"))?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let mut call_name_capture_ix = None;
+        let mut call_arguments_capture_ix = None;
+        let mut definition_name_capture_ix = None;
+        let mut definition_parameters_capture_ix = None;
+        let mut parameter_capture_ix = None;
+        get_capture_indices(
+            &query,
+            &mut [
+                ("call.name", &mut call_name_capture_ix),
+                ("call.arguments", &mut call_arguments_capture_ix),
+                ("definition.name", &mut definition_name_capture_ix),
+                (
+                    "definition.parameters",
+                    &mut definition_parameters_capture_ix,
+                ),
+                ("parameter", &mut parameter_capture_ix),
+            ],
+        );
+
+        if let (
+            Some(call_name_capture_ix),
+            Some(call_arguments_capture_ix),
+            Some(definition_name_capture_ix),
+            Some(definition_parameters_capture_ix),
+            Some(parameter_capture_ix),
+        ) = (
+            call_name_capture_ix,
+            call_arguments_capture_ix,
+            definition_name_capture_ix,
+            definition_parameters_capture_ix,
+            parameter_capture_ix,
+        ) {
+            grammar.parameter_hints_config = Some(ParameterHintsConfig {
+                query,
+                call_name_capture_ix,
+                call_arguments_capture_ix,
+                definition_name_capture_ix,
+                definition_parameters_capture_ix,
+                parameter_capture_ix,
+            });
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_documentation_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let grammar = self
+            .grammar_mut()
+            .ok_or_else(|| anyhow!("cannot mutate grammar"))?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let mut item_capture_ix = None;
+        let mut parameters_capture_ix = None;
+        let mut parameter_capture_ix = None;
+        let mut return_type_capture_ix = None;
+        get_capture_indices(
+            &query,
+            &mut [
+                ("item", &mut item_capture_ix),
+                ("parameters", &mut parameters_capture_ix),
+                ("parameter", &mut parameter_capture_ix),
+                ("return_type", &mut return_type_capture_ix),
+            ],
+        );
+
+        if let (Some(item_capture_ix), Some(parameters_capture_ix), Some(parameter_capture_ix)) =
+            (item_capture_ix, parameters_capture_ix, parameter_capture_ix)
+        {
+            grammar.documentation_config = Some(DocumentationConfig {
+                query,
+                item_capture_ix,
+                parameters_capture_ix,
+                parameter_capture_ix,
+                return_type_capture_ix,
+            });
+        }
+
+        Ok(self)
+    }
+
     fn grammar_mut(&mut self) -> Option<&mut Grammar> {
         Arc::get_mut(self.grammar.as_mut()?)
     }
@@ -1384,15 +1678,25 @@ impl Language {
     }
 
     pub fn lsp_id(&self) -> String {
-        match self.config.name.as_ref() {
-            "Plain Text" => "plaintext".to_string(),
-            language_name => language_name.to_lowercase(),
-        }
+        language_name_to_lsp_id(self.config.name.as_ref())
     }
 
     pub fn prettier_parser_name(&self) -> Option<&str> {
         self.config.prettier_parser_name.as_deref()
     }
+
+    pub fn documentation_comment(&self) -> Option<&DocumentationCommentConfig> {
+        self.config.documentation_comment.as_ref()
+    }
+}
+
+/// Returns the identifier of the language with the given name in the Language Server
+/// Protocol, which also keys the language's snippets.
+pub fn language_name_to_lsp_id(language_name: &str) -> String {
+    match language_name {
+        "Plain Text" => "plaintext".to_string(),
+        language_name => language_name.to_lowercase(),
+    }
 }
 
 impl LanguageScope {
@@ -1472,6 +1776,14 @@ impl LanguageScope {
         }
     }
 
+    /// Returns the name of the syntax override this scope is in, such as `comment` or `string`.
+    pub fn override_name(&self) -> Option<&str> {
+        let id = self.override_id?;
+        let grammar = self.language.grammar.as_ref()?;
+        let override_config = grammar.override_config.as_ref()?;
+        override_config.values.get(&id).map(|e| e.0.as_str())
+    }
+
     fn config_override(&self) -> Option<&LanguageConfigOverride> {
         let id = self.override_id?;
         let grammar = self.language.grammar.as_ref()?;
@@ -1606,6 +1918,8 @@ impl Default for FakeLspAdapter {
                 arguments: vec![],
                 env: Default::default(),
             },
+            remote_server_download: None,
+            remote_server_command: None,
         }
     }
 }
@@ -1652,6 +1966,19 @@ impl LspAdapter for FakeLspAdapter {
         unreachable!();
     }
 
+    async fn fetch_remote_server_download(
+        &self,
+        _: &str,
+        _: &str,
+        _: &dyn LspAdapterDelegate,
+    ) -> Result<Option<RemoteServerDownload>> {
+        Ok(self.remote_server_download.clone())
+    }
+
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        self.remote_server_command.clone()
+    }
+
     async fn installation_test_binary(&self, _: PathBuf) -> Option<LanguageServerBinary> {
         unreachable!();
     }
@@ -126,6 +126,10 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("overrides", |q| &mut q.overrides),
     ("redactions", |q| &mut q.redactions),
     ("runnables", |q| &mut q.runnables),
+    ("textobjects", |q| &mut q.textobjects),
+    ("linked_edits", |q| &mut q.linked_edits),
+    ("parameter_hints", |q| &mut q.parameter_hints),
+    ("documentation", |q| &mut q.documentation),
 ];
 
 /// Tree-sitter language queries for a given language.
@@ -140,6 +144,10 @@ pub struct LanguageQueries {
     pub overrides: Option<Cow<'static, str>>,
     pub redactions: Option<Cow<'static, str>>,
     pub runnables: Option<Cow<'static, str>>,
+    pub textobjects: Option<Cow<'static, str>>,
+    pub linked_edits: Option<Cow<'static, str>>,
+    pub parameter_hints: Option<Cow<'static, str>>,
+    pub documentation: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Default)]
@@ -784,51 +792,9 @@ impl LanguageRegistry {
 
                 #[cfg(any(test, feature = "test-support"))]
                 if true {
-                    let capabilities = adapter
-                        .as_fake()
-                        .map(|fake_adapter| fake_adapter.capabilities.clone())
-                        .unwrap_or_else(|| lsp::ServerCapabilities {
-                            completion_provider: Some(Default::default()),
-                            ..Default::default()
-                        });
-
-                    let (server, mut fake_server) = lsp::FakeLanguageServer::new(
-                        server_id,
-                        binary,
-                        adapter.name.0.to_string(),
-                        capabilities,
-                        cx.clone(),
+                    let server = Self::create_fake_language_server(
+                        this, server_id, binary, &adapter, &language, cx,
                     );
-
-                    if let Some(fake_adapter) = adapter.as_fake() {
-                        if let Some(initializer) = &fake_adapter.initializer {
-                            initializer(&mut fake_server);
-                        }
-                    }
-
-                    cx.background_executor()
-                        .spawn(async move {
-                            if fake_server
-                                .try_receive_notification::<lsp::notification::Initialized>()
-                                .await
-                                .is_some()
-                            {
-                                if let Some(this) = this.upgrade() {
-                                    if let Some(txs) = this
-                                        .state
-                                        .write()
-                                        .fake_server_txs
-                                        .get_mut(language.name().as_ref())
-                                    {
-                                        for tx in txs {
-                                            tx.unbounded_send(fake_server.clone()).ok();
-                                        }
-                                    }
-                                }
-                            }
-                        })
-                        .detach();
-
                     return Ok((server, options));
                 }
 
@@ -854,6 +820,116 @@ impl LanguageRegistry {
         })
     }
 
+    /// Creates a language server that runs the given command instead of the adapter's binary,
+    /// such as a server on the remote host of a project, once the command is resolved. The
+    /// adapter isn't asked for its initialization options, as it computes them for this
+    /// machine.
+    pub fn create_pending_remote_language_server(
+        self: &Arc<Self>,
+        stderr_capture: Arc<Mutex<Option<String>>>,
+        language: Arc<Language>,
+        adapter: Arc<CachedLspAdapter>,
+        binary: Task<Result<lsp::LanguageServerBinary>>,
+        root_path: Arc<Path>,
+        cx: &mut AppContext,
+    ) -> PendingLanguageServer {
+        let server_id = self.state.write().next_language_server_id();
+        log::info!(
+            "starting remote language server {:?}, path: {root_path:?}, id: {server_id}",
+            adapter.name.0
+        );
+
+        let this = Arc::downgrade(self);
+        let task = cx.spawn(move |cx| async move {
+            let binary = binary.await?;
+
+            #[cfg(any(test, feature = "test-support"))]
+            if true {
+                let server = Self::create_fake_language_server(
+                    this, server_id, binary, &adapter, &language, cx,
+                );
+                return Ok((server, None));
+            }
+
+            drop((this, language));
+            Ok((
+                lsp::LanguageServer::new(
+                    stderr_capture,
+                    server_id,
+                    binary,
+                    &root_path,
+                    adapter.code_action_kinds(),
+                    cx,
+                )?,
+                None,
+            ))
+        });
+
+        PendingLanguageServer {
+            server_id,
+            task,
+            container_dir: None,
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    fn create_fake_language_server(
+        this: std::sync::Weak<Self>,
+        server_id: LanguageServerId,
+        binary: lsp::LanguageServerBinary,
+        adapter: &CachedLspAdapter,
+        language: &Arc<Language>,
+        cx: gpui::AsyncAppContext,
+    ) -> lsp::LanguageServer {
+        let capabilities = adapter
+            .as_fake()
+            .map(|fake_adapter| fake_adapter.capabilities.clone())
+            .unwrap_or_else(|| lsp::ServerCapabilities {
+                completion_provider: Some(Default::default()),
+                ..Default::default()
+            });
+
+        let (server, mut fake_server) = lsp::FakeLanguageServer::new(
+            server_id,
+            binary,
+            adapter.name.0.to_string(),
+            capabilities,
+            cx.clone(),
+        );
+
+        if let Some(fake_adapter) = adapter.as_fake() {
+            if let Some(initializer) = &fake_adapter.initializer {
+                initializer(&mut fake_server);
+            }
+        }
+
+        let language = language.clone();
+        cx.background_executor()
+            .spawn(async move {
+                if fake_server
+                    .try_receive_notification::<lsp::notification::Initialized>()
+                    .await
+                    .is_some()
+                {
+                    if let Some(this) = this.upgrade() {
+                        if let Some(txs) = this
+                            .state
+                            .write()
+                            .fake_server_txs
+                            .get_mut(language.name().as_ref())
+                        {
+                            for tx in txs {
+                                tx.unbounded_send(fake_server.clone()).ok();
+                            }
+                        }
+                    }
+                }
+            })
+            .detach();
+
+        server
+    }
+
     pub fn language_server_binary_statuses(
         &self,
     ) -> mpsc::UnboundedReceiver<(LanguageServerName, LanguageServerBinaryStatus)> {
@@ -7,6 +7,7 @@ use core::slice;
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
 use gpui::AppContext;
 use itertools::{Either, Itertools};
+use parking_lot::RwLock;
 use schemars::{
     schema::{InstanceType, ObjectValidation, Schema, SchemaObject, SingleOrVec},
     JsonSchema,
@@ -16,7 +17,9 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 use serde_json::Value;
-use settings::{Settings, SettingsLocation, SettingsSources};
+use settings::{
+    EditorconfigProperties, Settings, SettingsLocation, SettingsSources, SettingsStore,
+};
 use std::{num::NonZeroU32, path::Path, sync::Arc};
 use util::serde::default_true;
 
@@ -35,13 +38,26 @@ pub fn init(cx: &mut AppContext) {
 }
 
 /// Returns the settings for the specified language from the provided file.
-pub fn language_settings<'a>(
+///
+/// The properties of the `.editorconfig` files applying to the file take precedence over the
+/// user settings, but not over the project settings.
+pub fn language_settings(
     language: Option<&Arc<Language>>,
     file: Option<&Arc<dyn File>>,
-    cx: &'a AppContext,
-) -> &'a LanguageSettings {
+    cx: &AppContext,
+) -> Arc<LanguageSettings> {
     let language_name = language.map(|l| l.name());
-    all_language_settings(file, cx).language(language_name.as_deref())
+    let all_settings = all_language_settings(file, cx);
+    let editorconfig_properties = file.and_then(|file| {
+        cx.global::<SettingsStore>()
+            .editorconfig_properties(file.as_ref().into())
+    });
+    match editorconfig_properties {
+        Some(properties) => all_settings.language_with_editorconfig(language_name, properties),
+        None => all_settings
+            .language_settings(language_name.as_deref())
+            .clone(),
+    }
 }
 
 /// Returns the settings for all languages from the provided file.
@@ -54,15 +70,53 @@ pub fn all_language_settings<'a>(
 }
 
 /// The settings for all languages.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct AllLanguageSettings {
     /// The inline completion settings.
     pub inline_completions: InlineCompletionSettings,
-    defaults: LanguageSettings,
-    languages: HashMap<Arc<str>, LanguageSettings>,
+    defaults: Arc<LanguageSettings>,
+    languages: HashMap<Arc<str>, Arc<LanguageSettings>>,
+    /// The settings that `.editorconfig` files can't override, because the project settings
+    /// files set them.
+    project_defaults: ProjectOverrides,
+    project_languages: HashMap<Arc<str>, ProjectOverrides>,
+    /// The settings with the properties of `.editorconfig` files applied, by language.
+    editorconfig_settings:
+        RwLock<HashMap<(Option<Arc<str>>, EditorconfigProperties), Arc<LanguageSettings>>>,
     pub(crate) file_types: HashMap<Arc<str>, GlobSet>,
 }
 
+/// The settings that `.editorconfig` files can set, and whether the project settings files set
+/// them.
+#[derive(Debug, Default, Clone, Copy)]
+struct ProjectOverrides {
+    tab_size: bool,
+    hard_tabs: bool,
+    remove_trailing_whitespace_on_save: bool,
+    ensure_final_newline_on_save: bool,
+}
+
+impl ProjectOverrides {
+    fn add(&mut self, settings: &LanguageSettingsContent) {
+        self.tab_size |= settings.tab_size.is_some();
+        self.hard_tabs |= settings.hard_tabs.is_some();
+        self.remove_trailing_whitespace_on_save |=
+            settings.remove_trailing_whitespace_on_save.is_some();
+        self.ensure_final_newline_on_save |= settings.ensure_final_newline_on_save.is_some();
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            tab_size: self.tab_size || other.tab_size,
+            hard_tabs: self.hard_tabs || other.hard_tabs,
+            remove_trailing_whitespace_on_save: self.remove_trailing_whitespace_on_save
+                || other.remove_trailing_whitespace_on_save,
+            ensure_final_newline_on_save: self.ensure_final_newline_on_save
+                || other.ensure_final_newline_on_save,
+        }
+    }
+}
+
 /// The settings for a particular language.
 #[derive(Debug, Clone, Deserialize)]
 pub struct LanguageSettings {
@@ -81,8 +135,8 @@ pub struct LanguageSettings {
     // if softwrap is set to 'preferred_line_length', and will show any
     // additional guides as specified by the 'wrap_guides' setting.
     pub show_wrap_guides: bool,
-    /// Character counts at which to show wrap guides (vertical rulers) in the editor.
-    pub wrap_guides: Vec<usize>,
+    /// The wrap guides (vertical rulers) to show in the editor.
+    pub wrap_guides: Vec<WrapGuide>,
     /// Indent guide related settings.
     pub indent_guides: IndentGuideSettings,
     /// Whether or not to perform a buffer format before saving.
@@ -93,6 +147,9 @@ pub struct LanguageSettings {
     /// Whether or not to ensure there's a single newline at the end of a buffer
     /// when saving it.
     pub ensure_final_newline_on_save: bool,
+    /// Which lines to remove the trailing whitespace of and to ensure the final
+    /// newline of when saving a buffer.
+    pub whitespace_fixes_on_save: WhitespaceFixesOnSave,
     /// How to perform a buffer format.
     pub formatter: SelectedFormatter,
     /// Zed's Prettier integration settings.
@@ -115,10 +172,24 @@ pub struct LanguageSettings {
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
     pub inlay_hints: InlayHintSettings,
+    /// Whether to show a swatch next to color literals, such as `#ff0000` or `rgb(255, 0, 0)`.
+    pub color_swatches: bool,
+    /// Whether to show the code lenses of language servers above the lines they belong to.
+    pub code_lens: bool,
+    /// Whether to highlight the semantic tokens of language servers over the syntax highlighting.
+    pub semantic_tokens: bool,
+    /// Which text to spell check.
+    pub spell_check: SpellCheckSetting,
+    /// The name of the Hunspell dictionary used to spell check, such as `en_US`.
+    pub spell_check_dictionary: String,
     /// Whether to automatically close brackets.
     pub use_autoclose: bool,
     /// Whether to automatically surround text with brackets.
     pub use_auto_surround: bool,
+    /// Whether to automatically close brackets inside strings and comments.
+    pub autoclose_in_strings_and_comments: bool,
+    /// Additional pairs of characters to automatically close and surround text with.
+    pub auto_pairs: Vec<AutoPair>,
     // Controls how the editor handles the autoclosed characters.
     pub always_treat_brackets_as_autoclosed: bool,
     /// Which code actions to run on save
@@ -174,6 +245,39 @@ impl LanguageSettings {
             })
             .collect::<Vec<_>>()
     }
+
+    /// Overrides the settings with the properties of `.editorconfig` files, except the ones set
+    /// by the project settings files.
+    fn apply_editorconfig(
+        &mut self,
+        properties: &EditorconfigProperties,
+        project_overrides: ProjectOverrides,
+    ) {
+        if let Some(hard_tabs) = properties
+            .hard_tabs()
+            .filter(|_| !project_overrides.hard_tabs)
+        {
+            self.hard_tabs = hard_tabs;
+        }
+        if let Some(tab_size) = properties
+            .tab_size()
+            .filter(|_| !project_overrides.tab_size)
+        {
+            self.tab_size = tab_size;
+        }
+        if let Some(trim_trailing_whitespace) = properties
+            .trim_trailing_whitespace
+            .filter(|_| !project_overrides.remove_trailing_whitespace_on_save)
+        {
+            self.remove_trailing_whitespace_on_save = trim_trailing_whitespace;
+        }
+        if let Some(insert_final_newline) = properties
+            .insert_final_newline
+            .filter(|_| !project_overrides.ensure_final_newline_on_save)
+        {
+            self.ensure_final_newline_on_save = insert_final_newline;
+        }
+    }
 }
 
 /// The provider that supplies inline completions.
@@ -250,11 +354,12 @@ pub struct LanguageSettingsContent {
     /// Default: true
     #[serde(default)]
     pub show_wrap_guides: Option<bool>,
-    /// Character counts at which to show wrap guides in the editor.
+    /// The wrap guides to show in the editor, each given either as the column at which
+    /// to show it, or as an object with its `column`, `style` and `color`.
     ///
     /// Default: []
     #[serde(default)]
-    pub wrap_guides: Option<Vec<usize>>,
+    pub wrap_guides: Option<Vec<WrapGuide>>,
     /// Indent guide related settings.
     #[serde(default)]
     pub indent_guides: Option<IndentGuideSettings>,
@@ -275,6 +380,12 @@ pub struct LanguageSettingsContent {
     /// Default: true
     #[serde(default)]
     pub ensure_final_newline_on_save: Option<bool>,
+    /// Which lines to remove the trailing whitespace of and to ensure the final
+    /// newline of when saving a buffer.
+    ///
+    /// Default: whole_file
+    #[serde(default)]
+    pub whitespace_fixes_on_save: Option<WhitespaceFixesOnSave>,
     /// How to perform a buffer format.
     ///
     /// Default: auto
@@ -319,6 +430,38 @@ pub struct LanguageSettingsContent {
     /// Inlay hint related settings.
     #[serde(default)]
     pub inlay_hints: Option<InlayHintSettings>,
+    /// Whether to show a swatch next to color literals, such as `#ff0000` or `rgb(255, 0, 0)`.
+    /// Clicking a swatch opens a color picker to change the color.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub color_swatches: Option<bool>,
+    /// Whether to show the code lenses of language servers, such as reference counts or
+    /// "Run test", above the lines they belong to. Clicking a lens runs its command.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub code_lens: Option<bool>,
+    /// Whether to highlight identifiers using the semantic tokens of language servers, over
+    /// the syntax highlighting, such as to tell parameters apart from other variables. Tokens
+    /// are highlighted with the theme's syntax styles of their type and modifiers, such as
+    /// `variable.parameter` or `variable.mutable`.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub semantic_tokens: Option<bool>,
+    /// Which text to spell check, underlining the misspelled words.
+    ///
+    /// Default: comments_and_strings
+    #[serde(default)]
+    pub spell_check: Option<SpellCheckSetting>,
+    /// The name of the Hunspell dictionary used to spell check, such as `en_US`.
+    /// Its `.aff` and `.dic` files are looked for in the `dictionaries` directory of
+    /// Zed's config directory, then in the system's dictionary directories.
+    ///
+    /// Default: en_US
+    #[serde(default)]
+    pub spell_check_dictionary: Option<String>,
     /// Whether to automatically type closing characters for you. For example,
     /// when you type (, Zed will automatically add a closing ) at the correct position.
     ///
@@ -329,6 +472,17 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub use_auto_surround: Option<bool>,
+    /// Whether to automatically type closing characters while the cursor is inside
+    /// a string or a comment, as determined by the language's syntax tree.
+    ///
+    /// Default: true
+    pub autoclose_in_strings_and_comments: Option<bool>,
+    /// Additional pairs of characters to automatically close, and to surround
+    /// selected text with, on top of the brackets defined by the language.
+    /// For example, `*` and `_` for emphasis in Markdown.
+    ///
+    /// Default: []
+    pub auto_pairs: Option<Vec<AutoPair>>,
     // Controls how the editor handles the autoclosed characters.
     // When set to `false`(default), skipping over and auto-removing of the closing characters
     // happen only for auto-inserted characters.
@@ -342,7 +496,8 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: {} (or {"source.organizeImports": true} for Go).
     pub code_actions_on_format: Option<HashMap<String, bool>>,
-    /// Whether to perform linked edits of associated ranges, if the language server supports it.
+    /// Whether to perform linked edits of associated ranges, using the language server if it
+    /// supports it, and the tags matched by the language's grammar otherwise.
     /// For example, when editing opening <html> tag, the contents of the closing </html> tag will be edited as well.
     ///
     /// Default: true
@@ -385,6 +540,19 @@ pub enum SoftWrap {
     PreferredLineLength,
 }
 
+/// Which lines the whitespace is fixed on when saving a buffer.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WhitespaceFixesOnSave {
+    /// Fix the whitespace of every line of the buffer.
+    #[default]
+    WholeFile,
+    /// Only fix the whitespace of the lines edited since the buffer was last
+    /// saved, leaving the other lines as they are to avoid noisy diffs. The
+    /// final newline is only ensured when the last line was edited.
+    ModifiedLines,
+}
+
 /// Controls the behavior of formatting files when they are saved.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FormatOnSave {
@@ -516,6 +684,91 @@ pub enum ShowWhitespaceSetting {
     Boundary,
 }
 
+/// A wrap guide (vertical ruler) shown in the editor.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum WrapGuide {
+    /// A solid guide at the given column, in the theme's wrap guide color.
+    Column(usize),
+    /// A guide at the given column, with its own style and color.
+    Styled {
+        /// The column at which to show the guide.
+        column: usize,
+        /// How to draw the guide.
+        #[serde(default)]
+        style: WrapGuideStyle,
+        /// The color of the guide, as a hex color such as `"#ff000080"`. Defaults to
+        /// the theme's `editor.wrap_guide` color.
+        #[serde(default)]
+        color: Option<String>,
+    },
+}
+
+impl WrapGuide {
+    pub fn column(&self) -> usize {
+        match self {
+            WrapGuide::Column(column) | WrapGuide::Styled { column, .. } => *column,
+        }
+    }
+
+    pub fn style(&self) -> WrapGuideStyle {
+        match self {
+            WrapGuide::Column(_) => WrapGuideStyle::default(),
+            WrapGuide::Styled { style, .. } => *style,
+        }
+    }
+
+    pub fn color(&self) -> Option<&str> {
+        match self {
+            WrapGuide::Column(_) => None,
+            WrapGuide::Styled { color, .. } => color.as_deref(),
+        }
+    }
+}
+
+/// How a wrap guide is drawn.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapGuideStyle {
+    /// A solid line.
+    #[default]
+    Solid,
+    /// A dashed line.
+    Dashed,
+}
+
+/// A pair of characters that the editor automatically closes and surrounds
+/// selected text with.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct AutoPair {
+    /// The opening characters of the pair.
+    pub start: String,
+    /// The closing characters of the pair.
+    pub end: String,
+    /// Whether to insert `end` right after `start` is typed.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub close: bool,
+    /// Whether to surround selected text with `start` and `end` when `start` is typed.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub surround: bool,
+}
+
+/// Which text to spell check.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpellCheckSetting {
+    /// Don't spell check.
+    Off,
+    /// Spell check comments and strings.
+    CommentsAndStrings,
+    /// Spell check all of the text, like in prose files.
+    All,
+}
+
 /// Controls which formatter should be used when formatting code.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum SelectedFormatter {
@@ -791,6 +1044,10 @@ impl InlayHintSettings {
 impl AllLanguageSettings {
     /// Returns the [`LanguageSettings`] for the language with the specified name.
     pub fn language<'a>(&'a self, language_name: Option<&str>) -> &'a LanguageSettings {
+        self.language_settings(language_name)
+    }
+
+    fn language_settings(&self, language_name: Option<&str>) -> &Arc<LanguageSettings> {
         if let Some(name) = language_name {
             if let Some(overrides) = self.languages.get(name) {
                 return overrides;
@@ -799,6 +1056,50 @@ impl AllLanguageSettings {
         &self.defaults
     }
 
+    /// Returns the [`LanguageSettings`] for the language with the specified name, with the
+    /// properties of `.editorconfig` files applied.
+    fn language_with_editorconfig(
+        &self,
+        language_name: Option<Arc<str>>,
+        properties: EditorconfigProperties,
+    ) -> Arc<LanguageSettings> {
+        let key = (language_name, properties);
+        if let Some(settings) = self.editorconfig_settings.read().get(&key) {
+            return settings.clone();
+        }
+
+        let language_name = key.0.as_deref();
+        let project_overrides = language_name
+            .and_then(|name| self.project_languages.get(name))
+            .map_or(self.project_defaults, |overrides| {
+                overrides.union(self.project_defaults)
+            });
+        let mut settings = LanguageSettings::clone(self.language_settings(language_name));
+        settings.apply_editorconfig(&properties, project_overrides);
+        let settings = Arc::new(settings);
+        self.editorconfig_settings
+            .write()
+            .insert(key, settings.clone());
+        settings
+    }
+
+    /// Returns the names of the languages whose settings override the default value
+    /// of the setting read by `setting`, sorted by name.
+    pub fn languages_overriding<T: PartialEq>(
+        &self,
+        setting: impl Fn(&LanguageSettings) -> T,
+    ) -> Vec<Arc<str>> {
+        let default = setting(self.defaults.as_ref());
+        let mut languages = self
+            .languages
+            .iter()
+            .filter(|(_, settings)| setting(settings.as_ref()) != default)
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        languages.sort();
+        languages
+    }
+
     /// Returns whether inline completions are enabled for the given path.
     pub fn inline_completions_enabled_for_path(&self, path: &Path) -> bool {
         !self
@@ -888,6 +1189,18 @@ impl settings::Settings for AllLanguageSettings {
 
         let mut file_types: HashMap<Arc<str>, GlobSet> = HashMap::default();
 
+        let mut project_defaults = ProjectOverrides::default();
+        let mut project_languages = HashMap::<Arc<str>, ProjectOverrides>::default();
+        for project_settings in sources.project {
+            project_defaults.add(&project_settings.defaults);
+            for (language_name, settings) in &project_settings.languages {
+                project_languages
+                    .entry(language_name.clone())
+                    .or_default()
+                    .add(settings);
+            }
+        }
+
         for (language, suffixes) in &default_value.file_types {
             let mut builder = GlobSetBuilder::new();
 
@@ -968,8 +1281,14 @@ impl settings::Settings for AllLanguageSettings {
                     .filter_map(|g| Some(globset::Glob::new(g).ok()?.compile_matcher()))
                     .collect(),
             },
-            defaults,
-            languages,
+            defaults: Arc::new(defaults),
+            languages: languages
+                .into_iter()
+                .map(|(language_name, settings)| (language_name, Arc::new(settings)))
+                .collect(),
+            project_defaults,
+            project_languages,
+            editorconfig_settings: Default::default(),
             file_types,
         })
     }
@@ -1036,6 +1355,11 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
     merge(&mut settings.soft_wrap, src.soft_wrap);
     merge(&mut settings.use_autoclose, src.use_autoclose);
     merge(&mut settings.use_auto_surround, src.use_auto_surround);
+    merge(
+        &mut settings.autoclose_in_strings_and_comments,
+        src.autoclose_in_strings_and_comments,
+    );
+    merge(&mut settings.auto_pairs, src.auto_pairs.clone());
     merge(
         &mut settings.always_treat_brackets_as_autoclosed,
         src.always_treat_brackets_as_autoclosed,
@@ -1065,6 +1389,10 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.ensure_final_newline_on_save,
         src.ensure_final_newline_on_save,
     );
+    merge(
+        &mut settings.whitespace_fixes_on_save,
+        src.whitespace_fixes_on_save,
+    );
     merge(
         &mut settings.enable_language_server,
         src.enable_language_server,
@@ -1075,6 +1403,14 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.show_inline_completions,
     );
     merge(&mut settings.show_whitespaces, src.show_whitespaces);
+    merge(&mut settings.color_swatches, src.color_swatches);
+    merge(&mut settings.code_lens, src.code_lens);
+    merge(&mut settings.semantic_tokens, src.semantic_tokens);
+    merge(&mut settings.spell_check, src.spell_check);
+    merge(
+        &mut settings.spell_check_dictionary,
+        src.spell_check_dictionary.clone(),
+    );
     merge(
         &mut settings.extend_comment_on_newline,
         src.extend_comment_on_newline,
@@ -1145,6 +1481,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wrap_guides_deserialization() {
+        let raw = r##"{"wrap_guides": [80, {"column": 100, "style": "dashed", "color": "#ff000080"}, {"column": 120}]}"##;
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        let wrap_guides = settings.wrap_guides.unwrap();
+        assert_eq!(
+            wrap_guides,
+            vec![
+                WrapGuide::Column(80),
+                WrapGuide::Styled {
+                    column: 100,
+                    style: WrapGuideStyle::Dashed,
+                    color: Some("#ff000080".into()),
+                },
+                WrapGuide::Styled {
+                    column: 120,
+                    style: WrapGuideStyle::Solid,
+                    color: None,
+                },
+            ]
+        );
+        assert_eq!(
+            wrap_guides
+                .iter()
+                .map(WrapGuide::column)
+                .collect::<Vec<_>>(),
+            [80, 100, 120]
+        );
+    }
+
     #[test]
     pub fn test_resolve_language_servers() {
         fn language_server_names(names: &[&str]) -> Vec<LanguageServerName> {
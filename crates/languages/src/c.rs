@@ -23,6 +23,14 @@ impl super::LspAdapter for CLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: Self::SERVER_NAME.into(),
+            arguments: Vec::new(),
+            env: None,
+        })
+    }
+
     async fn check_if_user_installed(
         &self,
         delegate: &dyn LspAdapterDelegate,
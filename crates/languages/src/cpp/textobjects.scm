@@ -0,0 +1,12 @@
+(function_definition
+    body: (_) @function.inside)
+
+[
+    (string_literal)
+    (raw_string_literal)
+] @string.around
+
+(comment) @comment.around
+
+(argument_list (_) @argument.around)
+(parameter_list (_) @argument.around)
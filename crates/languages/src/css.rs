@@ -37,6 +37,14 @@ impl LspAdapter for CssLspAdapter {
         LanguageServerName("vscode-css-language-server".into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: "vscode-css-language-server".into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
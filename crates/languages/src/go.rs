@@ -50,6 +50,14 @@ impl super::LspAdapter for GoLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: Self::SERVER_NAME.into(),
+            arguments: server_binary_arguments(),
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         delegate: &dyn LspAdapterDelegate,
@@ -0,0 +1,17 @@
+(call_expression
+    function: [
+        (identifier) @call.name
+        (selector_expression field: (field_identifier) @call.name)
+    ]
+    arguments: (argument_list) @call.arguments)
+
+(function_declaration
+    name: (identifier) @definition.name
+    parameters: (parameter_list) @definition.parameters)
+
+(method_declaration
+    name: (field_identifier) @definition.name
+    parameters: (parameter_list) @definition.parameters)
+
+(parameter_declaration name: (identifier) @parameter)
+(variadic_parameter_declaration name: (identifier) @parameter)
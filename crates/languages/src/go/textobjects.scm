@@ -0,0 +1,18 @@
+(function_declaration
+    body: (_) @function.inside)
+
+(method_declaration
+    body: (_) @function.inside)
+
+(func_literal
+    body: (_) @function.inside)
+
+[
+    (interpreted_string_literal)
+    (raw_string_literal)
+] @string.around
+
+(comment) @comment.around
+
+(argument_list (_) @argument.around)
+(parameter_list (_) @argument.around)
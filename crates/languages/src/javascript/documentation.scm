@@ -0,0 +1,22 @@
+(function_declaration
+    parameters: (formal_parameters) @parameters) @item
+
+(generator_function_declaration
+    parameters: (formal_parameters) @parameters) @item
+
+(method_definition
+    parameters: (formal_parameters) @parameters) @item
+
+(lexical_declaration
+    (variable_declarator
+        value: [
+            (arrow_function parameters: (formal_parameters) @parameters)
+            (function_expression parameters: (formal_parameters) @parameters)
+        ])) @item
+
+(formal_parameters
+    [
+        (identifier) @parameter
+        (assignment_pattern left: (identifier) @parameter)
+        (rest_pattern (identifier) @parameter)
+    ])
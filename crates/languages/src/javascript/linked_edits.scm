@@ -0,0 +1,3 @@
+(jsx_element
+  (jsx_opening_element [(identifier) (member_expression)] @open)
+  (jsx_closing_element [(identifier) (member_expression)] @close))
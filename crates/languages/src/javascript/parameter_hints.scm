@@ -0,0 +1,23 @@
+(call_expression
+    function: [
+        (identifier) @call.name
+        (member_expression property: (property_identifier) @call.name)
+    ]
+    arguments: (arguments) @call.arguments)
+
+(function_declaration
+    name: (identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(method_definition
+    name: (property_identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(variable_declarator
+    name: (identifier) @definition.name
+    value: [
+        (arrow_function parameters: (formal_parameters) @definition.parameters)
+        (function_expression parameters: (formal_parameters) @definition.parameters)
+    ])
+
+(formal_parameters (_) @parameter)
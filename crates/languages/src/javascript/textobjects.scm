@@ -0,0 +1,21 @@
+(function_declaration
+    body: (_) @function.inside)
+
+(function_expression
+    body: (_) @function.inside)
+
+(method_definition
+    body: (_) @function.inside)
+
+(arrow_function
+    body: (statement_block) @function.inside)
+
+[
+    (string)
+    (template_string)
+] @string.around
+
+(comment) @comment.around
+
+(arguments (_) @argument.around)
+(formal_parameters (_) @argument.around)
@@ -139,6 +139,14 @@ impl LspAdapter for JsonLspAdapter {
         LanguageServerName("json-language-server".into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: "vscode-json-language-server".into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
@@ -42,6 +42,14 @@ impl LspAdapter for PythonLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: "pyright-langserver".into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
@@ -0,0 +1,9 @@
+(function_definition
+    body: (_) @function.inside)
+
+(string) @string.around
+
+(comment) @comment.around
+
+(argument_list (_) @argument.around)
+(parameters (_) @argument.around)
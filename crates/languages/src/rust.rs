@@ -26,6 +26,17 @@ pub struct RustLspAdapter;
 
 impl RustLspAdapter {
     const SERVER_NAME: &'static str = "rust-analyzer";
+
+    /// The name of the release asset for the given OS and architecture.
+    fn asset_name(os: &str, arch: &str) -> Result<String> {
+        let os = match os {
+            "macos" => "apple-darwin",
+            "linux" => "unknown-linux-gnu",
+            "windows" => "pc-windows-msvc",
+            other => bail!("Running on unsupported os: {other}"),
+        };
+        Ok(format!("rust-analyzer-{arch}-{os}.gz"))
+    }
 }
 
 #[async_trait(?Send)]
@@ -34,6 +45,14 @@ impl LspAdapter for RustLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: Self::SERVER_NAME.into(),
+            arguments: Vec::new(),
+            env: None,
+        })
+    }
+
     async fn check_if_user_installed(
         &self,
         delegate: &dyn LspAdapterDelegate,
@@ -93,13 +112,7 @@ impl LspAdapter for RustLspAdapter {
             delegate.http_client(),
         )
         .await?;
-        let os = match consts::OS {
-            "macos" => "apple-darwin",
-            "linux" => "unknown-linux-gnu",
-            "windows" => "pc-windows-msvc",
-            other => bail!("Running on unsupported os: {other}"),
-        };
-        let asset_name = format!("rust-analyzer-{}-{os}.gz", consts::ARCH);
+        let asset_name = Self::asset_name(consts::OS, consts::ARCH)?;
         let asset = release
             .assets
             .iter()
@@ -157,6 +170,32 @@ impl LspAdapter for RustLspAdapter {
         get_cached_server_binary(container_dir).await
     }
 
+    async fn fetch_remote_server_download(
+        &self,
+        os: &str,
+        arch: &str,
+        delegate: &dyn LspAdapterDelegate,
+    ) -> Result<Option<RemoteServerDownload>> {
+        let release = latest_github_release(
+            "rust-lang/rust-analyzer",
+            true,
+            false,
+            delegate.http_client(),
+        )
+        .await?;
+        let asset_name = Self::asset_name(os, arch)?;
+        let asset = release
+            .assets
+            .iter()
+            .find(|asset| asset.name == asset_name)
+            .with_context(|| format!("no asset found matching `{asset_name:?}`"))?;
+        Ok(Some(RemoteServerDownload {
+            file_name: format!("rust-analyzer-{}", release.tag_name),
+            url: asset.browser_download_url.clone(),
+            gzipped: true,
+        }))
+    }
+
     async fn installation_test_binary(
         &self,
         container_dir: PathBuf,
@@ -0,0 +1,10 @@
+(function_item
+    parameters: (parameters) @parameters
+    return_type: (_)? @return_type) @item
+
+(function_signature_item
+    parameters: (parameters) @parameters
+    return_type: (_)? @return_type) @item
+
+(parameters
+    (parameter pattern: (identifier) @parameter))
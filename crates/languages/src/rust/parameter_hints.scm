@@ -0,0 +1,18 @@
+(call_expression
+    function: [
+        (identifier) @call.name
+        (scoped_identifier name: (identifier) @call.name)
+        (field_expression field: (field_identifier) @call.name)
+    ]
+    arguments: (arguments) @call.arguments)
+
+(function_item
+    name: (identifier) @definition.name
+    parameters: (parameters) @definition.parameters)
+
+(function_signature_item
+    name: (identifier) @definition.name
+    parameters: (parameters) @definition.parameters)
+
+(parameters
+    (parameter pattern: (_) @parameter))
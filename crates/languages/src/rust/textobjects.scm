@@ -0,0 +1,20 @@
+(function_item
+    body: (_) @function.inside)
+
+(closure_expression
+    body: (block) @function.inside)
+
+[
+    (string_literal)
+    (raw_string_literal)
+] @string.around
+
+[
+    (line_comment)
+    (block_comment)
+] @comment.around
+
+(arguments (_) @argument.around)
+(parameters (_) @argument.around)
+(closure_parameters (_) @argument.around)
+(type_arguments (_) @argument.around)
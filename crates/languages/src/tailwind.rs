@@ -45,6 +45,14 @@ impl LspAdapter for TailwindLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: Self::SERVER_NAME.into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn check_if_user_installed(
         &self,
         _delegate: &dyn LspAdapterDelegate,
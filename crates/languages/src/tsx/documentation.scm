@@ -0,0 +1,41 @@
+(function_declaration
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(generator_function_declaration
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(function_signature
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(method_definition
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(method_signature
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(abstract_method_signature
+    parameters: (formal_parameters) @parameters
+    return_type: (type_annotation)? @return_type) @item
+
+(lexical_declaration
+    (variable_declarator
+        value: [
+            (arrow_function
+                parameters: (formal_parameters) @parameters
+                return_type: (type_annotation)? @return_type)
+            (function_expression
+                parameters: (formal_parameters) @parameters
+                return_type: (type_annotation)? @return_type)
+        ])) @item
+
+(formal_parameters
+    [
+        (required_parameter pattern: (identifier) @parameter)
+        (required_parameter pattern: (rest_pattern (identifier) @parameter))
+        (optional_parameter pattern: (identifier) @parameter)
+    ])
@@ -97,6 +97,14 @@ impl LspAdapter for TypeScriptLspAdapter {
         LanguageServerName(Self::SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: Self::SERVER_NAME.into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
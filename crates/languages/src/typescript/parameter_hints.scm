@@ -0,0 +1,35 @@
+(call_expression
+    function: [
+        (identifier) @call.name
+        (member_expression property: (property_identifier) @call.name)
+    ]
+    arguments: (arguments) @call.arguments)
+
+(function_declaration
+    name: (identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(function_signature
+    name: (identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(method_definition
+    name: (property_identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(method_signature
+    name: (property_identifier) @definition.name
+    parameters: (formal_parameters) @definition.parameters)
+
+(variable_declarator
+    name: (identifier) @definition.name
+    value: [
+        (arrow_function parameters: (formal_parameters) @definition.parameters)
+        (function_expression parameters: (formal_parameters) @definition.parameters)
+    ])
+
+(formal_parameters
+    [
+        (required_parameter pattern: (_) @parameter)
+        (optional_parameter pattern: (_) @parameter)
+    ])
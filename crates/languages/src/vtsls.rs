@@ -56,6 +56,14 @@ impl LspAdapter for VtslsLspAdapter {
         LanguageServerName(SERVER_NAME.into())
     }
 
+    fn remote_server_command(&self) -> Option<LanguageServerBinary> {
+        Some(LanguageServerBinary {
+            path: SERVER_NAME.into(),
+            arguments: vec!["--stdio".into()],
+            env: None,
+        })
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
@@ -14,7 +14,8 @@ use language::{
     AutoindentMode, Buffer, BufferChunks, BufferRow, BufferSnapshot, Capability, CharKind, Chunk,
     CursorShape, DiagnosticEntry, File, IndentGuide, IndentSize, Language, LanguageScope,
     OffsetRangeExt, OffsetUtf16, Outline, OutlineItem, Point, PointUtf16, Selection, TextDimension,
-    ToOffset as _, ToOffsetUtf16 as _, ToPoint as _, ToPointUtf16 as _, TransactionId, Unclipped,
+    TextObject, ToOffset as _, ToOffsetUtf16 as _, ToPoint as _, ToPointUtf16 as _, TransactionId,
+    Unclipped,
 };
 use smallvec::SmallVec;
 use std::{
@@ -1670,11 +1671,7 @@ impl MultiBuffer {
             .and_then(|(buffer, offset, _)| buffer.read(cx).language_at(offset))
     }
 
-    pub fn settings_at<'a, T: ToOffset>(
-        &self,
-        point: T,
-        cx: &'a AppContext,
-    ) -> &'a LanguageSettings {
+    pub fn settings_at<T: ToOffset>(&self, point: T, cx: &AppContext) -> Arc<LanguageSettings> {
         let mut language = None;
         let mut file = None;
         if let Some((buffer, offset, _)) = self.point_to_buffer_offset(point, cx) {
@@ -3284,6 +3281,35 @@ impl MultiBufferSnapshot {
             .flatten()
     }
 
+    /// Returns the ranges of the bodies of the functions that lie within the given range.
+    pub fn function_body_ranges<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> impl Iterator<Item = Range<usize>> + '_ {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        self.excerpts_for_range(range.clone())
+            .flat_map(move |(excerpt, excerpt_offset)| {
+                let excerpt_buffer_start = excerpt.range.context.start.to_offset(&excerpt.buffer);
+                let excerpt_buffer_end = excerpt.range.context.end.to_offset(&excerpt.buffer);
+
+                excerpt
+                    .buffer
+                    .function_body_ranges(excerpt.range.context.clone())
+                    .filter(move |body_range| {
+                        body_range.start >= excerpt_buffer_start
+                            && body_range.end <= excerpt_buffer_end
+                    })
+                    .map(move |body_range| {
+                        // Re-base onto the excerpts coordinates in the multibuffer
+                        excerpt_offset + body_range.start - excerpt_buffer_start
+                            ..excerpt_offset + body_range.end - excerpt_buffer_start
+                    })
+            })
+            .filter(move |body_range| {
+                body_range.start >= range.start && body_range.end <= range.end
+            })
+    }
+
     pub fn runnable_ranges(
         &self,
         range: Range<Anchor>,
@@ -3389,11 +3415,7 @@ impl MultiBufferSnapshot {
             .and_then(|(buffer, offset)| buffer.language_at(offset))
     }
 
-    pub fn settings_at<'a, T: ToOffset>(
-        &'a self,
-        point: T,
-        cx: &'a AppContext,
-    ) -> &'a LanguageSettings {
+    pub fn settings_at<T: ToOffset>(&self, point: T, cx: &AppContext) -> Arc<LanguageSettings> {
         let mut language = None;
         let mut file = None;
         if let Some((buffer, offset)) = self.point_to_buffer_offset(point) {
@@ -3624,6 +3646,21 @@ impl MultiBufferSnapshot {
         Some(excerpt.map_range_from_buffer(ancestor_buffer_range))
     }
 
+    pub fn range_for_text_object<T: ToOffset>(
+        &self,
+        range: Range<T>,
+        text_object: TextObject,
+    ) -> Option<Range<usize>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let excerpt = self.excerpt_containing(range.clone())?;
+
+        let text_object_buffer_range = excerpt
+            .buffer()
+            .range_for_text_object(excerpt.map_range_to_buffer(range), text_object)?;
+
+        Some(excerpt.map_range_from_buffer(text_object_buffer_range))
+    }
+
     pub fn outline(&self, theme: Option<&SyntaxTheme>) -> Option<Outline<Anchor>> {
         let (excerpt_id, _, buffer) = self.as_singleton()?;
         let outline = buffer.outline(theme)?;
@@ -156,6 +156,30 @@ pub fn themes_dir() -> &'static PathBuf {
     THEMES_DIR.get_or_init(|| config_dir().join("themes"))
 }
 
+/// Returns the path to the dictionaries directory.
+///
+/// This is where the Hunspell dictionaries used for spell checking are looked for first.
+pub fn dictionaries_dir() -> &'static PathBuf {
+    static DICTIONARIES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    DICTIONARIES_DIR.get_or_init(|| config_dir().join("dictionaries"))
+}
+
+/// Returns the path to the user dictionary file.
+///
+/// This is where the words added to the dictionary while spell checking are stored.
+pub fn user_dictionary_file() -> &'static PathBuf {
+    static USER_DICTIONARY_FILE: OnceLock<PathBuf> = OnceLock::new();
+    USER_DICTIONARY_FILE.get_or_init(|| dictionaries_dir().join("user.txt"))
+}
+
+/// Returns the path to the snippets directory.
+///
+/// This is where the global snippets files are stored.
+pub fn snippets_dir() -> &'static PathBuf {
+    static SNIPPETS_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -230,6 +254,33 @@ pub fn remote_servers_dir() -> &'static PathBuf {
     REMOTE_SERVERS_DIR.get_or_init(|| support_dir().join("remote_servers"))
 }
 
+/// Returns the path to the directory containing the VS Code user configuration.
+pub fn vscode_user_dir() -> &'static PathBuf {
+    static VSCODE_USER_DIR: OnceLock<PathBuf> = OnceLock::new();
+    VSCODE_USER_DIR.get_or_init(|| {
+        if cfg!(target_os = "macos") {
+            return home_dir().join("Library/Application Support/Code/User");
+        }
+
+        dirs::config_dir()
+            .unwrap_or_else(|| home_dir().join(".config"))
+            .join("Code")
+            .join("User")
+    })
+}
+
+/// Returns the path to the VS Code `settings.json` file.
+pub fn vscode_settings_file() -> &'static PathBuf {
+    static VSCODE_SETTINGS_FILE: OnceLock<PathBuf> = OnceLock::new();
+    VSCODE_SETTINGS_FILE.get_or_init(|| vscode_user_dir().join("settings.json"))
+}
+
+/// Returns the path to the VS Code `keybindings.json` file.
+pub fn vscode_keybindings_file() -> &'static PathBuf {
+    static VSCODE_KEYBINDINGS_FILE: OnceLock<PathBuf> = OnceLock::new();
+    VSCODE_KEYBINDINGS_FILE.get_or_init(|| vscode_user_dir().join("keybindings.json"))
+}
+
 /// Returns the relative path to a `.zed` folder within a project.
 pub fn local_settings_folder_relative_path() -> &'static Path {
     Path::new(".zed")
@@ -245,6 +296,11 @@ pub fn local_tasks_file_relative_path() -> &'static Path {
     Path::new(".zed/tasks.json")
 }
 
+/// Returns the relative path to the snippets folder within a project.
+pub fn local_snippets_folder_relative_path() -> &'static Path {
+    Path::new(".zed/snippets")
+}
+
 /// Returns the relative path to a `.vscode/tasks.json` file within a project.
 pub fn local_vscode_tasks_file_relative_path() -> &'static Path {
     Path::new(".vscode/tasks.json")
@@ -0,0 +1,273 @@
+use collections::HashMap;
+use gpui::{Model, ModelContext, SharedString};
+use language::Buffer;
+use text::{Anchor, BufferId, Point, ToPoint};
+
+/// A bookmarked row of a buffer.
+#[derive(Clone, Debug)]
+pub struct Bookmark {
+    /// The start of the bookmarked row.
+    pub position: Anchor,
+    pub label: Option<SharedString>,
+}
+
+/// The bookmarks of a buffer, ordered by position.
+pub struct BufferBookmarks {
+    pub buffer: Model<Buffer>,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// The bookmarks of all buffers in a project.
+///
+/// Bookmarked buffers are kept open, so that their bookmarks follow the edits made to them.
+#[derive(Default)]
+pub struct BookmarkStore {
+    buffers: HashMap<BufferId, BufferBookmarks>,
+}
+
+impl BookmarkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bookmarks of the given buffer, ordered by position.
+    pub fn bookmarks_for_buffer(&self, buffer_id: BufferId) -> &[Bookmark] {
+        self.buffers
+            .get(&buffer_id)
+            .map_or(&[], |buffer_bookmarks| &buffer_bookmarks.bookmarks)
+    }
+
+    /// Returns the bookmarks of all buffers that have any.
+    pub fn buffers(&self) -> impl Iterator<Item = &BufferBookmarks> {
+        self.buffers.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Adds a bookmark on the given row, or removes the bookmark already on it.
+    ///
+    /// When the row is already bookmarked with a different label, the bookmark is relabeled instead
+    /// of being removed.
+    pub fn toggle(
+        &mut self,
+        buffer: Model<Buffer>,
+        row: u32,
+        label: Option<SharedString>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let buffer_id = buffer.read(cx).remote_id();
+        let snapshot = buffer.read(cx).snapshot();
+        let existing_ix = self
+            .bookmarks_for_buffer(buffer_id)
+            .iter()
+            .position(|bookmark| bookmark.position.to_point(&snapshot).row == row);
+
+        match existing_ix {
+            Some(ix) => {
+                let Some(buffer_bookmarks) = self.buffers.get_mut(&buffer_id) else {
+                    return;
+                };
+                let bookmark = &mut buffer_bookmarks.bookmarks[ix];
+                if label.is_some() && bookmark.label != label {
+                    bookmark.label = label;
+                } else {
+                    buffer_bookmarks.bookmarks.remove(ix);
+                    if buffer_bookmarks.bookmarks.is_empty() {
+                        self.buffers.remove(&buffer_id);
+                    }
+                }
+                cx.notify();
+            }
+            None => self.insert(buffer, row, label, cx),
+        }
+    }
+
+    /// Adds a bookmark on the given row, clipped to the buffer.
+    pub fn insert(
+        &mut self,
+        buffer: Model<Buffer>,
+        row: u32,
+        label: Option<SharedString>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let snapshot = buffer.read(cx).snapshot();
+        let position =
+            snapshot.anchor_before(snapshot.clip_point(Point::new(row, 0), text::Bias::Left));
+        let buffer_bookmarks =
+            self.buffers
+                .entry(snapshot.remote_id())
+                .or_insert_with(|| BufferBookmarks {
+                    buffer,
+                    bookmarks: Vec::new(),
+                });
+        let ix = buffer_bookmarks
+            .bookmarks
+            .partition_point(|bookmark| bookmark.position.cmp(&position, &snapshot).is_lt());
+        buffer_bookmarks
+            .bookmarks
+            .insert(ix, Bookmark { position, label });
+        cx.notify();
+    }
+
+    /// Removes the bookmark at the given position.
+    pub fn remove(&mut self, buffer_id: BufferId, position: Anchor, cx: &mut ModelContext<Self>) {
+        let Some(buffer_bookmarks) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        buffer_bookmarks
+            .bookmarks
+            .retain(|bookmark| bookmark.position != position);
+        if buffer_bookmarks.bookmarks.is_empty() {
+            self.buffers.remove(&buffer_id);
+        }
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, cx: &mut ModelContext<Self>) {
+        self.buffers.clear();
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{Context as _, TestAppContext};
+
+    fn bookmarked_rows(
+        bookmark_store: &Model<BookmarkStore>,
+        buffer: &Model<Buffer>,
+        cx: &TestAppContext,
+    ) -> Vec<(u32, Option<String>)> {
+        cx.read(|cx| {
+            let snapshot = buffer.read(cx).snapshot();
+            bookmark_store
+                .read(cx)
+                .bookmarks_for_buffer(snapshot.remote_id())
+                .iter()
+                .map(|bookmark| {
+                    (
+                        bookmark.position.to_point(&snapshot).row,
+                        bookmark.label.as_ref().map(|label| label.to_string()),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    #[gpui::test]
+    fn test_toggling_bookmarks(cx: &mut TestAppContext) {
+        let buffer = cx.new_model(|cx| Buffer::local("one\ntwo\nthree\nfour\n", cx));
+        let bookmark_store = cx.new_model(|_| BookmarkStore::new());
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 2, None, cx);
+            bookmark_store.toggle(buffer.clone(), 0, Some("first".into()), cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![(0, Some("first".to_string())), (2, None)]
+        );
+
+        // Toggling a bookmarked row with another label relabels its bookmark.
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 2, Some("third".into()), cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![
+                (0, Some("first".to_string())),
+                (2, Some("third".to_string()))
+            ]
+        );
+
+        // Toggling it with the same label or without a label removes it.
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 2, Some("third".into()), cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![(0, Some("first".to_string()))]
+        );
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 0, None, cx);
+        });
+        assert_eq!(bookmarked_rows(&bookmark_store, &buffer, cx), vec![]);
+        assert!(bookmark_store.read_with(cx, |bookmark_store, _| bookmark_store.is_empty()));
+
+        // Rows past the end of the buffer are clipped.
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 10, None, cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![(4, None)]
+        );
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            let buffer_id = buffer.read(cx).remote_id();
+            let position = bookmark_store.bookmarks_for_buffer(buffer_id)[0].position;
+            bookmark_store.remove(buffer_id, position, cx);
+        });
+        assert_eq!(bookmarked_rows(&bookmark_store, &buffer, cx), vec![]);
+        assert!(bookmark_store.read_with(cx, |bookmark_store, _| bookmark_store.is_empty()));
+    }
+
+    #[gpui::test]
+    fn test_bookmarks_follow_edits(cx: &mut TestAppContext) {
+        let buffer = cx.new_model(|cx| Buffer::local("one\ntwo\nthree\nfour\n", cx));
+        let bookmark_store = cx.new_model(|_| BookmarkStore::new());
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 3, None, cx);
+            bookmark_store.toggle(buffer.clone(), 1, None, cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![(1, None), (3, None)]
+        );
+
+        // Bookmarks move with the rows they're on.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(
+                [(Point::new(1, 3)..Point::new(1, 3), "\nnew\nnew")],
+                None,
+                cx,
+            );
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![(1, None), (5, None)]
+        );
+
+        // Bookmarks added after an edit are kept in order.
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 6, Some("last".into()), cx);
+            bookmark_store.toggle(buffer.clone(), 3, Some("new".into()), cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![
+                (1, None),
+                (3, Some("new".to_string())),
+                (5, None),
+                (6, Some("last".to_string()))
+            ]
+        );
+
+        // Toggling finds the bookmarks by their current row.
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(buffer.clone(), 5, None, cx);
+        });
+        assert_eq!(
+            bookmarked_rows(&bookmark_store, &buffer, cx),
+            vec![
+                (1, None),
+                (3, Some("new".to_string())),
+                (6, Some("last".to_string()))
+            ]
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
+    project_settings::ProjectSettings,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
     NoRepositoryError, ProjectPath,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{hash_map, HashMap};
 use futures::{channel::oneshot, stream::FuturesUnordered, StreamExt as _};
-use git::blame::Blame;
+use git::{
+    blame::Blame,
+    repository::{GitRepository, RepoPath},
+};
 use gpui::{
     AppContext, AsyncAppContext, Context as _, EventEmitter, Model, ModelContext, Task, WeakModel,
 };
@@ -18,14 +22,23 @@ use rpc::{
     proto::{self, AnyProtoClient, EnvelopedMessage, PeerId},
     ErrorExt as _, TypedEnvelope,
 };
+use settings::{EndOfLine, Settings as _, SettingsLocation, SettingsStore};
 use std::{io, path::Path, str::FromStr as _, sync::Arc};
-use text::BufferId;
+use text::{BufferId, LineEnding};
 use util::{debug_panic, maybe, ResultExt as _};
 use worktree::{
     File, PathChange, ProjectEntryId, RemoteWorktree, UpdatedGitRepositoriesSet, Worktree,
     WorktreeId,
 };
 
+/// The state of the git repository containing a buffer, as needed to build
+/// permalinks to it.
+#[derive(Clone, Debug, Default)]
+pub struct BufferGitInfo {
+    pub remote_url: Option<String>,
+    pub head_sha: Option<String>,
+}
+
 /// A set of open buffers.
 pub struct BufferStore {
     remote_id: Option<u64>,
@@ -300,17 +313,26 @@ impl BufferStore {
             let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
             cx.spawn(move |_, mut cx| async move {
                 let loaded = load_file.await?;
+                let is_large_file = cx.update(|cx| {
+                    ProjectSettings::get_global(cx)
+                        .large_files
+                        .is_large_file(&loaded.text)
+                })?;
                 let text_buffer = cx
                     .background_executor()
-                    .spawn(async move { text::Buffer::new(0, buffer_id, loaded.text) })
+                    .spawn(async move {
+                        text::Buffer::new_normalized(0, buffer_id, loaded.line_ending, loaded.text)
+                    })
                     .await;
                 cx.insert_model(reservation, |_| {
-                    Buffer::build(
+                    let mut buffer = Buffer::build(
                         text_buffer,
                         loaded.diff_base,
                         Some(loaded.file),
                         Capability::ReadWrite,
-                    )
+                    );
+                    buffer.set_large_file(is_large_file);
+                    buffer
                 })
             })
         });
@@ -464,7 +486,19 @@ impl BufferStore {
     ) -> Task<Result<()>> {
         let buffer = buffer_handle.read(cx);
         let text = buffer.as_rope().clone();
-        let line_ending = buffer.line_ending();
+        let line_ending = cx
+            .global::<SettingsStore>()
+            .editorconfig_properties(SettingsLocation {
+                worktree_id: worktree.entity_id().as_u64() as usize,
+                path: &path,
+            })
+            .and_then(|properties| match properties.end_of_line? {
+                EndOfLine::Lf => Some(LineEnding::Unix),
+                EndOfLine::Crlf => Some(LineEnding::Windows),
+                // Lone carriage returns aren't supported as line endings.
+                EndOfLine::Cr => None,
+            })
+            .unwrap_or_else(|| buffer.line_ending());
         let version = buffer.version();
         let buffer_id = buffer.remote_id();
         if buffer.file().is_some_and(|file| !file.is_created()) {
@@ -601,6 +635,89 @@ impl BufferStore {
         }
     }
 
+    pub fn buffer_git_info(
+        &self,
+        buffer: &Model<Buffer>,
+        remote_name: &str,
+        cx: &AppContext,
+    ) -> Task<Result<BufferGitInfo>> {
+        let buffer = buffer.read(cx);
+        let Some(file) = File::from_dyn(buffer.file()) else {
+            return Task::ready(Err(anyhow!("buffer has no file")));
+        };
+
+        match file.worktree.read(cx) {
+            Worktree::Local(worktree) => {
+                let Some(repo) = worktree.local_git_repo(&file.path) else {
+                    return Task::ready(Err(NoRepositoryError {}.into()));
+                };
+                Task::ready(Ok(BufferGitInfo {
+                    remote_url: repo.remote_url(remote_name),
+                    head_sha: repo.head_sha(),
+                }))
+            }
+            Worktree::Remote(worktree) => {
+                let request = proto::GetBufferGitInfo {
+                    project_id: worktree.project_id(),
+                    buffer_id: buffer.remote_id().into(),
+                    remote_name: remote_name.to_string(),
+                };
+                let client = worktree.client();
+                cx.background_executor().spawn(async move {
+                    let response = client.request(request).await?;
+                    Ok(BufferGitInfo {
+                        remote_url: response.remote_url,
+                        head_sha: response.head_sha,
+                    })
+                })
+            }
+        }
+    }
+
+    /// Loads the contents of the buffer's file in the HEAD commit of its git repository.
+    pub fn load_buffer_head_text(
+        &self,
+        buffer: &Model<Buffer>,
+        cx: &AppContext,
+    ) -> Task<Result<Option<String>>> {
+        let (repo, relative_path) = match local_git_repo_for_buffer(buffer, cx) {
+            Ok(repo) => repo,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        cx.background_executor()
+            .spawn(async move { Ok(repo.load_head_text(&relative_path)) })
+    }
+
+    /// Writes the contents of the buffer's file in the index of its git repository, and uses
+    /// them as the buffer's diff base.
+    pub fn set_buffer_index_text(
+        &self,
+        buffer: &Model<Buffer>,
+        index_text: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let (repo, relative_path) = match local_git_repo_for_buffer(buffer, cx) {
+            Ok(repo) => repo,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        // Diff bases are normalized to unix line endings, unlike the index.
+        let line_ending = buffer.read(cx).line_ending();
+        let content = match line_ending {
+            LineEnding::Unix => index_text.clone(),
+            LineEnding::Windows => index_text.replace('\n', line_ending.as_str()),
+        };
+        let buffer = buffer.downgrade();
+        cx.spawn(|_, mut cx| async move {
+            cx.background_executor()
+                .spawn(async move { repo.set_index_text(&relative_path, &content) })
+                .await
+                .with_context(|| format!("failed to update the index of {relative_path:?}"))?;
+            buffer.update(&mut cx, |buffer, cx| {
+                buffer.set_diff_base(Some(index_text), cx);
+            })
+        })
+    }
+
     fn add_buffer(&mut self, buffer: Model<Buffer>, cx: &mut ModelContext<Self>) -> Result<()> {
         let remote_id = buffer.read(cx).remote_id();
         let is_remote = buffer.read(cx).replica_id() != 0;
@@ -1256,6 +1373,24 @@ impl BufferStore {
         Ok(serialize_blame_buffer_response(blame))
     }
 
+    pub async fn handle_get_buffer_git_info(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::GetBufferGitInfo>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::GetBufferGitInfoResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let info = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.get_existing(buffer_id)?;
+                anyhow::Ok(this.buffer_git_info(&buffer, &envelope.payload.remote_name, cx))
+            })??
+            .await?;
+        Ok(proto::GetBufferGitInfoResponse {
+            remote_url: info.remote_url,
+            head_sha: info.head_sha,
+        })
+    }
+
     pub async fn wait_for_loading_buffer(
         mut receiver: postage::watch::Receiver<Option<Result<Model<Buffer>, Arc<anyhow::Error>>>>,
     ) -> Result<Model<Buffer>, Arc<anyhow::Error>> {
@@ -1385,3 +1520,25 @@ fn deserialize_blame_buffer_response(response: proto::BlameBufferResponse) -> gi
         remote_url: response.remote_url,
     }
 }
+
+/// Returns the git repository containing the file of the buffer, in a local worktree, and the
+/// path of the file in it.
+fn local_git_repo_for_buffer(
+    buffer: &Model<Buffer>,
+    cx: &AppContext,
+) -> Result<(Arc<dyn GitRepository>, RepoPath)> {
+    let buffer = buffer.read(cx);
+    let file = File::from_dyn(buffer.file()).context("buffer has no file")?;
+    let Worktree::Local(worktree) = file.worktree.read(cx) else {
+        anyhow::bail!("git changes can only be staged in local projects");
+    };
+    let worktree = worktree.snapshot();
+    let (repo_entry, local_repo_entry) = match worktree.repo_for_path(&file.path) {
+        Some(repo_for_path) => repo_for_path,
+        None => anyhow::bail!(NoRepositoryError {}),
+    };
+    let relative_path = repo_entry
+        .relativize(&worktree, &file.path)
+        .context("failed to relativize buffer path")?;
+    Ok((local_repo_entry.repo().clone(), relative_path))
+}
@@ -148,6 +148,9 @@ pub(crate) struct GetCompletions {
     pub context: CompletionContext,
 }
 
+#[derive(Clone)]
+pub(crate) struct GetCodeLens;
+
 #[derive(Clone)]
 pub(crate) struct GetCodeActions {
     pub range: Range<Anchor>,
@@ -2054,6 +2057,148 @@ impl LspCommand for GetCodeActions {
     }
 }
 
+/// Code lenses are returned as code actions that run the lens' command, so that clicking
+/// a lens goes through [`Project::apply_code_action`], remotely as well.
+#[async_trait(?Send)]
+impl LspCommand for GetCodeLens {
+    type Response = Vec<CodeAction>;
+    type LspRequest = lsp::request::CodeLensRequest;
+    type ProtoRequest = proto::GetCodeLens;
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .code_lens_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &AppContext,
+    ) -> lsp::CodeLensParams {
+        lsp::CodeLensParams {
+            text_document: lsp::TextDocumentIdentifier::new(
+                lsp::Url::from_file_path(path).unwrap(),
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    async fn response_from_lsp(
+        self,
+        lenses: Option<Vec<lsp::CodeLens>>,
+        project: Model<Project>,
+        buffer: Model<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncAppContext,
+    ) -> Result<Vec<CodeAction>> {
+        let language_server = project
+            .read_with(&cx, |project, _| project.language_server_for_id(server_id))?
+            .ok_or_else(|| anyhow!("no language server with id {server_id}"))?;
+        let can_resolve = language_server
+            .capabilities()
+            .code_lens_provider
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false);
+
+        let mut code_actions = Vec::new();
+        for mut lens in lenses.unwrap_or_default() {
+            // Lenses are often sent without their command, which is expensive to compute
+            // (such as a reference count), and have to be resolved to be shown.
+            if lens.command.is_none() && can_resolve {
+                match language_server
+                    .request::<lsp::request::CodeLensResolve>(lens.clone())
+                    .await
+                {
+                    Ok(resolved_lens) => lens = resolved_lens,
+                    Err(error) => log::error!("failed to resolve code lens: {error:#}"),
+                }
+            }
+            let Some(command) = lens.command else {
+                continue;
+            };
+
+            let range = buffer.read_with(&cx, |buffer, _| {
+                let start = buffer.clip_point_utf16(point_from_lsp(lens.range.start), Bias::Left);
+                let end = buffer.clip_point_utf16(point_from_lsp(lens.range.end), Bias::Left);
+                buffer.anchor_before(start)..buffer.anchor_after(end)
+            })?;
+            code_actions.push(CodeAction {
+                server_id,
+                range,
+                lsp_action: lsp::CodeAction {
+                    title: command.title.clone(),
+                    command: Some(command),
+                    ..Default::default()
+                },
+            });
+        }
+        Ok(code_actions)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetCodeLens {
+        proto::GetCodeLens {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetCodeLens,
+        _: Model<Project>,
+        buffer: Model<Buffer>,
+        mut cx: AsyncAppContext,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        lenses: Vec<CodeAction>,
+        _: &mut Project,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut AppContext,
+    ) -> proto::GetCodeLensResponse {
+        proto::GetCodeLensResponse {
+            lenses: lenses.iter().map(Project::serialize_code_action).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetCodeLensResponse,
+        _: Model<Project>,
+        buffer: Model<Buffer>,
+        mut cx: AsyncAppContext,
+    ) -> Result<Vec<CodeAction>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .lenses
+            .into_iter()
+            .map(Project::deserialize_code_action)
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetCodeLens) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 impl GetCodeActions {
     fn supported_code_action_kinds(
         capabilities: AdapterServerCapabilities,
@@ -2174,7 +2319,7 @@ impl LspCommand for OnTypeFormatting {
             .await?;
 
         let options = buffer.update(&mut cx, |buffer, cx| {
-            lsp_formatting_options(language_settings(buffer.language(), buffer.file(), cx))
+            lsp_formatting_options(&language_settings(buffer.language(), buffer.file(), cx))
         })?;
 
         Ok(Self {
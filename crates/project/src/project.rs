@@ -1,3 +1,4 @@
+pub mod bookmark_store;
 pub mod buffer_store;
 pub mod connection_manager;
 pub mod debounced_delay;
@@ -17,7 +18,8 @@ mod yarn;
 
 use anyhow::{anyhow, bail, Context as _, Result};
 use async_trait::async_trait;
-use buffer_store::{BufferStore, BufferStoreEvent};
+use bookmark_store::BookmarkStore;
+use buffer_store::{BufferGitInfo, BufferStore, BufferStoreEvent};
 use client::{
     proto, Client, Collaborator, DevServerProjectId, PendingEntitySubscription, ProjectId,
     TypedEnvelope, UserStore,
@@ -33,7 +35,11 @@ use futures::{
     AsyncWriteExt, Future, FutureExt, StreamExt,
 };
 use fuzzy::CharBag;
-use git::{blame::Blame, repository::GitRepository};
+use git::{
+    blame::Blame,
+    repository::{Branch, GitRepository, RepoPath},
+    status::StagingStatus,
+};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use gpui::{
     AnyModel, AppContext, AsyncAppContext, BackgroundExecutor, BorrowAppContext, Context, Entity,
@@ -44,7 +50,7 @@ use itertools::Itertools;
 use language::{
     language_settings::{
         language_settings, AllLanguageSettings, FormatOnSave, Formatter, InlayHintKind,
-        LanguageSettings, SelectedFormatter,
+        LanguageSettings, SelectedFormatter, WhitespaceFixesOnSave,
     },
     markdown, point_to_lsp, prepare_completion_documentation,
     proto::{
@@ -68,8 +74,8 @@ use lsp_command::*;
 use node_runtime::NodeRuntime;
 use parking_lot::{Mutex, RwLock};
 use paths::{
-    local_settings_file_relative_path, local_tasks_file_relative_path,
-    local_vscode_tasks_file_relative_path,
+    local_settings_file_relative_path, local_snippets_folder_relative_path,
+    local_tasks_file_relative_path, local_vscode_tasks_file_relative_path,
 };
 use postage::watch;
 use prettier_support::{DefaultPrettier, PrettierInstance};
@@ -80,7 +86,9 @@ use rpc::{proto::AddWorktree, ErrorCode};
 use search::SearchQuery;
 use search_history::SearchHistory;
 use serde::Serialize;
-use settings::{watch_config_file, Settings, SettingsLocation, SettingsStore};
+use settings::{
+    watch_config_file, Settings, SettingsLocation, SettingsStore, EDITORCONFIG_FILE_NAME,
+};
 use sha2::{Digest, Sha256};
 use similar::{ChangeTag, TextDiff};
 use smol::{
@@ -142,6 +150,8 @@ const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
 
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
+const MAX_SEARCH_RESULT_FILES: usize = 5_000;
+const MAX_SEARCH_RESULT_RANGES: usize = 10_000;
 
 pub trait Item {
     fn try_open(
@@ -200,17 +210,23 @@ pub struct Project {
     user_store: Model<UserStore>,
     fs: Arc<dyn Fs>,
     ssh_session: Option<Arc<SshSession>>,
+    /// Whether the user trusts the remote host of an SSH project to start language
+    /// servers and run tasks on it.
+    ssh_host_trusted: bool,
+    ssh_tasks_buffers: HashMap<WorktreeId, Option<Model<Buffer>>>,
     client_state: ProjectClientState,
     collaborators: HashMap<proto::PeerId, Collaborator>,
     client_subscriptions: Vec<client::Subscription>,
     worktree_store: Model<WorktreeStore>,
     buffer_store: Model<BufferStore>,
+    bookmark_store: Model<BookmarkStore>,
     _subscriptions: Vec<gpui::Subscription>,
     shared_buffers: HashMap<proto::PeerId, HashSet<BufferId>>,
     #[allow(clippy::type_complexity)]
     loading_worktrees:
         HashMap<Arc<Path>, Shared<Task<Result<Model<Worktree>, Arc<anyhow::Error>>>>>,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
+    semantic_tokens: HashMap<BufferId, HashMap<LanguageServerId, CachedSemanticTokens>>,
     buffers_being_formatted: HashSet<BufferId>,
     buffers_needing_diff: HashSet<WeakModel<Buffer>>,
     git_diff_debouncer: DebouncedDelay<Self>,
@@ -467,6 +483,24 @@ pub struct CodeAction {
     pub lsp_action: lsp::CodeAction,
 }
 
+/// A token of a buffer classified by a language server, such as a parameter or a mutable
+/// variable, to highlight it more accurately than its syntax allows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    /// The type of the token, such as `parameter` or `variable`.
+    pub token_type: String,
+    /// The modifiers of the token, such as `mutable` or `declaration`.
+    pub modifiers: Vec<String>,
+}
+
+/// The last semantic tokens a language server returned for a buffer, encoded the way they were
+/// sent, to request only the changes to them next time.
+struct CachedSemanticTokens {
+    result_id: Option<String>,
+    data: Vec<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResolveState {
     Resolved,
@@ -705,12 +739,14 @@ impl Project {
         client.add_model_request_handler(Self::handle_apply_code_action);
         client.add_model_request_handler(Self::handle_on_type_formatting);
         client.add_model_request_handler(Self::handle_inlay_hints);
+        client.add_model_request_handler(Self::handle_semantic_tokens);
         client.add_model_request_handler(Self::handle_resolve_inlay_hint);
         client.add_model_request_handler(Self::handle_refresh_inlay_hints);
         client.add_model_request_handler(Self::handle_reload_buffers);
         client.add_model_request_handler(Self::handle_synchronize_buffers);
         client.add_model_request_handler(Self::handle_format_buffers);
         client.add_model_request_handler(Self::handle_lsp_command::<GetCodeActions>);
+        client.add_model_request_handler(Self::handle_lsp_command::<GetCodeLens>);
         client.add_model_request_handler(Self::handle_lsp_command::<GetCompletions>);
         client.add_model_request_handler(Self::handle_lsp_command::<GetHover>);
         client.add_model_request_handler(Self::handle_lsp_command::<GetDefinition>);
@@ -744,6 +780,7 @@ impl Project {
         client.add_model_message_handler(BufferStore::handle_update_diff_base);
         client.add_model_request_handler(BufferStore::handle_save_buffer);
         client.add_model_request_handler(BufferStore::handle_blame_buffer);
+        client.add_model_request_handler(BufferStore::handle_get_buffer_git_info);
     }
 
     pub fn local(
@@ -759,7 +796,7 @@ impl Project {
             cx.spawn(move |this, cx| Self::send_buffer_ordered_messages(this, rx, cx))
                 .detach();
             let tasks = Inventory::new(cx);
-            let global_snippets_dir = paths::config_dir().join("snippets");
+            let global_snippets_dir = paths::snippets_dir().to_path_buf();
             let snippets =
                 SnippetProvider::new(fs.clone(), BTreeSet::from_iter([global_snippets_dir]), cx);
 
@@ -779,9 +816,11 @@ impl Project {
                 collaborators: Default::default(),
                 worktree_store,
                 buffer_store,
+                bookmark_store: cx.new_model(|_| BookmarkStore::new()),
                 shared_buffers: Default::default(),
                 loading_worktrees: Default::default(),
                 buffer_snapshots: Default::default(),
+                semantic_tokens: Default::default(),
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
                 client_subscriptions: Vec::new(),
@@ -800,6 +839,8 @@ impl Project {
                 user_store,
                 fs,
                 ssh_session: None,
+                ssh_host_trusted: false,
+                ssh_tasks_buffers: HashMap::default(),
                 next_entry_id: Default::default(),
                 next_diagnostic_group_id: Default::default(),
                 diagnostics: Default::default(),
@@ -818,6 +859,8 @@ impl Project {
                 nonce: StdRng::from_entropy().gen(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
+                    remote_outputs: HashMap::default(),
+                    next_remote_id: 0,
                 },
                 current_lsp_settings: ProjectSettings::get_global(cx).lsp.clone(),
                 node: Some(node),
@@ -848,6 +891,8 @@ impl Project {
 
             ssh.add_message_handler(cx.weak_model(), Self::handle_update_worktree);
             ssh.add_message_handler(cx.weak_model(), Self::handle_create_buffer_for_peer);
+            ssh.add_message_handler(cx.weak_model(), Self::handle_terminal_output);
+            ssh.add_message_handler(cx.weak_model(), Self::handle_terminal_exited);
             ssh.add_message_handler(buffer_store.clone(), BufferStore::handle_update_buffer_file);
             ssh.add_message_handler(buffer_store.clone(), BufferStore::handle_update_diff_base);
 
@@ -927,7 +972,7 @@ impl Project {
         let this = cx.new_model(|cx| {
             let replica_id = response.payload.replica_id as ReplicaId;
             let tasks = Inventory::new(cx);
-            let global_snippets_dir = paths::config_dir().join("snippets");
+            let global_snippets_dir = paths::snippets_dir().to_path_buf();
             let snippets =
                 SnippetProvider::new(fs.clone(), BTreeSet::from_iter([global_snippets_dir]), cx);
             let yarn = YarnPathStore::new(fs.clone(), cx);
@@ -949,6 +994,7 @@ impl Project {
             let mut this = Self {
                 buffer_ordered_messages_tx: tx,
                 buffer_store: buffer_store.clone(),
+                bookmark_store: cx.new_model(|_| BookmarkStore::new()),
                 worktree_store,
                 shared_buffers: Default::default(),
                 loading_worktrees: Default::default(),
@@ -963,6 +1009,8 @@ impl Project {
                 yarn,
                 fs,
                 ssh_session: None,
+                ssh_host_trusted: false,
+                ssh_tasks_buffers: HashMap::default(),
                 next_entry_id: Default::default(),
                 next_diagnostic_group_id: Default::default(),
                 diagnostic_summaries: Default::default(),
@@ -1007,9 +1055,12 @@ impl Project {
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
                 buffer_snapshots: Default::default(),
+                semantic_tokens: Default::default(),
                 nonce: StdRng::from_entropy().gen(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
+                    remote_outputs: HashMap::default(),
+                    next_remote_id: 0,
                 },
                 current_lsp_settings: ProjectSettings::get_global(cx).lsp.clone(),
                 node: None,
@@ -1235,7 +1286,7 @@ impl Project {
                     }
                 }
                 language_formatters_to_check
-                    .push((buffer_file.map(|f| f.worktree_id(cx)), settings.clone()));
+                    .push((buffer_file.map(|f| f.worktree_id(cx)), settings));
             }
         }
 
@@ -1386,6 +1437,40 @@ impl Project {
         server.ssh_connection_string.is_some()
     }
 
+    pub fn ssh_session(&self) -> Option<Arc<SshSession>> {
+        self.ssh_session.clone()
+    }
+
+    /// Whether code from the project may be executed on the remote host of an SSH
+    /// project. Other projects are always trusted.
+    pub fn is_ssh_host_trusted(&self) -> bool {
+        self.ssh_session.is_none() || self.ssh_host_trusted
+    }
+
+    /// Allows language servers to start and tasks to run on the remote host of an SSH
+    /// project, starting the language servers of the buffers that are already open.
+    pub fn trust_ssh_host(&mut self, cx: &mut ModelContext<Self>) {
+        if self.ssh_host_trusted {
+            return;
+        }
+        self.ssh_host_trusted = true;
+
+        let language_servers_to_start = self
+            .buffer_store
+            .read(cx)
+            .buffers()
+            .filter_map(|buffer| {
+                let buffer = buffer.read(cx);
+                let file = File::from_dyn(buffer.file())?;
+                Some((file.worktree.clone(), buffer.language()?.clone()))
+            })
+            .collect::<Vec<_>>();
+        for (worktree, language) in language_servers_to_start {
+            self.start_language_servers(&worktree, language, cx);
+        }
+        cx.notify();
+    }
+
     pub fn ssh_connection_string(&self, cx: &ModelContext<Self>) -> Option<SharedString> {
         if self.is_local() {
             return None;
@@ -1419,6 +1504,10 @@ impl Project {
         &self.tasks
     }
 
+    pub fn bookmark_store(&self) -> &Model<BookmarkStore> {
+        &self.bookmark_store
+    }
+
     pub fn snippets(&self) -> &Model<SnippetProvider> {
         &self.snippets
     }
@@ -2176,13 +2265,18 @@ impl Project {
     ) {
         let buffer = buffer_handle.read(cx);
         let buffer_id = buffer.remote_id();
+        if buffer.is_large_file() {
+            return;
+        }
 
         if let Some(file) = File::from_dyn(buffer.file()) {
-            if !file.is_local() {
+            if !file.is_local() && self.ssh_session.is_none() {
                 return;
             }
 
-            let abs_path = file.abs_path(cx);
+            let Some(abs_path) = language_server_abs_path(file, cx) else {
+                return;
+            };
             let Some(uri) = lsp::Url::from_file_path(&abs_path).log_err() else {
                 return;
             };
@@ -2261,9 +2355,11 @@ impl Project {
         old_file: &File,
         cx: &mut AppContext,
     ) {
-        let old_path = match old_file.as_local() {
-            Some(local) => local.abs_path(cx),
-            None => return,
+        if !old_file.is_local() && self.ssh_session.is_none() {
+            return;
+        }
+        let Some(old_path) = language_server_abs_path(old_file, cx) else {
+            return;
         };
 
         buffer.update(cx, |buffer, cx| {
@@ -2279,6 +2375,7 @@ impl Project {
             }
 
             self.buffer_snapshots.remove(&buffer.remote_id());
+            self.semantic_tokens.remove(&buffer.remote_id());
             let file_url = lsp::Url::from_file_path(old_path).unwrap();
             for (_, language_server) in self.language_servers_for_buffer(buffer, cx) {
                 language_server
@@ -2491,7 +2588,7 @@ impl Project {
             BufferEvent::Edited { .. } => {
                 let buffer = buffer.read(cx);
                 let file = File::from_dyn(buffer.file())?;
-                let abs_path = file.as_local()?.abs_path(cx);
+                let abs_path = language_server_abs_path(file, cx)?;
                 let uri = lsp::Url::from_file_path(abs_path).unwrap();
                 let next_snapshot = buffer.text_snapshot();
 
@@ -2586,7 +2683,7 @@ impl Project {
             BufferEvent::Saved => {
                 let file = File::from_dyn(buffer.read(cx).file())?;
                 let worktree_id = file.worktree_id(cx);
-                let abs_path = file.as_local()?.abs_path(cx);
+                let abs_path = language_server_abs_path(file, cx)?;
                 let text_document = lsp::TextDocumentIdentifier {
                     uri: lsp::Url::from_file_path(abs_path).unwrap(),
                 };
@@ -2917,7 +3014,7 @@ impl Project {
         });
 
         let buffer_file = buffer.read(cx).file().cloned();
-        let settings = language_settings(Some(&new_language), buffer_file.as_ref(), cx).clone();
+        let settings = language_settings(Some(&new_language), buffer_file.as_ref(), cx);
         let buffer_file = File::from_dyn(buffer_file.as_ref());
         let worktree = buffer_file.as_ref().map(|f| f.worktree_id(cx));
         if let Some(prettier_plugins) = prettier_support::prettier_plugins_for_language(&settings) {
@@ -2927,9 +3024,12 @@ impl Project {
                 cx,
             );
         };
+        if buffer.read(cx).is_large_file() {
+            return;
+        }
         if let Some(file) = buffer_file {
             let worktree = file.worktree.clone();
-            if worktree.read(cx).is_local() {
+            if worktree.read(cx).is_local() || self.ssh_session.is_some() {
                 self.start_language_servers(&worktree, new_language, cx);
             }
         }
@@ -2944,7 +3044,10 @@ impl Project {
         let (root_file, is_local) =
             worktree.update(cx, |tree, cx| (tree.root_file(cx), tree.is_local()));
         let settings = language_settings(Some(&language), root_file.map(|f| f as _).as_ref(), cx);
-        if !settings.enable_language_server || !is_local {
+        if !settings.enable_language_server || !(is_local || self.ssh_session.is_some()) {
+            return;
+        }
+        if !self.is_ssh_host_trusted() {
             return;
         }
 
@@ -3016,6 +3119,11 @@ impl Project {
             return;
         }
 
+        if let Some(ssh_session) = self.ssh_session.clone() {
+            self.start_ssh_language_server(ssh_session, worktree_handle, adapter, language, cx);
+            return;
+        }
+
         let stderr_capture = Arc::new(Mutex::new(Some(String::new())));
         let lsp_adapter_delegate = ProjectLspAdapterDelegate::new(self, worktree_handle, cx);
         let pending_server = match self.languages.create_pending_language_server(
@@ -3115,6 +3223,200 @@ impl Project {
         self.language_server_ids.insert(key, server_id);
     }
 
+    /// Starts a language server on the remote host of an SSH project, proxying its stdio
+    /// over the remote connection.
+    ///
+    /// The server is taken from the `lsp.<server>.binary` settings, or else installed on
+    /// the remote host when its adapter supports it, or else looked up by name on the
+    /// remote `PATH`. Its initialization options only come from the `lsp.<server>.initialization_options`
+    /// settings, as adapters compute their default ones for this machine.
+    fn start_ssh_language_server(
+        &mut self,
+        ssh_session: Arc<SshSession>,
+        worktree_handle: &Model<Worktree>,
+        adapter: Arc<CachedLspAdapter>,
+        language: Arc<Language>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let worktree = worktree_handle.read(cx);
+        let worktree_id = worktree.id();
+        let worktree_path = worktree.abs_path();
+        let key = (worktree_id, adapter.name.clone());
+        let Some(mut command_prefix) = ssh_session.shell_command_prefix() else {
+            return;
+        };
+
+        let project_settings = ProjectSettings::get(
+            Some(SettingsLocation {
+                worktree_id: worktree_id.to_proto() as usize,
+                path: Path::new(""),
+            }),
+            cx,
+        );
+        let lsp = project_settings.lsp.get(&adapter.name.0);
+        let override_options = lsp.and_then(|s| s.initialization_options.clone());
+        let binary_settings = lsp.and_then(|s| s.binary.clone()).unwrap_or_default();
+        let Some(remote_dir) = shlex::try_quote(&worktree_path.to_string_lossy())
+            .log_err()
+            .map(|dir| dir.into_owned())
+        else {
+            return;
+        };
+        if command_prefix.is_empty() {
+            return;
+        }
+        let program = command_prefix.remove(0);
+
+        let stderr_capture = Arc::new(Mutex::new(Some(String::new())));
+        let lsp_adapter_delegate: Arc<dyn LspAdapterDelegate> =
+            ProjectLspAdapterDelegate::new(self, worktree_handle, cx);
+        let binary = cx.spawn({
+            let adapter = adapter.clone();
+            let delegate = lsp_adapter_delegate.clone();
+            move |_, _| async move {
+                let remote_command = adapter.adapter.remote_server_command();
+                let server_path = match binary_settings.path {
+                    Some(path) => Some(path),
+                    None => Self::install_ssh_language_server(&ssh_session, &adapter, &delegate)
+                        .await
+                        .unwrap_or_else(|error| {
+                            log::warn!(
+                                "failed to install {:?} over ssh, using the remote PATH: {error:#}",
+                                adapter.name.0
+                            );
+                            None
+                        })
+                        .or_else(|| {
+                            remote_command
+                                .as_ref()
+                                .map(|command| command.path.to_string_lossy().into_owned())
+                        }),
+                };
+                let Some(server_path) = server_path else {
+                    let error = format!(
+                        "no binary found to start {:?} on the remote host, \
+                        set lsp.{}.binary.path to one",
+                        adapter.name.0, adapter.name.0
+                    );
+                    log::warn!("{error}");
+                    delegate.update_status(
+                        adapter.name.clone(),
+                        language::LanguageServerBinaryStatus::Failed {
+                            error: error.clone(),
+                        },
+                    );
+                    return Err(anyhow!(error));
+                };
+                // Arguments set in the settings replace the adapter's own.
+                let arguments = binary_settings.arguments.unwrap_or_else(|| {
+                    remote_command
+                        .map(|command| {
+                            command
+                                .arguments
+                                .iter()
+                                .map(|arg| arg.to_string_lossy().into_owned())
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                });
+                let server_command = Some(server_path)
+                    .into_iter()
+                    .chain(arguments)
+                    .filter_map(|arg| shlex::try_quote(&arg).ok().map(|arg| arg.into_owned()))
+                    .join(" ");
+                Ok(LanguageServerBinary {
+                    path: program.into(),
+                    arguments: command_prefix
+                        .into_iter()
+                        .chain([format!("cd {remote_dir} && exec {server_command}")])
+                        .map(Into::into)
+                        .collect(),
+                    env: None,
+                })
+            }
+        });
+        let pending_server = self.languages.create_pending_remote_language_server(
+            stderr_capture.clone(),
+            language.clone(),
+            adapter.clone(),
+            binary,
+            worktree_path,
+            cx,
+        );
+        let server_id = pending_server.server_id;
+
+        let state = LanguageServerState::Starting({
+            let server_name = adapter.name.0.clone();
+            let key = key.clone();
+            cx.spawn(move |this, mut cx| async move {
+                let result = Self::setup_and_insert_language_server(
+                    this,
+                    lsp_adapter_delegate,
+                    override_options,
+                    pending_server,
+                    adapter,
+                    language,
+                    server_id,
+                    key,
+                    &mut cx,
+                )
+                .await;
+                match result {
+                    Ok(server) => {
+                        stderr_capture.lock().take();
+                        server
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "failed to start language server {server_name:?} over ssh: {err}"
+                        );
+                        log::error!("server stderr: {:?}", stderr_capture.lock().take());
+                        None
+                    }
+                }
+            })
+        });
+
+        self.language_servers.insert(server_id, state);
+        self.language_server_ids.insert(key, server_id);
+    }
+
+    /// Installs the adapter's server into the data directory of the remote server of an
+    /// SSH project, unless it's already there, returning its path on the remote host.
+    /// Returns `None` when the adapter can't install its server on remote hosts.
+    async fn install_ssh_language_server(
+        ssh_session: &SshSession,
+        adapter: &CachedLspAdapter,
+        delegate: &Arc<dyn LspAdapterDelegate>,
+    ) -> Result<Option<String>> {
+        let info = ssh_session.request(proto::GetRemoteServerInfo {}).await?;
+        let download = adapter
+            .adapter
+            .fetch_remote_server_download(&info.os, &info.arch, delegate.as_ref())
+            .await?;
+        let Some(download) = download else {
+            return Ok(None);
+        };
+
+        delegate.update_status(
+            adapter.name.clone(),
+            language::LanguageServerBinaryStatus::Downloading,
+        );
+        let response = ssh_session
+            .request(proto::InstallLanguageServer {
+                server_name: adapter.name.0.to_string(),
+                file_name: download.file_name,
+                url: download.url,
+                gzipped: download.gzipped,
+            })
+            .await;
+        delegate.update_status(
+            adapter.name.clone(),
+            language::LanguageServerBinaryStatus::None,
+        );
+        Ok(Some(response?.path))
+    }
+
     fn reinstall_language_server(
         &mut self,
         language: Arc<Language>,
@@ -3705,6 +4007,9 @@ impl Project {
         self.buffer_store.update(cx, |buffer_store, cx| {
             for buffer_handle in buffer_store.buffers() {
                 let buffer = buffer_handle.read(cx);
+                if buffer.is_large_file() {
+                    continue;
+                }
                 let file = match File::from_dyn(buffer.file()) {
                     Some(file) => file,
                     None => continue,
@@ -3724,8 +4029,8 @@ impl Project {
                     continue;
                 }
 
-                let file = match file.as_local() {
-                    Some(file) => file,
+                let abs_path = match language_server_abs_path(file, cx) {
+                    Some(abs_path) => abs_path,
                     None => continue,
                 };
 
@@ -3744,7 +4049,7 @@ impl Project {
                 let snapshot = versions.last().unwrap();
                 let version = snapshot.version;
                 let initial_snapshot = &snapshot.snapshot;
-                let uri = lsp::Url::from_file_path(file.abs_path(cx)).unwrap();
+                let uri = lsp::Url::from_file_path(abs_path).unwrap();
                 language_server.notify::<lsp::notification::DidOpenTextDocument>(
                     lsp::DidOpenTextDocumentParams {
                         text_document: lsp::TextDocumentItem::new(
@@ -4948,17 +5253,33 @@ impl Project {
                 })?;
 
             let settings = buffer.update(&mut cx, |buffer, cx| {
-                language_settings(buffer.language(), buffer.file(), cx).clone()
+                language_settings(buffer.language(), buffer.file(), cx)
             })?;
 
             let remove_trailing_whitespace = settings.remove_trailing_whitespace_on_save;
-            let ensure_final_newline = settings.ensure_final_newline_on_save;
+            let mut ensure_final_newline = settings.ensure_final_newline_on_save;
+            let edited_rows = match settings.whitespace_fixes_on_save {
+                WhitespaceFixesOnSave::WholeFile => None,
+                WhitespaceFixesOnSave::ModifiedLines => {
+                    Some(buffer.update(&mut cx, |buffer, _| buffer.rows_edited_since_save())?)
+                }
+            };
+            if let Some(edited_rows) = &edited_rows {
+                let last_row = buffer.update(&mut cx, |buffer, _| buffer.max_point().row)?;
+                ensure_final_newline &=
+                    edited_rows.last().map_or(false, |rows| rows.end > last_row);
+            }
 
             // First, format buffer's whitespace according to the settings.
             let trailing_whitespace_diff = if remove_trailing_whitespace {
                 Some(
                     buffer
-                        .update(&mut cx, |b, cx| b.remove_trailing_whitespace(cx))?
+                        .update(&mut cx, |b, cx| match edited_rows {
+                            Some(edited_rows) => {
+                                b.remove_trailing_whitespace_in_rows(edited_rows, cx)
+                            }
+                            None => b.remove_trailing_whitespace(cx),
+                        })?
                         .await,
                 )
             } else {
@@ -5925,9 +6246,21 @@ impl Project {
         position: Anchor,
         cx: &mut ModelContext<Self>,
     ) -> Task<Result<Vec<Range<Anchor>>>> {
+        let enabled = maybe!({
+            let language_name = buffer.read(cx).language_at(position)?.name();
+            Some(
+                AllLanguageSettings::get_global(cx)
+                    .language(Some(&language_name))
+                    .linked_edits,
+            )
+        }) == Some(true);
+        if !enabled {
+            return Task::ready(Ok(vec![]));
+        }
+
         let snapshot = buffer.read(cx).snapshot();
         let scope = snapshot.language_scope_at(position);
-        let Some(server_id) = self
+        let server_id = self
             .language_servers_for_buffer(buffer.read(cx), cx)
             .filter(|(_, server)| {
                 server
@@ -5943,27 +6276,36 @@ impl Project {
             })
             .map(|(_, server)| LanguageServerToQuery::Other(server.server_id()))
             .next()
-            .or_else(|| self.is_remote().then_some(LanguageServerToQuery::Primary))
-            .filter(|_| {
-                maybe!({
-                    let language_name = buffer.read(cx).language_at(position)?.name();
-                    Some(
-                        AllLanguageSettings::get_global(cx)
-                            .language(Some(&language_name))
-                            .linked_edits,
-                    )
-                }) == Some(true)
-            })
-        else {
-            return Task::ready(Ok(vec![]));
+            .or_else(|| self.is_remote().then_some(LanguageServerToQuery::Primary));
+
+        // Fall back to the tags matched by the language's grammar when no language server
+        // links any range at the position.
+        let syntax_ranges = move || {
+            let offset = position.to_offset(&snapshot);
+            snapshot
+                .linked_edit_ranges(offset)
+                .into_iter()
+                .map(|range| snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end))
+                .collect::<Vec<_>>()
+        };
+        let Some(server_id) = server_id else {
+            return Task::ready(Ok(syntax_ranges()));
         };
 
-        self.request_lsp(
+        let request = self.request_lsp(
             buffer.clone(),
             server_id,
             LinkedEditingRange { position },
             cx,
-        )
+        );
+        cx.background_executor().spawn(async move {
+            let ranges = request.await?;
+            if ranges.is_empty() {
+                Ok(syntax_ranges())
+            } else {
+                Ok(ranges)
+            }
+        })
     }
 
     pub fn linked_edit(
@@ -6504,6 +6846,21 @@ impl Project {
         self.code_actions_impl(buffer_handle, range, cx)
     }
 
+    /// Fetches the code lenses of the buffer from its primary language server, as code actions
+    /// running each lens' command when applied.
+    pub fn code_lens(
+        &self,
+        buffer_handle: &Model<Buffer>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        self.request_lsp(
+            buffer_handle.clone(),
+            LanguageServerToQuery::Primary,
+            GetCodeLens,
+            cx,
+        )
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Model<Buffer>,
@@ -6966,7 +7323,7 @@ impl Project {
         cx: &mut ModelContext<Self>,
     ) -> Task<Result<Option<Transaction>>> {
         let options = buffer.update(cx, |buffer, cx| {
-            lsp_command::lsp_formatting_options(language_settings(
+            lsp_command::lsp_formatting_options(&language_settings(
                 buffer.language_at(position).as_ref(),
                 buffer.file(),
                 cx,
@@ -7020,6 +7377,15 @@ impl Project {
         let lsp_request = InlayHints { range };
 
         if self.is_local() {
+            let supports_inlay_hints = self
+                .primary_language_server_for_buffer(buffer, cx)
+                .map_or(false, |(_, server)| {
+                    lsp_request.check_capabilities(server.adapter_server_capabilities())
+                });
+            if !supports_inlay_hints {
+                return self.syntax_parameter_hints(buffer_handle, range_start..range_end, cx);
+            }
+
             let lsp_request_task = self.request_lsp(
                 buffer_handle.clone(),
                 LanguageServerToQuery::Primary,
@@ -7064,34 +7430,255 @@ impl Project {
         }
     }
 
-    pub fn resolve_inlay_hint(
+    /// Infers the parameter name hints of the calls in the range from the grammar of the
+    /// buffer's language, for the buffers whose language server doesn't provide inlay hints.
+    /// The called functions are looked up among the ones defined in the open buffers of the
+    /// same language, and calls to functions defined with different parameters are skipped.
+    fn syntax_parameter_hints(
         &self,
-        hint: InlayHint,
+        buffer: Model<Buffer>,
+        range: Range<Anchor>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<InlayHint>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let Some(language_name) = snapshot.language().map(|language| language.name()) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let definition_snapshots = self
+            .opened_buffers(cx)
+            .into_iter()
+            .map(|buffer| buffer.read(cx).snapshot())
+            .filter(|snapshot| {
+                snapshot.language().map(|language| language.name()) == Some(language_name.clone())
+            })
+            .collect::<Vec<_>>();
+
+        cx.background_executor().spawn(async move {
+            let mut parameters_by_function = HashMap::<String, Option<Vec<String>>>::default();
+            for definition_snapshot in &definition_snapshots {
+                for (name, parameters) in definition_snapshot.function_parameters() {
+                    parameters_by_function
+                        .entry(name)
+                        .and_modify(|existing| {
+                            if existing.as_ref() != Some(&parameters) {
+                                *existing = None;
+                            }
+                        })
+                        .or_insert(Some(parameters));
+                }
+            }
+
+            let range = range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot);
+            let mut hints = Vec::new();
+            for (name, arguments) in snapshot.call_arguments(range) {
+                let Some(Some(parameters)) = parameters_by_function.get(&name) else {
+                    continue;
+                };
+                for (argument, parameter) in arguments.into_iter().zip(parameters) {
+                    // Only name the arguments of parameters bound to a single identifier, and
+                    // leave out the ones that already tell the parameter's name.
+                    let is_identifier = parameter
+                        .chars()
+                        .all(|character| character.is_alphanumeric() || character == '_');
+                    if !is_identifier || parameter.starts_with('_') {
+                        continue;
+                    }
+                    let argument_text = snapshot
+                        .text_for_range(argument.clone())
+                        .collect::<String>();
+                    let names_parameter =
+                        argument_text
+                            .strip_suffix(parameter.as_str())
+                            .map_or(false, |prefix| {
+                                !prefix.ends_with(|character: char| {
+                                    character.is_alphanumeric() || character == '_'
+                                })
+                            });
+                    if names_parameter {
+                        continue;
+                    }
+
+                    hints.push(InlayHint {
+                        position: snapshot.anchor_before(argument.start),
+                        label: InlayHintLabel::String(format!("{parameter}:")),
+                        kind: Some(InlayHintKind::Parameter),
+                        padding_left: false,
+                        padding_right: true,
+                        tooltip: None,
+                        resolve_state: ResolveState::Resolved,
+                    });
+                }
+            }
+            Ok(hints)
+        })
+    }
+
+    /// Fetches the semantic tokens of the buffer from its primary language server. When the
+    /// server supports it, only the changes since the previous request are sent.
+    pub fn semantic_tokens(
+        &mut self,
         buffer_handle: Model<Buffer>,
-        server_id: LanguageServerId,
         cx: &mut ModelContext<Self>,
-    ) -> Task<anyhow::Result<InlayHint>> {
+    ) -> Task<Result<Vec<SemanticToken>>> {
+        let buffer = buffer_handle.read(cx);
+        let buffer_id = buffer.remote_id();
         if self.is_local() {
-            let buffer = buffer_handle.read(cx);
-            let (_, lang_server) = if let Some((adapter, server)) =
-                self.language_server_for_buffer(buffer, server_id, cx)
-            {
-                (adapter.clone(), server.clone())
-            } else {
-                return Task::ready(Ok(hint));
+            let Some((_, language_server)) = self.primary_language_server_for_buffer(buffer, cx)
+            else {
+                return Task::ready(Ok(Vec::new()));
             };
-            if !InlayHints::can_resolve_inlays(&lang_server.capabilities()) {
-                return Task::ready(Ok(hint));
-            }
+            let language_server = language_server.clone();
+            let server_id = language_server.server_id();
+            let Some(options) =
+                language_server
+                    .capabilities()
+                    .semantic_tokens_provider
+                    .map(|provider| {
+                        match provider {
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => {
+                        options
+                    }
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(
+                        options,
+                    ) => options.semantic_tokens_options,
+                }
+                    })
+            else {
+                return Task::ready(Ok(Vec::new()));
+            };
+            let Some(abs_path) =
+                File::from_dyn(buffer.file()).and_then(|file| language_server_abs_path(file, cx))
+            else {
+                return Task::ready(Ok(Vec::new()));
+            };
+            let text_document =
+                lsp::TextDocumentIdentifier::new(lsp::Url::from_file_path(abs_path).unwrap());
+            let supports_delta = matches!(
+                options.full,
+                Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+            );
+            let previous_tokens = self
+                .semantic_tokens
+                .get(&buffer_id)
+                .and_then(|tokens| tokens.get(&server_id))
+                .filter(|_| supports_delta)
+                .and_then(|tokens| Some((tokens.result_id.clone()?, tokens.data.clone())));
+            let snapshot = buffer.snapshot();
 
-            let buffer_snapshot = buffer.snapshot();
-            cx.spawn(move |_, mut cx| async move {
-                let resolve_task = lang_server.request::<lsp::request::InlayHintResolveRequest>(
-                    InlayHints::project_to_lsp_hint(hint, &buffer_snapshot),
-                );
-                let resolved_hint = resolve_task
-                    .await
-                    .context("inlay hint resolve LSP request")?;
+            cx.spawn(move |this, mut cx| async move {
+                let (result_id, data) = if let Some((previous_result_id, mut data)) =
+                    previous_tokens
+                {
+                    let response = language_server
+                        .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                            lsp::SemanticTokensDeltaParams {
+                                text_document,
+                                previous_result_id,
+                                work_done_progress_params: Default::default(),
+                                partial_result_params: Default::default(),
+                            },
+                        )
+                        .await?;
+                    match response {
+                        Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                            (tokens.result_id, encode_semantic_tokens(tokens.data))
+                        }
+                        Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                            apply_semantic_token_edits(&mut data, delta.edits);
+                            (delta.result_id, data)
+                        }
+                        Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits }) => {
+                            apply_semantic_token_edits(&mut data, edits);
+                            (None, data)
+                        }
+                        None => (None, Vec::new()),
+                    }
+                } else {
+                    let response = language_server
+                        .request::<lsp::request::SemanticTokensFullRequest>(
+                            lsp::SemanticTokensParams {
+                                text_document,
+                                work_done_progress_params: Default::default(),
+                                partial_result_params: Default::default(),
+                            },
+                        )
+                        .await?;
+                    match response {
+                        Some(lsp::SemanticTokensResult::Tokens(tokens)) => {
+                            (tokens.result_id, encode_semantic_tokens(tokens.data))
+                        }
+                        Some(lsp::SemanticTokensResult::Partial(partial)) => {
+                            (None, encode_semantic_tokens(partial.data))
+                        }
+                        None => (None, Vec::new()),
+                    }
+                };
+
+                let tokens = decode_semantic_tokens(&data, &options.legend, &snapshot);
+                this.update(&mut cx, |this, _| {
+                    this.semantic_tokens
+                        .entry(buffer_id)
+                        .or_default()
+                        .insert(server_id, CachedSemanticTokens { result_id, data });
+                })?;
+                Ok(tokens)
+            })
+        } else if let Some(project_id) = self.remote_id() {
+            let client = self.client.clone();
+            let request = proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer_id.into(),
+                version: serialize_version(&buffer.version()),
+            };
+            cx.spawn(move |_, mut cx| async move {
+                let response = client
+                    .request(request)
+                    .await
+                    .context("semantic tokens proto request")?;
+                buffer_handle
+                    .update(&mut cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&response.version))
+                    })?
+                    .await?;
+                response
+                    .tokens
+                    .into_iter()
+                    .map(deserialize_semantic_token)
+                    .collect()
+            })
+        } else {
+            Task::ready(Err(anyhow!("project does not have a remote id")))
+        }
+    }
+
+    pub fn resolve_inlay_hint(
+        &self,
+        hint: InlayHint,
+        buffer_handle: Model<Buffer>,
+        server_id: LanguageServerId,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<anyhow::Result<InlayHint>> {
+        if self.is_local() {
+            let buffer = buffer_handle.read(cx);
+            let (_, lang_server) = if let Some((adapter, server)) =
+                self.language_server_for_buffer(buffer, server_id, cx)
+            {
+                (adapter.clone(), server.clone())
+            } else {
+                return Task::ready(Ok(hint));
+            };
+            if !InlayHints::can_resolve_inlays(&lang_server.capabilities()) {
+                return Task::ready(Ok(hint));
+            }
+
+            let buffer_snapshot = buffer.snapshot();
+            cx.spawn(move |_, mut cx| async move {
+                let resolve_task = lang_server.request::<lsp::request::InlayHintResolveRequest>(
+                    InlayHints::project_to_lsp_hint(hint, &buffer_snapshot),
+                );
+                let resolved_hint = resolve_task
+                    .await
+                    .context("inlay hint resolve LSP request")?;
                 let resolved_hint = InlayHints::lsp_to_project_hint(
                     resolved_hint,
                     &buffer_handle,
@@ -7133,7 +7720,9 @@ impl Project {
         query: SearchQuery,
         cx: &mut ModelContext<Self>,
     ) -> Receiver<SearchResult> {
-        if self.is_local() {
+        if let Some(ssh_session) = self.ssh_session.clone() {
+            self.search_ssh(ssh_session, query, cx)
+        } else if self.is_local() {
             self.search_local(query, cx)
         } else if let Some(project_id) = self.remote_id() {
             let (tx, rx) = smol::channel::unbounded();
@@ -7237,7 +7826,166 @@ impl Project {
         }
         let workers = background.num_cpus().min(path_count);
         let (matching_paths_tx, matching_paths_rx) = smol::channel::bounded(1024);
-        let mut unnamed_files = vec![];
+        let (unnamed_files, opened_buffers) = self.search_open_buffers(&query, include_root, cx);
+        cx.background_executor()
+            .spawn(Self::background_search(
+                unnamed_files,
+                opened_buffers,
+                cx.background_executor().clone(),
+                self.fs.clone(),
+                workers,
+                query.clone(),
+                include_root,
+                path_count,
+                snapshots,
+                matching_paths_tx,
+            ))
+            .detach();
+
+        Self::search_matching_paths(query, matching_paths_rx, cx)
+    }
+
+    /// Searches an SSH project. The files that may contain a match are picked on the
+    /// remote host, so only those need to be transferred to be searched here. Open
+    /// buffers are searched as they are, as they may contain unsaved changes.
+    fn search_ssh(
+        &self,
+        ssh_session: Arc<SshSession>,
+        query: SearchQuery,
+        cx: &mut ModelContext<Self>,
+    ) -> Receiver<SearchResult> {
+        let include_root = self.visible_worktrees(cx).count() > 1;
+        let (unnamed_buffers, opened_buffers) = self.search_open_buffers(&query, include_root, cx);
+        let opened_paths = opened_buffers
+            .values()
+            .filter_map(|(buffer, _)| buffer.read(cx).project_path(cx))
+            .collect::<HashSet<_>>();
+        let request = ssh_session.request(proto::FindSearchCandidates {
+            query: Some(query.to_proto(0)),
+            limit: MAX_SEARCH_RESULT_FILES as u64 + 1,
+        });
+
+        let (matching_paths_tx, matching_paths_rx) = smol::channel::bounded(1024);
+        cx.background_executor()
+            .spawn(async move {
+                for buffer in unnamed_buffers {
+                    matching_paths_tx
+                        .send(SearchMatchCandidate::OpenBuffer { buffer, path: None })
+                        .await?;
+                }
+                for (path, (buffer, _)) in opened_buffers {
+                    matching_paths_tx
+                        .send(SearchMatchCandidate::OpenBuffer {
+                            buffer,
+                            path: Some(path),
+                        })
+                        .await?;
+                }
+
+                let response = request.await?;
+                for path in response.paths {
+                    let project_path = ProjectPath {
+                        worktree_id: WorktreeId::from_proto(path.worktree_id),
+                        path: PathBuf::from(path.path).into(),
+                    };
+                    if opened_paths.contains(&project_path) {
+                        continue;
+                    }
+                    matching_paths_tx
+                        .send(SearchMatchCandidate::Path {
+                            worktree_id: project_path.worktree_id,
+                            is_ignored: false,
+                            is_file: true,
+                            path: project_path.path,
+                        })
+                        .await?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+
+        Self::search_matching_paths(query, matching_paths_rx, cx)
+    }
+
+    /// Finds the paths of the files in the given local worktrees that contain at least
+    /// one match of the query, without opening them. Used by the remote server to
+    /// search on behalf of an SSH project.
+    pub fn find_search_candidates(
+        worktrees: &[Model<Worktree>],
+        fs: Arc<dyn Fs>,
+        query: SearchQuery,
+        limit: usize,
+        cx: &AppContext,
+    ) -> Task<Vec<ProjectPath>> {
+        let snapshots = worktrees
+            .iter()
+            .filter_map(|tree| {
+                let tree = tree.read(cx);
+                Some((tree.snapshot(), tree.as_local()?.settings()))
+            })
+            .collect::<Vec<_>>();
+        let include_root = snapshots.len() > 1;
+        let path_count: usize = snapshots
+            .iter()
+            .map(|(snapshot, _)| {
+                if query.include_ignored() {
+                    snapshot.file_count()
+                } else {
+                    snapshot.visible_file_count()
+                }
+            })
+            .sum();
+        if path_count == 0 {
+            return Task::ready(Vec::new());
+        }
+
+        let executor = cx.background_executor().clone();
+        let workers = executor.num_cpus().min(path_count);
+        let (matching_paths_tx, matching_paths_rx) = smol::channel::bounded(1024);
+        executor
+            .spawn(Self::background_search(
+                Vec::new(),
+                HashMap::default(),
+                executor.clone(),
+                fs,
+                workers,
+                query,
+                include_root,
+                path_count,
+                snapshots,
+                matching_paths_tx,
+            ))
+            .detach();
+
+        executor.spawn(async move {
+            matching_paths_rx
+                .take(limit)
+                .filter_map(|candidate| async move {
+                    match candidate {
+                        SearchMatchCandidate::Path {
+                            worktree_id, path, ..
+                        } => Some(ProjectPath { worktree_id, path }),
+                        SearchMatchCandidate::OpenBuffer { .. } => None,
+                    }
+                })
+                .collect()
+                .await
+        })
+    }
+
+    /// Returns the open buffers that should be searched for the query: the untitled
+    /// buffers, and the buffers whose path matches the query, keyed by path.
+    #[allow(clippy::type_complexity)]
+    fn search_open_buffers(
+        &self,
+        query: &SearchQuery,
+        include_root: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> (
+        Vec<Model<Buffer>>,
+        HashMap<Arc<Path>, (Model<Buffer>, BufferSnapshot)>,
+    ) {
+        let mut unnamed_buffers = vec![];
         let opened_buffers = self.buffer_store.update(cx, |buffer_store, cx| {
             buffer_store
                 .buffers()
@@ -7264,33 +8012,25 @@ impl Project {
                             None
                         }
                     } else {
-                        unnamed_files.push(buffer);
+                        unnamed_buffers.push(buffer);
                         None
                     }
                 })
                 .collect()
         });
-        cx.background_executor()
-            .spawn(Self::background_search(
-                unnamed_files,
-                opened_buffers,
-                cx.background_executor().clone(),
-                self.fs.clone(),
-                workers,
-                query.clone(),
-                include_root,
-                path_count,
-                snapshots,
-                matching_paths_tx,
-            ))
-            .detach();
+        (unnamed_buffers, opened_buffers)
+    }
 
+    /// Opens the buffers of the paths that may contain a match of the query, and
+    /// searches them, reporting the matches sorted by path.
+    fn search_matching_paths(
+        query: SearchQuery,
+        matching_paths_rx: Receiver<SearchMatchCandidate>,
+        cx: &mut ModelContext<Self>,
+    ) -> Receiver<SearchResult> {
         let (result_tx, result_rx) = smol::channel::bounded(1024);
 
         cx.spawn(|this, mut cx| async move {
-            const MAX_SEARCH_RESULT_FILES: usize = 5_000;
-            const MAX_SEARCH_RESULT_RANGES: usize = 10_000;
-
             let mut matching_paths = matching_paths_rx
                 .take(MAX_SEARCH_RESULT_FILES + 1)
                 .collect::<Vec<_>>()
@@ -7487,9 +8227,10 @@ impl Project {
                     .language_server_for_buffer(buffer, id, cx)
                     .map(|(_, server)| Arc::clone(server)),
             };
-            let file = File::from_dyn(buffer.file()).and_then(File::as_local);
-            if let (Some(file), Some(language_server)) = (file, language_server) {
-                let lsp_params = request.to_lsp(&file.abs_path(cx), buffer, &language_server, cx);
+            let abs_path =
+                File::from_dyn(buffer.file()).and_then(|file| language_server_abs_path(file, cx));
+            if let (Some(abs_path), Some(language_server)) = (abs_path, language_server) {
+                let lsp_params = request.to_lsp(&abs_path, buffer, &language_server, cx);
                 let status = request.status();
                 return cx.spawn(move |this, cx| async move {
                     if !request.check_capabilities(language_server.adapter_server_capabilities()) {
@@ -7685,14 +8426,17 @@ impl Project {
         abs_path: &Path,
         cx: &AppContext,
     ) -> Option<(Model<Worktree>, PathBuf)> {
+        // The worktrees of SSH projects are remote, but their paths are the ones that
+        // language servers running on the remote host refer to.
+        let include_remote = self.ssh_session.is_some();
         self.worktree_store.read_with(cx, |worktree_store, cx| {
-            for tree in worktree_store.worktrees() {
-                if let Some(relative_path) = tree
-                    .read(cx)
-                    .as_local()
-                    .and_then(|t| abs_path.strip_prefix(t.abs_path()).ok())
-                {
-                    return Some((tree.clone(), relative_path.into()));
+            for worktree in worktree_store.worktrees() {
+                let tree = worktree.read(cx);
+                if !tree.is_local() && !include_remote {
+                    continue;
+                }
+                if let Ok(relative_path) = abs_path.strip_prefix(tree.abs_path()) {
+                    return Some((worktree.clone(), relative_path.into()));
                 }
             }
             None
@@ -7954,6 +8698,16 @@ impl Project {
         self.task_inventory().update(cx, |inventory, _| {
             inventory.remove_worktree_sources(id_to_remove);
         });
+        self.ssh_tasks_buffers.remove(&id_to_remove);
+        if let Some(worktree) = self.worktree_for_id(id_to_remove, cx) {
+            let snippets_dir = worktree
+                .read(cx)
+                .abs_path()
+                .join(local_snippets_folder_relative_path());
+            self.snippets.update(cx, |snippets, _| {
+                snippets.remove_directory(&snippets_dir);
+            });
+        }
 
         self.worktree_store.update(cx, |worktree_store, cx| {
             worktree_store.remove_worktree(id_to_remove, cx);
@@ -7972,6 +8726,8 @@ impl Project {
                         this.update_local_worktree_language_servers(&worktree, changes, cx);
                         this.update_local_worktree_settings(&worktree, changes, cx);
                         this.update_prettier_settings(&worktree, changes, cx);
+                    } else if this.ssh_session.is_some() {
+                        this.update_ssh_worktree_tasks(&worktree, cx);
                     }
 
                     cx.emit(Event::WorktreeUpdatedEntries(
@@ -8074,6 +8830,8 @@ impl Project {
         let remote_worktree_id = worktree.read(cx).id();
 
         let mut settings_contents = Vec::new();
+        let mut editorconfig_contents = Vec::new();
+        let mut snippet_files = Vec::new();
         for (path, _, change) in changes.iter() {
             let removed = change == &PathChange::Removed;
             let abs_path = match worktree.read(cx).absolutize(path) {
@@ -8101,6 +8859,19 @@ impl Project {
                         },
                     )
                 });
+            } else if path.file_name() == Some(OsStr::new(EDITORCONFIG_FILE_NAME)) {
+                let directory: Arc<Path> = Arc::from(path.parent().unwrap_or(Path::new("")));
+                let fs = self.fs.clone();
+                editorconfig_contents.push(async move {
+                    (
+                        directory,
+                        if removed {
+                            None
+                        } else {
+                            Some(async move { fs.load(&abs_path).await }.await)
+                        },
+                    )
+                });
             } else if path.ends_with(local_tasks_file_relative_path()) {
                 self.task_inventory().update(cx, |task_inventory, cx| {
                     if removed {
@@ -8147,10 +8918,18 @@ impl Project {
                         );
                     }
                 })
+            } else if path.parent() == Some(local_snippets_folder_relative_path()) {
+                snippet_files.push(abs_path);
             }
         }
 
-        if settings_contents.is_empty() {
+        if !snippet_files.is_empty() {
+            self.snippets.update(cx, |snippets, cx| {
+                snippets.reload_files(snippet_files, cx);
+            });
+        }
+
+        if settings_contents.is_empty() && editorconfig_contents.is_empty() {
             return;
         }
 
@@ -8158,8 +8937,18 @@ impl Project {
         cx.spawn(move |_, cx| async move {
             let settings_contents: Vec<(Arc<Path>, _)> =
                 futures::future::join_all(settings_contents).await;
+            let editorconfig_contents: Vec<(Arc<Path>, _)> =
+                futures::future::join_all(editorconfig_contents).await;
             cx.update(|cx| {
                 cx.update_global::<SettingsStore, _>(|store, cx| {
+                    for (directory, file_content) in editorconfig_contents {
+                        let file_content = file_content.and_then(|content| content.log_err());
+                        store.set_local_editorconfig(
+                            worktree_id.as_u64() as usize,
+                            directory,
+                            file_content.as_deref(),
+                        );
+                    }
                     for (directory, file_content) in settings_contents {
                         let file_content = file_content.and_then(|content| content.log_err());
                         store
@@ -8175,7 +8964,7 @@ impl Project {
                                 .send(proto::UpdateWorktreeSettings {
                                     project_id: remote_id,
                                     worktree_id: remote_worktree_id.to_proto(),
-                                    path: directory.to_string_lossy().into_owned(),
+                                    path: directory.to_string_lossy(),
                                     content: file_content,
                                 })
                                 .log_err();
@@ -8188,6 +8977,62 @@ impl Project {
         .detach();
     }
 
+    /// Loads the tasks file of a worktree on the remote host of an SSH project, reloading
+    /// its tasks whenever the file is saved or changes on disk.
+    fn update_ssh_worktree_tasks(
+        &mut self,
+        worktree: &Model<Worktree>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let worktree_id = worktree.read(cx).id();
+        let tasks_path: Arc<Path> = local_tasks_file_relative_path().into();
+        let Some(abs_path) = worktree.read(cx).absolutize(&tasks_path).log_err() else {
+            return;
+        };
+        let exists = worktree.read(cx).entry_for_path(&tasks_path).is_some();
+        match (exists, self.ssh_tasks_buffers.contains_key(&worktree_id)) {
+            (true, false) => {}
+            (false, true) => {
+                self.ssh_tasks_buffers.remove(&worktree_id);
+                self.task_inventory().update(cx, |inventory, _| {
+                    inventory.remove_local_static_source(&abs_path);
+                });
+                return;
+            }
+            _ => return,
+        }
+
+        self.ssh_tasks_buffers.insert(worktree_id, None);
+        let open_buffer = self.open_buffer((worktree_id, tasks_path), cx);
+        cx.spawn(|this, mut cx| async move {
+            let buffer = open_buffer.await?;
+            this.update(&mut cx, |this, cx| {
+                let (tasks_file_tx, tasks_file_rx) = mpsc::unbounded();
+                tasks_file_tx.unbounded_send(buffer.read(cx).text()).ok();
+                cx.subscribe(&buffer, move |_, buffer, event, cx| {
+                    if matches!(event, BufferEvent::Saved | BufferEvent::Reloaded) {
+                        tasks_file_tx.unbounded_send(buffer.read(cx).text()).ok();
+                    }
+                })
+                .detach();
+
+                this.task_inventory().update(cx, |inventory, cx| {
+                    inventory.add_source(
+                        TaskSourceKind::Worktree {
+                            id: worktree_id,
+                            abs_path,
+                            id_base: "local_tasks_for_worktree".into(),
+                        },
+                        |tx, cx| StaticSource::new(TrackedFile::new(tasks_file_rx, tx, cx)),
+                        cx,
+                    );
+                });
+                this.ssh_tasks_buffers.insert(worktree_id, Some(buffer));
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn set_active_path(&mut self, entry: Option<ProjectPath>, cx: &mut ModelContext<Self>) {
         let new_active_entry = entry.and_then(|project_path| {
             let worktree = self.worktree_for_id(project_path.worktree_id, cx)?;
@@ -8400,6 +9245,60 @@ impl Project {
         worktree.get_local_repo(&root_entry)?.repo().clone().into()
     }
 
+    /// Runs the given function with the repository at the root of the first worktree, on the
+    /// background executor.
+    fn with_first_worktree_root_repo<T: Send + 'static>(
+        &self,
+        cx: &AppContext,
+        f: impl FnOnce(Arc<dyn GitRepository>) -> Result<T> + Send + 'static,
+    ) -> Task<Result<T>> {
+        let Some(repo) = self.get_first_worktree_root_repo(cx) else {
+            return Task::ready(Err(anyhow!("no git repository at the root of the project")));
+        };
+        cx.background_executor().spawn(async move { f(repo) })
+    }
+
+    /// Lists the local and remote branches of the repository at the root of the first worktree.
+    pub fn branches(&self, cx: &AppContext) -> Task<Result<Vec<Branch>>> {
+        self.with_first_worktree_root_repo(cx, |repo| repo.branches())
+    }
+
+    /// Checks out the given branch in the repository at the root of the first worktree. A remote
+    /// branch is checked out as a local branch that tracks it.
+    pub fn change_branch(&self, name: String, cx: &AppContext) -> Task<Result<()>> {
+        self.with_first_worktree_root_repo(cx, move |repo| repo.change_branch(&name))
+    }
+
+    /// Creates a branch pointing at the current HEAD of the repository at the root of the first
+    /// worktree, without checking it out.
+    pub fn create_branch(&self, name: String, cx: &AppContext) -> Task<Result<()>> {
+        self.with_first_worktree_root_repo(cx, move |repo| repo.create_branch(&name))
+    }
+
+    /// Lists the staged and unstaged changes of the repository at the root of the first
+    /// worktree.
+    pub fn staging_statuses(&self, cx: &AppContext) -> Task<Result<Vec<StagingStatus>>> {
+        self.with_first_worktree_root_repo(cx, |repo| repo.staging_statuses())
+    }
+
+    pub fn stage_paths(&self, paths: Vec<RepoPath>, cx: &AppContext) -> Task<Result<()>> {
+        self.with_first_worktree_root_repo(cx, move |repo| repo.stage_paths(&paths))
+    }
+
+    pub fn unstage_paths(&self, paths: Vec<RepoPath>, cx: &AppContext) -> Task<Result<()>> {
+        self.with_first_worktree_root_repo(cx, move |repo| repo.unstage_paths(&paths))
+    }
+
+    /// Commits the staged changes of the repository at the root of the first worktree, amending
+    /// its HEAD commit when `amend` is true.
+    pub fn commit(&self, message: String, amend: bool, cx: &AppContext) -> Task<Result<()>> {
+        self.with_first_worktree_root_repo(cx, move |repo| repo.commit(&message, amend))
+    }
+
+    pub fn head_commit_message(&self, cx: &AppContext) -> Task<Result<Option<String>>> {
+        self.with_first_worktree_root_repo(cx, |repo| Ok(repo.head_commit_message()))
+    }
+
     pub fn blame_buffer(
         &self,
         buffer: &Model<Buffer>,
@@ -8409,6 +9308,39 @@ impl Project {
         self.buffer_store.read(cx).blame_buffer(buffer, version, cx)
     }
 
+    /// Loads the contents of the buffer's file in the HEAD commit of its git repository.
+    pub fn load_head_text(
+        &self,
+        buffer: &Model<Buffer>,
+        cx: &AppContext,
+    ) -> Task<Result<Option<String>>> {
+        self.buffer_store.read(cx).load_buffer_head_text(buffer, cx)
+    }
+
+    /// Replaces the contents of the buffer's file in the index of its git repository, which
+    /// stages or unstages part of its changes.
+    pub fn set_index_text(
+        &self,
+        buffer: &Model<Buffer>,
+        index_text: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        self.buffer_store.update(cx, |buffer_store, cx| {
+            buffer_store.set_buffer_index_text(buffer, index_text, cx)
+        })
+    }
+
+    pub fn buffer_git_info(
+        &self,
+        buffer: &Model<Buffer>,
+        remote_name: &str,
+        cx: &AppContext,
+    ) -> Task<Result<BufferGitInfo>> {
+        self.buffer_store
+            .read(cx)
+            .buffer_git_info(buffer, remote_name, cx)
+    }
+
     // RPC message handlers
 
     async fn handle_multi_lsp_query(
@@ -9237,6 +10169,34 @@ impl Project {
         })
     }
 
+    async fn handle_semantic_tokens(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = this.update(&mut cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await
+            .with_context(|| format!("waiting for version for buffer {}", buffer.entity_id()))?;
+
+        let tokens = this
+            .update(&mut cx, |project, cx| {
+                project.semantic_tokens(buffer.clone(), cx)
+            })?
+            .await
+            .context("semantic tokens fetch")?;
+        Ok(proto::GetSemanticTokensResponse {
+            tokens: tokens.iter().map(serialize_semantic_token).collect(),
+            version: serialize_version(&buffer.update(&mut cx, |buffer, _| buffer.version())?),
+        })
+    }
+
     async fn handle_resolve_inlay_hint(
         this: Model<Self>,
         envelope: TypedEnvelope<proto::ResolveInlayHint>,
@@ -10252,6 +11212,9 @@ impl Project {
         buffer: &Buffer,
         cx: &AppContext,
     ) -> Vec<LanguageServerId> {
+        if buffer.is_large_file() {
+            return Vec::new();
+        }
         if let Some((file, language)) = File::from_dyn(buffer.file()).zip(buffer.language()) {
             let worktree_id = file.worktree_id(cx);
             self.languages
@@ -10983,6 +11946,114 @@ pub struct ProjectLspAdapterDelegate {
     load_direnv: DirenvSettings,
 }
 
+/// The path by which language servers refer to a file. The language servers of SSH
+/// projects run on the remote host, where files are addressed by their remote path.
+fn language_server_abs_path(file: &File, cx: &AppContext) -> Option<PathBuf> {
+    match file.as_local() {
+        Some(file) => Some(file.abs_path(cx)),
+        None => file.worktree.read(cx).absolutize(&file.path).log_err(),
+    }
+}
+
+/// Encodes semantic tokens as the integers they're sent as, which delta edits refer to.
+fn encode_semantic_tokens(tokens: Vec<lsp::SemanticToken>) -> Vec<u32> {
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+fn apply_semantic_token_edits(data: &mut Vec<u32>, mut edits: Vec<lsp::SemanticTokensEdit>) {
+    // Apply the edits from the last one, so that their indices stay valid.
+    edits.sort_by_key(|edit| cmp::Reverse(edit.start));
+    for edit in edits {
+        let start = (edit.start as usize).min(data.len());
+        let end = (start + edit.delete_count as usize).min(data.len());
+        data.splice(
+            start..end,
+            encode_semantic_tokens(edit.data.unwrap_or_default()),
+        );
+    }
+}
+
+/// Decodes semantic tokens, whose positions are relative to the previous token's, into ranges
+/// of the buffer they were computed for.
+fn decode_semantic_tokens(
+    data: &[u32],
+    legend: &lsp::SemanticTokensLegend,
+    snapshot: &BufferSnapshot,
+) -> Vec<SemanticToken> {
+    let mut tokens = Vec::new();
+    let mut row = 0;
+    let mut column = 0;
+    for token in data.chunks_exact(5) {
+        let [delta_line, delta_start, length, token_type, modifiers] = token else {
+            continue;
+        };
+        if *delta_line > 0 {
+            row += delta_line;
+            column = 0;
+        }
+        column += delta_start;
+
+        let Some(token_type) = legend.token_types.get(*token_type as usize) else {
+            continue;
+        };
+        let modifiers = legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .filter(|(ix, _)| *ix < 32 && modifiers & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.as_str().to_string())
+            .collect();
+        let start = snapshot.clip_point_utf16(Unclipped(PointUtf16::new(row, column)), Bias::Left);
+        let end =
+            snapshot.clip_point_utf16(Unclipped(PointUtf16::new(row, column + length)), Bias::Left);
+        if start == end {
+            continue;
+        }
+        tokens.push(SemanticToken {
+            range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+            token_type: token_type.as_str().to_string(),
+            modifiers,
+        });
+    }
+    tokens
+}
+
+fn serialize_semantic_token(token: &SemanticToken) -> proto::SemanticToken {
+    proto::SemanticToken {
+        start: Some(serialize_anchor(&token.range.start)),
+        end: Some(serialize_anchor(&token.range.end)),
+        token_type: token.token_type.clone(),
+        modifiers: token.modifiers.clone(),
+    }
+}
+
+fn deserialize_semantic_token(token: proto::SemanticToken) -> Result<SemanticToken> {
+    let start = token
+        .start
+        .and_then(deserialize_anchor)
+        .context("missing token start")?;
+    let end = token
+        .end
+        .and_then(deserialize_anchor)
+        .context("missing token end")?;
+    Ok(SemanticToken {
+        range: start..end,
+        token_type: token.token_type,
+        modifiers: token.modifiers,
+    })
+}
+
 impl ProjectLspAdapterDelegate {
     pub fn new(
         project: &Project,
@@ -1,9 +1,11 @@
 use collections::HashMap;
 use gpui::AppContext;
+use lsp::DiagnosticSeverity;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
 use std::{sync::Arc, time::Duration};
+use text::Rope;
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectSettings {
@@ -28,6 +30,14 @@ pub struct ProjectSettings {
     /// Configuration for session-related features
     #[serde(default)]
     pub session: SessionSettings,
+
+    /// Configuration for how diagnostics are shown
+    #[serde(default)]
+    pub diagnostics: DiagnosticsSettings,
+
+    /// Configuration for when files are opened in large file mode
+    #[serde(default)]
+    pub large_files: LargeFilesSettings,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -75,6 +85,16 @@ impl GitSettings {
             _ => None,
         }
     }
+
+    pub fn show_inline_commit_summary(&self) -> bool {
+        match self.inline_blame {
+            Some(InlineBlameSettings {
+                show_commit_summary,
+                ..
+            }) => show_commit_summary,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -105,6 +125,11 @@ pub struct InlineBlameSettings {
     ///
     /// Default: 0
     pub min_column: Option<u32>,
+    /// Whether to show the commit summary as part of the inline blame.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub show_commit_summary: bool,
 }
 
 const fn true_value() -> bool {
@@ -145,6 +170,115 @@ impl Default for SessionSettings {
     }
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DiagnosticsSettings {
+    /// Whether to show warnings or not by default.
+    ///
+    /// Default: true
+    pub include_warnings: bool,
+    /// Whether to underline warnings in the editor. Errors are always underlined.
+    ///
+    /// Default: true
+    pub underline_warnings: bool,
+    /// How long to wait after diagnostics are updated before refreshing the project
+    /// diagnostics, in milliseconds.
+    ///
+    /// Default: 0
+    pub update_debounce_ms: u64,
+    /// Configuration for showing diagnostics at the end of the lines they start on.
+    pub inline: InlineDiagnosticsSettings,
+}
+
+impl Default for DiagnosticsSettings {
+    fn default() -> Self {
+        Self {
+            include_warnings: true,
+            underline_warnings: true,
+            update_debounce_ms: 0,
+            inline: InlineDiagnosticsSettings::default(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct InlineDiagnosticsSettings {
+    /// Whether to show diagnostics at the end of the lines they start on.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The least severe diagnostics to show inline.
+    ///
+    /// Default: warning
+    pub min_severity: DiagnosticSeverityLevel,
+}
+
+impl Default for InlineDiagnosticsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_severity: DiagnosticSeverityLevel::Warning,
+        }
+    }
+}
+
+/// The severity of a diagnostic, from the most severe to the least.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverityLevel {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl DiagnosticSeverityLevel {
+    /// Returns whether a diagnostic with the given severity is at least this severe.
+    pub fn includes(&self, severity: DiagnosticSeverity) -> bool {
+        let min_severity = match self {
+            Self::Error => DiagnosticSeverity::ERROR,
+            Self::Warning => DiagnosticSeverity::WARNING,
+            Self::Info => DiagnosticSeverity::INFORMATION,
+            Self::Hint => DiagnosticSeverity::HINT,
+        };
+        // Language servers number severities from the most severe.
+        severity <= min_severity
+    }
+}
+
+/// Large files are opened without syntax highlighting, inlay hints and language servers,
+/// which would make the editor unresponsive.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LargeFilesSettings {
+    /// The size, in megabytes, past which a file is opened in large file mode.
+    ///
+    /// Default: 20
+    pub size_threshold_mb: u64,
+    /// The number of lines past which a file is opened in large file mode.
+    ///
+    /// Default: 500000
+    pub line_threshold: u32,
+}
+
+impl Default for LargeFilesSettings {
+    fn default() -> Self {
+        Self {
+            size_threshold_mb: 20,
+            line_threshold: 500_000,
+        }
+    }
+}
+
+impl LargeFilesSettings {
+    /// Returns whether a file with the given content is past one of the thresholds.
+    pub fn is_large_file(&self, text: &Rope) -> bool {
+        text.len() as u64 > self.size_threshold_mb.saturating_mul(1024 * 1024)
+            || text.max_point().row >= self.line_threshold
+    }
+}
+
 impl Settings for ProjectSettings {
     const KEY: Option<&'static str> = None;
 
@@ -1,6 +1,10 @@
 use crate::{Event, *};
 use fs::FakeFs;
 use futures::{future, StreamExt};
+use git::{
+    repository::{Branch, RepoPath},
+    status::{FileChange, StagingStatus},
+};
 use gpui::{AppContext, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
@@ -142,31 +146,33 @@ async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext)
 
     let all_tasks = cx
         .update(|cx| {
-            let tree = worktree.read(cx);
-
-            let settings_a = language_settings(
-                None,
-                Some(
-                    &(File::for_entry(
-                        tree.entry_for_path("a/a.rs").unwrap().clone(),
-                        worktree.clone(),
-                    ) as _),
-                ),
-                cx,
-            );
-            let settings_b = language_settings(
-                None,
-                Some(
-                    &(File::for_entry(
-                        tree.entry_for_path("b/b.rs").unwrap().clone(),
-                        worktree.clone(),
-                    ) as _),
-                ),
-                cx,
-            );
+            {
+                let tree = worktree.read(cx);
+
+                let settings_a = language_settings(
+                    None,
+                    Some(
+                        &(File::for_entry(
+                            tree.entry_for_path("a/a.rs").unwrap().clone(),
+                            worktree.clone(),
+                        ) as _),
+                    ),
+                    cx,
+                );
+                let settings_b = language_settings(
+                    None,
+                    Some(
+                        &(File::for_entry(
+                            tree.entry_for_path("b/b.rs").unwrap().clone(),
+                            worktree.clone(),
+                        ) as _),
+                    ),
+                    cx,
+                );
 
-            assert_eq!(settings_a.tab_size.get(), 8);
-            assert_eq!(settings_b.tab_size.get(), 2);
+                assert_eq!(settings_a.tab_size.get(), 8);
+                assert_eq!(settings_b.tab_size.get(), 2);
+            }
 
             get_all_tasks(&project, Some(worktree_id), &task_context, cx)
         })
@@ -295,6 +301,143 @@ async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_editorconfig_settings(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-root",
+        json!({
+            ".editorconfig": "root = true\n\n[*]\nindent_style = tab\ntab_width = 8\nend_of_line = crlf\n\n[*.md]\ntrim_trailing_whitespace = false\n",
+            ".zed": {
+                "settings.json": r#"{ "remove_trailing_whitespace_on_save": true }"#,
+            },
+            "a.rs": "fn a() {}\n",
+            "README.md": "# Readme\n",
+            "b": {
+                ".editorconfig": "[*.rs]\nindent_style = space\nindent_size = 2\n",
+                "b.rs": "fn b() {}\n",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/the-root".as_ref()], cx).await;
+    let worktree = project.update(cx, |project, cx| project.worktrees(cx).next().unwrap());
+    cx.executor().run_until_parked();
+
+    let settings_for = |path: &str, cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            let tree = worktree.read(cx);
+            let file =
+                File::for_entry(tree.entry_for_path(path).unwrap().clone(), worktree.clone());
+            language_settings(None, Some(&(file as _)), cx)
+        })
+    };
+
+    let settings_a = settings_for("a.rs", cx);
+    assert!(settings_a.hard_tabs);
+    assert_eq!(settings_a.tab_size.get(), 8);
+    assert!(settings_a.remove_trailing_whitespace_on_save);
+
+    // The project settings take precedence over the `.editorconfig` files.
+    let settings_readme = settings_for("README.md", cx);
+    assert!(settings_readme.remove_trailing_whitespace_on_save);
+
+    let settings_b = settings_for("b/b.rs", cx);
+    assert!(!settings_b.hard_tabs);
+    assert_eq!(settings_b.tab_size.get(), 2);
+
+    // Buffers are saved with the line endings of the `.editorconfig` files.
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/the-root/a.rs", cx)
+        })
+        .await
+        .unwrap();
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.load("/the-root/a.rs".as_ref()).await.unwrap(),
+        "fn a() {}\r\n"
+    );
+
+    // Changes to the `.editorconfig` files are picked up.
+    fs.save(
+        "/the-root/b/.editorconfig".as_ref(),
+        &"[*.rs]\nindent_style = space\nindent_size = 3\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+
+    let settings_b = settings_for("b/b.rs", cx);
+    assert!(!settings_b.hard_tabs);
+    assert_eq!(settings_b.tab_size.get(), 3);
+}
+
+#[gpui::test]
+async fn test_project_snippets(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-root",
+        json!({
+            ".zed": {
+                "snippets": {
+                    "rust.json": r#"{
+                        // Comments are allowed.
+                        "Test": { "prefix": "test", "body": "fn test_$1() {}" }
+                    }"#,
+                },
+            },
+            "a.rs": "fn a() {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/the-root".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+
+    let prefixes = |cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .snippets()
+                .read(cx)
+                .snippets_for(Some("rust".to_string()), cx)
+                .into_iter()
+                .flat_map(|snippet| snippet.prefix.clone())
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(prefixes(cx), ["test"]);
+
+    // Changes to the snippets files are picked up.
+    fs.save(
+        "/the-root/.zed/snippets/rust.json".as_ref(),
+        &r#"{ "Main": { "prefix": "main", "body": "fn main() {}" } }"#.into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(prefixes(cx), ["main"]);
+
+    fs.remove_file(
+        "/the-root/.zed/snippets/rust.json".as_ref(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    assert!(prefixes(cx).is_empty());
+}
+
 #[gpui::test]
 async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1608,6 +1751,73 @@ async fn test_toggling_enable_language_server(cx: &mut gpui::TestAppContext) {
         .await;
 }
 
+#[gpui::test]
+async fn test_large_file_mode(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings::<ProjectSettings>(cx, |settings| {
+                settings.large_files.line_threshold = 3;
+            });
+        })
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "large.rs": "fn a() {}\r\n\r\nfn b() {}\r\n",
+            "small.rs": "fn a() {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    let mut fake_servers =
+        language_registry.register_fake_lsp_adapter("Rust", FakeLspAdapter::default());
+    language_registry.add(rust_lang());
+
+    let large_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/large.rs", cx)
+        })
+        .await
+        .unwrap();
+    let _small_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/small.rs", cx)
+        })
+        .await
+        .unwrap();
+
+    // Only the small file is opened in the language server.
+    let mut fake_server = fake_servers.next().await.unwrap();
+    assert_eq!(
+        fake_server
+            .receive_notification::<lsp::notification::DidOpenTextDocument>()
+            .await
+            .text_document
+            .uri
+            .as_str(),
+        "file:///dir/small.rs"
+    );
+    cx.executor().run_until_parked();
+
+    project.update(cx, |project, cx| {
+        let buffer = large_buffer.read(cx);
+        assert!(buffer.is_large_file());
+        assert_eq!(buffer.text(), "fn a() {}\n\nfn b() {}\n");
+        assert_eq!(buffer.line_ending(), LineEnding::Windows);
+        assert_eq!(buffer.language().unwrap().name().as_ref(), "Rust");
+        assert!(buffer.snapshot().syntax_layers().next().is_none());
+        assert!(project
+            .language_servers_for_buffer(buffer, cx)
+            .next()
+            .is_none());
+    });
+}
+
 #[gpui::test(iterations = 3)]
 async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -2776,6 +2986,158 @@ async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "a.ts": "let a = b;\nconst c = a;",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp_adapter(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![
+                                    lsp::SemanticTokenType::VARIABLE,
+                                    lsp::SemanticTokenType::PARAMETER,
+                                ],
+                                token_modifiers: vec![
+                                    lsp::SemanticTokenModifier::DECLARATION,
+                                    lsp::SemanticTokenModifier::READONLY,
+                                ],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..lsp::SemanticTokensOptions::default()
+                        },
+                    ),
+                ),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer("/dir/a.ts", cx))
+        .await
+        .unwrap();
+    let fake_server = fake_language_servers.next().await.unwrap();
+
+    let token =
+        |delta_line, delta_start, length, token_type, token_modifiers_bitset| lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset,
+        };
+    let describe_tokens = |tokens: Vec<SemanticToken>, cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            tokens
+                .into_iter()
+                .map(|token| {
+                    (
+                        buffer.text_for_range(token.range).collect::<String>(),
+                        token.token_type,
+                        token.modifiers,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // The first request fetches all of the tokens, whose positions are relative to each other.
+    fake_server.handle_request::<lsp::request::SemanticTokensFullRequest, _, _>(
+        move |_, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".into()),
+                    data: vec![
+                        token(0, 4, 1, 0, 1),
+                        token(0, 4, 1, 1, 0),
+                        token(1, 6, 1, 0, 3),
+                    ],
+                },
+            )))
+        },
+    );
+    let tokens = project
+        .update(cx, |project, cx| {
+            project.semantic_tokens(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        describe_tokens(tokens, cx),
+        [
+            (
+                "a".to_string(),
+                "variable".to_string(),
+                vec!["declaration".to_string()]
+            ),
+            ("b".to_string(), "parameter".to_string(), vec![]),
+            (
+                "c".to_string(),
+                "variable".to_string(),
+                vec!["declaration".to_string(), "readonly".to_string()]
+            ),
+        ]
+    );
+
+    // The next requests only fetch the changes to the previous tokens.
+    fake_server.handle_request::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.previous_result_id, "1");
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                lsp::SemanticTokensDelta {
+                    result_id: Some("2".into()),
+                    edits: vec![lsp::SemanticTokensEdit {
+                        start: 5,
+                        delete_count: 5,
+                        data: Some(vec![token(0, 4, 1, 0, 0)]),
+                    }],
+                },
+            )))
+        },
+    );
+    let tokens = project
+        .update(cx, |project, cx| {
+            project.semantic_tokens(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        describe_tokens(tokens, cx),
+        [
+            (
+                "a".to_string(),
+                "variable".to_string(),
+                vec!["declaration".to_string()]
+            ),
+            ("b".to_string(), "variable".to_string(), vec![]),
+            (
+                "c".to_string(),
+                "variable".to_string(),
+                vec!["declaration".to_string(), "readonly".to_string()]
+            ),
+        ]
+    );
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_save_file(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4408,6 +4770,165 @@ async fn test_search_multiple_worktrees_with_inclusions(cx: &mut gpui::TestAppCo
     );
 }
 
+#[gpui::test]
+async fn test_branches(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            ".git": {},
+            "a.txt": "a",
+        }),
+    )
+    .await;
+    let branch = |name: &str, is_remote: bool| Branch {
+        is_head: false,
+        name: name.into(),
+        is_remote,
+        upstream: None,
+        unix_timestamp: None,
+    };
+    fs.set_branches_for_repo(
+        Path::new("/dir/.git"),
+        vec![branch("main", false), branch("origin/main", true)],
+    );
+    fs.set_branch_name(Path::new("/dir/.git"), Some("main"));
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+
+    let branches = project
+        .update(cx, |project, cx| project.branches(cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        branches
+            .iter()
+            .map(|branch| (&*branch.name, branch.is_head, branch.is_remote))
+            .collect::<Vec<_>>(),
+        [("main", true, false), ("origin/main", false, true)]
+    );
+
+    project
+        .update(cx, |project, cx| {
+            project.create_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    assert!(project
+        .update(cx, |project, cx| project
+            .create_branch("feature".into(), cx))
+        .await
+        .is_err());
+    project
+        .update(cx, |project, cx| {
+            project.change_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    let branches = project
+        .update(cx, |project, cx| project.branches(cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        branches
+            .iter()
+            .map(|branch| (&*branch.name, branch.is_head))
+            .collect::<Vec<_>>(),
+        [("main", false), ("origin/main", false), ("feature", true)]
+    );
+}
+
+#[gpui::test]
+async fn test_staging_and_committing(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            ".git": {},
+            "a.txt": "a",
+            "b.txt": "b",
+        }),
+    )
+    .await;
+    let status = |path: &str, staged, unstaged| StagingStatus {
+        repo_path: RepoPath(path.into()),
+        staged,
+        unstaged,
+    };
+    fs.set_staging_statuses_for_repo(
+        Path::new("/dir/.git"),
+        vec![
+            status("a.txt", None, Some(FileChange::Modified)),
+            status("b.txt", None, Some(FileChange::Untracked)),
+        ],
+    );
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+
+    project
+        .update(cx, |project, cx| {
+            project.stage_paths(vec![RepoPath("a.txt".into()), RepoPath("b.txt".into())], cx)
+        })
+        .await
+        .unwrap();
+    project
+        .update(cx, |project, cx| {
+            project.unstage_paths(vec![RepoPath("b.txt".into())], cx)
+        })
+        .await
+        .unwrap();
+    let statuses = project
+        .update(cx, |project, cx| project.staging_statuses(cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        statuses,
+        [
+            status("a.txt", Some(FileChange::Modified), None),
+            status("b.txt", None, Some(FileChange::Untracked)),
+        ]
+    );
+
+    project
+        .update(cx, |project, cx| {
+            project.commit("Change a".into(), false, cx)
+        })
+        .await
+        .unwrap();
+    let statuses = project
+        .update(cx, |project, cx| project.staging_statuses(cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        statuses,
+        [status("b.txt", None, Some(FileChange::Untracked))]
+    );
+    // Nothing is left to commit, but the last commit can still be amended.
+    assert!(project
+        .update(cx, |project, cx| project.commit(
+            "Change b".into(),
+            false,
+            cx
+        ))
+        .await
+        .is_err());
+    project
+        .update(cx, |project, cx| {
+            project.commit("Change a, again".into(), true, cx)
+        })
+        .await
+        .unwrap();
+    let message = project
+        .update(cx, |project, cx| project.head_commit_message(cx))
+        .await
+        .unwrap();
+    assert_eq!(message.as_deref(), Some("Change a, again"));
+}
+
 #[gpui::test]
 async fn test_search_in_gitignored_dirs(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4979,6 +5500,88 @@ async fn test_multiple_language_server_actions(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_syntax_parameter_hints(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "a.rs": "fn add(left: i32, right: i32) -> i32 { left + right }",
+            "b.rs": "fn main() { let right = 2; add(1, right); add(1 /* one */, 2); }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(Arc::new(
+        Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::language()),
+        )
+        .with_parameter_hints_query(
+            r#"
+            (call_expression
+                function: (identifier) @call.name
+                arguments: (arguments) @call.arguments)
+            (function_item
+                name: (identifier) @definition.name
+                parameters: (parameters) @definition.parameters)
+            (parameters (parameter pattern: (_) @parameter))
+            "#,
+        )
+        .unwrap(),
+    ));
+
+    let _buffer_a = project
+        .update(cx, |project, cx| project.open_local_buffer("/dir/a.rs", cx))
+        .await
+        .unwrap();
+    let buffer_b = project
+        .update(cx, |project, cx| project.open_local_buffer("/dir/b.rs", cx))
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    // Without a language server, the parameters of the functions defined in the open buffers
+    // name the arguments, except for the ones that already tell their parameter's name.
+    let len = buffer_b.read_with(cx, |buffer, _| buffer.len());
+    let hints = project
+        .update(cx, |project, cx| {
+            project.inlay_hints(buffer_b.clone(), 0..len, cx)
+        })
+        .await
+        .unwrap();
+    let hints = buffer_b.read_with(cx, |buffer, _| {
+        hints
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("unexpected label parts");
+                };
+                (hint.position.to_offset(buffer), label)
+            })
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        hints,
+        vec![
+            (31, "left:".to_string()),
+            (46, "left:".to_string()),
+            (59, "right:".to_string()),
+        ]
+    );
+}
+
 #[gpui::test]
 async fn test_reordering_worktrees(cx: &mut gpui::TestAppContext) {
     init_test(cx);
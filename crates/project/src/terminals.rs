@@ -1,22 +1,31 @@
 use crate::Project;
-use anyhow::Context as _;
+use anyhow::{Context as _, Result};
+use client::{proto, TypedEnvelope};
 use collections::HashMap;
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    StreamExt as _,
+};
 use gpui::{
-    AnyWindowHandle, AppContext, Context, Entity, Model, ModelContext, SharedString, WeakModel,
+    AnyWindowHandle, AppContext, AsyncAppContext, Context, Entity, Model, ModelContext,
+    SharedString, WeakModel,
 };
 use itertools::Itertools;
+use remote::SshSession;
 use settings::{Settings, SettingsLocation};
 use smol::channel::bounded;
 use std::{
     env,
     fs::File,
+    future::Future,
     io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use task::{Shell, SpawnInTerminal, TerminalWorkDir};
 use terminal::{
     terminal_settings::{self, TerminalSettings, VenvSettingsContent},
-    TaskState, TaskStatus, Terminal, TerminalBuilder,
+    RemoteTerminalInput, RemoteTerminalOutput, TaskState, TaskStatus, Terminal, TerminalBuilder,
 };
 use util::ResultExt;
 
@@ -25,6 +34,10 @@ use util::ResultExt;
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakModel<terminal::Terminal>>,
+    /// Where the output of the terminals open on the remote host of an SSH project goes,
+    /// by the ids they were opened with.
+    pub(crate) remote_outputs: HashMap<u64, UnboundedSender<RemoteTerminalOutput>>,
+    pub(crate) next_remote_id: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +52,17 @@ impl Project {
         pathbuf: Option<&Path>,
         cx: &AppContext,
     ) -> Option<TerminalWorkDir> {
+        if self.ssh_session.is_some() {
+            let path = pathbuf
+                .map(Path::to_path_buf)
+                .or_else(|| {
+                    self.visible_worktrees(cx)
+                        .next()
+                        .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                })
+                .map(|path| path.to_string_lossy().to_string());
+            return Some(TerminalWorkDir::Remote { path });
+        }
         if self.is_local() {
             return Some(TerminalWorkDir::Local(pathbuf?.to_owned()));
         }
@@ -74,6 +98,10 @@ impl Project {
         window: AnyWindowHandle,
         cx: &mut ModelContext<Self>,
     ) -> anyhow::Result<Model<Terminal>> {
+        if spawn_task.is_some() && !self.is_ssh_host_trusted() {
+            anyhow::bail!("tasks can't run until the remote host is trusted");
+        }
+
         // used only for TerminalSettings::get
         let worktree = {
             let terminal_cwd = working_directory.as_ref().and_then(|cwd| cwd.local_path());
@@ -111,47 +139,78 @@ impl Project {
             .and_then(|cwd| cwd.local_path())
             .unwrap_or_else(|| Path::new(""));
 
-        let (spawn_task, shell) = match working_directory.as_ref() {
-            Some(TerminalWorkDir::Ssh { ssh_command, path }) => {
-                log::debug!("Connecting to a remote server: {ssh_command:?}");
-                let tmp_dir = tempfile::tempdir()?;
-                let ssh_shell_result = prepare_ssh_shell(
-                    &mut env,
-                    tmp_dir.path(),
-                    spawn_task.as_ref(),
-                    ssh_command,
-                    path.as_deref(),
-                );
-                retained_script = Some(tmp_dir);
-                let ssh_shell = ssh_shell_result?;
-
-                (
-                    spawn_task.map(|spawn_task| TaskState {
-                        id: spawn_task.id,
-                        full_label: spawn_task.full_label,
-                        label: spawn_task.label,
-                        command_label: spawn_task.command_label,
-                        hide: spawn_task.hide,
-                        status: TaskStatus::Running,
-                        completion_rx,
-                    }),
-                    ssh_shell,
-                )
-            }
-            _ => {
-                if let Some(spawn_task) = spawn_task {
-                    log::debug!("Spawning task: {spawn_task:?}");
-                    env.extend(spawn_task.env);
-                    // Activate minimal Python virtual environment
-                    if let Some(python_settings) = &python_settings.as_option() {
-                        self.set_python_venv_path_for_tasks(
-                            python_settings,
-                            &venv_base_directory,
-                            &mut env,
-                        );
-                    }
+        let builder = if let Some(TerminalWorkDir::Remote { path }) = working_directory.as_ref() {
+            let ssh_session = self
+                .ssh_session
+                .clone()
+                .context("the project has no remote host")?;
+            log::debug!("Opening a terminal on the remote host in {path:?}");
+            // The remote host may not have the terminfo of alacritty, so use one it's
+            // likely to have.
+            env.entry("TERM".to_string())
+                .or_insert_with(|| "xterm-256color".to_string());
+            terminal::insert_term_program_env(&mut env, cx);
+            let (program, args) = match spawn_task.as_ref() {
+                Some(spawn_task) => {
+                    env.extend(spawn_task.env.clone());
+                    (Some(spawn_task.command.clone()), spawn_task.args.clone())
+                }
+                None => (None, Vec::new()),
+            };
+
+            let terminal_id = self.terminals.next_remote_id;
+            self.terminals.next_remote_id += 1;
+            let (input_tx, input_rx) = unbounded();
+            let (output_tx, output_rx) = unbounded();
+            self.terminals.remote_outputs.insert(terminal_id, output_tx);
+            let open = ssh_session.request(proto::OpenTerminal {
+                terminal_id,
+                program,
+                args,
+                cwd: path.clone(),
+                env: env.into_iter().collect(),
+            });
+
+            let builder = TerminalBuilder::new_remote(
+                spawn_task.map(|spawn_task| TaskState {
+                    id: spawn_task.id,
+                    full_label: spawn_task.full_label,
+                    label: spawn_task.label,
+                    command_label: spawn_task.command_label,
+                    hide: spawn_task.hide,
+                    status: TaskStatus::Running,
+                    completion_rx,
+                }),
+                input_tx,
+                output_rx,
+                Some(settings.blinking),
+                settings.alternate_scroll,
+                settings.max_scroll_history_lines,
+                completion_tx,
+                cx,
+            );
+            cx.spawn(move |project, cx| {
+                forward_remote_terminal_input(project, ssh_session, terminal_id, open, input_rx, cx)
+            })
+            .detach();
+            builder
+        } else {
+            let (spawn_task, shell) = match working_directory.as_ref() {
+                Some(TerminalWorkDir::Ssh { ssh_command, path }) => {
+                    log::debug!("Connecting to a remote server: {ssh_command:?}");
+                    let tmp_dir = tempfile::tempdir()?;
+                    let ssh_shell_result = prepare_ssh_shell(
+                        &mut env,
+                        tmp_dir.path(),
+                        spawn_task.as_ref(),
+                        ssh_command,
+                        path.as_deref(),
+                    );
+                    retained_script = Some(tmp_dir);
+                    let ssh_shell = ssh_shell_result?;
+
                     (
-                        Some(TaskState {
+                        spawn_task.map(|spawn_task| TaskState {
                             id: spawn_task.id,
                             full_label: spawn_task.full_label,
                             label: spawn_task.label,
@@ -160,73 +219,96 @@ impl Project {
                             status: TaskStatus::Running,
                             completion_rx,
                         }),
-                        Shell::WithArguments {
-                            program: spawn_task.command,
-                            args: spawn_task.args,
-                        },
+                        ssh_shell,
                     )
-                } else {
-                    (None, settings.shell.clone())
                 }
-            }
+                _ => {
+                    if let Some(spawn_task) = spawn_task {
+                        log::debug!("Spawning task: {spawn_task:?}");
+                        env.extend(spawn_task.env);
+                        // Activate minimal Python virtual environment
+                        if let Some(python_settings) = &python_settings.as_option() {
+                            self.set_python_venv_path_for_tasks(
+                                python_settings,
+                                &venv_base_directory,
+                                &mut env,
+                            );
+                        }
+                        (
+                            Some(TaskState {
+                                id: spawn_task.id,
+                                full_label: spawn_task.full_label,
+                                label: spawn_task.label,
+                                command_label: spawn_task.command_label,
+                                hide: spawn_task.hide,
+                                status: TaskStatus::Running,
+                                completion_rx,
+                            }),
+                            Shell::WithArguments {
+                                program: spawn_task.command,
+                                args: spawn_task.args,
+                            },
+                        )
+                    } else {
+                        (None, settings.shell.clone())
+                    }
+                }
+            };
+
+            TerminalBuilder::new(
+                working_directory
+                    .as_ref()
+                    .and_then(|cwd| cwd.local_path())
+                    .map(ToOwned::to_owned),
+                spawn_task,
+                shell,
+                env,
+                Some(settings.blinking),
+                settings.alternate_scroll,
+                settings.max_scroll_history_lines,
+                window,
+                completion_tx,
+                cx,
+            )?
         };
 
-        let terminal = TerminalBuilder::new(
-            working_directory
-                .as_ref()
-                .and_then(|cwd| cwd.local_path())
-                .map(ToOwned::to_owned),
-            spawn_task,
-            shell,
-            env,
-            Some(settings.blinking),
-            settings.alternate_scroll,
-            settings.max_scroll_history_lines,
-            window,
-            completion_tx,
-            cx,
-        )
-        .map(|builder| {
-            let terminal_handle = cx.new_model(|cx| builder.subscribe(cx));
-
-            self.terminals
-                .local_handles
-                .push(terminal_handle.downgrade());
-
-            let id = terminal_handle.entity_id();
-            cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
-                drop(retained_script);
-                let handles = &mut project.terminals.local_handles;
-
-                if let Some(index) = handles
-                    .iter()
-                    .position(|terminal| terminal.entity_id() == id)
-                {
-                    handles.remove(index);
-                    cx.notify();
-                }
-            })
-            .detach();
+        let terminal_handle = cx.new_model(|cx| builder.subscribe(cx));
 
-            // if the terminal is not a task, activate full Python virtual environment
-            if is_terminal {
-                if let Some(python_settings) = &python_settings.as_option() {
-                    if let Some(activate_script_path) =
-                        self.find_activate_script_path(python_settings, &venv_base_directory)
-                    {
-                        self.activate_python_virtual_environment(
-                            Project::get_activate_command(python_settings),
-                            activate_script_path,
-                            &terminal_handle,
-                            cx,
-                        );
-                    }
-                }
+        self.terminals
+            .local_handles
+            .push(terminal_handle.downgrade());
+
+        let id = terminal_handle.entity_id();
+        cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
+            drop(retained_script);
+            let handles = &mut project.terminals.local_handles;
+
+            if let Some(index) = handles
+                .iter()
+                .position(|terminal| terminal.entity_id() == id)
+            {
+                handles.remove(index);
+                cx.notify();
             }
-            terminal_handle
-        });
+        })
+        .detach();
 
-        terminal
+        // if the terminal is not a task, activate full Python virtual environment
+        if is_terminal {
+            if let Some(python_settings) = &python_settings.as_option() {
+                if let Some(activate_script_path) =
+                    self.find_activate_script_path(python_settings, &venv_base_directory)
+                {
+                    self.activate_python_virtual_environment(
+                        Project::get_activate_command(python_settings),
+                        activate_script_path,
+                        &terminal_handle,
+                        cx,
+                    );
+                }
+            }
+        }
+        Ok(terminal_handle)
     }
 
     pub fn find_activate_script_path(
@@ -308,6 +390,93 @@ impl Project {
     pub fn local_terminal_handles(&self) -> &Vec<WeakModel<terminal::Terminal>> {
         &self.terminals.local_handles
     }
+
+    pub(crate) async fn handle_terminal_output(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::TerminalOutput>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, _| {
+            if let Some(output_tx) = this
+                .terminals
+                .remote_outputs
+                .get(&envelope.payload.terminal_id)
+            {
+                output_tx
+                    .unbounded_send(RemoteTerminalOutput::Data(envelope.payload.data))
+                    .ok();
+            }
+        })
+    }
+
+    pub(crate) async fn handle_terminal_exited(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::TerminalExited>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, _| {
+            if let Some(output_tx) = this
+                .terminals
+                .remote_outputs
+                .remove(&envelope.payload.terminal_id)
+            {
+                output_tx
+                    .unbounded_send(RemoteTerminalOutput::Exit(envelope.payload.exit_code))
+                    .ok();
+            }
+        })
+    }
+}
+
+/// Sends the input of a terminal open on the remote host to it, until the terminal is dropped.
+async fn forward_remote_terminal_input(
+    project: WeakModel<Project>,
+    ssh_session: Arc<SshSession>,
+    terminal_id: u64,
+    open: impl Future<Output = Result<proto::Ack>>,
+    mut input_rx: UnboundedReceiver<RemoteTerminalInput>,
+    mut cx: AsyncAppContext,
+) {
+    if let Err(error) = open.await {
+        log::error!("failed to open a terminal on the remote host: {error:#}");
+        project
+            .update(&mut cx, |project, _| {
+                if let Some(output_tx) = project.terminals.remote_outputs.get(&terminal_id) {
+                    let message = format!("Failed to open the terminal: {error:#}\r\n");
+                    output_tx
+                        .unbounded_send(RemoteTerminalOutput::Data(message.into_bytes()))
+                        .ok();
+                }
+            })
+            .ok();
+        // Keep the error shown until the terminal is closed.
+        while input_rx.next().await.is_some() {}
+    } else {
+        while let Some(input) = input_rx.next().await {
+            let sent = match input {
+                RemoteTerminalInput::Write(data) => {
+                    ssh_session.send(proto::WriteToTerminal { terminal_id, data })
+                }
+                RemoteTerminalInput::Resize { rows, columns } => {
+                    ssh_session.send(proto::ResizeTerminal {
+                        terminal_id,
+                        rows: rows as u32,
+                        columns: columns as u32,
+                    })
+                }
+            };
+            if sent.log_err().is_none() {
+                break;
+            }
+        }
+        ssh_session.send(proto::CloseTerminal { terminal_id }).ok();
+    }
+
+    project
+        .update(&mut cx, |project, _| {
+            project.terminals.remote_outputs.remove(&terminal_id);
+        })
+        .ok();
 }
 
 fn prepare_ssh_shell(
@@ -328,25 +497,7 @@ fn prepare_ssh_shell(
     let ssh_path = tmp_dir.join("ssh");
     let mut ssh_file = File::create(&ssh_path)?;
 
-    let to_run = if let Some(spawn_task) = spawn_task {
-        Some(shlex::try_quote(&spawn_task.command)?)
-            .into_iter()
-            .chain(
-                spawn_task
-                    .args
-                    .iter()
-                    .filter_map(|arg| shlex::try_quote(arg).ok()),
-            )
-            .join(" ")
-    } else {
-        "exec $SHELL -l".to_string()
-    };
-
-    let commands = if let Some(path) = path {
-        format!("cd {path}; {to_run}")
-    } else {
-        format!("cd; {to_run}")
-    };
+    let commands = remote_shell_command_line(spawn_task, path)?;
     let shell_invocation = &format!("sh -c {}", shlex::try_quote(&commands)?);
 
     // To support things like `gh cs ssh`/`coder ssh`, we run whatever command
@@ -371,6 +522,47 @@ fn prepare_ssh_shell(
     Ok(Shell::WithArguments { program, args })
 }
 
+/// Builds the shell command line that runs the task, or a login shell, in `path` of a
+/// remote environment.
+fn remote_shell_command_line(
+    spawn_task: Option<&SpawnInTerminal>,
+    path: Option<&str>,
+) -> anyhow::Result<String> {
+    let to_run = if let Some(spawn_task) = spawn_task {
+        Some(shlex::try_quote(&spawn_task.command)?)
+            .into_iter()
+            .chain(
+                spawn_task
+                    .args
+                    .iter()
+                    .filter_map(|arg| shlex::try_quote(arg).ok()),
+            )
+            .join(" ")
+    } else {
+        "exec $SHELL -l".to_string()
+    };
+
+    // The environment of the local ssh process isn't forwarded, so export the task's
+    // variables in the remote shell instead.
+    let env_exports = spawn_task
+        .map(|spawn_task| {
+            spawn_task
+                .env
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some(format!("export {name}={}; ", shlex::try_quote(value).ok()?))
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    Ok(if let Some(path) = path {
+        format!("cd {path}; {env_exports}{to_run}")
+    } else {
+        format!("cd; {env_exports}{to_run}")
+    })
+}
+
 fn add_environment_path(env: &mut HashMap<String, String>, new_path: &Path) -> anyhow::Result<()> {
     let mut env_paths = vec![new_path.to_path_buf()];
     if let Some(path) = env.get("PATH").or(env::var("PATH").ok().as_ref()) {
@@ -11,6 +11,7 @@ use editor::{
     Editor,
 };
 use file_icons::FileIcons;
+use futures::{channel::mpsc, Future, StreamExt as _};
 
 use anyhow::{anyhow, Result};
 use collections::{hash_map, BTreeSet, HashMap};
@@ -19,9 +20,9 @@ use gpui::{
     actions, anchored, deferred, div, impl_actions, px, uniform_list, Action, AnyElement,
     AppContext, AssetSource, AsyncWindowContext, ClipboardItem, DismissEvent, Div, DragMoveEvent,
     EventEmitter, ExternalPaths, FocusHandle, FocusableView, InteractiveElement, KeyContext,
-    ListSizingBehavior, Model, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
-    PromptLevel, Render, Stateful, Styled, Subscription, Task, UniformListScrollHandle, View,
-    ViewContext, VisualContext as _, WeakView, WindowContext,
+    ListSizingBehavior, Model, MouseButton, MouseDownEvent, ParentElement, PathPromptOptions,
+    Pixels, Point, PromptLevel, Render, Stateful, Styled, Subscription, Task,
+    UniformListScrollHandle, View, ViewContext, VisualContext as _, WeakView, WindowContext,
 };
 use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
 use project::{Entry, EntryKind, Fs, Project, ProjectEntryId, ProjectPath, Worktree, WorktreeId};
@@ -42,8 +43,8 @@ use ui::{prelude::*, v_flex, ContextMenu, Icon, KeyBinding, Label, ListItem, Too
 use util::{maybe, ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
-    notifications::{DetachAndPromptErr, NotifyTaskExt},
-    DraggedSelection, OpenInTerminal, SelectedEntry, Workspace,
+    notifications::{DetachAndPromptErr, NotificationId, NotifyTaskExt},
+    DraggedSelection, OpenInTerminal, SelectedEntry, Toast, Workspace,
 };
 use worktree::CreatedEntry;
 
@@ -148,6 +149,8 @@ actions!(
         UnfoldDirectory,
         FoldDirectory,
         SelectParent,
+        Download,
+        Upload,
     ]
 );
 
@@ -462,6 +465,7 @@ impl ProjectPanel {
             let worktree_id = worktree.id();
             let is_read_only = project.is_read_only();
             let is_remote = project.is_remote() && project.dev_server_project_id().is_none();
+            let is_via_ssh = project.ssh_session().is_some();
 
             let context_menu = ContextMenu::build(cx, |menu, cx| {
                 menu.context(self.focus_handle.clone()).map(|menu| {
@@ -516,6 +520,13 @@ impl ProjectPanel {
                             .separator()
                             .action("Copy Path", Box::new(CopyPath))
                             .action("Copy Relative Path", Box::new(CopyRelativePath))
+                            .when(is_via_ssh, |menu| {
+                                menu.separator()
+                                    .when(!is_dir, |menu| {
+                                        menu.action("Download…", Box::new(Download))
+                                    })
+                                    .action("Upload Files…", Box::new(Upload))
+                            })
                             .separator()
                             .action("Rename", Box::new(Rename))
                             .when(!is_root, |menu| {
@@ -1398,6 +1409,92 @@ impl ProjectPanel {
         }
     }
 
+    fn download(&mut self, _: &Download, cx: &mut ViewContext<Self>) {
+        let Some((worktree, entry)) = self.selected_entry(cx) else {
+            return;
+        };
+        let Some(session) = self.project.read(cx).ssh_session() else {
+            return;
+        };
+        if entry.is_dir() {
+            return;
+        }
+        let src_path = worktree.abs_path().join(&entry.path);
+        let transfer_id = entry.id.to_usize();
+        let dest_path = cx.prompt_for_new_path(util::paths::home_dir());
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            let Some(dest_path) = dest_path.await?? else {
+                return Ok(());
+            };
+            let name = file_name(&src_path);
+            transfer_file(
+                workspace,
+                transfer_id,
+                format!("Downloading {name}"),
+                format!("Downloaded {name}"),
+                move |on_progress| async move {
+                    session
+                        .download_file(&src_path, &dest_path, on_progress)
+                        .await
+                },
+                &mut cx,
+            )
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn upload(&mut self, _: &Upload, cx: &mut ViewContext<Self>) {
+        let Some((worktree, entry)) = self.selected_entry(cx) else {
+            return;
+        };
+        let Some(session) = self.project.read(cx).ssh_session() else {
+            return;
+        };
+        let entry_path = worktree.abs_path().join(&entry.path);
+        let dest_dir = if entry.is_dir() {
+            entry_path
+        } else {
+            match entry_path.parent() {
+                Some(parent) => parent.to_path_buf(),
+                None => return,
+            }
+        };
+        let transfer_id = entry.id.to_usize();
+        let src_paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: true,
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            let Some(src_paths) = src_paths.await?? else {
+                return Ok(());
+            };
+            for src_path in src_paths {
+                let name = file_name(&src_path);
+                let dest_path = dest_dir.join(&name);
+                let session = session.clone();
+                transfer_file(
+                    workspace.clone(),
+                    transfer_id,
+                    format!("Uploading {name}"),
+                    format!("Uploaded {name}"),
+                    move |on_progress| async move {
+                        session
+                            .upload_file(&src_path, &dest_path, on_progress)
+                            .await
+                    },
+                    &mut cx,
+                )
+                .await?;
+            }
+            Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn new_search_in_directory(
         &mut self,
         _: &NewSearchInDirectory,
@@ -2383,6 +2480,64 @@ impl ProjectPanel {
     }
 }
 
+/// The notification reporting the progress of a file transfer to or from a remote host.
+struct FileTransfer;
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
+/// Runs a file transfer in the background, showing its progress in a toast that is
+/// replaced by the outcome once the transfer is done.
+async fn transfer_file<F, Fut>(
+    workspace: WeakView<Workspace>,
+    transfer_id: usize,
+    progress_message: String,
+    done_message: String,
+    transfer: F,
+    cx: &mut AsyncWindowContext,
+) -> Result<()>
+where
+    F: 'static + Send + FnOnce(Box<dyn FnMut(u64, u64) + Send>) -> Fut,
+    Fut: 'static + Send + Future<Output = Result<()>>,
+{
+    let notification_id = NotificationId::identified::<FileTransfer>(transfer_id);
+    let (progress_tx, mut progress_rx) = mpsc::unbounded();
+    let mut reported_percentage = None;
+    let on_progress = Box::new(move |transferred_len: u64, len: u64| {
+        let percentage = transferred_len * 100 / len.max(1);
+        if reported_percentage != Some(percentage) {
+            reported_percentage = Some(percentage);
+            progress_tx.unbounded_send(percentage).ok();
+        }
+    });
+    let task = cx.background_executor().spawn(transfer(on_progress));
+
+    while let Some(percentage) = progress_rx.next().await {
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(
+                    notification_id.clone(),
+                    format!("{progress_message}… {percentage}%"),
+                ),
+                cx,
+            )
+        })?;
+    }
+
+    let result = task.await;
+    workspace.update(cx, |workspace, cx| {
+        let toast = match &result {
+            Ok(()) => Toast::new(notification_id, done_message).autohide(),
+            Err(error) => Toast::new(notification_id, format!("{error:#}")),
+        };
+        workspace.show_toast(toast, cx)
+    })?;
+    result
+}
+
 impl Render for ProjectPanel {
     fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
         let has_worktree = self.visible_entries.len() != 0;
@@ -2442,6 +2597,10 @@ impl Render for ProjectPanel {
                     el.on_action(cx.listener(Self::reveal_in_finder))
                         .on_action(cx.listener(Self::open_in_terminal))
                 })
+                .when(project.ssh_session().is_some(), |el| {
+                    el.on_action(cx.listener(Self::download))
+                        .on_action(cx.listener(Self::upload))
+                })
                 .on_mouse_down(
                     MouseButton::Right,
                     cx.listener(move |this, event: &MouseDownEvent, cx| {
@@ -238,6 +238,10 @@ messages!(
     (GetChannelMessagesResponse, Background),
     (GetCodeActions, Background),
     (GetCodeActionsResponse, Background),
+    (GetCodeLens, Background),
+    (GetCodeLensResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
     (GetCompletions, Background),
     (GetCompletionsResponse, Background),
     (GetDefinition, Background),
@@ -365,6 +369,8 @@ messages!(
     (SetRoomParticipantRole, Foreground),
     (BlameBuffer, Foreground),
     (BlameBufferResponse, Foreground),
+    (GetBufferGitInfo, Background),
+    (GetBufferGitInfoResponse, Background),
     (CreateDevServerProject, Background),
     (CreateDevServerProjectResponse, Foreground),
     (CreateDevServer, Foreground),
@@ -401,6 +407,20 @@ messages!(
     (SynchronizeContextsResponse, Foreground),
     (AddWorktree, Foreground),
     (AddWorktreeResponse, Foreground),
+    (GetRemoteServerInfo, Background),
+    (GetRemoteServerInfoResponse, Background),
+    (RemoteHandshake, Foreground),
+    (RemoteHandshakeResponse, Foreground),
+    (FindSearchCandidates, Background),
+    (FindSearchCandidatesResponse, Background),
+    (InstallLanguageServer, Background),
+    (InstallLanguageServerResponse, Background),
+    (OpenTerminal, Foreground),
+    (WriteToTerminal, Foreground),
+    (ResizeTerminal, Foreground),
+    (CloseTerminal, Foreground),
+    (TerminalOutput, Foreground),
+    (TerminalExited, Foreground),
 );
 
 request_messages!(
@@ -430,6 +450,8 @@ request_messages!(
     (GetChannelMessages, GetChannelMessagesResponse),
     (GetChannelMessagesById, GetChannelMessagesResponse),
     (GetCodeActions, GetCodeActionsResponse),
+    (GetCodeLens, GetCodeLensResponse),
+    (GetSemanticTokens, GetSemanticTokensResponse),
     (GetCompletions, GetCompletionsResponse),
     (GetDefinition, GetDefinitionResponse),
     (GetImplementation, GetImplementationResponse),
@@ -502,6 +524,7 @@ request_messages!(
     (LspExtExpandMacro, LspExtExpandMacroResponse),
     (SetRoomParticipantRole, Ack),
     (BlameBuffer, BlameBufferResponse),
+    (GetBufferGitInfo, GetBufferGitInfoResponse),
     (CreateDevServerProject, CreateDevServerProjectResponse),
     (CreateDevServer, CreateDevServerResponse),
     (ShutdownDevServer, Ack),
@@ -519,6 +542,11 @@ request_messages!(
     (OpenContext, OpenContextResponse),
     (SynchronizeContexts, SynchronizeContextsResponse),
     (AddWorktree, AddWorktreeResponse),
+    (GetRemoteServerInfo, GetRemoteServerInfoResponse),
+    (RemoteHandshake, RemoteHandshakeResponse),
+    (FindSearchCandidates, FindSearchCandidatesResponse),
+    (InstallLanguageServer, InstallLanguageServerResponse),
+    (OpenTerminal, Ack),
 );
 
 entity_messages!(
@@ -535,7 +563,10 @@ entity_messages!(
     DeleteProjectEntry,
     ExpandProjectEntry,
     FormatBuffers,
+    GetBufferGitInfo,
     GetCodeActions,
+    GetCodeLens,
+    GetSemanticTokens,
     GetCompletions,
     GetDefinition,
     GetImplementation,
@@ -41,7 +41,7 @@ fn default_create_new_window() -> bool {
 }
 
 gpui::impl_actions!(projects, [OpenRecent]);
-gpui::actions!(projects, [OpenRemote]);
+gpui::actions!(projects, [OpenRemote, OpenWsl]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(RecentProjects::register).detach();
@@ -251,6 +251,9 @@ impl PickerDelegate for RecentProjectsDelegate {
                             dev_server_project.paths.join("")
                         )
                     }
+                    SerializedWorkspaceLocation::Ssh(ssh_project) => {
+                        format!("{}{}", ssh_project.host, ssh_project.paths.join(""))
+                    }
                 };
 
                 StringMatchCandidate::new(id, combined_string)
@@ -357,6 +360,9 @@ impl PickerDelegate for RecentProjectsDelegate {
                                 };
                                 open_dev_server_project(replace_current_window, dev_server_project.id, project_id, cx)
                         }
+                            // SSH projects are only reopened when restoring a session, and
+                            // aren't listed here.
+                            SerializedWorkspaceLocation::Ssh(_) => Task::ready(Ok(())),
                     }
                 }
                 })
@@ -418,6 +424,9 @@ impl PickerDelegate for RecentProjectsDelegate {
                     dev_server_project.paths.join(", ")
                 ))])
             }
+            SerializedWorkspaceLocation::Ssh(ssh_project) => Arc::new(vec![PathBuf::from(
+                format!("{}:{}", ssh_project.host, ssh_project.paths.join(", ")),
+            )]),
         };
 
         let (match_labels, paths): (Vec<_>, Vec<_>) = paths
@@ -533,6 +542,16 @@ impl PickerDelegate for RecentProjectsDelegate {
                         .child(Label::new("Open remote folder…").color(Color::Muted))
                         .on_click(|_, cx| cx.dispatch_action(OpenRemote.boxed_clone())),
                 )
+                .when(cfg!(target_os = "windows"), |footer| {
+                    footer.child(
+                        ButtonLike::new("wsl")
+                            .when_some(KeyBinding::for_action(&OpenWsl, cx), |button, key| {
+                                button.child(key)
+                            })
+                            .child(Label::new("Open WSL folder…").color(Color::Muted))
+                            .on_click(|_, cx| cx.dispatch_action(OpenWsl.boxed_clone())),
+                    )
+                })
                 .child(
                     ButtonLike::new("local")
                         .when_some(
@@ -0,0 +1,138 @@
+use crate::ssh_session::RemoteConnection;
+use anyhow::{anyhow, Context as _, Result};
+use smol::process::{self, Stdio};
+
+/// Options for attaching to a running container.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DockerConnectionOptions {
+    /// The name or id of the container.
+    pub container: String,
+    /// The user to run commands as, instead of the container's default user.
+    pub user: Option<String>,
+}
+
+/// A running container, as listed by `docker ps`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DockerContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// The container's working directory, which is where devcontainers mount the
+    /// workspace folder.
+    pub working_dir: Option<String>,
+}
+
+/// A connection to a container through `docker exec`.
+pub struct DockerConnection {
+    options: DockerConnectionOptions,
+}
+
+impl DockerConnection {
+    pub(crate) async fn new(options: DockerConnectionOptions) -> Result<Self> {
+        let output = process::Command::new("docker")
+            .args([
+                "inspect",
+                "--format",
+                "{{.State.Running}}",
+                &options.container,
+            ])
+            .output()
+            .await
+            .context("failed to run docker")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to find container {}: {}",
+                options.container,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        if String::from_utf8_lossy(&output.stdout).trim() != "true" {
+            return Err(anyhow!("container {} is not running", options.container));
+        }
+        Ok(Self { options })
+    }
+
+    /// Lists the running containers.
+    pub async fn list_containers() -> Result<Vec<DockerContainer>> {
+        let output = process::Command::new("docker")
+            .args(["ps", "--format", "{{.ID}}\t{{.Names}}\t{{.Image}}"])
+            .output()
+            .await
+            .context("failed to run docker")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to list containers: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let mut containers = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                Some(DockerContainer {
+                    id: fields.next()?.to_string(),
+                    name: fields.next()?.to_string(),
+                    image: fields.next()?.to_string(),
+                    working_dir: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        if containers.is_empty() {
+            return Ok(containers);
+        }
+
+        let output = process::Command::new("docker")
+            .args(["inspect", "--format", "{{.Config.WorkingDir}}"])
+            .args(containers.iter().map(|container| &container.id))
+            .output()
+            .await
+            .context("failed to inspect containers")?;
+        if output.status.success() {
+            for (container, working_dir) in containers
+                .iter_mut()
+                .zip(String::from_utf8_lossy(&output.stdout).lines())
+            {
+                let working_dir = working_dir.trim();
+                if !working_dir.is_empty() {
+                    container.working_dir = Some(working_dir.to_string());
+                }
+            }
+        }
+
+        Ok(containers)
+    }
+
+    fn exec_args(&self) -> Vec<String> {
+        let mut args = vec!["exec".to_string(), "-i".to_string()];
+        if let Some(user) = &self.options.user {
+            args.extend(["-u".to_string(), user.clone()]);
+        }
+        args.push(self.options.container.clone());
+        args
+    }
+}
+
+impl RemoteConnection for DockerConnection {
+    fn command(&self, program: &str) -> process::Command {
+        // Unlike ssh, `docker exec` doesn't go through a shell and starts in the
+        // container's working directory, so wrap the command line accordingly. Any
+        // arguments added later become the positional parameters of that shell.
+        let mut command = process::Command::new("docker");
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(self.exec_args())
+            .args(["sh", "-c", &format!("cd && {program} \"$@\""), "sh"]);
+        command
+    }
+
+    fn shell_command_prefix(&self) -> Vec<String> {
+        Some("docker".to_string())
+            .into_iter()
+            .chain(self.exec_args())
+            .chain(["sh".to_string(), "-c".to_string()])
+            .collect()
+    }
+}
@@ -21,6 +21,15 @@ impl<'a> LogRecord<'a> {
         }
     }
 
+    /// Formats the record as a line of a log file, e.g. `INFO [module] message`.
+    pub fn to_log_line(&self) -> String {
+        let level = deserialize_level(self.level).map_or("UNKNOWN", |level| level.as_str());
+        match self.module_path {
+            Some(module_path) => format!("{level} [{module_path}] {}", self.message),
+            None => format!("{level} {}", self.message),
+        }
+    }
+
     pub fn log(&'a self, logger: &dyn Log) {
         if let Some(level) = deserialize_level(self.level) {
             logger.log(
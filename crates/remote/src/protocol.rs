@@ -1,12 +1,20 @@
-use anyhow::Result;
-use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use anyhow::{anyhow, Result};
+use futures::{
+    channel::mpsc, select_biased, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+    FutureExt as _, StreamExt as _,
+};
+use parking_lot::Mutex;
 use prost::Message as _;
 use rpc::proto::Envelope;
-use std::mem::size_of;
+use std::{collections::VecDeque, mem::size_of, time::Duration};
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub struct MessageId(pub u32);
 
+/// The version of the messages exchanged with the remote server, which must be
+/// bumped whenever they change in a way that older servers or clients can't handle.
+pub const PROTOCOL_VERSION: u32 = 2;
+
 pub type MessageLen = u32;
 pub const MESSAGE_LEN_SIZE: usize = size_of::<MessageLen>();
 
@@ -49,3 +57,423 @@ pub async fn write_message<S: AsyncWrite + Unpin>(
     stream.write_all(buffer).await?;
     Ok(())
 }
+
+/// A frame of a resumable stream, over which the messages of a session are exchanged
+/// with a remote server that outlives the connections to it. See [`ResumableState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+    Message(Envelope),
+    /// The number of messages received so far, which acknowledges them. It is also the
+    /// first frame sent over each new connection, telling the peer which messages to
+    /// send again.
+    Ack(u64),
+    /// Sent when ending the session on purpose, after which the peer shuts down rather
+    /// than waiting for the session to be resumed.
+    Goodbye,
+}
+
+const FRAME_MESSAGE: u8 = 0;
+const FRAME_ACK: u8 = 1;
+const FRAME_GOODBYE: u8 = 2;
+
+/// How many messages are received before acknowledging them, which bounds how many
+/// messages the peer keeps around in case they need to be sent again.
+const ACK_INTERVAL: u64 = 64;
+
+/// How long a connection of a resumable session may go without receiving anything
+/// before it's considered broken. Both sides send acknowledgements while idle, so this
+/// is only reached when the network went away without the connection noticing.
+pub const RESUMABLE_READ_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// How long a resumable session is kept after its connection broke, for the client to
+/// reconnect and resume it.
+pub const RESUMABLE_SESSION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+pub async fn read_frame<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+) -> Result<Frame> {
+    let mut kind = [0; 1];
+    stream.read_exact(&mut kind).await?;
+    match kind[0] {
+        FRAME_MESSAGE => Ok(Frame::Message(read_message(stream, buffer).await?)),
+        FRAME_ACK => {
+            let mut count = [0; size_of::<u64>()];
+            stream.read_exact(&mut count).await?;
+            Ok(Frame::Ack(u64::from_le_bytes(count)))
+        }
+        FRAME_GOODBYE => Ok(Frame::Goodbye),
+        kind => Err(anyhow!("invalid frame kind {kind}")),
+    }
+}
+
+pub async fn write_frame<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+    frame: &Frame,
+) -> Result<()> {
+    match frame {
+        Frame::Message(message) => {
+            stream.write_all(&[FRAME_MESSAGE]).await?;
+            buffer.clear();
+            buffer.extend_from_slice(&(message.encoded_len() as MessageLen).to_le_bytes());
+            message.encode(buffer)?;
+            stream.write_all(buffer).await?;
+        }
+        Frame::Ack(count) => {
+            stream.write_all(&[FRAME_ACK]).await?;
+            stream.write_all(&count.to_le_bytes()).await?;
+        }
+        Frame::Goodbye => stream.write_all(&[FRAME_GOODBYE]).await?,
+    }
+    stream.flush().await?;
+    Ok(())
+}
+
+/// One end of a session whose messages are exchanged over a succession of connections,
+/// such as ssh channels that break when the network changes. Messages are kept until
+/// the peer acknowledges them, so that those lost with a broken connection are sent
+/// again over the next one.
+#[derive(Default)]
+pub struct ResumableState {
+    /// The messages sent that the peer hasn't acknowledged yet, oldest first.
+    unacknowledged: VecDeque<Envelope>,
+    /// The number of messages sent, including the unacknowledged ones.
+    sent_count: u64,
+    received_count: u64,
+    /// The number of received messages that were last acknowledged to the peer.
+    acknowledged_count: u64,
+}
+
+impl ResumableState {
+    /// Records a message about to be sent, until the peer acknowledges it.
+    pub fn sent(&mut self, message: Envelope) {
+        self.unacknowledged.push_back(message);
+        self.sent_count += 1;
+    }
+
+    /// Records a received message, returning the acknowledgement to send for it, if one
+    /// is due.
+    pub fn received(&mut self) -> Option<Frame> {
+        self.received_count += 1;
+        if self.received_count - self.acknowledged_count >= ACK_INTERVAL {
+            Some(self.ack())
+        } else {
+            None
+        }
+    }
+
+    /// The acknowledgement of all the messages received so far.
+    pub fn ack(&mut self) -> Frame {
+        self.acknowledged_count = self.received_count;
+        Frame::Ack(self.received_count)
+    }
+
+    /// Forgets the messages that the peer acknowledged receiving, given the number of
+    /// messages it received. Fails when the peer received messages that were never sent,
+    /// or missed some that were already forgotten, which happens when either side was
+    /// restarted.
+    pub fn acknowledged(&mut self, received_count: u64) -> Result<(), NotResumableError> {
+        let forgotten_count = self.sent_count - self.unacknowledged.len() as u64;
+        if received_count > self.sent_count || received_count < forgotten_count {
+            return Err(NotResumableError {
+                received_count,
+                sent_count: self.sent_count,
+                forgotten_count,
+            });
+        }
+        self.unacknowledged
+            .drain(..(received_count - forgotten_count) as usize);
+        Ok(())
+    }
+
+    /// The number of messages received so far.
+    pub fn received_count(&self) -> u64 {
+        self.received_count
+    }
+
+    /// The messages that were sent without being acknowledged, oldest first.
+    pub fn unacknowledged(&self) -> impl Iterator<Item = &Envelope> {
+        self.unacknowledged.iter()
+    }
+}
+
+/// The peer of a resumable session acknowledged messages that don't match the ones
+/// that were sent, because either side was restarted.
+#[derive(Debug)]
+pub struct NotResumableError {
+    pub received_count: u64,
+    pub sent_count: u64,
+    pub forgotten_count: u64,
+}
+
+impl std::fmt::Display for NotResumableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the session can't be resumed: the peer received {} messages, but {} were \
+            sent and {} acknowledged",
+            self.received_count, self.sent_count, self.forgotten_count
+        )
+    }
+}
+
+impl std::error::Error for NotResumableError {}
+
+/// How a connection of a resumable session ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResumableStreamEnd {
+    /// This side ended the session, by dropping the sender of its outgoing messages.
+    Closed,
+    /// The peer ended the session.
+    ClosedByPeer,
+}
+
+/// Resumes a session over a new connection, sending again the messages that the peer
+/// didn't receive over the previous ones, and then exchanges messages until either side
+/// ends the session. Returns an error when the connection breaks, or when no frame is
+/// received for `read_timeout`, after which the session can be resumed over another
+/// connection.
+pub async fn run_resumable_stream<R, W>(
+    mut reader: R,
+    mut writer: W,
+    state: &Mutex<ResumableState>,
+    outgoing_rx: &mut mpsc::UnboundedReceiver<Envelope>,
+    incoming_tx: &mpsc::UnboundedSender<Envelope>,
+    read_timeout: Duration,
+) -> Result<ResumableStreamEnd>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buffer = Vec::new();
+    let ack = state.lock().ack();
+    write_frame(&mut writer, &mut buffer, &ack).await?;
+    let frame = select_biased! {
+        frame = read_frame(&mut reader, &mut buffer).fuse() => frame?,
+        _ = smol::Timer::after(read_timeout).fuse() => {
+            return Err(anyhow!("no acknowledgement received for {read_timeout:?}"));
+        }
+    };
+    match frame {
+        Frame::Ack(received_count) => state.lock().acknowledged(received_count)?,
+        Frame::Goodbye => return Ok(ResumableStreamEnd::ClosedByPeer),
+        Frame::Message(_) => return Err(anyhow!("expected an acknowledgement")),
+    }
+    let unacknowledged = state.lock().unacknowledged().cloned().collect::<Vec<_>>();
+
+    // Reading and writing happen concurrently, so that neither side blocks on writing
+    // while the other one is sending its unacknowledged messages.
+    let (ack_tx, mut ack_rx) = mpsc::unbounded();
+    let read = read_frames(&mut reader, state, incoming_tx, &ack_tx, read_timeout);
+    let write = write_frames(
+        &mut writer,
+        state,
+        unacknowledged,
+        outgoing_rx,
+        &mut ack_rx,
+        read_timeout / 4,
+    );
+    select_biased! {
+        result = read.fuse() => result,
+        result = write.fuse() => result,
+    }
+}
+
+async fn read_frames<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    state: &Mutex<ResumableState>,
+    incoming_tx: &mpsc::UnboundedSender<Envelope>,
+    ack_tx: &mpsc::UnboundedSender<Frame>,
+    read_timeout: Duration,
+) -> Result<ResumableStreamEnd> {
+    let mut buffer = Vec::new();
+    loop {
+        let frame = select_biased! {
+            frame = read_frame(reader, &mut buffer).fuse() => frame?,
+            _ = smol::Timer::after(read_timeout).fuse() => {
+                return Err(anyhow!("no message received for {read_timeout:?}"));
+            }
+        };
+        match frame {
+            Frame::Message(message) => {
+                if let Some(ack) = state.lock().received() {
+                    ack_tx.unbounded_send(ack).ok();
+                }
+                incoming_tx.unbounded_send(message).ok();
+            }
+            Frame::Ack(received_count) => state.lock().acknowledged(received_count)?,
+            Frame::Goodbye => return Ok(ResumableStreamEnd::ClosedByPeer),
+        }
+    }
+}
+
+async fn write_frames<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    state: &Mutex<ResumableState>,
+    unacknowledged: Vec<Envelope>,
+    outgoing_rx: &mut mpsc::UnboundedReceiver<Envelope>,
+    ack_rx: &mut mpsc::UnboundedReceiver<Frame>,
+    keepalive_interval: Duration,
+) -> Result<ResumableStreamEnd> {
+    let mut buffer = Vec::new();
+    for message in unacknowledged {
+        write_frame(writer, &mut buffer, &Frame::Message(message)).await?;
+    }
+    loop {
+        select_biased! {
+            ack = ack_rx.next().fuse() => {
+                if let Some(ack) = ack {
+                    write_frame(writer, &mut buffer, &ack).await?;
+                }
+            }
+            message = outgoing_rx.next().fuse() => {
+                let Some(message) = message else {
+                    write_frame(writer, &mut buffer, &Frame::Goodbye).await?;
+                    return Ok(ResumableStreamEnd::Closed);
+                };
+                // The message is recorded before being written, so that it's sent again
+                // if the connection breaks while writing it.
+                state.lock().sent(message.clone());
+                write_frame(writer, &mut buffer, &Frame::Message(message)).await?;
+            }
+            // Idle sessions send acknowledgements, so that the peer can tell them apart
+            // from broken connections.
+            _ = smol::Timer::after(keepalive_interval).fuse() => {
+                let ack = state.lock().ack();
+                write_frame(writer, &mut buffer, &ack).await?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: u32) -> Envelope {
+        Envelope {
+            id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_acknowledged_messages_are_forgotten() {
+        let mut state = ResumableState::default();
+        for id in 1..=3 {
+            state.sent(message(id));
+        }
+
+        state.acknowledged(2).unwrap();
+        let ids = state.unacknowledged().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids, [3]);
+
+        // Acknowledging fewer messages than before is fine as long as none of the
+        // missing ones were forgotten.
+        state.acknowledged(2).unwrap();
+        assert!(state.acknowledged(1).is_err());
+        assert!(state.acknowledged(4).is_err());
+
+        state.acknowledged(3).unwrap();
+        assert_eq!(state.unacknowledged().count(), 0);
+    }
+
+    #[test]
+    fn test_received_messages_are_acknowledged_periodically() {
+        let mut state = ResumableState::default();
+        for _ in 1..ACK_INTERVAL {
+            assert_eq!(state.received(), None);
+        }
+        assert_eq!(state.received(), Some(Frame::Ack(ACK_INTERVAL)));
+        assert_eq!(state.received(), None);
+        assert_eq!(state.ack(), Frame::Ack(ACK_INTERVAL + 1));
+        assert_eq!(state.received_count(), ACK_INTERVAL + 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resuming_sends_again_the_messages_that_were_lost() {
+        use smol::net::unix::UnixStream;
+
+        smol::block_on(async {
+            // The previous connection broke after the client sent two messages, only
+            // the first of which reached the server.
+            let client_state = Mutex::new(ResumableState::default());
+            client_state.lock().sent(message(1));
+            client_state.lock().sent(message(2));
+            let server_state = Mutex::new(ResumableState::default());
+            server_state.lock().received();
+
+            let (client_outgoing_tx, mut client_outgoing_rx) = mpsc::unbounded();
+            let (client_incoming_tx, _client_incoming_rx) = mpsc::unbounded();
+            let (_server_outgoing_tx, mut server_outgoing_rx) = mpsc::unbounded();
+            let (server_incoming_tx, server_incoming_rx) = mpsc::unbounded();
+            client_outgoing_tx.unbounded_send(message(3)).unwrap();
+            drop(client_outgoing_tx);
+
+            let (client_stream, server_stream) = UnixStream::pair().unwrap();
+            let (client_end, server_end) = futures::join!(
+                run_resumable_stream(
+                    client_stream.clone(),
+                    client_stream,
+                    &client_state,
+                    &mut client_outgoing_rx,
+                    &client_incoming_tx,
+                    RESUMABLE_READ_TIMEOUT,
+                ),
+                run_resumable_stream(
+                    server_stream.clone(),
+                    server_stream,
+                    &server_state,
+                    &mut server_outgoing_rx,
+                    &server_incoming_tx,
+                    RESUMABLE_READ_TIMEOUT,
+                ),
+            );
+            assert_eq!(client_end.unwrap(), ResumableStreamEnd::Closed);
+            assert_eq!(server_end.unwrap(), ResumableStreamEnd::ClosedByPeer);
+
+            drop(server_incoming_tx);
+            let ids = server_incoming_rx
+                .map(|message| message.id)
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(ids, [2, 3]);
+            assert_eq!(server_state.lock().received_count(), 3);
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resuming_fails_when_the_peer_was_restarted() {
+        use smol::net::unix::UnixStream;
+
+        smol::block_on(async {
+            // The server restarted and lost the messages it had received.
+            let client_state = Mutex::new(ResumableState::default());
+            client_state.lock().sent(message(1));
+            client_state.lock().acknowledged(1).unwrap();
+
+            let (_client_outgoing_tx, mut client_outgoing_rx) = mpsc::unbounded();
+            let (client_incoming_tx, _client_incoming_rx) = mpsc::unbounded();
+            let (client_stream, mut server_stream) = UnixStream::pair().unwrap();
+            let mut server_buffer = Vec::new();
+            write_frame(&mut server_stream, &mut server_buffer, &Frame::Ack(0))
+                .await
+                .unwrap();
+
+            let error = run_resumable_stream(
+                client_stream.clone(),
+                client_stream,
+                &client_state,
+                &mut client_outgoing_rx,
+                &client_incoming_tx,
+                RESUMABLE_READ_TIMEOUT,
+            )
+            .await
+            .unwrap_err();
+            assert!(error.is::<NotResumableError>());
+        });
+    }
+}
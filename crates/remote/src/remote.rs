@@ -1,5 +1,14 @@
+pub mod docker_connection;
 pub mod json_log;
 pub mod protocol;
+pub mod resilient_connection;
 pub mod ssh_session;
+pub mod wsl_connection;
 
-pub use ssh_session::{SshClientDelegate, SshPlatform, SshSession};
+pub use docker_connection::{DockerConnection, DockerConnectionOptions, DockerContainer};
+pub use ssh_session::{
+    format_byte_count, RemoteConnection, ServerLog, SshAuthPrompt, SshAuthenticationError,
+    SshClientDelegate, SshConnectTimeoutError, SshConnectionOptions, SshConnectionState,
+    SshIdentity, SshPlatform, SshPromptKind, SshProxy, SshSession, SshTimeouts, SshTransport,
+};
+pub use wsl_connection::{WslConnection, WslConnectionOptions};
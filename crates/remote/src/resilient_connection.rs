@@ -0,0 +1,74 @@
+use crate::ssh_session::{
+    RemoteConnection, SshClientDelegate, SshClientState, SshConnectionOptions,
+};
+use anyhow::Result;
+use futures::{future::LocalBoxFuture, FutureExt as _};
+use gpui::AsyncAppContext;
+use parking_lot::Mutex;
+use smol::process;
+use std::{mem, sync::Arc};
+
+/// An ssh connection that is replaced by a new one when it's lost, for sessions whose
+/// remote server keeps running in the meantime. Unlike the connections of other
+/// sessions, it isn't shared with the sessions to the same host, so that it can be
+/// re-established without affecting them.
+pub struct ResilientConnection {
+    options: SshConnectionOptions,
+    delegate: Arc<dyn SshClientDelegate>,
+    state: Mutex<Arc<SshClientState>>,
+}
+
+impl ResilientConnection {
+    pub(crate) async fn new(
+        options: SshConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Self> {
+        let state = SshClientState::new(&options, delegate.clone(), cx).await?;
+        Ok(Self {
+            options,
+            delegate,
+            state: Mutex::new(Arc::new(state)),
+        })
+    }
+
+    fn state(&self) -> Arc<SshClientState> {
+        self.state.lock().clone()
+    }
+}
+
+impl RemoteConnection for ResilientConnection {
+    fn command(&self, program: &str) -> process::Command {
+        self.state().command(program)
+    }
+
+    fn shell_command_prefix(&self) -> Vec<String> {
+        self.state().shell_command_prefix()
+    }
+
+    fn ssh_args(&self) -> Option<Vec<String>> {
+        self.state().ssh_args()
+    }
+
+    fn reconnect(
+        self: Arc<Self>,
+        mut cx: AsyncAppContext,
+    ) -> Option<LocalBoxFuture<'static, Result<()>>> {
+        Some(
+            async move {
+                log::info!(
+                    "re-establishing ssh connection to {}",
+                    self.options.connection_string()
+                );
+                let state =
+                    SshClientState::new(&self.options, self.delegate.clone(), &mut cx).await?;
+                // Dropping the previous connection closes its master process, which may
+                // not have noticed that the connection was lost yet.
+                let previous_state = mem::replace(&mut *self.state.lock(), Arc::new(state));
+                drop(previous_state);
+                Ok(())
+            }
+            .boxed_local(),
+        )
+    }
+}
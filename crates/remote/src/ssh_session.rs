@@ -1,18 +1,29 @@
 use crate::{
+    docker_connection::{DockerConnection, DockerConnectionOptions},
     json_log::LogRecord,
     protocol::{
-        message_len_from_buffer, read_message_with_len, write_message, MessageId, MESSAGE_LEN_SIZE,
+        message_len_from_buffer, read_message_with_len, run_resumable_stream, write_message,
+        MessageId, NotResumableError, ResumableState, MESSAGE_LEN_SIZE, PROTOCOL_VERSION,
+        RESUMABLE_READ_TIMEOUT, RESUMABLE_SESSION_TIMEOUT,
     },
+    resilient_connection::ResilientConnection,
+    wsl_connection::{WslConnection, WslConnectionOptions},
 };
 use anyhow::{anyhow, Context as _, Result};
+use async_compression::{
+    futures::{bufread::GzipDecoder, write::GzipEncoder},
+    Level,
+};
 use collections::HashMap;
 use futures::{
     channel::{mpsc, oneshot},
-    future::{BoxFuture, LocalBoxFuture},
-    select_biased, AsyncReadExt as _, AsyncWriteExt as _, Future, FutureExt as _, StreamExt as _,
+    future::{BoxFuture, LocalBoxFuture, Shared},
+    io, select_biased, AsyncReadExt as _, AsyncSeekExt as _, AsyncWriteExt as _, Future,
+    FutureExt as _, StreamExt as _,
 };
-use gpui::{AppContext, AsyncAppContext, Model, SemanticVersion, WeakModel};
+use gpui::{AppContext, AsyncAppContext, Global, Model, SemanticVersion, Task, WeakModel};
 use parking_lot::Mutex;
+use postage::watch;
 use rpc::{
     proto::{
         self, build_typed_envelope, AnyTypedEnvelope, Envelope, EnvelopedMessage, PeerId,
@@ -20,19 +31,22 @@ use rpc::{
     },
     TypedEnvelope,
 };
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
 use smol::{
     fs,
     process::{self, Stdio},
 };
 use std::{
     any::TypeId,
+    collections::VecDeque,
     ffi::OsStr,
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicU32, Ordering::SeqCst},
-        Arc,
+        atomic::{AtomicBool, AtomicU32, Ordering::SeqCst},
+        Arc, Weak,
     },
-    time::Instant,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tempfile::TempDir;
 
@@ -41,6 +55,14 @@ pub struct SshSession {
     response_channels: ResponseChannels,
     outgoing_tx: mpsc::UnboundedSender<Envelope>,
     spawn_process_tx: mpsc::UnboundedSender<SpawnRequest>,
+    connection_options: Option<SshConnectionOptions>,
+    connection: Option<Arc<dyn RemoteConnection>>,
+    state_rx: watch::Receiver<SshConnectionState>,
+    server_log: Arc<Mutex<ServerLog>>,
+    latency_tx: Mutex<watch::Sender<Option<Duration>>>,
+    latency_rx: watch::Receiver<Option<Duration>>,
+    server_version: Mutex<Option<SemanticVersion>>,
+    disconnect_requested: AtomicBool,
     message_handlers: Mutex<
         HashMap<
             TypeId,
@@ -57,12 +79,362 @@ pub struct SshSession {
     >,
 }
 
-struct SshClientState {
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SshConnectionOptions {
+    pub host: String,
+    pub username: String,
+    pub port: u16,
+    pub password: Option<String>,
+    pub proxy: Option<SshProxy>,
+    /// The identity to authenticate with. When `None`, `ssh` picks one on its own
+    /// according to the user's ssh_config.
+    pub identity: Option<SshIdentity>,
+    pub timeouts: SshTimeouts,
+    /// Extra arguments for the `ssh` process that establishes the connection, such as
+    /// `["-o", "Ciphers=aes128-ctr"]`.
+    pub additional_args: Vec<String>,
+    /// Whether to forward the local ssh agent, which lets e.g. git on the remote host
+    /// authenticate with the local keys. Anyone with root access on the host can use
+    /// them while connected.
+    pub forward_agent: bool,
+    pub transport: SshTransport,
+}
+
+impl SshConnectionOptions {
+    /// A `user@host` string identifying the connection, including the port when it isn't 22.
+    pub fn connection_string(&self) -> String {
+        if self.port == 22 {
+            format!("{}@{}", self.username, self.host)
+        } else {
+            format!("{}@{}:{}", self.username, self.host, self.port)
+        }
+    }
+
+    /// The options of the `ssh` process that establishes the connection. Files that they
+    /// refer to, such as the public key of an agent identity, are written to `dir`.
+    async fn master_args(&self, dir: &Path) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        if let Some(proxy) = &self.proxy {
+            args.extend([
+                "-o".to_string(),
+                format!("ProxyCommand={}", proxy.proxy_command()),
+            ]);
+        }
+        if let Some(identity) = &self.identity {
+            args.extend(identity.ssh_args(dir).await?);
+        }
+        args.extend(self.timeouts.ssh_args());
+        args.extend(self.additional_args.iter().cloned());
+        if self.forward_agent {
+            args.extend(["-o".to_string(), "ForwardAgent=yes".to_string()]);
+        }
+        Ok(args)
+    }
+}
+
+/// How the messages of a session are carried to the remote server over ssh.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SshTransport {
+    /// Through the stdio of the remote server, over a single ssh connection. The
+    /// session ends when the connection is lost.
+    #[default]
+    Direct,
+    /// Through a proxy to a remote server that keeps running when the connection is
+    /// lost. The connection is then re-established in the background, and the messages
+    /// that didn't make it are sent again, so the session survives network changes. The
+    /// ssh connection isn't shared with other sessions to the same host.
+    Resilient,
+}
+
+/// A proxy through which the SSH transport is tunneled. When no proxy is configured,
+/// `ssh` still honors any `ProxyCommand` or `ProxyJump` from the user's ssh_config.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SshProxy {
+    Socks5 { host: String, port: u16 },
+    Http { host: String, port: u16 },
+}
+
+impl SshProxy {
+    /// Parses a proxy URL of the form `socks5://host:port` or `http://host:port`. IPv6
+    /// hosts are written in brackets, e.g. `socks5://[::1]:1080`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let (scheme, address) = url
+            .split_once("://")
+            .ok_or_else(|| anyhow!("invalid proxy url {url:?}"))?;
+        let address = address.trim_end_matches('/');
+        let (host, port) = match address.strip_prefix('[') {
+            Some(address) => {
+                let (host, rest) = address
+                    .split_once(']')
+                    .ok_or_else(|| anyhow!("invalid ipv6 host in proxy url {url:?}"))?;
+                let port = match rest {
+                    "" => None,
+                    rest => Some(
+                        rest.strip_prefix(':')
+                            .ok_or_else(|| anyhow!("invalid proxy url {url:?}"))?,
+                    ),
+                };
+                (host, port)
+            }
+            None => match address.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (address, None),
+            },
+        };
+        let port = port
+            .map(|port| port.parse::<u16>().context("invalid proxy port"))
+            .transpose()?;
+        if host.is_empty() {
+            return Err(anyhow!("missing host in proxy url {url:?}"));
+        }
+        let host = host.to_string();
+        match scheme {
+            "socks5" | "socks5h" => Ok(Self::Socks5 {
+                host,
+                port: port.unwrap_or(1080),
+            }),
+            "http" => Ok(Self::Http {
+                host,
+                port: port.unwrap_or(8080),
+            }),
+            _ => Err(anyhow!("unsupported proxy scheme {scheme:?}")),
+        }
+    }
+
+    /// The `ProxyCommand` that tunnels the connection through this proxy.
+    fn proxy_command(&self) -> String {
+        match self {
+            Self::Socks5 { host, port } => format!("nc -X 5 -x {host}:{port} %h %p"),
+            Self::Http { host, port } => format!("nc -X connect -x {host}:{port} %h %p"),
+        }
+    }
+}
+
+/// Timeouts for the ssh transport, in seconds. Options that are zero are left to
+/// ssh's own defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SshTimeouts {
+    /// How long to wait for the TCP connection to the host to be established.
+    pub connect_timeout: u64,
+    /// How often to check that the host is still responsive once connected.
+    pub server_alive_interval: u64,
+    /// How many unanswered checks to tolerate before the connection is dropped.
+    pub server_alive_count_max: u64,
+}
+
+impl SshTimeouts {
+    fn ssh_args(&self) -> Vec<String> {
+        [
+            ("ConnectTimeout", self.connect_timeout),
+            ("ServerAliveInterval", self.server_alive_interval),
+            ("ServerAliveCountMax", self.server_alive_count_max),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value > 0)
+        .flat_map(|(option, value)| ["-o".to_string(), format!("{option}={value}")])
+        .collect()
+    }
+}
+
+/// The host could not be reached before the connect timeout elapsed.
+#[derive(Clone, Debug)]
+pub struct SshConnectTimeoutError {
+    pub host: String,
+    pub timeout: u64,
+}
+
+impl std::fmt::Display for SshConnectTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.timeout > 0 {
+            write!(
+                f,
+                "timed out connecting to {} after {} seconds",
+                self.host, self.timeout
+            )
+        } else {
+            write!(f, "timed out connecting to {}", self.host)
+        }
+    }
+}
+
+impl std::error::Error for SshConnectTimeoutError {}
+
+/// The host rejected the credentials that were offered.
+#[derive(Clone, Debug)]
+pub struct SshAuthenticationError {
+    pub host: String,
+    /// What `ssh` reported on its stderr.
+    pub output: String,
+}
+
+impl std::fmt::Display for SshAuthenticationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to authenticate to {}: {}",
+            self.host, self.output
+        )
+    }
+}
+
+impl std::error::Error for SshAuthenticationError {}
+
+/// An identity that the user explicitly chose to authenticate with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SshIdentity {
+    /// A key held by the ssh agent.
+    Agent { public_key: String, comment: String },
+    /// A private key file, such as the ones in `~/.ssh`.
+    KeyFile(PathBuf),
+    /// Skip public key authentication and prompt for a password instead.
+    Password,
+}
+
+impl SshIdentity {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Agent {
+                public_key,
+                comment,
+            } => {
+                let key_type = public_key.split_whitespace().next().unwrap_or_default();
+                if comment.is_empty() {
+                    format!("Agent: {key_type} key")
+                } else {
+                    format!("Agent: {comment} ({key_type})")
+                }
+            }
+            Self::KeyFile(path) => format!("Key: {}", path.display()),
+            Self::Password => "Password".to_string(),
+        }
+    }
+
+    /// Lists the keys held by the ssh agent, followed by the private keys in `~/.ssh`
+    /// that aren't already loaded into the agent, followed by password authentication.
+    pub async fn list_available() -> Vec<Self> {
+        let mut identities = Vec::new();
+        let mut agent_public_keys = Vec::new();
+
+        if let Ok(output) = process::Command::new("ssh-add").arg("-L").output().await {
+            if output.status.success() {
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    let mut parts = line.splitn(3, ' ');
+                    let (Some(key_type), Some(key)) = (parts.next(), parts.next()) else {
+                        continue;
+                    };
+                    let public_key = format!("{key_type} {key}");
+                    agent_public_keys.push(public_key.clone());
+                    identities.push(Self::Agent {
+                        public_key,
+                        comment: parts.next().unwrap_or_default().trim().to_string(),
+                    });
+                }
+            }
+        }
+
+        let ssh_dir = util::paths::home_dir().join(".ssh");
+        if let Ok(mut entries) = fs::read_dir(&ssh_dir).await {
+            let mut key_files = Vec::new();
+            while let Some(Ok(entry)) = entries.next().await {
+                let public_key_path = entry.path();
+                if public_key_path.extension() != Some(OsStr::new("pub")) {
+                    continue;
+                }
+                let private_key_path = public_key_path.with_extension("");
+                if !private_key_path.is_file() {
+                    continue;
+                }
+                let in_agent = fs::read_to_string(&public_key_path)
+                    .await
+                    .map(|contents| {
+                        agent_public_keys
+                            .iter()
+                            .any(|public_key| contents.starts_with(public_key.as_str()))
+                    })
+                    .unwrap_or(false);
+                if !in_agent {
+                    key_files.push(private_key_path);
+                }
+            }
+            key_files.sort();
+            identities.extend(key_files.into_iter().map(Self::KeyFile));
+        }
+
+        identities.push(Self::Password);
+        identities
+    }
+
+    /// Arguments that restrict `ssh` to this identity. Agent keys are selected by
+    /// writing their public key to `dir`.
+    async fn ssh_args(&self, dir: &Path) -> Result<Vec<String>> {
+        let identity_file = match self {
+            Self::Agent { public_key, .. } => {
+                let path = dir.join("identity.pub");
+                fs::write(&path, format!("{public_key}\n")).await?;
+                path
+            }
+            Self::KeyFile(path) => path.clone(),
+            Self::Password => {
+                return Ok(vec![
+                    "-o".to_string(),
+                    "PubkeyAuthentication=no".to_string(),
+                    "-o".to_string(),
+                    "PreferredAuthentications=keyboard-interactive,password".to_string(),
+                ]);
+            }
+        };
+        Ok(vec![
+            "-o".to_string(),
+            "IdentitiesOnly=yes".to_string(),
+            "-i".to_string(),
+            identity_file.to_string_lossy().to_string(),
+        ])
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SshConnectionState {
+    Connected,
+    Disconnected,
+}
+
+pub(crate) struct SshClientState {
     socket_path: PathBuf,
     port: u16,
     url: String,
-    _master_process: process::Child,
-    _temp_dir: TempDir,
+    forward_agent: bool,
+    /// The master process and the directory of its control socket, which are only taken
+    /// when the connection is closed.
+    master_process: Mutex<Option<process::Child>>,
+    temp_dir: Option<TempDir>,
+}
+
+type PendingConnection = Shared<Task<Result<Arc<SshClientState>, Arc<anyhow::Error>>>>;
+
+/// The ssh connections that are currently open, keyed by the options they were
+/// established with. Sessions to the same host share a single connection, so that
+/// opening a second workspace there doesn't prompt for credentials again. Each
+/// session holds a reference to its connection, which is closed once the last
+/// session using it has ended.
+#[derive(Default)]
+struct SshConnectionRegistry {
+    connections: HashMap<SshConnectionOptions, Weak<SshClientState>>,
+    pending: HashMap<SshConnectionOptions, PendingConnection>,
+    /// The connection logs of every host connected to so far, keyed by connection
+    /// string, so that failed attempts can be inspected after the fact.
+    logs: HashMap<String, Arc<Mutex<ServerLog>>>,
+}
+
+impl Global for SshConnectionRegistry {}
+
+impl SshConnectionRegistry {
+    fn connection(&mut self, key: &SshConnectionOptions) -> Option<Arc<SshClientState>> {
+        self.connections.retain(|_, state| state.strong_count() > 0);
+        self.connections
+            .get(key)
+            .and_then(Weak::upgrade)
+            .filter(|state| state.is_connected())
+    }
 }
 
 struct SpawnRequest {
@@ -76,58 +448,371 @@ pub struct SshPlatform {
     pub arch: &'static str,
 }
 
-pub trait SshClientDelegate {
+/// A single prompt issued by `ssh` while authenticating. Keyboard-interactive
+/// authentication may issue several of these in a row, e.g. a password followed
+/// by a one-time code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SshAuthPrompt {
+    /// The prompt text as printed by `ssh`, e.g. "Password:" or "Verification code:".
+    /// For yes/no questions, the list of accepted answers is left out.
+    pub label: String,
+    pub kind: SshPromptKind,
+    /// The 1-based position of this prompt within the current connection attempt.
+    pub step: usize,
+}
+
+/// How an [`SshAuthPrompt`] is answered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SshPromptKind {
+    /// A response that is typed without being shown, such as a password.
+    Secret,
+    /// A response that is shown as it's typed, such as a username or a one-time code.
+    Plain,
+    /// A question such as whether to trust the host's key, answered with
+    /// [`SshAuthPrompt::YES`] or [`SshAuthPrompt::NO`].
+    YesNo,
+}
+
+impl SshAuthPrompt {
+    pub const YES: &'static str = "yes";
+    pub const NO: &'static str = "no";
+
+    fn new(label: String, prompt_kind: &str, step: usize) -> Self {
+        // `ssh` sets `SSH_ASKPASS_PROMPT=confirm` for yes/no questions such as
+        // confirming the use of an agent key, and lists the accepted answers in
+        // the text of others, e.g. "(yes/no/[fingerprint])?" when verifying a host
+        // key.
+        if let Some((question, _)) = label.rsplit_once(" (yes/no") {
+            let label = format!("{}?", question.trim_end());
+            return Self {
+                label,
+                kind: SshPromptKind::YesNo,
+                step,
+            };
+        }
+        let kind = if prompt_kind == "confirm" {
+            SshPromptKind::YesNo
+        } else if is_plain_prompt(&label) {
+            SshPromptKind::Plain
+        } else {
+            SshPromptKind::Secret
+        };
+        Self { label, kind, step }
+    }
+}
+
+/// Whether the response to a prompt can be shown as it's typed.
+///
+/// `ssh` doesn't tell askpass programs whether the server asked for the response to a
+/// keyboard-interactive prompt to be echoed, so this is inferred from the label. Prompts
+/// that mention a password or PIN, as well as unknown prompts, are kept secret.
+fn is_plain_prompt(label: &str) -> bool {
+    const SECRET_WORDS: &[&str] = &["password", "passphrase", "passcode", "pin", "secret"];
+    const PLAIN_WORDS: &[&str] = &["username", "login", "code", "otp"];
+
+    let label = label.to_lowercase();
+    let words = label
+        .split(|char: char| !char.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    !words.iter().any(|word| SECRET_WORDS.contains(word))
+        && words.iter().any(|word| PLAIN_WORDS.contains(word))
+}
+
+pub trait SshClientDelegate: Send + Sync {
     fn ask_password(
         &self,
-        prompt: String,
+        prompt: SshAuthPrompt,
         cx: &mut AsyncAppContext,
     ) -> oneshot::Receiver<Result<String>>;
-    fn remote_server_binary_path(&self, cx: &mut AsyncAppContext) -> Result<PathBuf>;
+    /// The path of a version of the remote server on the remote host, relative to the home
+    /// directory. It must end with the version, so that the other versions installed next
+    /// to it can be found and removed.
+    fn remote_server_binary_path(
+        &self,
+        version: SemanticVersion,
+        cx: &mut AsyncAppContext,
+    ) -> Result<PathBuf>;
     fn get_server_binary(
         &self,
         platform: SshPlatform,
         cx: &mut AsyncAppContext,
     ) -> oneshot::Receiver<Result<(PathBuf, SemanticVersion)>>;
     fn set_status(&self, status: Option<&str>, cx: &mut AsyncAppContext);
+    fn set_progress(&self, progress: Option<f32>, cx: &mut AsyncAppContext);
+    /// Shows that `ssh` is waiting for the user to touch their security key, with the
+    /// message printed by `ssh`, or that it's no longer waiting when `None`.
+    fn set_security_key_prompt(&self, message: Option<String>, cx: &mut AsyncAppContext);
+    /// Whether to upload the remote server gzipped, which is faster on slow links
+    /// but needs `gunzip` on the remote host.
+    fn compress_server_upload(&self, cx: &mut AsyncAppContext) -> bool;
 }
 
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+const UPLOAD_RATE_INTERVAL: Duration = Duration::from_millis(500);
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// How many versions of the remote server to keep installed on each host, including the
+/// one in use, so that switching between a few versions of Zed doesn't reinstall it.
+const REMOTE_SERVER_VERSIONS_TO_KEEP: usize = 3;
+
 type ResponseChannels = Mutex<HashMap<MessageId, oneshot::Sender<(Envelope, oneshot::Sender<()>)>>>;
 
+const MAX_SERVER_LOG_LINES: usize = 2000;
+
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+const PING_TIMEOUT: Duration = Duration::from_secs(30);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait before re-establishing the lost connection of a resilient session,
+/// which doubles after each failed attempt up to [`MAX_RECONNECT_DELAY`].
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// How long an attempt at re-establishing a connection may take, e.g. when it's stuck
+/// on a prompt that nobody answers.
+const RECONNECT_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long to spend checking that a host is reachable before leaving it to `ssh`,
+/// which reports its own timeout.
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The most recent lines of a log kept in memory, such as those written by the remote
+/// server to its stderr, or the events of the connections made to a host.
+pub struct ServerLog {
+    lines: VecDeque<String>,
+    line_count: usize,
+    line_count_tx: watch::Sender<usize>,
+    line_count_rx: watch::Receiver<usize>,
+}
+
+impl Default for ServerLog {
+    fn default() -> Self {
+        let (line_count_tx, line_count_rx) = watch::channel_with(0);
+        Self {
+            lines: VecDeque::with_capacity(MAX_SERVER_LOG_LINES),
+            line_count: 0,
+            line_count_tx,
+            line_count_rx,
+        }
+    }
+}
+
+impl ServerLog {
+    fn push(&mut self, line: String) {
+        while self.lines.len() >= MAX_SERVER_LOG_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.line_count += 1;
+        *self.line_count_tx.borrow_mut() = self.line_count;
+    }
+
+    /// The total number of lines logged so far, including those that were dropped.
+    pub fn line_count(&self) -> usize {
+        self.line_count
+    }
+
+    /// Returns the lines that are still kept, out of those logged after the first
+    /// `line_count` ones.
+    pub fn lines_since(&self, line_count: usize) -> impl Iterator<Item = &str> {
+        let new_lines = self.line_count.saturating_sub(line_count);
+        self.lines
+            .iter()
+            .skip(self.lines.len().saturating_sub(new_lines))
+            .map(String::as_str)
+    }
+
+    /// Yields the number of lines logged so far whenever lines are added.
+    pub fn line_count_changes(&self) -> watch::Receiver<usize> {
+        self.line_count_rx.clone()
+    }
+}
+
+/// A transport to an environment that the remote server can be installed into and
+/// run in, such as a host reached over ssh or a container.
+pub trait RemoteConnection: Send + Sync {
+    /// Returns a command that runs `program`, a shell command line, in the remote
+    /// environment from the user's home directory. Arguments added to the command
+    /// are passed on to `program`.
+    fn command(&self, program: &str) -> process::Command;
+
+    /// The program and arguments that run a shell command line, given as one more
+    /// argument, in the remote environment.
+    fn shell_command_prefix(&self) -> Vec<String>;
+
+    /// Arguments for an `ssh` invocation that reuses this connection, if it is one.
+    fn ssh_args(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Re-establishes the connection after it was lost. Connections that return `None`
+    /// can't be re-established, and their session ends with them.
+    fn reconnect(
+        self: Arc<Self>,
+        _cx: AsyncAppContext,
+    ) -> Option<LocalBoxFuture<'static, Result<()>>> {
+        None
+    }
+}
+
+/// The connection of fake client sessions, whose remote server runs on this machine.
+#[cfg(any(test, feature = "test-support"))]
+struct FakeRemoteConnection;
+
+#[cfg(any(test, feature = "test-support"))]
+impl RemoteConnection for FakeRemoteConnection {
+    fn command(&self, program: &str) -> process::Command {
+        let mut command = process::Command::new("sh");
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["-c", &format!("cd && {program} \"$@\""), "sh"]);
+        command
+    }
+
+    fn shell_command_prefix(&self) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string()]
+    }
+}
+
 impl SshSession {
     pub async fn client(
-        user: String,
-        host: String,
-        port: u16,
+        connection_options: SshConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
+        let connection: Arc<dyn RemoteConnection> = match connection_options.transport {
+            SshTransport::Direct => {
+                SshClientState::shared(&connection_options, delegate.clone(), cx).await?
+            }
+            SshTransport::Resilient => Arc::new(
+                ResilientConnection::new(connection_options.clone(), delegate.clone(), cx).await?,
+            ),
+        };
+        let connection_options = SshConnectionOptions {
+            password: None,
+            ..connection_options
+        };
+        Self::start(connection, Some(connection_options), delegate, cx).await
+    }
+
+    /// Attaches to a running container, installing the remote server into it.
+    pub async fn docker(
+        options: DockerConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
+        delegate.set_status(Some("connecting"), cx);
+        let connection = DockerConnection::new(options).await?;
+        Self::start(Arc::new(connection), None, delegate, cx).await
+    }
+
+    /// Opens a session in a WSL distribution, installing the remote server into it.
+    pub async fn wsl(
+        options: WslConnectionOptions,
         delegate: Arc<dyn SshClientDelegate>,
         cx: &mut AsyncAppContext,
     ) -> Result<Arc<Self>> {
-        let client_state = SshClientState::new(user, host, port, delegate.clone(), cx).await?;
+        delegate.set_status(Some("connecting"), cx);
+        let connection = WslConnection::new(options).await?;
+        Self::start(Arc::new(connection), None, delegate, cx).await
+    }
 
-        let platform = client_state.query_platform().await?;
+    /// Installs the remote server over the given connection and starts a session
+    /// with it.
+    async fn start(
+        connection: Arc<dyn RemoteConnection>,
+        connection_options: Option<SshConnectionOptions>,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
+        let platform = query_platform(connection.as_ref()).await?;
         let (local_binary_path, version) = delegate.get_server_binary(platform, cx).await??;
-        let remote_binary_path = delegate.remote_server_binary_path(cx)?;
-        client_state
-            .ensure_server_binary(
+        let remote_binary_path = delegate.remote_server_binary_path(version, cx)?;
+        ensure_server_binary(
+            connection.as_ref(),
+            &delegate,
+            &local_binary_path,
+            &remote_binary_path,
+            version,
+            false,
+            cx,
+        )
+        .await?;
+
+        let resilient = connection_options.as_ref().map_or(false, |options| {
+            options.transport == SshTransport::Resilient
+        });
+        let mut this = if resilient {
+            Self::launch_resilient(
+                connection.clone(),
+                connection_options.clone(),
+                &remote_binary_path,
+                cx,
+            )
+            .await?
+        } else {
+            Self::launch(
+                connection.clone(),
+                connection_options.clone(),
+                &remote_binary_path,
+                cx,
+            )
+            .await?
+        };
+        if let Err(error) = this.handshake(version, cx).await {
+            // The installed server can be stale even though its stamp matches, e.g. when
+            // it was replaced by another version of Zed connecting to the same host, so it
+            // is reinstalled once rather than failing to connect.
+            log::warn!("reinstalling the remote server: {error:#}");
+            if let Some(connection_options) = &connection_options {
+                cx.update(|cx| Self::connection_log(connection_options, cx))?
+                    .lock()
+                    .push(format!("reinstalling the remote server: {error:#}"));
+            }
+            this.disconnect();
+            ensure_server_binary(
+                connection.as_ref(),
                 &delegate,
                 &local_binary_path,
                 &remote_binary_path,
                 version,
+                true,
                 cx,
             )
             .await?;
+            this = if resilient {
+                Self::launch_resilient(connection, connection_options, &remote_binary_path, cx)
+                    .await?
+            } else {
+                Self::launch(connection, connection_options, &remote_binary_path, cx).await?
+            };
+            this.handshake(version, cx).await?;
+        }
+
+        cx.spawn({
+            let this = Arc::downgrade(&this);
+            |cx| Self::measure_latency(this, cx)
+        })
+        .detach();
+        Ok(this)
+    }
 
+    /// Spawns the installed remote server and starts exchanging messages with it.
+    async fn launch(
+        connection: Arc<dyn RemoteConnection>,
+        connection_options: Option<SshConnectionOptions>,
+        remote_binary_path: &Path,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
         let (spawn_process_tx, mut spawn_process_rx) = mpsc::unbounded::<SpawnRequest>();
         let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded::<Envelope>();
         let (incoming_tx, incoming_rx) = mpsc::unbounded::<Envelope>();
+        let (mut state_tx, state_rx) = watch::channel_with(SshConnectionState::Connected);
 
-        run_cmd(client_state.ssh_command(&remote_binary_path).arg("version")).await?;
+        let remote_binary = quote_path(remote_binary_path)?;
+        run_cmd(connection.command(&remote_binary).arg("version")).await?;
 
-        let mut remote_server_child = client_state
-            .ssh_command(&format!(
-                "RUST_LOG={} {:?} run",
-                std::env::var("RUST_LOG").unwrap_or(String::new()),
-                remote_binary_path,
-            ))
+        let mut remote_server_child = connection
+            .command(&format!("RUST_LOG={} {remote_binary} run", rust_log()?))
             .spawn()
             .context("failed to spawn remote server")?;
         let mut child_stderr = remote_server_child.stderr.take().unwrap();
@@ -135,7 +820,10 @@ impl SshSession {
         let mut child_stdin = remote_server_child.stdin.take().unwrap();
 
         let executor = cx.background_executor().clone();
-        executor.clone().spawn(async move {
+        let io_connection = connection.clone();
+        let server_log = Arc::new(Mutex::new(ServerLog::default()));
+        let io_server_log = server_log.clone();
+        let io_task = executor.clone().spawn(async move {
             let mut stdin_buffer = Vec::new();
             let mut stdout_buffer = Vec::new();
             let mut stderr_buffer = Vec::new();
@@ -160,8 +848,8 @@ impl SshSession {
                         };
 
                         log::info!("spawn process: {:?}", request.command);
-                        let child = client_state
-                            .ssh_command(&request.command)
+                        let child = io_connection
+                            .command(&request.command)
                             .spawn()
                             .context("failed to create channel")?;
                         request.process_tx.send(child).ok();
@@ -206,13 +894,8 @@ impl SshSession {
                                 let mut start_ix = 0;
                                 while let Some(ix) = stderr_buffer[start_ix..stderr_offset].iter().position(|b| b == &b'\n') {
                                     let line_ix = start_ix + ix;
-                                    let content = &stderr_buffer[start_ix..line_ix];
+                                    log_server_line(&stderr_buffer[start_ix..line_ix], &io_server_log);
                                     start_ix = line_ix + 1;
-                                    if let Ok(record) = serde_json::from_slice::<LogRecord>(&content) {
-                                        record.log(log::logger())
-                                    } else {
-                                        eprintln!("(remote) {}", String::from_utf8_lossy(content));
-                                    }
                                 }
                                 stderr_buffer.drain(0..start_ix);
                                 stderr_offset -= start_ix;
@@ -224,9 +907,257 @@ impl SshSession {
                     }
                 }
             }
-        }).detach();
+        });
+        executor
+            .spawn(async move {
+                let result = io_task.await;
+                *state_tx.borrow_mut() = SshConnectionState::Disconnected;
+                result
+            })
+            .detach();
+
+        cx.update(|cx| {
+            Self::new(
+                incoming_rx,
+                outgoing_tx,
+                spawn_process_tx,
+                connection_options,
+                Some(connection),
+                state_rx,
+                server_log,
+                cx,
+            )
+        })
+    }
+
+    /// Spawns the installed remote server as a daemon that keeps running when the
+    /// connection is lost, and exchanges messages with it through a proxy spawned over the
+    /// connection. When the proxy's connection breaks, the connection is re-established
+    /// and a new proxy resumes the session, until it can't be resumed or
+    /// [`RESUMABLE_SESSION_TIMEOUT`] elapses.
+    async fn launch_resilient(
+        connection: Arc<dyn RemoteConnection>,
+        connection_options: Option<SshConnectionOptions>,
+        remote_binary_path: &Path,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
+        let (spawn_process_tx, mut spawn_process_rx) = mpsc::unbounded::<SpawnRequest>();
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded::<Envelope>();
+        let (incoming_tx, incoming_rx) = mpsc::unbounded::<Envelope>();
+        let (mut state_tx, state_rx) = watch::channel_with(SshConnectionState::Connected);
+        let (reconnect_tx, mut reconnect_rx) = mpsc::unbounded::<oneshot::Sender<Result<()>>>();
+
+        let remote_binary = quote_path(remote_binary_path)?;
+        run_cmd(connection.command(&remote_binary).arg("version")).await?;
+
+        // The daemon listens on a socket next to the binary, named after this session.
+        let socket_path =
+            remote_binary_path.with_file_name(format!("zed-session-{}.sock", session_id()));
+        let proxy_command = format!(
+            "RUST_LOG={} {remote_binary} proxy {}",
+            rust_log()?,
+            quote_path(&socket_path)?,
+        );
+
+        let executor = cx.background_executor().clone();
+        executor
+            .spawn({
+                let connection = connection.clone();
+                async move {
+                    while let Some(request) = spawn_process_rx.next().await {
+                        log::info!("spawn process: {:?}", request.command);
+                        match connection.command(&request.command).spawn() {
+                            Ok(child) => {
+                                request.process_tx.send(child).ok();
+                            }
+                            Err(error) => log::error!("failed to create channel: {error:?}"),
+                        }
+                    }
+                }
+            })
+            .detach();
+
+        // Re-establishing the connection may prompt for credentials, which needs the app.
+        cx.spawn({
+            let connection = connection.clone();
+            |cx| async move {
+                while let Some(result_tx) = reconnect_rx.next().await {
+                    let result = match connection.clone().reconnect(cx.clone()) {
+                        Some(reconnect) => select_biased! {
+                            result = reconnect.fuse() => result,
+                            _ = cx.background_executor().timer(RECONNECT_ATTEMPT_TIMEOUT).fuse() => {
+                                Err(anyhow!("timed out reconnecting"))
+                            }
+                        },
+                        None => Err(anyhow!("the connection can't be re-established")),
+                    };
+                    result_tx.send(result).ok();
+                }
+            }
+        })
+        .detach();
+
+        let server_log = Arc::new(Mutex::new(ServerLog::default()));
+        let io_server_log = server_log.clone();
+        let io_connection = connection.clone();
+        let io_executor = executor.clone();
+        let io_task = executor.spawn(async move {
+            let state = Mutex::new(ResumableState::default());
+            let mut lost_at = None;
+            let mut reconnect_delay = RECONNECT_DELAY;
+            loop {
+                let received_count = state.lock().received_count();
+                let mut proxy = io_connection
+                    .command(&proxy_command)
+                    .kill_on_drop(true)
+                    .spawn()
+                    .context("failed to spawn remote server proxy")?;
+                let stdin = proxy.stdin.take().unwrap();
+                let stdout = proxy.stdout.take().unwrap();
+                let stderr = proxy.stderr.take().unwrap();
+                io_executor
+                    .spawn(log_server_stderr(stderr, io_server_log.clone()))
+                    .detach();
+
+                let result = run_resumable_stream(
+                    stdout,
+                    stdin,
+                    &state,
+                    &mut outgoing_rx,
+                    &incoming_tx,
+                    RESUMABLE_READ_TIMEOUT,
+                )
+                .await;
+                drop(proxy);
+                match result {
+                    Ok(_) => return anyhow::Ok(()),
+                    Err(error) if error.is::<NotResumableError>() => return Err(error),
+                    Err(error) => {
+                        log::warn!("connection to the remote server lost: {error:#}");
+                        io_server_log
+                            .lock()
+                            .push(format!("connection to the remote server lost: {error:#}"));
+                    }
+                }
+
+                // Only give up once the session couldn't be resumed for a while, counting
+                // from when it last worked.
+                if state.lock().received_count() > received_count {
+                    lost_at = None;
+                    reconnect_delay = RECONNECT_DELAY;
+                }
+                let lost_since = *lost_at.get_or_insert_with(Instant::now);
+                loop {
+                    if lost_since.elapsed() >= RESUMABLE_SESSION_TIMEOUT {
+                        return Err(anyhow!(
+                            "failed to reconnect to the remote server within {:?}",
+                            RESUMABLE_SESSION_TIMEOUT
+                        ));
+                    }
+                    io_executor.timer(reconnect_delay).await;
+                    reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+
+                    // Messages sent in the meantime are sent once the session is resumed,
+                    // unless the session was disconnected.
+                    loop {
+                        match outgoing_rx.try_next() {
+                            Ok(Some(message)) => state.lock().sent(message),
+                            Ok(None) => return Ok(()),
+                            Err(_) => break,
+                        }
+                    }
+
+                    let (result_tx, result_rx) = oneshot::channel();
+                    reconnect_tx.unbounded_send(result_tx)?;
+                    match result_rx.await? {
+                        Ok(()) => break,
+                        Err(error) => log::warn!("failed to reconnect: {error:#}"),
+                    }
+                }
+            }
+        });
+        executor
+            .spawn(async move {
+                let result = io_task.await;
+                *state_tx.borrow_mut() = SshConnectionState::Disconnected;
+                result
+            })
+            .detach();
+
+        cx.update(|cx| {
+            Self::new(
+                incoming_rx,
+                outgoing_tx,
+                spawn_process_tx,
+                connection_options,
+                Some(connection),
+                state_rx,
+                server_log,
+                cx,
+            )
+        })
+    }
+
+    /// Checks that the remote server speaks the same protocol as this client, and
+    /// records its version.
+    async fn handshake(&self, client_version: SemanticVersion, cx: &AsyncAppContext) -> Result<()> {
+        let response = self.request(proto::RemoteHandshake {
+            protocol_version: PROTOCOL_VERSION,
+            client_version: client_version.to_string(),
+        });
+        // Servers that predate the handshake never respond to it.
+        let response = select_biased! {
+            response = response.fuse() => response?,
+            _ = cx.background_executor().timer(HANDSHAKE_TIMEOUT).fuse() => {
+                return Err(anyhow!("the remote server didn't respond to the handshake"));
+            }
+        };
+        if response.protocol_version != PROTOCOL_VERSION {
+            return Err(anyhow!(
+                "remote server {} uses protocol version {}, but version {} is expected",
+                response.server_version,
+                response.protocol_version,
+                PROTOCOL_VERSION
+            ));
+        }
+        *self.server_version.lock() = response.server_version.parse().ok();
+        Ok(())
+    }
+
+    /// Periodically pings the remote server while connected, recording how long it
+    /// takes to respond.
+    async fn measure_latency(this: Weak<Self>, cx: AsyncAppContext) {
+        loop {
+            let Some(session) = this.upgrade() else {
+                break;
+            };
+            if session.connection_state() == SshConnectionState::Disconnected {
+                session.set_latency(None);
+                break;
+            }
+
+            let start = Instant::now();
+            let response = session.request(proto::Ping {});
+            drop(session);
+            let latency = select_biased! {
+                response = response.fuse() => response.ok().map(|_| start.elapsed()),
+                _ = cx.background_executor().timer(PING_TIMEOUT).fuse() => None,
+            };
+
+            let Some(session) = this.upgrade() else {
+                break;
+            };
+            session.set_latency(latency);
+            drop(session);
+
+            cx.background_executor().timer(PING_INTERVAL).await;
+        }
+    }
 
-        cx.update(|cx| Self::new(incoming_rx, outgoing_tx, spawn_process_tx, cx))
+    fn set_latency(&self, latency: Option<Duration>) {
+        if *self.latency_rx.borrow() != latency {
+            *self.latency_tx.lock().borrow_mut() = latency;
+        }
     }
 
     pub fn server(
@@ -235,7 +1166,17 @@ impl SshSession {
         cx: &AppContext,
     ) -> Arc<SshSession> {
         let (tx, _rx) = mpsc::unbounded();
-        Self::new(incoming_rx, outgoing_tx, tx, cx)
+        let (_, state_rx) = watch::channel_with(SshConnectionState::Connected);
+        Self::new(
+            incoming_rx,
+            outgoing_tx,
+            tx,
+            None,
+            None,
+            state_rx,
+            Default::default(),
+            cx,
+        )
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -246,11 +1187,32 @@ impl SshSession {
         let (server_to_client_tx, server_to_client_rx) = mpsc::unbounded();
         let (client_to_server_tx, client_to_server_rx) = mpsc::unbounded();
         let (tx, _rx) = mpsc::unbounded();
+        let (_, state_rx) = watch::channel_with(SshConnectionState::Connected);
         (
-            client_cx
-                .update(|cx| Self::new(server_to_client_rx, client_to_server_tx, tx.clone(), cx)),
-            server_cx
-                .update(|cx| Self::new(client_to_server_rx, server_to_client_tx, tx.clone(), cx)),
+            client_cx.update(|cx| {
+                Self::new(
+                    server_to_client_rx,
+                    client_to_server_tx,
+                    tx.clone(),
+                    None,
+                    Some(Arc::new(FakeRemoteConnection)),
+                    state_rx.clone(),
+                    Default::default(),
+                    cx,
+                )
+            }),
+            server_cx.update(|cx| {
+                Self::new(
+                    client_to_server_rx,
+                    server_to_client_tx,
+                    tx.clone(),
+                    None,
+                    None,
+                    state_rx.clone(),
+                    Default::default(),
+                    cx,
+                )
+            }),
         )
     }
 
@@ -258,13 +1220,26 @@ impl SshSession {
         mut incoming_rx: mpsc::UnboundedReceiver<Envelope>,
         outgoing_tx: mpsc::UnboundedSender<Envelope>,
         spawn_process_tx: mpsc::UnboundedSender<SpawnRequest>,
+        connection_options: Option<SshConnectionOptions>,
+        connection: Option<Arc<dyn RemoteConnection>>,
+        state_rx: watch::Receiver<SshConnectionState>,
+        server_log: Arc<Mutex<ServerLog>>,
         cx: &AppContext,
     ) -> Arc<SshSession> {
+        let (latency_tx, latency_rx) = watch::channel();
         let this = Arc::new(Self {
             next_message_id: AtomicU32::new(0),
             response_channels: ResponseChannels::default(),
             outgoing_tx,
             spawn_process_tx,
+            connection_options,
+            connection,
+            state_rx,
+            server_log,
+            latency_tx: Mutex::new(latency_tx),
+            latency_rx,
+            server_version: Default::default(),
+            disconnect_requested: AtomicBool::new(false),
             message_handlers: Default::default(),
         });
 
@@ -309,29 +1284,96 @@ impl SshSession {
         this
     }
 
-    pub fn request<T: RequestMessage>(
-        &self,
-        payload: T,
-    ) -> impl 'static + Future<Output = Result<T::Response>> {
-        log::debug!("ssh request start. name:{}", T::NAME);
-        let response = self.request_dynamic(payload.into_envelope(0, None, None), "");
-        async move {
-            let response = response.await?;
-            log::debug!("ssh request finish. name:{}", T::NAME);
-            T::Response::from_envelope(response)
-                .ok_or_else(|| anyhow!("received a response of the wrong type"))
-        }
+    /// The options this session was established with, or `None` on the server side.
+    pub fn connection_options(&self) -> Option<&SshConnectionOptions> {
+        self.connection_options.as_ref()
     }
 
-    pub fn send<T: EnvelopedMessage>(&self, payload: T) -> Result<()> {
-        self.send_dynamic(payload.into_envelope(0, None, None))
+    /// The program and arguments that run a shell command line, given as one more
+    /// argument, in the remote environment. Returns `None` on the server side.
+    pub fn shell_command_prefix(&self) -> Option<Vec<String>> {
+        Some(self.connection.as_ref()?.shell_command_prefix())
     }
 
-    pub fn request_dynamic(
-        &self,
-        mut envelope: proto::Envelope,
-        _request_type: &'static str,
-    ) -> impl 'static + Future<Output = Result<proto::Envelope>> {
+    pub fn connection_state(&self) -> SshConnectionState {
+        *self.state_rx.borrow()
+    }
+
+    pub fn connection_state_changes(&self) -> watch::Receiver<SshConnectionState> {
+        self.state_rx.clone()
+    }
+
+    /// The round-trip time of the last request to the remote server that checks its
+    /// responsiveness, or `None` if it hasn't been measured or the server didn't respond.
+    pub fn latency(&self) -> Option<Duration> {
+        *self.latency_rx.borrow()
+    }
+
+    pub fn latency_changes(&self) -> watch::Receiver<Option<Duration>> {
+        self.latency_rx.clone()
+    }
+
+    /// The version of the remote server, as reported when the session was established.
+    /// It is `None` on the server side.
+    pub fn server_version(&self) -> Option<SemanticVersion> {
+        *self.server_version.lock()
+    }
+
+    /// The output of the remote server, which is kept after the session is
+    /// disconnected. It is empty on the server side.
+    pub fn server_log(&self) -> Arc<Mutex<ServerLog>> {
+        self.server_log.clone()
+    }
+
+    /// The log of the connections made to the host of the given options: the `ssh`
+    /// commands that were spawned, the authentication prompts, the output of `ssh`,
+    /// and any errors.
+    pub fn connection_log(
+        connection_options: &SshConnectionOptions,
+        cx: &mut AppContext,
+    ) -> Arc<Mutex<ServerLog>> {
+        cx.default_global::<SshConnectionRegistry>()
+            .logs
+            .entry(connection_options.connection_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Shuts down the remote server and the underlying ssh connection.
+    pub fn disconnect(&self) {
+        self.disconnect_requested.store(true, SeqCst);
+        self.outgoing_tx.close_channel();
+    }
+
+    /// Whether the session was disconnected on purpose, rather than because the
+    /// connection was lost.
+    pub fn disconnect_requested(&self) -> bool {
+        self.disconnect_requested.load(SeqCst)
+    }
+
+    pub fn request<T: RequestMessage>(
+        &self,
+        payload: T,
+    ) -> impl 'static + Future<Output = Result<T::Response>> {
+        log::debug!("ssh request start. name:{}", T::NAME);
+        let response = self.request_dynamic(payload.into_envelope(0, None, None), "");
+        async move {
+            let response = response.await?;
+            log::debug!("ssh request finish. name:{}", T::NAME);
+            T::Response::from_envelope(response)
+                .ok_or_else(|| anyhow!("received a response of the wrong type"))
+        }
+    }
+
+    pub fn send<T: EnvelopedMessage>(&self, payload: T) -> Result<()> {
+        self.send_dynamic(payload.into_envelope(0, None, None))
+    }
+
+    pub fn request_dynamic(
+        &self,
+        mut envelope: proto::Envelope,
+        _request_type: &'static str,
+    ) -> impl 'static + Future<Output = Result<proto::Envelope>> {
         envelope.id = self.next_message_id.fetch_add(1, SeqCst);
         let (tx, rx) = oneshot::channel();
         self.response_channels
@@ -358,6 +1400,118 @@ impl SshSession {
         process_rx.await.unwrap()
     }
 
+    /// Copies a local file to a path on the remote host, replacing any existing file.
+    /// `on_progress` is called with the number of bytes copied so far and the size of
+    /// the file.
+    pub async fn upload_file(
+        &self,
+        src_path: &Path,
+        dest_path: &Path,
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> Result<()> {
+        let connection = self.connection.as_ref().context("not connected")?;
+        let size = fs::metadata(src_path)
+            .await
+            .with_context(|| format!("failed to read {}", src_path.display()))?
+            .len();
+        let mut src_file = fs::File::open(src_path)
+            .await
+            .with_context(|| format!("failed to open {}", src_path.display()))?;
+        let mut child = connection
+            .command(&format!("cat > {}", quote_path(dest_path)?))
+            .spawn()
+            .context("failed to spawn upload command")?;
+        let mut child_stdin = child.stdin.take().context("failed to get stdin")?;
+
+        let mut buffer = vec![0; UPLOAD_CHUNK_SIZE];
+        let mut uploaded_len = 0;
+        on_progress(uploaded_len, size);
+        loop {
+            let len = src_file.read(&mut buffer).await?;
+            if len == 0 {
+                break;
+            }
+            child_stdin.write_all(&buffer[..len]).await?;
+            uploaded_len += len as u64;
+            on_progress(uploaded_len, size);
+        }
+        child_stdin.close().await?;
+        drop(child_stdin);
+
+        let output = child.output().await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "failed to upload {} to {}: {}",
+                src_path.display(),
+                dest_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    /// Copies a file on the remote host to a local path, replacing any existing file.
+    /// `on_progress` is called with the number of bytes copied so far and the size of
+    /// the file.
+    pub async fn download_file(
+        &self,
+        src_path: &Path,
+        dest_path: &Path,
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> Result<()> {
+        let connection = self.connection.as_ref().context("not connected")?;
+        let src = quote_path(src_path)?;
+        let size = run_cmd(&mut connection.command(&format!("wc -c < {src}")))
+            .await
+            .with_context(|| format!("failed to read {}", src_path.display()))?
+            .trim()
+            .parse::<u64>()
+            .context("failed to parse file size")?;
+
+        let mut child = connection
+            .command(&format!("cat {src}"))
+            .spawn()
+            .context("failed to spawn download command")?;
+        let mut child_stdout = child.stdout.take().context("failed to get stdout")?;
+        let result = async {
+            let mut dest_file = fs::File::create(dest_path)
+                .await
+                .with_context(|| format!("failed to create {}", dest_path.display()))?;
+            let mut buffer = vec![0; UPLOAD_CHUNK_SIZE];
+            let mut downloaded_len = 0;
+            on_progress(downloaded_len, size);
+            loop {
+                let len = child_stdout.read(&mut buffer).await?;
+                if len == 0 {
+                    break;
+                }
+                dest_file.write_all(&buffer[..len]).await?;
+                downloaded_len += len as u64;
+                on_progress(downloaded_len, size);
+            }
+            dest_file.flush().await?;
+            drop(child_stdout);
+
+            let output = child.output().await?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "failed to download {}: {}",
+                    src_path.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ))
+            }
+        }
+        .await;
+
+        if result.is_err() {
+            fs::remove_file(dest_path).await.ok();
+        }
+        result
+    }
+
     pub fn add_message_handler<M, E, H, F>(&self, entity: WeakModel<E>, handler: H)
     where
         M: EnvelopedMessage,
@@ -422,11 +1576,87 @@ impl ProtoClient for SshSession {
 }
 
 impl SshClientState {
+    /// Returns a connection to the given host, reusing the one that another session
+    /// has already established (or is establishing) when possible.
+    async fn shared(
+        connection_options: &SshConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<Arc<Self>> {
+        let key = SshConnectionOptions {
+            password: None,
+            ..connection_options.clone()
+        };
+
+        let existing = cx.update(|cx| {
+            cx.default_global::<SshConnectionRegistry>()
+                .connection(&key)
+        })?;
+        if let Some(state) = existing {
+            log::info!("reusing ssh connection to {}", key.connection_string());
+            return Ok(state);
+        }
+
+        let pending = cx.update(|cx| {
+            if let Some(pending) = cx
+                .default_global::<SshConnectionRegistry>()
+                .pending
+                .get(&key)
+            {
+                return pending.clone();
+            }
+
+            let pending = cx
+                .spawn({
+                    let key = key.clone();
+                    |mut cx| async move {
+                        let result = Self::new(&key, delegate, &mut cx)
+                            .await
+                            .map(Arc::new)
+                            .map_err(Arc::new);
+                        cx.update(|cx| {
+                            let registry = cx.default_global::<SshConnectionRegistry>();
+                            registry.pending.remove(&key);
+                            if let Ok(state) = &result {
+                                registry.connections.insert(key, Arc::downgrade(state));
+                            }
+                        })
+                        .ok();
+                        result
+                    }
+                })
+                .shared();
+            cx.default_global::<SshConnectionRegistry>()
+                .pending
+                .insert(key, pending.clone());
+            pending
+        })?;
+
+        pending.await.map_err(|error| {
+            // The error is shared between everyone waiting on this connection, so
+            // rebuild it, keeping timeouts and authentication failures distinguishable
+            // for callers.
+            if let Some(timeout_error) = error.downcast_ref::<SshConnectTimeoutError>() {
+                timeout_error.clone().into()
+            } else if let Some(authentication_error) =
+                error.downcast_ref::<SshAuthenticationError>()
+            {
+                authentication_error.clone().into()
+            } else {
+                anyhow!("{error:#}")
+            }
+        })
+    }
+
+    /// Whether the master process is still running. It removes its control socket
+    /// when the connection goes away.
+    fn is_connected(&self) -> bool {
+        self.socket_path.exists()
+    }
+
     #[cfg(not(unix))]
-    async fn new(
-        _user: String,
-        _host: String,
-        _port: u16,
+    pub(crate) async fn new(
+        _connection_options: &SshConnectionOptions,
         _delegate: Arc<dyn SshClientDelegate>,
         _cx: &mut AsyncAppContext,
     ) -> Result<Self> {
@@ -434,10 +1664,8 @@ impl SshClientState {
     }
 
     #[cfg(unix)]
-    async fn new(
-        user: String,
-        host: String,
-        port: u16,
+    pub(crate) async fn new(
+        connection_options: &SshConnectionOptions,
         delegate: Arc<dyn SshClientDelegate>,
         cx: &mut AsyncAppContext,
     ) -> Result<Self> {
@@ -447,7 +1675,25 @@ impl SshClientState {
 
         delegate.set_status(Some("connecting"), cx);
 
-        let url = format!("{user}@{host}");
+        let url = format!(
+            "{}@{}",
+            connection_options.username, connection_options.host
+        );
+        let port = connection_options.port;
+        let log = cx.update(|cx| SshSession::connection_log(connection_options, cx))?;
+        log.lock()
+            .push(format!("connecting to {url} on port {port}"));
+
+        let executor = cx.background_executor().clone();
+        select_biased! {
+            result = check_host_reachable(connection_options).fuse() => {
+                if let Err(error) = result {
+                    log.lock().push(format!("{error:#}"));
+                    return Err(error);
+                }
+            }
+            _ = executor.timer(REACHABILITY_TIMEOUT).fuse() => {}
+        }
         let temp_dir = tempfile::Builder::new()
             .prefix("zed-ssh-session")
             .tempdir()?;
@@ -457,16 +1703,45 @@ impl SshClientState {
         let listener =
             UnixListener::bind(&askpass_socket).context("failed to create askpass socket")?;
 
+        let askpass_delegate = delegate.clone();
+        let askpass_log = log.clone();
         let askpass_task = cx.spawn(|mut cx| async move {
+            let delegate = askpass_delegate;
+            let mut step = 0;
             while let Ok((mut stream, _)) = listener.accept().await {
+                // The askpass script sends the prompt kind followed by the prompt text,
+                // each terminated by a NUL byte.
+                let mut prompt_kind = Vec::new();
                 let mut buffer = Vec::new();
                 let mut reader = BufReader::new(&mut stream);
-                if reader.read_until(b'\0', &mut buffer).await.is_err() {
+                if reader.read_until(b'\0', &mut prompt_kind).await.is_err()
+                    || reader.read_until(b'\0', &mut buffer).await.is_err()
+                {
+                    prompt_kind.clear();
                     buffer.clear();
                 }
-                let password_prompt = String::from_utf8_lossy(&buffer);
+                let label = String::from_utf8_lossy(&buffer)
+                    .trim_end_matches('\0')
+                    .to_string();
+                let prompt_kind = String::from_utf8_lossy(&prompt_kind);
+                let prompt_kind = prompt_kind.trim_end_matches('\0');
+
+                // `ssh` sets `SSH_ASKPASS_PROMPT=none` when it only notifies that a
+                // security key must be touched, and doesn't read an answer. Closing the
+                // stream lets the askpass program exit right away.
+                if prompt_kind == "none" {
+                    askpass_log.lock().push(format!("security key: {label}"));
+                    drop(stream);
+                    delegate.set_security_key_prompt(Some(label), &mut cx);
+                    continue;
+                }
+                delegate.set_security_key_prompt(None, &mut cx);
+
+                step += 1;
+                askpass_log.lock().push(format!("prompt {step}: {label}"));
+                let prompt = SshAuthPrompt::new(label, prompt_kind, step);
                 if let Some(password) = delegate
-                    .ask_password(password_prompt.to_string(), &mut cx)
+                    .ask_password(prompt, &mut cx)
                     .await
                     .context("failed to get ssh password")
                     .and_then(|p| p)
@@ -481,7 +1756,7 @@ impl SshClientState {
         let askpass_script = format!(
             "{shebang}\n{print_args} | nc -U {askpass_socket} 2> /dev/null \n",
             askpass_socket = askpass_socket.display(),
-            print_args = "printf '%s\\0' \"$@\"",
+            print_args = "printf '%s\\0' \"$SSH_ASKPASS_PROMPT\" \"$@\"",
             shebang = "#!/bin/sh",
         );
         let askpass_script_path = temp_dir.path().join("askpass.sh");
@@ -492,7 +1767,14 @@ impl SshClientState {
         // the connection and keep it open, allowing other ssh commands to reuse it
         // via a control socket.
         let socket_path = temp_dir.path().join("ssh.sock");
-        let mut master_process = process::Command::new("ssh")
+        if let Some(identity) = &connection_options.identity {
+            log.lock()
+                .push(format!("authenticating with {}", identity.label()));
+        }
+        let mut master_command = process::Command::new("ssh");
+        master_command
+            .args(connection_options.master_args(temp_dir.path()).await?)
+            .kill_on_drop(true)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -501,8 +1783,26 @@ impl SshClientState {
             .args(["-N", "-o", "ControlMaster=yes", "-o"])
             .arg(format!("ControlPath={}", socket_path.display()))
             .args(["-p", &port.to_string()])
-            .arg(&url)
-            .spawn()?;
+            .arg(&url);
+        log.lock().push(format!("spawning {master_command:?}"));
+        let mut master_process = master_command.spawn()?;
+
+        // ssh reports banners, warnings and errors on its stderr for as long as the
+        // connection is open.
+        let stderr = master_process.stderr.take().unwrap();
+        let stderr_task = cx.background_executor().spawn({
+            let log = log.clone();
+            async move {
+                let mut output = String::new();
+                let mut lines = BufReader::new(stderr).lines();
+                while let Some(Ok(line)) = lines.next().await {
+                    log.lock().push(line.clone());
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+                output
+            }
+        });
 
         // Wait for this ssh process to close its stdout, indicating that authentication
         // has completed.
@@ -510,145 +1810,627 @@ impl SshClientState {
         let mut output = Vec::new();
         stdout.read_to_end(&mut output).await?;
         drop(askpass_task);
-
-        if master_process.try_status()?.is_some() {
-            output.clear();
-            let mut stderr = master_process.stderr.take().unwrap();
-            stderr.read_to_end(&mut output).await?;
-            Err(anyhow!(
-                "failed to connect: {}",
-                String::from_utf8_lossy(&output)
-            ))?;
+        delegate.set_security_key_prompt(None, cx);
+
+        if let Some(status) = master_process.try_status()? {
+            let output = stderr_task.await;
+            log.lock().push(format!("ssh exited with {status}"));
+            if output.contains("timed out") {
+                Err(SshConnectTimeoutError {
+                    host: connection_options.host.clone(),
+                    timeout: connection_options.timeouts.connect_timeout,
+                })?;
+            }
+            if output.contains("Permission denied")
+                || output.contains("Too many authentication failures")
+            {
+                Err(SshAuthenticationError {
+                    host: connection_options.host.clone(),
+                    output: output.trim().to_string(),
+                })?;
+            }
+            Err(anyhow!("failed to connect: {output}"))?;
         }
+        stderr_task.detach();
+        log.lock().push("connected".to_string());
 
         Ok(Self {
             url,
             port,
             socket_path,
-            _master_process: master_process,
-            _temp_dir: temp_dir,
+            forward_agent: connection_options.forward_agent,
+            master_process: Mutex::new(Some(master_process)),
+            temp_dir: Some(temp_dir),
         })
     }
 
-    async fn ensure_server_binary(
-        &self,
-        delegate: &Arc<dyn SshClientDelegate>,
-        src_path: &Path,
-        dst_path: &Path,
-        version: SemanticVersion,
-        cx: &mut AsyncAppContext,
-    ) -> Result<()> {
-        let mut dst_path_gz = dst_path.to_path_buf();
-        dst_path_gz.set_extension("gz");
+    fn ssh_command<S: AsRef<OsStr>>(&self, program: S) -> process::Command {
+        let mut command = process::Command::new("ssh");
+        self.ssh_options(&mut command)
+            .arg("-p")
+            .arg(&self.port.to_string())
+            .arg(&self.url)
+            .arg(program);
+        command
+    }
 
-        if let Some(parent) = dst_path.parent() {
-            run_cmd(self.ssh_command("mkdir").arg("-p").arg(parent)).await?;
+    fn ssh_options<'a>(&self, command: &'a mut process::Command) -> &'a mut process::Command {
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["-o", "ControlMaster=no", "-o"])
+            .arg(format!("ControlPath={}", self.socket_path.display()));
+        // Each session multiplexed over the master connection requests agent
+        // forwarding on its own.
+        if self.forward_agent {
+            command.args(["-o", "ForwardAgent=yes"]);
         }
+        command
+    }
+}
 
-        let mut server_binary_exists = false;
-        if cfg!(not(debug_assertions)) {
-            if let Ok(installed_version) = run_cmd(self.ssh_command(&dst_path).arg("version")).await
-            {
-                if installed_version.trim() == version.to_string() {
-                    server_binary_exists = true;
-                }
-            }
+impl RemoteConnection for SshClientState {
+    fn command(&self, program: &str) -> process::Command {
+        self.ssh_command(program)
+    }
+
+    fn shell_command_prefix(&self) -> Vec<String> {
+        let mut prefix = vec!["ssh".to_string()];
+        prefix.extend(self.ssh_args().unwrap_or_default());
+        prefix
+    }
+
+    fn ssh_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            "-o".to_string(),
+            "ControlMaster=no".to_string(),
+            "-o".to_string(),
+            format!("ControlPath={}", self.socket_path.display()),
+        ];
+        if self.forward_agent {
+            args.extend(["-o".to_string(), "ForwardAgent=yes".to_string()]);
         }
+        args.extend(["-p".to_string(), self.port.to_string(), self.url.clone()]);
+        Some(args)
+    }
+}
 
-        if server_binary_exists {
-            log::info!("remote development server already present",);
-            return Ok(());
+impl Drop for SshClientState {
+    fn drop(&mut self) {
+        if !self.is_connected() {
+            return;
         }
 
-        let src_stat = fs::metadata(src_path).await?;
-        let size = src_stat.len();
-        let server_mode = 0o755;
+        // Ask the master process to close the connection cleanly, rather than relying on
+        // it being killed when dropped. This can take a while when the host is unreachable,
+        // so it's done on its own thread, which also keeps the master process and its
+        // control socket until then.
+        log::info!("closing ssh connection to {}", self.url);
+        let mut exit_command = std::process::Command::new("ssh");
+        exit_command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .args(["-O", "exit", "-o"])
+            .arg(format!("ControlPath={}", self.socket_path.display()))
+            .args(["-p", &self.port.to_string()])
+            .arg(&self.url);
+        let master_process = self.master_process.get_mut().take();
+        let temp_dir = self.temp_dir.take();
+        // If the thread can't be spawned, the master process is killed when the closure
+        // is dropped.
+        std::thread::Builder::new()
+            .name("ssh connection closer".into())
+            .spawn(move || {
+                exit_command.status().ok();
+                drop((master_process, temp_dir));
+            })
+            .ok();
+    }
+}
+
+async fn ensure_server_binary(
+    connection: &dyn RemoteConnection,
+    delegate: &Arc<dyn SshClientDelegate>,
+    src_path: &Path,
+    dst_path: &Path,
+    version: SemanticVersion,
+    reinstall: bool,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    // The version in the file name looks like an extension, so suffixes are appended
+    // rather than set as extensions.
+    let dst_path_gz = path_with_suffix(dst_path, ".gz");
+    let dst_path_stamp = path_with_suffix(dst_path, ".sha256");
+
+    if let Some(parent) = dst_path.parent() {
+        run_cmd(connection.command("mkdir").arg("-p").arg(parent)).await?;
+    }
+
+    // The stamp file records the version and the digest of the binary that was
+    // uploaded last, so reconnecting to the same host can skip the upload entirely.
+    let src_digest = {
+        let src_path = src_path.to_path_buf();
+        cx.background_executor()
+            .spawn(async move { file_digest(&src_path).await })
+            .await?
+    };
+    let expected_stamp = format!("{version} {src_digest}");
+
+    if !reinstall && is_server_installed(connection, dst_path, &expected_stamp).await {
+        log::info!("remote development server already present");
+        return Ok(());
+    }
 
-        let t0 = Instant::now();
-        delegate.set_status(Some("uploading remote development server"), cx);
+    let compress = delegate.compress_server_upload(cx);
+    let temp_dir = tempfile::tempdir()?;
+    let (upload_path, compressed) = {
+        let src_path = src_path.to_path_buf();
+        let temp_dir = temp_dir.path().to_path_buf();
+        cx.background_executor()
+            .spawn(async move { prepare_server_upload(&src_path, compress, &temp_dir).await })
+            .await?
+    };
+    let size = fs::metadata(&upload_path).await?.len();
+    let server_mode = 0o755;
+
+    // Upload into a file named after the binary's digest, so that an upload that was
+    // interrupted, e.g. by a dropped connection, is resumed on the next attempt.
+    let dst_path_partial = path_with_suffix(
+        dst_path,
+        &format!(
+            ".{}.{}partial",
+            &src_digest[..16],
+            if compressed { "gz." } else { "" }
+        ),
+    );
+    let uploaded_len = uploaded_len(connection, &dst_path_partial, size).await;
+
+    let t0 = Instant::now();
+    delegate.set_status(Some("uploading remote development server"), cx);
+    if uploaded_len > 0 {
+        log::info!(
+            "resuming upload of remote development server ({}kb of {}kb)",
+            uploaded_len / 1024,
+            size / 1024
+        );
+    } else {
         log::info!("uploading remote development server ({}kb)", size / 1024);
-        self.upload_file(src_path, &dst_path_gz)
-            .await
-            .context("failed to upload server binary")?;
-        log::info!("uploaded remote development server in {:?}", t0.elapsed());
+    }
+    upload_file(
+        connection,
+        &upload_path,
+        &dst_path_partial,
+        uploaded_len,
+        size,
+        delegate,
+        cx,
+    )
+    .await
+    .context("failed to upload server binary")?;
+    log::info!("uploaded remote development server in {:?}", t0.elapsed());
+
+    if compressed {
+        run_cmd(
+            connection
+                .command("mv")
+                .arg(&dst_path_partial)
+                .arg(&dst_path_gz),
+        )
+        .await?;
 
         delegate.set_status(Some("extracting remote development server"), cx);
-        run_cmd(self.ssh_command("gunzip").arg("--force").arg(&dst_path_gz)).await?;
-
-        delegate.set_status(Some("unzipping remote development server"), cx);
         run_cmd(
-            self.ssh_command("chmod")
-                .arg(format!("{:o}", server_mode))
-                .arg(&dst_path),
+            connection
+                .command("gunzip")
+                .arg("--force")
+                .arg(&dst_path_gz),
         )
         .await?;
+    } else {
+        run_cmd(
+            connection
+                .command("mv")
+                .arg(&dst_path_partial)
+                .arg(dst_path),
+        )
+        .await?;
+    }
 
-        Ok(())
+    delegate.set_status(Some("unzipping remote development server"), cx);
+    run_cmd(
+        connection
+            .command("chmod")
+            .arg(format!("{:o}", server_mode))
+            .arg(&dst_path),
+    )
+    .await?;
+
+    let stamp = shlex::try_quote(&expected_stamp).context("invalid remote server stamp")?;
+    run_cmd(connection.command(&format!("echo {stamp} > {}", quote_path(&dst_path_stamp)?)))
+        .await
+        .context("failed to record remote server digest")?;
+
+    if let Err(error) = remove_stale_server_files(connection, delegate, dst_path, version, cx).await
+    {
+        log::warn!("failed to remove stale remote server files: {error:#}");
     }
 
-    async fn query_platform(&self) -> Result<SshPlatform> {
-        let os = run_cmd(self.ssh_command("uname").arg("-s")).await?;
-        let arch = run_cmd(self.ssh_command("uname").arg("-m")).await?;
+    Ok(())
+}
 
-        let os = match os.trim() {
-            "Darwin" => "macos",
-            "Linux" => "linux",
-            _ => Err(anyhow!("unknown uname os {os:?}"))?,
-        };
-        let arch = if arch.starts_with("arm") || arch.starts_with("aarch64") {
-            "aarch64"
-        } else if arch.starts_with("x86") || arch.starts_with("i686") {
-            "x86_64"
-        } else {
-            Err(anyhow!("unknown uname architecture {arch:?}"))?
-        };
+/// Whether the binary at `dst_path` runs and its stamp matches `expected_stamp`, meaning it
+/// was uploaded from the same build of the same version.
+async fn is_server_installed(
+    connection: &dyn RemoteConnection,
+    dst_path: &Path,
+    expected_stamp: &str,
+) -> bool {
+    let dst_path_stamp = path_with_suffix(dst_path, ".sha256");
+    match run_cmd(connection.command("cat").arg(&dst_path_stamp)).await {
+        Ok(installed_stamp) if installed_stamp.trim() == expected_stamp => {
+            let Ok(dst_path) = quote_path(dst_path) else {
+                return false;
+            };
+            run_cmd(connection.command(&dst_path).arg("version"))
+                .await
+                .is_ok()
+        }
+        _ => false,
+    }
+}
 
-        Ok(SshPlatform { os, arch })
+/// The length of the partial upload at `partial_path`, from which the upload of a file of
+/// `size` bytes resumes. Uploads start over when the partial file is missing or too long.
+async fn uploaded_len(connection: &dyn RemoteConnection, partial_path: &Path, size: u64) -> u64 {
+    let Ok(partial_path) = quote_path(partial_path) else {
+        return 0;
+    };
+    run_cmd(connection.command(&format!("wc -c < {partial_path}")))
+        .await
+        .ok()
+        .and_then(|output| output.trim().parse::<u64>().ok())
+        .filter(|len| *len <= size)
+        .unwrap_or(0)
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    path.into()
+}
+
+/// Removes the versions of the remote server that are older than the newest
+/// [`REMOTE_SERVER_VERSIONS_TO_KEEP`] installed next to `dst_path`, which is always kept, as
+/// well as the partial uploads that were never resumed, and reports the space that was
+/// reclaimed.
+async fn remove_stale_server_files(
+    connection: &dyn RemoteConnection,
+    delegate: &Arc<dyn SshClientDelegate>,
+    dst_path: &Path,
+    version: SemanticVersion,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let file_name = dst_path
+        .file_name()
+        .context("missing remote server file name")?
+        .to_string_lossy();
+    let prefix = file_name
+        .strip_suffix(&version.to_string())
+        .context("remote server file name doesn't end with its version")?;
+    let dir = dst_path.parent().unwrap_or(Path::new(""));
+
+    let listing = run_cmd(connection.command("ls").arg("-1").arg(dir.join("."))).await?;
+    let mut other_versions = listing
+        .lines()
+        .filter_map(|name| parse_server_version(name.strip_prefix(prefix)?))
+        .filter(|other_version| *other_version != version)
+        .collect::<Vec<_>>();
+    other_versions.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut stale_paths = other_versions
+        .into_iter()
+        .skip(REMOTE_SERVER_VERSIONS_TO_KEEP - 1)
+        .map(|version| dir.join(format!("{prefix}{version}")))
+        .collect::<Vec<_>>();
+    // Older versions of Zed installed a single version of the server, named after the
+    // release channel only.
+    stale_paths.push(dir.join(prefix.trim_end_matches('-')));
+
+    // Remove the stamps, compressed files and partial uploads that go with each binary.
+    // The globs are appended unquoted, so that the shell still expands them.
+    let mut patterns = Vec::new();
+    for path in &stale_paths {
+        let path = quote_path(path)?;
+        patterns.push(format!("{path} {path}.*"));
     }
+    patterns.push(format!("{}.*partial", quote_path(dst_path)?));
+    let patterns = patterns.join(" ");
+    // `du` reports a total of 0 when the patterns match nothing.
+    let output = run_cmd(connection.command(&format!(
+        "du -ck {patterns} 2>/dev/null | tail -n 1; rm -f {patterns}"
+    )))
+    .await?;
+    let reclaimed_kb = output
+        .split_whitespace()
+        .next()
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .unwrap_or(0);
+    if reclaimed_kb > 0 {
+        let reclaimed = format_byte_count((reclaimed_kb * 1024) as f64);
+        log::info!("removed stale remote server files ({reclaimed})");
+        delegate.set_status(
+            Some(&format!(
+                "removed stale remote server files, freeing {reclaimed}"
+            )),
+            cx,
+        );
+    }
+    Ok(())
+}
 
-    async fn upload_file(&self, src_path: &Path, dest_path: &Path) -> Result<()> {
-        let mut command = process::Command::new("scp");
-        let output = self
-            .ssh_options(&mut command)
-            .arg("-P")
-            .arg(&self.port.to_string())
-            .arg(&src_path)
-            .arg(&format!("{}:{}", self.url, dest_path.display()))
-            .output()
-            .await?;
+/// Parses the version at the end of the name of a remote server binary, rejecting the names
+/// of the files that go with it, such as `0.150.0.sha256`.
+fn parse_server_version(version: &str) -> Option<SemanticVersion> {
+    let components = version.split('.').collect::<Vec<_>>();
+    if components.len() != 3
+        || components
+            .iter()
+            .any(|component| component.is_empty() || !component.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    version.parse().ok()
+}
 
-        if output.status.success() {
-            Ok(())
-        } else {
-            Err(anyhow!(
-                "failed to upload file {} -> {}: {}",
-                src_path.display(),
-                dest_path.display(),
-                String::from_utf8_lossy(&output.stderr)
-            ))
+/// Checks that the host of a connection can be resolved and accepts connections on its
+/// ssh port, so that a mistyped host or a closed port is reported right away instead
+/// of after ssh's connect timeout. Hosts reached through a proxy aren't checked, and
+/// errors other than these are left for ssh to report.
+async fn check_host_reachable(connection_options: &SshConnectionOptions) -> Result<()> {
+    if connection_options.proxy.is_some() {
+        return Ok(());
+    }
+
+    // The host may be an alias from the user's ssh_config, so ask ssh where it would
+    // actually connect to.
+    let mut hostname = connection_options.host.clone();
+    let mut port = connection_options.port;
+    let Ok(config) = run_cmd(
+        process::Command::new("ssh")
+            .arg("-G")
+            .args(&connection_options.additional_args)
+            .args(["-p", &port.to_string()])
+            .arg(&connection_options.host),
+    )
+    .await
+    else {
+        return Ok(());
+    };
+    for line in config.lines() {
+        match line.split_once(' ') {
+            Some(("hostname", value)) => hostname = value.to_string(),
+            Some(("port", value)) => port = value.parse().unwrap_or(port),
+            Some(("proxycommand" | "proxyjump", value)) if value != "none" => return Ok(()),
+            _ => {}
         }
     }
 
-    fn ssh_command<S: AsRef<OsStr>>(&self, program: S) -> process::Command {
-        let mut command = process::Command::new("ssh");
-        self.ssh_options(&mut command)
-            .arg("-p")
-            .arg(&self.port.to_string())
-            .arg(&self.url)
-            .arg(program);
-        command
+    let address = smol::net::resolve((hostname.as_str(), port))
+        .await
+        .ok()
+        .and_then(|addresses| addresses.into_iter().next())
+        .ok_or_else(|| anyhow!("host {hostname} not found"))?;
+    match smol::net::TcpStream::connect(address).await {
+        Err(error) if error.kind() == io::ErrorKind::ConnectionRefused => Err(anyhow!(
+            "{hostname} refused the connection on port {port}. Check that an ssh server \
+            is running there."
+        )),
+        _ => Ok(()),
     }
+}
 
-    fn ssh_options<'a>(&self, command: &'a mut process::Command) -> &'a mut process::Command {
-        command
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .args(["-o", "ControlMaster=no", "-o"])
-            .arg(format!("ControlPath={}", self.socket_path.display()))
+/// Returns the file to upload as the remote server and whether it's gzipped, which
+/// is a copy in `temp_dir` when the binary isn't compressed as requested.
+async fn prepare_server_upload(
+    src_path: &Path,
+    compress: bool,
+    temp_dir: &Path,
+) -> Result<(PathBuf, bool)> {
+    let mut magic = [0; 2];
+    let is_gzipped = fs::File::open(src_path)
+        .await?
+        .read_exact(&mut magic)
+        .await
+        .is_ok()
+        && magic == GZIP_MAGIC;
+    if is_gzipped == compress {
+        return Ok((src_path.to_path_buf(), is_gzipped));
+    }
+
+    let src_file = io::BufReader::new(fs::File::open(src_path).await?);
+    if compress {
+        let dest_path = temp_dir.join("remote_server.gz");
+        let mut encoder =
+            GzipEncoder::with_quality(fs::File::create(&dest_path).await?, Level::Best);
+        io::copy(src_file, &mut encoder).await?;
+        encoder.close().await?;
+        Ok((dest_path, true))
+    } else {
+        let dest_path = temp_dir.join("remote_server");
+        let mut dest_file = fs::File::create(&dest_path).await?;
+        io::copy(GzipDecoder::new(src_file), &mut dest_file).await?;
+        dest_file.flush().await?;
+        Ok((dest_path, false))
     }
 }
 
+async fn query_platform(connection: &dyn RemoteConnection) -> Result<SshPlatform> {
+    let os = run_cmd(connection.command("uname").arg("-s")).await?;
+    let arch = run_cmd(connection.command("uname").arg("-m")).await?;
+
+    let os = match os.trim() {
+        "Darwin" => "macos",
+        "Linux" => "linux",
+        _ => Err(anyhow!("unknown uname os {os:?}"))?,
+    };
+    let arch = if arch.starts_with("arm") || arch.starts_with("aarch64") {
+        "aarch64"
+    } else if arch.starts_with("x86") || arch.starts_with("i686") {
+        "x86_64"
+    } else {
+        Err(anyhow!("unknown uname architecture {arch:?}"))?
+    };
+
+    Ok(SshPlatform { os, arch })
+}
+
+/// Uploads `src_path` to `dest_path`, skipping the first `offset` bytes, which a
+/// previous attempt already uploaded.
+async fn upload_file(
+    connection: &dyn RemoteConnection,
+    src_path: &Path,
+    dest_path: &Path,
+    offset: u64,
+    size: u64,
+    delegate: &Arc<dyn SshClientDelegate>,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let mut src_file = fs::File::open(src_path).await?;
+    src_file.seek(io::SeekFrom::Start(offset)).await?;
+    let redirect = if offset > 0 { ">>" } else { ">" };
+    let mut child = connection
+        .command(&format!("cat {redirect} {}", quote_path(dest_path)?))
+        .spawn()
+        .context("failed to spawn upload command")?;
+    let mut child_stdin = child.stdin.take().context("failed to get stdin")?;
+
+    let mut buffer = vec![0; UPLOAD_CHUNK_SIZE];
+    let mut uploaded_len = offset;
+    let mut reported_percentage = 0;
+    let mut rate_start = (Instant::now(), uploaded_len);
+    loop {
+        let len = src_file.read(&mut buffer).await?;
+        if len == 0 {
+            break;
+        }
+        child_stdin.write_all(&buffer[..len]).await?;
+        uploaded_len += len as u64;
+
+        let percentage = uploaded_len * 100 / size.max(1);
+        if percentage > reported_percentage {
+            reported_percentage = percentage;
+            delegate.set_progress(Some(percentage as f32 / 100.), cx);
+        }
+
+        let elapsed = rate_start.0.elapsed();
+        if elapsed >= UPLOAD_RATE_INTERVAL {
+            let rate = (uploaded_len - rate_start.1) as f64 / elapsed.as_secs_f64();
+            delegate.set_status(
+                Some(&format!(
+                    "uploading remote development server ({}/s)",
+                    format_byte_count(rate)
+                )),
+                cx,
+            );
+            // Setting the status hides the progress bar, so it's shown again.
+            delegate.set_progress(Some(reported_percentage as f32 / 100.), cx);
+            rate_start = (Instant::now(), uploaded_len);
+        }
+    }
+    child_stdin.close().await?;
+    drop(child_stdin);
+    delegate.set_progress(None, cx);
+
+    let output = child.output().await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "failed to upload file {} -> {}: {}",
+            src_path.display(),
+            dest_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+/// Logs a line that the remote server wrote to its stderr, which is a JSON log record
+/// unless the server failed before setting up logging.
+fn log_server_line(line: &[u8], server_log: &Mutex<ServerLog>) {
+    if let Ok(record) = serde_json::from_slice::<LogRecord>(line) {
+        record.log(log::logger());
+        server_log.lock().push(record.to_log_line());
+    } else {
+        let line = String::from_utf8_lossy(line);
+        eprintln!("(remote) {}", line);
+        server_log.lock().push(line.into_owned());
+    }
+}
+
+async fn log_server_stderr(stderr: process::ChildStderr, server_log: Arc<Mutex<ServerLog>>) {
+    use futures::AsyncBufReadExt as _;
+
+    let mut stderr = io::BufReader::new(stderr);
+    let mut line = Vec::new();
+    while let Ok(len) = stderr.read_until(b'\n', &mut line).await {
+        if len == 0 {
+            break;
+        }
+        log_server_line(line.strip_suffix(b"\n").unwrap_or(&line), &server_log);
+        line.clear();
+    }
+}
+
+/// An identifier for a new session, which doesn't collide with those of the other
+/// sessions on the same host.
+fn session_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!("{:x}-{nanos:x}", std::process::id())
+}
+
+/// Formats a number of bytes with the largest unit that keeps it above one, e.g. "1.5 MB".
+pub fn format_byte_count(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// The local `RUST_LOG`, quoted to be passed on to the remote server.
+fn rust_log() -> Result<String> {
+    let rust_log = std::env::var("RUST_LOG").unwrap_or_default();
+    Ok(shlex::try_quote(&rust_log)
+        .context("invalid RUST_LOG")?
+        .into_owned())
+}
+
+/// Quotes a path for use in a command line run by the remote shell.
+fn quote_path(path: &Path) -> Result<String> {
+    Ok(shlex::try_quote(&path.to_string_lossy())
+        .with_context(|| format!("invalid path {}", path.display()))?
+        .into_owned())
+}
+
+async fn file_digest(path: &Path) -> Result<String> {
+    let contents = fs::read(path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&contents)))
+}
+
 async fn run_cmd(command: &mut process::Command) -> Result<String> {
     let output = command.output().await?;
     if output.status.success() {
@@ -660,3 +2442,541 @@ async fn run_cmd(command: &mut process::Command) -> Result<String> {
         ))
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt as _;
+
+    /// Runs commands locally, from a temporary directory standing in for the remote home
+    /// directory.
+    struct TestConnection {
+        dir: TempDir,
+    }
+
+    impl TestConnection {
+        fn new() -> Self {
+            Self {
+                dir: tempfile::tempdir().unwrap(),
+            }
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.path().join(name)
+        }
+
+        fn write(&self, name: &str, contents: &str) {
+            std::fs::write(self.path(name), contents).unwrap();
+        }
+
+        fn write_executable(&self, name: &str, contents: &str) {
+            self.write(name, contents);
+            std::fs::set_permissions(self.path(name), std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        }
+    }
+
+    impl RemoteConnection for TestConnection {
+        fn command(&self, program: &str) -> process::Command {
+            let mut command = process::Command::new("sh");
+            command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .arg("-c")
+                .arg(format!("cd {:?} && {program} \"$@\"", self.dir.path()))
+                .arg("sh");
+            command
+        }
+
+        fn shell_command_prefix(&self) -> Vec<String> {
+            vec!["sh".to_string(), "-c".to_string()]
+        }
+    }
+
+    struct TestDelegate;
+
+    impl SshClientDelegate for TestDelegate {
+        fn ask_password(
+            &self,
+            _: SshAuthPrompt,
+            _: &mut AsyncAppContext,
+        ) -> oneshot::Receiver<Result<String>> {
+            unimplemented!()
+        }
+
+        fn remote_server_binary_path(
+            &self,
+            _: SemanticVersion,
+            _: &mut AsyncAppContext,
+        ) -> Result<PathBuf> {
+            unimplemented!()
+        }
+
+        fn get_server_binary(
+            &self,
+            _: SshPlatform,
+            _: &mut AsyncAppContext,
+        ) -> oneshot::Receiver<Result<(PathBuf, SemanticVersion)>> {
+            unimplemented!()
+        }
+
+        fn set_status(&self, _: Option<&str>, _: &mut AsyncAppContext) {}
+
+        fn set_progress(&self, _: Option<f32>, _: &mut AsyncAppContext) {}
+
+        fn set_security_key_prompt(&self, _: Option<String>, _: &mut AsyncAppContext) {}
+
+        fn compress_server_upload(&self, _: &mut AsyncAppContext) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_server_is_installed_when_its_stamp_matches() {
+        let connection = TestConnection::new();
+        let dst_path = connection.path("zed-remote-server-stable-0.150.0");
+        connection.write_executable("zed-remote-server-stable-0.150.0", "#!/bin/sh\nexit 0\n");
+        connection.write("zed-remote-server-stable-0.150.0.sha256", "0.150.0 abcd\n");
+
+        smol::block_on(async {
+            assert!(is_server_installed(&connection, &dst_path, "0.150.0 abcd").await);
+            // A different build of the same version is uploaded again.
+            assert!(!is_server_installed(&connection, &dst_path, "0.150.0 ef01").await);
+            assert!(!is_server_installed(&connection, &dst_path, "0.151.0 abcd").await);
+
+            // A binary that doesn't run, e.g. because it was truncated, is uploaded again.
+            connection.write_executable("zed-remote-server-stable-0.150.0", "#!/bin/sh\nexit 1\n");
+            assert!(!is_server_installed(&connection, &dst_path, "0.150.0 abcd").await);
+        });
+
+        connection.write_executable("zed-remote-server-stable-0.150.0", "#!/bin/sh\nexit 0\n");
+        std::fs::remove_file(connection.path("zed-remote-server-stable-0.150.0.sha256")).unwrap();
+        smol::block_on(async {
+            assert!(!is_server_installed(&connection, &dst_path, "0.150.0 abcd").await);
+        });
+    }
+
+    #[test]
+    fn test_askpass_prompt_kind() {
+        let prompt = SshAuthPrompt::new("Password:".into(), "", 1);
+        assert_eq!(prompt.kind, SshPromptKind::Secret);
+        assert_eq!(prompt.label, "Password:");
+
+        let prompt = SshAuthPrompt::new("Verification code:".into(), "", 2);
+        assert_eq!(prompt.kind, SshPromptKind::Plain);
+        assert_eq!(prompt.step, 2);
+
+        let prompt = SshAuthPrompt::new("Username: ".into(), "", 1);
+        assert_eq!(prompt.kind, SshPromptKind::Plain);
+
+        // Prompts mentioning a secret stay redacted, even with a plain word in them.
+        for label in [
+            "(alice@example.com) Password:",
+            "Enter passphrase for key '/home/alice/.ssh/id_ed25519':",
+            "Enter PIN code for ECDSA-SK key:",
+            "One-time password (OTP):",
+            "Response:",
+        ] {
+            let prompt = SshAuthPrompt::new(label.into(), "", 1);
+            assert_eq!(prompt.kind, SshPromptKind::Secret, "{label}");
+        }
+
+        let prompt = SshAuthPrompt::new(
+            "Allow use of key ~/.ssh/id_ed25519?\nKey fingerprint SHA256:abc.".into(),
+            "confirm",
+            1,
+        );
+        assert_eq!(prompt.kind, SshPromptKind::YesNo);
+    }
+
+    #[test]
+    fn test_proxy_url() {
+        assert_eq!(
+            SshProxy::from_url("socks5://127.0.0.1:9050").unwrap(),
+            SshProxy::Socks5 {
+                host: "127.0.0.1".into(),
+                port: 9050
+            }
+        );
+        assert_eq!(
+            SshProxy::from_url("socks5h://proxy.example.com/").unwrap(),
+            SshProxy::Socks5 {
+                host: "proxy.example.com".into(),
+                port: 1080
+            }
+        );
+        assert_eq!(
+            SshProxy::from_url("http://proxy.example.com").unwrap(),
+            SshProxy::Http {
+                host: "proxy.example.com".into(),
+                port: 8080
+            }
+        );
+        assert!(SshProxy::from_url("proxy.example.com:1080").is_err());
+        assert!(SshProxy::from_url("socks5://:1080").is_err());
+        assert!(SshProxy::from_url("socks5://proxy.example.com:port").is_err());
+        assert!(SshProxy::from_url("https://proxy.example.com").is_err());
+
+        // IPv6 hosts are passed to `nc` without their brackets.
+        assert_eq!(
+            SshProxy::from_url("socks5://[::1]:9050").unwrap(),
+            SshProxy::Socks5 {
+                host: "::1".into(),
+                port: 9050
+            }
+        );
+        assert_eq!(
+            SshProxy::from_url("http://[2001:db8::1]/").unwrap(),
+            SshProxy::Http {
+                host: "2001:db8::1".into(),
+                port: 8080
+            }
+        );
+        assert!(SshProxy::from_url("socks5://[::1").is_err());
+        assert!(SshProxy::from_url("socks5://[]:1080").is_err());
+        assert!(SshProxy::from_url("socks5://[::1]1080").is_err());
+        assert_eq!(
+            SshProxy::from_url("socks5://[::1]:9050")
+                .unwrap()
+                .proxy_command(),
+            "nc -X 5 -x ::1:9050 %h %p"
+        );
+
+        assert_eq!(
+            SshProxy::from_url("socks5://127.0.0.1:9050")
+                .unwrap()
+                .proxy_command(),
+            "nc -X 5 -x 127.0.0.1:9050 %h %p"
+        );
+        assert_eq!(
+            SshProxy::from_url("http://proxy.example.com:3128")
+                .unwrap()
+                .proxy_command(),
+            "nc -X connect -x proxy.example.com:3128 %h %p"
+        );
+    }
+
+    #[test]
+    fn test_master_args_with_proxy() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SshConnectionOptions {
+            host: "example.com".into(),
+            proxy: Some(SshProxy::Http {
+                host: "proxy.example.com".into(),
+                port: 3128,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            smol::block_on(options.master_args(dir.path())).unwrap(),
+            [
+                "-o",
+                "ProxyCommand=nc -X connect -x proxy.example.com:3128 %h %p"
+            ]
+        );
+        assert!(
+            smol::block_on(SshConnectionOptions::default().master_args(dir.path()))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_identity_args() {
+        let dir = tempfile::tempdir().unwrap();
+        smol::block_on(async {
+            let agent_key = SshIdentity::Agent {
+                public_key: "ssh-ed25519 AAAAC3Nza".into(),
+                comment: "me@laptop".into(),
+            };
+            let public_key_path = dir.path().join("identity.pub");
+            assert_eq!(
+                agent_key.ssh_args(dir.path()).await.unwrap(),
+                [
+                    "-o",
+                    "IdentitiesOnly=yes",
+                    "-i",
+                    public_key_path.to_str().unwrap()
+                ]
+            );
+            // ssh selects the agent key matching the public key file.
+            assert_eq!(
+                std::fs::read_to_string(&public_key_path).unwrap(),
+                "ssh-ed25519 AAAAC3Nza\n"
+            );
+
+            let key_file = SshIdentity::KeyFile("/home/me/.ssh/id_rsa".into());
+            assert_eq!(
+                key_file.ssh_args(dir.path()).await.unwrap(),
+                ["-o", "IdentitiesOnly=yes", "-i", "/home/me/.ssh/id_rsa"]
+            );
+
+            assert_eq!(
+                SshIdentity::Password.ssh_args(dir.path()).await.unwrap(),
+                [
+                    "-o",
+                    "PubkeyAuthentication=no",
+                    "-o",
+                    "PreferredAuthentications=keyboard-interactive,password"
+                ]
+            );
+
+            let options = SshConnectionOptions {
+                host: "example.com".into(),
+                identity: Some(key_file),
+                ..Default::default()
+            };
+            assert_eq!(
+                options.master_args(dir.path()).await.unwrap(),
+                ["-o", "IdentitiesOnly=yes", "-i", "/home/me/.ssh/id_rsa"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_timeouts_args() {
+        assert!(SshTimeouts::default().ssh_args().is_empty());
+
+        let timeouts = SshTimeouts {
+            connect_timeout: 10,
+            server_alive_interval: 0,
+            server_alive_count_max: 3,
+        };
+        assert_eq!(
+            timeouts.ssh_args(),
+            ["-o", "ConnectTimeout=10", "-o", "ServerAliveCountMax=3"]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let options = SshConnectionOptions {
+            host: "example.com".into(),
+            timeouts: SshTimeouts {
+                connect_timeout: 5,
+                server_alive_interval: 15,
+                server_alive_count_max: 4,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            smol::block_on(options.master_args(dir.path())).unwrap(),
+            [
+                "-o",
+                "ConnectTimeout=5",
+                "-o",
+                "ServerAliveInterval=15",
+                "-o",
+                "ServerAliveCountMax=4"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_partial_upload_resume_offset() {
+        let connection = TestConnection::new();
+        let partial_path =
+            connection.path("zed-remote-server-stable-0.150.0.0123456789abcdef.partial");
+
+        smol::block_on(async {
+            assert_eq!(uploaded_len(&connection, &partial_path, 100).await, 0);
+
+            connection.write(
+                "zed-remote-server-stable-0.150.0.0123456789abcdef.partial",
+                &"x".repeat(40),
+            );
+            assert_eq!(uploaded_len(&connection, &partial_path, 100).await, 40);
+            assert_eq!(uploaded_len(&connection, &partial_path, 40).await, 40);
+            // A partial upload that's longer than the file doesn't belong to it.
+            assert_eq!(uploaded_len(&connection, &partial_path, 39).await, 0);
+        });
+    }
+
+    #[test]
+    fn test_uploaded_len_quotes_path() {
+        let connection = TestConnection::new();
+        let file_name = "it's $(touch substituted) `touch substituted`.partial";
+        connection.write(file_name, "xyz");
+
+        smol::block_on(async {
+            assert_eq!(
+                uploaded_len(&connection, &connection.path(file_name), 100).await,
+                3
+            );
+        });
+        assert!(!connection.path("substituted").exists());
+    }
+
+    #[test]
+    fn test_askpass_yes_no_prompt() {
+        let prompt = SshAuthPrompt::new(
+            "The authenticity of host 'example.com (93.184.216.34)' can't be established.\n\
+             ED25519 key fingerprint is SHA256:abc.\n\
+             Are you sure you want to continue connecting (yes/no/[fingerprint])?"
+                .into(),
+            "",
+            1,
+        );
+        assert_eq!(prompt.kind, SshPromptKind::YesNo);
+        assert_eq!(
+            prompt.label,
+            "The authenticity of host 'example.com (93.184.216.34)' can't be established.\n\
+             ED25519 key fingerprint is SHA256:abc.\n\
+             Are you sure you want to continue connecting?"
+        );
+
+        let prompt = SshAuthPrompt::new("Accept the new host key (yes/no)? ".into(), "", 2);
+        assert_eq!(prompt.kind, SshPromptKind::YesNo);
+        assert_eq!(prompt.label, "Accept the new host key?");
+    }
+
+    #[gpui::test]
+    async fn test_handshake_protocol_version(
+        cx: &mut gpui::TestAppContext,
+        server_cx: &mut gpui::TestAppContext,
+    ) {
+        let (client, server) = SshSession::fake(cx, server_cx);
+        let server_protocol_version = server_cx.new_model(|_| PROTOCOL_VERSION + 1);
+        server.add_request_handler(
+            server_protocol_version.downgrade(),
+            |protocol_version, _: TypedEnvelope<proto::RemoteHandshake>, cx| async move {
+                Ok(proto::RemoteHandshakeResponse {
+                    protocol_version: protocol_version.read_with(&cx, |version, _| *version)?,
+                    server_version: "0.151.0".into(),
+                })
+            },
+        );
+
+        let client_version = SemanticVersion::new(0, 150, 0);
+        let error = client
+            .handshake(client_version, &cx.to_async())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "remote server 0.151.0 uses protocol version {}, but version {} is expected",
+                PROTOCOL_VERSION + 1,
+                PROTOCOL_VERSION
+            )
+        );
+        assert_eq!(client.server_version(), None);
+
+        server_protocol_version.update(server_cx, |version, _| *version = PROTOCOL_VERSION);
+        client
+            .handshake(client_version, &cx.to_async())
+            .await
+            .unwrap();
+        assert_eq!(
+            client.server_version(),
+            Some(SemanticVersion::new(0, 151, 0))
+        );
+    }
+
+    #[test]
+    fn test_master_args_with_additional_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SshConnectionOptions {
+            host: "example.com".into(),
+            timeouts: SshTimeouts {
+                connect_timeout: 10,
+                ..Default::default()
+            },
+            additional_args: vec!["-o".into(), "Ciphers=aes128-ctr".into(), "-4".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            smol::block_on(options.master_args(dir.path())).unwrap(),
+            ["-o", "ConnectTimeout=10", "-o", "Ciphers=aes128-ctr", "-4"]
+        );
+    }
+
+    #[test]
+    fn test_forward_agent_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SshConnectionOptions {
+            host: "example.com".into(),
+            forward_agent: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            smol::block_on(options.master_args(dir.path())).unwrap(),
+            ["-o", "ForwardAgent=yes"]
+        );
+
+        // Sessions multiplexed over the master connection request forwarding too.
+        let socket_path = dir.path().join("ssh.sock");
+        let control_path = format!("ControlPath={}", socket_path.display());
+        let connection = SshClientState {
+            socket_path,
+            port: 2222,
+            url: "me@example.com".into(),
+            forward_agent: true,
+            master_process: Mutex::new(None),
+            temp_dir: None,
+        };
+        assert_eq!(
+            connection.ssh_args().unwrap(),
+            [
+                "-o",
+                "ControlMaster=no",
+                "-o",
+                control_path.as_str(),
+                "-o",
+                "ForwardAgent=yes",
+                "-p",
+                "2222",
+                "me@example.com"
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_removing_stale_server_versions(cx: &mut gpui::TestAppContext) {
+        cx.executor().allow_parking();
+
+        let connection = TestConnection::new();
+        for version in ["0.148.0", "0.149.0", "0.150.0", "0.151.0", "0.152.0"] {
+            connection.write(&format!("zed-remote-server-stable-{version}"), "server");
+            connection.write(
+                &format!("zed-remote-server-stable-{version}.sha256"),
+                version,
+            );
+        }
+        connection.write("zed-remote-server-stable", "legacy server");
+        connection.write(
+            "zed-remote-server-stable-0.150.0.0123456789abcdef.partial",
+            "",
+        );
+        connection.write("zed-remote-server-preview-0.149.0", "preview server");
+
+        let delegate: Arc<dyn SshClientDelegate> = Arc::new(TestDelegate);
+        remove_stale_server_files(
+            &connection,
+            &delegate,
+            &connection.path("zed-remote-server-stable-0.150.0"),
+            SemanticVersion::new(0, 150, 0),
+            &mut cx.to_async(),
+        )
+        .await
+        .unwrap();
+
+        // The version in use is kept along with the two newest others.
+        let mut file_names = std::fs::read_dir(connection.dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        file_names.sort();
+        assert_eq!(
+            file_names,
+            [
+                "zed-remote-server-preview-0.149.0",
+                "zed-remote-server-stable-0.150.0",
+                "zed-remote-server-stable-0.150.0.sha256",
+                "zed-remote-server-stable-0.151.0",
+                "zed-remote-server-stable-0.151.0.sha256",
+                "zed-remote-server-stable-0.152.0",
+                "zed-remote-server-stable-0.152.0.sha256",
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::ssh_session::RemoteConnection;
+use anyhow::{anyhow, Context as _, Result};
+use smol::process::{self, Stdio};
+use std::path::PathBuf;
+
+#[cfg(windows)]
+use smol::process::windows::CommandExt;
+
+/// Options for opening a session in a WSL distribution.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WslConnectionOptions {
+    /// The name of the distribution, as listed by `wsl.exe --list`.
+    pub distro: String,
+    /// The user to run commands as, instead of the distribution's default user.
+    pub user: Option<String>,
+}
+
+/// A connection to a WSL distribution through `wsl.exe`. The remote server runs inside
+/// the distribution, so that it accesses the Linux filesystem natively rather than
+/// through the `\\wsl$` share.
+pub struct WslConnection {
+    options: WslConnectionOptions,
+}
+
+impl WslConnection {
+    pub(crate) async fn new(options: WslConnectionOptions) -> Result<Self> {
+        let distros = Self::list_distros().await?;
+        if !distros.contains(&options.distro) {
+            return Err(anyhow!(
+                "WSL distribution {} is not installed",
+                options.distro
+            ));
+        }
+        Ok(Self { options })
+    }
+
+    /// Lists the installed distributions.
+    pub async fn list_distros() -> Result<Vec<String>> {
+        let output = wsl_command()
+            .args(["--list", "--quiet"])
+            .output()
+            .await
+            .context("failed to run wsl.exe")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to list WSL distributions: {}",
+                decode_output(&output.stderr).trim()
+            ));
+        }
+
+        Ok(decode_output(&output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect())
+    }
+
+    /// Returns the home directory of the user in the given distribution, which is
+    /// where projects are opened by default.
+    pub async fn home_dir(options: &WslConnectionOptions) -> Result<PathBuf> {
+        let output = wsl_command()
+            .args(wsl_args(options))
+            .args(["--cd", "~", "--exec", "pwd"])
+            .output()
+            .await
+            .context("failed to run wsl.exe")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to find home directory in {}: {}",
+                options.distro,
+                decode_output(&output.stderr).trim()
+            ));
+        }
+        Ok(PathBuf::from(decode_output(&output.stdout).trim()))
+    }
+}
+
+impl RemoteConnection for WslConnection {
+    fn command(&self, program: &str) -> process::Command {
+        let mut command = wsl_command();
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(wsl_args(&self.options))
+            .args(["--cd", "~", "--exec", "sh", "-c"])
+            .arg(format!("{program} \"$@\""))
+            .arg("sh");
+        command
+    }
+
+    fn shell_command_prefix(&self) -> Vec<String> {
+        let mut prefix = vec!["wsl.exe".to_string()];
+        prefix.extend(wsl_args(&self.options));
+        prefix.extend(["--cd", "~", "--exec", "sh", "-c"].map(String::from));
+        prefix
+    }
+}
+
+fn wsl_command() -> process::Command {
+    #[allow(unused_mut)]
+    let mut command = process::Command::new("wsl.exe");
+    #[cfg(windows)]
+    command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+    command
+}
+
+fn wsl_args(options: &WslConnectionOptions) -> Vec<String> {
+    let mut args = vec!["--distribution".to_string(), options.distro.clone()];
+    if let Some(user) = &options.user {
+        args.extend(["--user".to_string(), user.clone()]);
+    }
+    args
+}
+
+/// `wsl.exe` writes its own messages in UTF-16 unless `WSL_UTF8` is set, while the
+/// output of commands run in the distribution is passed through unchanged.
+fn decode_output(output: &[u8]) -> String {
+    if output.len() % 2 == 0 && output.iter().skip(1).step_by(2).any(|byte| *byte == 0) {
+        let units = output
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect::<Vec<_>>();
+        String::from_utf16_lossy(&units)
+            .trim_start_matches('\u{feff}')
+            .to_string()
+    } else {
+        String::from_utf8_lossy(output).into_owned()
+    }
+}
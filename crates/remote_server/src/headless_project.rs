@@ -1,33 +1,60 @@
-use anyhow::Result;
-use fs::Fs;
+use anyhow::{anyhow, Context as _, Result};
+use async_compression::futures::bufread::GzipDecoder;
+use fs::{Fs, RenameOptions};
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    io::BufReader,
+    StreamExt as _,
+};
 use gpui::{AppContext, AsyncAppContext, Context, Model, ModelContext};
+use http_client::HttpClient;
 use project::{
     buffer_store::{BufferStore, BufferStoreEvent},
+    search::SearchQuery,
     worktree_store::WorktreeStore,
-    ProjectPath, WorktreeId, WorktreeSettings,
+    Project, ProjectPath, WorktreeId, WorktreeSettings,
 };
-use remote::SshSession;
+use remote::{protocol::PROTOCOL_VERSION, SshSession};
 use rpc::{
     proto::{self, AnyProtoClient, PeerId},
     TypedEnvelope,
 };
 use settings::{Settings as _, SettingsStore};
 use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    os::unix::process::CommandExt as _,
     path::{Path, PathBuf},
+    process::Command,
     sync::{atomic::AtomicUsize, Arc},
 };
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, RefreshKind, System};
 use util::ResultExt as _;
 use worktree::Worktree;
 
+use crate::pty::Pty;
+
 const PEER_ID: PeerId = PeerId { owner_id: 0, id: 0 };
 const PROJECT_ID: u64 = 0;
 
 pub struct HeadlessProject {
     pub fs: Arc<dyn Fs>,
+    pub http_client: Arc<dyn HttpClient>,
     pub session: AnyProtoClient,
     pub worktree_store: Model<WorktreeStore>,
     pub buffer_store: Model<BufferStore>,
     pub next_entry_id: Arc<AtomicUsize>,
+    /// Refreshed whenever the client asks for the server's resource usage. CPU usage
+    /// is measured over the time elapsed since the previous refresh.
+    system: System,
+    /// The terminals open for the client, by the ids it chose for them.
+    terminals: HashMap<u64, RemoteTerminal>,
+}
+
+struct RemoteTerminal {
+    pty: Arc<Pty>,
+    /// Writes to the pty in the order the input was received.
+    input_tx: UnboundedSender<Vec<u8>>,
 }
 
 impl HeadlessProject {
@@ -36,7 +63,12 @@ impl HeadlessProject {
         WorktreeSettings::register(cx);
     }
 
-    pub fn new(session: Arc<SshSession>, fs: Arc<dyn Fs>, cx: &mut ModelContext<Self>) -> Self {
+    pub fn new(
+        session: Arc<SshSession>,
+        fs: Arc<dyn Fs>,
+        http_client: Arc<dyn HttpClient>,
+        cx: &mut ModelContext<Self>,
+    ) -> Self {
         let this = cx.weak_model();
 
         let worktree_store = cx.new_model(|_| WorktreeStore::new(true));
@@ -45,10 +77,23 @@ impl HeadlessProject {
         cx.subscribe(&buffer_store, Self::on_buffer_store_event)
             .detach();
 
+        session.add_request_handler(this.clone(), Self::handle_remote_handshake);
+        session.add_request_handler(this.clone(), Self::handle_ping);
+        session.add_request_handler(this.clone(), Self::handle_get_remote_server_info);
         session.add_request_handler(this.clone(), Self::handle_add_worktree);
         session.add_request_handler(this.clone(), Self::handle_open_buffer_by_path);
+        session.add_request_handler(this.clone(), Self::handle_find_search_candidates);
+        session.add_request_handler(this.clone(), Self::handle_install_language_server);
+        session.add_request_handler(this.clone(), Self::handle_open_terminal);
+        session.add_message_handler(this.clone(), Self::handle_write_to_terminal);
+        session.add_message_handler(this.clone(), Self::handle_resize_terminal);
+        session.add_message_handler(this.clone(), Self::handle_close_terminal);
 
         session.add_request_handler(buffer_store.downgrade(), BufferStore::handle_blame_buffer);
+        session.add_request_handler(
+            buffer_store.downgrade(),
+            BufferStore::handle_get_buffer_git_info,
+        );
         session.add_request_handler(buffer_store.downgrade(), BufferStore::handle_update_buffer);
         session.add_request_handler(buffer_store.downgrade(), BufferStore::handle_save_buffer);
 
@@ -76,10 +121,266 @@ impl HeadlessProject {
         HeadlessProject {
             session: session.into(),
             fs,
+            http_client,
             worktree_store,
             buffer_store,
             next_entry_id: Default::default(),
+            system: System::new_with_specifics(
+                RefreshKind::new()
+                    .with_cpu(CpuRefreshKind::new().with_cpu_usage())
+                    .with_processes(ProcessRefreshKind::new().with_cpu()),
+            ),
+            terminals: HashMap::default(),
+        }
+    }
+
+    pub async fn handle_remote_handshake(
+        _: Model<Self>,
+        envelope: TypedEnvelope<proto::RemoteHandshake>,
+        _: AsyncAppContext,
+    ) -> Result<proto::RemoteHandshakeResponse> {
+        let client_protocol_version = envelope.payload.protocol_version;
+        if client_protocol_version != PROTOCOL_VERSION {
+            log::warn!(
+                "client {} uses protocol version {}, but this server uses version {}",
+                envelope.payload.client_version,
+                client_protocol_version,
+                PROTOCOL_VERSION
+            );
+        }
+        Ok(proto::RemoteHandshakeResponse {
+            protocol_version: PROTOCOL_VERSION,
+            server_version: env!("ZED_PKG_VERSION").to_string(),
+        })
+    }
+
+    pub async fn handle_ping(
+        _: Model<Self>,
+        _: TypedEnvelope<proto::Ping>,
+        _: AsyncAppContext,
+    ) -> Result<proto::Ack> {
+        Ok(proto::Ack {})
+    }
+
+    pub async fn handle_get_remote_server_info(
+        this: Model<Self>,
+        _: TypedEnvelope<proto::GetRemoteServerInfo>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::GetRemoteServerInfoResponse> {
+        this.update(&mut cx, |this, _| {
+            let system = &mut this.system;
+            let pid = Pid::from_u32(std::process::id());
+            system.refresh_cpu_specifics(CpuRefreshKind::new().with_cpu_usage());
+            system.refresh_memory_specifics(MemoryRefreshKind::new().with_ram());
+            system
+                .refresh_process_specifics(pid, ProcessRefreshKind::new().with_cpu().with_memory());
+            let (process_memory, process_cpu_usage) = system
+                .process(pid)
+                .map_or((0, 0.), |process| (process.memory(), process.cpu_usage()));
+
+            proto::GetRemoteServerInfoResponse {
+                version: env!("ZED_PKG_VERSION").to_string(),
+                os: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+                process_memory,
+                process_cpu_usage,
+                used_memory: system.used_memory(),
+                total_memory: system.total_memory(),
+                cpu_usage: system.global_cpu_info().cpu_usage(),
+                cpu_count: system.cpus().len() as u32,
+                load_average: System::load_average().one,
+            }
+        })
+    }
+
+    /// Downloads a language server into the data directory of this server, unless it's
+    /// already there, so that the client can run it on this host.
+    pub async fn handle_install_language_server(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::InstallLanguageServer>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::InstallLanguageServerResponse> {
+        let message = envelope.payload;
+        let (fs, http_client) = this.update(&mut cx, |this, _| {
+            (this.fs.clone(), this.http_client.clone())
+        })?;
+
+        // The names are joined to the languages directory, which they must not escape.
+        for name in [&message.server_name, &message.file_name] {
+            if Path::new(name).file_name() != Some(OsStr::new(name)) {
+                return Err(anyhow!("invalid language server name {name:?}"));
+            }
         }
+        let server_dir = paths::languages_dir().join(&message.server_name);
+        let path = server_dir.join(&message.file_name);
+        if !fs.is_file(&path).await {
+            log::info!("downloading language server from {}", message.url);
+            fs.create_dir(&server_dir).await?;
+            let mut response = http_client
+                .get(&message.url, Default::default(), true)
+                .await
+                .context("error downloading language server")?;
+            if !response.status().is_success() {
+                return Err(anyhow!(
+                    "language server download failed with status {}",
+                    response.status()
+                ));
+            }
+
+            // The server is only moved into place once complete, so that an interrupted
+            // download isn't taken for an installed server.
+            let partial_path = server_dir.join(format!("{}.partial", message.file_name));
+            let body = BufReader::new(response.body_mut());
+            if message.gzipped {
+                let body = GzipDecoder::new(body);
+                futures::pin_mut!(body);
+                fs.create_file_with(&partial_path, body).await?;
+            } else {
+                futures::pin_mut!(body);
+                fs.create_file_with(&partial_path, body).await?;
+            }
+            fs.set_executable(&partial_path).await?;
+            fs.rename(
+                &partial_path,
+                &path,
+                RenameOptions {
+                    overwrite: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+
+        Ok(proto::InstallLanguageServerResponse {
+            path: path.to_string_lossy().into_owned(),
+        })
+    }
+
+    /// Runs the program, or a login shell, in a new pseudo-terminal, whose output is
+    /// sent to the client until the program exits.
+    pub async fn handle_open_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::OpenTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::Ack> {
+        let message = envelope.payload;
+        let terminal_id = message.terminal_id;
+        let mut command = if let Some(program) = message.program {
+            let mut command = Command::new(program);
+            command.args(message.args);
+            command
+        } else {
+            let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+            let name = Path::new(&shell)
+                .file_name()
+                .map_or(shell.clone(), |name| name.to_string_lossy().into_owned());
+            let mut command = Command::new(&shell);
+            // A leading dash tells the shell to run as a login shell.
+            command.arg0(format!("-{name}"));
+            command
+        };
+        command.envs(message.env);
+        command.current_dir(
+            message
+                .cwd
+                .map_or_else(|| util::paths::home_dir().clone(), PathBuf::from),
+        );
+
+        // The client resizes the terminal once it's laid out.
+        let (pty, mut child) = Pty::spawn(command, 24, 80)?;
+        let pty = Arc::new(pty);
+        let (input_tx, mut input_rx) = mpsc::unbounded::<Vec<u8>>();
+        let session = this.update(&mut cx, |this, _| {
+            this.terminals.insert(
+                terminal_id,
+                RemoteTerminal {
+                    pty: pty.clone(),
+                    input_tx,
+                },
+            );
+            this.session.clone()
+        })?;
+
+        cx.background_executor()
+            .spawn({
+                let pty = pty.clone();
+                async move {
+                    while let Some(data) = input_rx.next().await {
+                        if pty.write(&data).await.log_err().is_none() {
+                            break;
+                        }
+                    }
+                }
+            })
+            .detach();
+
+        cx.spawn(|mut cx| async move {
+            let mut buffer = vec![0; 4096];
+            // Reading fails rather than returning 0 on Linux once the process exits.
+            while let Ok(len @ 1..) = pty.read(&mut buffer).await {
+                session
+                    .send(proto::TerminalOutput {
+                        terminal_id,
+                        data: buffer[..len].to_vec(),
+                    })
+                    .ok();
+            }
+            let exit_code = smol::unblock(move || child.wait())
+                .await
+                .log_err()
+                .and_then(|status| status.code());
+            session
+                .send(proto::TerminalExited {
+                    terminal_id,
+                    exit_code,
+                })
+                .ok();
+            this.update(&mut cx, |this, _| this.terminals.remove(&terminal_id))
+                .ok();
+        })
+        .detach();
+
+        Ok(proto::Ack {})
+    }
+
+    pub async fn handle_write_to_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::WriteToTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, _| {
+            if let Some(terminal) = this.terminals.get(&envelope.payload.terminal_id) {
+                terminal.input_tx.unbounded_send(envelope.payload.data).ok();
+            }
+        })
+    }
+
+    pub async fn handle_resize_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::ResizeTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let message = envelope.payload;
+        this.update(&mut cx, |this, _| {
+            if let Some(terminal) = this.terminals.get(&message.terminal_id) {
+                terminal
+                    .pty
+                    .resize(message.rows as u16, message.columns as u16)
+                    .log_err();
+            }
+        })
+    }
+
+    pub async fn handle_close_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::CloseTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, _| {
+            if let Some(terminal) = this.terminals.remove(&envelope.payload.terminal_id) {
+                terminal.pty.hang_up();
+            }
+        })
     }
 
     pub async fn handle_add_worktree(
@@ -157,6 +458,40 @@ impl HeadlessProject {
         })
     }
 
+    pub async fn handle_find_search_candidates(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::FindSearchCandidates>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::FindSearchCandidatesResponse> {
+        let query = SearchQuery::from_proto(
+            message
+                .payload
+                .query
+                .ok_or_else(|| anyhow!("missing query field"))?,
+        )?;
+        let limit = message.payload.limit as usize;
+        let paths = this
+            .update(&mut cx, |this, cx| {
+                let worktrees = this
+                    .worktree_store
+                    .read(cx)
+                    .visible_worktrees(cx)
+                    .collect::<Vec<_>>();
+                Project::find_search_candidates(&worktrees, this.fs.clone(), query, limit, cx)
+            })?
+            .await;
+
+        Ok(proto::FindSearchCandidatesResponse {
+            paths: paths
+                .into_iter()
+                .map(|path| proto::ProjectPath {
+                    worktree_id: path.worktree_id.to_proto(),
+                    path: path.path.to_string_lossy().to_string(),
+                })
+                .collect(),
+        })
+    }
+
     pub fn on_buffer_store_event(
         &mut self,
         _: Model<BufferStore>,
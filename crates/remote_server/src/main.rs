@@ -1,19 +1,34 @@
+use anyhow::{anyhow, Context as _, Result};
 use fs::RealFs;
-use futures::channel::mpsc;
-use gpui::Context as _;
+use futures::{channel::mpsc, select_biased, FutureExt as _};
+use gpui::{AppContext, Context as _};
+use http_client::HttpClientWithProxy;
 use remote::{
     json_log::LogRecord,
-    protocol::{read_message, write_message},
+    protocol::{
+        read_message, run_resumable_stream, write_message, NotResumableError,
+        RESUMABLE_READ_TIMEOUT, RESUMABLE_SESSION_TIMEOUT,
+    },
     SshSession,
 };
 use remote_server::HeadlessProject;
-use smol::{io::AsyncWriteExt, stream::StreamExt as _, Async};
+use rpc::proto::Envelope;
+use smol::{io::AsyncWriteExt, net::unix::UnixListener, stream::StreamExt as _, Async};
 use std::{
     env,
-    io::{self, Write},
-    mem, process,
+    io::{self, Read, Write},
+    mem,
+    os::unix::{net::UnixStream, process::CommandExt as _},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
     sync::Arc,
+    thread,
+    time::Duration,
 };
+use util::ResultExt as _;
+
+/// How long the proxy waits for the daemon it started to listen on its socket.
+const DAEMON_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 
 fn main() {
     env::set_var("RUST_BACKTRACE", "1");
@@ -25,64 +40,249 @@ fn main() {
         })
         .init();
 
-    let subcommand = std::env::args().nth(1);
-    match subcommand.as_deref() {
-        Some("run") => {}
-        Some("version") => {
+    let args = env::args().collect::<Vec<_>>();
+    let socket_path = args.get(2).map(PathBuf::from);
+    let socket_path = match (args.get(1).map(String::as_str), socket_path) {
+        (Some("run"), None) => None,
+        (Some("daemon"), Some(socket_path)) => Some(socket_path),
+        (Some("proxy"), Some(socket_path)) => {
+            if let Err(error) = proxy(&socket_path) {
+                eprintln!("{error:#}");
+                process::exit(1);
+            }
+            return;
+        }
+        (Some("version"), None) => {
             println!("{}", env!("ZED_PKG_VERSION"));
             return;
         }
         _ => {
-            eprintln!("usage: remote <run|version>");
+            eprintln!("usage: remote <run|daemon SOCKET|proxy SOCKET|version>");
             process::exit(1);
         }
-    }
+    };
+
+    // The server is started by a non-interactive, non-login ssh command, so its
+    // environment lacks anything set up by the user's shell profile. Load it here so
+    // that language servers and tasks spawned on the remote host inherit it.
+    smol::block_on(util::load_login_shell_environment(true, Stdio::null())).log_err();
 
     gpui::App::headless().run(move |cx| {
         HeadlessProject::init(cx);
 
         let (incoming_tx, incoming_rx) = mpsc::unbounded();
-        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded();
-
-        let mut stdin = Async::new(io::stdin()).unwrap();
-        let mut stdout = Async::new(io::stdout()).unwrap();
+        let (outgoing_tx, outgoing_rx) = mpsc::unbounded();
 
         let session = SshSession::server(incoming_rx, outgoing_tx, cx);
         let project = cx.new_model(|cx| {
             HeadlessProject::new(
                 session.clone(),
                 Arc::new(RealFs::new(Default::default(), None)),
+                Arc::new(HttpClientWithProxy::new(None)),
                 cx,
             )
         });
 
-        cx.background_executor()
-            .spawn(async move {
-                let mut output_buffer = Vec::new();
-                while let Some(message) = outgoing_rx.next().await {
-                    write_message(&mut stdout, &mut output_buffer, message).await?;
-                    stdout.flush().await?;
+        match socket_path {
+            Some(socket_path) => {
+                if let Err(error) = serve_resumable(socket_path, incoming_tx, outgoing_rx, cx) {
+                    log::error!("failed to serve session: {error:#}");
+                    process::exit(1);
                 }
-                anyhow::Ok(())
-            })
-            .detach();
-
-        cx.background_executor()
-            .spawn(async move {
-                let mut input_buffer = Vec::new();
-                loop {
-                    let message = match read_message(&mut stdin, &mut input_buffer).await {
-                        Ok(message) => message,
-                        Err(error) => {
-                            log::warn!("error reading message: {:?}", error);
-                            process::exit(0);
+            }
+            None => serve_stdio(incoming_tx, outgoing_rx, cx),
+        }
+
+        mem::forget(project);
+    });
+}
+
+/// Exchanges messages with the client over stdio, exiting when the client goes away.
+fn serve_stdio(
+    incoming_tx: mpsc::UnboundedSender<Envelope>,
+    mut outgoing_rx: mpsc::UnboundedReceiver<Envelope>,
+    cx: &AppContext,
+) {
+    let mut stdin = Async::new(io::stdin()).unwrap();
+    let mut stdout = Async::new(io::stdout()).unwrap();
+
+    cx.background_executor()
+        .spawn(async move {
+            let mut output_buffer = Vec::new();
+            while let Some(message) = outgoing_rx.next().await {
+                write_message(&mut stdout, &mut output_buffer, message).await?;
+                stdout.flush().await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach();
+
+    cx.background_executor()
+        .spawn(async move {
+            let mut input_buffer = Vec::new();
+            loop {
+                let message = match read_message(&mut stdin, &mut input_buffer).await {
+                    Ok(message) => message,
+                    Err(error) => {
+                        log::warn!("error reading message: {:?}", error);
+                        process::exit(0);
+                    }
+                };
+                incoming_tx.unbounded_send(message).ok();
+            }
+        })
+        .detach();
+}
+
+/// Exchanges messages with the client through the proxies that connect to
+/// `socket_path`, so that the client can resume the session through a new proxy after
+/// the connection of the previous one broke. A new proxy replaces the current one.
+/// Exits when the session ends, or when no proxy connects for
+/// [`RESUMABLE_SESSION_TIMEOUT`].
+fn serve_resumable(
+    socket_path: PathBuf,
+    incoming_tx: mpsc::UnboundedSender<Envelope>,
+    mut outgoing_rx: mpsc::UnboundedReceiver<Envelope>,
+    cx: &AppContext,
+) -> Result<()> {
+    // The socket is removed when exiting, from whatever directory the server is in then.
+    let socket_path = env::current_dir()?.join(socket_path);
+    std::fs::remove_file(&socket_path).ok();
+    let listener = UnixListener::bind(&socket_path)
+        .with_context(|| format!("failed to listen on {}", socket_path.display()))?;
+
+    cx.background_executor()
+        .spawn(async move {
+            let state = Default::default();
+            let mut next_stream = None;
+            let result = loop {
+                let stream = match next_stream.take() {
+                    Some(stream) => stream,
+                    None => select_biased! {
+                        connection = listener.accept().fuse() => match connection {
+                            Ok((stream, _)) => stream,
+                            Err(error) => break Err(error.into()),
+                        },
+                        _ = smol::Timer::after(RESUMABLE_SESSION_TIMEOUT).fuse() => {
+                            break Err(anyhow!("the client didn't reconnect in time"));
+                        }
+                    },
+                };
+                log::info!("client connected");
+
+                let run = run_resumable_stream(
+                    stream.clone(),
+                    stream,
+                    &state,
+                    &mut outgoing_rx,
+                    &incoming_tx,
+                    RESUMABLE_READ_TIMEOUT,
+                );
+                select_biased! {
+                    connection = listener.accept().fuse() => match connection {
+                        Ok((stream, _)) => {
+                            log::info!("client reconnected, dropping its previous connection");
+                            next_stream = Some(stream);
                         }
-                    };
-                    incoming_tx.unbounded_send(message).ok();
+                        Err(error) => break Err(error.into()),
+                    },
+                    result = run.fuse() => match result {
+                        Ok(end) => {
+                            log::info!("session ended: {end:?}");
+                            break Ok(());
+                        }
+                        Err(error) if error.is::<NotResumableError>() => break Err(error),
+                        Err(error) => log::warn!("connection to the client lost: {error:#}"),
+                    },
                 }
-            })
-            .detach();
+            };
 
-        mem::forget(project);
+            std::fs::remove_file(&socket_path).ok();
+            match result {
+                Ok(()) => {
+                    std::fs::remove_file(log_path(&socket_path)).ok();
+                    process::exit(0);
+                }
+                Err(error) => {
+                    log::error!("{error:#}");
+                    process::exit(1);
+                }
+            }
+        })
+        .detach();
+    Ok(())
+}
+
+/// Connects the stdio of this process to the daemon serving the session at
+/// `socket_path`, starting the daemon first if it isn't running. The daemon outlives
+/// the proxy, so that the session can be resumed through another proxy.
+fn proxy(socket_path: &Path) -> Result<()> {
+    let stream = match UnixStream::connect(socket_path) {
+        Ok(stream) => stream,
+        Err(_) => {
+            spawn_daemon(socket_path)?;
+            connect_to_daemon(socket_path)?
+        }
+    };
+
+    let mut daemon_writer = stream.try_clone()?;
+    thread::spawn(move || {
+        io::copy(&mut io::stdin().lock(), &mut daemon_writer).ok();
+        // The client is gone, so let the daemon know right away.
+        process::exit(0);
     });
+
+    // Stdout is line buffered, so flush it after each read rather than waiting for a
+    // newline that binary messages may not contain.
+    let mut daemon_reader = stream;
+    let mut stdout = io::stdout().lock();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let len = daemon_reader.read(&mut buffer)?;
+        if len == 0 {
+            return Ok(());
+        }
+        stdout.write_all(&buffer[..len])?;
+        stdout.flush()?;
+    }
+}
+
+fn spawn_daemon(socket_path: &Path) -> Result<()> {
+    let log_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(socket_path))
+        .context("failed to create the daemon's log file")?;
+    Command::new(env::current_exe()?)
+        .arg("daemon")
+        .arg(socket_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(log_file)
+        // In its own process group, the daemon isn't killed along with the proxy when
+        // the ssh connection is lost.
+        .process_group(0)
+        .spawn()
+        .context("failed to spawn daemon")?;
+    Ok(())
+}
+
+fn connect_to_daemon(socket_path: &Path) -> Result<UnixStream> {
+    let start = std::time::Instant::now();
+    loop {
+        match UnixStream::connect(socket_path) {
+            Ok(stream) => return Ok(stream),
+            Err(error) if start.elapsed() >= DAEMON_STARTUP_TIMEOUT => {
+                return Err(error).context("failed to connect to the daemon")
+            }
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+/// The daemon of a session logs next to its socket, since it has no client to log to.
+fn log_path(socket_path: &Path) -> PathBuf {
+    let mut path = socket_path.as_os_str().to_owned();
+    path.push(".log");
+    path.into()
 }
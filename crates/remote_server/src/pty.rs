@@ -0,0 +1,125 @@
+use anyhow::{Context as _, Result};
+use smol::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    Async,
+};
+use std::{
+    fs::File,
+    io,
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd},
+        unix::process::CommandExt as _,
+    },
+    process::{Child, Command},
+};
+
+/// The pseudo-terminal of a process run for a terminal of the client.
+pub struct Pty {
+    master: Async<File>,
+    pid: u32,
+}
+
+impl Pty {
+    /// Spawns the command in a new session, whose controlling terminal is a new
+    /// pseudo-terminal.
+    pub fn spawn(mut command: Command, rows: u16, columns: u16) -> Result<(Self, Child)> {
+        let mut master = -1;
+        let mut slave = -1;
+        let mut size = window_size(rows, columns);
+        // SAFETY: openpty only writes to the pointers it's given.
+        let result = unsafe {
+            libc::openpty(
+                &mut master,
+                &mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut size,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error()).context("failed to open a pseudo-terminal");
+        }
+        // SAFETY: openpty succeeded, so both descriptors are open and owned by nothing else.
+        let (master, slave) =
+            unsafe { (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) };
+        // Only the copies of the slave made for the standard streams are inherited.
+        set_cloexec(&master)?;
+        set_cloexec(&slave)?;
+
+        command
+            .stdin(slave.try_clone()?)
+            .stdout(slave.try_clone()?)
+            .stderr(slave);
+        // SAFETY: only async-signal-safe functions are called after the fork.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = command
+            .spawn()
+            .context("failed to spawn the terminal process")?;
+        // Closes the slave in this process, so that reading the master ends once the
+        // process exits.
+        drop(command);
+
+        let master = Async::new(File::from(master))?;
+        Ok((
+            Self {
+                master,
+                pid: child.id(),
+            },
+            child,
+        ))
+    }
+
+    pub async fn read(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut master = &self.master;
+        master.read(buffer).await
+    }
+
+    pub async fn write(&self, data: &[u8]) -> io::Result<()> {
+        let mut master = &self.master;
+        master.write_all(data).await
+    }
+
+    pub fn resize(&self, rows: u16, columns: u16) -> io::Result<()> {
+        let size = window_size(rows, columns);
+        // SAFETY: the master is open for as long as `self` is.
+        if unsafe { libc::ioctl(self.master.as_raw_fd(), libc::TIOCSWINSZ, &size) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Sends SIGHUP to the session of the process, as closing a terminal does.
+    pub fn hang_up(&self) {
+        // SAFETY: kill has no memory safety requirements.
+        unsafe {
+            libc::kill(-(self.pid as libc::pid_t), libc::SIGHUP);
+        }
+    }
+}
+
+fn window_size(rows: u16, columns: u16) -> libc::winsize {
+    libc::winsize {
+        ws_row: rows,
+        ws_col: columns,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
+
+fn set_cloexec(fd: &OwnedFd) -> io::Result<()> {
+    // SAFETY: the descriptor is open for as long as `fd` is.
+    if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
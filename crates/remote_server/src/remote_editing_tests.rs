@@ -1,16 +1,34 @@
 use crate::headless_project::HeadlessProject;
+use async_compression::futures::bufread::GzipEncoder;
 use client::{Client, UserStore};
 use clock::FakeSystemClock;
 use fs::{FakeFs, Fs as _};
+use futures::{AsyncReadExt as _, StreamExt as _};
+use git::{
+    blame::{Blame, BlameEntry},
+    repository::GitFileStatus,
+};
 use gpui::{Context, Model, TestAppContext};
-use http_client::FakeHttpClient;
-use language::LanguageRegistry;
+use http_client::{FakeHttpClient, Response};
+use language::{
+    FakeLspAdapter, Language, LanguageConfig, LanguageMatcher, LanguageRegistry,
+    RemoteServerDownload,
+};
+use lsp::LanguageServerBinary;
 use node_runtime::FakeNodeRuntime;
-use project::Project;
-use remote::SshSession;
+use project::{search::SearchQuery, Project, SearchResult};
+use remote::{protocol::PROTOCOL_VERSION, SshSession};
+use rpc::proto;
 use serde_json::json;
 use settings::SettingsStore;
-use std::{path::Path, sync::Arc};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc, Mutex,
+    },
+};
 
 fn init_logger() {
     if std::env::var("RUST_LOG").is_ok() {
@@ -46,8 +64,14 @@ async fn test_remote_editing(cx: &mut TestAppContext, server_cx: &mut TestAppCon
     );
 
     server_cx.update(HeadlessProject::init);
-    let _headless_project =
-        server_cx.new_model(|cx| HeadlessProject::new(server_ssh, fs.clone(), cx));
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
 
     let project = build_project(client_ssh, cx);
     let (worktree, _) = project
@@ -150,6 +174,553 @@ async fn test_remote_editing(cx: &mut TestAppContext, server_cx: &mut TestAppCon
     });
 }
 
+#[gpui::test]
+async fn test_remote_git_state(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            "project1": {
+                ".git": {},
+                "README.md": "# project 1",
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+    fs.set_status_for_repo_via_git_operation(
+        Path::new("/code/project1/.git"),
+        &[(Path::new("src/lib.rs"), GitFileStatus::Modified)],
+    );
+    fs.set_blame_for_repo(
+        Path::new("/code/project1/.git"),
+        vec![(
+            Path::new("src/lib.rs"),
+            Blame {
+                entries: vec![BlameEntry {
+                    sha: "1b1b1b".parse().unwrap(),
+                    range: 0..1,
+                    author: Some("the author".into()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )],
+    );
+
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    let project = build_project(client_ssh, cx);
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree("/code/project1", true, cx)
+        })
+        .await
+        .unwrap();
+
+    // The client sees the git statuses of the remote repository.
+    cx.executor().run_until_parked();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    worktree.read_with(cx, |worktree, _| {
+        assert_eq!(
+            worktree.status_for_file(Path::new("src/lib.rs")),
+            Some(GitFileStatus::Modified)
+        );
+        assert_eq!(worktree.status_for_file(Path::new("README.md")), None);
+    });
+
+    fs.set_status_for_repo_via_working_copy_change(
+        Path::new("/code/project1/.git"),
+        &[(Path::new("README.md"), GitFileStatus::Added)],
+    );
+    cx.executor().run_until_parked();
+    worktree.read_with(cx, |worktree, _| {
+        assert_eq!(
+            worktree.status_for_file(Path::new("README.md")),
+            Some(GitFileStatus::Added)
+        );
+    });
+
+    // Blame and repository information for an open buffer are computed
+    // on the remote host.
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let blame = project
+        .update(cx, |project, cx| project.blame_buffer(&buffer, None, cx))
+        .await
+        .unwrap();
+    assert_eq!(blame.entries.len(), 1);
+    assert_eq!(blame.entries[0].author.as_deref(), Some("the author"));
+
+    let git_info = project
+        .update(cx, |project, cx| {
+            project.buffer_git_info(&buffer, "origin", cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(git_info.remote_url, None);
+    assert_eq!(git_info.head_sha, None);
+}
+
+#[gpui::test]
+async fn test_remote_server_info(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    // The server reports the version it was built as, and the platform it runs on.
+    let info = client_ssh
+        .request(proto::GetRemoteServerInfo {})
+        .await
+        .unwrap();
+    assert_eq!(info.version, env!("ZED_PKG_VERSION"));
+    assert_eq!(info.os, std::env::consts::OS);
+    assert_eq!(info.arch, std::env::consts::ARCH);
+    assert!(info.total_memory >= info.used_memory);
+}
+
+#[gpui::test]
+async fn test_remote_handshake(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    // The server reports its protocol version even when it differs from the client's,
+    // so that the client can reinstall it.
+    let response = client_ssh
+        .request(proto::RemoteHandshake {
+            protocol_version: PROTOCOL_VERSION + 1,
+            client_version: "0.0.0".into(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.protocol_version, PROTOCOL_VERSION);
+    assert_eq!(response.server_version, env!("ZED_PKG_VERSION"));
+}
+
+#[gpui::test]
+async fn test_remote_project_search(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            "project1": {
+                "README.md": "# project 1",
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }",
+                    "main.rs": "fn main() { one(); }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    let project = build_project(client_ssh, cx);
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree("/code/project1", true, cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    // The files containing a match are found on the remote host, and their
+    // buffers are opened in the project to report the matches.
+    let query = SearchQuery::text(
+        "one",
+        false,
+        true,
+        false,
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let results = project.update(cx, |project, cx| project.search(query, cx));
+    let mut paths = Vec::new();
+    while let Ok(result) = results.recv().await {
+        if let SearchResult::Buffer { buffer, ranges } = result {
+            assert_eq!(ranges.len(), 1);
+            buffer.read_with(cx, |buffer, _| {
+                paths.push(buffer.file().unwrap().path().to_path_buf())
+            });
+        }
+    }
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![PathBuf::from("src/lib.rs"), PathBuf::from("src/main.rs")]
+    );
+}
+
+#[gpui::test]
+async fn test_remote_language_server(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            "project1": {
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    let project = build_project(client_ssh, cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store
+                .set_user_settings(
+                    r#"{
+                        "lsp": {
+                            "rust-analyzer": {
+                                "binary": {
+                                    "path": "/opt/bin/rust-analyzer",
+                                    "arguments": ["--log", "verbose"]
+                                },
+                                "initialization_options": { "from_settings": true }
+                            }
+                        }
+                    }"#,
+                    cx,
+                )
+                .unwrap();
+        })
+    });
+
+    let initialization_options = Arc::new(Mutex::new(None));
+    let languages = project.read_with(cx, |project, _| project.languages().clone());
+    languages.add(Arc::new(Language::new(
+        LanguageConfig {
+            name: "Rust".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["rs".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        None,
+    )));
+    let mut fake_servers = languages.register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            name: "rust-analyzer",
+            initialization_options: Some(json!({ "from_adapter": true })),
+            initializer: Some(Box::new({
+                let initialization_options = initialization_options.clone();
+                move |fake_server| {
+                    let initialization_options = initialization_options.clone();
+                    fake_server.handle_request::<lsp::request::Initialize, _, _>(
+                        move |params, _| {
+                            *initialization_options.lock().unwrap() = params.initialization_options;
+                            async move { Ok(Default::default()) }
+                        },
+                    );
+                }
+            })),
+            ..Default::default()
+        },
+    );
+
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree("/code/project1", true, cx)
+        })
+        .await
+        .unwrap();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    project.update(cx, |project, cx| project.trust_ssh_host(cx));
+    let _buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    // The server configured in the settings is run on the remote host, from the
+    // worktree's directory, with the initialization options of the settings only.
+    let mut fake_server = fake_servers.next().await.unwrap();
+    assert_eq!(fake_server.binary.path, PathBuf::from("sh"));
+    assert_eq!(
+        fake_server.binary.arguments,
+        vec![
+            OsString::from("-c"),
+            OsString::from("cd /code/project1 && exec /opt/bin/rust-analyzer --log verbose"),
+        ]
+    );
+    assert_eq!(
+        initialization_options.lock().unwrap().take(),
+        Some(json!({ "from_settings": true }))
+    );
+
+    // Files are referred to by their path on the remote host.
+    let params = fake_server
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await;
+    assert_eq!(
+        params.text_document.uri,
+        lsp::Url::from_file_path("/code/project1/src/lib.rs").unwrap()
+    );
+}
+
+#[gpui::test]
+async fn test_remote_language_server_from_path(
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            "project1": {
+                "lib.rs": "fn one() -> usize { 1 }"
+            },
+        }),
+    )
+    .await;
+
+    server_cx.update(HeadlessProject::init);
+    let _headless_project = server_cx.new_model(|cx| {
+        HeadlessProject::new(
+            server_ssh,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            cx,
+        )
+    });
+
+    let project = build_project(client_ssh, cx);
+    let languages = project.read_with(cx, |project, _| project.languages().clone());
+    languages.add(Arc::new(Language::new(
+        LanguageConfig {
+            name: "Rust".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["rs".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        None,
+    )));
+    let mut fake_servers = languages.register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            name: "fake-analyzer",
+            remote_server_command: Some(LanguageServerBinary {
+                path: "fake-analyzer-bin".into(),
+                arguments: vec!["--stdio".into()],
+                env: None,
+            }),
+            ..Default::default()
+        },
+    );
+    project.update(cx, |project, cx| project.trust_ssh_host(cx));
+
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree("/code/project1", true, cx)
+        })
+        .await
+        .unwrap();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    let _buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    // The server can't be downloaded, so the adapter's command is run from the remote PATH.
+    let fake_server = fake_servers.next().await.unwrap();
+    assert_eq!(
+        fake_server.binary.arguments,
+        vec![
+            OsString::from("-c"),
+            OsString::from("cd /code/project1 && exec fake-analyzer-bin --stdio"),
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_installing_remote_language_server(
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    let (client_ssh, server_ssh) = SshSession::fake(cx, server_cx);
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            "project1": {
+                "lib.rs": "fn one() -> usize { 1 }"
+            },
+            "project2": {
+                "lib.rs": "fn two() -> usize { 2 }"
+            },
+        }),
+    )
+    .await;
+
+    let mut gzipped_server = Vec::new();
+    GzipEncoder::new(&b"the server binary"[..])
+        .read_to_end(&mut gzipped_server)
+        .await
+        .unwrap();
+    let download_count = Arc::new(AtomicUsize::new(0));
+    let http_client = FakeHttpClient::create({
+        let download_count = download_count.clone();
+        move |request| {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://example.com/fake-analyzer.gz"
+            );
+            download_count.fetch_add(1, SeqCst);
+            let gzipped_server = gzipped_server.clone();
+            async move { Ok(Response::builder().body(gzipped_server.into()).unwrap()) }
+        }
+    });
+
+    server_cx.update(HeadlessProject::init);
+    let _headless_project =
+        server_cx.new_model(|cx| HeadlessProject::new(server_ssh, fs.clone(), http_client, cx));
+
+    let project = build_project(client_ssh, cx);
+    let languages = project.read_with(cx, |project, _| project.languages().clone());
+    languages.add(Arc::new(Language::new(
+        LanguageConfig {
+            name: "Rust".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["rs".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        None,
+    )));
+    let mut fake_servers = languages.register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            name: "fake-analyzer",
+            remote_server_download: Some(RemoteServerDownload {
+                file_name: "fake-analyzer-1.0".into(),
+                url: "https://example.com/fake-analyzer.gz".into(),
+                gzipped: true,
+            }),
+            ..Default::default()
+        },
+    );
+    project.update(cx, |project, cx| project.trust_ssh_host(cx));
+
+    // The server isn't on the remote host, so it's downloaded into the data directory of
+    // the remote server, and run from there.
+    let server_path = paths::languages_dir()
+        .join("fake-analyzer")
+        .join("fake-analyzer-1.0");
+    for worktree_path in ["/code/project1", "/code/project2"] {
+        let (worktree, _) = project
+            .update(cx, |project, cx| {
+                project.find_or_create_worktree(worktree_path, true, cx)
+            })
+            .await
+            .unwrap();
+        let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+        let _buffer = project
+            .update(cx, |project, cx| {
+                project.open_buffer((worktree_id, Path::new("lib.rs")), cx)
+            })
+            .await
+            .unwrap();
+
+        let fake_server = fake_servers.next().await.unwrap();
+        assert_eq!(
+            fake_server.binary.arguments,
+            vec![
+                OsString::from("-c"),
+                OsString::from(format!(
+                    "cd {worktree_path} && exec {}",
+                    server_path.display()
+                )),
+            ]
+        );
+    }
+
+    // It's only downloaded once.
+    assert_eq!(
+        fs.load_bytes(&server_path).await.unwrap(),
+        b"the server binary"
+    );
+    assert_eq!(download_count.load(SeqCst), 1);
+}
+
 fn build_project(ssh: Arc<SshSession>, cx: &mut TestAppContext) -> Model<Project> {
     cx.update(|cx| {
         let settings_store = SettingsStore::test(cx);
@@ -0,0 +1,379 @@
+use std::{ops::Range, sync::Arc};
+
+use editor::{actions::ReplaceInSelection, Anchor, Editor, EditorEvent};
+use gpui::{
+    div, prelude::*, AnyWindowHandle, AppContext, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, Render, SharedString, Styled, Subscription, Task, View, ViewContext,
+    VisualContext,
+};
+use project::search::SearchQuery;
+use ui::{h_flex, prelude::*, v_flex, Label};
+use workspace::{searchable::SearchableItem, ModalView};
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(ReplaceInSelectionModal::register)
+        .detach();
+}
+
+/// A prompt for a regular expression and its replacement, which replaces the matches of the
+/// expression within the selections of the editor it was opened from.
+pub struct ReplaceInSelectionModal {
+    query_editor: View<Editor>,
+    replacement_editor: View<Editor>,
+    active_editor: View<Editor>,
+    /// The selections that the replacement applies to, as they were when the prompt was opened.
+    ranges: Vec<Range<Anchor>>,
+    matches: Vec<Range<Anchor>>,
+    query_error: Option<SharedString>,
+    pending_search: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ModalView for ReplaceInSelectionModal {}
+
+impl FocusableView for ReplaceInSelectionModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.query_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ReplaceInSelectionModal {}
+
+enum ReplaceInSelectionHighlights {}
+
+impl ReplaceInSelectionModal {
+    fn register(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+        let handle = cx.view().downgrade();
+        editor
+            .register_action(move |_: &ReplaceInSelection, cx| {
+                let Some(editor) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor.read(cx).workspace() else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(cx, move |cx| Self::new(editor, cx));
+                })
+            })
+            .detach();
+    }
+
+    pub fn new(active_editor: View<Editor>, cx: &mut ViewContext<Self>) -> Self {
+        let ranges = active_editor
+            .read(cx)
+            .selections
+            .disjoint_anchor_ranges()
+            .into_iter()
+            .filter(|range| range.start != range.end)
+            .collect();
+
+        let query_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Regular expression", cx);
+            editor
+        });
+        let replacement_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Replacement, with $1 or ${name} for groups", cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe(&query_editor, Self::on_query_editor_event),
+            cx.on_release(Self::release),
+        ];
+
+        Self {
+            query_editor,
+            replacement_editor,
+            active_editor,
+            ranges,
+            matches: Vec::new(),
+            query_error: None,
+            pending_search: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn release(&mut self, window: AnyWindowHandle, cx: &mut AppContext) {
+        window
+            .update(cx, |_, cx| {
+                self.active_editor.update(cx, |editor, cx| {
+                    editor.clear_background_highlights::<ReplaceInSelectionHighlights>(cx);
+                })
+            })
+            .ok();
+    }
+
+    fn on_query_editor_event(
+        &mut self,
+        _: View<Editor>,
+        event: &EditorEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let EditorEvent::BufferEdited { .. } = event {
+            self.update_matches(cx);
+        }
+    }
+
+    /// Builds the query from the prompt, with the replacement when there is one, or returns
+    /// `None` when the expression is empty or invalid.
+    fn query(&mut self, cx: &mut ViewContext<Self>) -> Option<Arc<SearchQuery>> {
+        self.query_error = None;
+        let text = self.query_editor.read(cx).text(cx);
+        if text.is_empty() {
+            return None;
+        }
+        match SearchQuery::regex(
+            text,
+            false,
+            true,
+            false,
+            Default::default(),
+            Default::default(),
+        ) {
+            Ok(query) => {
+                let replacement = self.replacement_editor.read(cx).text(cx);
+                Some(Arc::new(query.with_replacement(replacement)))
+            }
+            Err(error) => {
+                self.query_error = Some(error.to_string().into());
+                None
+            }
+        }
+    }
+
+    /// Highlights the matches of the expression within the selections.
+    fn update_matches(&mut self, cx: &mut ViewContext<Self>) {
+        let query = self.query(cx);
+        cx.notify();
+        let Some(query) = query.filter(|_| !self.ranges.is_empty()) else {
+            self.pending_search = None;
+            self.matches.clear();
+            self.active_editor.update(cx, |editor, cx| {
+                editor.clear_background_highlights::<ReplaceInSelectionHighlights>(cx);
+            });
+            return;
+        };
+
+        let matches = self.active_editor.update(cx, |editor, cx| {
+            editor.find_matches_in_ranges(query, self.ranges.clone(), cx)
+        });
+        self.pending_search = Some(cx.spawn(|this, mut cx| async move {
+            let matches = matches.await;
+            this.update(&mut cx, |this, cx| {
+                this.active_editor.update(cx, |editor, cx| {
+                    editor.highlight_background::<ReplaceInSelectionHighlights>(
+                        &matches,
+                        |colors| colors.search_match_background,
+                        cx,
+                    );
+                });
+                this.matches = matches;
+                this.pending_search = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        self.toggle_focused_editor(cx);
+    }
+
+    fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
+        self.toggle_focused_editor(cx);
+    }
+
+    fn toggle_focused_editor(&mut self, cx: &mut ViewContext<Self>) {
+        if self.query_editor.focus_handle(cx).is_focused(cx) {
+            self.replacement_editor.focus_handle(cx).focus(cx);
+        } else {
+            self.query_editor.focus_handle(cx).focus(cx);
+        }
+    }
+
+    /// Replaces the matches within the selections, searching for them again so that they are up
+    /// to date with the expression.
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let Some(query) = self.query(cx).filter(|_| !self.ranges.is_empty()) else {
+            cx.notify();
+            return;
+        };
+
+        let matches = self.active_editor.update(cx, |editor, cx| {
+            editor.find_matches_in_ranges(query.clone(), self.ranges.clone(), cx)
+        });
+        self.pending_search = Some(cx.spawn(|this, mut cx| async move {
+            let matches = matches.await;
+            this.update(&mut cx, |this, cx| {
+                this.active_editor.update(cx, |editor, cx| {
+                    editor.clear_background_highlights::<ReplaceInSelectionHighlights>(cx);
+                    editor.replace_all(&mut matches.iter(), &query, cx);
+                    editor.focus(cx);
+                });
+                cx.emit(DismissEvent);
+            })
+            .ok();
+        }));
+    }
+}
+
+impl Render for ReplaceInSelectionModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let (help_text, color): (SharedString, Color) = if self.ranges.is_empty() {
+            ("Select the text to replace in first".into(), Color::Warning)
+        } else if let Some(error) = self.query_error.clone() {
+            (error, Color::Error)
+        } else {
+            let count = self.matches.len();
+            let noun = if count == 1 { "match" } else { "matches" };
+            (
+                format!("{count} {noun} in the selection").into(),
+                Color::Muted,
+            )
+        };
+
+        let input = |editor: View<Editor>| {
+            div()
+                .px_1()
+                .py_0p5()
+                .child(div().px_1().py_0p5().child(editor))
+        };
+        div()
+            .elevation_2(cx)
+            .key_context("ReplaceInSelection")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .w_96()
+            .child(
+                v_flex()
+                    .px_1()
+                    .pt_0p5()
+                    .gap_px()
+                    .child(input(self.query_editor.clone()))
+                    .child(input(self.replacement_editor.clone()))
+                    .child(
+                        div()
+                            .h_px()
+                            .w_full()
+                            .bg(cx.theme().colors().element_background),
+                    )
+                    .child(
+                        h_flex()
+                            .justify_between()
+                            .px_2()
+                            .py_1()
+                            .child(Label::new(help_text).color(color)),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use workspace::Workspace;
+
+    #[gpui::test]
+    async fn test_replace_in_selection(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "a.txt": "let a1 = b1;\nlet a2 = b2;\nlet a3 = b3;\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path((worktree_id, "a.txt"), None, true, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |selections| {
+                selections.select_ranges([13..38]);
+            })
+        });
+
+        let modal = workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, |cx| ReplaceInSelectionModal::new(editor.clone(), cx));
+            workspace
+                .active_modal::<ReplaceInSelectionModal>(cx)
+                .unwrap()
+        });
+        modal.update(cx, |modal, cx| {
+            modal
+                .query_editor
+                .update(cx, |editor, cx| editor.set_text(r"(\w)(\d)", cx));
+        });
+        cx.run_until_parked();
+        modal.update(cx, |modal, _| assert_eq!(modal.matches.len(), 4));
+
+        modal.update(cx, |modal, cx| {
+            modal
+                .query_editor
+                .update(cx, |editor, cx| editor.set_text(r"(\w", cx));
+        });
+        cx.run_until_parked();
+        modal.update(cx, |modal, _| {
+            assert!(modal.query_error.is_some());
+            assert!(modal.matches.is_empty());
+        });
+
+        modal.update(cx, |modal, cx| {
+            modal
+                .query_editor
+                .update(cx, |editor, cx| editor.set_text(r"(?P<letter>\w)(\d)", cx));
+            modal
+                .replacement_editor
+                .update(cx, |editor, cx| editor.set_text("${letter}_$2", cx));
+            modal.confirm(&menu::Confirm, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "let a1 = b1;\nlet a_2 = b_2;\nlet a_3 = b_3;\n"
+        );
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace
+                .active_modal::<ReplaceInSelectionModal>(cx)
+                .is_none())
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            super::init(cx);
+        });
+    }
+}
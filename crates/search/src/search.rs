@@ -10,12 +10,14 @@ use workspace::{Toast, Workspace};
 
 pub mod buffer_search;
 pub mod project_search;
+pub mod replace_in_selection;
 pub(crate) mod search_bar;
 
 pub fn init(cx: &mut AppContext) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    replace_in_selection::init(cx);
 }
 
 actions!(
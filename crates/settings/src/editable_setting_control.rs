@@ -1,7 +1,8 @@
 use fs::Fs;
 use gpui::{AppContext, RenderOnce, SharedString};
+use serde_json::Value;
 
-use crate::{update_settings_file, Settings};
+use crate::{settings_store::PROFILES_KEY, Settings, SettingsStore};
 
 /// A UI control that can be used to edit a setting.
 pub trait EditableSettingControl: RenderOnce {
@@ -27,11 +28,154 @@ pub trait EditableSettingControl: RenderOnce {
     );
 
     /// Writes the given setting value to the settings files.
+    ///
+    /// When a settings profile is active, the value is written to that profile.
     fn write(value: Self::Value, cx: &AppContext) {
-        let fs = <dyn Fs>::global(cx);
+        write_setting::<Self::Settings>(move |settings, cx| Self::apply(settings, value, cx), cx);
+    }
+
+    /// Returns whether the setting is set in the user settings file, instead of using
+    /// its default value.
+    fn is_modified(cx: &AppContext) -> bool
+    where
+        Self: Sized,
+    {
+        is_setting_modified::<Self::Settings>(
+            |settings, cx| Self::apply(settings, Self::read(cx), cx),
+            cx,
+        )
+    }
+
+    /// Removes the setting from the user settings file, so that its default value
+    /// applies again.
+    fn reset(cx: &AppContext)
+    where
+        Self: Sized,
+    {
+        reset_setting::<Self::Settings>(
+            |settings, cx| Self::apply(settings, Self::read(cx), cx),
+            cx,
+        )
+    }
+}
+
+/// Writes a setting to the settings files, within the active profile if there is one.
+///
+/// This is what [`EditableSettingControl::write`] does, for controls that apply their
+/// value with a closure.
+pub fn write_setting<S: Settings>(
+    apply: impl 'static + Send + FnOnce(&mut S::FileContent, &AppContext),
+    cx: &AppContext,
+) {
+    let fs = <dyn Fs>::global(cx);
+
+    SettingsStore::global(cx).update_active_profile_in_settings_file::<S>(fs, apply);
+}
+
+/// Applies a setting to the user settings, as [`write_setting`] does, but without going
+/// through the settings file.
+#[cfg(any(test, feature = "test-support"))]
+pub fn apply_test_setting<S: Settings>(
+    apply: impl FnOnce(&mut S::FileContent, &AppContext),
+    cx: &mut AppContext,
+) {
+    let new_text = {
+        let cx: &AppContext = cx;
+        let store = SettingsStore::global(cx);
+        let old_text = serde_json::to_string(store.raw_user_settings()).unwrap();
+        store.new_text_for_update::<S>(old_text, |settings| apply(settings, cx))
+    };
+    cx.update_global::<SettingsStore, _>(|store, cx| {
+        store.set_user_settings(&new_text, cx).unwrap();
+    });
+}
+
+/// Applies the value of a control to the user settings, as
+/// [`EditableSettingControl::write`] does, but without going through the settings file.
+#[cfg(any(test, feature = "test-support"))]
+pub fn write_test_setting<C: EditableSettingControl>(value: C::Value, cx: &mut AppContext) {
+    apply_test_setting::<C::Settings>(|settings, cx| C::apply(settings, value, cx), cx);
+}
+
+/// Returns whether the keys written by `apply` are set in the user settings file.
+pub fn is_setting_modified<S: Settings>(
+    apply: impl FnOnce(&mut S::FileContent, &AppContext),
+    cx: &AppContext,
+) -> bool {
+    let user_settings = SettingsStore::global(cx).raw_user_settings();
+    key_paths::<S>(apply, cx).iter().any(|key_path| {
+        key_path
+            .iter()
+            .try_fold(user_settings, |value, key| value.get(key))
+            .is_some()
+    })
+}
+
+/// Removes the keys written by `apply` from the user settings file, so that their
+/// default values apply again.
+pub fn reset_setting<S: Settings>(
+    apply: impl FnOnce(&mut S::FileContent, &AppContext),
+    cx: &AppContext,
+) {
+    let fs = <dyn Fs>::global(cx);
+
+    SettingsStore::global(cx).remove_keys_from_settings_file(fs, key_paths::<S>(apply, cx));
+}
+
+/// Returns the paths of the keys in the settings file that `apply` writes, within the
+/// active profile if there is one.
+fn key_paths<S: Settings>(
+    apply: impl FnOnce(&mut S::FileContent, &AppContext),
+    cx: &AppContext,
+) -> Vec<Vec<String>> {
+    let default_content = S::FileContent::default();
+    let mut content = default_content.clone();
+    apply(&mut content, cx);
+
+    let (Ok(default_content), Ok(content)) = (
+        serde_json::to_value(default_content),
+        serde_json::to_value(content),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut key_path = Vec::new();
+    if let Some(profile) = SettingsStore::global(cx).active_profile() {
+        key_path.push(PROFILES_KEY.to_string());
+        key_path.push(profile.to_string());
+    }
+    key_path.extend(S::KEY.map(String::from));
+    let mut key_paths = Vec::new();
+    collect_changed_key_paths(&default_content, &content, &mut key_path, &mut key_paths);
+    key_paths
+}
 
-        update_settings_file::<Self::Settings>(fs, cx, move |settings, cx| {
-            Self::apply(settings, value, cx);
-        });
+fn collect_changed_key_paths(
+    old_value: &Value,
+    new_value: &Value,
+    key_path: &mut Vec<String>,
+    key_paths: &mut Vec<Vec<String>>,
+) {
+    match (old_value, new_value) {
+        (Value::Object(old_object), Value::Object(new_object)) => {
+            for (key, new_sub_value) in new_object {
+                key_path.push(key.clone());
+                let old_sub_value = old_object.get(key).unwrap_or(&Value::Null);
+                collect_changed_key_paths(old_sub_value, new_sub_value, key_path, key_paths);
+                key_path.pop();
+            }
+        }
+        (Value::Null, Value::Object(new_object)) => {
+            for (key, new_sub_value) in new_object {
+                key_path.push(key.clone());
+                collect_changed_key_paths(&Value::Null, new_sub_value, key_path, key_paths);
+                key_path.pop();
+            }
+        }
+        _ => {
+            if old_value != new_value && !new_value.is_null() {
+                key_paths.push(key_path.clone());
+            }
+        }
     }
 }
@@ -0,0 +1,253 @@
+use std::{num::NonZeroU32, path::Path};
+
+use globset::{GlobBuilder, GlobMatcher};
+use util::ResultExt as _;
+
+/// The file name of [EditorConfig](https://editorconfig.org) files.
+pub const EDITORCONFIG_FILE_NAME: &str = ".editorconfig";
+
+/// A parsed `.editorconfig` file.
+#[derive(Debug, Default, Clone)]
+pub struct Editorconfig {
+    /// Whether the file is the topmost one, ignoring the files of the parent directories.
+    pub root: bool,
+    sections: Vec<EditorconfigSection>,
+}
+
+/// The properties applying to the files matched by a glob.
+#[derive(Debug, Clone)]
+struct EditorconfigSection {
+    glob: GlobMatcher,
+    properties: Vec<(String, String)>,
+}
+
+/// The properties of the `.editorconfig` files applying to a file.
+///
+/// Properties that aren't set, set to `unset` or set to an invalid value are `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EditorconfigProperties {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_size: Option<IndentWidth>,
+    pub tab_width: Option<NonZeroU32>,
+    pub end_of_line: Option<EndOfLine>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub insert_final_newline: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+/// The number of columns used for each indentation level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentWidth {
+    Columns(NonZeroU32),
+    /// The width of a tab, as set by `tab_width`.
+    Tab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndOfLine {
+    Lf,
+    Crlf,
+    Cr,
+}
+
+impl Editorconfig {
+    /// Parses the content of an `.editorconfig` file.
+    ///
+    /// Like other EditorConfig implementations, this is lenient: lines that can't be parsed and
+    /// sections with an invalid glob are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut editorconfig = Self::default();
+        let mut section: Option<EditorconfigSection> = None;
+        let mut skipping_section = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(glob) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                editorconfig.sections.extend(section.take());
+                section = section_glob(glob)
+                    .log_err()
+                    .map(|glob| EditorconfigSection {
+                        glob,
+                        properties: Vec::new(),
+                    });
+                skipping_section = section.is_none();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_string();
+            match section.as_mut() {
+                Some(section) => section.properties.push((key, value)),
+                // Only `root` is allowed before the first section.
+                None if !skipping_section && key == "root" => {
+                    editorconfig.root = value.eq_ignore_ascii_case("true");
+                }
+                None => {}
+            }
+        }
+        editorconfig.sections.extend(section);
+
+        editorconfig
+    }
+
+    /// Applies the properties of the sections matching the given path, relative to the
+    /// directory of the file, in the order they're written.
+    pub fn apply_to(&self, path: &Path, properties: &mut EditorconfigProperties) {
+        for section in &self.sections {
+            if section.glob.is_match(path) {
+                for (key, value) in &section.properties {
+                    properties.set(key, value);
+                }
+            }
+        }
+    }
+}
+
+/// Converts the glob of a section to match paths relative to the directory of the file.
+///
+/// Globs without a slash match files in any subdirectory, while the other ones are relative to
+/// the directory of the file.
+fn section_glob(glob: &str) -> anyhow::Result<GlobMatcher> {
+    let glob = if glob.contains('/') {
+        glob.trim_start_matches('/').to_string()
+    } else {
+        format!("**/{glob}")
+    };
+    Ok(GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher())
+}
+
+impl EditorconfigProperties {
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.to_lowercase();
+        let value = value.as_str();
+        match key {
+            "indent_style" => {
+                self.indent_style = match value {
+                    "tab" => Some(IndentStyle::Tab),
+                    "space" => Some(IndentStyle::Space),
+                    _ => None,
+                }
+            }
+            "indent_size" => {
+                self.indent_size = match value {
+                    "tab" => Some(IndentWidth::Tab),
+                    _ => value.parse().ok().map(IndentWidth::Columns),
+                }
+            }
+            "tab_width" => self.tab_width = value.parse().ok(),
+            "end_of_line" => {
+                self.end_of_line = match value {
+                    "lf" => Some(EndOfLine::Lf),
+                    "crlf" => Some(EndOfLine::Crlf),
+                    "cr" => Some(EndOfLine::Cr),
+                    _ => None,
+                }
+            }
+            "trim_trailing_whitespace" => self.trim_trailing_whitespace = value.parse().ok(),
+            "insert_final_newline" => self.insert_final_newline = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    /// Returns whether indentation uses tabs, or `None` when it isn't set.
+    pub fn hard_tabs(&self) -> Option<bool> {
+        self.indent_style.map(|style| style == IndentStyle::Tab)
+    }
+
+    /// Returns the number of columns of a tab, following the EditorConfig defaults where
+    /// `tab_width` falls back to `indent_size` and vice versa.
+    pub fn tab_size(&self) -> Option<NonZeroU32> {
+        let indent_size = match self.indent_size {
+            Some(IndentWidth::Columns(columns)) => Some(columns),
+            Some(IndentWidth::Tab) | None => None,
+        };
+        if self.hard_tabs() == Some(true) {
+            self.tab_width.or(indent_size)
+        } else {
+            indent_size.or(self.tab_width)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties_for(editorconfig: &Editorconfig, path: &str) -> EditorconfigProperties {
+        let mut properties = EditorconfigProperties::default();
+        editorconfig.apply_to(Path::new(path), &mut properties);
+        properties
+    }
+
+    #[test]
+    fn test_parse_editorconfig() {
+        let editorconfig = Editorconfig::parse(
+            r#"
+            # top-most EditorConfig file
+            root = true
+
+            [*]
+            end_of_line = lf
+            insert_final_newline = true
+            indent_style = space
+            indent_size = 4
+
+            ; Makefiles need tabs
+            [Makefile]
+            indent_style = tab
+            tab_width = 8
+
+            [{*.js,*.json}]
+            indent_size = 2
+            trim_trailing_whitespace = TRUE
+
+            [docs/*.md]
+            trim_trailing_whitespace = false
+            insert_final_newline = unset
+            "#,
+        );
+        assert!(editorconfig.root);
+
+        let rust = properties_for(&editorconfig, "src/main.rs");
+        assert_eq!(rust.hard_tabs(), Some(false));
+        assert_eq!(rust.tab_size(), NonZeroU32::new(4));
+        assert_eq!(rust.end_of_line, Some(EndOfLine::Lf));
+        assert_eq!(rust.insert_final_newline, Some(true));
+        assert_eq!(rust.trim_trailing_whitespace, None);
+
+        let makefile = properties_for(&editorconfig, "build/Makefile");
+        assert_eq!(makefile.hard_tabs(), Some(true));
+        assert_eq!(makefile.tab_size(), NonZeroU32::new(8));
+
+        let json = properties_for(&editorconfig, "package.json");
+        assert_eq!(json.tab_size(), NonZeroU32::new(2));
+        assert_eq!(json.trim_trailing_whitespace, Some(true));
+
+        let docs = properties_for(&editorconfig, "docs/README.md");
+        assert_eq!(docs.trim_trailing_whitespace, Some(false));
+        assert_eq!(docs.insert_final_newline, None);
+
+        // Globs with a slash are relative to the directory of the file.
+        let nested_docs = properties_for(&editorconfig, "crates/docs/README.md");
+        assert_eq!(nested_docs.trim_trailing_whitespace, None);
+        assert_eq!(nested_docs.insert_final_newline, Some(true));
+    }
+}
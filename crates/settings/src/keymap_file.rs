@@ -1,6 +1,7 @@
 use crate::{settings_store::parse_json_with_comments, SettingsAssets};
 use anyhow::{anyhow, Context, Result};
 use collections::BTreeMap;
+use fs::Fs;
 use gpui::{Action, AppContext, KeyBinding, SharedString};
 use schemars::{
     gen::{SchemaGenerator, SchemaSettings},
@@ -9,6 +10,7 @@ use schemars::{
 };
 use serde::Deserialize;
 use serde_json::Value;
+use std::sync::Arc;
 use util::{asset_str, ResultExt};
 
 #[derive(Debug, Deserialize, Default, Clone, JsonSchema)]
@@ -99,6 +101,18 @@ impl KeymapFile {
         Ok(())
     }
 
+    /// Appends the given blocks to the user's keymap file, preserving its existing contents.
+    pub async fn append_to_user_keymap(fs: &Arc<dyn Fs>, blocks: &[Value]) -> Result<()> {
+        let keymap_path = paths::keymap_file();
+        let mut keymap = if fs.is_file(keymap_path).await {
+            fs.load(keymap_path).await?
+        } else {
+            crate::initial_keymap_content().to_string()
+        };
+        append_keymap_blocks(&mut keymap, blocks)?;
+        fs.atomic_write(keymap_path.clone(), keymap).await
+    }
+
     pub fn generate_json_schema(action_names: &[SharedString]) -> serde_json::Value {
         let mut root_schema = SchemaSettings::draft07()
             .with(|settings| settings.option_add_null_type = false)
@@ -144,9 +158,83 @@ fn no_action() -> Box<dyn gpui::Action> {
     gpui::NoAction.boxed_clone()
 }
 
+/// Appends the given blocks to the keymap array, preserving the existing contents of
+/// the keymap file, including its comments.
+fn append_keymap_blocks(keymap: &mut String, blocks: &[Value]) -> Result<()> {
+    let existing_blocks = parse_json_with_comments::<Vec<Value>>(keymap)?;
+    let Some(end) = keymap.rfind(']') else {
+        anyhow::bail!("keymap file is not an array");
+    };
+
+    // Insert the blocks right after the last one, before any comment following it.
+    let insertion_point = end_of_last_token(&keymap[..end]);
+    let needs_separator = !existing_blocks.is_empty() && !keymap[..insertion_point].ends_with(',');
+
+    let mut new_text = String::new();
+    for (ix, block) in blocks.iter().enumerate() {
+        if ix > 0 || needs_separator {
+            new_text.push(',');
+        }
+        for line in serde_json::to_string_pretty(block)?.lines() {
+            new_text.push_str("\n  ");
+            new_text.push_str(line);
+        }
+    }
+    if !keymap[insertion_point..end].contains('\n') {
+        new_text.push('\n');
+    }
+
+    keymap.insert_str(insertion_point, &new_text);
+    Ok(())
+}
+
+/// Returns the offset following the last character of the JSON text that isn't whitespace or
+/// part of a comment.
+fn end_of_last_token(text: &str) -> usize {
+    let mut end = 0;
+    let mut in_string = false;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '/' && chars.next_if(|(_, c)| *c == '/').is_some() {
+            for (_, c) in chars.by_ref() {
+                if c == '\n' {
+                    break;
+                }
+            }
+            continue;
+        } else if c == '/' && chars.next_if(|(_, c)| *c == '*').is_some() {
+            let mut previous = None;
+            for (_, c) in chars.by_ref() {
+                if previous == Some('*') && c == '/' {
+                    break;
+                }
+                previous = Some(c);
+            }
+            continue;
+        } else if c.is_whitespace() {
+            continue;
+        } else if c == '"' {
+            in_string = true;
+        }
+        end = chars.peek().map_or(text.len(), |(ix, _)| *ix);
+    }
+    end
+}
+
 #[cfg(test)]
 mod tests {
     use crate::KeymapFile;
+    use serde_json::json;
+
+    use super::append_keymap_blocks;
 
     #[test]
     fn can_deserialize_keymap_with_trailing_comma() {
@@ -163,4 +251,85 @@ mod tests {
         };
         KeymapFile::parse(json).unwrap();
     }
+
+    #[test]
+    fn append_keymap_blocks_preserves_existing_content() {
+        let mut keymap = indoc::indoc! {"
+            [
+              // My bindings
+              {
+                \"bindings\": { \"ctrl-a\": \"editor::SelectAll\" }
+              }
+            ]
+        "}
+        .to_string();
+        append_keymap_blocks(
+            &mut keymap,
+            &[json!({ "context": "Editor", "bindings": { "ctrl-b": "editor::Backspace" } })],
+        )
+        .unwrap();
+
+        assert!(keymap.contains("// My bindings"));
+        let blocks = crate::parse_json_with_comments::<Vec<serde_json::Value>>(&keymap).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1]["bindings"]["ctrl-b"], json!("editor::Backspace"));
+
+        // Blocks are appended after trailing commas and before trailing comments.
+        let mut keymap = indoc::indoc! {"
+            [
+              {
+                \"bindings\": { \"ctrl-a\": \"editor::SelectAll\" },
+              },
+              // More bindings, \"soon\" /* maybe */
+              /* { \"bindings\": {} } */
+            ]
+        "}
+        .to_string();
+        append_keymap_blocks(
+            &mut keymap,
+            &[
+                json!({ "bindings": { "ctrl-b": "editor::Backspace" } }),
+                json!({ "bindings": { "ctrl-c": "editor::Copy" } }),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            keymap,
+            indoc::indoc! {"
+                [
+                  {
+                    \"bindings\": { \"ctrl-a\": \"editor::SelectAll\" },
+                  },
+                  {
+                    \"bindings\": {
+                      \"ctrl-b\": \"editor::Backspace\"
+                    }
+                  },
+                  {
+                    \"bindings\": {
+                      \"ctrl-c\": \"editor::Copy\"
+                    }
+                  }
+                  // More bindings, \"soon\" /* maybe */
+                  /* { \"bindings\": {} } */
+                ]
+            "}
+        );
+
+        let mut keymap = "[{ \"bindings\": {} } // Comment\n]".to_string();
+        append_keymap_blocks(&mut keymap, &[json!({ "bindings": {} })]).unwrap();
+        assert_eq!(
+            keymap,
+            "[{ \"bindings\": {} },\n  {\n    \"bindings\": {}\n  } // Comment\n]"
+        );
+
+        let mut empty_keymap = "[]".to_string();
+        append_keymap_blocks(&mut empty_keymap, &[json!({ "bindings": {} })]).unwrap();
+        assert_eq!(
+            crate::parse_json_with_comments::<Vec<serde_json::Value>>(&empty_keymap)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
 }
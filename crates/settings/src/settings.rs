@@ -1,4 +1,5 @@
 mod editable_setting_control;
+mod editorconfig;
 mod keymap_file;
 mod settings_file;
 mod settings_store;
@@ -9,10 +10,15 @@ use std::{borrow::Cow, str};
 use util::asset_str;
 
 pub use editable_setting_control::*;
+pub use editorconfig::{
+    Editorconfig, EditorconfigProperties, EndOfLine, IndentStyle, IndentWidth,
+    EDITORCONFIG_FILE_NAME,
+};
 pub use keymap_file::KeymapFile;
 pub use settings_file::*;
 pub use settings_store::{
-    Settings, SettingsJsonSchemaParams, SettingsLocation, SettingsSources, SettingsStore,
+    parse_json_with_comments, Settings, SettingsJsonSchemaParams, SettingsLocation,
+    SettingsSources, SettingsStore,
 };
 
 #[derive(RustEmbed)]
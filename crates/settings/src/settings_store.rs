@@ -4,6 +4,7 @@ use fs::Fs;
 use futures::{channel::mpsc, future::LocalBoxFuture, FutureExt, StreamExt};
 use gpui::{AppContext, AsyncAppContext, BorrowAppContext, Global, Task, UpdateGlobal};
 use lazy_static::lazy_static;
+use parking_lot::RwLock;
 use schemars::{gen::SchemaGenerator, schema::RootSchema, JsonSchema};
 use serde::{de::DeserializeOwned, Deserialize as _, Serialize};
 use smallvec::SmallVec;
@@ -17,6 +18,11 @@ use std::{
 };
 use util::{merge_non_null_json_value_into, RangeExt, ResultExt as _};
 
+use crate::{Editorconfig, EditorconfigProperties};
+
+/// The key in the user settings under which the settings profiles are defined.
+pub const PROFILES_KEY: &str = "profiles";
+
 /// A value that can be defined as a user setting.
 ///
 /// Settings can be loaded from a combination of multiple JSON files.
@@ -99,6 +105,8 @@ pub struct SettingsSources<'a, T> {
     pub user: Option<&'a T>,
     /// The user settings for the current release channel.
     pub release_channel: Option<&'a T>,
+    /// The user settings for the active profile.
+    pub profile: Option<&'a T>,
     /// The project settings, ordered from least specific to most specific.
     pub project: &'a [&'a T],
 }
@@ -115,6 +123,7 @@ impl<'a, T: Serialize> SettingsSources<'a, T> {
             .into_iter()
             .chain(self.user)
             .chain(self.release_channel)
+            .chain(self.profile)
             .chain(self.project.iter().copied())
     }
 
@@ -159,6 +168,15 @@ pub struct SettingsStore {
     raw_user_settings: serde_json::Value,
     raw_extension_settings: serde_json::Value,
     raw_local_settings: BTreeMap<(usize, Arc<Path>), serde_json::Value>,
+    /// Settings applying to whole worktrees, below their local settings files.
+    raw_host_settings: HashMap<usize, serde_json::Value>,
+    /// The `.editorconfig` files of each worktree, by directory.
+    editorconfigs: HashMap<usize, BTreeMap<Arc<Path>, Editorconfig>>,
+    /// The properties of the `.editorconfig` files applying to each file, resolved the first time
+    /// they're queried since the `.editorconfig` files of the worktree changed.
+    editorconfig_properties:
+        RwLock<HashMap<usize, HashMap<Arc<Path>, Option<EditorconfigProperties>>>>,
+    active_profile: Option<String>,
     tab_size_callback: Option<(
         TypeId,
         Box<dyn Fn(&dyn Any) -> Option<usize> + Send + Sync + 'static>,
@@ -189,6 +207,7 @@ trait AnySettingValue: 'static + Send + Sync {
     fn value_for_path(&self, path: Option<SettingsLocation>) -> &dyn Any;
     fn set_global_value(&mut self, value: Box<dyn Any>);
     fn set_local_value(&mut self, root_id: usize, path: Arc<Path>, value: Box<dyn Any>);
+    fn clear_local_values(&mut self, root_id: usize);
     fn json_schema(
         &self,
         generator: &mut SchemaGenerator,
@@ -208,6 +227,10 @@ impl SettingsStore {
             raw_user_settings: serde_json::json!({}),
             raw_extension_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
+            raw_host_settings: Default::default(),
+            editorconfigs: Default::default(),
+            editorconfig_properties: Default::default(),
+            active_profile: None,
             tab_size_callback: Default::default(),
             setting_file_updates_tx,
             _setting_file_updates: cx.spawn(|cx| async move {
@@ -256,6 +279,15 @@ impl SettingsStore {
                     .log_err();
             }
 
+            let mut profile_value = None;
+            if let Some(profile_settings) =
+                active_profile_settings(&self.raw_user_settings, self.active_profile.as_deref())
+            {
+                profile_value = setting_value
+                    .deserialize_setting(profile_settings)
+                    .log_err();
+            }
+
             let extension_value = setting_value
                 .deserialize_setting(&self.raw_extension_settings)
                 .log_err();
@@ -265,6 +297,7 @@ impl SettingsStore {
                     SettingsSources {
                         default: &default_settings,
                         release_channel: release_channel_value.as_ref(),
+                        profile: profile_value.as_ref(),
                         extensions: extension_value.as_ref(),
                         user: user_value.as_ref(),
                         project: &[],
@@ -310,6 +343,35 @@ impl SettingsStore {
         &self.raw_user_settings
     }
 
+    /// Returns the name of the active settings profile, if any.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Returns the names of the settings profiles defined in the user settings.
+    pub fn profile_names(&self) -> Vec<String> {
+        self.raw_user_settings
+            .get(PROFILES_KEY)
+            .and_then(|profiles| profiles.as_object())
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Activates the settings profile with the given name, overlaying its settings on top
+    /// of the user settings. Passing `None` goes back to the user settings alone.
+    pub fn set_active_profile(
+        &mut self,
+        profile: Option<String>,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        if self.active_profile == profile {
+            return Ok(());
+        }
+        self.active_profile = profile;
+        self.recompute_values(None, cx)?;
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut AppContext) -> Self {
         let mut this = Self::new(cx);
@@ -352,41 +414,83 @@ impl SettingsStore {
         &self,
         fs: Arc<dyn Fs>,
         update: impl 'static + Send + FnOnce(&mut T::FileContent, &AppContext),
+    ) {
+        self.update_profile_in_settings_file::<T>(fs, None, update)
+    }
+
+    /// Updates the value of a setting in the active profile, or in the user's global
+    /// configuration when no profile is active.
+    pub fn update_active_profile_in_settings_file<T: Settings>(
+        &self,
+        fs: Arc<dyn Fs>,
+        update: impl 'static + Send + FnOnce(&mut T::FileContent, &AppContext),
+    ) {
+        self.update_profile_in_settings_file::<T>(fs, self.active_profile.clone(), update)
+    }
+
+    fn update_profile_in_settings_file<T: Settings>(
+        &self,
+        fs: Arc<dyn Fs>,
+        profile: Option<String>,
+        update: impl 'static + Send + FnOnce(&mut T::FileContent, &AppContext),
     ) {
         self.setting_file_updates_tx
             .unbounded_send(Box::new(move |cx: AsyncAppContext| {
                 async move {
                     let old_text = Self::load_settings(&fs).await?;
                     let new_text = cx.read_global(|store: &SettingsStore, cx| {
-                        store.new_text_for_update::<T>(old_text, |content| update(content, cx))
+                        let edits = store.edits_for_profile_update::<T>(
+                            &old_text,
+                            profile.as_deref(),
+                            |content| update(content, cx),
+                        );
+                        apply_edits(old_text, edits)
                     })?;
-                    let initial_path = paths::settings_file().as_path();
-                    if fs.is_file(initial_path).await {
-                        let resolved_path =
-                            fs.canonicalize(initial_path).await.with_context(|| {
-                                format!("Failed to canonicalize settings path {:?}", initial_path)
-                            })?;
-
-                        fs.atomic_write(resolved_path.clone(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", resolved_path)
-                            })?;
-                    } else {
-                        fs.atomic_write(initial_path.to_path_buf(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", initial_path)
-                            })?;
-                    }
+                    Self::write_settings(&fs, new_text).await
+                }
+                .boxed_local()
+            }))
+            .ok();
+    }
 
-                    anyhow::Ok(())
+    /// Removes the keys at the given paths from the settings file, so that the default
+    /// values of their settings apply again.
+    pub fn remove_keys_from_settings_file(&self, fs: Arc<dyn Fs>, key_paths: Vec<Vec<String>>) {
+        self.setting_file_updates_tx
+            .unbounded_send(Box::new(move |_: AsyncAppContext| {
+                async move {
+                    let mut text = Self::load_settings(&fs).await?;
+                    for key_path in &key_paths {
+                        if let Some(range) = key_removal_range_in_json_text(&text, key_path) {
+                            text.replace_range(range, "");
+                        }
+                    }
+                    Self::write_settings(&fs, text).await
                 }
                 .boxed_local()
             }))
             .ok();
     }
 
+    async fn write_settings(fs: &Arc<dyn Fs>, text: String) -> Result<()> {
+        let initial_path = paths::settings_file().as_path();
+        if fs.is_file(initial_path).await {
+            let resolved_path = fs.canonicalize(initial_path).await.with_context(|| {
+                format!("Failed to canonicalize settings path {:?}", initial_path)
+            })?;
+
+            fs.atomic_write(resolved_path.clone(), text)
+                .await
+                .with_context(|| format!("Failed to write settings to file {:?}", resolved_path))?;
+        } else {
+            fs.atomic_write(initial_path.to_path_buf(), text)
+                .await
+                .with_context(|| format!("Failed to write settings to file {:?}", initial_path))?;
+        }
+
+        Ok(())
+    }
+
     /// Updates the value of a setting in a JSON file, returning the new text
     /// for that JSON file.
     pub fn new_text_for_update<T: Settings>(
@@ -395,11 +499,7 @@ impl SettingsStore {
         update: impl FnOnce(&mut T::FileContent),
     ) -> String {
         let edits = self.edits_for_update::<T>(&old_text, update);
-        let mut new_text = old_text;
-        for (range, replacement) in edits.into_iter() {
-            new_text.replace_range(range, &replacement);
-        }
-        new_text
+        apply_edits(old_text, edits)
     }
 
     /// Updates the value of a setting in a JSON file, returning a list
@@ -408,6 +508,17 @@ impl SettingsStore {
         &self,
         text: &str,
         update: impl FnOnce(&mut T::FileContent),
+    ) -> Vec<(Range<usize>, String)> {
+        self.edits_for_profile_update::<T>(text, None, update)
+    }
+
+    /// Like [`Self::edits_for_update`], but updates the settings of the given profile
+    /// instead of the top-level settings.
+    fn edits_for_profile_update<T: Settings>(
+        &self,
+        text: &str,
+        profile: Option<&str>,
+        update: impl FnOnce(&mut T::FileContent),
     ) -> Vec<(Range<usize>, String)> {
         let setting_type_id = TypeId::of::<T>();
 
@@ -415,7 +526,13 @@ impl SettingsStore {
             .setting_values
             .get(&setting_type_id)
             .unwrap_or_else(|| panic!("unregistered setting type {}", type_name::<T>()));
-        let raw_settings = parse_json_with_comments::<serde_json::Value>(text).unwrap_or_default();
+        let mut raw_settings =
+            parse_json_with_comments::<serde_json::Value>(text).unwrap_or_default();
+        if profile.is_some() {
+            raw_settings = active_profile_settings(&raw_settings, profile)
+                .cloned()
+                .unwrap_or_else(|| serde_json::json!({}));
+        }
         let old_content = match setting.deserialize_setting(&raw_settings) {
             Ok(content) => content.0.downcast::<T::FileContent>().unwrap(),
             Err(_) => Box::<<T as Settings>::FileContent>::default(),
@@ -427,6 +544,10 @@ impl SettingsStore {
         let new_value = serde_json::to_value(new_content).unwrap();
 
         let mut key_path = Vec::new();
+        if let Some(profile) = profile {
+            key_path.push(PROFILES_KEY);
+            key_path.push(profile);
+        }
         if let Some(key) = T::KEY {
             key_path.push(key);
         }
@@ -543,9 +664,38 @@ impl SettingsStore {
         }
     }
 
+    /// Add or remove, via a JSON string, the settings applying to a whole worktree, such as the
+    /// settings for the host of a remote project.
+    ///
+    /// These take precedence over the user settings, but the settings files of the worktree take
+    /// precedence over them.
+    pub fn set_host_settings(
+        &mut self,
+        root_id: usize,
+        settings_content: Option<&str>,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        if settings_content.is_some_and(|content| !content.is_empty()) {
+            self.raw_host_settings.insert(
+                root_id,
+                parse_json_with_comments(settings_content.unwrap())?,
+            );
+        } else if self.raw_host_settings.remove(&root_id).is_some() {
+            // The worktree may not have any settings file at its root to hold a value anymore.
+            for setting_value in self.setting_values.values_mut() {
+                setting_value.clear_local_values(root_id);
+            }
+        }
+        self.recompute_values(Some((root_id, "".as_ref())), cx)?;
+        Ok(())
+    }
+
     /// Add or remove a set of local settings via a JSON string.
     pub fn clear_local_settings(&mut self, root_id: usize, cx: &mut AppContext) -> Result<()> {
         self.raw_local_settings.retain(|k, _| k.0 != root_id);
+        self.raw_host_settings.remove(&root_id);
+        self.editorconfigs.remove(&root_id);
+        self.editorconfig_properties.get_mut().remove(&root_id);
         self.recompute_values(Some((root_id, "".as_ref())), cx)?;
         Ok(())
     }
@@ -556,6 +706,62 @@ impl SettingsStore {
             .map(|((_, path), content)| (path.clone(), serde_json::to_string(content).unwrap()))
     }
 
+    /// Add or remove the `.editorconfig` file of a directory.
+    pub fn set_local_editorconfig(
+        &mut self,
+        root_id: usize,
+        directory: Arc<Path>,
+        content: Option<&str>,
+    ) {
+        self.editorconfig_properties.get_mut().remove(&root_id);
+        match content {
+            Some(content) => {
+                self.editorconfigs
+                    .entry(root_id)
+                    .or_default()
+                    .insert(directory, Editorconfig::parse(content));
+            }
+            None => {
+                if let hash_map::Entry::Occupied(mut editorconfigs) =
+                    self.editorconfigs.entry(root_id)
+                {
+                    editorconfigs.get_mut().remove(&directory);
+                    if editorconfigs.get().is_empty() {
+                        editorconfigs.remove();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the properties of the `.editorconfig` files applying to the given file, or `None`
+    /// when there aren't any.
+    ///
+    /// Files in deeper directories take precedence, and files above the closest one marked as
+    /// `root` are ignored.
+    pub fn editorconfig_properties(
+        &self,
+        location: SettingsLocation,
+    ) -> Option<EditorconfigProperties> {
+        let editorconfigs = self.editorconfigs.get(&location.worktree_id)?;
+        if let Some(properties) = self
+            .editorconfig_properties
+            .read()
+            .get(&location.worktree_id)
+            .and_then(|properties| properties.get(location.path))
+        {
+            return *properties;
+        }
+
+        let properties = resolve_editorconfig_properties(editorconfigs, location.path);
+        self.editorconfig_properties
+            .write()
+            .entry(location.worktree_id)
+            .or_default()
+            .insert(location.path.into(), properties);
+        properties
+    }
+
     pub fn json_schema(
         &self,
         schema_params: &SettingsJsonSchemaParams,
@@ -563,7 +769,7 @@ impl SettingsStore {
     ) -> serde_json::Value {
         use schemars::{
             gen::SchemaSettings,
-            schema::{Schema, SchemaObject},
+            schema::{InstanceType, ObjectValidation, Schema, SchemaObject},
         };
 
         let settings = SchemaSettings::draft07().with(|settings| {
@@ -651,6 +857,20 @@ impl SettingsStore {
             }
         }
 
+        let profile_schema = SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                additional_properties: Some(Box::new(combined_schema.schema.clone().into())),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        combined_schema
+            .schema
+            .object()
+            .properties
+            .insert(PROFILES_KEY.to_string(), profile_schema.into());
+
         for release_stage in ["dev", "nightly", "stable", "preview"] {
             let schema = combined_schema.schema.clone();
             combined_schema
@@ -668,6 +888,19 @@ impl SettingsStore {
         changed_local_path: Option<(usize, &Path)>,
         cx: &mut AppContext,
     ) -> Result<()> {
+        // Worktrees with host settings have a value at their root, even without a settings file
+        // there.
+        let mut local_settings = self
+            .raw_local_settings
+            .iter()
+            .map(|(key, settings)| (key.clone(), Some(settings)))
+            .collect::<BTreeMap<_, _>>();
+        for root_id in self.raw_host_settings.keys() {
+            local_settings
+                .entry((*root_id, Path::new("").into()))
+                .or_insert(None);
+        }
+
         // Reload the global and local values for every setting.
         let mut project_settings_stack = Vec::<DeserializedSetting>::new();
         let mut paths_stack = Vec::<Option<(usize, &Path)>>::new();
@@ -695,6 +928,15 @@ impl SettingsStore {
                 }
             }
 
+            let mut profile_settings = None;
+            if let Some(raw_profile_settings) =
+                active_profile_settings(&self.raw_user_settings, self.active_profile.as_deref())
+            {
+                profile_settings = setting_value
+                    .deserialize_setting(raw_profile_settings)
+                    .log_err();
+            }
+
             // If the global settings file changed, reload the global value for the field.
             if changed_local_path.is_none() {
                 if let Some(value) = setting_value
@@ -704,6 +946,7 @@ impl SettingsStore {
                             extensions: extension_settings.as_ref(),
                             user: user_settings.as_ref(),
                             release_channel: release_channel_settings.as_ref(),
+                            profile: profile_settings.as_ref(),
                             project: &[],
                         },
                         cx,
@@ -717,7 +960,7 @@ impl SettingsStore {
             // Reload the local values for the setting.
             paths_stack.clear();
             project_settings_stack.clear();
-            for ((root_id, path), local_settings) in &self.raw_local_settings {
+            for ((root_id, path), local_settings) in &local_settings {
                 // Build a stack of all of the local values for that setting.
                 while let Some(prev_entry) = paths_stack.last() {
                     if let Some((prev_root_id, prev_path)) = prev_entry {
@@ -730,35 +973,50 @@ impl SettingsStore {
                     break;
                 }
 
-                if let Some(local_settings) =
-                    setting_value.deserialize_setting(local_settings).log_err()
-                {
+                // Worktrees with host settings but no settings file at their root only have
+                // the host settings there.
+                if let Some(local_settings) = local_settings {
+                    let Some(local_settings) =
+                        setting_value.deserialize_setting(local_settings).log_err()
+                    else {
+                        continue;
+                    };
                     paths_stack.push(Some((*root_id, path.as_ref())));
                     project_settings_stack.push(local_settings);
+                }
 
-                    // If a local settings file changed, then avoid recomputing local
-                    // settings for any path outside of that directory.
-                    if changed_local_path.map_or(false, |(changed_root_id, changed_local_path)| {
-                        *root_id != changed_root_id || !path.starts_with(changed_local_path)
-                    }) {
-                        continue;
-                    }
+                // If a local settings file changed, then avoid recomputing local
+                // settings for any path outside of that directory.
+                if changed_local_path.map_or(false, |(changed_root_id, changed_local_path)| {
+                    *root_id != changed_root_id || !path.starts_with(changed_local_path)
+                }) {
+                    continue;
+                }
 
-                    if let Some(value) = setting_value
-                        .load_setting(
-                            SettingsSources {
-                                default: &default_settings,
-                                extensions: extension_settings.as_ref(),
-                                user: user_settings.as_ref(),
-                                release_channel: release_channel_settings.as_ref(),
-                                project: &project_settings_stack.iter().collect::<Vec<_>>(),
-                            },
-                            cx,
-                        )
-                        .log_err()
-                    {
-                        setting_value.set_local_value(*root_id, path.clone(), value);
-                    }
+                let host_settings = self
+                    .raw_host_settings
+                    .get(root_id)
+                    .and_then(|host_settings| {
+                        setting_value.deserialize_setting(host_settings).log_err()
+                    });
+                if let Some(value) = setting_value
+                    .load_setting(
+                        SettingsSources {
+                            default: &default_settings,
+                            extensions: extension_settings.as_ref(),
+                            user: user_settings.as_ref(),
+                            release_channel: release_channel_settings.as_ref(),
+                            profile: profile_settings.as_ref(),
+                            project: &host_settings
+                                .iter()
+                                .chain(&project_settings_stack)
+                                .collect::<Vec<_>>(),
+                        },
+                        cx,
+                    )
+                    .log_err()
+                {
+                    setting_value.set_local_value(*root_id, path.clone(), value);
                 }
             }
         }
@@ -810,6 +1068,9 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
                 release_channel: values
                     .release_channel
                     .map(|value| value.0.downcast_ref::<T::FileContent>().unwrap()),
+                profile: values
+                    .profile
+                    .map(|value| value.0.downcast_ref::<T::FileContent>().unwrap()),
                 project: values
                     .project
                     .iter()
@@ -862,6 +1123,11 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
         }
     }
 
+    fn clear_local_values(&mut self, root_id: usize) {
+        self.local_values
+            .retain(|(settings_root_id, _, _)| *settings_root_id != root_id);
+    }
+
     fn json_schema(
         &self,
         generator: &mut SchemaGenerator,
@@ -872,6 +1138,48 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
     }
 }
 
+/// Returns the settings of the given profile within the user settings.
+/// Resolves the properties of the `.editorconfig` files of a worktree applying to one of its files.
+fn resolve_editorconfig_properties(
+    editorconfigs: &BTreeMap<Arc<Path>, Editorconfig>,
+    path: &Path,
+) -> Option<EditorconfigProperties> {
+    let mut applying = Vec::new();
+    for directory in path.ancestors().skip(1) {
+        if let Some(editorconfig) = editorconfigs.get(directory) {
+            applying.push((directory, editorconfig));
+            if editorconfig.root {
+                break;
+            }
+        }
+    }
+    if applying.is_empty() {
+        return None;
+    }
+
+    let mut properties = EditorconfigProperties::default();
+    for (directory, editorconfig) in applying.into_iter().rev() {
+        if let Ok(path) = path.strip_prefix(directory) {
+            editorconfig.apply_to(path, &mut properties);
+        }
+    }
+    Some(properties)
+}
+
+fn active_profile_settings<'a>(
+    user_settings: &'a serde_json::Value,
+    profile: Option<&str>,
+) -> Option<&'a serde_json::Value> {
+    user_settings.get(PROFILES_KEY)?.get(profile?)
+}
+
+fn apply_edits(mut text: String, edits: Vec<(Range<usize>, String)>) -> String {
+    for (range, replacement) in edits.into_iter() {
+        text.replace_range(range, &replacement);
+    }
+    text
+}
+
 fn update_value_in_json_text<'a>(
     text: &mut String,
     key_path: &mut Vec<&'a str>,
@@ -1044,6 +1352,51 @@ fn replace_value_in_json_text(
     }
 }
 
+/// Returns the range to delete from the JSON text to remove the key at the given path,
+/// along with its value and the comma separating it from the surrounding keys.
+fn key_removal_range_in_json_text(text: &str, key_path: &[String]) -> Option<Range<usize>> {
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&tree_sitter_json::language()).unwrap();
+    let syntax_tree = parser.parse(text, None)?;
+
+    let root = syntax_tree.root_node();
+    let mut cursor = root.walk();
+    let mut object = root
+        .named_children(&mut cursor)
+        .find(|node| node.kind() == "object")?;
+    let mut pair = None;
+    for key in key_path {
+        if let Some(pair) = pair {
+            object = pair.child_by_field_name("value")?;
+        }
+        let key_text = format!("\"{key}\"");
+        let mut cursor = object.walk();
+        pair = Some(object.named_children(&mut cursor).find(|node| {
+            node.kind() == "pair"
+                && node
+                    .child_by_field_name("key")
+                    .and_then(|key| text.get(key.byte_range()))
+                    == Some(key_text.as_str())
+        })?);
+    }
+
+    let pair = pair?;
+    if let Some(next) = pair.next_named_sibling() {
+        Some(pair.start_byte()..next.start_byte())
+    } else if let Some(previous) = pair.prev_named_sibling() {
+        Some(previous.end_byte()..pair.end_byte())
+    } else {
+        let start = pair
+            .prev_sibling()
+            .map_or(pair.start_byte(), |brace| brace.end_byte());
+        let end = pair
+            .next_sibling()
+            .filter(|comma| comma.kind() == ",")
+            .map_or(pair.end_byte(), |comma| comma.end_byte());
+        Some(start..end)
+    }
+}
+
 fn to_pretty_json(value: &impl Serialize, indent_size: usize, indent_prefix_len: usize) -> String {
     const SPACES: [u8; 32] = [b' '; 32];
 
@@ -1210,6 +1563,90 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_host_settings(cx: &mut AppContext) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<UserSettings>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        let location = |worktree_id, path| {
+            Some(SettingsLocation {
+                worktree_id,
+                path: Path::new(path),
+            })
+        };
+
+        // Host settings apply to the whole worktree, even without any settings file in it.
+        store
+            .set_host_settings(1, Some(r#"{ "user": { "age": 40, "staff": true } }"#), cx)
+            .unwrap();
+        assert_eq!(store.get::<UserSettings>(location(1, "a/b.rs")).age, 40);
+        assert_eq!(store.get::<UserSettings>(location(2, "a/b.rs")).age, 30);
+
+        // Settings files, including the one at the root of the worktree, take precedence.
+        store
+            .set_local_settings(
+                1,
+                Path::new("").into(),
+                Some(r#"{ "user": { "age": 50 } }"#),
+                cx,
+            )
+            .unwrap();
+        store
+            .set_local_settings(
+                1,
+                Path::new("a").into(),
+                Some(r#"{ "user": { "name": "Jane Doe" } }"#),
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(location(1, "a/b.rs")),
+            &UserSettings {
+                name: "Jane Doe".to_string(),
+                age: 50,
+                staff: true,
+            }
+        );
+
+        // Updating the host settings keeps the settings files.
+        store
+            .set_host_settings(1, Some(r#"{ "user": { "age": 60 } }"#), cx)
+            .unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(location(1, "a/b.rs")),
+            &UserSettings {
+                name: "Jane Doe".to_string(),
+                age: 50,
+                staff: false,
+            }
+        );
+
+        store.set_host_settings(1, None, cx).unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(location(1, "b.rs")),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 50,
+                staff: false,
+            }
+        );
+        store
+            .set_local_settings(1, Path::new("").into(), Some("{}"), cx)
+            .unwrap();
+        assert_eq!(store.get::<UserSettings>(location(1, "b.rs")).age, 30);
+    }
+
     #[gpui::test]
     fn test_setting_store_assign_json_before_register(cx: &mut AppContext) {
         let mut store = SettingsStore::new(cx);
@@ -1373,6 +1810,145 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_settings_profiles(cx: &mut AppContext) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<UserSettings>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store
+            .set_user_settings(
+                r#"{
+                    "user": { "age": 31 },
+                    "profiles": {
+                        "Presentation": {
+                            "user": { "name": "Presenter" }
+                        },
+                        "Pairing": {}
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+
+        assert_eq!(store.profile_names(), vec!["Presentation", "Pairing"]);
+        assert_eq!(store.get::<UserSettings>(None).name, "John Doe");
+
+        // The active profile is overlaid on top of the user settings.
+        store
+            .set_active_profile(Some("Presentation".into()), cx)
+            .unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "Presenter".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+
+        // Updates to the active profile are written within that profile.
+        let old_json = serde_json::to_string_pretty(store.raw_user_settings()).unwrap();
+        let edits = store.edits_for_profile_update::<UserSettings>(
+            &old_json,
+            store.active_profile(),
+            |settings| settings.staff = Some(true),
+        );
+        let new_json = apply_edits(old_json, edits);
+        store.set_user_settings(&new_json, cx).unwrap();
+        assert_eq!(
+            store.raw_user_settings()["profiles"]["Presentation"]["user"]["staff"],
+            serde_json::json!(true)
+        );
+        assert!(store.get::<UserSettings>(None).staff);
+
+        store.set_active_profile(None, cx).unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_key_removal_in_json_text() {
+        let text = r#"{
+            // The user's name.
+            "name": "John Doe",
+            "age": 30,
+            "languages": {
+                "Rust": {
+                    "tab_size": 2
+                }
+            }
+        }"#
+        .unindent();
+
+        check_key_removal(
+            &text,
+            &["name"],
+            r#"{
+                // The user's name.
+                "age": 30,
+                "languages": {
+                    "Rust": {
+                        "tab_size": 2
+                    }
+                }
+            }"#,
+        );
+        check_key_removal(
+            &text,
+            &["languages"],
+            r#"{
+                // The user's name.
+                "name": "John Doe",
+                "age": 30
+            }"#,
+        );
+        check_key_removal(
+            &text,
+            &["languages", "Rust", "tab_size"],
+            r#"{
+                // The user's name.
+                "name": "John Doe",
+                "age": 30,
+                "languages": {
+                    "Rust": {
+                    }
+                }
+            }"#,
+        );
+        check_key_removal(&text, &["languages", "JSON"], &text);
+        check_key_removal(&text, &["age", "value"], &text);
+    }
+
+    #[track_caller]
+    fn check_key_removal(text: &str, key_path: &[&str], expected_text: &str) {
+        let key_path = key_path
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>();
+        let mut text = text.to_string();
+        if let Some(range) = key_removal_range_in_json_text(&text, &key_path) {
+            text.replace_range(range, "");
+        }
+        pretty_assertions::assert_eq!(text, expected_text.unindent());
+    }
+
     fn check_settings_update<T: Settings>(
         store: &mut SettingsStore,
         old_json: String,
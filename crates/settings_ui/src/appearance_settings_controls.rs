@@ -1,45 +1,56 @@
 use std::sync::Arc;
 
-use gpui::{AppContext, FontFeatures, FontWeight};
+use editor::{NumericSettingControl, NumericSettingEditor};
+use gpui::{AppContext, FontFeatures, FontWeight, View};
 use settings::{EditableSettingControl, Settings};
-use theme::{FontFamilyCache, SystemAppearance, ThemeMode, ThemeRegistry, ThemeSettings};
+use theme::{
+    clamp_font_size, Appearance, FontFamilyCache, SystemAppearance, ThemeMode, ThemeRegistry,
+    ThemeSettings,
+};
 use ui::{
     prelude::*, CheckboxWithLabel, ContextMenu, DropdownMenu, NumericStepper, SettingsContainer,
-    SettingsGroup, ToggleButton,
+    SettingsControl, SettingsFilter, SettingsGroup, ToggleButton,
 };
 
-#[derive(IntoElement)]
-pub struct AppearanceSettingsControls {}
+pub struct AppearanceSettingsControls {
+    ui_font_size: View<NumericSettingEditor>,
+}
 
 impl AppearanceSettingsControls {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            ui_font_size: cx.new_view(NumericSettingEditor::new::<UiFontSizeControl>),
+        }
     }
 }
 
-impl RenderOnce for AppearanceSettingsControls {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+impl Render for AppearanceSettingsControls {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
         SettingsContainer::new()
             .child(
-                SettingsGroup::new("Theme").child(
+                SettingsGroup::new("Theme").searchable_child(
+                    [ThemeControl.name(), ThemeModeControl.name()],
                     h_flex()
                         .gap_2()
                         .justify_between()
-                        .child(ThemeControl)
-                        .child(ThemeModeControl),
+                        .child(SettingsControl::new(ThemeControl))
+                        .child(SettingsControl::new(ThemeModeControl)),
                 ),
             )
             .child(
-                SettingsGroup::new("Font")
-                    .child(
+                SettingsGroup::new("Interface")
+                    .searchable_child(
+                        [UiFontFamilyControl.name(), UiFontWeightControl.name()],
                         h_flex()
                             .gap_2()
                             .justify_between()
-                            .child(UiFontFamilyControl)
-                            .child(UiFontWeightControl),
+                            .child(SettingsControl::new(UiFontFamilyControl))
+                            .child(SettingsControl::new(UiFontWeightControl)),
                     )
-                    .child(UiFontSizeControl)
-                    .child(UiFontLigaturesControl),
+                    .control(UiFontSizeControl {
+                        value_editor: self.ui_font_size.clone(),
+                    })
+                    .control(UiFontLigaturesControl),
             )
     }
 }
@@ -47,8 +58,23 @@ impl RenderOnce for AppearanceSettingsControls {
 #[derive(IntoElement)]
 struct ThemeControl;
 
+impl ThemeControl {
+    /// Shows the given theme without writing it to the settings file. The theme from
+    /// the settings is restored once they're reloaded.
+    fn preview(theme_name: &str, cx: &mut AppContext) {
+        let Ok(theme) = ThemeRegistry::global(cx).get(theme_name) else {
+            return;
+        };
+        let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        theme_settings.active_theme = theme;
+        theme_settings.apply_theme_overrides();
+        ThemeSettings::override_global(theme_settings, cx);
+        cx.refresh();
+    }
+}
+
 impl EditableSettingControl for ThemeControl {
-    type Value = String;
+    type Value = SharedString;
     type Settings = ThemeSettings;
 
     fn name(&self) -> SharedString {
@@ -61,8 +87,8 @@ impl EditableSettingControl for ThemeControl {
         settings
             .theme_selection
             .as_ref()
-            .map(|selection| selection.theme(appearance.0).to_string())
-            .unwrap_or_else(|| ThemeSettings::default_theme(*appearance).to_string())
+            .map(|selection| selection.theme(appearance.0).to_string().into())
+            .unwrap_or_else(|| ThemeSettings::default_theme(*appearance).into())
     }
 
     fn apply(
@@ -71,7 +97,7 @@ impl EditableSettingControl for ThemeControl {
         cx: &AppContext,
     ) {
         let appearance = SystemAppearance::global(cx);
-        settings.set_theme(value, appearance.0);
+        settings.set_theme(value.to_string(), appearance.0);
     }
 }
 
@@ -83,21 +109,45 @@ impl RenderOnce for ThemeControl {
             "theme",
             value.clone(),
             ContextMenu::build(cx, |mut menu, cx| {
-                let theme_registry = ThemeRegistry::global(cx);
-
-                for theme in theme_registry.list_names(false) {
-                    menu = menu.custom_entry(
-                        {
-                            let theme = theme.clone();
-                            move |_cx| Label::new(theme.clone()).into_any_element()
-                        },
-                        {
-                            let theme = theme.clone();
-                            move |cx| {
-                                Self::write(theme.to_string(), cx);
-                            }
-                        },
-                    )
+                let mut themes = ThemeRegistry::global(cx).list(false);
+                themes.sort_by(|a, b| a.name.cmp(&b.name));
+
+                for (header, appearance) in
+                    [("Light", Appearance::Light), ("Dark", Appearance::Dark)]
+                {
+                    menu = menu.header(header);
+                    for theme in themes.iter().filter(|theme| theme.appearance == appearance) {
+                        menu = menu.custom_entry(
+                            {
+                                let theme = theme.name.clone();
+                                move |_cx| {
+                                    // Hovering a theme previews it, until the pointer
+                                    // leaves it or a theme is picked.
+                                    div()
+                                        .id(ElementId::Name(theme.clone()))
+                                        .w_full()
+                                        .on_hover({
+                                            let theme = theme.clone();
+                                            move |hovered, cx| {
+                                                if *hovered {
+                                                    Self::preview(&theme, cx);
+                                                } else {
+                                                    ThemeSettings::reload_current_theme(cx);
+                                                }
+                                            }
+                                        })
+                                        .child(Label::new(theme.clone()))
+                                        .into_any_element()
+                                }
+                            },
+                            {
+                                let theme = theme.name.clone();
+                                move |cx| {
+                                    Self::write(theme.clone(), cx);
+                                }
+                            },
+                        )
+                    }
                 }
 
                 menu
@@ -228,7 +278,9 @@ impl RenderOnce for UiFontFamilyControl {
 }
 
 #[derive(IntoElement)]
-struct UiFontSizeControl;
+struct UiFontSizeControl {
+    value_editor: View<NumericSettingEditor>,
+}
 
 impl EditableSettingControl for UiFontSizeControl {
     type Value = Pixels;
@@ -248,7 +300,17 @@ impl EditableSettingControl for UiFontSizeControl {
         value: Self::Value,
         _cx: &AppContext,
     ) {
-        settings.ui_font_size = Some(value.into());
+        settings.ui_font_size = Some(clamp_font_size(value).into());
+    }
+}
+
+impl NumericSettingControl for UiFontSizeControl {
+    fn format(value: &Self::Value) -> String {
+        value.0.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self::Value> {
+        text.trim_end_matches("px").parse().ok().map(px)
     }
 }
 
@@ -259,15 +321,21 @@ impl RenderOnce for UiFontSizeControl {
         h_flex()
             .gap_2()
             .child(Icon::new(IconName::FontSize))
-            .child(NumericStepper::new(
-                value.to_string(),
-                move |_, cx| {
-                    Self::write(value - px(1.), cx);
-                },
-                move |_, cx| {
-                    Self::write(value + px(1.), cx);
-                },
-            ))
+            .child(
+                NumericStepper::new(
+                    value.to_string(),
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value - px(steps), cx);
+                    },
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value + px(steps), cx);
+                    },
+                )
+                .value_editor(self.value_editor),
+            )
+            .child(Label::new("px").color(Color::Muted))
     }
 }
 
@@ -375,7 +443,7 @@ impl RenderOnce for UiFontLigaturesControl {
 
         CheckboxWithLabel::new(
             "ui-font-ligatures",
-            Label::new(self.name()),
+            SettingsFilter::label(self.name(), cx),
             value.into(),
             |selection, cx| {
                 Self::write(
@@ -389,3 +457,116 @@ impl RenderOnce for UiFontLigaturesControl {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::UpdateGlobal;
+    use settings::{apply_test_setting, write_test_setting, SettingsStore};
+    use theme::{AppearanceContent, ThemeContent};
+
+    fn init_test(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        theme::init(theme::LoadThemes::JustBase, cx);
+        SystemAppearance::init(cx);
+    }
+
+    fn active_theme(cx: &AppContext) -> SharedString {
+        ThemeSettings::get_global(cx).active_theme.name.clone()
+    }
+
+    #[gpui::test]
+    fn test_theme_preview(cx: &mut AppContext) {
+        init_test(cx);
+        ThemeRegistry::global(cx).insert_user_themes([ThemeContent {
+            name: "Test Light".into(),
+            appearance: AppearanceContent::Light,
+            style: Default::default(),
+        }]);
+        apply_test_setting::<ThemeSettings>(
+            |settings, cx| ThemeControl::apply(settings, "One Dark".into(), cx),
+            cx,
+        );
+        assert_eq!(ThemeControl::read(cx), "One Dark");
+        assert_eq!(active_theme(cx), "One Dark");
+
+        // Previewing a theme shows it without changing the settings.
+        ThemeControl::preview("Test Light", cx);
+        assert_eq!(active_theme(cx), "Test Light");
+        assert_eq!(ThemeControl::read(cx), "One Dark");
+
+        ThemeControl::preview("Missing Theme", cx);
+        assert_eq!(active_theme(cx), "Test Light");
+
+        ThemeSettings::reload_current_theme(cx);
+        assert_eq!(active_theme(cx), "One Dark");
+    }
+
+    #[gpui::test]
+    fn test_modified_controls(cx: &mut AppContext) {
+        init_test(cx);
+        assert!(!UiFontSizeControl::is_modified(cx));
+        assert!(!UiFontWeightControl::is_modified(cx));
+
+        write_test_setting::<UiFontSizeControl>(px(18.), cx);
+        assert_eq!(UiFontSizeControl::read(cx), px(18.));
+        assert!(UiFontSizeControl::is_modified(cx));
+        assert!(!UiFontWeightControl::is_modified(cx));
+
+        // Controls sharing a settings object are only modified by their own keys.
+        write_test_setting::<ThemeModeControl>(ThemeMode::Dark, cx);
+        assert_eq!(ThemeModeControl::read(cx), ThemeMode::Dark);
+        assert!(ThemeModeControl::is_modified(cx));
+        assert!(!UiFontWeightControl::is_modified(cx));
+    }
+
+    #[gpui::test]
+    fn test_controls_in_profile(cx: &mut AppContext) {
+        init_test(cx);
+        let default_font_size = UiFontSizeControl::read(cx);
+        SettingsStore::update_global(cx, |store, cx| {
+            store
+                .set_user_settings(
+                    r#"{ "profiles": { "Presenting": { "ui_font_size": 24 } } }"#,
+                    cx,
+                )
+                .unwrap();
+            store
+                .set_active_profile(Some("Presenting".into()), cx)
+                .unwrap();
+        });
+        assert_eq!(UiFontSizeControl::read(cx), px(24.));
+        assert!(UiFontSizeControl::is_modified(cx));
+        assert!(!UiFontWeightControl::is_modified(cx));
+
+        SettingsStore::update_global(cx, |store, cx| store.set_active_profile(None, cx).unwrap());
+        assert_eq!(UiFontSizeControl::read(cx), default_font_size);
+        assert!(!UiFontSizeControl::is_modified(cx));
+    }
+
+    #[test]
+    fn test_ui_font_size_parsing() {
+        assert_eq!(UiFontSizeControl::parse("16"), Some(px(16.)));
+        assert_eq!(UiFontSizeControl::parse("12.5px"), Some(px(12.5)));
+        assert_eq!(UiFontSizeControl::parse("large"), None);
+        assert_eq!(UiFontSizeControl::format(&px(16.)), "16");
+    }
+
+    #[gpui::test]
+    fn test_interface_font_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<UiFontFamilyControl>("Zed Plex Sans".into(), cx);
+        assert_eq!(UiFontFamilyControl::read(cx), "Zed Plex Sans");
+
+        write_test_setting::<UiFontSizeControl>(px(20.), cx);
+        assert_eq!(UiFontSizeControl::read(cx), px(20.));
+        write_test_setting::<UiFontSizeControl>(px(1000.), cx);
+        assert_eq!(UiFontSizeControl::read(cx), clamp_font_size(px(1000.)));
+
+        write_test_setting::<UiFontWeightControl>(FontWeight::BOLD, cx);
+        assert_eq!(UiFontWeightControl::read(cx), FontWeight::BOLD);
+        assert_eq!(UiFontFamilyControl::read(cx), "Zed Plex Sans");
+    }
+}
@@ -0,0 +1,344 @@
+use auto_update::{
+    AutoUpdateChannelSetting, AutoUpdateSetting, AutoUpdateSettingContent, AutoUpdateStatus,
+    AutoUpdater, UpdateChannel,
+};
+use client::TelemetrySettings;
+use gpui::{AppContext, Subscription};
+use settings::{EditableSettingControl, Settings};
+use ui::{
+    prelude::*, ButtonLike, CheckboxWithLabel, DropdownSettingControl, SettingsContainer,
+    SettingsFilter, SettingsGroup,
+};
+
+pub struct GeneralSettingsControls {
+    _auto_updater_subscription: Option<Subscription>,
+}
+
+impl GeneralSettingsControls {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        // Show the progress of update checks as they happen.
+        let _auto_updater_subscription =
+            AutoUpdater::get(cx).map(|updater| cx.observe(&updater, |_, _, cx| cx.notify()));
+
+        Self {
+            _auto_updater_subscription,
+        }
+    }
+}
+
+impl Render for GeneralSettingsControls {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        SettingsContainer::new()
+            .child(
+                SettingsGroup::new("Updates")
+                    .control(AutoUpdateControl)
+                    .dropdown(update_channel_control())
+                    .searchable_child(
+                        [CheckForUpdatesControl::NAME.into()],
+                        CheckForUpdatesControl,
+                    ),
+            )
+            .child(
+                SettingsGroup::new("Privacy")
+                    .child(TelemetryDescription)
+                    .control(CrashReportsControl)
+                    .control(UsageDataControl),
+            )
+    }
+}
+
+#[derive(IntoElement)]
+struct AutoUpdateControl;
+
+impl EditableSettingControl for AutoUpdateControl {
+    type Value = bool;
+    type Settings = AutoUpdateSetting;
+
+    fn name(&self) -> SharedString {
+        "Automatically Install Updates".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        AutoUpdateSetting::get_global(cx).0
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        *settings = Some(AutoUpdateSettingContent(value));
+    }
+}
+
+impl RenderOnce for AutoUpdateControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "auto-update",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+fn update_channel_control(
+) -> DropdownSettingControl<AutoUpdateChannelSetting, Option<UpdateChannel>> {
+    DropdownSettingControl::<AutoUpdateChannelSetting, _>::new(
+        "Update Channel",
+        |cx| AutoUpdateChannelSetting::get_global(cx).0,
+        |settings, value, _cx| *settings = value,
+    )
+    .option(None, "Default")
+    .option(Some(UpdateChannel::Stable), "Stable")
+    .option(Some(UpdateChannel::Preview), "Preview")
+    .option(Some(UpdateChannel::Nightly), "Nightly")
+}
+
+/// A button checking for updates right away, followed by the status of the update.
+#[derive(IntoElement)]
+struct CheckForUpdatesControl;
+
+impl CheckForUpdatesControl {
+    const NAME: &'static str = "Check for Updates";
+}
+
+impl RenderOnce for CheckForUpdatesControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let status = AutoUpdater::get(cx).map(|updater| updater.read(cx).status());
+        let is_updating = matches!(
+            status,
+            Some(
+                AutoUpdateStatus::Checking
+                    | AutoUpdateStatus::Downloading
+                    | AutoUpdateStatus::Installing
+            )
+        );
+
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(Self::NAME, cx))
+            .child(
+                Button::new("check-for-updates", "Check Now")
+                    .disabled(is_updating)
+                    .on_click(|_, cx| auto_update::check(&auto_update::Check, cx)),
+            )
+            .children(status.and_then(|status| {
+                match status {
+                    AutoUpdateStatus::Idle => None,
+                    AutoUpdateStatus::Checking => Some(
+                        Label::new("Checking for updates…")
+                            .color(Color::Muted)
+                            .into_any_element(),
+                    ),
+                    AutoUpdateStatus::Downloading => Some(
+                        Label::new("Downloading update…")
+                            .color(Color::Muted)
+                            .into_any_element(),
+                    ),
+                    AutoUpdateStatus::Installing => Some(
+                        Label::new("Installing update…")
+                            .color(Color::Muted)
+                            .into_any_element(),
+                    ),
+                    AutoUpdateStatus::Updated { binary_path } => Some(
+                        Button::new("restart-to-update", "Restart to Update")
+                            .on_click(move |_, cx| {
+                                workspace::reload(
+                                    &workspace::Reload {
+                                        binary_path: Some(binary_path.clone()),
+                                    },
+                                    cx,
+                                )
+                            })
+                            .into_any_element(),
+                    ),
+                    AutoUpdateStatus::Errored => Some(
+                        Label::new("Update failed")
+                            .color(Color::Error)
+                            .into_any_element(),
+                    ),
+                }
+            }))
+    }
+}
+
+/// Describes what is collected by Zed, linking to the telemetry documentation.
+#[derive(IntoElement)]
+struct TelemetryDescription;
+
+impl RenderOnce for TelemetryDescription {
+    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .child(
+                Label::new("Zed never collects your code or the contents of your files.")
+                    .color(Color::Muted),
+            )
+            .child(
+                ButtonLike::new("open-telemetry-docs")
+                    .style(ButtonStyle::Transparent)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new("Learn more…"))
+                            .child(Icon::new(IconName::ArrowUpRight).size(IconSize::Small)),
+                    )
+                    .on_click(|_, cx| cx.open_url("https://zed.dev/docs/telemetry")),
+            )
+    }
+}
+
+#[derive(IntoElement)]
+struct CrashReportsControl;
+
+impl EditableSettingControl for CrashReportsControl {
+    type Value = bool;
+    type Settings = TelemetrySettings;
+
+    fn name(&self) -> SharedString {
+        "Send Crash Reports".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        TelemetrySettings::get_global(cx).diagnostics
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.diagnostics = Some(value);
+    }
+}
+
+impl RenderOnce for CrashReportsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "telemetry-diagnostics",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[derive(IntoElement)]
+struct UsageDataControl;
+
+impl EditableSettingControl for UsageDataControl {
+    type Value = bool;
+    type Settings = TelemetrySettings;
+
+    fn name(&self) -> SharedString {
+        "Send Anonymous Usage Data".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        TelemetrySettings::get_global(cx).metrics
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.metrics = Some(value);
+    }
+}
+
+impl RenderOnce for UsageDataControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "telemetry-metrics",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::{apply_test_setting, write_test_setting, SettingsStore};
+
+    fn init_test(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        AutoUpdateSetting::register(cx);
+        AutoUpdateChannelSetting::register(cx);
+        TelemetrySettings::register(cx);
+    }
+
+    #[gpui::test]
+    fn test_auto_update_controls(cx: &mut AppContext) {
+        init_test(cx);
+        assert_eq!(update_channel_control().read(cx), None);
+
+        write_test_setting::<AutoUpdateControl>(false, cx);
+        assert!(!AutoUpdateControl::read(cx));
+        write_test_setting::<AutoUpdateControl>(true, cx);
+        assert!(AutoUpdateControl::read(cx));
+
+        let control = update_channel_control();
+        for (value, _) in control.options().iter().rev() {
+            apply_test_setting::<AutoUpdateChannelSetting>(
+                |settings, cx| control.apply(settings, *value, cx),
+                cx,
+            );
+            assert_eq!(control.read(cx), *value);
+            assert!(AutoUpdateControl::read(cx));
+        }
+    }
+
+    #[gpui::test]
+    fn test_telemetry_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        // Each control only opts out of its own kind of telemetry.
+        write_test_setting::<CrashReportsControl>(false, cx);
+        write_test_setting::<UsageDataControl>(true, cx);
+        assert!(!CrashReportsControl::read(cx));
+        assert!(UsageDataControl::read(cx));
+
+        write_test_setting::<UsageDataControl>(false, cx);
+        assert!(!UsageDataControl::read(cx));
+        assert!(!CrashReportsControl::read(cx));
+
+        write_test_setting::<CrashReportsControl>(true, cx);
+        assert!(CrashReportsControl::read(cx));
+        assert!(!UsageDataControl::read(cx));
+    }
+}
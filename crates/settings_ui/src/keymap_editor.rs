@@ -0,0 +1,568 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use collections::BTreeMap;
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    actions, uniform_list, AppContext, EventEmitter, FocusHandle, FocusableView,
+    KeyBindingContextPredicate, KeymapVersion, Keystroke, NoAction, Stateful, Subscription,
+    UniformListScrollHandle, View,
+};
+use serde_json::{json, Map, Value};
+use settings::KeymapFile;
+use ui::{prelude::*, ButtonLike, KeyBinding, Tooltip};
+use workspace::item::{Item, ItemEvent};
+use workspace::Workspace;
+
+actions!(zed, [OpenKeymapEditor]);
+
+/// The most keystrokes a recorded key binding can have.
+const MAX_RECORDED_KEYSTROKES: usize = 3;
+
+pub fn open_keymap_editor(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .find_map(|item| item.downcast::<KeymapEditor>());
+
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, cx);
+    } else {
+        let fs = workspace.app_state().fs.clone();
+        let keymap_editor = cx.new_view(|cx| KeymapEditor::new(fs, cx));
+        workspace.add_item_to_active_pane(Box::new(keymap_editor), None, true, cx)
+    }
+}
+
+/// An action along with the key bindings that trigger it.
+struct KeymapEntry {
+    action_name: SharedString,
+    bindings: Vec<gpui::KeyBinding>,
+    /// Whether the action can be bound without arguments, as the keymap editor can't
+    /// write the arguments of an action.
+    can_bind: bool,
+}
+
+impl KeymapEntry {
+    /// Returns whether the binding triggers the action without arguments, so that it can
+    /// be replaced from the keymap editor.
+    fn can_replace(&self, binding: &gpui::KeyBinding, cx: &AppContext) -> bool {
+        self.can_bind
+            && cx
+                .build_action(&self.action_name, None)
+                .map_or(false, |action| binding.action().partial_eq(action.as_ref()))
+    }
+}
+
+/// A key binding being recorded for an action.
+struct Recording {
+    action_name: SharedString,
+    /// The binding replaced by the recorded one, which is added in the same context.
+    replaced: Option<gpui::KeyBinding>,
+    keystrokes: Vec<Keystroke>,
+    _interceptor: Subscription,
+}
+
+impl Recording {
+    fn context(&self) -> Option<&KeyBindingContextPredicate> {
+        self.replaced
+            .as_ref()
+            .and_then(|binding| binding.predicate())
+    }
+
+    /// Returns the keymap block binding the recorded keystrokes to the action, and
+    /// unbinding the replaced keystrokes.
+    fn keymap_block(&self) -> Value {
+        let mut bindings = Map::new();
+        if let Some(replaced) = &self.replaced {
+            bindings.insert(unparse_keystrokes(replaced.keystrokes()), Value::Null);
+        }
+        bindings.insert(
+            unparse_keystrokes(&self.keystrokes),
+            json!(self.action_name.as_ref()),
+        );
+
+        let mut block = json!({ "bindings": bindings });
+        if let Some(context) = self.context() {
+            block["context"] = json!(context.to_string());
+        }
+        block
+    }
+}
+
+/// Lists the registered actions with their key bindings, allowing to record new
+/// bindings that are written to the user's keymap file.
+pub struct KeymapEditor {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    search_editor: View<Editor>,
+    entries: Vec<KeymapEntry>,
+    filtered_entry_indices: Vec<usize>,
+    keymap_version: Option<KeymapVersion>,
+    recording: Option<Recording>,
+    recording_focus_handle: FocusHandle,
+    list: UniformListScrollHandle,
+    _search_editor_subscription: Subscription,
+}
+
+impl KeymapEditor {
+    fn new(fs: Arc<dyn Fs>, cx: &mut ViewContext<Self>) -> Self {
+        let search_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Search actions and key bindings…", cx);
+            editor
+        });
+        let _search_editor_subscription =
+            cx.subscribe(&search_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.filter_entries(cx);
+                    cx.notify();
+                }
+            });
+
+        let mut this = Self {
+            fs,
+            focus_handle: cx.focus_handle(),
+            search_editor,
+            entries: Vec::new(),
+            filtered_entry_indices: Vec::new(),
+            keymap_version: None,
+            recording: None,
+            recording_focus_handle: cx.focus_handle(),
+            list: UniformListScrollHandle::new(),
+            _search_editor_subscription,
+        };
+        this.update_entries(cx);
+        this
+    }
+
+    /// Rebuilds the entries when the keymap changed since they were last built.
+    fn update_entries(&mut self, cx: &mut ViewContext<Self>) {
+        let keymap = cx.key_bindings();
+        let keymap = keymap.borrow();
+        if self.keymap_version == Some(keymap.version()) {
+            return;
+        }
+        self.keymap_version = Some(keymap.version());
+
+        let mut bindings_by_action = BTreeMap::<&str, Vec<gpui::KeyBinding>>::default();
+        for binding in keymap.bindings() {
+            if binding.action().partial_eq(&NoAction) {
+                continue;
+            }
+            bindings_by_action
+                .entry(binding.action().name())
+                .or_default()
+                .push(binding.clone());
+        }
+
+        self.entries = cx
+            .all_action_names()
+            .iter()
+            .map(|action_name| KeymapEntry {
+                action_name: action_name.clone(),
+                bindings: bindings_by_action
+                    .remove(action_name.as_ref())
+                    .unwrap_or_default(),
+                can_bind: cx.build_action(action_name, None).is_ok(),
+            })
+            .collect();
+        self.entries
+            .sort_by(|a, b| a.action_name.cmp(&b.action_name));
+        drop(keymap);
+
+        self.filter_entries(cx);
+    }
+
+    fn filter_entries(&mut self, cx: &mut ViewContext<Self>) {
+        let query = self.search_editor.read(cx).text(cx).to_lowercase();
+        let query = query.trim();
+
+        self.filtered_entry_indices = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                query.is_empty()
+                    || entry.action_name.to_lowercase().contains(query)
+                    || entry
+                        .bindings
+                        .iter()
+                        .any(|binding| unparse_keystrokes(binding.keystrokes()).contains(query))
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+    }
+
+    fn start_recording(
+        &mut self,
+        action_name: SharedString,
+        replaced: Option<gpui::KeyBinding>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        // Keystrokes are intercepted before they're matched against the key bindings, so
+        // that recording an existing binding doesn't trigger its action.
+        let view = cx.view().downgrade();
+        let _interceptor = cx.intercept_keystrokes(move |event, cx| {
+            let keystroke = event.keystroke.clone();
+            view.update(cx, |this, cx| this.record_keystroke(keystroke, cx))
+                .ok();
+        });
+
+        self.recording = Some(Recording {
+            action_name,
+            replaced,
+            keystrokes: Vec::new(),
+            _interceptor,
+        });
+        cx.focus(&self.recording_focus_handle);
+        cx.notify();
+    }
+
+    fn record_keystroke(&mut self, mut keystroke: Keystroke, cx: &mut ViewContext<Self>) {
+        if !self.recording_focus_handle.is_focused(cx) {
+            return;
+        }
+        let Some(recording) = self.recording.as_mut() else {
+            return;
+        };
+        cx.stop_propagation();
+
+        if keystroke.key == "escape" && !keystroke.modifiers.modified() {
+            self.cancel_recording(cx);
+            return;
+        }
+        if recording.keystrokes.len() < MAX_RECORDED_KEYSTROKES {
+            // Key bindings match keystrokes regardless of the text they input.
+            keystroke.ime_key = None;
+            recording.keystrokes.push(keystroke);
+        }
+        cx.notify();
+    }
+
+    fn cancel_recording(&mut self, cx: &mut ViewContext<Self>) {
+        self.recording = None;
+        cx.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn save_recording(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(recording) = self.recording.take() else {
+            return;
+        };
+        cx.focus(&self.focus_handle);
+        cx.notify();
+        if recording.keystrokes.is_empty() {
+            return;
+        }
+
+        let block = recording.keymap_block();
+        let fs = self.fs.clone();
+        cx.background_executor()
+            .spawn(async move { KeymapFile::append_to_user_keymap(&fs, &[block]).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// Returns the actions bound to keystrokes that would conflict with the recorded ones
+    /// in the same context, as either binding would shadow the other.
+    fn conflicting_actions(&self, recording: &Recording, cx: &AppContext) -> Vec<SharedString> {
+        if recording.keystrokes.is_empty() {
+            return Vec::new();
+        }
+
+        let keymap = cx.key_bindings();
+        let keymap = keymap.borrow();
+        let mut actions = Vec::<SharedString>::new();
+        for binding in keymap.bindings() {
+            let keystrokes = binding.keystrokes();
+            let len = keystrokes.len().min(recording.keystrokes.len());
+            if keystrokes[..len] != recording.keystrokes[..len]
+                || binding.predicate() != recording.context()
+                || binding.action().partial_eq(&NoAction)
+            {
+                continue;
+            }
+            let is_replaced = recording
+                .replaced
+                .as_ref()
+                .map_or(false, |replaced| replaced.keystrokes() == keystrokes);
+            let action_name = binding.action().name();
+            if !is_replaced
+                && action_name != recording.action_name.as_ref()
+                && !actions.iter().any(|action| action.as_ref() == action_name)
+            {
+                actions.push(action_name.to_string().into());
+            }
+        }
+        actions
+    }
+
+    fn render_recording(
+        &self,
+        recording: &Recording,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let keystrokes = if recording.keystrokes.is_empty() {
+            Label::new("Press keys…").color(Color::Muted)
+        } else {
+            Label::new(
+                recording
+                    .keystrokes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        };
+        let context = match recording.context() {
+            Some(context) => format!("in {context}"),
+            None => "everywhere".to_string(),
+        };
+        let conflicts = self.conflicting_actions(recording, cx);
+
+        v_flex()
+            .track_focus(&self.recording_focus_handle)
+            .gap_1()
+            .p_2()
+            .border_1()
+            .rounded_md()
+            .border_color(cx.theme().colors().border_focused)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(format!(
+                        "Recording a key binding for {}",
+                        recording.action_name
+                    )))
+                    .child(Label::new(context).color(Color::Muted)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(div().flex_1().child(keystrokes))
+                    .child(
+                        Button::new("save-recording", "Save")
+                            .disabled(recording.keystrokes.is_empty())
+                            .on_click(cx.listener(|this, _, cx| this.save_recording(cx))),
+                    )
+                    .child(
+                        Button::new("cancel-recording", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel_recording(cx))),
+                    ),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::ExclamationTriangle)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(
+                            Label::new(format!("Conflicts with {}", conflicts.join(", ")))
+                                .color(Color::Warning),
+                        ),
+                )
+            })
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        cx: &mut ViewContext<Self>,
+    ) -> Vec<Stateful<Div>> {
+        range
+            .map(|ix| {
+                let entry = &self.entries[self.filtered_entry_indices[ix]];
+                let action_name = entry.action_name.clone();
+
+                h_flex()
+                    .id(ix)
+                    .w_full()
+                    .gap_2()
+                    .py_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Label::new(entry.action_name.clone())),
+                    )
+                    .children(entry.bindings.iter().enumerate().map(|(binding_ix, binding)| {
+                        let can_replace = entry.can_replace(binding, cx);
+                        let context = binding.predicate().map(|context| context.to_string());
+
+                        ButtonLike::new(binding_ix)
+                            .disabled(!can_replace)
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(KeyBinding::new(binding.clone()))
+                                    .when_some(context.clone(), |this, context| {
+                                        this.child(
+                                            Label::new(context)
+                                                .size(LabelSize::Small)
+                                                .color(Color::Muted),
+                                        )
+                                    }),
+                            )
+                            .tooltip(move |cx| {
+                                if can_replace {
+                                    Tooltip::text("Record a new key binding", cx)
+                                } else {
+                                    Tooltip::text(
+                                        "Bindings with action arguments can only be edited in the keymap file",
+                                        cx,
+                                    )
+                                }
+                            })
+                            .on_click({
+                                let action_name = action_name.clone();
+                                let binding = binding.clone();
+                                cx.listener(move |this, _, cx| {
+                                    this.start_recording(
+                                        action_name.clone(),
+                                        Some(binding.clone()),
+                                        cx,
+                                    )
+                                })
+                            })
+                    }))
+                    .child(
+                        IconButton::new("add-binding", IconName::Plus)
+                            .icon_size(IconSize::Small)
+                            .disabled(!entry.can_bind)
+                            .tooltip(|cx| Tooltip::text("Add a key binding", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.start_recording(action_name.clone(), None, cx)
+                            })),
+                    )
+            })
+            .collect()
+    }
+}
+
+/// Returns the keystrokes the way they're written in the keymap file.
+fn unparse_keystrokes(keystrokes: &[Keystroke]) -> String {
+    keystrokes
+        .iter()
+        .map(Keystroke::unparse)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl EventEmitter<ItemEvent> for KeymapEditor {}
+
+impl FocusableView for KeymapEditor {
+    fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for KeymapEditor {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _cx: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::Settings))
+    }
+
+    fn tab_content_text(&self, _cx: &WindowContext) -> Option<SharedString> {
+        Some("Key Bindings".into())
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for KeymapEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.update_entries(cx);
+
+        let recording = self
+            .recording
+            .as_ref()
+            .map(|recording| self.render_recording(recording, cx));
+        let view = cx.view().clone();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .p_4()
+            .size_full()
+            .gap_4()
+            .child(Label::new("Key Bindings").size(LabelSize::Large))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Icon::new(IconName::MagnifyingGlass))
+                    .child(self.search_editor.clone()),
+            )
+            .children(recording)
+            .child(
+                uniform_list(
+                    view,
+                    "keymap-entries",
+                    self.filtered_entry_indices.len(),
+                    Self::render_entries,
+                )
+                .flex_grow()
+                .track_scroll(self.list.clone()),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(keystrokes: &str, replaced: Option<gpui::KeyBinding>) -> Recording {
+        Recording {
+            action_name: "editor::ToggleComments".into(),
+            replaced,
+            keystrokes: keystrokes
+                .split_whitespace()
+                .map(|keystroke| Keystroke::parse(keystroke).unwrap())
+                .collect(),
+            _interceptor: Subscription::new(|| {}),
+        }
+    }
+
+    #[test]
+    fn test_unparse_keystrokes() {
+        let keystrokes = ["ctrl-k", "shift-alt-c", "cmd-fn-f1"]
+            .into_iter()
+            .map(|keystroke| Keystroke::parse(keystroke).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unparse_keystrokes(&keystrokes),
+            "ctrl-k alt-shift-c cmd-fn-f1"
+        );
+        assert_eq!(unparse_keystrokes(&[]), "");
+    }
+
+    #[test]
+    fn test_recording_keymap_block() {
+        assert_eq!(
+            recording("ctrl-/", None).keymap_block(),
+            json!({ "bindings": { "ctrl-/": "editor::ToggleComments" } })
+        );
+
+        // Replacing a binding unbinds its keystrokes in the same context.
+        let replaced = gpui::KeyBinding::new("ctrl-k ctrl-c", NoAction, Some("Editor"));
+        assert_eq!(
+            recording("ctrl-k ctrl-/", Some(replaced)).keymap_block(),
+            json!({
+                "context": "Editor",
+                "bindings": {
+                    "ctrl-k ctrl-c": null,
+                    "ctrl-k ctrl-/": "editor::ToggleComments",
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render,
+    UpdateGlobal, View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use settings::SettingsStore;
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+actions!(settings_profile_selector, [Toggle]);
+
+/// The label of the entry that deactivates the settings profiles.
+const DEFAULT_PROFILE_LABEL: &str = "Default";
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(SettingsProfileSelector::register)
+        .detach();
+}
+
+pub struct SettingsProfileSelector {
+    picker: View<Picker<SettingsProfileSelectorDelegate>>,
+}
+
+impl SettingsProfileSelector {
+    fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &Toggle, cx| {
+            workspace.toggle_modal(cx, SettingsProfileSelector::new);
+        });
+    }
+
+    fn new(cx: &mut ViewContext<Self>) -> Self {
+        let delegate = SettingsProfileSelectorDelegate::new(cx.view().downgrade(), cx);
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+}
+
+impl Render for SettingsProfileSelector {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for SettingsProfileSelector {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SettingsProfileSelector {}
+impl ModalView for SettingsProfileSelector {}
+
+pub struct SettingsProfileSelectorDelegate {
+    settings_profile_selector: WeakView<SettingsProfileSelector>,
+    /// The profile names, preceded by `None` for the default settings.
+    profiles: Vec<Option<String>>,
+    active_profile: Option<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SettingsProfileSelectorDelegate {
+    fn new(settings_profile_selector: WeakView<SettingsProfileSelector>, cx: &AppContext) -> Self {
+        let store = SettingsStore::global(cx);
+        let active_profile = store.active_profile().map(ToString::to_string);
+        let profiles = [None]
+            .into_iter()
+            .chain(store.profile_names().into_iter().map(Some))
+            .collect::<Vec<_>>();
+        let selected_index = profiles
+            .iter()
+            .position(|profile| *profile == active_profile)
+            .unwrap_or(0);
+
+        Self {
+            settings_profile_selector,
+            profiles,
+            active_profile,
+            matches: Vec::new(),
+            selected_index,
+        }
+    }
+
+    fn profile_label(profile: &Option<String>) -> &str {
+        profile.as_deref().unwrap_or(DEFAULT_PROFILE_LABEL)
+    }
+}
+
+impl PickerDelegate for SettingsProfileSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Select a settings profile...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let profile = self.profiles[mat.candidate_id].clone();
+            SettingsStore::update_global(cx, |store, cx| store.set_active_profile(profile, cx))
+                .log_err();
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.settings_profile_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| StringMatchCandidate::new(id, Self::profile_label(profile).into()))
+            .collect::<Vec<_>>();
+
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let mut label = mat.string.clone();
+        if self.profiles[mat.candidate_id] == self.active_profile {
+            label.push_str(" (active)");
+        }
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(label, mat.positions.clone())),
+        )
+    }
+}
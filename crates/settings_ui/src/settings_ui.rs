@@ -1,16 +1,26 @@
 mod appearance_settings_controls;
+mod general_settings_controls;
+mod keymap_editor;
+mod settings_profile_selector;
+mod vscode_import;
 
 use std::any::TypeId;
 
 use command_palette_hooks::CommandPaletteFilter;
-use editor::EditorSettingsControls;
+use editor::{Editor, EditorEvent, EditorSettingsControls};
 use feature_flags::{FeatureFlag, FeatureFlagViewExt};
-use gpui::{actions, AppContext, EventEmitter, FocusHandle, FocusableView, View};
-use ui::prelude::*;
+use gpui::{actions, AppContext, EventEmitter, FocusHandle, FocusableView, Subscription, View};
+use settings::SettingsStore;
+use terminal_view::TerminalSettingsControls;
+use ui::{prelude::*, SettingsFilter};
 use workspace::item::{Item, ItemEvent};
 use workspace::Workspace;
 
 use crate::appearance_settings_controls::AppearanceSettingsControls;
+use crate::general_settings_controls::GeneralSettingsControls;
+
+pub use keymap_editor::OpenKeymapEditor;
+pub use vscode_import::ImportVsCodeSettings;
 
 pub struct SettingsUiFeatureFlag;
 
@@ -21,6 +31,8 @@ impl FeatureFlag for SettingsUiFeatureFlag {
 actions!(zed, [OpenSettingsEditor]);
 
 pub fn init(cx: &mut AppContext) {
+    settings_profile_selector::init(cx);
+
     cx.observe_new_views(|workspace: &mut Workspace, cx| {
         workspace.register_action(|workspace, _: &OpenSettingsEditor, cx| {
             let existing = workspace
@@ -36,8 +48,17 @@ pub fn init(cx: &mut AppContext) {
                 workspace.add_item_to_active_pane(Box::new(settings_page), None, true, cx)
             }
         });
+        workspace.register_action(|workspace, _: &OpenKeymapEditor, cx| {
+            keymap_editor::open_keymap_editor(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &ImportVsCodeSettings, cx| {
+            vscode_import::import_vscode_settings(workspace, cx);
+        });
 
-        let settings_ui_actions = [TypeId::of::<OpenSettingsEditor>()];
+        let settings_ui_actions = [
+            TypeId::of::<OpenSettingsEditor>(),
+            TypeId::of::<OpenKeymapEditor>(),
+        ];
 
         CommandPaletteFilter::update_global(cx, |filter, _cx| {
             filter.hide_action_types(&settings_ui_actions);
@@ -61,12 +82,46 @@ pub fn init(cx: &mut AppContext) {
 
 pub struct SettingsPage {
     focus_handle: FocusHandle,
+    search_editor: View<Editor>,
+    appearance_settings_controls: View<AppearanceSettingsControls>,
+    editor_settings_controls: View<EditorSettingsControls>,
+    terminal_settings_controls: View<TerminalSettingsControls>,
+    general_settings_controls: View<GeneralSettingsControls>,
+    _search_editor_subscription: Subscription,
 }
 
 impl SettingsPage {
-    pub fn new(_workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
-        cx.new_view(|cx| Self {
-            focus_handle: cx.focus_handle(),
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let languages = workspace.app_state().languages.clone();
+
+        cx.new_view(|cx| {
+            SettingsFilter::set_query("", cx);
+
+            let search_editor = cx.new_view(|cx| {
+                let mut editor = Editor::single_line(cx);
+                editor.set_placeholder_text("Search settings…", cx);
+                editor
+            });
+            let _search_editor_subscription = cx.subscribe(
+                &search_editor,
+                |_, search_editor, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        SettingsFilter::set_query(search_editor.read(cx).text(cx), cx);
+                        cx.notify();
+                    }
+                },
+            );
+
+            Self {
+                focus_handle: cx.focus_handle(),
+                search_editor,
+                appearance_settings_controls: cx.new_view(AppearanceSettingsControls::new),
+                editor_settings_controls: cx
+                    .new_view(|cx| EditorSettingsControls::new(languages, cx)),
+                terminal_settings_controls: cx.new_view(TerminalSettingsControls::new),
+                general_settings_controls: cx.new_view(GeneralSettingsControls::new),
+                _search_editor_subscription,
+            }
         })
     }
 }
@@ -100,23 +155,57 @@ impl Item for SettingsPage {
 }
 
 impl Render for SettingsPage {
-    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let active_profile = SettingsStore::global(cx).active_profile();
+
         v_flex()
             .p_4()
             .size_full()
             .gap_4()
-            .child(Label::new("Settings").size(LabelSize::Large))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new("Settings").size(LabelSize::Large))
+                    .when_some(active_profile, |this, profile| {
+                        this.child(
+                            Label::new(format!("Editing profile: {profile}")).color(Color::Muted),
+                        )
+                    })
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("open-keymap-editor", "Edit Key Bindings")
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(OpenKeymapEditor))),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Icon::new(IconName::MagnifyingGlass))
+                    .child(self.search_editor.clone()),
+            )
             .child(
                 v_flex()
                     .gap_1()
                     .child(Label::new("Appearance"))
-                    .child(AppearanceSettingsControls::new()),
+                    .child(self.appearance_settings_controls.clone()),
             )
             .child(
                 v_flex()
                     .gap_1()
                     .child(Label::new("Editor"))
-                    .child(EditorSettingsControls::new()),
+                    .child(self.editor_settings_controls.clone()),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Label::new("Terminal"))
+                    .child(self.terminal_settings_controls.clone()),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Label::new("General"))
+                    .child(self.general_settings_controls.clone()),
             )
     }
 }
@@ -0,0 +1,500 @@
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use collections::HashSet;
+use editor::{Editor, MultiBuffer};
+use fs::Fs;
+use gpui::{actions, AppContext, ViewContext, VisualContext};
+use language::language_settings::{
+    AllLanguageSettings, FormatOnSave, LanguageSettingsContent, SoftWrap,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+use settings::{parse_json_with_comments, update_settings_file, KeymapFile};
+use theme::{ThemeRegistry, ThemeSelection, ThemeSettings, ThemeSettingsContent};
+use util::{merge_non_null_json_value_into, ResultExt};
+use workspace::Workspace;
+
+actions!(zed, [ImportVsCodeSettings]);
+
+/// VS Code commands that have an equivalent Zed action.
+const COMMAND_ACTIONS: &[(&str, &str)] = &[
+    ("workbench.action.showCommands", "command_palette::Toggle"),
+    ("workbench.action.quickOpen", "file_finder::Toggle"),
+    ("workbench.action.gotoLine", "go_to_line::Toggle"),
+    ("workbench.action.files.save", "workspace::Save"),
+    ("workbench.action.files.saveAll", "workspace::SaveAll"),
+    (
+        "workbench.action.closeActiveEditor",
+        "pane::CloseActiveItem",
+    ),
+    ("workbench.action.nextEditor", "pane::ActivateNextItem"),
+    ("workbench.action.previousEditor", "pane::ActivatePrevItem"),
+    ("workbench.action.splitEditor", "pane::SplitRight"),
+    (
+        "workbench.action.toggleSidebarVisibility",
+        "workspace::ToggleLeftDock",
+    ),
+    (
+        "workbench.action.terminal.toggleTerminal",
+        "terminal_panel::ToggleFocus",
+    ),
+    ("workbench.action.findInFiles", "pane::DeploySearch"),
+    ("workbench.view.explorer", "project_panel::ToggleFocus"),
+    ("editor.action.formatDocument", "editor::Format"),
+    ("editor.action.commentLine", "editor::ToggleComments"),
+    ("editor.action.rename", "editor::Rename"),
+    ("editor.action.revealDefinition", "editor::GoToDefinition"),
+    ("editor.action.goToReferences", "editor::FindAllReferences"),
+    ("editor.action.quickFix", "editor::ToggleCodeActions"),
+    (
+        "editor.action.copyLinesDownAction",
+        "editor::DuplicateLineDown",
+    ),
+    ("editor.action.moveLinesUpAction", "editor::MoveLineUp"),
+    ("editor.action.moveLinesDownAction", "editor::MoveLineDown"),
+    ("editor.action.deleteLines", "editor::DeleteLine"),
+    (
+        "editor.action.addSelectionToNextFindMatch",
+        "editor::SelectNext",
+    ),
+    ("editor.action.selectHighlights", "editor::SelectAllMatches"),
+    (
+        "editor.action.insertCursorAbove",
+        "editor::AddSelectionAbove",
+    ),
+    (
+        "editor.action.insertCursorBelow",
+        "editor::AddSelectionBelow",
+    ),
+    ("editor.action.triggerSuggest", "editor::ShowCompletions"),
+    ("editor.action.showHover", "editor::Hover"),
+    ("editor.fold", "editor::Fold"),
+    ("editor.unfold", "editor::UnfoldLines"),
+    ("actions.find", "buffer_search::Deploy"),
+];
+
+/// The outcome of an import, listing which VS Code settings and key bindings were
+/// migrated and which were not.
+#[derive(Default)]
+struct ImportReport {
+    imported: Vec<String>,
+    skipped: Vec<String>,
+}
+
+impl ImportReport {
+    fn to_markdown(&self) -> String {
+        let mut text = String::from("# VS Code Import\n\n");
+        text.push_str(&format!(
+            "Imported from `{}`.\n",
+            paths::vscode_user_dir().display()
+        ));
+        for (title, entries) in [
+            ("Imported", &self.imported),
+            ("Not Imported", &self.skipped),
+        ] {
+            text.push_str(&format!("\n## {title}\n\n"));
+            if entries.is_empty() {
+                text.push_str("Nothing.\n");
+            }
+            for entry in entries {
+                text.push_str(&format!("- {entry}\n"));
+            }
+        }
+        text
+    }
+}
+
+pub fn import_vscode_settings(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let fs = workspace.app_state().fs.clone();
+    let markdown = workspace
+        .app_state()
+        .languages
+        .language_for_name("Markdown");
+
+    cx.spawn(|workspace, mut cx| async move {
+        let mut report = ImportReport::default();
+
+        if let Some(settings) = load_vscode_file(&fs, paths::vscode_settings_file()).await? {
+            cx.update(|cx| import_settings(&settings, fs.clone(), &mut report, cx))?;
+        }
+        if let Some(keybindings) = load_vscode_file(&fs, paths::vscode_keybindings_file()).await? {
+            import_keybindings(&keybindings, &fs, &mut report).await?;
+        }
+
+        let markdown = markdown.await.log_err();
+        let report = report.to_markdown();
+        workspace
+            .update(&mut cx, |workspace, cx| {
+                workspace.with_local_workspace(cx, move |workspace, cx| {
+                    let project = workspace.project().clone();
+                    let buffer = project.update(cx, |project, cx| {
+                        project.create_local_buffer(&report, markdown, cx)
+                    });
+                    let buffer = cx.new_model(|cx| {
+                        MultiBuffer::singleton(buffer, cx).with_title("VS Code Import".into())
+                    });
+                    workspace.add_item_to_active_pane(
+                        Box::new(cx.new_view(|cx| {
+                            Editor::for_multibuffer(buffer, Some(project), true, cx)
+                        })),
+                        None,
+                        true,
+                        cx,
+                    );
+                })
+            })?
+            .await
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Loads a VS Code configuration file, returning `None` if it doesn't exist.
+async fn load_vscode_file(fs: &Arc<dyn Fs>, path: &Path) -> Result<Option<Value>> {
+    if !fs.is_file(path).await {
+        return Ok(None);
+    }
+    let text = fs.load(path).await?;
+    Ok(Some(parse_json_with_comments(&text)?))
+}
+
+fn import_settings(settings: &Value, fs: Arc<dyn Fs>, report: &mut ImportReport, cx: &AppContext) {
+    let Some(settings) = settings.as_object() else {
+        return;
+    };
+
+    let mut theme_settings = ThemeSettingsContent::default();
+    let mut language_settings = LanguageSettingsContent::default();
+    for (key, value) in settings {
+        let imported = match key.as_str() {
+            "editor.fontFamily" => {
+                theme_settings.buffer_font_family =
+                    value.as_str().and_then(first_font_family).map(Into::into);
+                theme_settings.buffer_font_family.is_some()
+            }
+            "editor.fontSize" => {
+                theme_settings.buffer_font_size = value.as_f64().map(|size| size as f32);
+                theme_settings.buffer_font_size.is_some()
+            }
+            "workbench.colorTheme" => {
+                // VS Code themes can only be used if Zed has a theme with the same name.
+                theme_settings.theme = value
+                    .as_str()
+                    .filter(|name| ThemeRegistry::global(cx).get(name).is_ok())
+                    .map(|name| ThemeSelection::Static(name.into()));
+                theme_settings.theme.is_some()
+            }
+            "editor.tabSize" => {
+                language_settings.tab_size = value
+                    .as_u64()
+                    .and_then(|size| NonZeroU32::new(u32::try_from(size).ok()?));
+                language_settings.tab_size.is_some()
+            }
+            "editor.insertSpaces" => {
+                language_settings.hard_tabs = value.as_bool().map(|insert_spaces| !insert_spaces);
+                language_settings.hard_tabs.is_some()
+            }
+            "editor.wordWrap" => {
+                language_settings.soft_wrap = match value.as_str() {
+                    Some("off") => Some(SoftWrap::None),
+                    Some("on") => Some(SoftWrap::EditorWidth),
+                    Some("wordWrapColumn" | "bounded") => Some(SoftWrap::PreferredLineLength),
+                    _ => None,
+                };
+                language_settings.soft_wrap.is_some()
+            }
+            "editor.wordWrapColumn" => {
+                language_settings.preferred_line_length =
+                    value.as_u64().and_then(|column| u32::try_from(column).ok());
+                language_settings.preferred_line_length.is_some()
+            }
+            "editor.formatOnSave" => {
+                language_settings.format_on_save = value.as_bool().map(|format_on_save| {
+                    if format_on_save {
+                        FormatOnSave::On
+                    } else {
+                        FormatOnSave::Off
+                    }
+                });
+                language_settings.format_on_save.is_some()
+            }
+            _ => false,
+        };
+
+        if imported {
+            report.imported.push(format!("`{key}`"));
+        } else {
+            report.skipped.push(format!("`{key}`"));
+        }
+    }
+
+    if has_values(&theme_settings) {
+        update_settings_file::<ThemeSettings>(fs.clone(), cx, move |content, _| {
+            merge_into(content, &theme_settings);
+        });
+    }
+    if has_values(&language_settings) {
+        update_settings_file::<AllLanguageSettings>(fs, cx, move |content, _| {
+            merge_into(&mut content.defaults, &language_settings);
+        });
+    }
+}
+
+/// Returns whether any of the fields of the settings file contents are set.
+fn has_values<T: Serialize>(content: &T) -> bool {
+    serde_json::to_value(content).map_or(false, |value| {
+        value.as_object().map_or(false, |object| {
+            object.values().any(|value| !value.is_null())
+        })
+    })
+}
+
+/// Sets the fields of `content` that are set in `imported`.
+fn merge_into<T: Serialize + DeserializeOwned>(content: &mut T, imported: &T) {
+    let (Ok(mut value), Ok(imported)) = (
+        serde_json::to_value(&*content),
+        serde_json::to_value(imported),
+    ) else {
+        return;
+    };
+    merge_non_null_json_value_into(imported, &mut value);
+    if let Some(merged) = serde_json::from_value(value).log_err() {
+        *content = merged;
+    }
+}
+
+/// Returns the first family of a CSS-style font family list, such as
+/// `"'Fira Code', Menlo, monospace"`.
+fn first_font_family(families: &str) -> Option<&str> {
+    let family = families.split(',').next()?.trim();
+    let family = family.trim_matches(|c| c == '\'' || c == '"');
+    (!family.is_empty()).then_some(family)
+}
+
+/// Converts a VS Code key binding, such as `"ctrl+k ctrl+c"`, into a Zed keystroke
+/// sequence.
+fn vscode_keystrokes(key: &str) -> String {
+    key.split_whitespace()
+        .map(|chord| {
+            chord
+                .split('+')
+                .map(|key| match key {
+                    "meta" | "win" => "cmd",
+                    key => key,
+                })
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+async fn import_keybindings(
+    keybindings: &Value,
+    fs: &Arc<dyn Fs>,
+    report: &mut ImportReport,
+) -> Result<()> {
+    let keymap_path = paths::keymap_file();
+    let keymap = if fs.is_file(keymap_path).await {
+        fs.load(keymap_path).await?
+    } else {
+        String::new()
+    };
+
+    let blocks = keymap_blocks(keybindings, &bound_keystrokes(&keymap), report);
+    if blocks.is_empty() {
+        return Ok(());
+    }
+    KeymapFile::append_to_user_keymap(fs, &blocks).await
+}
+
+/// Returns the keystrokes bound in the given keymap, with the context of their binding.
+fn bound_keystrokes(keymap: &str) -> HashSet<(Option<String>, String)> {
+    let blocks = if keymap.trim().is_empty() {
+        Vec::new()
+    } else {
+        parse_json_with_comments::<Vec<Value>>(keymap)
+            .log_err()
+            .unwrap_or_default()
+    };
+
+    let mut keystrokes = HashSet::default();
+    for block in blocks {
+        let context = block
+            .get("context")
+            .and_then(Value::as_str)
+            .map(ToString::to_string);
+        if let Some(bindings) = block.get("bindings").and_then(Value::as_object) {
+            keystrokes.extend(
+                bindings
+                    .keys()
+                    .map(|keystroke| (context.clone(), keystroke.clone())),
+            );
+        }
+    }
+    keystrokes
+}
+
+/// Converts VS Code key bindings into keymap blocks, leaving out the keystrokes that are
+/// already bound in the same context, such as the ones of a previous import.
+fn keymap_blocks(
+    keybindings: &Value,
+    bound_keystrokes: &HashSet<(Option<String>, String)>,
+    report: &mut ImportReport,
+) -> Vec<Value> {
+    let Some(keybindings) = keybindings.as_array() else {
+        return Vec::new();
+    };
+
+    let mut global_bindings = Map::new();
+    let mut editor_bindings = Map::new();
+    for keybinding in keybindings {
+        let (Some(key), Some(command)) = (
+            keybinding.get("key").and_then(Value::as_str),
+            keybinding.get("command").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let description = format!("`{key}`: `{command}`");
+
+        let (context, bindings) = match keybinding.get("when").and_then(Value::as_str) {
+            None => (None, &mut global_bindings),
+            Some("editorTextFocus") => (Some("Editor"), &mut editor_bindings),
+            Some(_) => {
+                report.skipped.push(description);
+                continue;
+            }
+        };
+        let Some((_, action)) = COMMAND_ACTIONS
+            .iter()
+            .find(|(vscode_command, _)| *vscode_command == command)
+        else {
+            report.skipped.push(description);
+            continue;
+        };
+
+        let keystrokes = vscode_keystrokes(key);
+        if bound_keystrokes.contains(&(context.map(ToString::to_string), keystrokes.clone())) {
+            report
+                .skipped
+                .push(format!("{description} (already bound in your keymap)"));
+            continue;
+        }
+        bindings.insert(keystrokes, json!(action));
+        report.imported.push(description);
+    }
+
+    let mut blocks = Vec::new();
+    if !global_bindings.is_empty() {
+        blocks.push(json!({ "bindings": global_bindings }));
+    }
+    if !editor_bindings.is_empty() {
+        blocks.push(json!({ "context": "Editor", "bindings": editor_bindings }));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use language::language_settings::LanguageSettingsContent;
+
+    #[test]
+    fn test_first_font_family() {
+        assert_eq!(
+            first_font_family("'Fira Code', Menlo, monospace"),
+            Some("Fira Code")
+        );
+        assert_eq!(
+            first_font_family("\"JetBrains Mono\""),
+            Some("JetBrains Mono")
+        );
+        assert_eq!(first_font_family(" Menlo "), Some("Menlo"));
+        assert_eq!(first_font_family(""), None);
+        assert_eq!(first_font_family("'', Menlo"), None);
+    }
+
+    #[test]
+    fn test_vscode_keystrokes() {
+        assert_eq!(vscode_keystrokes("ctrl+shift+p"), "ctrl-shift-p");
+        assert_eq!(vscode_keystrokes("ctrl+k ctrl+c"), "ctrl-k ctrl-c");
+        assert_eq!(vscode_keystrokes("meta+s"), "cmd-s");
+        assert_eq!(vscode_keystrokes("win+alt+up"), "cmd-alt-up");
+        assert_eq!(vscode_keystrokes("f12"), "f12");
+    }
+
+    #[test]
+    fn test_keymap_blocks() {
+        let keybindings = json!([
+            { "key": "ctrl+p", "command": "workbench.action.quickOpen" },
+            { "key": "ctrl+s", "command": "workbench.action.files.save" },
+            { "key": "ctrl+/", "command": "editor.action.commentLine", "when": "editorTextFocus" },
+            { "key": "ctrl+d", "command": "editor.action.deleteLines", "when": "editorTextFocus" },
+            { "key": "ctrl+b", "command": "unknown.command" },
+            { "key": "ctrl+f", "command": "actions.find", "when": "terminalFocus" },
+        ]);
+        let keymap = r#"[
+            // Bindings from a previous import.
+            { "bindings": { "ctrl-s": "workspace::Save" } },
+            { "context": "Editor", "bindings": { "ctrl-d": "editor::SelectNext" } },
+        ]"#;
+
+        let mut report = ImportReport::default();
+        let blocks = keymap_blocks(&keybindings, &bound_keystrokes(keymap), &mut report);
+        assert_eq!(
+            blocks,
+            vec![
+                json!({ "bindings": { "ctrl-p": "file_finder::Toggle" } }),
+                json!({ "context": "Editor", "bindings": { "ctrl-/": "editor::ToggleComments" } }),
+            ]
+        );
+        assert_eq!(
+            report.imported,
+            [
+                "`ctrl+p`: `workbench.action.quickOpen`",
+                "`ctrl+/`: `editor.action.commentLine`",
+            ]
+        );
+        assert_eq!(
+            report.skipped,
+            [
+                "`ctrl+s`: `workbench.action.files.save` (already bound in your keymap)",
+                "`ctrl+d`: `editor.action.deleteLines` (already bound in your keymap)",
+                "`ctrl+b`: `unknown.command`",
+                "`ctrl+f`: `actions.find`",
+            ]
+        );
+
+        // Importing the same key bindings again doesn't add anything.
+        let mut bound = bound_keystrokes(keymap);
+        bound.extend(bound_keystrokes(&serde_json::to_string(&blocks).unwrap()));
+        let mut report = ImportReport::default();
+        assert_eq!(
+            keymap_blocks(&keybindings, &bound, &mut report),
+            Vec::<Value>::new()
+        );
+        assert!(report.imported.is_empty());
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let mut content = LanguageSettingsContent {
+            tab_size: NonZeroU32::new(2),
+            hard_tabs: Some(true),
+            ..Default::default()
+        };
+        let imported = LanguageSettingsContent {
+            tab_size: NonZeroU32::new(4),
+            format_on_save: Some(FormatOnSave::Off),
+            ..Default::default()
+        };
+        merge_into(&mut content, &imported);
+
+        assert_eq!(content.tab_size, NonZeroU32::new(4));
+        assert_eq!(content.hard_tabs, Some(true));
+        assert!(matches!(content.format_on_save, Some(FormatOnSave::Off)));
+        assert!(content.soft_wrap.is_none());
+    }
+}
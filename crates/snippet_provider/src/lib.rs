@@ -74,7 +74,7 @@ async fn process_updates(
         }
         let entry_metadata = fs.metadata(&entry_path).await;
         // Entry could have been removed, in which case we should no longer show completions for it.
-        let entry_exists = entry_metadata.is_ok();
+        let entry_exists = entry_metadata.as_ref().map_or(false, Option::is_some);
         if entry_metadata.map_or(false, |entry| entry.map_or(false, |e| e.is_dir)) {
             // Don't process dirs.
             continue;
@@ -96,7 +96,8 @@ async fn process_updates(
                 let Some(file_contents) = contents else {
                     return;
                 };
-                let Ok(as_json) = serde_json::from_str::<VSSnippetsFile>(&file_contents) else {
+                let Ok(as_json) = serde_json_lenient::from_str::<VSSnippetsFile>(&file_contents)
+                else {
                     return;
                 };
                 let snippets = file_to_snippets(as_json);
@@ -175,6 +176,27 @@ impl SnippetProvider {
         })
     }
 
+    /// Reloads the snippets of the given files, removing the ones of the files that no
+    /// longer exist.
+    pub fn reload_files(&mut self, paths: Vec<PathBuf>, cx: &mut ModelContext<Self>) {
+        cx.spawn(|this, cx| process_updates(this, paths, cx))
+            .detach_and_log_err(cx);
+    }
+
+    /// Removes the snippets of the files in the given directory.
+    pub fn remove_directory(&mut self, dir: &Path) {
+        for snippets_of_kind in self.snippets.values_mut() {
+            snippets_of_kind.retain(|path, _| !path.starts_with(dir));
+        }
+    }
+
+    /// Returns the number of snippets defined for the given kind in the snippets files.
+    pub fn snippet_count(&self, kind: &SnippetKind) -> usize {
+        self.snippets
+            .get(kind)
+            .map_or(0, |files| files.values().map(Vec::len).sum())
+    }
+
     fn lookup_snippets<'a>(
         &'a self,
         language: &'a SnippetKind,
@@ -0,0 +1,369 @@
+use std::{path::PathBuf, sync::Arc};
+
+use fs::Fs;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, Model, ParentElement, Render, Styled, View, ViewContext, VisualContext,
+    WeakView,
+};
+use language::{language_name_to_lsp_id, LanguageRegistry};
+use paths::local_snippets_folder_relative_path;
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use text::{LineEnding, Rope};
+use ui::{prelude::*, HighlightedLabel, KeyBinding, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{
+    notifications::{MessageNotification, NotificationId},
+    ModalView, Workspace,
+};
+
+actions!(snippets, [ConfigureSnippets, OpenFolder]);
+
+/// The content of a newly created snippets file.
+const NEW_SNIPPETS_FILE_CONTENT: &str = r#"{
+  // Each snippet is keyed by its name, and is inserted by completing one of its
+  // prefixes. Its body can contain tab stops like `$1` and placeholders like
+  // `${2:default}`. For example:
+  //
+  // "Log to the console": {
+  //   "prefix": "log",
+  //   "body": ["console.log($1);"],
+  //   "description": "Logs a value to the console"
+  // }
+}
+"#;
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+    workspace.register_action(configure_snippets);
+    workspace.register_action(open_folder);
+}
+
+fn configure_snippets(
+    workspace: &mut Workspace,
+    _: &ConfigureSnippets,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let language_registry = workspace.app_state().languages.clone();
+    let fs = workspace.app_state().fs.clone();
+    let project = workspace.project().clone();
+    let workspace_handle = cx.view().downgrade();
+    workspace.toggle_modal(cx, move |cx| {
+        ScopeSelector::new(language_registry, fs, project, workspace_handle, cx)
+    });
+}
+
+fn open_folder(workspace: &mut Workspace, _: &OpenFolder, cx: &mut ViewContext<Workspace>) {
+    let fs = workspace.app_state().fs.clone();
+    cx.spawn(|_, mut cx| async move {
+        fs.create_dir(paths::snippets_dir()).await?;
+        cx.update(|cx| cx.reveal_path(paths::snippets_dir()))
+    })
+    .detach_and_log_err(cx);
+}
+
+/// A modal listing the scopes snippets can be defined for, which opens the snippets file of
+/// the picked scope, creating it when needed.
+pub struct ScopeSelector {
+    picker: View<Picker<ScopeSelectorDelegate>>,
+}
+
+impl ScopeSelector {
+    fn new(
+        language_registry: Arc<LanguageRegistry>,
+        fs: Arc<dyn Fs>,
+        project: Model<Project>,
+        workspace: WeakView<Workspace>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let delegate = ScopeSelectorDelegate::new(
+            cx.view().downgrade(),
+            workspace,
+            fs,
+            project,
+            language_registry,
+            cx,
+        );
+
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+}
+
+impl Render for ScopeSelector {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for ScopeSelector {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ScopeSelector {}
+impl ModalView for ScopeSelector {}
+
+/// The snippets of a language, or the global snippets available in every language.
+struct SnippetScope {
+    label: String,
+    /// The stem of the scope's snippets files.
+    file_stem: String,
+    snippet_count: usize,
+}
+
+pub struct ScopeSelectorDelegate {
+    scope_selector: WeakView<ScopeSelector>,
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    /// The snippets folder of the project's first local folder.
+    project_snippets_dir: Option<PathBuf>,
+    scopes: Vec<SnippetScope>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ScopeSelectorDelegate {
+    fn new(
+        scope_selector: WeakView<ScopeSelector>,
+        workspace: WeakView<Workspace>,
+        fs: Arc<dyn Fs>,
+        project: Model<Project>,
+        language_registry: Arc<LanguageRegistry>,
+        cx: &mut ViewContext<ScopeSelector>,
+    ) -> Self {
+        let project = project.read(cx);
+        let snippets = project.snippets().read(cx);
+        let scopes = [("Global".to_string(), None)]
+            .into_iter()
+            .chain(language_registry.language_names().into_iter().map(|name| {
+                let lsp_id = language_name_to_lsp_id(&name);
+                (name, Some(lsp_id))
+            }))
+            .map(|(label, kind)| SnippetScope {
+                label,
+                file_stem: kind.clone().unwrap_or_else(|| "snippets".to_string()),
+                snippet_count: snippets.snippet_count(&kind),
+            })
+            .collect::<Vec<_>>();
+        let candidates = scopes
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, scope)| {
+                StringMatchCandidate::new(candidate_id, scope.label.clone())
+            })
+            .collect::<Vec<_>>();
+        let project_snippets_dir = project.visible_worktrees(cx).find_map(|worktree| {
+            let worktree = worktree.read(cx);
+            (worktree.is_local() && worktree.root_entry()?.is_dir()).then(|| {
+                worktree
+                    .abs_path()
+                    .join(local_snippets_folder_relative_path())
+            })
+        });
+
+        Self {
+            scope_selector,
+            workspace,
+            fs,
+            project_snippets_dir,
+            scopes,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for ScopeSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Select the snippets to configure...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(mat) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let scope = &self.scopes[mat.candidate_id];
+        let dir = if secondary {
+            let Some(dir) = self.project_snippets_dir.clone() else {
+                struct NoOpenFolders;
+
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.show_notification(
+                            NotificationId::unique::<NoOpenFolders>(),
+                            cx,
+                            |cx| {
+                                cx.new_view(|_| {
+                                    MessageNotification::new("This project has no folders open.")
+                                })
+                            },
+                        )
+                    })
+                    .log_err();
+                self.dismissed(cx);
+                return;
+            };
+            dir
+        } else {
+            paths::snippets_dir().clone()
+        };
+        let path = dir.join(format!("{}.json", scope.file_stem));
+
+        let fs = self.fs.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            if !fs.is_file(&path).await {
+                fs.create_dir(&dir).await?;
+                fs.save(
+                    &path,
+                    &Rope::from(NEW_SNIPPETS_FILE_CONTENT),
+                    LineEnding::default(),
+                )
+                .await?;
+            }
+            workspace
+                .update(&mut cx, |workspace, cx| {
+                    workspace.open_abs_path(path, false, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.scope_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let scope = &self.scopes[mat.candidate_id];
+        let snippet_count = match scope.snippet_count {
+            0 => None,
+            1 => Some("1 snippet".to_string()),
+            count => Some(format!("{count} snippets")),
+        };
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                ))
+                .end_slot::<Label>(snippet_count.map(|snippet_count| {
+                    Label::new(snippet_count)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                })),
+        )
+    }
+
+    fn render_footer(&self, cx: &mut ViewContext<Picker<Self>>) -> Option<AnyElement> {
+        Some(
+            h_flex()
+                .w_full()
+                .h_8()
+                .p_2()
+                .gap_2()
+                .justify_end()
+                .rounded_b_md()
+                .bg(cx.theme().colors().ghost_element_selected)
+                .when(self.project_snippets_dir.is_some(), |this| {
+                    this.children(KeyBinding::for_action(&menu::SecondaryConfirm, cx).map(
+                        |keybind| {
+                            Button::new("open-project-snippets", "Open Project Snippets")
+                                .label_size(LabelSize::Small)
+                                .key_binding(keybind)
+                                .on_click(|_, cx| {
+                                    cx.dispatch_action(menu::SecondaryConfirm.boxed_clone())
+                                })
+                        },
+                    ))
+                })
+                .children(KeyBinding::for_action(&menu::Confirm, cx).map(|keybind| {
+                    Button::new("open-global-snippets", "Open Global Snippets")
+                        .label_size(LabelSize::Small)
+                        .key_binding(keybind)
+                        .on_click(|_, cx| cx.dispatch_action(menu::Confirm.boxed_clone()))
+                }))
+                .into_any_element(),
+        )
+    }
+}
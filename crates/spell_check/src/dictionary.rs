@@ -0,0 +1,566 @@
+use std::iter;
+
+use anyhow::{anyhow, Result};
+use collections::{HashMap, HashSet};
+
+/// The maximum number of suggestions returned for a misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+type Flag = u32;
+
+/// A dictionary in the Hunspell format, made of an affix file (`.aff`) describing how words
+/// can be derived from the ones of the dictionary file (`.dic`).
+///
+/// Only a subset of the format is supported: prefixes and suffixes, including their cross
+/// products, the forbidden and "need affix" flags, as well as the `TRY` and `REP`
+/// directives used to suggest corrections. Compound words aren't supported.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashMap<String, Vec<Flag>>,
+    /// The prefixes, by the text they add to the start of words.
+    prefixes: HashMap<String, Vec<Affix>>,
+    /// The suffixes, by the text they add to the end of words.
+    suffixes: HashMap<String, Vec<Affix>>,
+    forbidden_word_flag: Option<Flag>,
+    need_affix_flag: Option<Flag>,
+    /// The characters tried when looking for corrections, from the most common one.
+    try_chars: Vec<char>,
+    /// Common misspellings, replaced when looking for corrections.
+    replacements: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagFormat {
+    /// One character per flag.
+    Char,
+    /// Two characters per flag.
+    Long,
+    /// Comma-separated numbers.
+    Num,
+}
+
+#[derive(Debug)]
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    /// The text removed from the word before adding the affix.
+    strip: String,
+    condition: Vec<CharPattern>,
+}
+
+/// A character of an affix condition.
+#[derive(Debug)]
+enum CharPattern {
+    Any,
+    Char(char),
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl CharPattern {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Char(expected) => c == *expected,
+            Self::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Casing {
+    Lower,
+    Capitalized,
+    Upper,
+    Mixed,
+}
+
+impl Dictionary {
+    /// Parses a dictionary from the content of its affix and dictionary files.
+    pub fn new(aff: &[u8], dic: &[u8]) -> Result<Self> {
+        let aff = decode(aff, None);
+        let encoding = aff
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("SET "));
+        let dic = decode(dic, encoding.map(str::trim));
+
+        let mut dictionary = Self::default();
+        let mut flag_format = FlagFormat::Char;
+        let mut lines = aff.lines().map(str::trim);
+        while let Some(line) = lines.next() {
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("FLAG") => {
+                    flag_format = match fields.next() {
+                        Some("long") => FlagFormat::Long,
+                        Some("num") => FlagFormat::Num,
+                        _ => FlagFormat::Char,
+                    }
+                }
+                Some("FORBIDDENWORD") => {
+                    dictionary.forbidden_word_flag = fields
+                        .next()
+                        .and_then(|flags| parse_flags(flags, flag_format).first().copied());
+                }
+                Some("NEEDAFFIX") => {
+                    dictionary.need_affix_flag = fields
+                        .next()
+                        .and_then(|flags| parse_flags(flags, flag_format).first().copied());
+                }
+                Some("TRY") => {
+                    dictionary.try_chars = fields.next().unwrap_or_default().chars().collect();
+                }
+                Some("REP") => {
+                    if let (Some(from), Some(to)) = (fields.next(), fields.next()) {
+                        // The header line only has the number of replacements.
+                        dictionary
+                            .replacements
+                            .push((from.replace('_', " "), to.replace('_', " ")));
+                    }
+                }
+                Some(kind @ ("PFX" | "SFX")) => {
+                    let (Some(flag), Some(cross_product), Some(count)) =
+                        (fields.next(), fields.next(), fields.next())
+                    else {
+                        continue;
+                    };
+                    let Some(&flag) = parse_flags(flag, flag_format).first() else {
+                        continue;
+                    };
+                    let cross_product = cross_product == "Y";
+                    let count = count.parse::<usize>().unwrap_or(0);
+                    let affixes = if kind == "PFX" {
+                        &mut dictionary.prefixes
+                    } else {
+                        &mut dictionary.suffixes
+                    };
+
+                    for line in lines.by_ref().take(count) {
+                        let mut fields = line.split_whitespace().skip(2);
+                        let (Some(strip), Some(add)) = (fields.next(), fields.next()) else {
+                            continue;
+                        };
+                        // Continuation flags, used for words with several suffixes, aren't
+                        // supported.
+                        let add = add.split('/').next().unwrap_or_default();
+                        let condition = fields.next().unwrap_or(".");
+                        affixes
+                            .entry(empty_if_zero(add).to_string())
+                            .or_default()
+                            .push(Affix {
+                                flag,
+                                cross_product,
+                                strip: empty_if_zero(strip).to_string(),
+                                condition: parse_condition(condition),
+                            });
+                    }
+                }
+                _ => {}
+            }
+        }
+        if dictionary.try_chars.is_empty() {
+            dictionary.try_chars = ('a'..='z').collect();
+        }
+
+        let mut lines = dic.lines();
+        // The first line has the approximate number of words.
+        lines.next();
+        for line in lines {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            if entry.is_empty() {
+                continue;
+            }
+            let (word, flags) = split_entry(entry);
+            dictionary
+                .words
+                .entry(word.replace("\\/", "/"))
+                .or_default()
+                .extend(parse_flags(flags, flag_format));
+        }
+
+        if dictionary.words.is_empty() {
+            Err(anyhow!("the dictionary has no words"))
+        } else {
+            Ok(dictionary)
+        }
+    }
+
+    /// Returns whether the word is spelled correctly.
+    pub fn check(&self, word: &str) -> bool {
+        if self.is_forbidden(word) {
+            return false;
+        }
+        if self.check_exact(word) {
+            return true;
+        }
+
+        let lowercase = word.to_lowercase();
+        match casing(word) {
+            Casing::Lower | Casing::Mixed => false,
+            Casing::Capitalized => self.check_exact(&lowercase),
+            Casing::Upper => {
+                self.check_exact(&lowercase) || self.check_exact(&capitalize(&lowercase))
+            }
+        }
+    }
+
+    /// Returns the most likely corrections of a misspelled word.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let casing = casing(word);
+        let lowercase = if casing == Casing::Mixed {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        };
+
+        let mut seen = HashSet::default();
+        let mut suggestions = Vec::new();
+        for candidate in self.candidates(&lowercase) {
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
+            if candidate == lowercase || !seen.insert(candidate.clone()) {
+                continue;
+            }
+            let is_correct = candidate
+                .split(' ')
+                .all(|word| !word.is_empty() && self.check(word));
+            if is_correct {
+                suggestions.push(match casing {
+                    Casing::Capitalized => capitalize(&candidate),
+                    Casing::Upper => candidate.to_uppercase(),
+                    Casing::Lower | Casing::Mixed => candidate,
+                });
+            }
+        }
+        suggestions
+    }
+
+    /// Returns the words one edit away from the given one, from the most likely corrections.
+    fn candidates<'a>(&'a self, word: &'a str) -> impl Iterator<Item = String> + 'a {
+        let chars = word.chars().collect::<Vec<_>>();
+        let replacements = self.replacements.iter().flat_map(move |(from, to)| {
+            word.match_indices(from.as_str())
+                .map(move |(ix, _)| format!("{}{}{}", &word[..ix], to, &word[ix + from.len()..]))
+        });
+        let transpositions = (1..chars.len()).map({
+            let chars = chars.clone();
+            move |ix| {
+                let mut chars = chars.clone();
+                chars.swap(ix - 1, ix);
+                chars.into_iter().collect::<String>()
+            }
+        });
+        let substitutions = (0..chars.len()).flat_map({
+            let chars = chars.clone();
+            move |ix| {
+                let chars = chars.clone();
+                self.try_chars.iter().map(move |&c| {
+                    let mut chars = chars.clone();
+                    chars[ix] = c;
+                    chars.into_iter().collect::<String>()
+                })
+            }
+        });
+        let deletions = (0..chars.len()).map({
+            let chars = chars.clone();
+            move |ix| {
+                let mut chars = chars.clone();
+                chars.remove(ix);
+                chars.into_iter().collect::<String>()
+            }
+        });
+        let insertions = (0..=chars.len()).flat_map({
+            let chars = chars.clone();
+            move |ix| {
+                let chars = chars.clone();
+                self.try_chars.iter().map(move |&c| {
+                    let mut chars = chars.clone();
+                    chars.insert(ix, c);
+                    chars.into_iter().collect::<String>()
+                })
+            }
+        });
+        let splits = (1..chars.len()).map(move |ix| {
+            let (start, end) = chars.split_at(ix);
+            format!(
+                "{} {}",
+                start.iter().collect::<String>(),
+                end.iter().collect::<String>()
+            )
+        });
+
+        replacements
+            .chain(transpositions)
+            .chain(substitutions)
+            .chain(deletions)
+            .chain(insertions)
+            .chain(splits)
+    }
+
+    fn is_forbidden(&self, word: &str) -> bool {
+        self.words
+            .get(word)
+            .map_or(false, |flags| has_flag(flags, self.forbidden_word_flag))
+    }
+
+    /// Returns whether the word, as written, is in the dictionary or derived from one of its
+    /// words with affixes.
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(flags) = self.words.get(word) {
+            if !has_flag(flags, self.forbidden_word_flag) && !has_flag(flags, self.need_affix_flag)
+            {
+                return true;
+            }
+        }
+        self.check_suffixed(word, None) || self.check_prefixed(word)
+    }
+
+    /// Returns whether the word is a word of the dictionary with a suffix, whose flags also
+    /// include the given prefix's.
+    fn check_suffixed(&self, word: &str, prefix: Option<&Affix>) -> bool {
+        let boundaries = word
+            .char_indices()
+            .skip(1)
+            .map(|(ix, _)| ix)
+            .chain(iter::once(word.len()));
+        for ix in boundaries {
+            let Some(suffixes) = self.suffixes.get(&word[ix..]) else {
+                continue;
+            };
+            for suffix in suffixes {
+                if prefix.is_some() && !suffix.cross_product {
+                    continue;
+                }
+                let stem = format!("{}{}", &word[..ix], suffix.strip);
+                if !matches_end(&stem, &suffix.condition) {
+                    continue;
+                }
+                let Some(flags) = self.words.get(&stem) else {
+                    continue;
+                };
+                if flags.contains(&suffix.flag)
+                    && prefix.map_or(true, |prefix| flags.contains(&prefix.flag))
+                    && !has_flag(flags, self.forbidden_word_flag)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns whether the word is a word of the dictionary with a prefix, and possibly a
+    /// suffix.
+    fn check_prefixed(&self, word: &str) -> bool {
+        let boundaries = iter::once(0).chain(word.char_indices().skip(1).map(|(ix, _)| ix));
+        for ix in boundaries {
+            let Some(prefixes) = self.prefixes.get(&word[..ix]) else {
+                continue;
+            };
+            for prefix in prefixes {
+                let stem = format!("{}{}", prefix.strip, &word[ix..]);
+                if !matches_start(&stem, &prefix.condition) {
+                    continue;
+                }
+                let has_prefix = self.words.get(&stem).map_or(false, |flags| {
+                    flags.contains(&prefix.flag) && !has_flag(flags, self.forbidden_word_flag)
+                });
+                if has_prefix || (prefix.cross_product && self.check_suffixed(&stem, Some(prefix)))
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Decodes the content of a dictionary file, which is UTF-8 unless its encoding says
+/// otherwise.
+fn decode(bytes: &[u8], encoding: Option<&str>) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match encoding {
+        // ISO 8859-1 maps each byte to the code point of the same value.
+        Some(encoding) if encoding.to_uppercase().starts_with("ISO8859-1") => {
+            bytes.iter().map(|&byte| byte as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+fn empty_if_zero(text: &str) -> &str {
+    if text == "0" {
+        ""
+    } else {
+        text
+    }
+}
+
+/// Splits a line of the dictionary file into its word and its flags, skipping escaped
+/// slashes.
+fn split_entry(entry: &str) -> (&str, &str) {
+    let mut previous = None;
+    for (ix, c) in entry.char_indices() {
+        if c == '/' && previous != Some('\\') && ix > 0 {
+            return (&entry[..ix], &entry[ix + 1..]);
+        }
+        previous = Some(c);
+    }
+    (entry, "")
+}
+
+fn parse_flags(flags: &str, format: FlagFormat) -> Vec<Flag> {
+    match format {
+        FlagFormat::Char => flags.chars().map(|c| c as Flag).collect(),
+        FlagFormat::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |flag, &c| (flag << 16) | c as Flag))
+            .collect(),
+        FlagFormat::Num => flags
+            .split(',')
+            .filter_map(|flag| flag.trim().parse().ok())
+            .collect(),
+    }
+}
+
+fn has_flag(flags: &[Flag], flag: Option<Flag>) -> bool {
+    flag.map_or(false, |flag| flags.contains(&flag))
+}
+
+fn parse_condition(condition: &str) -> Vec<CharPattern> {
+    if condition == "." {
+        return Vec::new();
+    }
+
+    let mut patterns = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        patterns.push(match c {
+            '.' => CharPattern::Any,
+            '[' => {
+                let mut set = chars.by_ref().take_while(|&c| c != ']').peekable();
+                let negated = set.next_if_eq(&'^').is_some();
+                CharPattern::Set {
+                    chars: set.collect(),
+                    negated,
+                }
+            }
+            c => CharPattern::Char(c),
+        });
+    }
+    patterns
+}
+
+fn matches_start(word: &str, condition: &[CharPattern]) -> bool {
+    let mut chars = word.chars();
+    condition
+        .iter()
+        .all(|pattern| chars.next().map_or(false, |c| pattern.matches(c)))
+}
+
+fn matches_end(word: &str, condition: &[CharPattern]) -> bool {
+    let mut chars = word.chars().rev();
+    condition
+        .iter()
+        .rev()
+        .all(|pattern| chars.next().map_or(false, |c| pattern.matches(c)))
+}
+
+fn casing(word: &str) -> Casing {
+    let mut chars = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = chars.next() else {
+        return Casing::Lower;
+    };
+    let (mut has_lower, mut has_upper) = (false, false);
+    for c in chars {
+        has_lower |= c.is_lowercase();
+        has_upper |= c.is_uppercase();
+    }
+    match (first.is_uppercase(), has_lower, has_upper) {
+        (false, _, false) => Casing::Lower,
+        (true, _, false) => Casing::Capitalized,
+        (true, false, true) => Casing::Upper,
+        _ => Casing::Mixed,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = r#"
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+FORBIDDENWORD !
+
+REP 1
+REP f ph
+
+PFX A Y 1
+PFX A 0 re .
+
+SFX D Y 2
+SFX D 0 ed [^ey]
+SFX D y ied [^aeiou]y
+
+SFX S N 1
+SFX S 0 s .
+"#;
+
+    const DIC: &str = "7
+work/ADS
+carry/AD
+phone/S
+test/S
+and
+or
+and\\/or
+colour/!
+";
+
+    #[test]
+    fn test_check() {
+        let dictionary = Dictionary::new(AFF.as_bytes(), DIC.as_bytes()).unwrap();
+
+        assert!(dictionary.check("work"));
+        assert!(dictionary.check("worked"));
+        assert!(dictionary.check("works"));
+        assert!(dictionary.check("rework"));
+        assert!(dictionary.check("reworked"));
+        assert!(dictionary.check("carried"));
+        assert!(dictionary.check("recarried"));
+        assert!(dictionary.check("and/or"));
+        assert!(!dictionary.check("carryed"));
+        assert!(!dictionary.check("reworks"), "S can't be combined with A");
+        assert!(!dictionary.check("retests"));
+        assert!(
+            !dictionary.check("colour"),
+            "forbidden words are misspelled"
+        );
+
+        // Capitalized and uppercase words are checked in lowercase.
+        assert!(dictionary.check("Worked"));
+        assert!(dictionary.check("WORKED"));
+        assert!(!dictionary.check("wORKED"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let dictionary = Dictionary::new(AFF.as_bytes(), DIC.as_bytes()).unwrap();
+
+        assert_eq!(dictionary.suggest("wrok"), vec!["work"]);
+        assert_eq!(dictionary.suggest("Tset"), vec!["Test"]);
+        assert_eq!(dictionary.suggest("fones"), vec!["phones"]);
+        assert_eq!(dictionary.suggest("testand"), vec!["test and"]);
+        assert!(dictionary.suggest("xyzzy").is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+mod dictionary;
+mod words;
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use collections::{HashMap, HashSet};
+use fs::Fs;
+use gpui::{AppContext, AsyncAppContext, Context as _, Global, Model, ModelContext, Task};
+
+pub use dictionary::Dictionary;
+pub use words::words_to_check;
+
+struct GlobalSpellChecker(Model<SpellChecker>);
+
+impl Global for GlobalSpellChecker {}
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    let spell_checker = cx.new_model(|cx| SpellChecker::new(fs, cx));
+    cx.set_global(GlobalSpellChecker(spell_checker));
+}
+
+/// Loads the dictionaries used to spell check buffers, and keeps the words the user added
+/// to their own dictionary.
+pub struct SpellChecker {
+    fs: Arc<dyn Fs>,
+    dictionaries: HashMap<String, DictionaryState>,
+    user_words: Arc<HashSet<String>>,
+}
+
+enum DictionaryState {
+    Loading {
+        _load: Task<()>,
+    },
+    Loaded(Arc<Dictionary>),
+    /// The dictionary couldn't be found or parsed.
+    Unavailable,
+}
+
+impl SpellChecker {
+    pub fn global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalSpellChecker>()
+            .map(|spell_checker| spell_checker.0.clone())
+    }
+
+    fn new(fs: Arc<dyn Fs>, cx: &mut ModelContext<Self>) -> Self {
+        cx.spawn({
+            let fs = fs.clone();
+            |this, mut cx| async move {
+                let Ok(content) = fs.load(paths::user_dictionary_file()).await else {
+                    return;
+                };
+                this.update(&mut cx, |this, cx| {
+                    Arc::make_mut(&mut this.user_words).extend(
+                        content
+                            .lines()
+                            .map(str::trim)
+                            .filter(|word| !word.is_empty())
+                            .map(String::from),
+                    );
+                    cx.notify();
+                })
+                .ok();
+            }
+        })
+        .detach();
+
+        Self {
+            fs,
+            dictionaries: HashMap::default(),
+            user_words: Arc::default(),
+        }
+    }
+
+    /// Returns the dictionary with the given name, such as `en_US`, or starts loading it
+    /// when it isn't loaded yet. Observers are notified once it's loaded.
+    pub fn dictionary(
+        &mut self,
+        name: &str,
+        cx: &mut ModelContext<Self>,
+    ) -> Option<Arc<Dictionary>> {
+        if let Some(state) = self.dictionaries.get(name) {
+            return match state {
+                DictionaryState::Loaded(dictionary) => Some(dictionary.clone()),
+                DictionaryState::Loading { .. } | DictionaryState::Unavailable => None,
+            };
+        }
+
+        let fs = self.fs.clone();
+        let load = cx.spawn({
+            let name = name.to_string();
+            |this, mut cx| async move {
+                let state = match load_dictionary(fs, &name, &cx).await {
+                    Ok(dictionary) => DictionaryState::Loaded(Arc::new(dictionary)),
+                    Err(error) => {
+                        log::warn!("failed to load the {name:?} dictionary: {error}");
+                        DictionaryState::Unavailable
+                    }
+                };
+                this.update(&mut cx, |this, cx| {
+                    this.dictionaries.insert(name, state);
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+        self.dictionaries
+            .insert(name.to_string(), DictionaryState::Loading { _load: load });
+        None
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn insert_dictionary(
+        &mut self,
+        name: &str,
+        dictionary: Dictionary,
+        cx: &mut ModelContext<Self>,
+    ) {
+        self.dictionaries.insert(
+            name.to_string(),
+            DictionaryState::Loaded(Arc::new(dictionary)),
+        );
+        cx.notify();
+    }
+
+    /// Returns the words the user added to their dictionary.
+    pub fn user_words(&self) -> Arc<HashSet<String>> {
+        self.user_words.clone()
+    }
+
+    /// Adds a word to the user's dictionary, so that it's no longer reported as misspelled.
+    pub fn add_to_user_dictionary(
+        &mut self,
+        word: &str,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        if !Arc::make_mut(&mut self.user_words).insert(word.to_string()) {
+            return Task::ready(Ok(()));
+        }
+        cx.notify();
+
+        let mut words = self.user_words.iter().cloned().collect::<Vec<_>>();
+        words.sort();
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let path = paths::user_dictionary_file();
+            if let Some(dir) = path.parent() {
+                fs.create_dir(dir).await?;
+            }
+            let mut content = words.join("\n");
+            content.push('\n');
+            fs.atomic_write(path.clone(), content).await
+        })
+    }
+}
+
+/// Loads the `.aff` and `.dic` files of a dictionary from the first directory having them.
+async fn load_dictionary(fs: Arc<dyn Fs>, name: &str, cx: &AsyncAppContext) -> Result<Dictionary> {
+    for dir in dictionary_dirs() {
+        let aff_path = dir.join(format!("{name}.aff"));
+        let dic_path = dir.join(format!("{name}.dic"));
+        if !fs.is_file(&aff_path).await || !fs.is_file(&dic_path).await {
+            continue;
+        }
+
+        let aff = fs.load_bytes(&aff_path).await?;
+        let dic = fs.load_bytes(&dic_path).await?;
+        return cx
+            .background_executor()
+            .spawn(async move { Dictionary::new(&aff, &dic) })
+            .await;
+    }
+    Err(anyhow!("no dictionary files were found"))
+}
+
+/// The directories dictionaries are looked for in, starting with Zed's own.
+fn dictionary_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![paths::dictionaries_dir().clone()];
+    if cfg!(target_os = "macos") {
+        dirs.push(paths::home_dir().join("Library/Spelling"));
+        dirs.push(PathBuf::from("/Library/Spelling"));
+    } else if cfg!(target_os = "linux") {
+        dirs.extend(
+            [
+                "/usr/share/hunspell",
+                "/usr/share/myspell",
+                "/usr/share/myspell/dicts",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+    dirs
+}
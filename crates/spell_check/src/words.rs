@@ -0,0 +1,108 @@
+use std::ops::Range;
+
+/// Characters that join words into paths, URLs or member accesses, like in `a.b` or `a/b`.
+const JOINING_CHARS: &[char] = &['.', '/', '\\', ':'];
+
+/// Characters that mark the word following them as code, like in `$variable` or `<tag>`.
+const SIGIL_CHARS: &[char] = &['$', '@', '#', '\\', '<', '&', '%'];
+
+/// Returns the ranges of the words of the text that should be spell checked.
+///
+/// Words that look like code are skipped: identifiers written in `snake_case` or
+/// `camelCase`, acronyms, words with digits, parts of paths and URLs, function calls and
+/// text within backticks.
+pub fn words_to_check(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut in_backticks = false;
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '`' => in_backticks = !in_backticks,
+            '\n' => in_backticks = false,
+            _ => {}
+        }
+        if !is_word_char(c) {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some(&(ix, c)) = chars.peek() {
+            let is_inner_apostrophe = is_apostrophe(c)
+                && text[ix + c.len_utf8()..]
+                    .chars()
+                    .next()
+                    .map_or(false, char::is_alphabetic);
+            if !is_word_char(c) && !is_inner_apostrophe {
+                break;
+            }
+            end = ix + c.len_utf8();
+            chars.next();
+        }
+
+        if !in_backticks && is_prose_word(text, start..end) {
+            words.push(start..end);
+        }
+    }
+    words
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
+}
+
+fn is_prose_word(text: &str, range: Range<usize>) -> bool {
+    let word = &text[range.clone()];
+    if word.chars().count() < 2
+        || !word.chars().all(|c| c.is_alphabetic() || is_apostrophe(c))
+        || word.chars().skip(1).any(char::is_uppercase)
+    {
+        return false;
+    }
+
+    let mut preceding = text[..range.start].chars().rev();
+    let mut following = text[range.end..].chars();
+    let joined_to = |c: Option<char>, beyond: Option<char>| {
+        c.map_or(false, |c| JOINING_CHARS.contains(&c))
+            && beyond.map_or(false, |c| !c.is_whitespace())
+    };
+    let (previous, before_previous) = (preceding.next(), preceding.next());
+    let (next, after_next) = (following.next(), following.next());
+    !(previous.map_or(false, |c| SIGIL_CHARS.contains(&c))
+        || next == Some('(')
+        || joined_to(previous, before_previous)
+        || joined_to(next, after_next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        words_to_check(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_words_to_check() {
+        assert_eq!(
+            words("Thsi isn't a sentence, it's a tset."),
+            ["Thsi", "isn't", "sentence", "it's", "tset"]
+        );
+        assert_eq!(
+            words("Call `parse_dictionary` or parseDictionary() with NEEDAFFIX"),
+            ["Call", "or", "with"]
+        );
+        assert_eq!(
+            words("See https://example.com/docs or src/main.rs, e.g. $HOME and <div>"),
+            ["See", "or", "and"]
+        );
+        assert_eq!(words("utf8 v2 version"), ["version"]);
+        assert_eq!(words("Ärger über Straße"), ["Ärger", "über", "Straße"]);
+    }
+}
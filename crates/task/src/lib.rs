@@ -33,6 +33,12 @@ pub enum TerminalWorkDir {
         /// The path on the remote server
         path: Option<String>,
     },
+    /// Remote runs the terminal on the remote host of the project, over its connection to
+    /// the remote server
+    Remote {
+        /// The path on the remote host
+        path: Option<String>,
+    },
 }
 
 impl TerminalWorkDir {
@@ -40,7 +46,7 @@ impl TerminalWorkDir {
     pub fn is_local(&self) -> bool {
         match self {
             Self::Local(_) => true,
-            Self::Ssh { .. } => false,
+            Self::Ssh { .. } | Self::Remote { .. } => false,
         }
     }
 
@@ -48,7 +54,7 @@ impl TerminalWorkDir {
     pub fn local_path(&self) -> Option<&Path> {
         match self {
             Self::Local(path) => Some(path),
-            Self::Ssh { .. } => None,
+            Self::Ssh { .. } | Self::Remote { .. } => None,
         }
     }
 }
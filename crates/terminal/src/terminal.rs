@@ -18,7 +18,7 @@ use alacritty_terminal::{
         Config, RenderableCursor, TermMode,
     },
     tty::{self, setup_env},
-    vte::ansi::{ClearMode, Handler, NamedPrivateMode, PrivateMode, Rgb},
+    vte::ansi::{ClearMode, Handler, NamedPrivateMode, PrivateMode, Processor, Rgb},
     Term,
 };
 use anyhow::{bail, Result};
@@ -45,6 +45,7 @@ use theme::{ActiveTheme, Theme};
 use util::truncate_and_trailoff;
 
 use std::{
+    borrow::Cow,
     cmp::{self, min},
     fmt::Display,
     ops::{Deref, Index, RangeInclusive},
@@ -150,10 +151,76 @@ impl EventListener for ZedListener {
     }
 }
 
+/// The input of a terminal that runs on a remote host, to be sent to its process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteTerminalInput {
+    /// Bytes written to the terminal, such as typed characters.
+    Write(Vec<u8>),
+    /// The terminal was resized.
+    Resize { rows: u16, columns: u16 },
+}
+
+/// The output of the process of a terminal that runs on a remote host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteTerminalOutput {
+    Data(Vec<u8>),
+    /// The process exited, with its exit code unless it was killed by a signal.
+    Exit(Option<i32>),
+}
+
+/// Where the input of a terminal goes: the event loop of its local pty, or the
+/// connection to the remote host it runs on.
+enum PtyTx {
+    Local(Notifier),
+    Remote(UnboundedSender<RemoteTerminalInput>),
+}
+
+impl PtyTx {
+    fn notify<B: Into<Cow<'static, [u8]>>>(&self, bytes: B) {
+        match self {
+            PtyTx::Local(notifier) => notifier.notify(bytes),
+            PtyTx::Remote(input_tx) => {
+                let bytes = bytes.into();
+                if !bytes.is_empty() {
+                    input_tx
+                        .unbounded_send(RemoteTerminalInput::Write(bytes.into_owned()))
+                        .ok();
+                }
+            }
+        }
+    }
+
+    fn resize(&self, size: WindowSize) {
+        match self {
+            PtyTx::Local(notifier) => {
+                notifier.0.send(Msg::Resize(size)).ok();
+            }
+            PtyTx::Remote(input_tx) => {
+                input_tx
+                    .unbounded_send(RemoteTerminalInput::Resize {
+                        rows: size.num_lines,
+                        columns: size.num_cols,
+                    })
+                    .ok();
+            }
+        }
+    }
+}
+
 pub fn init(cx: &mut AppContext) {
     TerminalSettings::register(cx);
 }
 
+/// Sets the variables that tell the programs run in a terminal that it's Zed's.
+pub fn insert_term_program_env(env: &mut HashMap<String, String>, cx: &AppContext) {
+    env.insert("ZED_TERM".to_string(), "true".to_string());
+    env.insert("TERM_PROGRAM".to_string(), "zed".to_string());
+    env.insert(
+        "TERM_PROGRAM_VERSION".to_string(),
+        release_channel::AppVersion::global(cx).to_string(),
+    );
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TerminalSize {
     pub cell_width: Pixels,
@@ -325,12 +392,7 @@ impl TerminalBuilder {
         env.entry("LC_ALL".to_string())
             .or_insert_with(|| "en_US.UTF-8".to_string());
 
-        env.insert("ZED_TERM".to_string(), "true".to_string());
-        env.insert("TERM_PROGRAM".to_string(), "zed".to_string());
-        env.insert(
-            "TERM_PROGRAM_VERSION".to_string(),
-            release_channel::AppVersion::global(cx).to_string(),
-        );
+        insert_term_program_env(&mut env, cx);
 
         let pty_options = {
             let alac_shell = match shell.clone() {
@@ -358,43 +420,17 @@ impl TerminalBuilder {
         // Setup Alacritty's env
         setup_env();
 
-        let scrolling_history = if task.is_some() {
-            // Tasks like `cargo build --all` may produce a lot of output, ergo allow maximum scrolling.
-            // After the task finishes, we do not allow appending to that terminal, so small tasks output should not
-            // cause excessive memory usage over time.
-            MAX_SCROLL_HISTORY_LINES
-        } else {
-            max_scroll_history_lines
-                .unwrap_or(DEFAULT_SCROLL_HISTORY_LINES)
-                .min(MAX_SCROLL_HISTORY_LINES)
-        };
-        let config = Config {
-            scrolling_history,
-            ..Config::default()
-        };
-
         //Spawn a task so the Alacritty EventLoop can communicate with us in a view context
         //TODO: Remove with a bounded sender which can be dispatched on &self
         let (events_tx, events_rx) = unbounded();
-        //Set up the terminal...
-        let mut term = Term::new(
-            config,
-            &TerminalSize::default(),
-            ZedListener(events_tx.clone()),
+        let term = Self::new_term(
+            task.is_some(),
+            blink_settings,
+            alternate_scroll,
+            max_scroll_history_lines,
+            events_tx.clone(),
         );
 
-        //Start off blinking if we need to
-        if let Some(TerminalBlink::On) = blink_settings {
-            term.set_private_mode(PrivateMode::Named(NamedPrivateMode::BlinkingCursor));
-        }
-
-        //Alacritty defaults to alternate scrolling being on, so we just need to turn it off.
-        if let AlternateScroll::Off = alternate_scroll {
-            term.unset_private_mode(PrivateMode::Named(NamedPrivateMode::AlternateScroll));
-        }
-
-        let term = Arc::new(FairMutex::new(term));
-
         //Setup the pty...
         let pty = match tty::new(
             &pty_options,
@@ -426,37 +462,119 @@ impl TerminalBuilder {
         let pty_tx = event_loop.channel();
         let _io_thread = event_loop.spawn(); // DANGER
 
-        let url_regex = RegexSearch::new(r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`]+"#).unwrap();
-        let word_regex = RegexSearch::new(r#"[\$\+\w.\[\]:/\\@\-~]+"#).unwrap();
-
-        let terminal = Terminal {
-            task,
-            pty_tx: Notifier(pty_tx),
-            completion_tx,
-            term,
-            events: VecDeque::with_capacity(10), //Should never get this high.
-            last_content: Default::default(),
-            last_mouse: None,
-            matches: Vec::new(),
-            selection_head: None,
-            pty_info,
-            breadcrumb_text: String::new(),
-            scroll_px: px(0.),
-            last_mouse_position: None,
-            next_link_id: 0,
-            selection_phase: SelectionPhase::Ended,
-            secondary_pressed: false,
-            hovered_word: false,
-            url_regex,
-            word_regex,
-        };
-
         Ok(TerminalBuilder {
-            terminal,
+            terminal: Terminal::new(
+                task,
+                PtyTx::Local(Notifier(pty_tx)),
+                Some(pty_info),
+                completion_tx,
+                term,
+            ),
             events_rx,
         })
     }
 
+    /// Builds a terminal whose process runs on a remote host, such as the remote host of an
+    /// SSH project. Its input is sent to `input_tx`, and the output of the process is read
+    /// from `output_rx` until the process exits.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_remote(
+        task: Option<TaskState>,
+        input_tx: UnboundedSender<RemoteTerminalInput>,
+        mut output_rx: UnboundedReceiver<RemoteTerminalOutput>,
+        blink_settings: Option<TerminalBlink>,
+        alternate_scroll: AlternateScroll,
+        max_scroll_history_lines: Option<usize>,
+        completion_tx: Sender<()>,
+        cx: &mut AppContext,
+    ) -> TerminalBuilder {
+        let (events_tx, events_rx) = unbounded();
+        let term = Self::new_term(
+            task.is_some(),
+            blink_settings,
+            alternate_scroll,
+            max_scroll_history_lines,
+            events_tx.clone(),
+        );
+
+        // The process starts with the size of the terminal until it's laid out.
+        let pty_tx = PtyTx::Remote(input_tx);
+        pty_tx.resize(TerminalSize::default().into());
+
+        // Parses the output of the remote process like the event loop of a local pty does.
+        cx.background_executor()
+            .spawn({
+                let term = term.clone();
+                let listener = ZedListener(events_tx);
+                async move {
+                    let mut parser: Processor = Processor::new();
+                    while let Some(output) = output_rx.next().await {
+                        match output {
+                            RemoteTerminalOutput::Data(data) => {
+                                let mut term = term.lock();
+                                for byte in data {
+                                    parser.advance(&mut *term, byte);
+                                }
+                                drop(term);
+                                listener.send_event(AlacTermEvent::Wakeup);
+                            }
+                            RemoteTerminalOutput::Exit(exit_code) => {
+                                if let Some(exit_code) = exit_code {
+                                    listener.send_event(AlacTermEvent::ChildExit(exit_code));
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    listener.send_event(AlacTermEvent::Exit);
+                }
+            })
+            .detach();
+
+        TerminalBuilder {
+            terminal: Terminal::new(task, pty_tx, None, completion_tx, term),
+            events_rx,
+        }
+    }
+
+    fn new_term(
+        is_task: bool,
+        blink_settings: Option<TerminalBlink>,
+        alternate_scroll: AlternateScroll,
+        max_scroll_history_lines: Option<usize>,
+        events_tx: UnboundedSender<AlacTermEvent>,
+    ) -> Arc<FairMutex<Term<ZedListener>>> {
+        let scrolling_history = if is_task {
+            // Tasks like `cargo build --all` may produce a lot of output, ergo allow maximum scrolling.
+            // After the task finishes, we do not allow appending to that terminal, so small tasks output should not
+            // cause excessive memory usage over time.
+            MAX_SCROLL_HISTORY_LINES
+        } else {
+            max_scroll_history_lines
+                .unwrap_or(DEFAULT_SCROLL_HISTORY_LINES)
+                .min(MAX_SCROLL_HISTORY_LINES)
+        };
+        let config = Config {
+            scrolling_history,
+            ..Config::default()
+        };
+
+        //Set up the terminal...
+        let mut term = Term::new(config, &TerminalSize::default(), ZedListener(events_tx));
+
+        //Start off blinking if we need to
+        if let Some(TerminalBlink::On) = blink_settings {
+            term.set_private_mode(PrivateMode::Named(NamedPrivateMode::BlinkingCursor));
+        }
+
+        //Alacritty defaults to alternate scrolling being on, so we just need to turn it off.
+        if let AlternateScroll::Off = alternate_scroll {
+            term.unset_private_mode(PrivateMode::Named(NamedPrivateMode::AlternateScroll));
+        }
+
+        Arc::new(FairMutex::new(term))
+    }
+
     pub fn subscribe(mut self, cx: &mut ModelContext<Terminal>) -> Terminal {
         //Event loop
         cx.spawn(|terminal, mut cx| async move {
@@ -582,7 +700,7 @@ pub enum SelectionPhase {
 }
 
 pub struct Terminal {
-    pty_tx: Notifier,
+    pty_tx: PtyTx,
     completion_tx: Sender<()>,
     term: Arc<FairMutex<Term<ZedListener>>>,
     events: VecDeque<InternalEvent>,
@@ -594,7 +712,8 @@ pub struct Terminal {
     pub last_content: TerminalContent,
     pub selection_head: Option<AlacPoint>,
     pub breadcrumb_text: String,
-    pub pty_info: PtyProcessInfo,
+    /// The local process of the terminal, which remote terminals don't have.
+    pub pty_info: Option<PtyProcessInfo>,
     scroll_px: Pixels,
     next_link_id: usize,
     selection_phase: SelectionPhase,
@@ -642,6 +761,39 @@ impl TaskStatus {
 }
 
 impl Terminal {
+    fn new(
+        task: Option<TaskState>,
+        pty_tx: PtyTx,
+        pty_info: Option<PtyProcessInfo>,
+        completion_tx: Sender<()>,
+        term: Arc<FairMutex<Term<ZedListener>>>,
+    ) -> Self {
+        let url_regex = RegexSearch::new(r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`]+"#).unwrap();
+        let word_regex = RegexSearch::new(r#"[\$\+\w.\[\]:/\\@\-~]+"#).unwrap();
+
+        Terminal {
+            task,
+            pty_tx,
+            completion_tx,
+            term,
+            events: VecDeque::with_capacity(10), //Should never get this high.
+            last_content: Default::default(),
+            last_mouse: None,
+            matches: Vec::new(),
+            selection_head: None,
+            pty_info,
+            breadcrumb_text: String::new(),
+            scroll_px: px(0.),
+            last_mouse_position: None,
+            next_link_id: 0,
+            selection_phase: SelectionPhase::Ended,
+            secondary_pressed: false,
+            hovered_word: false,
+            url_regex,
+            word_regex,
+        }
+    }
+
     fn process_event(&mut self, event: &AlacTermEvent, cx: &mut ModelContext<Self>) {
         match event {
             AlacTermEvent::Title(title) => {
@@ -677,7 +829,11 @@ impl Terminal {
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
 
-                if self.pty_info.has_changed() {
+                if self
+                    .pty_info
+                    .as_mut()
+                    .is_some_and(|pty_info| pty_info.has_changed())
+                {
                     cx.emit(Event::TitleChanged);
                 }
             }
@@ -696,7 +852,11 @@ impl Terminal {
     }
 
     pub fn get_cwd(&self) -> Option<PathBuf> {
-        self.pty_info.current.as_ref().map(|info| info.cwd.clone())
+        self.pty_info
+            .as_ref()?
+            .current
+            .as_ref()
+            .map(|info| info.cwd.clone())
     }
 
     ///Takes events from Alacritty and translates them to behavior on this view
@@ -719,7 +879,7 @@ impl Terminal {
 
                 self.last_content.size = new_size;
 
-                self.pty_tx.0.send(Msg::Resize(new_size.into())).ok();
+                self.pty_tx.resize(new_size.into());
 
                 term.resize(new_size);
             }
@@ -1471,6 +1631,7 @@ impl Terminal {
 
     pub fn working_directory(&self) -> Option<PathBuf> {
         self.pty_info
+            .as_ref()?
             .current
             .as_ref()
             .map(|process| process.cwd.clone())
@@ -1488,8 +1649,8 @@ impl Terminal {
             }
             None => self
                 .pty_info
-                .current
                 .as_ref()
+                .and_then(|pty_info| pty_info.current.as_ref())
                 .map(|fpi| {
                     let process_file = fpi
                         .cwd
@@ -1646,7 +1807,9 @@ unsafe fn append_text_to_term(term: &mut Term<ZedListener>, text_lines: &[&str])
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        self.pty_tx.0.send(Msg::Shutdown).ok();
+        if let PtyTx::Local(notifier) = &self.pty_tx {
+            notifier.0.send(Msg::Shutdown).ok();
+        }
     }
 }
 
@@ -362,8 +362,11 @@ impl TerminalPanel {
 
     fn spawn_task(&mut self, spawn_in_terminal: &SpawnInTerminal, cx: &mut ViewContext<Self>) {
         let mut spawn_task = spawn_in_terminal.clone();
+        let ssh_work_dir = self.ssh_task_work_dir(spawn_in_terminal, cx);
         // Set up shell args unconditionally, as tasks are always spawned inside of a shell.
         let Some((shell, mut user_args)) = (match spawn_in_terminal.shell.clone() {
+            // The local system shell may not exist on the remote host.
+            Shell::System if ssh_work_dir.is_some() => Some(("sh".to_string(), Vec::new())),
             Shell::System => retrieve_system_shell().map(|shell| (shell, Vec::new())),
             Shell::Program(shell) => Some((shell, Vec::new())),
             Shell::WithArguments { program, args } => Some((program, args)),
@@ -425,6 +428,9 @@ impl TerminalPanel {
             }
         }
         spawn_task.args = user_args;
+        if let Some(ssh_work_dir) = ssh_work_dir {
+            spawn_task.cwd = Some(ssh_work_dir);
+        }
         let spawn_task = spawn_task;
 
         let allow_concurrent_runs = spawn_in_terminal.allow_concurrent_runs;
@@ -478,6 +484,24 @@ impl TerminalPanel {
         }
     }
 
+    /// Tasks of SSH projects run on the remote host. Returns the remote working directory
+    /// for the task, or `None` if it should run locally.
+    fn ssh_task_work_dir(
+        &self,
+        spawn_task: &SpawnInTerminal,
+        cx: &AppContext,
+    ) -> Option<TerminalWorkDir> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        project.ssh_session()?;
+        match &spawn_task.cwd {
+            Some(TerminalWorkDir::Ssh { .. } | TerminalWorkDir::Remote { .. }) => {
+                spawn_task.cwd.clone()
+            }
+            cwd => project.terminal_work_dir_for(cwd.as_ref().and_then(|cwd| cwd.local_path()), cx),
+        }
+    }
+
     pub fn spawn_in_new_terminal(
         &mut self,
         spawn_task: SpawnInTerminal,
@@ -542,7 +566,7 @@ impl TerminalPanel {
             if spawn_task.is_none()
                 || !matches!(
                     spawn_task.as_ref().unwrap().cwd,
-                    Some(TerminalWorkDir::Ssh { .. })
+                    Some(TerminalWorkDir::Ssh { .. } | TerminalWorkDir::Remote { .. })
                 )
             {
                 return Task::ready(Err(anyhow::anyhow!(
@@ -0,0 +1,396 @@
+use editor::{Editor, EditorEvent, NumericSettingControl, NumericSettingEditor};
+use gpui::{AppContext, Subscription, View};
+use settings::{EditableSettingControl, Settings};
+use task::Shell;
+use terminal::terminal_settings::{AlternateScroll, TerminalBlink, TerminalSettings};
+use theme::{clamp_font_size, FontFamilyCache, ThemeSettings};
+use ui::{
+    prelude::*, CheckboxWithLabel, ContextMenu, DropdownMenu, DropdownSettingControl,
+    NumericStepper, SettingsContainer, SettingsFilter, SettingsGroup,
+};
+
+pub struct TerminalSettingsControls {
+    font_size: View<NumericSettingEditor>,
+    shell_program: View<Editor>,
+    _subscription: Subscription,
+}
+
+impl TerminalSettingsControls {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let program = ShellControl::program(cx).unwrap_or_default();
+
+        let shell_program = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("System Shell", cx);
+            editor.set_text(program, cx);
+            editor
+        });
+        let _subscription = cx.subscribe(&shell_program, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::Blurred = event {
+                this.save_shell_program(cx);
+            }
+        });
+
+        Self {
+            font_size: cx.new_view(NumericSettingEditor::new::<TerminalFontSizeControl>),
+            shell_program,
+            _subscription,
+        }
+    }
+
+    /// Writes the shell from the program editor, keeping the configured arguments when
+    /// only the program changes.
+    fn save_shell_program(&mut self, cx: &mut ViewContext<Self>) {
+        let program = self.shell_program.read(cx).text(cx).trim().to_string();
+        if ShellControl::program(cx).unwrap_or_default() == program {
+            return;
+        }
+
+        let shell = if program.is_empty() {
+            Shell::System
+        } else {
+            match ShellControl::read(cx) {
+                Shell::WithArguments { args, .. } => Shell::WithArguments { program, args },
+                Shell::System | Shell::Program(_) => Shell::Program(program),
+            }
+        };
+        ShellControl::write(shell, cx);
+    }
+}
+
+impl Render for TerminalSettingsControls {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        SettingsContainer::new()
+            .child(
+                SettingsGroup::new("Font")
+                    .control(TerminalFontFamilyControl)
+                    .control(TerminalFontSizeControl {
+                        value_editor: self.font_size.clone(),
+                    }),
+            )
+            .child(
+                SettingsGroup::new("Terminal")
+                    .control(ShellControl {
+                        program: self.shell_program.clone(),
+                    })
+                    .dropdown(terminal_blinking_control())
+                    .control(AlternateScrollControl),
+            )
+    }
+}
+
+#[derive(IntoElement)]
+struct TerminalFontFamilyControl;
+
+impl EditableSettingControl for TerminalFontFamilyControl {
+    type Value = Option<SharedString>;
+    type Settings = TerminalSettings;
+
+    fn name(&self) -> SharedString {
+        "Terminal Font Family".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = TerminalSettings::get_global(cx);
+        settings.font_family.clone().map(SharedString::from)
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.font_family = value.map(|value| value.to_string());
+    }
+}
+
+impl RenderOnce for TerminalFontFamilyControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::Font))
+            .child(DropdownMenu::new(
+                "terminal-font-family",
+                value.unwrap_or_else(|| "Buffer Font".into()),
+                ContextMenu::build(cx, |mut menu, cx| {
+                    // Without a font family, the terminal uses the buffer font.
+                    menu = menu
+                        .custom_entry(
+                            |_cx| Label::new("Buffer Font").into_any_element(),
+                            |cx| Self::write(None, cx),
+                        )
+                        .separator();
+
+                    let font_family_cache = FontFamilyCache::global(cx);
+
+                    for font_name in font_family_cache.list_font_families(cx) {
+                        menu = menu.custom_entry(
+                            {
+                                let font_name = font_name.clone();
+                                move |_cx| Label::new(font_name.clone()).into_any_element()
+                            },
+                            {
+                                let font_name = font_name.clone();
+                                move |cx| {
+                                    Self::write(Some(font_name.clone()), cx);
+                                }
+                            },
+                        )
+                    }
+
+                    menu
+                }),
+            ))
+    }
+}
+
+#[derive(IntoElement)]
+struct TerminalFontSizeControl {
+    value_editor: View<NumericSettingEditor>,
+}
+
+impl EditableSettingControl for TerminalFontSizeControl {
+    type Value = Pixels;
+    type Settings = TerminalSettings;
+
+    fn name(&self) -> SharedString {
+        "Terminal Font Size".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = TerminalSettings::get_global(cx);
+        settings
+            .font_size
+            .unwrap_or_else(|| ThemeSettings::get_global(cx).buffer_font_size)
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.font_size = Some(clamp_font_size(value).into());
+    }
+}
+
+impl NumericSettingControl for TerminalFontSizeControl {
+    fn format(value: &Self::Value) -> String {
+        value.0.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self::Value> {
+        text.trim_end_matches("px").parse().ok().map(px)
+    }
+}
+
+impl RenderOnce for TerminalFontSizeControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::FontSize))
+            .child(
+                NumericStepper::new(
+                    value.to_string(),
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value - px(steps), cx);
+                    },
+                    move |event, cx| {
+                        let steps = NumericStepper::step_count(event) as f32;
+                        Self::write(value + px(steps), cx);
+                    },
+                )
+                .value_editor(self.value_editor),
+            )
+            .child(Label::new("px").color(Color::Muted))
+    }
+}
+
+#[derive(IntoElement)]
+struct ShellControl {
+    program: View<Editor>,
+}
+
+impl ShellControl {
+    /// Returns the configured shell program, if it isn't the system shell.
+    fn program(cx: &AppContext) -> Option<String> {
+        match Self::read(cx) {
+            Shell::System => None,
+            Shell::Program(program) | Shell::WithArguments { program, .. } => Some(program),
+        }
+    }
+}
+
+impl EditableSettingControl for ShellControl {
+    type Value = Shell;
+    type Settings = TerminalSettings;
+
+    fn name(&self) -> SharedString {
+        "Shell".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = TerminalSettings::get_global(cx);
+        settings.shell.clone()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.shell = Some(value);
+    }
+}
+
+impl RenderOnce for ShellControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(self.name(), cx))
+            .child(div().w_64().child(self.program))
+    }
+}
+
+fn terminal_blinking_control() -> DropdownSettingControl<TerminalSettings, TerminalBlink> {
+    DropdownSettingControl::<TerminalSettings, _>::new(
+        "Cursor Blinking",
+        |cx| TerminalSettings::get_global(cx).blinking,
+        |settings, value, _cx| settings.blinking = Some(value),
+    )
+    .option(TerminalBlink::Off, "Off")
+    .option(TerminalBlink::TerminalControlled, "Terminal Controlled")
+    .option(TerminalBlink::On, "On")
+}
+
+#[derive(IntoElement)]
+struct AlternateScrollControl;
+
+impl EditableSettingControl for AlternateScrollControl {
+    type Value = bool;
+    type Settings = TerminalSettings;
+
+    fn name(&self) -> SharedString {
+        "Alternate Scroll".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = TerminalSettings::get_global(cx);
+        settings.alternate_scroll == AlternateScroll::On
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.alternate_scroll = Some(if value {
+            AlternateScroll::On
+        } else {
+            AlternateScroll::Off
+        });
+    }
+}
+
+impl RenderOnce for AlternateScrollControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        CheckboxWithLabel::new(
+            "terminal-alternate-scroll",
+            SettingsFilter::label(self.name(), cx),
+            value.into(),
+            |selection, cx| {
+                Self::write(
+                    match selection {
+                        Selection::Selected => true,
+                        Selection::Unselected | Selection::Indeterminate => false,
+                    },
+                    cx,
+                );
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::{apply_test_setting, write_test_setting, SettingsStore};
+
+    fn init_test(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        theme::init(theme::LoadThemes::JustBase, cx);
+        terminal::init(cx);
+    }
+
+    #[gpui::test]
+    fn test_terminal_controls(cx: &mut AppContext) {
+        init_test(cx);
+
+        write_test_setting::<TerminalFontFamilyControl>(Some("Fira Code".into()), cx);
+        assert_eq!(
+            TerminalFontFamilyControl::read(cx),
+            Some("Fira Code".into())
+        );
+        write_test_setting::<TerminalFontFamilyControl>(None, cx);
+        assert_eq!(TerminalFontFamilyControl::read(cx), None);
+
+        // Without a font size of its own, the terminal uses the buffer font size.
+        assert_eq!(
+            TerminalFontSizeControl::read(cx),
+            ThemeSettings::get_global(cx).buffer_font_size
+        );
+        write_test_setting::<TerminalFontSizeControl>(px(18.), cx);
+        assert_eq!(TerminalFontSizeControl::read(cx), px(18.));
+        write_test_setting::<TerminalFontSizeControl>(px(1000.), cx);
+        assert_eq!(
+            TerminalFontSizeControl::read(cx),
+            clamp_font_size(px(1000.))
+        );
+
+        write_test_setting::<ShellControl>(
+            Shell::WithArguments {
+                program: "bash".into(),
+                args: vec!["--login".into()],
+            },
+            cx,
+        );
+        assert_eq!(ShellControl::program(cx), Some("bash".into()));
+        write_test_setting::<ShellControl>(Shell::System, cx);
+        assert_eq!(ShellControl::read(cx), Shell::System);
+        assert_eq!(ShellControl::program(cx), None);
+
+        write_test_setting::<AlternateScrollControl>(false, cx);
+        assert!(!AlternateScrollControl::read(cx));
+        write_test_setting::<AlternateScrollControl>(true, cx);
+        assert!(AlternateScrollControl::read(cx));
+    }
+
+    #[test]
+    fn test_terminal_font_size_parsing() {
+        assert_eq!(TerminalFontSizeControl::parse("14"), Some(px(14.)));
+        assert_eq!(TerminalFontSizeControl::parse("13.5px"), Some(px(13.5)));
+        assert_eq!(TerminalFontSizeControl::parse(""), None);
+        assert_eq!(TerminalFontSizeControl::format(&px(14.)), "14");
+    }
+
+    #[gpui::test]
+    fn test_terminal_blinking_control(cx: &mut AppContext) {
+        init_test(cx);
+
+        let control = terminal_blinking_control();
+        for (value, _) in control.options() {
+            apply_test_setting::<TerminalSettings>(
+                |settings, cx| control.apply(settings, *value, cx),
+                cx,
+            );
+            assert_eq!(control.read(cx), *value);
+        }
+    }
+}
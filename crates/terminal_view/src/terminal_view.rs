@@ -1,6 +1,7 @@
 mod persistence;
 pub mod terminal_element;
 pub mod terminal_panel;
+mod terminal_settings_controls;
 
 use collections::HashSet;
 use editor::{actions::SelectAll, scroll::Autoscroll, Editor};
@@ -26,6 +27,7 @@ use terminal::{
     TerminalSize,
 };
 use terminal_element::{is_blank, TerminalElement};
+pub use terminal_settings_controls::*;
 use ui::{h_flex, prelude::*, ContextMenu, Icon, IconName, Label, Tooltip};
 use util::{paths::PathLikeWithPosition, ResultExt};
 use workspace::{
@@ -1281,7 +1283,8 @@ pub fn get_working_directory(
     cx: &AppContext,
     strategy: WorkingDirectory,
 ) -> Option<TerminalWorkDir> {
-    if workspace.project().read(cx).is_local() {
+    let project = workspace.project().read(cx);
+    if project.is_local() && project.ssh_session().is_none() {
         let res = match strategy {
             WorkingDirectory::CurrentProjectDirectory => current_project_directory(workspace, cx)
                 .or_else(|| first_project_directory(workspace, cx)),
@@ -1296,7 +1299,7 @@ pub fn get_working_directory(
         };
         res.or_else(home_dir).map(|cwd| TerminalWorkDir::Local(cwd))
     } else {
-        workspace.project().read(cx).terminal_work_dir_for(None, cx)
+        project.terminal_work_dir_for(None, cx)
     }
 }
 
@@ -19,6 +19,7 @@ use std::sync::Arc;
 use util::ResultExt as _;
 
 const MIN_FONT_SIZE: Pixels = px(6.0);
+const MAX_FONT_SIZE: Pixels = px(100.0);
 const MIN_LINE_HEIGHT: f32 = 1.0;
 
 #[derive(
@@ -410,6 +411,11 @@ impl ThemeSettings {
     }
 }
 
+/// Clamps a font size to the range of font sizes that can be set in the settings.
+pub fn clamp_font_size(size: Pixels) -> Pixels {
+    size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+}
+
 pub fn observe_buffer_font_size_adjustment<V: 'static>(
     cx: &mut ViewContext<V>,
     f: impl 'static + Fn(&mut V, &mut ViewContext<V>),
@@ -532,7 +538,12 @@ impl settings::Settings for ThemeSettings {
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
         };
 
-        for value in sources.user.into_iter().chain(sources.release_channel) {
+        for value in sources
+            .user
+            .into_iter()
+            .chain(sources.release_channel)
+            .chain(sources.profile)
+        {
             if let Some(value) = value.ui_density {
                 this.ui_density = value;
             }
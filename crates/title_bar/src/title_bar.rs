@@ -393,14 +393,12 @@ impl TitleBar {
                     Tooltip::with_meta(
                         "Recent Branches",
                         Some(&ToggleVcsMenu),
-                        "Local branches only",
+                        "Local and remote branches",
                         cx,
                     )
                 })
                 .on_click(move |_, cx| {
-                    let _ = workspace.update(cx, |this, cx| {
-                        BranchList::open(this, &Default::default(), cx)
-                    });
+                    workspace.update(cx, |this, cx| BranchList::open(this, cx));
                 }),
         )
     }
@@ -1,10 +1,12 @@
 mod avatar;
 mod button;
 mod checkbox;
+mod color_picker;
 mod context_menu;
 mod disclosure;
 mod divider;
 mod dropdown_menu;
+mod dropdown_setting_control;
 mod facepile;
 mod icon;
 mod indicator;
@@ -19,6 +21,8 @@ mod radio;
 mod right_click_menu;
 mod setting;
 mod settings_container;
+mod settings_control;
+mod settings_filter;
 mod settings_group;
 mod stack;
 mod tab;
@@ -32,10 +36,12 @@ mod stories;
 pub use avatar::*;
 pub use button::*;
 pub use checkbox::*;
+pub use color_picker::*;
 pub use context_menu::*;
 pub use disclosure::*;
 pub use divider::*;
 pub use dropdown_menu::*;
+pub use dropdown_setting_control::*;
 pub use facepile::*;
 pub use icon::*;
 pub use indicator::*;
@@ -50,6 +56,8 @@ pub use radio::*;
 pub use right_click_menu::*;
 pub use setting::*;
 pub use settings_container::*;
+pub use settings_control::*;
+pub use settings_filter::*;
 pub use settings_group::*;
 pub use stack::*;
 pub use tab::*;
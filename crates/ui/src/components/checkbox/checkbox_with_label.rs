@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
+use gpui::AnyElement;
+
 use crate::{prelude::*, Checkbox};
 
 /// A [`Checkbox`] that has a [`Label`].
 #[derive(IntoElement)]
 pub struct CheckboxWithLabel {
     id: ElementId,
-    label: Label,
+    label: AnyElement,
     checked: Selection,
     on_click: Arc<dyn Fn(&Selection, &mut WindowContext) + 'static>,
 }
@@ -14,13 +16,13 @@ pub struct CheckboxWithLabel {
 impl CheckboxWithLabel {
     pub fn new(
         id: impl Into<ElementId>,
-        label: Label,
+        label: impl IntoElement,
         checked: Selection,
         on_click: impl Fn(&Selection, &mut WindowContext) + 'static,
     ) -> Self {
         Self {
             id: id.into(),
-            label,
+            label: label.into_any_element(),
             checked,
             on_click: Arc::new(on_click),
         }
@@ -0,0 +1,198 @@
+use std::rc::Rc;
+
+use gpui::{hsla, ClickEvent, Hsla, Rgba};
+
+use crate::{prelude::*, NumericStepper};
+
+/// Picks a color, either from a palette or by adjusting its hue, saturation, lightness and
+/// alpha channels.
+#[derive(IntoElement)]
+pub struct ColorPicker {
+    id: ElementId,
+    color: Hsla,
+    on_change: Rc<dyn Fn(Hsla, &mut WindowContext) + 'static>,
+}
+
+impl ColorPicker {
+    const PALETTE_HUES: usize = 12;
+    const PALETTE_LIGHTNESSES: [f32; 5] = [0.85, 0.7, 0.55, 0.4, 0.25];
+    const PALETTE_SATURATION: f32 = 0.75;
+    const SWATCH_SIZE: Pixels = px(16.);
+
+    pub fn new(
+        id: impl Into<ElementId>,
+        color: Hsla,
+        on_change: impl Fn(Hsla, &mut WindowContext) + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            color,
+            on_change: Rc::new(on_change),
+        }
+    }
+
+    fn palette_rows() -> impl Iterator<Item = Vec<Hsla>> {
+        let hues = (0..Self::PALETTE_HUES)
+            .map(|ix| ix as f32 / Self::PALETTE_HUES as f32)
+            .collect::<Vec<_>>();
+        let grays = (0..Self::PALETTE_HUES)
+            .map(|ix| hsla(0., 0., ix as f32 / (Self::PALETTE_HUES - 1) as f32, 1.))
+            .collect::<Vec<_>>();
+
+        Self::PALETTE_LIGHTNESSES
+            .into_iter()
+            .map(move |lightness| {
+                hues.iter()
+                    .map(|&hue| hsla(hue, Self::PALETTE_SATURATION, lightness, 1.))
+                    .collect()
+            })
+            .chain(Some(grays))
+    }
+
+    fn render_swatch(
+        &self,
+        id: impl Into<ElementId>,
+        color: Hsla,
+        cx: &WindowContext,
+    ) -> impl IntoElement {
+        let on_change = self.on_change.clone();
+        let selected = Rgba::from(color)
+            == Rgba::from(Hsla {
+                a: 1.,
+                ..self.color
+            });
+
+        div()
+            .id(id.into())
+            .size(Self::SWATCH_SIZE)
+            .rounded_sm()
+            .bg(color)
+            .border_1()
+            .border_color(if selected {
+                cx.theme().colors().border_selected
+            } else {
+                cx.theme().colors().border_variant
+            })
+            .hover(|style| style.border_color(cx.theme().colors().border_focused))
+            .cursor_pointer()
+            .on_click({
+                let alpha = self.color.a;
+                move |_, cx| on_change(Hsla { a: alpha, ..color }, cx)
+            })
+    }
+
+    /// Renders a stepper changing one channel of the color by `step` per click.
+    fn render_channel_stepper(
+        &self,
+        label: &'static str,
+        value: SharedString,
+        step: f32,
+        update: fn(&mut Hsla, f32),
+    ) -> impl IntoElement {
+        let color = self.color;
+        let on_decrement = self.on_change.clone();
+        let on_increment = self.on_change.clone();
+
+        h_flex()
+            .id(label)
+            .justify_between()
+            .gap_2()
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(NumericStepper::new(
+                value,
+                move |event: &ClickEvent, cx| {
+                    let mut color = color;
+                    update(&mut color, -step * NumericStepper::step_count(event) as f32);
+                    on_decrement(color, cx);
+                },
+                move |event: &ClickEvent, cx| {
+                    let mut color = color;
+                    update(&mut color, step * NumericStepper::step_count(event) as f32);
+                    on_increment(color, cx);
+                },
+            ))
+    }
+}
+
+/// Formats the color as `#rrggbb`, or `#rrggbbaa` when it isn't opaque.
+fn hex_label(color: Hsla) -> SharedString {
+    let rgba = Rgba::from(color);
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    let mut label = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(rgba.r),
+        channel(rgba.g),
+        channel(rgba.b)
+    );
+    if rgba.a < 1. {
+        label.push_str(&format!("{:02x}", channel(rgba.a)));
+    }
+    label.into()
+}
+
+impl RenderOnce for ColorPicker {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let color = self.color;
+
+        v_flex()
+            .id(self.id.clone())
+            .gap_2()
+            .p_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .size(Self::SWATCH_SIZE * 2.)
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(color),
+                    )
+                    .child(Label::new(hex_label(color))),
+            )
+            .child(
+                v_flex()
+                    .gap_0p5()
+                    .children(Self::palette_rows().enumerate().map(|(row_ix, row)| {
+                        h_flex().gap_0p5().children(row.into_iter().enumerate().map(
+                            |(column_ix, swatch_color)| {
+                                self.render_swatch(
+                                    SharedString::from(format!("swatch-{row_ix}-{column_ix}")),
+                                    swatch_color,
+                                    cx,
+                                )
+                            },
+                        ))
+                    })),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(self.render_channel_stepper(
+                        "Hue",
+                        format!("{:.0}°", color.h * 360.).into(),
+                        5. / 360.,
+                        |color, delta| color.h = (color.h + delta).rem_euclid(1.),
+                    ))
+                    .child(self.render_channel_stepper(
+                        "Saturation",
+                        format!("{:.0}%", color.s * 100.).into(),
+                        0.05,
+                        |color, delta| color.s = (color.s + delta).clamp(0., 1.),
+                    ))
+                    .child(self.render_channel_stepper(
+                        "Lightness",
+                        format!("{:.0}%", color.l * 100.).into(),
+                        0.05,
+                        |color, delta| color.l = (color.l + delta).clamp(0., 1.),
+                    ))
+                    .child(self.render_channel_stepper(
+                        "Alpha",
+                        format!("{:.0}%", color.a * 100.).into(),
+                        0.05,
+                        |color, delta| color.a = (color.a + delta).clamp(0., 1.),
+                    )),
+            )
+    }
+}
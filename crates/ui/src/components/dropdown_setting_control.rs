@@ -0,0 +1,116 @@
+use gpui::AppContext;
+use settings::{is_setting_modified, reset_setting, write_setting, Settings};
+
+use crate::{prelude::*, ContextMenu, DropdownMenu, SettingsControl, SettingsFilter};
+
+/// A control for a setting that takes one of a list of values, picked from a dropdown.
+///
+/// Unlike an [`EditableSettingControl`](settings::EditableSettingControl), it's declared
+/// with functions reading and applying the value, so that such a setting doesn't need a
+/// type of its own:
+///
+/// ```ignore
+/// DropdownSettingControl::<EditorSettings, _>::new(
+///     "Cursor Shape",
+///     |cx| EditorSettings::get_global(cx).cursor_shape,
+///     |settings, value, _cx| settings.cursor_shape = Some(value),
+/// )
+/// .option(CursorShape::Bar, "Bar")
+/// .option(CursorShape::Block, "Block")
+/// ```
+#[derive(IntoElement)]
+pub struct DropdownSettingControl<S: Settings, V: 'static + Copy + PartialEq + Send> {
+    name: SharedString,
+    options: Vec<(V, SharedString)>,
+    read: fn(&AppContext) -> V,
+    apply: fn(&mut S::FileContent, V, &AppContext),
+}
+
+impl<S: Settings, V: 'static + Copy + PartialEq + Send> DropdownSettingControl<S, V> {
+    pub fn new(
+        name: impl Into<SharedString>,
+        read: fn(&AppContext) -> V,
+        apply: fn(&mut S::FileContent, V, &AppContext),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            options: Vec::new(),
+            read,
+            apply,
+        }
+    }
+
+    /// Adds an option to the dropdown, shown with the given label.
+    pub fn option(mut self, value: V, label: impl Into<SharedString>) -> Self {
+        self.options.push((value, label.into()));
+        self
+    }
+
+    pub fn name(&self) -> SharedString {
+        self.name.clone()
+    }
+
+    /// Returns the values offered by the dropdown, with their labels.
+    pub fn options(&self) -> &[(V, SharedString)] {
+        &self.options
+    }
+
+    /// Returns the current value of the setting.
+    pub fn read(&self, cx: &AppContext) -> V {
+        (self.read)(cx)
+    }
+
+    /// Applies the given value to the settings file contents, as picking it does.
+    pub fn apply(&self, settings: &mut S::FileContent, value: V, cx: &AppContext) {
+        (self.apply)(settings, value, cx)
+    }
+}
+
+impl<S: Settings, V: 'static + Copy + PartialEq + Send> RenderOnce
+    for DropdownSettingControl<S, V>
+{
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let Self {
+            name,
+            options,
+            read,
+            apply,
+        } = self;
+        let value = read(cx);
+        let label = options
+            .iter()
+            .find(|(option, _)| *option == value)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_default();
+
+        let dropdown = h_flex()
+            .gap_2()
+            .child(SettingsFilter::label(name.clone(), cx))
+            .child(DropdownMenu::new(
+                name.clone(),
+                label,
+                ContextMenu::build(cx, move |mut menu, _cx| {
+                    for (option, label) in options {
+                        menu = menu.custom_entry(
+                            move |_cx| Label::new(label.clone()).into_any_element(),
+                            move |cx| {
+                                write_setting::<S>(
+                                    move |settings, cx| apply(settings, option, cx),
+                                    cx,
+                                )
+                            },
+                        )
+                    }
+
+                    menu
+                }),
+            ));
+
+        SettingsControl::custom(
+            name,
+            dropdown,
+            move |cx| is_setting_modified::<S>(|settings, cx| apply(settings, read(cx), cx), cx),
+            move |cx| reset_setting::<S>(|settings, cx| apply(settings, read(cx), cx), cx),
+        )
+    }
+}
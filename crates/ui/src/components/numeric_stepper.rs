@@ -10,6 +10,7 @@ pub struct NumericStepper {
     /// Whether to reserve space for the reset button.
     reserve_space_for_reset: bool,
     on_reset: Option<Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
+    value_editor: Option<AnyElement>,
 }
 
 impl NumericStepper {
@@ -24,6 +25,17 @@ impl NumericStepper {
             on_increment: Box::new(on_increment),
             reserve_space_for_reset: false,
             on_reset: None,
+            value_editor: None,
+        }
+    }
+
+    /// Returns by how many steps a click on the stepper buttons changes the value,
+    /// which is larger while Shift is held.
+    pub fn step_count(event: &ClickEvent) -> u32 {
+        if event.up.modifiers.shift {
+            5
+        } else {
+            1
         }
     }
 
@@ -39,6 +51,13 @@ impl NumericStepper {
         self.on_reset = Some(Box::new(on_reset));
         self
     }
+
+    /// Renders the given element in place of the value, such as an editor for typing
+    /// an exact value.
+    pub fn value_editor(mut self, value_editor: impl IntoElement) -> Self {
+        self.value_editor = Some(value_editor.into_any_element());
+        self
+    }
 }
 
 impl RenderOnce for NumericStepper {
@@ -79,7 +98,10 @@ impl RenderOnce for NumericStepper {
                             .icon_size(icon_size)
                             .on_click(self.on_decrement),
                     )
-                    .child(Label::new(self.value))
+                    .map(|element| match self.value_editor {
+                        Some(value_editor) => element.child(value_editor),
+                        None => element.child(Label::new(self.value)),
+                    })
                     .child(
                         IconButton::new("increment", IconName::Plus)
                             .shape(shape)
@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use gpui::{AnyElement, AppContext};
+use settings::EditableSettingControl;
+
+use crate::{prelude::*, Indicator, Tooltip};
+
+/// A control for a setting, which shows when the setting is modified and allows
+/// resetting it to its default value.
+#[derive(IntoElement)]
+pub struct SettingsControl {
+    name: SharedString,
+    control: AnyElement,
+    is_modified: Rc<dyn Fn(&AppContext) -> bool>,
+    reset: Rc<dyn Fn(&AppContext)>,
+}
+
+impl SettingsControl {
+    pub fn new<C: EditableSettingControl + IntoElement>(control: C) -> Self {
+        Self::custom(control.name(), control, C::is_modified, C::reset)
+    }
+
+    /// Creates a control for a setting that isn't edited by an [`EditableSettingControl`].
+    pub fn custom(
+        name: impl Into<SharedString>,
+        control: impl IntoElement,
+        is_modified: impl Fn(&AppContext) -> bool + 'static,
+        reset: impl Fn(&AppContext) + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            control: control.into_any_element(),
+            is_modified: Rc::new(is_modified),
+            reset: Rc::new(reset),
+        }
+    }
+}
+
+impl RenderOnce for SettingsControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let reset = self.reset;
+
+        h_flex()
+            .gap_2()
+            .child(self.control)
+            .when((self.is_modified)(cx), |this| {
+                this.child(Indicator::dot().color(Color::Modified)).child(
+                    Button::new(SharedString::from(format!("reset-{}", self.name)), "Reset")
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .tooltip(|cx| Tooltip::text("Reset to Default", cx))
+                        .on_click(move |_, cx| reset(cx)),
+                )
+            })
+    }
+}
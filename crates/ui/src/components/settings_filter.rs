@@ -0,0 +1,92 @@
+use gpui::{AppContext, Global};
+
+use crate::{prelude::*, HighlightedLabel};
+
+/// The query used to filter the controls in the settings UI.
+#[derive(Default)]
+pub struct SettingsFilter {
+    query: SharedString,
+}
+
+impl Global for SettingsFilter {}
+
+impl SettingsFilter {
+    /// Sets the query used to filter the settings controls.
+    pub fn set_query(query: impl Into<SharedString>, cx: &mut AppContext) {
+        cx.set_global(Self {
+            query: query.into(),
+        });
+    }
+
+    /// Returns the current query, which is empty when nothing is filtered.
+    pub fn query(cx: &AppContext) -> SharedString {
+        cx.try_global::<Self>()
+            .map(|filter| filter.query.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the byte positions of the query characters in the given text, if they
+    /// all appear in it in order, ignoring case.
+    pub fn match_positions(text: &str, cx: &AppContext) -> Option<Vec<usize>> {
+        let query = Self::query(cx);
+        let mut query_chars = query
+            .chars()
+            .filter(|char| !char.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .peekable();
+
+        let mut positions = Vec::new();
+        for (ix, char) in text.char_indices() {
+            let Some(query_char) = query_chars.peek() else {
+                break;
+            };
+            if char.to_lowercase().eq([*query_char]) {
+                positions.push(ix);
+                query_chars.next();
+            }
+        }
+
+        query_chars.peek().is_none().then_some(positions)
+    }
+
+    /// Returns a label for the given setting name, with the characters matching the
+    /// query highlighted.
+    pub fn label(name: impl Into<SharedString>, cx: &AppContext) -> HighlightedLabel {
+        let name = name.into();
+        let positions = Self::match_positions(&name, cx).unwrap_or_default();
+        HighlightedLabel::new(name, positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    fn test_match_positions(cx: &mut AppContext) {
+        assert_eq!(SettingsFilter::query(cx), "");
+        assert_eq!(
+            SettingsFilter::match_positions("Tab Size", cx),
+            Some(vec![])
+        );
+
+        SettingsFilter::set_query("tab sz", cx);
+        assert_eq!(
+            SettingsFilter::match_positions("Tab Size", cx),
+            Some(vec![0, 1, 2, 4, 6])
+        );
+        assert_eq!(
+            SettingsFilter::match_positions("Buffer Font Size", cx),
+            None
+        );
+
+        SettingsFilter::set_query("ZT", cx);
+        assert_eq!(SettingsFilter::match_positions("Tab Size", cx), None);
+
+        SettingsFilter::set_query("", cx);
+        assert_eq!(
+            SettingsFilter::match_positions("Tab Size", cx),
+            Some(vec![])
+        );
+    }
+}
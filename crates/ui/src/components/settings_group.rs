@@ -1,13 +1,17 @@
 use gpui::AnyElement;
+use settings::{EditableSettingControl, Settings};
 use smallvec::SmallVec;
 
-use crate::{prelude::*, ListHeader};
+use crate::{prelude::*, DropdownSettingControl, ListHeader, SettingsControl, SettingsFilter};
 
 /// A group of settings.
+///
+/// While the settings are filtered, only the children matching the [`SettingsFilter`]
+/// query are shown, unless the header matches it. The group is hidden when none do.
 #[derive(IntoElement)]
 pub struct SettingsGroup {
     header: SharedString,
-    children: SmallVec<[AnyElement; 2]>,
+    children: SmallVec<[(Vec<SharedString>, AnyElement); 2]>,
 }
 
 impl SettingsGroup {
@@ -17,20 +21,63 @@ impl SettingsGroup {
             children: SmallVec::new(),
         }
     }
+
+    /// Adds a control to the group, which is matched against the query by its name.
+    pub fn control<C: EditableSettingControl + IntoElement>(self, control: C) -> Self {
+        let name = control.name();
+        self.searchable_child([name], SettingsControl::new(control))
+    }
+
+    /// Adds a dropdown control to the group, which is matched against the query by its
+    /// name.
+    pub fn dropdown<S: Settings, V: 'static + Copy + PartialEq + Send>(
+        self,
+        control: DropdownSettingControl<S, V>,
+    ) -> Self {
+        let name = control.name();
+        self.searchable_child([name], control)
+    }
+
+    /// Adds a child to the group, which is matched against the query by the given
+    /// keywords.
+    pub fn searchable_child(
+        mut self,
+        keywords: impl IntoIterator<Item = SharedString>,
+        child: impl IntoElement,
+    ) -> Self {
+        self.children
+            .push((keywords.into_iter().collect(), child.into_any_element()));
+        self
+    }
 }
 
 impl ParentElement for SettingsGroup {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
-        self.children.extend(elements)
+        self.children
+            .extend(elements.into_iter().map(|element| (Vec::new(), element)))
     }
 }
 
 impl RenderOnce for SettingsGroup {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
-        v_flex()
-            .p_1()
-            .gap_2()
-            .child(ListHeader::new(self.header))
-            .children(self.children)
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let show_all = SettingsFilter::match_positions(&self.header, cx).is_some();
+        let children = self
+            .children
+            .into_iter()
+            .filter(|(keywords, _)| {
+                show_all
+                    || keywords
+                        .iter()
+                        .any(|keyword| SettingsFilter::match_positions(keyword, cx).is_some())
+            })
+            .map(|(_, child)| child)
+            .collect::<Vec<_>>();
+
+        v_flex().when(!children.is_empty(), |this| {
+            this.p_1()
+                .gap_2()
+                .child(ListHeader::new(self.header))
+                .children(children)
+        })
     }
 }
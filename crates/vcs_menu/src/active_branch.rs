@@ -0,0 +1,75 @@
+use gpui::{Model, Render, Subscription, ViewContext, WeakView};
+use project::Project;
+use std::sync::Arc;
+use ui::{
+    div, Button, ButtonCommon, Clickable, Color, FluentBuilder, IconName, IconPosition, IconSize,
+    IntoElement, LabelSize, ParentElement, Tooltip,
+};
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+use crate::{BranchList, CheckoutBranch};
+
+const MAX_BRANCH_NAME_LENGTH: usize = 40;
+
+/// The branch checked out in the repository at the root of the project, shown in the status bar,
+/// which opens the branch picker when clicked.
+pub struct ActiveBranch {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    _observe_project: Subscription,
+}
+
+impl ActiveBranch {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let _observe_project = cx.observe(&project, |_, _, cx| cx.notify());
+        Self {
+            project,
+            workspace: workspace.weak_handle(),
+            _observe_project,
+        }
+    }
+
+    fn branch_name(&self, cx: &ViewContext<Self>) -> Option<Arc<str>> {
+        self.project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()?
+            .read(cx)
+            .root_git_entry()?
+            .branch()
+    }
+}
+
+impl Render for ActiveBranch {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div().when_some(self.branch_name(cx), |el, branch_name| {
+            el.child(
+                Button::new(
+                    "active-branch",
+                    util::truncate_and_trailoff(&branch_name, MAX_BRANCH_NAME_LENGTH),
+                )
+                .icon(IconName::FileGit)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Muted)
+                .label_size(LabelSize::Small)
+                .on_click(cx.listener(|this, _, cx| {
+                    if let Some(workspace) = this.workspace.upgrade() {
+                        workspace.update(cx, |workspace, cx| BranchList::open(workspace, cx));
+                    }
+                }))
+                .tooltip(|cx| Tooltip::for_action("Checkout Branch", &CheckoutBranch, cx)),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ActiveBranch {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _cx: &mut ViewContext<Self>,
+    ) {
+    }
+}
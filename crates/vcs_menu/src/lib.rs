@@ -1,4 +1,5 @@
-use anyhow::{Context, Result};
+mod active_branch;
+
 use fuzzy::{StringMatch, StringMatchCandidate};
 use git::repository::Branch;
 use gpui::{
@@ -16,12 +17,18 @@ use util::ResultExt;
 use workspace::notifications::NotificationId;
 use workspace::{ModalView, Toast, Workspace};
 
+pub use active_branch::ActiveBranch;
+
 actions!(branches, [OpenRecent]);
+actions!(git, [CheckoutBranch]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
-        workspace.register_action(|workspace, action, cx| {
-            BranchList::open(workspace, action, cx).log_err();
+        workspace.register_action(|workspace, _: &OpenRecent, cx| {
+            BranchList::open(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &CheckoutBranch, cx| {
+            BranchList::open(workspace, cx);
         });
     })
     .detach();
@@ -43,16 +50,19 @@ impl BranchList {
             _subscription,
         }
     }
-    pub fn open(
-        workspace: &mut Workspace,
-        _: &OpenRecent,
-        cx: &mut ViewContext<Workspace>,
-    ) -> Result<()> {
-        // Modal branch picker has a longer trailoff than a popover one.
-        let delegate = BranchListDelegate::new(workspace, cx.view().clone(), 70, cx)?;
-        workspace.toggle_modal(cx, |cx| BranchList::new(delegate, 34., cx));
 
-        Ok(())
+    /// Opens the branch picker once the branches of the project's repository are listed.
+    pub fn open(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let branches = workspace.project().read(cx).branches(cx);
+        cx.spawn(|workspace, mut cx| async move {
+            let all_branches = branches.await?;
+            workspace.update(&mut cx, |workspace, cx| {
+                // Modal branch picker has a longer trailoff than a popover one.
+                let delegate = BranchListDelegate::new(cx.view().clone(), all_branches, 70);
+                workspace.toggle_modal(cx, |cx| BranchList::new(delegate, 34., cx));
+            })
+        })
+        .detach_and_log_err(cx);
     }
 }
 impl ModalView for BranchList {}
@@ -89,25 +99,18 @@ pub struct BranchListDelegate {
 
 impl BranchListDelegate {
     fn new(
-        workspace: &Workspace,
-        handle: View<Workspace>,
+        workspace: View<Workspace>,
+        all_branches: Vec<Branch>,
         branch_name_trailoff_after: usize,
-        cx: &AppContext,
-    ) -> Result<Self> {
-        let project = workspace.project().read(&cx);
-        let repo = project
-            .get_first_worktree_root_repo(cx)
-            .context("failed to get root repository for first worktree")?;
-
-        let all_branches = repo.branches()?;
-        Ok(Self {
+    ) -> Self {
+        Self {
             matches: vec![],
-            workspace: handle,
+            workspace,
             all_branches,
             selected_index: 0,
             last_query: Default::default(),
             branch_name_trailoff_after,
-        })
+        }
     }
 
     fn display_error_toast(&self, message: String, cx: &mut WindowContext<'_>) {
@@ -118,6 +121,48 @@ impl BranchListDelegate {
             model.show_toast(Toast::new(id, message), ctx)
         });
     }
+
+    fn checkout_branch(&self, name: String, cx: &mut ViewContext<Picker<Self>>) {
+        let checkout = self
+            .workspace
+            .read(cx)
+            .project()
+            .read(cx)
+            .change_branch(name.clone(), cx);
+        cx.spawn(|picker, mut cx| async move {
+            let result = checkout.await;
+            picker.update(&mut cx, |picker, cx| {
+                if result.is_err() {
+                    picker.delegate.display_error_toast(format!("Failed to checkout branch '{name}', check for conflicts or unstashed files"), cx);
+                } else {
+                    cx.emit(DismissEvent);
+                }
+            })?;
+            result
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Creates a branch at the current HEAD and checks it out.
+    fn create_branch(&self, name: String, cx: &mut ViewContext<Picker<Self>>) {
+        let create = self
+            .workspace
+            .read(cx)
+            .project()
+            .read(cx)
+            .create_branch(name.clone(), cx);
+        cx.spawn(|picker, mut cx| async move {
+            let result = create.await;
+            if result.is_err() {
+                picker.update(&mut cx, |picker, cx| {
+                    picker.delegate.display_error_toast(format!("Failed to create branch '{name}', check for conflicts or unstashed files"), cx);
+                })?;
+                return result;
+            }
+            picker.update(&mut cx, |picker, cx| picker.delegate.checkout_branch(name, cx))
+        })
+        .detach_and_log_err(cx);
+    }
 }
 
 impl PickerDelegate for BranchListDelegate {
@@ -147,16 +192,20 @@ impl PickerDelegate for BranchListDelegate {
                 if query.is_empty() {
                     if branches.len() > RECENT_BRANCHES_COUNT {
                         // Truncate list of recent branches
-                        // Do a partial sort to show recent-ish branches first.
+                        // Do a partial sort to show recent-ish local branches first.
                         branches.select_nth_unstable_by(RECENT_BRANCHES_COUNT - 1, |lhs, rhs| {
                             rhs.is_head
                                 .cmp(&lhs.is_head)
+                                .then(lhs.is_remote.cmp(&rhs.is_remote))
                                 .then(rhs.unix_timestamp.cmp(&lhs.unix_timestamp))
                         });
                         branches.truncate(RECENT_BRANCHES_COUNT);
                     }
                     branches.sort_unstable_by(|lhs, rhs| {
-                        rhs.is_head.cmp(&lhs.is_head).then(lhs.name.cmp(&rhs.name))
+                        rhs.is_head
+                            .cmp(&lhs.is_head)
+                            .then(lhs.is_remote.cmp(&rhs.is_remote))
+                            .then(lhs.name.cmp(&rhs.name))
                     });
                 }
                 branches
@@ -212,33 +261,12 @@ impl PickerDelegate for BranchListDelegate {
 
     fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
         let current_pick = self.selected_index();
-        let Some(current_pick) = self
-            .matches
-            .get(current_pick)
-            .map(|pick| pick.string.clone())
-        else {
-            return;
-        };
-        cx.spawn(|picker, mut cx| async move {
-            picker
-                .update(&mut cx, |this, cx| {
-                    let project = this.delegate.workspace.read(cx).project().read(cx);
-                    let repo = project
-                        .get_first_worktree_root_repo(cx)
-                        .context("failed to get root repository for first worktree")?;
-                    let status = repo
-                        .change_branch(&current_pick);
-                    if status.is_err() {
-                        this.delegate.display_error_toast(format!("Failed to checkout branch '{current_pick}', check for conflicts or unstashed files"), cx);
-                        status?;
-                    }
-                    cx.emit(DismissEvent);
-
-                    Ok::<(), anyhow::Error>(())
-                })
-                .log_err();
-        })
-        .detach();
+        match self.matches.get(current_pick) {
+            Some(pick) => self.checkout_branch(pick.string.clone(), cx),
+            // Confirming a query that matches no branch creates a branch with that name.
+            None if !self.last_query.is_empty() => self.create_branch(self.last_query.clone(), cx),
+            None => {}
+        }
     }
 
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
@@ -260,12 +288,32 @@ impl PickerDelegate for BranchListDelegate {
             .filter(|index| index < &&self.branch_name_trailoff_after)
             .copied()
             .collect();
+        let upstream_status = self
+            .all_branches
+            .iter()
+            .find(|branch| *branch.name == *hit.string)
+            .and_then(|branch| branch.upstream.as_ref())
+            .and_then(|upstream| {
+                let mut counts = Vec::new();
+                if upstream.ahead > 0 {
+                    counts.push(format!("↑{}", upstream.ahead));
+                }
+                if upstream.behind > 0 {
+                    counts.push(format!("↓{}", upstream.behind));
+                }
+                counts.is_empty().not().then(|| {
+                    Label::new(counts.join(" "))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                })
+            });
         Some(
             ListItem::new(SharedString::from(format!("vcs-menu-{ix}")))
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .selected(selected)
-                .start_slot(HighlightedLabel::new(shortened_branch_name, highlights)),
+                .start_slot(HighlightedLabel::new(shortened_branch_name, highlights))
+                .end_slot(upstream_status),
         )
     }
 
@@ -305,30 +353,9 @@ impl PickerDelegate for BranchListDelegate {
                 .child(h_flex().w_full())
                 .child(
                     Button::new("branch-picker-create-branch-button", "Create branch")
-                        .on_click(cx.listener(|_, _, cx| {
-                            cx.spawn(|picker, mut cx| async move {
-                                picker.update(&mut cx, |this, cx| {
-                                    let project =
-                                        this.delegate.workspace.read(cx).project().read(cx);
-                                    let current_pick = &this.delegate.last_query;
-                                    let repo = project.get_first_worktree_root_repo(cx).context(
-                                        "failed to get root repository for first worktree",
-                                    )?;
-                                    let status = repo.create_branch(&current_pick);
-                                    if status.is_err() {
-                                        this.delegate.display_error_toast(format!("Failed to create branch '{current_pick}', check for conflicts or unstashed files"), cx);
-                                        status?;
-                                    }
-                                    let status = repo.change_branch(&current_pick);
-                                    if status.is_err() {
-                                        this.delegate.display_error_toast(format!("Failed to check branch '{current_pick}', check for conflicts or unstashed files"), cx);
-                                        status?;
-                                    }
-                                    this.cancel(&Default::default(), cx);
-                                    Ok::<(), anyhow::Error>(())
-                                })
-                            })
-                            .detach_and_log_err(cx);
+                        .on_click(cx.listener(|picker, _, cx| {
+                            let name = picker.delegate.last_query.clone();
+                            picker.delegate.create_branch(name, cx);
                         }))
                         .style(ui::ButtonStyle::Filled),
                 )
@@ -250,11 +250,23 @@ fn generate_commands(_: &AppContext) -> Vec<VimCommand> {
         VimCommand::str(("cl", "ist"), "diagnostics::Deploy"),
         VimCommand::new(("cc", ""), editor::actions::Hover),
         VimCommand::new(("ll", ""), editor::actions::Hover),
-        VimCommand::new(("cn", "ext"), editor::actions::GoToDiagnostic),
-        VimCommand::new(("cp", "revious"), editor::actions::GoToPrevDiagnostic),
-        VimCommand::new(("cN", "ext"), editor::actions::GoToPrevDiagnostic),
-        VimCommand::new(("lp", "revious"), editor::actions::GoToPrevDiagnostic),
-        VimCommand::new(("lN", "ext"), editor::actions::GoToPrevDiagnostic),
+        VimCommand::new(("cn", "ext"), editor::actions::GoToDiagnostic::default()),
+        VimCommand::new(
+            ("cp", "revious"),
+            editor::actions::GoToPrevDiagnostic::default(),
+        ),
+        VimCommand::new(
+            ("cN", "ext"),
+            editor::actions::GoToPrevDiagnostic::default(),
+        ),
+        VimCommand::new(
+            ("lp", "revious"),
+            editor::actions::GoToPrevDiagnostic::default(),
+        ),
+        VimCommand::new(
+            ("lN", "ext"),
+            editor::actions::GoToPrevDiagnostic::default(),
+        ),
         VimCommand::new(("j", "oin"), JoinLines),
         VimCommand::new(("d", "elete"), editor::actions::DeleteLine),
         VimCommand::new(("sor", "t"), SortLinesCaseSensitive),
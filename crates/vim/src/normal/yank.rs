@@ -150,6 +150,7 @@ fn copy_selections_content_internal(
                 len: text.len() - initial_len,
                 is_entire_line: linewise,
                 first_line_indent: buffer.indent_size_for_line(MultiBufferRow(start.row)).len,
+                is_block: false,
             });
         }
     }
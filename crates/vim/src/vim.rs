@@ -22,13 +22,13 @@ use collections::HashMap;
 use command_palette_hooks::{CommandPaletteFilter, CommandPaletteInterceptor};
 use editor::{
     movement::{self, FindRange},
-    Anchor, Bias, Editor, EditorEvent, EditorMode, ToPoint,
+    Anchor, Bias, Editor, EditorEvent, EditorMode, EditorSettings, ToPoint,
 };
 use gpui::{
     actions, impl_actions, Action, AppContext, EntityId, FocusableView, Global, KeystrokeEvent,
     Subscription, UpdateGlobal, View, ViewContext, WeakView, WindowContext,
 };
-use language::{CursorShape, Point, SelectionGoal, TransactionId};
+use language::{Point, SelectionGoal, TransactionId};
 pub use mode_indicator::ModeIndicator;
 use motion::Motion;
 use normal::{
@@ -1020,7 +1020,7 @@ impl Vim {
 
     fn unhook_vim_settings(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
         if editor.mode() == EditorMode::Full {
-            editor.set_cursor_shape(CursorShape::Bar, cx);
+            editor.set_cursor_shape(EditorSettings::get_global(cx).cursor_shape, cx);
             editor.set_clip_at_line_ends(false, cx);
             editor.set_collapse_matches(false);
             editor.set_input_enabled(true);
@@ -280,6 +280,10 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
 
     fn added_to_workspace(&mut self, _workspace: &mut Workspace, _cx: &mut ViewContext<Self>) {}
 
+    /// Called when the workspace enters or leaves zen mode, for the item to hide or show
+    /// the parts of itself that are distracting while reading or writing.
+    fn set_zen_mode(&mut self, _zen_mode: bool, _cx: &mut ViewContext<Self>) {}
+
     fn show_toolbar(&self) -> bool {
         true
     }
@@ -390,6 +394,7 @@ pub trait ItemHandle: 'static + Send {
     );
     fn deactivated(&self, cx: &mut WindowContext);
     fn workspace_deactivated(&self, cx: &mut WindowContext);
+    fn set_zen_mode(&self, zen_mode: bool, cx: &mut WindowContext);
     fn navigate(&self, data: Box<dyn Any>, cx: &mut WindowContext) -> bool;
     fn item_id(&self) -> EntityId;
     fn to_any(&self) -> AnyView;
@@ -726,6 +731,10 @@ impl<T: Item> ItemHandle for View<T> {
         self.update(cx, |this, cx| this.workspace_deactivated(cx));
     }
 
+    fn set_zen_mode(&self, zen_mode: bool, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.set_zen_mode(zen_mode, cx));
+    }
+
     fn navigate(&self, data: Box<dyn Any>, cx: &mut WindowContext) -> bool {
         self.update(cx, |this, cx| this.navigate(data, cx))
     }
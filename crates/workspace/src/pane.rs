@@ -219,6 +219,7 @@ pub struct Pane {
     activation_history: Vec<ActivationHistoryEntry>,
     next_activation_timestamp: Arc<AtomicUsize>,
     zoomed: bool,
+    zen_mode: bool,
     was_focused: bool,
     active_item_index: usize,
     preview_item_id: Option<EntityId>,
@@ -331,6 +332,7 @@ impl Pane {
             next_activation_timestamp: next_timestamp.clone(),
             was_focused: false,
             zoomed: false,
+            zen_mode: false,
             active_item_index: 0,
             preview_item_id: None,
             last_focus_handle_by_item: Default::default(),
@@ -1920,6 +1922,20 @@ impl Pane {
         self.zoomed
     }
 
+    /// Hides the tab bar and the toolbar while the workspace is in zen mode, and lets the
+    /// pane's items hide their own distractions.
+    pub fn set_zen_mode(&mut self, zen_mode: bool, cx: &mut ViewContext<Self>) {
+        self.zen_mode = zen_mode;
+        for item in &self.items {
+            item.set_zen_mode(zen_mode, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode
+    }
+
     fn handle_drag_move<T>(&mut self, event: &DragMoveEvent<T>, cx: &mut ViewContext<Self>) {
         if !self.can_split {
             return;
@@ -2122,7 +2138,7 @@ impl Render for Pane {
         }
 
         let should_display_tab_bar = self.should_display_tab_bar.clone();
-        let display_tab_bar = should_display_tab_bar(cx);
+        let display_tab_bar = !self.zen_mode && should_display_tab_bar(cx);
 
         v_flex()
             .key_context(key_context)
@@ -2241,7 +2257,7 @@ impl Render for Pane {
                     .map(|div| {
                         if let Some(item) = self.active_item() {
                             div.v_flex()
-                                .child(self.toolbar.clone())
+                                .when(!self.zen_mode, |div| div.child(self.toolbar.clone()))
                                 .child(item.to_any())
                         } else {
                             let placeholder = div.h_flex().size_full().justify_center();
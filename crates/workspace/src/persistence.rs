@@ -24,7 +24,8 @@ use model::{
 };
 
 use self::model::{
-    DockStructure, LocalPathsOrder, SerializedDevServerProject, SerializedWorkspaceLocation,
+    DockStructure, LocalPathsOrder, SerializedDevServerProject, SerializedSshProject,
+    SerializedWorkspaceLocation,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -348,6 +349,17 @@ define_connection! {
     sql!(
         ALTER TABLE workspaces ADD COLUMN session_id TEXT DEFAULT NULL;
     ),
+    sql!(
+        CREATE TABLE ssh_projects (
+            id INTEGER PRIMARY KEY,
+            host TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            user TEXT NOT NULL,
+            paths TEXT NOT NULL,
+            UNIQUE(host, port, user, paths)
+        );
+        ALTER TABLE workspaces ADD COLUMN ssh_project_id INTEGER REFERENCES ssh_projects(id) ON DELETE CASCADE;
+    ),
     ];
 }
 
@@ -655,6 +667,74 @@ impl WorkspaceDb {
                         ))
                         .context("Updating workspace")?;
                     }
+                    SerializedWorkspaceLocation::Ssh(ssh_project) => {
+                        conn.exec_bound(sql!(
+                            INSERT INTO ssh_projects(host, port, user, paths)
+                            VALUES (?1, ?2, ?3, ?4)
+                            ON CONFLICT DO NOTHING
+                        ))?(&ssh_project)?;
+                        let ssh_project_id = conn.select_row_bound::<_, u64>(sql!(
+                            SELECT id FROM ssh_projects
+                            WHERE host = ? AND port = ? AND user = ? AND paths = ?
+                        ))?(&ssh_project)?
+                        .context("missing ssh project")?;
+
+                        conn.exec_bound(sql!(
+                            DELETE FROM workspaces WHERE ssh_project_id = ? AND workspace_id != ?
+                        ))?((ssh_project_id, workspace.id))
+                        .context("clearing out old locations")?;
+
+                        // Upsert
+                        conn.exec_bound(sql!(
+                            INSERT INTO workspaces(
+                                workspace_id,
+                                ssh_project_id,
+                                left_dock_visible,
+                                left_dock_active_panel,
+                                left_dock_zoom,
+                                right_dock_visible,
+                                right_dock_active_panel,
+                                right_dock_zoom,
+                                bottom_dock_visible,
+                                bottom_dock_active_panel,
+                                bottom_dock_zoom,
+                                session_id,
+                                timestamp
+                            )
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, CURRENT_TIMESTAMP)
+                            ON CONFLICT DO
+                            UPDATE SET
+                                ssh_project_id = ?2,
+                                left_dock_visible = ?3,
+                                left_dock_active_panel = ?4,
+                                left_dock_zoom = ?5,
+                                right_dock_visible = ?6,
+                                right_dock_active_panel = ?7,
+                                right_dock_zoom = ?8,
+                                bottom_dock_visible = ?9,
+                                bottom_dock_active_panel = ?10,
+                                bottom_dock_zoom = ?11,
+                                session_id = ?12,
+                                timestamp = CURRENT_TIMESTAMP
+                        ))?((
+                            workspace.id,
+                            ssh_project_id,
+                            workspace.docks,
+                            workspace.session_id,
+                        ))
+                        .context("Updating workspace")?;
+
+                        // The paths of a project change as folders are added to it, so
+                        // remove the locations that no workspace uses anymore.
+                        conn.exec(sql!(
+                            DELETE FROM ssh_projects
+                            WHERE id NOT IN (
+                                SELECT ssh_project_id FROM workspaces
+                                WHERE ssh_project_id IS NOT NULL
+                            )
+                        ))?()
+                        .context("clearing out unused ssh projects")?;
+                    }
                 }
 
                 // Save center pane group
@@ -687,11 +767,36 @@ impl WorkspaceDb {
         fn session_workspace_locations(session_id: String) -> Result<Vec<LocalPaths>> {
             SELECT local_paths
             FROM workspaces
-            WHERE session_id = ?1 AND dev_server_project_id IS NULL
+            WHERE session_id = ?1 AND local_paths IS NOT NULL
             ORDER BY timestamp DESC
         }
     }
 
+    query! {
+        pub fn session_ssh_projects(session_id: String) -> Result<Vec<SerializedSshProject>> {
+            SELECT ssh_projects.host, ssh_projects.port, ssh_projects.user, ssh_projects.paths
+            FROM workspaces
+            JOIN ssh_projects ON ssh_projects.id = workspaces.ssh_project_id
+            WHERE workspaces.session_id = ?1
+            ORDER BY workspaces.timestamp DESC
+        }
+    }
+
+    pub(crate) fn workspace_id_for_ssh_project(
+        &self,
+        ssh_project: &SerializedSshProject,
+    ) -> Result<Option<WorkspaceId>> {
+        self.select_row_bound(sql!(
+            SELECT workspaces.workspace_id
+            FROM workspaces
+            JOIN ssh_projects ON ssh_projects.id = workspaces.ssh_project_id
+            WHERE ssh_projects.host = ?
+                AND ssh_projects.port = ?
+                AND ssh_projects.user = ?
+                AND ssh_projects.paths = ?
+        ))?(ssh_project)
+    }
+
     query! {
         fn dev_server_projects() -> Result<Vec<SerializedDevServerProject>> {
             SELECT id, path, dev_server_name
@@ -782,7 +887,9 @@ impl WorkspaceDb {
             .into_iter()
             .filter_map(|(_, location)| match location {
                 SerializedWorkspaceLocation::Local(local_paths, _) => Some(local_paths),
-                SerializedWorkspaceLocation::DevServer(_) => None,
+                SerializedWorkspaceLocation::DevServer(_) | SerializedWorkspaceLocation::Ssh(_) => {
+                    None
+                }
             })
             .next())
     }
@@ -27,6 +27,16 @@ pub struct SerializedDevServerProject {
     pub paths: Vec<SharedString>,
 }
 
+/// A project opened over SSH. The password, if any, isn't stored: reconnecting prompts
+/// for credentials again, unless they were saved in the keychain.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SerializedSshProject {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub paths: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct LocalPaths(Arc<Vec<PathBuf>>);
 
@@ -111,6 +121,48 @@ impl From<SerializedDevServerProject> for SerializedWorkspaceLocation {
     }
 }
 
+impl From<SerializedSshProject> for SerializedWorkspaceLocation {
+    fn from(ssh_project: SerializedSshProject) -> Self {
+        Self::Ssh(ssh_project)
+    }
+}
+
+impl StaticColumnCount for SerializedSshProject {
+    fn column_count() -> usize {
+        4
+    }
+}
+
+impl Bind for &SerializedSshProject {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let next_index = statement.bind(&self.host, start_index)?;
+        let next_index = statement.bind(&(self.port as u32), next_index)?;
+        let next_index = statement.bind(&self.user, next_index)?;
+        let paths = serde_json::to_string(&self.paths)?;
+        statement.bind(&paths, next_index)
+    }
+}
+
+impl Column for SerializedSshProject {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let host = statement.column_text(start_index)?.to_string();
+        let port = statement.column_int64(start_index + 1)? as u16;
+        let user = statement.column_text(start_index + 2)?.to_string();
+        let paths = statement.column_text(start_index + 3)?;
+        let paths = serde_json::from_str(paths).context("JSON deserialization of paths failed")?;
+
+        Ok((
+            Self {
+                host,
+                port,
+                user,
+                paths,
+            },
+            start_index + 4,
+        ))
+    }
+}
+
 impl StaticColumnCount for SerializedDevServerProject {}
 impl Bind for &SerializedDevServerProject {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
@@ -147,6 +199,7 @@ impl Column for SerializedDevServerProject {
 pub enum SerializedWorkspaceLocation {
     Local(LocalPaths, LocalPathsOrder),
     DevServer(SerializedDevServerProject),
+    Ssh(SerializedSshProject),
 }
 
 impl SerializedWorkspaceLocation {
@@ -52,7 +52,10 @@ pub use pane::*;
 pub use pane_group::*;
 use persistence::{model::SerializedWorkspace, SerializedWindowBounds, DB};
 pub use persistence::{
-    model::{ItemId, LocalPaths, SerializedDevServerProject, SerializedWorkspaceLocation},
+    model::{
+        ItemId, LocalPaths, SerializedDevServerProject, SerializedSshProject,
+        SerializedWorkspaceLocation,
+    },
     WorkspaceDb, DB as WORKSPACE_DB,
 };
 use postage::stream::Stream;
@@ -140,6 +143,7 @@ actions!(
         ToggleCenteredLayout,
         ToggleLeftDock,
         ToggleRightDock,
+        ToggleZenMode,
         ToggleZoom,
         Unfollow,
         Welcome,
@@ -729,6 +733,7 @@ pub struct Workspace {
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     centered_layout: bool,
+    zen_mode: bool,
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
@@ -1026,6 +1031,7 @@ impl Workspace {
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
@@ -2372,6 +2378,9 @@ impl Workspace {
                 cx,
             )
         });
+        if self.zen_mode {
+            pane.update(cx, |pane, cx| pane.set_zen_mode(true, cx));
+        }
         cx.subscribe(&pane, Self::handle_pane_event).detach();
         self.panes.push(pane.clone());
         cx.focus_view(&pane);
@@ -3833,7 +3842,7 @@ impl Workspace {
     fn local_paths(&self, cx: &AppContext) -> Option<Vec<Arc<Path>>> {
         let project = self.project().read(cx);
 
-        if project.is_local() {
+        if project.is_local() && project.ssh_session().is_none() {
             Some(
                 project
                     .visible_worktrees(cx)
@@ -4013,6 +4022,8 @@ impl Workspace {
                 };
                 Some(SerializedWorkspaceLocation::DevServer(dev_server_project))
             })
+        } else if let Some(ssh_project) = ssh_project_for_project(self.project(), cx) {
+            Some(SerializedWorkspaceLocation::Ssh(ssh_project))
         } else {
             None
         };
@@ -4278,6 +4289,7 @@ impl Workspace {
                 }),
             )
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_zen_mode))
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -4353,6 +4365,37 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Toggles zen mode, which hides the docks and the panes' tab bars and toolbars, and
+    /// centers the panes at the width configured in the `zen_mode` settings.
+    pub fn toggle_zen_mode(&mut self, _: &ToggleZenMode, cx: &mut ViewContext<Self>) {
+        self.zen_mode = !self.zen_mode;
+        for pane in &self.panes {
+            pane.update(cx, |pane, cx| pane.set_zen_mode(self.zen_mode, cx));
+        }
+        cx.notify();
+    }
+
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode
+    }
+
+    /// The width of the panes in zen mode: the configured number of columns of the buffer font.
+    fn zen_mode_width(&self, cx: &WindowContext) -> Pixels {
+        let theme_settings = ThemeSettings::get_global(cx);
+        let font_id = cx.text_system().resolve_font(&theme_settings.buffer_font);
+        let font_size = theme_settings.buffer_font_size(cx);
+        let em_advance = cx
+            .text_system()
+            .advance(font_id, font_size, 'm')
+            .map(|advance| advance.width)
+            .unwrap_or(font_size);
+        let columns = WorkspaceSettings::get_global(cx)
+            .zen_mode
+            .width
+            .unwrap_or(100);
+        em_advance * columns as f32
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(Self::DEFAULT_PADDING)
@@ -4365,7 +4408,7 @@ impl Workspace {
         dock: &View<Dock>,
         cx: &WindowContext,
     ) -> Option<Div> {
-        if self.zoomed_position == Some(position) {
+        if self.zoomed_position == Some(position) || self.zen_mode {
             return None;
         }
 
@@ -4562,6 +4605,7 @@ impl Render for Workspace {
         let mut context = KeyContext::new_with_defaults();
         context.add("Workspace");
         let centered_layout = self.centered_layout
+            && !self.zen_mode
             && self.center.panes().len() == 1
             && self.active_item(cx).is_some();
         let render_padding = |size| {
@@ -4582,6 +4626,7 @@ impl Render for Workspace {
         } else {
             (None, None)
         };
+        let zen_mode_width = self.zen_mode.then(|| self.zen_mode_width(cx));
         let ui_font = theme::setup_ui_font(cx);
 
         let theme = cx.theme().clone();
@@ -4667,15 +4712,35 @@ impl Render for Workspace {
                                                 .when_some(paddings.0, |this, p| {
                                                     this.child(p.border_r_1())
                                                 })
-                                                .child(self.center.render(
-                                                    &self.project,
-                                                    &self.follower_states,
-                                                    self.active_call(),
-                                                    &self.active_pane,
-                                                    self.zoomed.as_ref(),
-                                                    &self.app_state,
-                                                    cx,
-                                                ))
+                                                .when(zen_mode_width.is_some(), |this| {
+                                                    this.justify_center()
+                                                        .bg(colors.editor_background)
+                                                })
+                                                .child({
+                                                    let center = self
+                                                        .center
+                                                        .render(
+                                                            &self.project,
+                                                            &self.follower_states,
+                                                            self.active_call(),
+                                                            &self.active_pane,
+                                                            self.zoomed.as_ref(),
+                                                            &self.app_state,
+                                                            cx,
+                                                        )
+                                                        .into_any_element();
+                                                    if let Some(width) = zen_mode_width {
+                                                        div()
+                                                            .flex()
+                                                            .h_full()
+                                                            .w(width)
+                                                            .max_w_full()
+                                                            .child(center)
+                                                            .into_any_element()
+                                                    } else {
+                                                        center
+                                                    }
+                                                })
                                                 .when_some(paddings.1, |this, p| {
                                                     this.child(p.border_l_1())
                                                 }),
@@ -4905,6 +4970,43 @@ pub fn last_session_workspace_locations(last_session_id: &str) -> Option<Vec<Loc
         .log_err()
 }
 
+/// Returns the projects that were opened over SSH in the given session.
+pub fn last_session_ssh_projects(last_session_id: &str) -> Vec<SerializedSshProject> {
+    DB.session_ssh_projects(last_session_id.to_owned())
+        .log_err()
+        .unwrap_or_default()
+}
+
+/// Returns the id to serialize the workspace of an SSH project as, which is the one it
+/// had the last time the same folders were opened on that host.
+pub async fn workspace_id_for_ssh_project(
+    ssh_project: &SerializedSshProject,
+) -> Result<WorkspaceId> {
+    if let Some(workspace_id) = DB.workspace_id_for_ssh_project(ssh_project)? {
+        return Ok(workspace_id);
+    }
+    DB.next_id().await
+}
+
+/// The serialized location of a project opened over SSH, or `None` for other projects.
+pub fn ssh_project_for_project(
+    project: &Model<Project>,
+    cx: &AppContext,
+) -> Option<SerializedSshProject> {
+    let project = project.read(cx);
+    let session = project.ssh_session()?;
+    let connection_options = session.connection_options()?;
+    Some(SerializedSshProject {
+        host: connection_options.host.clone(),
+        port: connection_options.port,
+        user: connection_options.username.clone(),
+        paths: project
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_string_lossy().into_owned())
+            .collect(),
+    })
+}
+
 actions!(collab, [OpenChannelNotes]);
 actions!(zed, [OpenLog]);
 
@@ -6365,6 +6467,44 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_zen_mode(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        pane.update(cx, |pane, cx| {
+            let item = cx.new_view(|cx| TestItem::new(cx));
+            pane.add_item(Box::new(item), true, true, None, cx);
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_zen_mode(&ToggleZenMode, cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.is_zen_mode());
+            assert!(pane.read(cx).is_zen_mode());
+        });
+
+        // Panes that are added while in zen mode are in zen mode too.
+        let split_pane = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, cx)
+        });
+        split_pane.update(cx, |split_pane, _| assert!(split_pane.is_zen_mode()));
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_zen_mode(&ToggleZenMode, cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.is_zen_mode());
+            assert!(!pane.read(cx).is_zen_mode());
+            assert!(!split_pane.read(cx).is_zen_mode());
+        });
+    }
+
     struct TestModal(FocusHandle);
 
     impl TestModal {
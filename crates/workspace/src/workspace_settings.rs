@@ -17,6 +17,7 @@ pub struct WorkspaceSettings {
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
     pub use_system_path_prompts: bool,
     pub command_aliases: HashMap<String, String>,
+    pub zen_mode: ZenModeSettings,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -98,6 +99,8 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: true
     pub command_aliases: Option<HashMap<String, String>>,
+    // Zen mode related settings.
+    pub zen_mode: Option<ZenModeSettings>,
 }
 
 #[derive(Deserialize)]
@@ -146,6 +149,20 @@ pub struct CenteredLayoutSettings {
     pub right_padding: Option<f32>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ZenModeSettings {
+    /// The width of the central panes in zen mode, in columns of the buffer font.
+    ///
+    /// Default: 100
+    pub width: Option<u32>,
+    /// Whether to keep the cursor vertically centered in editors in zen mode,
+    /// scrolling as it moves.
+    ///
+    /// Default: false
+    pub typewriter_scrolling: Option<bool>,
+}
+
 impl Settings for WorkspaceSettings {
     const KEY: Option<&'static str> = None;
 
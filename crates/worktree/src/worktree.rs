@@ -102,7 +102,9 @@ pub enum CreatedEntry {
 
 pub struct LoadedFile {
     pub file: Arc<File>,
-    pub text: String,
+    /// The content of the file, with normalized line endings.
+    pub text: Rope,
+    pub line_ending: LineEnding,
     pub diff_base: Option<String>,
 }
 
@@ -1204,7 +1206,7 @@ impl LocalWorktree {
 
         cx.spawn(|this, mut cx| async move {
             let abs_path = abs_path?;
-            let text = fs.load(&abs_path).await?;
+            let (text, line_ending) = fs.load_rope(&abs_path).await?;
             let mut index_task = None;
             let snapshot = this.update(&mut cx, |this, _| this.as_local().unwrap().snapshot())?;
             if let Some(repo) = snapshot.repository_for_path(&path) {
@@ -1255,6 +1257,7 @@ impl LocalWorktree {
             Ok(LoadedFile {
                 file,
                 text,
+                line_ending,
                 diff_base,
             })
         })
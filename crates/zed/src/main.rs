@@ -28,20 +28,20 @@ use assets::Assets;
 use node_runtime::RealNodeRuntime;
 use parking_lot::Mutex;
 use release_channel::{AppCommitSha, AppVersion};
+use remote::SshConnectionOptions;
 use session::Session;
 use settings::{handle_settings_file_changes, watch_config_file, Settings, SettingsStore};
 use simplelog::ConfigBuilder;
-use smol::process::Command;
 use std::{
     env,
     fs::OpenOptions,
     io::{IsTerminal, Write},
-    path::Path,
-    process,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
     sync::Arc,
 };
 use theme::{ActiveTheme, SystemAppearance, ThemeRegistry, ThemeSettings};
-use util::{maybe, parse_env_output, with_clone, ResultExt, TryFutureExt};
+use util::{maybe, paths::PathLikeWithPosition, with_clone, ResultExt, TryFutureExt};
 use uuid::Uuid;
 use welcome::{show_welcome_view, BaseKeymap, FIRST_OPEN};
 use workspace::{AppState, WorkspaceSettings, WorkspaceStore};
@@ -204,6 +204,7 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     crate::zed::linux_prompts::init(cx);
 
     app_state.languages.set_theme(cx.theme().clone());
+    spell_check::init(app_state.fs.clone(), cx);
     editor::init(cx);
     image_viewer::init(cx);
     diagnostics::init(cx);
@@ -220,6 +221,8 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     project_symbols::init(cx);
     project_panel::init(Assets, cx);
     outline_panel::init(Assets, cx);
+    bookmarks_panel::init(cx);
+    git_panel::init(cx);
     tasks_ui::init(cx);
     channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
     search::init(cx);
@@ -227,6 +230,7 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     terminal_view::init(cx);
     journal::init(app_state.clone(), cx);
     language_selector::init(cx);
+    snippets_ui::init(cx);
     theme_selector::init(cx);
     language_tools::init(cx);
     call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -377,7 +381,9 @@ fn main() {
             {
                 load_shell_from_passwd().await.log_err();
             }
-            load_login_shell_environment().await.log_err();
+            util::load_login_shell_environment(true, Stdio::null())
+                .await
+                .log_err();
         })
     };
 
@@ -535,13 +541,16 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
         return;
     };
 
-    if let Some(connection_info) = request.ssh_connection {
+    if let Some(connection_options) = request.ssh_connection {
         cx.spawn(|mut cx| async move {
             open_ssh_paths(
-                connection_info,
+                connection_options,
                 request.open_paths,
                 app_state,
-                workspace::OpenOptions::default(),
+                workspace::OpenOptions {
+                    open_new_workspace: request.open_new_workspace,
+                    ..Default::default()
+                },
                 &mut cx,
             )
             .await
@@ -671,6 +680,52 @@ async fn restore_or_create_workspace(
     app_state: Arc<AppState>,
     cx: &mut AsyncAppContext,
 ) -> Result<()> {
+    let ssh_projects = restorable_ssh_projects(cx, &app_state);
+    let reopening_ssh_projects = !ssh_projects.is_empty();
+
+    // Reconnecting may prompt for credentials, so each project is opened in its own
+    // window without holding up the others.
+    for ssh_project in ssh_projects {
+        let connection_options = SshConnectionOptions {
+            host: ssh_project.host,
+            username: ssh_project.user,
+            port: ssh_project.port,
+            password: None,
+            proxy: None,
+            identity: None,
+            timeouts: Default::default(),
+            additional_args: Vec::new(),
+            forward_agent: false,
+            transport: Default::default(),
+        };
+        let paths = ssh_project
+            .paths
+            .into_iter()
+            .map(|path| PathLikeWithPosition {
+                path_like: PathBuf::from(path),
+                row: None,
+                column: None,
+            })
+            .collect();
+        let app_state = app_state.clone();
+        cx.update(|cx| {
+            cx.spawn(|mut cx| async move {
+                open_ssh_paths(
+                    connection_options,
+                    paths,
+                    app_state,
+                    workspace::OpenOptions {
+                        open_new_workspace: Some(true),
+                        ..Default::default()
+                    },
+                    &mut cx,
+                )
+                .await
+            })
+            .detach_and_log_err(cx)
+        })?;
+    }
+
     if let Some(locations) = restorable_workspace_locations(cx, &app_state).await {
         for location in locations {
             cx.update(|cx| {
@@ -683,6 +738,8 @@ async fn restore_or_create_workspace(
             })?
             .await?;
         }
+    } else if reopening_ssh_projects {
+        // The reopened SSH projects take the place of the welcome page or an empty buffer.
     } else if matches!(KEY_VALUE_STORE.read_kvp(FIRST_OPEN), Ok(None)) {
         cx.update(|cx| show_welcome_view(app_state, cx))?.await?;
     } else {
@@ -697,6 +754,27 @@ async fn restore_or_create_workspace(
     Ok(())
 }
 
+/// The projects opened over SSH in the last session, which are reopened along with
+/// the local ones when restoring it.
+fn restorable_ssh_projects(
+    cx: &mut AsyncAppContext,
+    app_state: &Arc<AppState>,
+) -> Vec<workspace::SerializedSshProject> {
+    let restore_behavior = cx
+        .update(|cx| WorkspaceSettings::get(None, cx).restore_on_startup)
+        .ok();
+    if !matches!(
+        restore_behavior,
+        Some(workspace::RestoreOnStartupBehavior::LastSession)
+    ) {
+        return Vec::new();
+    }
+    match app_state.session.last_session_id() {
+        Some(last_session_id) => workspace::last_session_ssh_projects(last_session_id),
+        None => Vec::new(),
+    }
+}
+
 pub(crate) async fn restorable_workspace_locations(
     cx: &mut AsyncAppContext,
     app_state: &Arc<AppState>,
@@ -872,58 +950,6 @@ async fn load_shell_from_passwd() -> Result<()> {
     Ok(())
 }
 
-async fn load_login_shell_environment() -> Result<()> {
-    let marker = "ZED_LOGIN_SHELL_START";
-    let shell = env::var("SHELL").context(
-        "SHELL environment variable is not assigned so we can't source login environment variables",
-    )?;
-
-    // If possible, we want to `cd` in the user's `$HOME` to trigger programs
-    // such as direnv, asdf, mise, ... to adjust the PATH. These tools often hook
-    // into shell's `cd` command (and hooks) to manipulate env.
-    // We do this so that we get the env a user would have when spawning a shell
-    // in home directory.
-    let shell_cmd_prefix = std::env::var_os("HOME")
-        .and_then(|home| home.into_string().ok())
-        .map(|home| format!("cd '{home}';"));
-
-    // The `exit 0` is the result of hours of debugging, trying to find out
-    // why running this command here, without `exit 0`, would mess
-    // up signal process for our process so that `ctrl-c` doesn't work
-    // anymore.
-    // We still don't know why `$SHELL -l -i -c '/usr/bin/env -0'`  would
-    // do that, but it does, and `exit 0` helps.
-    let shell_cmd = format!(
-        "{}printf '%s' {marker}; /usr/bin/env; exit 0;",
-        shell_cmd_prefix.as_deref().unwrap_or("")
-    );
-
-    let output = Command::new(&shell)
-        .args(["-l", "-i", "-c", &shell_cmd])
-        .output()
-        .await
-        .context("failed to spawn login shell to source login environment variables")?;
-    if !output.status.success() {
-        Err(anyhow!("login shell exited with error"))?;
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
-    if let Some(env_output_start) = stdout.find(marker) {
-        let env_output = &stdout[env_output_start + marker.len()..];
-
-        parse_env_output(env_output, |key, value| env::set_var(key, value));
-
-        log::info!(
-            "set environment variables from shell:{}, path:{}",
-            shell,
-            env::var("PATH").unwrap_or_default(),
-        );
-    }
-
-    Ok(())
-}
-
 fn stdout_is_a_pty() -> bool {
     std::env::var(FORCE_CLI_MODE_ENV_VAR_NAME).ok().is_none() && std::io::stdout().is_terminal()
 }
@@ -936,7 +962,7 @@ struct Args {
     /// Use `path:line:row` syntax to open a file at a specific location.
     /// Non-existing paths and directories will ignore `:line:row` suffix.
     ///
-    /// URLs can either be `file://` or `zed://` scheme, or relative to <https://zed.dev>.
+    /// URLs can either be `file://`, `ssh://` or `zed://` scheme, or relative to <https://zed.dev>.
     paths_or_urls: Vec<String>,
 
     /// Instructs zed to run as a dev server on this machine. (not implemented)
@@ -5,14 +5,21 @@ pub(crate) mod linux_prompts;
 #[cfg(not(target_os = "linux"))]
 pub(crate) mod only_instance;
 mod open_listener;
+mod remote_info_modal;
+mod remote_server_log;
+pub mod remote_settings;
+mod remote_target_picker;
 pub(crate) mod session;
 mod ssh_connection_modal;
+mod ssh_status_indicator;
 
 pub use app_menus::*;
+use bookmarks_panel::BookmarksPanel;
 use breadcrumbs::Breadcrumbs;
 use client::ZED_URL_SCHEME;
 use collections::VecDeque;
-use editor::{scroll::Autoscroll, Editor, MultiBuffer};
+use editor::{scroll::Autoscroll, Editor, LargeFileBanner, MultiBuffer};
+use git_panel::GitPanel;
 use gpui::{
     actions, point, px, AppContext, AsyncAppContext, Context, FocusableView, MenuItem, PromptLevel,
     ReadGlobal, TitlebarOptions, View, ViewContext, VisualContext, WindowKind, WindowOptions,
@@ -27,13 +34,21 @@ use project::TaskSourceKind;
 use project_panel::ProjectPanel;
 use quick_action_bar::QuickActionBar;
 use release_channel::{AppCommitSha, ReleaseChannel};
+use remote::SshConnectionState;
+use remote_settings::RemoteSettings;
 use rope::Rope;
 use search::project_search::ProjectSearchBar;
 use settings::{
     initial_local_settings_content, initial_tasks_content, watch_config_file, KeymapFile, Settings,
     SettingsStore, DEFAULT_KEYMAP_PATH,
 };
-use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
+use std::{
+    borrow::Cow,
+    ops::Deref,
+    path::Path,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 use task::static_source::{StaticSource, TrackedFile};
 use theme::ActiveTheme;
 use workspace::notifications::NotificationId;
@@ -71,7 +86,18 @@ actions!(
     ]
 );
 
+/// How long to wait after the connection of an SSH project is lost before the first
+/// attempt to reconnect automatically. The delay doubles after each failed attempt, up
+/// to [`MAX_AUTO_RECONNECT_DELAY`].
+const AUTO_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_AUTO_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+/// How many times to try reconnecting automatically before giving up. The error of the
+/// last attempt is shown to the user.
+const MAX_AUTO_RECONNECT_ATTEMPTS: usize = 10;
+
 pub fn init(cx: &mut AppContext) {
+    remote_settings::RemoteSettings::register(cx);
+
     #[cfg(target_os = "macos")]
     cx.on_action(|_: &Hide, cx| cx.hide());
     #[cfg(target_os = "macos")]
@@ -196,7 +222,12 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
         let vim_mode_indicator = cx.new_view(|cx| vim::ModeIndicator::new(cx));
         let cursor_position =
             cx.new_view(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
+        let ssh_status_indicator =
+            cx.new_view(|cx| ssh_status_indicator::SshStatusIndicator::new(workspace, cx));
+        let active_branch = cx.new_view(|cx| vcs_menu::ActiveBranch::new(workspace, cx));
         workspace.status_bar().update(cx, |status_bar, cx| {
+            status_bar.add_left_item(ssh_status_indicator, cx);
+            status_bar.add_left_item(active_branch, cx);
             status_bar.add_left_item(diagnostic_summary, cx);
             status_bar.add_left_item(activity_indicator, cx);
             status_bar.add_right_item(inline_completion_button, cx);
@@ -206,6 +237,8 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
         });
 
         auto_update::notify_of_any_new_update(cx);
+        remote_settings::observe_host_settings(workspace, cx);
+        reconnect_when_connection_lost(workspace, &app_state, cx);
 
         let handle = cx.view().downgrade();
         cx.on_window_should_close(move |cx| {
@@ -245,6 +278,8 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
 
             let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
             let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+            let bookmarks_panel = BookmarksPanel::load(workspace_handle.clone(), cx.clone());
+            let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
             let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
             let channels_panel =
                 collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
@@ -258,6 +293,8 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             let (
                 project_panel,
                 outline_panel,
+                bookmarks_panel,
+                git_panel,
                 terminal_panel,
                 assistant_panel,
                 channels_panel,
@@ -266,6 +303,8 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             ) = futures::try_join!(
                 project_panel,
                 outline_panel,
+                bookmarks_panel,
+                git_panel,
                 terminal_panel,
                 assistant_panel,
                 channels_panel,
@@ -277,6 +316,8 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
                 workspace.add_panel(assistant_panel, cx);
                 workspace.add_panel(project_panel, cx);
                 workspace.add_panel(outline_panel, cx);
+                workspace.add_panel(bookmarks_panel, cx);
+                workspace.add_panel(git_panel, cx);
                 workspace.add_panel(terminal_panel, cx);
                 workspace.add_panel(channels_panel, cx);
                 workspace.add_panel(chat_panel, cx);
@@ -525,6 +566,45 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
                         .detach();
                     }
                 }
+            })
+            .register_action(|workspace, _: &remote_target_picker::ConnectToContainer, cx| {
+                remote_target_picker::RemoteTargetPicker::toggle_containers(workspace, cx);
+            })
+            .register_action(|workspace, _: &recent_projects::OpenWsl, cx| {
+                remote_target_picker::RemoteTargetPicker::toggle_wsl_distros(workspace, cx);
+            })
+            .register_action(|workspace, _: &remote_info_modal::ShowRemoteInfo, cx| {
+                remote_info_modal::RemoteInfoModal::toggle(workspace, cx);
+            })
+            .register_action(|workspace, _: &remote_server_log::OpenServerLog, cx| {
+                remote_server_log::RemoteServerLogView::open(workspace, cx);
+            })
+            .register_action(|workspace, _: &remote_server_log::OpenConnectionLog, cx| {
+                remote_server_log::RemoteServerLogView::open_connection_log(workspace, cx);
+            })
+            .register_action(|workspace, _: &ssh_status_indicator::Disconnect, cx| {
+                if let Some(session) = workspace.project().read(cx).ssh_session() {
+                    session.disconnect();
+                }
+            })
+            .register_action({
+                let app_state = Arc::downgrade(&app_state);
+                move |_, _: &ssh_status_indicator::Reconnect, cx| {
+                    reconnect_ssh_window(&app_state, cx)
+                }
+            })
+            .register_action(|workspace, _: &ssh_status_indicator::TrustHost, cx| {
+                let project = workspace.project().clone();
+                cx.spawn(|_, mut cx| async move { trust_ssh_host(project, &mut cx).await })
+                    .detach_and_log_err(cx);
+            })
+            .register_action({
+                // Reconnecting installs the remote server matching this version of Zed,
+                // restarts it, and reopens the same folders in this window.
+                let app_state = Arc::downgrade(&app_state);
+                move |_, _: &ssh_status_indicator::UpgradeRemoteServer, cx| {
+                    reconnect_ssh_window(&app_state, cx)
+                }
             });
 
         workspace.focus_handle(cx).focus(cx);
@@ -532,6 +612,102 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
     .detach();
 }
 
+/// Reconnects the SSH project of the window to its host, reopening the same folders.
+fn reconnect_ssh_window(app_state: &Weak<AppState>, cx: &mut ViewContext<Workspace>) {
+    let Some(app_state) = app_state.upgrade() else {
+        return;
+    };
+    let Some(window) = cx.window_handle().downcast::<Workspace>() else {
+        return;
+    };
+    cx.spawn(|_, mut cx| async move {
+        reconnect_ssh_workspace(window, app_state, false, &mut cx).await
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Reconnects the SSH project of the window after its connection is lost, if its host
+/// has `remote.ssh.auto_reconnect` enabled, retrying with an exponential backoff while
+/// the host can't be reached.
+///
+/// This restarts the remote server and reopens the project: requests that were in
+/// flight when the connection was lost fail rather than being resent.
+fn reconnect_when_connection_lost(
+    workspace: &Workspace,
+    app_state: &Arc<AppState>,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let Some(session) = workspace.project().read(cx).ssh_session() else {
+        return;
+    };
+    let Some(connection_options) = session.connection_options().cloned() else {
+        return;
+    };
+    let Some(window) = cx.window_handle().downcast::<Workspace>() else {
+        return;
+    };
+    let mut state_changes = session.connection_state_changes();
+    let session = Arc::downgrade(&session);
+    let app_state = Arc::downgrade(app_state);
+    cx.spawn(|_, mut cx| async move {
+        loop {
+            match state_changes.next().await {
+                Some(SshConnectionState::Disconnected) => break,
+                Some(_) => continue,
+                None => return Ok(()),
+            }
+        }
+        let connection_lost = session
+            .upgrade()
+            .map_or(false, |session| !session.disconnect_requested());
+        let auto_reconnect = cx.update(|cx| {
+            RemoteSettings::get_global(cx)
+                .ssh
+                .auto_reconnect_for(&connection_options)
+        })?;
+        if !connection_lost || !auto_reconnect {
+            return Ok(());
+        }
+
+        let mut delay = AUTO_RECONNECT_DELAY;
+        for attempt in 1..=MAX_AUTO_RECONNECT_ATTEMPTS {
+            // Give the network a moment to come back before trying again.
+            cx.background_executor().timer(delay).await;
+            delay = (delay * 2).min(MAX_AUTO_RECONNECT_DELAY);
+
+            // Stop when the window was closed, or its project was reconnected otherwise.
+            let still_lost = window
+                .update(&mut cx, |workspace, cx| {
+                    let current_session = workspace.project().read(cx).ssh_session();
+                    current_session.map_or(false, |current_session| {
+                        Weak::ptr_eq(&Arc::downgrade(&current_session), &session)
+                    })
+                })
+                .unwrap_or(false);
+            let Some(app_state) = app_state.upgrade() else {
+                return Ok(());
+            };
+            if !still_lost {
+                return Ok(());
+            }
+
+            let retrying = attempt < MAX_AUTO_RECONNECT_ATTEMPTS;
+            match reconnect_ssh_workspace(window, app_state, retrying, &mut cx).await {
+                Ok(()) => return Ok(()),
+                Err(error) if error.downcast_ref::<ConnectionCancelledError>().is_some() => {
+                    return Ok(());
+                }
+                Err(error) => log::info!(
+                    "failed to reconnect to {} (attempt {attempt} of {MAX_AUTO_RECONNECT_ATTEMPTS}): {error:#}",
+                    connection_options.host
+                ),
+            }
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
 fn initialize_pane(workspace: &mut Workspace, pane: &View<Pane>, cx: &mut ViewContext<Workspace>) {
     pane.update(cx, |pane, cx| {
         pane.toolbar().update(cx, |toolbar, cx| {
@@ -539,6 +715,8 @@ fn initialize_pane(workspace: &mut Workspace, pane: &View<Pane>, cx: &mut ViewCo
             toolbar.add_item(multibuffer_hint, cx);
             let breadcrumbs = cx.new_view(|_| Breadcrumbs::new());
             toolbar.add_item(breadcrumbs, cx);
+            let large_file_banner = cx.new_view(|_| LargeFileBanner::new());
+            toolbar.add_item(large_file_banner, cx);
             let buffer_search_bar = cx.new_view(search::BufferSearchBar::new);
             toolbar.add_item(buffer_search_bar.clone(), cx);
 
@@ -3457,6 +3635,8 @@ mod tests {
             collab_ui::init(&app_state, cx);
             project_panel::init((), cx);
             outline_panel::init((), cx);
+            bookmarks_panel::init(cx);
+            git_panel::init(cx);
             terminal_view::init(cx);
             language_model::init(app_state.client.clone(), cx);
             assistant::init(app_state.fs.clone(), app_state.client.clone(), cx);
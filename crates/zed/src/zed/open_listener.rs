@@ -1,6 +1,7 @@
 use crate::restorable_workspace_locations;
 use crate::{
-    handle_open_request, init_headless, init_ui, zed::ssh_connection_modal::SshConnectionModal,
+    handle_open_request, init_headless, init_ui,
+    zed::{remote_settings::RemoteSettings, ssh_connection_modal::SshConnectionModal},
 };
 use anyhow::{anyhow, Context, Result};
 use auto_update::AutoUpdater;
@@ -15,11 +16,19 @@ use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures::channel::{mpsc, oneshot};
 use futures::{FutureExt, SinkExt, StreamExt};
 use gpui::{
-    AppContext, AsyncAppContext, Global, SemanticVersion, View, VisualContext as _, WindowHandle,
+    AppContext, AsyncAppContext, DismissEvent, Global, Model, PromptLevel, SemanticVersion, View,
+    VisualContext as _, WindowHandle,
 };
 use language::{Bias, Point};
+use parking_lot::Mutex;
+use project::Project;
 use release_channel::{AppVersion, ReleaseChannel};
-use remote::SshPlatform;
+use remote::{
+    DockerConnectionOptions, SshAuthPrompt, SshAuthenticationError, SshConnectTimeoutError,
+    SshConnectionOptions, SshIdentity, SshPlatform, SshPromptKind, SshProxy, WslConnection,
+    WslConnectionOptions,
+};
+use settings::Settings;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -37,15 +46,8 @@ pub struct OpenRequest {
     pub open_paths: Vec<PathLikeWithPosition<PathBuf>>,
     pub open_channel_notes: Vec<(u64, Option<String>)>,
     pub join_channel: Option<u64>,
-    pub ssh_connection: Option<SshConnectionInfo>,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct SshConnectionInfo {
-    pub username: String,
-    pub password: Option<String>,
-    pub host: String,
-    pub port: u16,
+    pub ssh_connection: Option<SshConnectionOptions>,
+    pub open_new_workspace: Option<bool>,
 }
 
 impl OpenRequest {
@@ -95,11 +97,17 @@ impl OpenRequest {
         if !self.open_paths.is_empty() {
             return Err(anyhow!("cannot open both local and ssh paths"));
         }
-        let connection = SshConnectionInfo {
+        let connection = SshConnectionOptions {
             username,
             password,
             host,
             port,
+            proxy: None,
+            identity: None,
+            timeouts: Default::default(),
+            additional_args: Vec::new(),
+            forward_agent: false,
+            transport: Default::default(),
         };
         if let Some(ssh_connection) = &self.ssh_connection {
             if *ssh_connection != connection {
@@ -162,28 +170,62 @@ impl OpenListener {
 struct SshClientDelegate {
     window: WindowHandle<Workspace>,
     modal: View<SshConnectionModal>,
+    credentials_url: String,
+    username: String,
+    credentials: Arc<Mutex<SshCredentials>>,
+}
+
+#[derive(Default)]
+struct SshCredentials {
+    /// The password given with the connection options, answered to the first password
+    /// prompt only.
     known_password: Option<String>,
+    used_stored_password: bool,
+    entered_password: Option<String>,
 }
 
 impl remote::SshClientDelegate for SshClientDelegate {
     fn ask_password(
         &self,
-        prompt: String,
+        prompt: SshAuthPrompt,
         cx: &mut AsyncAppContext,
     ) -> oneshot::Receiver<Result<String>> {
         let (tx, rx) = oneshot::channel();
-        let mut known_password = self.known_password.clone();
-        if let Some(password) = known_password.take() {
-            tx.send(Ok(password)).ok();
-        } else {
-            self.window
-                .update(cx, |_, cx| {
-                    self.modal.update(cx, |modal, cx| {
-                        modal.set_prompt(prompt, tx, cx);
+        if is_secret_prompt(&prompt) {
+            if let Some(password) = self.credentials.lock().known_password.take() {
+                tx.send(Ok(password)).ok();
+                return rx;
+            }
+        }
+
+        let this = self.clone();
+        cx.spawn(|mut cx| async move {
+            let is_secret = is_secret_prompt(&prompt);
+            if is_secret {
+                if let Some(password) = this.stored_password(&mut cx).await {
+                    tx.send(Ok(password)).ok();
+                    return;
+                }
+            }
+
+            let (answer_tx, answer_rx) = oneshot::channel();
+            this.window
+                .update(&mut cx, |_, cx| {
+                    this.modal.update(cx, |modal, cx| {
+                        modal.set_prompt(prompt, answer_tx, cx);
                     });
                 })
                 .ok();
-        }
+
+            let answer = answer_rx
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("ssh prompt was dismissed")));
+            if let (true, Ok(password)) = (is_secret, &answer) {
+                this.credentials.lock().entered_password = Some(password.clone());
+            }
+            tx.send(answer).ok();
+        })
+        .detach();
         rx
     }
 
@@ -191,6 +233,25 @@ impl remote::SshClientDelegate for SshClientDelegate {
         self.update_status(status, cx)
     }
 
+    fn set_progress(&self, progress: Option<f32>, cx: &mut AsyncAppContext) {
+        self.update_progress(progress, cx)
+    }
+
+    fn set_security_key_prompt(&self, message: Option<String>, cx: &mut AsyncAppContext) {
+        self.window
+            .update(cx, |_, cx| {
+                self.modal.update(cx, |modal, cx| {
+                    modal.set_security_key_prompt(message, cx);
+                });
+            })
+            .ok();
+    }
+
+    fn compress_server_upload(&self, cx: &mut AsyncAppContext) -> bool {
+        cx.update(|cx| RemoteSettings::get_global(cx).compress_server_upload)
+            .unwrap_or(true)
+    }
+
     fn get_server_binary(
         &self,
         platform: SshPlatform,
@@ -206,13 +267,91 @@ impl remote::SshClientDelegate for SshClientDelegate {
         rx
     }
 
-    fn remote_server_binary_path(&self, cx: &mut AsyncAppContext) -> Result<PathBuf> {
+    fn remote_server_binary_path(
+        &self,
+        version: SemanticVersion,
+        cx: &mut AsyncAppContext,
+    ) -> Result<PathBuf> {
         let release_channel = cx.update(|cx| ReleaseChannel::global(cx))?;
-        Ok(format!(".local/zed-remote-server-{}", release_channel.dev_name()).into())
+        Ok(format!(
+            ".local/zed-remote-server-{}-{version}",
+            release_channel.dev_name()
+        )
+        .into())
     }
 }
 
 impl SshClientDelegate {
+    /// Returns the password saved in the keychain for this user@host:port, at most once per
+    /// connection attempt so that a stale password falls back to prompting the user.
+    async fn stored_password(&self, cx: &mut AsyncAppContext) -> Option<String> {
+        let store_credentials = cx
+            .update(|cx| RemoteSettings::get_global(cx).ssh.store_credentials)
+            .ok()?;
+        if !store_credentials {
+            return None;
+        }
+        {
+            let mut credentials = self.credentials.lock();
+            if credentials.used_stored_password {
+                return None;
+            }
+            credentials.used_stored_password = true;
+        }
+
+        let (_, password) = cx
+            .update(|cx| cx.read_credentials(&self.credentials_url))
+            .ok()?
+            .await
+            .log_err()??;
+        String::from_utf8(password).log_err()
+    }
+
+    /// Persists the secret that was accepted by the remote host, or forgets a stored one
+    /// that the host rejected. Failures to reach the host keep the stored secret.
+    async fn update_stored_password(
+        &self,
+        result: Result<(), &anyhow::Error>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<()> {
+        let store_credentials =
+            cx.update(|cx| RemoteSettings::get_global(cx).ssh.store_credentials)?;
+        if !store_credentials {
+            return Ok(());
+        }
+
+        let (used_stored_password, entered_password) = {
+            let mut credentials = self.credentials.lock();
+            (
+                credentials.used_stored_password,
+                credentials.entered_password.take(),
+            )
+        };
+
+        match result {
+            Ok(()) => {
+                if let Some(password) = entered_password {
+                    cx.update(|cx| {
+                        cx.write_credentials(
+                            &self.credentials_url,
+                            &self.username,
+                            password.as_bytes(),
+                        )
+                    })?
+                    .await?;
+                }
+            }
+            Err(error) => {
+                let rejected = error.is::<SshAuthenticationError>();
+                if rejected && used_stored_password && entered_password.is_none() {
+                    cx.update(|cx| cx.delete_credentials(&self.credentials_url))?
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn update_status(&self, status: Option<&str>, cx: &mut AsyncAppContext) {
         self.window
             .update(cx, |_, cx| {
@@ -223,13 +362,40 @@ impl SshClientDelegate {
             .ok();
     }
 
+    fn update_progress(&self, progress: Option<f32>, cx: &mut AsyncAppContext) {
+        self.window
+            .update(cx, |_, cx| {
+                self.modal.update(cx, |modal, cx| {
+                    modal.set_progress(progress, cx);
+                });
+            })
+            .ok();
+    }
+
     async fn get_server_binary_impl(
         &self,
         platform: SshPlatform,
         cx: &mut AsyncAppContext,
     ) -> Result<(PathBuf, SemanticVersion)> {
-        let (version, release_channel) =
-            cx.update(|cx| (AppVersion::global(cx), ReleaseChannel::global(cx)))?;
+        let (version, release_channel, server_binary_path) = cx.update(|cx| {
+            (
+                AppVersion::global(cx),
+                ReleaseChannel::global(cx),
+                RemoteSettings::get_global(cx).server_binary_path.clone(),
+            )
+        })?;
+
+        if let Some(path) = server_binary_path {
+            let path = PathBuf::from(shellexpand::tilde(&path.to_string_lossy()).as_ref());
+            smol::fs::metadata(&path).await.with_context(|| {
+                format!(
+                    "the remote server binary {} set in remote.server_binary_path could not be read",
+                    path.display()
+                )
+            })?;
+            log::info!("using remote server binary {}", path.display());
+            return Ok((path, version));
+        }
 
         // In dev mode, build the remote server binary from source
         #[cfg(debug_assertions)]
@@ -244,9 +410,9 @@ impl SshClientDelegate {
             log::info!("building remote server binary from source");
             run_cmd(Command::new("cargo").args(["build", "--package", "remote_server"])).await?;
             run_cmd(Command::new("strip").args(["target/debug/remote_server"])).await?;
-            run_cmd(Command::new("gzip").args(["-9", "-f", "target/debug/remote_server"])).await?;
 
-            let path = std::env::current_dir()?.join("target/debug/remote_server.gz");
+            // The binary is compressed, if needed, when it's uploaded.
+            let path = std::env::current_dir()?.join("target/debug/remote_server");
             return Ok((path, version));
 
             async fn run_cmd(command: &mut Command) -> Result<()> {
@@ -263,14 +429,33 @@ impl SshClientDelegate {
             platform.os,
             platform.arch,
             release_channel,
+            &|progress: f32, cx: &mut AsyncAppContext| {
+                self.update_status(Some("downloading remote server"), cx);
+                self.update_progress(Some(progress), cx);
+            },
             cx,
         )
-        .await?;
+        .await
+        .with_context(|| {
+            format!(
+                "failed to download the remote server for {}-{}. Without internet access, \
+                download it manually and set remote.server_binary_path to its location",
+                platform.os, platform.arch
+            )
+        })?;
 
         Ok((binary_path, version))
     }
 }
 
+/// Whether an askpass prompt is asking for a reusable secret, as opposed to e.g. confirming
+/// a host key or entering a one-time verification code.
+fn is_secret_prompt(prompt: &SshAuthPrompt) -> bool {
+    let label = prompt.label.to_lowercase();
+    prompt.kind == SshPromptKind::Secret
+        && (label.contains("password") || label.contains("passphrase"))
+}
+
 #[cfg(target_os = "linux")]
 pub fn listen_for_cli_connections(opener: OpenListener) -> Result<()> {
     use release_channel::RELEASE_CHANNEL_NAME;
@@ -323,14 +508,163 @@ fn connect_to_cli(
 }
 
 pub async fn open_ssh_paths(
-    connection_info: SshConnectionInfo,
-    paths: Vec<PathLikeWithPosition<PathBuf>>,
+    connection_options: SshConnectionOptions,
+    path_likes: Vec<PathLikeWithPosition<PathBuf>>,
+    app_state: Arc<AppState>,
+    open_options: workspace::OpenOptions,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let paths: Vec<PathBuf> = path_likes
+        .iter()
+        .map(|path| path.path_like.clone())
+        .collect();
+
+    if open_options.open_new_workspace != Some(true) {
+        if let Some(window) = ssh_workspace_for_host(&connection_options, cx) {
+            let add_paths = open_options.open_new_workspace == Some(false);
+            let project = window.update(cx, |workspace, cx| {
+                cx.activate_window();
+                workspace.project().clone()
+            })?;
+            let all_paths_open = project.update(cx, |project, cx| {
+                paths.iter().all(|path| {
+                    project
+                        .visible_worktrees(cx)
+                        .any(|worktree| path.starts_with(worktree.read(cx).abs_path()))
+                })
+            })?;
+            if add_paths || all_paths_open {
+                for path in paths {
+                    project
+                        .update(cx, |project, cx| {
+                            project.find_or_create_worktree(&path, true, cx)
+                        })?
+                        .await?;
+                }
+                return open_ssh_items(window, &path_likes, cx).await;
+            }
+        }
+    }
+
+    let window = if let Some(window) = open_options.replace_window {
+        window
+    } else {
+        open_empty_window(&app_state, cx)?
+    };
+
+    let result =
+        connect_ssh_workspace(window, connection_options, paths, app_state, false, cx).await;
+    if result.is_err() && open_options.replace_window.is_none() {
+        window.update(cx, |_, cx| cx.remove_window()).ok();
+    }
+    result?;
+    open_ssh_items(window, &path_likes, cx).await
+}
+
+/// Opens the files among `path_likes` in the workspace of an SSH project whose
+/// worktrees have been added, moving the cursor to the position they were given with.
+async fn open_ssh_items(
+    window: WindowHandle<Workspace>,
+    path_likes: &[PathLikeWithPosition<PathBuf>],
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    for path_like in path_likes {
+        let open_task = window.update(cx, |workspace, cx| {
+            let (worktree, relative_path) = workspace
+                .project()
+                .read(cx)
+                .find_worktree(&path_like.path_like, cx)?;
+            let worktree = worktree.read(cx);
+            // The worktree's entries may not have arrived from the host yet, so a path
+            // given with a row is assumed to be a file. Folders are only added.
+            let is_file = path_like.row.is_some()
+                || worktree
+                    .entry_for_path(&relative_path)
+                    .map_or(false, |entry| entry.is_file());
+            let project_path = (worktree.id(), relative_path);
+            is_file.then(|| workspace.open_path(project_path, None, true, cx))
+        })?;
+        let Some(open_task) = open_task else {
+            continue;
+        };
+        let item = open_task.await?;
+
+        let Some(row) = path_like.row else {
+            continue;
+        };
+        let Some(editor) = item.downcast::<Editor>() else {
+            continue;
+        };
+        let column = path_like.column.unwrap_or(0).saturating_sub(1);
+        let point = Point::new(row.saturating_sub(1), column);
+        window.update(cx, |_, cx| {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.snapshot(cx).display_snapshot;
+                let point = snapshot.buffer_snapshot.clip_point(point, Bias::Left);
+                editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                    s.select_ranges([point..point])
+                });
+            })
+        })?;
+    }
+    Ok(())
+}
+
+/// An environment on this machine that a remote project can be opened in, without
+/// going through ssh.
+pub enum RemoteEnvironment {
+    Container(DockerConnectionOptions),
+    Wsl(WslConnectionOptions),
+}
+
+impl RemoteEnvironment {
+    fn name(&self) -> &str {
+        match self {
+            RemoteEnvironment::Container(options) => &options.container,
+            RemoteEnvironment::Wsl(options) => &options.distro,
+        }
+    }
+
+    fn credentials_url(&self) -> String {
+        match self {
+            RemoteEnvironment::Container(options) => format!("docker://{}", options.container),
+            RemoteEnvironment::Wsl(options) => format!("wsl://{}", options.distro),
+        }
+    }
+
+    fn user(&self) -> Option<&str> {
+        match self {
+            RemoteEnvironment::Container(options) => options.user.as_deref(),
+            RemoteEnvironment::Wsl(options) => options.user.as_deref(),
+        }
+    }
+}
+
+/// Opens the given paths of a container or WSL distribution in a new window. When no
+/// paths are given, the user's home directory in the WSL distribution is opened.
+pub async fn open_remote_environment_paths(
+    environment: RemoteEnvironment,
+    paths: Vec<PathBuf>,
     app_state: Arc<AppState>,
-    _open_options: workspace::OpenOptions,
     cx: &mut AsyncAppContext,
 ) -> Result<()> {
+    let window = open_empty_window(&app_state, cx)?;
+    let result =
+        connect_remote_environment_workspace(window, environment, paths, app_state, cx).await;
+    if result.is_err() {
+        window.update(cx, |_, cx| cx.remove_window()).ok();
+    }
+    result
+}
+
+/// Opens a window with an empty local workspace, in which a remote project can be
+/// connected.
+fn open_empty_window(
+    app_state: &Arc<AppState>,
+    cx: &mut AsyncAppContext,
+) -> Result<WindowHandle<Workspace>> {
     let options = cx.update(|cx| (app_state.build_window_options)(None, cx))?;
-    let window = cx.open_window(options, |cx| {
+    cx.open_window(options, |cx| {
         let project = project::Project::local(
             app_state.client.clone(),
             app_state.node_runtime.clone(),
@@ -340,35 +674,301 @@ pub async fn open_ssh_paths(
             cx,
         );
         cx.new_view(|cx| Workspace::new(None, project, app_state.clone(), cx))
+    })
+}
+
+/// Finds a window whose workspace is connected to the same host as the given options.
+fn ssh_workspace_for_host(
+    connection_options: &SshConnectionOptions,
+    cx: &mut AsyncAppContext,
+) -> Option<WindowHandle<Workspace>> {
+    cx.update(|cx| {
+        cx.windows().into_iter().find_map(|window| {
+            let window = window.downcast::<Workspace>()?;
+            let session = window.read(cx).ok()?.project().read(cx).ssh_session()?;
+            let options = session.connection_options()?;
+            (options.username == connection_options.username
+                && options.host == connection_options.host
+                && options.port == connection_options.port)
+                .then_some(window)
+        })
+    })
+    .ok()
+    .flatten()
+}
+
+/// The error of connection attempts that the user cancelled.
+#[derive(Clone, Debug)]
+pub struct ConnectionCancelledError {
+    pub host: String,
+}
+
+impl std::fmt::Display for ConnectionCancelledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "connection to {} was cancelled", self.host)
+    }
+}
+
+impl std::error::Error for ConnectionCancelledError {}
+
+/// Re-establishes the SSH connection of a remote workspace in its existing window,
+/// reopening the folders that were open before.
+///
+/// When `retrying`, connection errors close the connection modal instead of staying
+/// on screen until the user dismisses them, so that the caller can try again.
+pub async fn reconnect_ssh_workspace(
+    window: WindowHandle<Workspace>,
+    app_state: Arc<AppState>,
+    retrying: bool,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let (session, paths) = window.update(cx, |workspace, cx| {
+        let project = workspace.project().read(cx);
+        let paths = project
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect::<Vec<_>>();
+        (project.ssh_session(), paths)
     })?;
+    let session = session.context("workspace is not connected over ssh")?;
+    let connection_options = session
+        .connection_options()
+        .cloned()
+        .context("missing ssh connection options")?;
+    session.disconnect();
+
+    connect_ssh_workspace(window, connection_options, paths, app_state, retrying, cx).await
+}
 
+async fn connect_ssh_workspace(
+    window: WindowHandle<Workspace>,
+    mut connection_options: SshConnectionOptions,
+    paths: Vec<PathBuf>,
+    app_state: Arc<AppState>,
+    retrying: bool,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    if connection_options.proxy.is_none() {
+        let proxy = cx.update(|cx| RemoteSettings::get_global(cx).ssh.proxy.clone())?;
+        connection_options.proxy = proxy
+            .as_deref()
+            .map(SshProxy::from_url)
+            .transpose()
+            .context("invalid remote.ssh.proxy setting")?;
+    }
+    connection_options.timeouts = cx.update(|cx| RemoteSettings::get_global(cx).ssh.timeouts())?;
+    connection_options.additional_args = cx.update(|cx| {
+        RemoteSettings::get_global(cx)
+            .ssh
+            .additional_args_for(&connection_options)
+    })?;
+    connection_options.forward_agent = cx.update(|cx| {
+        RemoteSettings::get_global(cx)
+            .ssh
+            .forward_agent_for(&connection_options)
+    })?;
+    connection_options.transport = cx.update(|cx| {
+        RemoteSettings::get_global(cx)
+            .ssh
+            .transport_for(&connection_options)
+    })?;
+
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
     let modal = window.update(cx, |workspace, cx| {
         cx.activate_window();
         workspace.toggle_modal(cx, |cx| {
-            SshConnectionModal::new(connection_info.host.clone(), cx)
+            let mut modal = SshConnectionModal::new(connection_options.host.clone(), cancel_tx, cx);
+            modal.set_forward_agent(connection_options.forward_agent);
+            modal
         });
         workspace.active_modal::<SshConnectionModal>(cx).unwrap()
     })?;
 
-    let session = remote::SshSession::client(
-        connection_info.username,
-        connection_info.host,
-        connection_info.port,
-        Arc::new(SshClientDelegate {
-            window,
-            modal,
-            known_password: connection_info.password,
-        }),
-        cx,
-    )
-    .await;
+    let identity_key = format!("ssh-identity-{}", connection_options.connection_string());
+    let mut chosen_identity = false;
+    if connection_options.identity.is_none() {
+        let stored_identity = KEY_VALUE_STORE.read_kvp(&identity_key).log_err().flatten();
+        if let Some(identity) = stored_identity {
+            connection_options.identity = serde_json::from_str(&identity).log_err().flatten();
+        } else {
+            let identities = SshIdentity::list_available().await;
+            let key_count = identities
+                .iter()
+                .filter(|identity| **identity != SshIdentity::Password)
+                .count();
+            if key_count > 1 {
+                let (identity_tx, identity_rx) = oneshot::channel();
+                window.update(cx, |_, cx| {
+                    modal.update(cx, |modal, cx| {
+                        modal.choose_identity(identities, identity_tx, cx)
+                    })
+                })?;
+                let identity = futures::select_biased! {
+                    _ = (&mut cancel_rx).fuse() => None,
+                    identity = identity_rx.fuse() => identity.ok(),
+                };
+                let Some(identity) = identity else {
+                    return Err(ConnectionCancelledError {
+                        host: connection_options.host,
+                    }
+                    .into());
+                };
+                connection_options.identity = identity;
+                chosen_identity = true;
+            }
+        }
+    }
+    let identity = connection_options.identity.clone();
+
+    let delegate = Arc::new(SshClientDelegate {
+        window,
+        modal: modal.clone(),
+        credentials_url: format!(
+            "ssh://{}@{}:{}",
+            connection_options.username, connection_options.host, connection_options.port
+        ),
+        username: connection_options.username.clone(),
+        credentials: Arc::new(Mutex::new(SshCredentials {
+            known_password: connection_options.password.clone(),
+            ..Default::default()
+        })),
+    });
 
-    if session.is_err() {
-        window.update(cx, |_, cx| cx.remove_window()).ok();
+    let host = connection_options.host.clone();
+    let session = futures::select_biased! {
+        _ = (&mut cancel_rx).fuse() => None,
+        session = remote::SshSession::client(connection_options, delegate.clone(), cx).fuse() => {
+            Some(session)
+        }
+    };
+    let Some(session) = session else {
+        return Err(ConnectionCancelledError { host }.into());
+    };
+
+    delegate
+        .update_stored_password(session.as_ref().map(|_| ()), cx)
+        .await
+        .log_err();
+
+    // Remember the identity the user picked once it has been accepted, and forget
+    // it if it no longer works so that the user gets asked again next time.
+    if session.is_ok() && chosen_identity {
+        if let Some(identity) = serde_json::to_string(&identity).log_err() {
+            KEY_VALUE_STORE
+                .write_kvp(identity_key, identity)
+                .await
+                .log_err();
+        }
+    } else if session.is_err() && identity.is_some() && !chosen_identity {
+        KEY_VALUE_STORE.delete_kvp(identity_key).await.log_err();
     }
 
-    let session = session?;
+    let session = match session {
+        Ok(session) => session,
+        Err(error) => {
+            let message = match error.downcast_ref::<SshConnectTimeoutError>() {
+                Some(timeout_error) => format!(
+                    "{timeout_error}. Check that the host is reachable, or increase the \
+                    remote.ssh.connect_timeout setting."
+                ),
+                None => format!("{error:#}"),
+            };
+            if retrying {
+                window
+                    .update(cx, |_, cx| modal.update(cx, |_, cx| cx.emit(DismissEvent)))
+                    .ok();
+            } else {
+                show_connection_error(window, &modal, message, cancel_rx, cx).await;
+            }
+            return Err(error);
+        }
+    };
 
+    open_remote_project(window, session, paths, app_state, cx).await
+}
+
+/// Installs the remote server in a container or WSL distribution, and replaces the
+/// window's workspace with a project of the given paths.
+async fn connect_remote_environment_workspace(
+    window: WindowHandle<Workspace>,
+    environment: RemoteEnvironment,
+    mut paths: Vec<PathBuf>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let name = environment.name().to_string();
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+    let modal = window.update(cx, |workspace, cx| {
+        cx.activate_window();
+        workspace.toggle_modal(cx, |cx| {
+            SshConnectionModal::new(name.clone(), cancel_tx, cx)
+        });
+        workspace.active_modal::<SshConnectionModal>(cx).unwrap()
+    })?;
+
+    let delegate = Arc::new(SshClientDelegate {
+        window,
+        modal: modal.clone(),
+        credentials_url: environment.credentials_url(),
+        username: environment.user().unwrap_or_default().to_string(),
+        credentials: Default::default(),
+    });
+
+    let connect = async {
+        match environment {
+            RemoteEnvironment::Container(options) => {
+                remote::SshSession::docker(options, delegate, cx).await
+            }
+            RemoteEnvironment::Wsl(options) => {
+                if paths.is_empty() {
+                    paths.push(WslConnection::home_dir(&options).await?);
+                }
+                remote::SshSession::wsl(options, delegate, cx).await
+            }
+        }
+    };
+    let session = futures::select_biased! {
+        _ = (&mut cancel_rx).fuse() => None,
+        session = connect.fuse() => Some(session),
+    };
+    let session = match session {
+        Some(Ok(session)) => session,
+        Some(Err(error)) => {
+            show_connection_error(window, &modal, format!("{error:#}"), cancel_rx, cx).await;
+            return Err(error);
+        }
+        None => return Err(ConnectionCancelledError { host: name }.into()),
+    };
+
+    open_remote_project(window, session, paths, app_state, cx).await
+}
+
+/// Shows a connection error in the modal, and keeps it on screen until the user
+/// dismisses it.
+async fn show_connection_error(
+    window: WindowHandle<Workspace>,
+    modal: &View<SshConnectionModal>,
+    message: String,
+    cancel_rx: oneshot::Receiver<()>,
+    cx: &mut AsyncAppContext,
+) {
+    window
+        .update(cx, |_, cx| {
+            modal.update(cx, |modal, cx| modal.set_status(Some(message), cx))
+        })
+        .ok();
+    cancel_rx.await.ok();
+}
+
+/// Replaces the window's workspace with a project of `paths` on the other end of the
+/// given session.
+async fn open_remote_project(
+    window: WindowHandle<Workspace>,
+    session: Arc<remote::SshSession>,
+    paths: Vec<PathBuf>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
     let project = cx.update(|cx| {
         project::Project::ssh(
             session,
@@ -384,19 +984,103 @@ pub async fn open_ssh_paths(
     for path in paths {
         project
             .update(cx, |project, cx| {
-                project.find_or_create_worktree(&path.path_like, true, cx)
+                project.find_or_create_worktree(&path, true, cx)
             })?
             .await?;
     }
 
+    // Only projects opened over ssh can be reconnected to when restoring a session,
+    // so the workspaces of containers and WSL distributions aren't serialized.
+    let workspace_id = match cx.update(|cx| workspace::ssh_project_for_project(&project, cx))? {
+        Some(ssh_project) => workspace::workspace_id_for_ssh_project(&ssh_project)
+            .await
+            .log_err(),
+        None => None,
+    };
+
     window.update(cx, |_, cx| {
-        cx.replace_root_view(|cx| Workspace::new(None, project, app_state, cx))
+        cx.replace_root_view(|cx| Workspace::new(workspace_id, project.clone(), app_state, cx))
     })?;
     window.update(cx, |_, cx| cx.activate_window())?;
 
+    confirm_ssh_host_trust(window, &project, cx).await
+}
+
+fn ssh_host_trust_key(connection_options: &SshConnectionOptions) -> String {
+    format!(
+        "ssh-trusted-host-{}",
+        connection_options.connection_string()
+    )
+}
+
+/// Asks the user whether they trust the host of an SSH project until they trust it, which
+/// is remembered. Declining only applies to this window, so the question is asked again
+/// the next time the host is opened. Language servers and tasks are only started on
+/// trusted hosts, as they run code from the project.
+async fn confirm_ssh_host_trust(
+    window: WindowHandle<Workspace>,
+    project: &Model<Project>,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let connection_options = project.read_with(cx, |project, _| {
+        project
+            .ssh_session()
+            .and_then(|session| session.connection_options().cloned())
+    })?;
+    // Containers and WSL distributions run on this machine, so they're always trusted.
+    let Some(connection_options) = connection_options else {
+        project.update(cx, |project, cx| project.trust_ssh_host(cx))?;
+        return Ok(());
+    };
+
+    let key = ssh_host_trust_key(&connection_options);
+    // Earlier versions also remembered hosts that weren't trusted, as "false".
+    let trusted = match KEY_VALUE_STORE.read_kvp(&key).log_err().flatten() {
+        Some(trusted) if trusted == "true" => true,
+        _ => {
+            let answer = window.update(cx, |_, cx| {
+                cx.prompt(
+                    PromptLevel::Warning,
+                    &format!("Do you trust {}?", connection_options.host),
+                    Some(
+                        "Trusting this host allows Zed to start language servers and run tasks \
+                        on it, which may execute code from the projects you open there.",
+                    ),
+                    &["Trust", "Don't Trust"],
+                )
+            })?;
+            let trusted = answer.await? == 0;
+            if trusted {
+                KEY_VALUE_STORE
+                    .write_kvp(key, trusted.to_string())
+                    .await
+                    .log_err();
+            }
+            trusted
+        }
+    };
+
+    if trusted {
+        project.update(cx, |project, cx| project.trust_ssh_host(cx))?;
+    }
     Ok(())
 }
 
+/// Trusts the host of the given SSH project from now on, starting its language servers.
+pub async fn trust_ssh_host(project: Model<Project>, cx: &mut AsyncAppContext) -> Result<()> {
+    let connection_options = project.read_with(cx, |project, _| {
+        project
+            .ssh_session()
+            .and_then(|session| session.connection_options().cloned())
+    })?;
+    if let Some(connection_options) = connection_options {
+        KEY_VALUE_STORE
+            .write_kvp(ssh_host_trust_key(&connection_options), true.to_string())
+            .await?;
+    }
+    project.update(cx, |project, cx| project.trust_ssh_host(cx))
+}
+
 pub async fn open_paths_with_positions(
     path_likes: &Vec<PathLikeWithPosition<PathBuf>>,
     app_state: Arc<AppState>,
@@ -498,7 +1182,8 @@ pub async fn handle_cli_connection(
                 if !urls.is_empty() {
                     cx.update(|cx| {
                         match OpenRequest::parse(urls, cx) {
-                            Ok(open_request) => {
+                            Ok(mut open_request) => {
+                                open_request.open_new_workspace = open_new_workspace;
                                 handle_open_request(open_request, app_state.clone(), cx);
                                 responses.send(CliResponse::Exit { status: 0 }).log_err();
                             }
@@ -0,0 +1,181 @@
+use std::{sync::Arc, time::Duration};
+
+use client::proto;
+use gpui::{
+    actions, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, SemanticVersion, Task,
+};
+use release_channel::AppVersion;
+use remote::{format_byte_count, SshSession};
+use ui::{prelude::*, Modal, ModalHeader, Section};
+use workspace::{ModalView, Workspace};
+
+actions!(remote, [ShowRemoteInfo]);
+
+/// How often the resource usage shown in the modal is refreshed while it is open.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A modal showing the version of the remote server of an SSH project, along with the
+/// resources in use by it and on the remote host.
+pub struct RemoteInfoModal {
+    host: SharedString,
+    info: Option<proto::GetRemoteServerInfoResponse>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _refresh: Task<()>,
+}
+
+impl RemoteInfoModal {
+    pub fn toggle(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let Some(session) = workspace.project().read(cx).ssh_session() else {
+            return;
+        };
+        let Some(host) = session
+            .connection_options()
+            .map(|options| options.connection_string())
+        else {
+            return;
+        };
+        workspace.toggle_modal(cx, |cx| Self::new(host, session, cx));
+    }
+
+    fn new(host: String, session: Arc<SshSession>, cx: &mut ViewContext<Self>) -> Self {
+        let refresh = cx.spawn(|this, mut cx| async move {
+            loop {
+                let result = session.request(proto::GetRemoteServerInfo {}).await;
+                let updated = this.update(&mut cx, |this, cx| {
+                    match result {
+                        Ok(info) => {
+                            this.info = Some(info);
+                            this.error = None;
+                        }
+                        Err(error) => this.error = Some(error.to_string().into()),
+                    }
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+            }
+        });
+
+        Self {
+            host: host.into(),
+            info: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            _refresh: refresh,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent)
+    }
+
+    fn render_row(
+        label: impl Into<SharedString>,
+        value: impl Into<SharedString>,
+        color: Color,
+    ) -> impl IntoElement {
+        h_flex()
+            .justify_between()
+            .gap_4()
+            .child(Label::new(label.into()).color(Color::Muted))
+            .child(Label::new(value.into()).color(color))
+    }
+
+    fn render_info(
+        info: &proto::GetRemoteServerInfoResponse,
+        local_version: SemanticVersion,
+    ) -> impl IntoElement {
+        // The remote server is only reinstalled when connecting, so a mismatch means that
+        // Zed was updated since the session was established.
+        let version_matches = info.version.parse::<SemanticVersion>().ok() == Some(local_version);
+        let (version, version_color) = if version_matches {
+            (info.version.clone(), Color::Default)
+        } else {
+            (
+                format!("{} (local: {local_version})", info.version),
+                Color::Warning,
+            )
+        };
+
+        v_flex()
+            .gap_1()
+            .child(Self::render_row("Version", version, version_color))
+            .child(Self::render_row(
+                "Platform",
+                format!("{} ({})", info.os, info.arch),
+                Color::Default,
+            ))
+            .child(Self::render_row(
+                "Server Memory",
+                format_byte_count(info.process_memory as f64),
+                Color::Default,
+            ))
+            .child(Self::render_row(
+                "Server CPU",
+                format!("{:.1}%", info.process_cpu_usage),
+                Color::Default,
+            ))
+            .child(Self::render_row(
+                "Host Memory",
+                format!(
+                    "{} / {}",
+                    format_byte_count(info.used_memory as f64),
+                    format_byte_count(info.total_memory as f64)
+                ),
+                Color::Default,
+            ))
+            .child(Self::render_row(
+                "Host CPU",
+                format!("{:.1}% of {} cores", info.cpu_usage, info.cpu_count),
+                Color::Default,
+            ))
+            .child(Self::render_row(
+                "Load Average",
+                format!("{:.2}", info.load_average),
+                Color::Default,
+            ))
+    }
+}
+
+impl Render for RemoteInfoModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let local_version = AppVersion::global(cx);
+        let content = if let Some(error) = self.error.clone() {
+            Label::new(error).color(Color::Error).into_any_element()
+        } else if let Some(info) = self.info.as_ref() {
+            Self::render_info(info, local_version).into_any_element()
+        } else {
+            Label::new("Waiting for the remote server…")
+                .color(Color::Muted)
+                .into_any_element()
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .elevation_3(cx)
+            .on_action(cx.listener(Self::cancel))
+            .w(rems(28.))
+            .child(
+                Modal::new("remote-info", None)
+                    .header(
+                        ModalHeader::new()
+                            .show_dismiss_button(true)
+                            .child(Headline::new(self.host.clone()).size(HeadlineSize::Small)),
+                    )
+                    .section(Section::new().child(content)),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for RemoteInfoModal {}
+
+impl FocusableView for RemoteInfoModal {
+    fn focus_handle(&self, _: &gpui::AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for RemoteInfoModal {}
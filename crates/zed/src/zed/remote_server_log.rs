@@ -0,0 +1,251 @@
+use std::sync::Arc;
+
+use editor::{actions::MoveToEnd, Editor, EditorEvent};
+use futures::StreamExt as _;
+use gpui::{
+    actions, AppContext, EventEmitter, FocusHandle, FocusableView, Render, SharedString,
+    Subscription, Task, View, ViewContext, VisualContext as _, WindowContext,
+};
+use parking_lot::Mutex;
+use project::search::SearchQuery;
+use remote::{ServerLog, SshSession};
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    searchable::{SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
+    Workspace,
+};
+
+actions!(remote, [OpenServerLog, OpenConnectionLog]);
+
+/// A read-only view of a log of an SSH project, such as the output of its remote
+/// server, which keeps following it as new lines are logged.
+pub struct RemoteServerLogView {
+    editor: View<Editor>,
+    focus_handle: FocusHandle,
+    title: SharedString,
+    _subscriptions: Vec<Subscription>,
+    _follow_log: Task<()>,
+}
+
+impl RemoteServerLogView {
+    /// Opens the server log of the workspace's remote project, or activates it if it's
+    /// already open.
+    pub fn open(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let Some(session) = workspace.project().read(cx).ssh_session() else {
+            return;
+        };
+        let title = match session.connection_options() {
+            Some(options) => format!("Server Log: {}", options.host).into(),
+            None => "Server Log".into(),
+        };
+        Self::open_log(workspace, session.server_log(), title, cx);
+    }
+
+    /// Opens the log of the connections made to the host of the workspace's remote
+    /// project, or activates it if it's already open.
+    pub fn open_connection_log(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let Some(session) = workspace.project().read(cx).ssh_session() else {
+            return;
+        };
+        let Some(options) = session.connection_options() else {
+            return;
+        };
+        let title = format!("Connection Log: {}", options.host).into();
+        let log = SshSession::connection_log(options, cx);
+        Self::open_log(workspace, log, title, cx);
+    }
+
+    fn open_log(
+        workspace: &mut Workspace,
+        log: Arc<Mutex<ServerLog>>,
+        title: SharedString,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|view| view.read(cx).title == title);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, cx);
+            return;
+        }
+
+        let view = cx.new_view(|cx| Self::new(log, title, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, cx);
+    }
+
+    fn new(
+        server_log: Arc<Mutex<ServerLog>>,
+        title: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let (contents, mut shown_line_count, mut line_count_changes) = {
+            let server_log = server_log.lock();
+            (
+                server_log.lines_since(0).collect::<Vec<_>>().join("\n"),
+                server_log.line_count(),
+                server_log.line_count_changes(),
+            )
+        };
+
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::multi_line(cx);
+            editor.set_text(contents, cx);
+            editor.move_to_end(&MoveToEnd, cx);
+            editor.set_read_only(true);
+            editor.set_show_inline_completions(false);
+            editor
+        });
+
+        let focus_handle = cx.focus_handle();
+        let subscriptions = vec![
+            cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            cx.subscribe(&editor, |_, _, event: &SearchEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            cx.on_focus(&focus_handle, |this, cx| cx.focus_view(&this.editor)),
+        ];
+
+        let follow_log = cx.spawn(|this, mut cx| async move {
+            while line_count_changes.next().await.is_some() {
+                let new_lines = {
+                    let server_log = server_log.lock();
+                    let new_lines = server_log
+                        .lines_since(shown_line_count)
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    shown_line_count = server_log.line_count();
+                    new_lines
+                };
+                if new_lines.is_empty() {
+                    continue;
+                }
+                if this
+                    .update(&mut cx, |this, cx| this.append(&new_lines, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            editor,
+            focus_handle,
+            title,
+            _subscriptions: subscriptions,
+            _follow_log: follow_log,
+        }
+    }
+
+    fn append(&mut self, lines: &str, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.set_read_only(false);
+            let end = editor.buffer().read(cx).len(cx);
+            let separator = if end > 0 { "\n" } else { "" };
+            editor.edit([(end..end, format!("{separator}{lines}"))], cx);
+            editor.set_read_only(true);
+        });
+    }
+}
+
+impl Render for RemoteServerLogView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.editor
+            .update(cx, |editor, cx| editor.render(cx).into_any_element())
+    }
+}
+
+impl FocusableView for RemoteServerLogView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<EditorEvent> for RemoteServerLogView {}
+impl EventEmitter<SearchEvent> for RemoteServerLogView {}
+
+impl Item for RemoteServerLogView {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &Self::Event, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn tab_content_text(&self, _: &WindowContext) -> Option<SharedString> {
+        Some(self.title.clone())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn as_searchable(&self, handle: &View<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(handle.clone()))
+    }
+}
+
+impl SearchableItem for RemoteServerLogView {
+    type Match = <Editor as SearchableItem>::Match;
+
+    fn clear_matches(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |e, cx| e.clear_matches(cx))
+    }
+
+    fn update_matches(&mut self, matches: &[Self::Match], cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |e, cx| e.update_matches(matches, cx))
+    }
+
+    fn query_suggestion(&mut self, cx: &mut ViewContext<Self>) -> String {
+        self.editor.update(cx, |e, cx| e.query_suggestion(cx))
+    }
+
+    fn activate_match(
+        &mut self,
+        index: usize,
+        matches: &[Self::Match],
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.editor
+            .update(cx, |e, cx| e.activate_match(index, matches, cx))
+    }
+
+    fn select_matches(&mut self, matches: &[Self::Match], cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |e, cx| e.select_matches(matches, cx))
+    }
+
+    fn find_matches(
+        &mut self,
+        query: Arc<SearchQuery>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Vec<Self::Match>> {
+        self.editor.update(cx, |e, cx| e.find_matches(query, cx))
+    }
+
+    fn replace(&mut self, _: &Self::Match, _: &SearchQuery, _: &mut ViewContext<Self>) {
+        // The log is read-only.
+    }
+
+    fn supported_options() -> SearchOptions {
+        SearchOptions {
+            case: true,
+            word: true,
+            regex: true,
+            replacement: false,
+            selection: false,
+        }
+    }
+
+    fn active_match_index(
+        &mut self,
+        matches: &[Self::Match],
+        cx: &mut ViewContext<Self>,
+    ) -> Option<usize> {
+        self.editor
+            .update(cx, |e, cx| e.active_match_index(matches, cx))
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::Result;
+use collections::HashMap;
+use gpui::{AppContext, Model, ViewContext};
+use project::Project;
+use remote::{SshConnectionOptions, SshTimeouts, SshTransport};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources, SettingsStore};
+use std::path::PathBuf;
+use util::ResultExt as _;
+use workspace::Workspace;
+
+#[derive(Deserialize, Debug)]
+pub struct RemoteSettings {
+    pub ssh: SshSettings,
+    pub hosts: HashMap<String, serde_json::Value>,
+    pub server_binary_path: Option<PathBuf>,
+    pub compress_server_upload: bool,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct SshSettings {
+    pub store_credentials: bool,
+    pub proxy: Option<String>,
+    pub connect_timeout: u64,
+    pub server_alive_interval: u64,
+    pub server_alive_count_max: u64,
+    pub additional_args: HashMap<String, Vec<String>>,
+    pub forward_agent: HashMap<String, bool>,
+    pub auto_reconnect: HashMap<String, bool>,
+    pub transport: HashMap<String, SshTransportSetting>,
+}
+
+impl SshSettings {
+    pub fn timeouts(&self) -> SshTimeouts {
+        SshTimeouts {
+            connect_timeout: self.connect_timeout,
+            server_alive_interval: self.server_alive_interval,
+            server_alive_count_max: self.server_alive_count_max,
+        }
+    }
+
+    /// The extra `ssh` arguments configured for a connection.
+    pub fn additional_args_for(&self, connection_options: &SshConnectionOptions) -> Vec<String> {
+        setting_for_host(&self.additional_args, connection_options)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether the local ssh agent should be forwarded to the host of a connection.
+    pub fn forward_agent_for(&self, connection_options: &SshConnectionOptions) -> bool {
+        setting_for_host(&self.forward_agent, connection_options)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Whether to reconnect to the host of a connection when the connection is lost.
+    pub fn auto_reconnect_for(&self, connection_options: &SshConnectionOptions) -> bool {
+        setting_for_host(&self.auto_reconnect, connection_options)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// How to carry the messages of a session to the host of a connection.
+    pub fn transport_for(&self, connection_options: &SshConnectionOptions) -> SshTransport {
+        match setting_for_host(&self.transport, connection_options) {
+            Some(SshTransportSetting::Resilient) => SshTransport::Resilient,
+            Some(SshTransportSetting::Direct) | None => SshTransport::Direct,
+        }
+    }
+}
+
+/// Looks up a per-host setting by `user@host`, and then by `host`.
+fn setting_for_host<'a, T>(
+    settings: &'a HashMap<String, T>,
+    connection_options: &SshConnectionOptions,
+) -> Option<&'a T> {
+    settings
+        .get(&format!(
+            "{}@{}",
+            connection_options.username, connection_options.host
+        ))
+        .or_else(|| settings.get(&connection_options.host))
+}
+
+/// Remote development configuration.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct RemoteSettingsContent {
+    /// Settings for connections made over SSH.
+    pub ssh: Option<SshSettingsContent>,
+    /// Settings overrides for projects opened on specific hosts, keyed by `host` or
+    /// `user@host`. These take precedence over the user settings, e.g.:
+    ///
+    /// "hosts": { "prod.example.com": { "format_on_save": "off" } }
+    ///
+    /// Default: {}
+    pub hosts: Option<HashMap<String, serde_json::Value>>,
+    /// The remote server binary to install on remote hosts, gzipped or not, such as a
+    /// `zed-remote-server-linux-x86_64.gz` downloaded from the releases page, instead of
+    /// downloading it when connecting. This is needed when this machine can't reach the
+    /// internet.
+    ///
+    /// Default: null
+    pub server_binary_path: Option<PathBuf>,
+    /// Whether to gzip the remote server when uploading it to a host. This makes the
+    /// upload much faster on slow links, but requires `gunzip` on the host.
+    ///
+    /// Default: true
+    pub compress_server_upload: Option<bool>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct SshSettingsContent {
+    /// Whether to save passwords and key passphrases that were accepted by the
+    /// remote host in the system keychain, so that reconnecting does not prompt again.
+    ///
+    /// Default: true
+    pub store_credentials: Option<bool>,
+    /// A proxy to tunnel SSH connections through, such as `socks5://localhost:1080`
+    /// or `http://proxy.example.com:3128`. When unset, any `ProxyCommand` from your
+    /// ssh_config is used instead.
+    ///
+    /// Default: null
+    pub proxy: Option<String>,
+    /// How many seconds to wait for the connection to the host to be established
+    /// before giving up. Set to 0 to use ssh's default.
+    ///
+    /// Default: 15
+    pub connect_timeout: Option<u64>,
+    /// How often, in seconds, to check that the host is still responsive. Set to 0
+    /// to disable these checks.
+    ///
+    /// Default: 30
+    pub server_alive_interval: Option<u64>,
+    /// How many of these checks may go unanswered before the connection is
+    /// considered lost.
+    ///
+    /// Default: 3
+    pub server_alive_count_max: Option<u64>,
+    /// Extra arguments to pass to `ssh` when connecting to specific hosts, keyed by
+    /// `host` or `user@host`, e.g.:
+    ///
+    /// "additional_args": { "prod.example.com": ["-o", "BindAddress=10.0.0.2"] }
+    ///
+    /// Default: {}
+    pub additional_args: Option<HashMap<String, Vec<String>>>,
+    /// Whether to forward the local ssh agent to specific hosts, keyed by `host` or
+    /// `user@host`, so that e.g. git on the host can use your local keys. Only enable
+    /// this for hosts you trust, as anyone with root access on them can use your keys
+    /// while you're connected.
+    ///
+    /// "forward_agent": { "dev.example.com": true }
+    ///
+    /// Default: {}
+    pub forward_agent: Option<HashMap<String, bool>>,
+    /// Whether to reconnect automatically when the connection to specific hosts is
+    /// lost, keyed by `host` or `user@host`. Zed retries for a few minutes, waiting
+    /// longer between each attempt. This helps on unreliable networks, such as when
+    /// roaming between access points, at the cost of restarting the remote server and
+    /// its language servers.
+    ///
+    /// "auto_reconnect": { "dev.example.com": true }
+    ///
+    /// Default: {}
+    pub auto_reconnect: Option<HashMap<String, bool>>,
+    /// How to carry the messages of sessions to specific hosts, keyed by `host` or
+    /// `user@host`. With `"resilient"`, the remote server keeps running when the
+    /// connection is lost, and Zed resumes the session over a new connection without
+    /// losing messages, so language servers and terminals survive network changes.
+    /// Reconnecting in the background can't prompt for a password, so this works best
+    /// with key based authentication.
+    ///
+    /// "transport": { "dev.example.com": "resilient" }
+    ///
+    /// Default: {}
+    pub transport: Option<HashMap<String, SshTransportSetting>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SshTransportSetting {
+    /// Talk to the remote server over a single ssh connection, ending the session
+    /// when it is lost.
+    Direct,
+    /// Resume the session over a new ssh connection when the connection is lost.
+    Resilient,
+}
+
+impl Settings for RemoteSettings {
+    const KEY: Option<&'static str> = Some("remote");
+
+    type FileContent = RemoteSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+/// Applies the `remote.hosts` entry matching the host of an SSH project to each of
+/// its worktrees, keeping them up to date as worktrees are added or settings change.
+pub fn observe_host_settings(workspace: &Workspace, cx: &mut ViewContext<Workspace>) {
+    let project = workspace.project().clone();
+    let Some(connection_options) = project
+        .read(cx)
+        .ssh_session()
+        .and_then(|session| session.connection_options().cloned())
+    else {
+        return;
+    };
+
+    let mut host_settings = host_settings_content(&connection_options, cx);
+    set_host_settings(&project, host_settings.as_deref(), cx);
+
+    cx.subscribe(&project, {
+        let connection_options = connection_options.clone();
+        move |_, project, event, cx| {
+            if let project::Event::WorktreeAdded = event {
+                let content = host_settings_content(&connection_options, cx);
+                set_host_settings(&project, content.as_deref(), cx);
+            }
+        }
+    })
+    .detach();
+
+    cx.observe_global::<SettingsStore>(move |workspace, cx| {
+        // Applying the host settings updates the store again, so only do it when
+        // they've actually changed.
+        let content = host_settings_content(&connection_options, cx);
+        if content != host_settings {
+            host_settings = content;
+            set_host_settings(workspace.project(), host_settings.as_deref(), cx);
+        }
+    })
+    .detach();
+}
+
+fn host_settings_content(
+    connection_options: &SshConnectionOptions,
+    cx: &AppContext,
+) -> Option<String> {
+    let hosts = &RemoteSettings::get_global(cx).hosts;
+    hosts
+        .get(&format!(
+            "{}@{}",
+            connection_options.username, connection_options.host
+        ))
+        .or_else(|| hosts.get(&connection_options.host))
+        .map(|settings| settings.to_string())
+}
+
+/// Host settings are the least specific project settings, below the settings files of
+/// each worktree.
+fn set_host_settings(project: &Model<Project>, content: Option<&str>, cx: &mut AppContext) {
+    let worktree_ids = project
+        .read(cx)
+        .worktrees(cx)
+        .map(|worktree| worktree.read(cx).id().to_proto() as usize)
+        .collect::<Vec<_>>();
+    cx.update_global::<SettingsStore, _>(|store, cx| {
+        for worktree_id in worktree_ids {
+            store.set_host_settings(worktree_id, content, cx).log_err();
+        }
+    });
+}
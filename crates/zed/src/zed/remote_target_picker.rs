@@ -0,0 +1,288 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Task,
+    View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use remote::{
+    DockerConnection, DockerConnectionOptions, DockerContainer, WslConnection, WslConnectionOptions,
+};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{AppState, ModalView, Workspace};
+
+use super::open_listener::{open_remote_environment_paths, RemoteEnvironment};
+
+actions!(remote, [ConnectToContainer]);
+
+/// An environment on this machine that a remote project can be opened in.
+enum RemoteTarget {
+    Container(DockerContainer),
+    WslDistro(String),
+}
+
+impl RemoteTarget {
+    fn name(&self) -> &str {
+        match self {
+            RemoteTarget::Container(container) => &container.name,
+            RemoteTarget::WslDistro(distro) => distro,
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            RemoteTarget::Container(container) => Some(&container.image),
+            RemoteTarget::WslDistro(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RemoteTargetKind {
+    Container,
+    WslDistro,
+}
+
+impl RemoteTargetKind {
+    async fn list(self) -> Result<Vec<RemoteTarget>> {
+        Ok(match self {
+            RemoteTargetKind::Container => DockerConnection::list_containers()
+                .await?
+                .into_iter()
+                .map(RemoteTarget::Container)
+                .collect(),
+            RemoteTargetKind::WslDistro => WslConnection::list_distros()
+                .await?
+                .into_iter()
+                .map(RemoteTarget::WslDistro)
+                .collect(),
+        })
+    }
+}
+
+/// A modal listing the running Docker containers or the installed WSL distributions,
+/// opening the selected one as a remote project.
+pub struct RemoteTargetPicker {
+    picker: View<Picker<RemoteTargetPickerDelegate>>,
+}
+
+impl RemoteTargetPicker {
+    pub fn toggle_containers(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        Self::toggle(RemoteTargetKind::Container, workspace, cx);
+    }
+
+    pub fn toggle_wsl_distros(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        Self::toggle(RemoteTargetKind::WslDistro, workspace, cx);
+    }
+
+    fn toggle(kind: RemoteTargetKind, workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let app_state = workspace.app_state().clone();
+        workspace.toggle_modal(cx, |cx| Self::new(kind, app_state, cx));
+    }
+
+    fn new(kind: RemoteTargetKind, app_state: Arc<AppState>, cx: &mut ViewContext<Self>) -> Self {
+        let delegate = RemoteTargetPickerDelegate {
+            remote_target_picker: cx.view().downgrade(),
+            app_state,
+            kind,
+            targets: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+            error: None,
+        };
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+
+        cx.spawn(|_, mut cx| {
+            let picker = picker.downgrade();
+            async move {
+                let targets = kind.list().await;
+                picker.update(&mut cx, |picker, cx| {
+                    match targets {
+                        Ok(targets) => picker.delegate.targets = targets,
+                        Err(error) => picker.delegate.error = Some(format!("{error:#}").into()),
+                    }
+                    picker.refresh(cx);
+                })
+            }
+        })
+        .detach_and_log_err(cx);
+
+        Self { picker }
+    }
+}
+
+impl Render for RemoteTargetPicker {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for RemoteTargetPicker {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for RemoteTargetPicker {}
+impl ModalView for RemoteTargetPicker {}
+
+pub struct RemoteTargetPickerDelegate {
+    remote_target_picker: WeakView<RemoteTargetPicker>,
+    app_state: Arc<AppState>,
+    kind: RemoteTargetKind,
+    targets: Vec<RemoteTarget>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    error: Option<SharedString>,
+}
+
+impl PickerDelegate for RemoteTargetPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _: &mut WindowContext) -> Arc<str> {
+        match self.kind {
+            RemoteTargetKind::Container => "Connect to a container...".into(),
+            RemoteTargetKind::WslDistro => "Open a WSL distribution...".into(),
+        }
+    }
+
+    fn no_matches_text(&self, _: &mut WindowContext) -> SharedString {
+        if let Some(error) = self.error.clone() {
+            return error;
+        }
+        match self.kind {
+            RemoteTargetKind::Container => "No running containers".into(),
+            RemoteTargetKind::WslDistro => "No WSL distributions installed".into(),
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(id, target)| StringMatchCandidate::new(id, target.name().to_string()))
+            .collect::<Vec<_>>();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(target) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.targets.get(mat.candidate_id))
+        else {
+            return;
+        };
+
+        let (environment, paths) = match target {
+            RemoteTarget::Container(container) => {
+                let options = DockerConnectionOptions {
+                    container: container.name.clone(),
+                    user: None,
+                };
+                let path = PathBuf::from(container.working_dir.as_deref().unwrap_or("/"));
+                (RemoteEnvironment::Container(options), vec![path])
+            }
+            RemoteTarget::WslDistro(distro) => {
+                let options = WslConnectionOptions {
+                    distro: distro.clone(),
+                    user: None,
+                };
+                (RemoteEnvironment::Wsl(options), Vec::new())
+            }
+        };
+        let app_state = self.app_state.clone();
+        cx.spawn(|_, mut cx| async move {
+            open_remote_environment_paths(environment, paths, app_state, &mut cx).await
+        })
+        .detach_and_log_err(cx);
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.remote_target_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let target = self.targets.get(mat.candidate_id)?;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .when_some(target.detail(), |this, detail| {
+                            this.child(
+                                Label::new(detail.to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+}
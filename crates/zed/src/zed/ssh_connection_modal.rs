@@ -1,58 +1,207 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use editor::Editor;
 use futures::channel::oneshot;
 use gpui::{
-    px, DismissEvent, EventEmitter, FocusableView, ParentElement as _, Render, SharedString, View,
+    actions, div, px, relative, DismissEvent, EventEmitter, FocusHandle, FocusableView, KeyContext,
+    ParentElement as _, Render, SharedString, Task, View,
 };
+use remote::{SshAuthPrompt, SshIdentity, SshPromptKind};
 use ui::{
-    v_flex, FluentBuilder as _, InteractiveElement, Label, LabelCommon, Styled, StyledExt as _,
-    ViewContext, VisualContext,
+    h_flex, v_flex, ActiveTheme as _, Button, ButtonCommon as _, ButtonStyle, Clickable as _,
+    FluentBuilder as _, InteractiveElement, KeyBinding, Label, LabelCommon, ListItem,
+    ListItemSpacing, Selectable as _, Styled, StyledExt as _, ViewContext, VisualContext,
 };
 use workspace::ModalView;
 
+actions!(remote, [AnswerYes, AnswerNo]);
+
+/// How long to wait for the security key to be touched before suggesting that it
+/// may not be plugged in.
+const SECURITY_KEY_TIMEOUT: Duration = Duration::from_secs(20);
+
 pub struct SshConnectionModal {
     host: SharedString,
     status_message: Option<SharedString>,
-    prompt: Option<(SharedString, oneshot::Sender<Result<String>>)>,
+    progress: Option<f32>,
+    prompt: Option<(SshAuthPrompt, oneshot::Sender<Result<String>>)>,
+    identity_choice: Option<IdentityChoice>,
+    security_key_prompt: Option<SecurityKeyPrompt>,
+    forward_agent: bool,
     editor: View<Editor>,
+    focus_handle: FocusHandle,
+    cancel_tx: Option<oneshot::Sender<()>>,
+}
+
+/// A pending choice of the identity to authenticate with, where `None` lets `ssh`
+/// decide on its own.
+struct IdentityChoice {
+    identities: Vec<Option<SshIdentity>>,
+    selected_index: usize,
+    tx: oneshot::Sender<Option<SshIdentity>>,
+}
+
+/// `ssh` waiting for the user to touch their security key, e.g. for `ed25519-sk` keys.
+struct SecurityKeyPrompt {
+    message: SharedString,
+    timed_out: bool,
+    _timeout: Task<()>,
 }
 
 impl SshConnectionModal {
-    pub fn new(host: String, cx: &mut ViewContext<Self>) -> Self {
+    /// Creates the modal for a connection attempt to `host`. `cancel_tx` is notified
+    /// when the user cancels, so that the in-flight connection can be aborted.
+    pub fn new(host: String, cancel_tx: oneshot::Sender<()>, cx: &mut ViewContext<Self>) -> Self {
         Self {
             host: host.into(),
             prompt: None,
+            identity_choice: None,
+            security_key_prompt: None,
+            forward_agent: false,
             status_message: None,
+            progress: None,
             editor: cx.new_view(|cx| Editor::single_line(cx)),
+            focus_handle: cx.focus_handle(),
+            cancel_tx: Some(cancel_tx),
         }
     }
 
     pub fn set_prompt(
         &mut self,
-        prompt: String,
+        prompt: SshAuthPrompt,
         tx: oneshot::Sender<Result<String>>,
         cx: &mut ViewContext<Self>,
     ) {
         self.editor.update(cx, |editor, cx| {
-            if prompt.contains("yes/no") {
-                editor.set_redact_all(false, cx);
-            } else {
-                editor.set_redact_all(true, cx);
-            }
+            editor.clear(cx);
+            editor.set_redact_all(prompt.kind == SshPromptKind::Secret, cx);
         });
-        self.prompt = Some((prompt.into(), tx));
+        let kind = prompt.kind;
+        self.prompt = Some((prompt, tx));
         self.status_message.take();
-        cx.focus_view(&self.editor);
+        match kind {
+            SshPromptKind::Secret | SshPromptKind::Plain => cx.focus_view(&self.editor),
+            SshPromptKind::YesNo => cx.focus(&self.focus_handle),
+        }
+        cx.notify();
+    }
+
+    fn is_yes_no_prompt(&self) -> bool {
+        self.prompt
+            .as_ref()
+            .map_or(false, |(prompt, _)| prompt.kind == SshPromptKind::YesNo)
+    }
+
+    fn answer_yes(&mut self, _: &AnswerYes, cx: &mut ViewContext<Self>) {
+        self.answer(SshAuthPrompt::YES, cx);
+    }
+
+    fn answer_no(&mut self, _: &AnswerNo, cx: &mut ViewContext<Self>) {
+        self.answer(SshAuthPrompt::NO, cx);
+    }
+
+    fn answer(&mut self, answer: &str, cx: &mut ViewContext<Self>) {
+        if self.is_yes_no_prompt() {
+            if let Some((_, tx)) = self.prompt.take() {
+                tx.send(Ok(answer.to_string())).ok();
+            }
+            cx.notify();
+        }
+    }
+
+    /// Warns that the local ssh agent is forwarded to the host, since it's usable by
+    /// anyone with root access there.
+    pub fn set_forward_agent(&mut self, forward_agent: bool) {
+        self.forward_agent = forward_agent;
+    }
+
+    /// Shows that `ssh` is waiting for the user to touch their security key, or that
+    /// it's no longer waiting when `message` is `None`.
+    pub fn set_security_key_prompt(&mut self, message: Option<String>, cx: &mut ViewContext<Self>) {
+        self.security_key_prompt = message.map(|message| SecurityKeyPrompt {
+            message: message.into(),
+            timed_out: false,
+            _timeout: cx.spawn(|this, mut cx| async move {
+                cx.background_executor().timer(SECURITY_KEY_TIMEOUT).await;
+                this.update(&mut cx, |this, cx| {
+                    if let Some(prompt) = this.security_key_prompt.as_mut() {
+                        prompt.timed_out = true;
+                        cx.notify();
+                    }
+                })
+                .ok();
+            }),
+        });
         cx.notify();
     }
 
+    /// Asks the user which of `identities` to authenticate with, sending the choice
+    /// through `tx`.
+    pub fn choose_identity(
+        &mut self,
+        identities: Vec<SshIdentity>,
+        tx: oneshot::Sender<Option<SshIdentity>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.identity_choice = Some(IdentityChoice {
+            identities: std::iter::once(None)
+                .chain(identities.into_iter().map(Some))
+                .collect(),
+            selected_index: 0,
+            tx,
+        });
+        self.status_message = Some("Choose how to authenticate".into());
+        cx.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        if let Some(choice) = self.identity_choice.as_mut() {
+            choice.selected_index = (choice.selected_index + 1) % choice.identities.len();
+            cx.notify();
+        }
+    }
+
+    fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
+        if let Some(choice) = self.identity_choice.as_mut() {
+            choice.selected_index = choice
+                .selected_index
+                .checked_sub(1)
+                .unwrap_or(choice.identities.len() - 1);
+            cx.notify();
+        }
+    }
+
+    fn confirm_identity(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        if let Some(mut choice) = self.identity_choice.take() {
+            if index < choice.identities.len() {
+                choice.tx.send(choice.identities.swap_remove(index)).ok();
+            }
+            self.status_message = None;
+            cx.notify();
+        }
+    }
+
     pub fn set_status(&mut self, status: Option<String>, cx: &mut ViewContext<Self>) {
         self.status_message = status.map(|s| s.into());
+        self.progress = None;
+        cx.notify();
+    }
+
+    /// Sets the completion ratio (between 0 and 1) of the current download or upload.
+    pub fn set_progress(&mut self, progress: Option<f32>, cx: &mut ViewContext<Self>) {
+        self.progress = progress.map(|progress| progress.clamp(0., 1.));
         cx.notify();
     }
 
     fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
-        if let Some((_, tx)) = self.prompt.take() {
+        if let Some(choice) = self.identity_choice.as_ref() {
+            self.confirm_identity(choice.selected_index, cx);
+        } else if self.is_yes_no_prompt() {
+            // Yes/no questions are only answered explicitly, so that e.g. an unknown host
+            // key isn't trusted by pressing enter.
+        } else if let Some((_, tx)) = self.prompt.take() {
             self.editor.update(cx, |editor, cx| {
                 tx.send(Ok(editor.text(cx))).ok();
                 editor.clear(cx);
@@ -61,34 +210,169 @@ impl SshConnectionModal {
     }
 
     fn dismiss(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
-        cx.remove_window();
+        self.cancel(cx);
+    }
+
+    fn cancel(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(cancel_tx) = self.cancel_tx.take() {
+            cancel_tx.send(()).ok();
+        }
+        cx.emit(DismissEvent);
     }
 }
 
 impl Render for SshConnectionModal {
     fn render(&mut self, cx: &mut ui::ViewContext<Self>) -> impl ui::IntoElement {
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("PasswordPrompt");
+        let is_yes_no_prompt = self.is_yes_no_prompt();
+        if is_yes_no_prompt {
+            key_context.add("yes_no");
+        }
+        let yes_binding = KeyBinding::for_action_in(&AnswerYes, &self.focus_handle, cx);
+        let no_binding = KeyBinding::for_action_in(&AnswerNo, &self.focus_handle, cx);
+
         v_flex()
-            .key_context("PasswordPrompt")
+            .key_context(key_context)
+            .track_focus(&self.focus_handle)
             .elevation_3(cx)
             .p_4()
             .gap_2()
             .on_action(cx.listener(Self::dismiss))
             .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::answer_yes))
+            .on_action(cx.listener(Self::answer_no))
             .w(px(400.))
             .child(Label::new(format!("SSH: {}", self.host)).size(ui::LabelSize::Large))
+            .when(self.forward_agent, |el| {
+                el.child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            ui::Icon::new(ui::IconName::Warning)
+                                .size(ui::IconSize::Small)
+                                .color(ui::Color::Warning),
+                        )
+                        .child(
+                            Label::new(
+                                "Your ssh agent is forwarded. Anyone with root access \
+                                on this host can use your keys while you're connected.",
+                            )
+                            .size(ui::LabelSize::Small)
+                            .color(ui::Color::Muted),
+                        ),
+                )
+            })
             .when_some(self.status_message.as_ref(), |el, status| {
                 el.child(Label::new(status.clone()))
             })
-            .when_some(self.prompt.as_ref(), |el, prompt| {
-                el.child(Label::new(prompt.0.clone()))
-                    .child(self.editor.clone())
+            .when_some(self.progress, |el, progress| {
+                el.child(
+                    div()
+                        .h_1()
+                        .w_full()
+                        .rounded_sm()
+                        .bg(cx.theme().colors().element_background)
+                        .child(
+                            div()
+                                .h_full()
+                                .w(relative(progress))
+                                .rounded_sm()
+                                .bg(cx.theme().status().info),
+                        ),
+                )
+            })
+            .when_some(self.security_key_prompt.as_ref(), |el, prompt| {
+                el.child(
+                    v_flex()
+                        .gap_1()
+                        .child(Label::new("Touch your security key").color(ui::Color::Accent))
+                        .child(
+                            Label::new(prompt.message.clone())
+                                .size(ui::LabelSize::Small)
+                                .color(ui::Color::Muted),
+                        )
+                        .when(prompt.timed_out, |el| {
+                            el.child(
+                                Label::new(
+                                    "Still waiting for your security key. \
+                                    Make sure it is plugged in.",
+                                )
+                                .size(ui::LabelSize::Small)
+                                .color(ui::Color::Warning),
+                            )
+                        }),
+                )
             })
+            .when_some(self.identity_choice.as_ref(), |el, choice| {
+                el.child(v_flex().children(choice.identities.iter().enumerate().map(
+                    |(ix, identity)| {
+                        let label = identity
+                            .as_ref()
+                            .map_or("Automatic".to_string(), SshIdentity::label);
+                        ListItem::new(ix)
+                            .inset(true)
+                            .spacing(ListItemSpacing::Sparse)
+                            .selected(ix == choice.selected_index)
+                            .on_click(cx.listener(move |this, _, cx| this.confirm_identity(ix, cx)))
+                            .child(Label::new(label))
+                    },
+                )))
+            })
+            .when_some(self.prompt.as_ref(), |el, (prompt, _)| {
+                el.child(
+                    h_flex()
+                        .justify_between()
+                        .child(Label::new(prompt.label.clone()))
+                        .when(prompt.step > 1, |el| {
+                            el.child(
+                                Label::new(format!("Step {}", prompt.step))
+                                    .size(ui::LabelSize::Small)
+                                    .color(ui::Color::Muted),
+                            )
+                        }),
+                )
+                .when(prompt.kind != SshPromptKind::YesNo, |el| {
+                    el.child(self.editor.clone())
+                })
+            })
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        Button::new("cancel", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(cx))),
+                    )
+                    .when(is_yes_no_prompt, |el| {
+                        el.child(
+                            Button::new("answer-no", "No")
+                                .style(ButtonStyle::Filled)
+                                .key_binding(no_binding)
+                                .on_click(cx.listener(|this, _, cx| this.answer_no(&AnswerNo, cx))),
+                        )
+                        .child(
+                            Button::new("answer-yes", "Yes")
+                                .style(ButtonStyle::Filled)
+                                .key_binding(yes_binding)
+                                .on_click(
+                                    cx.listener(|this, _, cx| this.answer_yes(&AnswerYes, cx)),
+                                ),
+                        )
+                    }),
+            )
     }
 }
 
 impl FocusableView for SshConnectionModal {
     fn focus_handle(&self, cx: &gpui::AppContext) -> gpui::FocusHandle {
-        self.editor.focus_handle(cx)
+        if self.prompt.is_some() && !self.is_yes_no_prompt() {
+            self.editor.focus_handle(cx)
+        } else {
+            self.focus_handle.clone()
+        }
     }
 }
 
@@ -0,0 +1,175 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::StreamExt as _;
+use gpui::{actions, Action, AnchorCorner, Model, Render, Subscription, Task, View, ViewContext};
+use project::Project;
+use release_channel::AppVersion;
+use remote::{SshConnectionState, SshSession};
+use ui::{prelude::*, ContextMenu, PopoverMenu, Tooltip};
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+use super::{
+    remote_info_modal::ShowRemoteInfo,
+    remote_server_log::{OpenConnectionLog, OpenServerLog},
+};
+
+actions!(
+    remote,
+    [Disconnect, Reconnect, TrustHost, UpgradeRemoteServer]
+);
+
+/// Latencies below which the host is considered responsive or merely slow. Above
+/// them, edits and completions will noticeably lag.
+const GOOD_LATENCY: Duration = Duration::from_millis(150);
+const SLOW_LATENCY: Duration = Duration::from_millis(500);
+
+/// A status bar item shown in workspaces that were opened over SSH, reflecting the
+/// state of the connection to the remote host.
+pub struct SshStatusIndicator {
+    session: Option<Arc<SshSession>>,
+    project: Model<Project>,
+    _observe_session: Vec<Task<()>>,
+    _observe_project: Subscription,
+}
+
+impl SshStatusIndicator {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let session = project.read(cx).ssh_session();
+        let mut observe_session = Vec::new();
+        if let Some(session) = session.as_ref() {
+            let mut state_changes = session.connection_state_changes();
+            observe_session.push(cx.spawn(|this, mut cx| async move {
+                while state_changes.next().await.is_some() {
+                    if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            }));
+            let mut latency_changes = session.latency_changes();
+            observe_session.push(cx.spawn(|this, mut cx| async move {
+                while latency_changes.next().await.is_some() {
+                    if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+
+        Self {
+            session,
+            _observe_project: cx.observe(&project, |_, _, cx| cx.notify()),
+            project,
+            _observe_session: observe_session,
+        }
+    }
+
+    fn build_menu(
+        state: SshConnectionState,
+        server_outdated: bool,
+        host_trusted: bool,
+        cx: &mut WindowContext,
+    ) -> View<ContextMenu> {
+        ContextMenu::build(cx, |menu, _| {
+            let menu = match state {
+                SshConnectionState::Connected => menu
+                    .when(!host_trusted, |menu| {
+                        menu.action("Trust Host", TrustHost.boxed_clone())
+                    })
+                    .when(server_outdated, |menu| {
+                        menu.action("Upgrade Remote Server", UpgradeRemoteServer.boxed_clone())
+                    })
+                    .action("Disconnect", Disconnect.boxed_clone()),
+                SshConnectionState::Disconnected => {
+                    menu.action("Reconnect", Reconnect.boxed_clone())
+                }
+            };
+            menu.separator()
+                .action("Show Remote Info", ShowRemoteInfo.boxed_clone())
+                .action("Show Server Log", OpenServerLog.boxed_clone())
+                .action("Show Connection Log", OpenConnectionLog.boxed_clone())
+        })
+    }
+}
+
+impl Render for SshStatusIndicator {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(session) = self.session.as_ref() else {
+            return div();
+        };
+        let Some(connection_options) = session.connection_options() else {
+            return div();
+        };
+
+        let state = session.connection_state();
+        let latency = session
+            .latency()
+            .filter(|_| state == SshConnectionState::Connected);
+        // The remote server of a session that was established before Zed was updated
+        // keeps running the previous version until it's upgraded.
+        let local_version = AppVersion::global(cx);
+        let server_version = session
+            .server_version()
+            .filter(|version| *version != local_version);
+        let server_outdated = server_version.is_some();
+        let host_trusted = self.project.read(cx).is_ssh_host_trusted();
+        let (icon, color, tooltip) = match state {
+            SshConnectionState::Connected => {
+                let color = match latency {
+                    Some(latency) if latency >= SLOW_LATENCY => Color::Error,
+                    Some(latency) if latency >= GOOD_LATENCY => Color::Warning,
+                    _ if server_outdated => Color::Warning,
+                    _ => Color::Muted,
+                };
+                (IconName::Server, color, "Connected")
+            }
+            SshConnectionState::Disconnected => {
+                (IconName::Disconnected, Color::Error, "Disconnected")
+            }
+        };
+        let host = match latency {
+            Some(latency) => format!("{} · {}ms", connection_options.host, latency.as_millis()),
+            None => connection_options.host.clone(),
+        };
+        let title = format!("{tooltip} to {}", connection_options.connection_string());
+        let meta = match (state, latency) {
+            (SshConnectionState::Disconnected, _) => None,
+            (_, Some(latency)) => Some(format!("Round-trip time: {}ms", latency.as_millis())),
+            (_, None) => Some("Waiting for the remote server to respond".to_string()),
+        };
+        let meta = match (meta, server_version) {
+            (Some(meta), Some(server_version)) => Some(format!(
+                "{meta}\nRemote server {server_version} differs from Zed {local_version}"
+            )),
+            (meta, _) => meta,
+        };
+        let meta = match meta {
+            Some(meta) if !host_trusted => Some(format!(
+                "{meta}\nLanguage servers and tasks are disabled until the host is trusted"
+            )),
+            meta => meta,
+        };
+
+        div().child(
+            PopoverMenu::new("ssh-status-menu")
+                .menu(move |cx| Some(Self::build_menu(state, server_outdated, host_trusted, cx)))
+                .anchor(AnchorCorner::BottomLeft)
+                .trigger(
+                    Button::new("ssh-status", host)
+                        .label_size(LabelSize::Small)
+                        .icon(icon)
+                        .icon_size(IconSize::Small)
+                        .icon_color(color)
+                        .icon_position(IconPosition::Start)
+                        .tooltip(move |cx| match &meta {
+                            Some(meta) => Tooltip::with_meta(title.clone(), None, meta.clone(), cx),
+                            None => Tooltip::text(title.clone(), cx),
+                        }),
+                ),
+        )
+    }
+}
+
+impl StatusItemView for SshStatusIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}